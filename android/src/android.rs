@@ -11,7 +11,10 @@ pub mod android {
 
     use server::settings::{ServerSettings, Settings};
     use server::start_server;
-    use service::sync_settings::SyncSettings;
+    use service::sync_settings::{
+        SyncSettings, DEFAULT_MAX_BACKOFF_SEC, DEFAULT_MAX_JITTER_SEC,
+        DEFAULT_PARTIAL_FAILURE_RETRY_SEC,
+    };
     use tokio::sync::oneshot;
 
     use self::jni::objects::{JClass, JString};
@@ -105,6 +108,9 @@ pub mod android {
                         central_server_site_id: 1,
                         site_id: 2,
                         site_hardware_id: "".to_string(),
+                        max_backoff_sec: DEFAULT_MAX_BACKOFF_SEC,
+                        partial_failure_retry_sec: DEFAULT_PARTIAL_FAILURE_RETRY_SEC,
+                        max_jitter_sec: DEFAULT_MAX_JITTER_SEC,
                     }),
                 };
                 let _ = start_server(settings, off_switch_receiver).await;
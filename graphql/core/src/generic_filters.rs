@@ -1,6 +1,6 @@
 use async_graphql::{InputObject, InputType};
-use chrono::{DateTime, Utc};
-use repository::{DatetimeFilter, EqualFilter, SimpleStringFilter};
+use chrono::{DateTime, NaiveDate, Utc};
+use repository::{DateFilter, DatetimeFilter, EqualFilter, SimpleStringFilter};
 
 // simple string filter
 #[derive(InputObject, Clone)]
@@ -88,3 +88,22 @@ impl From<DatetimeFilterInput> for DatetimeFilter {
         }
     }
 }
+
+// Date filter
+
+#[derive(InputObject, Clone)]
+pub struct DateFilterInput {
+    pub equal_to: Option<NaiveDate>,
+    pub before_or_equal_to: Option<NaiveDate>,
+    pub after_or_equal_to: Option<NaiveDate>,
+}
+
+impl From<DateFilterInput> for DateFilter {
+    fn from(f: DateFilterInput) -> Self {
+        DateFilter {
+            equal_to: f.equal_to,
+            before_or_equal_to: f.before_or_equal_to,
+            after_or_equal_to: f.after_or_equal_to,
+        }
+    }
+}
@@ -1,7 +1,25 @@
 use async_graphql::*;
+use service::NullableUpdate;
 
 #[derive(InputObject)]
 pub struct TaxUpdate {
     /// Set or unset the tax value (in percentage)
     pub percentage: Option<f64>,
 }
+
+/// Update of an optional field, the field is kept as is if the update isn't set
+#[derive(InputObject)]
+#[graphql(concrete(name = "NullableStringUpdate", params(String)))]
+#[graphql(concrete(name = "NullableIntUpdate", params(i32)))]
+#[graphql(concrete(name = "NullableUnsignedIntUpdate", params(u32)))]
+#[graphql(concrete(name = "NullableFloatUpdate", params(f64)))]
+pub struct NullableUpdateInput<T: InputType> {
+    /// Set or unset the value
+    pub value: Option<T>,
+}
+
+impl<T: InputType> NullableUpdateInput<T> {
+    pub fn to_domain(self) -> NullableUpdate<T> {
+        NullableUpdate { value: self.value }
+    }
+}
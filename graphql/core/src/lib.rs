@@ -17,6 +17,7 @@ use service::auth_data::AuthData;
 use service::service_provider::ServiceProvider;
 
 use loader::LoaderRegistry;
+use service::sync_actor::SyncSenderActor;
 use service::sync_settings::SyncSettings;
 use tokio::sync::mpsc::Sender;
 
@@ -37,6 +38,8 @@ pub trait ContextExt {
     // Sync settings might not be available during initial setup phase
     fn get_sync_settings(&self) -> Option<&SyncSettings>;
     fn restart_switch(&self) -> Sender<bool>;
+    // Sync sender is only available once the server is fully configured
+    fn sync_sender(&self) -> Option<&SyncSenderActor>;
 }
 
 impl<'a> ContextExt for Context<'a> {
@@ -74,6 +77,11 @@ impl<'a> ContextExt for Context<'a> {
     fn restart_switch(&self) -> Sender<bool> {
         self.data_unchecked::<Data<Sender<bool>>>().as_ref().clone()
     }
+
+    fn sync_sender(&self) -> Option<&SyncSenderActor> {
+        self.data_opt::<Data<SyncSenderActor>>()
+            .map(|data| data.get_ref())
+    }
 }
 
 #[derive(Clone)]
@@ -126,6 +126,13 @@ pub async fn get_loaders(
         async_std::task::spawn,
     );
 
+    let stocktake_by_id_loader = DataLoader::new(
+        StocktakeByIdLoader {
+            connection_manager: connection_manager.clone(),
+        },
+        async_std::task::spawn,
+    );
+
     let stocktake_line_loader = DataLoader::new(
         StocktakeLineByStocktakeIdLoader {
             connection_manager: connection_manager.clone(),
@@ -200,6 +207,7 @@ pub async fn get_loaders(
     loaders.insert(requisition_line_by_requisition_id_loader);
     loaders.insert(requisition_line_by_linked_requisition_line_id_loader);
     loaders.insert(item_stats_for_item_loader);
+    loaders.insert(stocktake_by_id_loader);
     loaders.insert(stocktake_line_loader);
     loaders.insert(requisition_line_supply_status_loader);
     loaders.insert(requisition_lines_remaining_to_supply_loader);
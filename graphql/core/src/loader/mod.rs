@@ -11,6 +11,7 @@ mod requisition;
 mod requisition_line;
 mod requisition_supply_status;
 mod stock_line;
+mod stocktake;
 mod stocktake_lines;
 mod store;
 mod user;
@@ -30,6 +31,7 @@ pub use requisition::*;
 pub use requisition_line::*;
 pub use requisition_supply_status::*;
 pub use stock_line::*;
+pub use stocktake::StocktakeByIdLoader;
 pub use stocktake_lines::*;
 pub use store::*;
 pub use user::*;
@@ -0,0 +1,31 @@
+use repository::EqualFilter;
+use repository::{
+    RepositoryError, Stocktake, StocktakeFilter, StocktakeRepository, StorageConnectionManager,
+};
+
+use async_graphql::dataloader::*;
+use async_graphql::*;
+use std::collections::HashMap;
+
+pub struct StocktakeByIdLoader {
+    pub connection_manager: StorageConnectionManager,
+}
+
+#[async_trait::async_trait]
+impl Loader<String> for StocktakeByIdLoader {
+    type Value = Stocktake;
+    type Error = RepositoryError;
+
+    async fn load(&self, ids: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        let connection = self.connection_manager.connection()?;
+        let repo = StocktakeRepository::new(&connection);
+
+        let result = repo
+            .query_by_filter(StocktakeFilter::new().id(EqualFilter::equal_any(ids.to_owned())))?;
+
+        Ok(result
+            .into_iter()
+            .map(|stocktake| (stocktake.id.clone(), stocktake))
+            .collect())
+    }
+}
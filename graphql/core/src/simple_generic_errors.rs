@@ -246,6 +246,7 @@ pub enum ForeignKey {
     StockLineId,
     LocationId,
     RequisitionId,
+    ParentLocationId,
 }
 
 pub struct ForeignKeyError(pub ForeignKey);
@@ -272,6 +273,8 @@ impl InvoiceLineBelongsToAnotherInvoice {
 #[graphql(rename_items = "camelCase")]
 pub enum UniqueValueKey {
     Code,
+    Username,
+    Gtin,
 }
 
 pub struct UniqueValueViolation(pub UniqueValueKey);
@@ -5,12 +5,32 @@ use self::queries::*;
 
 use async_graphql::*;
 use graphql_core::pagination::PaginationInput;
-use mutations::server_settings::{
-    update_server_settings, UpdateServerSettingsInput, UpdateServerSettingsResponse,
+use graphql_types::types::StorePreferenceNode;
+use mutations::{
+    alert::*,
+    barcode::*,
+    local_user::*,
+    manual_sync::{manual_sync, ManualSyncNode},
+    revoke_user_sessions::{revoke_user_sessions, RevokeUserSessionsResponse},
+    server_settings::{
+        update_server_settings, UpdateServerSettingsInput, UpdateServerSettingsResponse,
+    },
+    store_preference::*,
+    sync_error::{
+        discard_sync_error, retry_sync_error, DiscardSyncErrorResponse, RetrySyncErrorResponse,
+    },
 };
 use queries::{
+    alert::{
+        alert_config, alerts, AlertConfigNode, AlertFilterInput, AlertSortInput, AlertsResponse,
+    },
     requisition_line_chart::{ConsumptionOptionsInput, StockEvolutionOptionsInput},
     server_settings::{get_server_settings, server_restart, RestartNode, ServerSettingsResponse},
+    sync_error::{sync_errors, SyncErrorsResponse},
+    sync_status::{
+        initial_sync_status, sync_history, sync_status, InitialSyncStatusNode, SyncHistoryResponse,
+        SyncStatusNode,
+    },
 };
 
 #[derive(Default, Clone)]
@@ -48,6 +68,20 @@ impl GeneralQueries {
         me(ctx)
     }
 
+    /// Lists the active login sessions of the current user
+    pub async fn sessions(&self, ctx: &Context<'_>) -> Result<UserSessionsResponse> {
+        sessions(ctx)
+    }
+
+    /// Logs the current user out of one of its sessions
+    pub async fn revoke_session(
+        &self,
+        ctx: &Context<'_>,
+        session_id: String,
+    ) -> Result<RevokeSessionResponse> {
+        revoke_session(ctx, session_id)
+    }
+
     /// Query omSupply "name" entries
     pub async fn names(
         &self,
@@ -85,6 +119,28 @@ impl GeneralQueries {
         master_lists(ctx, store_id, page, filter, sort)
     }
 
+    /// Query the configured reasons for inventory adjustments
+    pub async fn inventory_adjustment_reasons(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(desc = "Pagination option (first and offset)")] page: Option<PaginationInput>,
+        #[graphql(desc = "Filter option")] filter: Option<InventoryAdjustmentReasonFilterInput>,
+        #[graphql(desc = "Sort options (only first sort input is evaluated for this endpoint)")]
+        sort: Option<Vec<InventoryAdjustmentReasonSortInput>>,
+    ) -> Result<InventoryAdjustmentReasonsResponse> {
+        inventory_adjustment_reasons(ctx, store_id, page, filter, sort)
+    }
+
+    /// Allocation preferences of the store
+    pub async fn store_preference(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<StorePreferenceNode> {
+        get_store_preference(ctx, store_id)
+    }
+
     /// Query omSupply "item" entries
     pub async fn items(
         &self,
@@ -98,6 +154,16 @@ impl GeneralQueries {
         items(ctx, store_id, page, filter, sort)
     }
 
+    /// Resolves a scanned barcode to the item and the matching stock lines of the store
+    pub async fn item_by_barcode(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(desc = "Scanned EAN/UPC, GS1-128 or GS1 DataMatrix content")] barcode: String,
+    ) -> Result<ItemByBarcodeResponse> {
+        item_by_barcode(ctx, &store_id, &barcode)
+    }
+
     pub async fn invoice_counts(
         &self,
         ctx: &Context<'_>,
@@ -117,6 +183,28 @@ impl GeneralQueries {
         stock_counts(ctx, store_id, timezone_offset, days_till_expired)
     }
 
+    /// Alert inbox of the store, newest alerts first if no sort is given
+    pub async fn alerts(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(desc = "Pagination option (first and offset)")] page: Option<PaginationInput>,
+        #[graphql(desc = "Filter option")] filter: Option<AlertFilterInput>,
+        #[graphql(desc = "Sort options (only first sort input is evaluated for this endpoint)")]
+        sort: Option<Vec<AlertSortInput>>,
+    ) -> Result<AlertsResponse> {
+        alerts(ctx, store_id, page, filter, sort)
+    }
+
+    /// Alert rules of the store
+    pub async fn alert_config(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<AlertConfigNode> {
+        alert_config(ctx, store_id)
+    }
+
     pub async fn requisition_line_chart(
         &self,
         ctx: &Context<'_>,
@@ -135,6 +223,88 @@ impl GeneralQueries {
     }
 }
 
+#[derive(Default, Clone)]
+pub struct GeneralMutations;
+
+#[Object]
+impl GeneralMutations {
+    /// Updates the preferences of the store
+    pub async fn update_store_preference(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: UpdateStorePreferenceInput,
+    ) -> Result<UpdateStorePreferenceResponse> {
+        update_store_preference(ctx, &store_id, input)
+    }
+
+    /// Sets or removes the minimum remaining shelf life of stock issued to a customer
+    pub async fn set_customer_shelf_life(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: SetCustomerShelfLifeInput,
+    ) -> Result<SetCustomerShelfLifeResponse> {
+        set_customer_shelf_life(ctx, &store_id, input)
+    }
+
+    pub async fn acknowledge_alert(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<UpdateAlertResponse> {
+        acknowledge_alert(ctx, &store_id, &id)
+    }
+
+    pub async fn dismiss_alert(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<UpdateAlertResponse> {
+        dismiss_alert(ctx, &store_id, &id)
+    }
+
+    /// Evaluates the alert rules of the store now instead of waiting for the next scheduled run
+    pub async fn evaluate_alerts(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<AlertEvaluationNode> {
+        evaluate_alerts(ctx, &store_id)
+    }
+
+    /// Replaces the alert rules of the store
+    pub async fn update_alert_config(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: UpdateAlertConfigInput,
+    ) -> Result<AlertConfigNode> {
+        update_alert_config(ctx, &store_id, input)
+    }
+
+    /// Registers the GTIN of an item so that it can be found by scanning its barcode
+    pub async fn insert_barcode(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: InsertBarcodeInput,
+    ) -> Result<InsertBarcodeResponse> {
+        insert_barcode(ctx, &store_id, input)
+    }
+
+    pub async fn delete_barcode(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<DeleteBarcodeResponse> {
+        delete_barcode(ctx, &store_id, id)
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct ServerAdminQueries;
 
@@ -150,6 +320,34 @@ impl ServerAdminQueries {
     pub async fn server_restart(&self, ctx: &Context<'_>) -> Result<RestartNode> {
         server_restart(ctx, false).await
     }
+
+    /// Whether a sync is running and the outcome of the latest syncs
+    pub async fn sync_status(&self, ctx: &Context<'_>) -> Result<SyncStatusNode> {
+        sync_status(ctx)
+    }
+
+    /// Past syncs, newest first
+    pub async fn sync_history(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Pagination option (first and offset)")] page: Option<PaginationInput>,
+    ) -> Result<SyncHistoryResponse> {
+        sync_history(ctx, page)
+    }
+
+    /// Progress of the initial sync
+    pub async fn initial_sync_status(&self, ctx: &Context<'_>) -> Result<InitialSyncStatusNode> {
+        initial_sync_status(ctx, false)
+    }
+
+    /// Sync records that failed translation or integration, most recently attempted first
+    pub async fn sync_errors(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Pagination option (first and offset)")] page: Option<PaginationInput>,
+    ) -> Result<SyncErrorsResponse> {
+        sync_errors(ctx, page)
+    }
 }
 #[derive(Default, Clone)]
 pub struct ServerAdminMutations;
@@ -163,6 +361,70 @@ impl ServerAdminMutations {
     ) -> Result<UpdateServerSettingsResponse> {
         update_server_settings(ctx, input, false)
     }
+
+    /// Queues a sync to run now instead of waiting for the next scheduled sync
+    pub async fn manual_sync(&self, ctx: &Context<'_>) -> Result<ManualSyncNode> {
+        manual_sync(ctx)
+    }
+
+    pub async fn retry_sync_error(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<RetrySyncErrorResponse> {
+        retry_sync_error(ctx, &id)
+    }
+
+    pub async fn discard_sync_error(
+        &self,
+        ctx: &Context<'_>,
+        id: String,
+    ) -> Result<DiscardSyncErrorResponse> {
+        discard_sync_error(ctx, &id)
+    }
+
+    /// Creates a user that is managed on this site only
+    pub async fn insert_local_user(
+        &self,
+        ctx: &Context<'_>,
+        input: InsertInput,
+    ) -> Result<InsertResponse> {
+        insert_local_user(ctx, input)
+    }
+
+    pub async fn update_local_user(
+        &self,
+        ctx: &Context<'_>,
+        input: UpdateInput,
+    ) -> Result<UpdateResponse> {
+        update_local_user(ctx, input)
+    }
+
+    pub async fn reset_local_user_password(
+        &self,
+        ctx: &Context<'_>,
+        input: ResetPasswordInput,
+    ) -> Result<ResetPasswordResponse> {
+        reset_local_user_password(ctx, input)
+    }
+
+    /// Replaces the stores and store permissions of a local user
+    pub async fn set_local_user_stores(
+        &self,
+        ctx: &Context<'_>,
+        input: SetStoresInput,
+    ) -> Result<SetStoresResponse> {
+        set_local_user_stores(ctx, input)
+    }
+
+    /// Logs a user out of all its sessions
+    pub async fn revoke_user_sessions(
+        &self,
+        ctx: &Context<'_>,
+        user_id: String,
+    ) -> Result<RevokeUserSessionsResponse> {
+        revoke_user_sessions(ctx, user_id)
+    }
 }
 
 /// No access control during init stage
@@ -181,6 +443,11 @@ impl ServerAdminStage0Queries {
     pub async fn server_restart(&self, ctx: &Context<'_>) -> Result<RestartNode> {
         server_restart(ctx, true).await
     }
+
+    /// Progress of the initial sync, the initial sync runs while the bootstrap API is served
+    pub async fn initial_sync_status(&self, ctx: &Context<'_>) -> Result<InitialSyncStatusNode> {
+        initial_sync_status(ctx, true)
+    }
 }
 /// No access control during init stage
 #[derive(Default, Clone)]
@@ -0,0 +1,212 @@
+use async_graphql::*;
+use graphql_core::{
+    simple_generic_errors::{RecordBelongsToAnotherStore, RecordNotFound},
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use repository::Alert;
+use service::{
+    alert::{
+        config::{UpdateAlertConfig, UpdateAlertConfigError as UpdateConfigServiceError},
+        evaluate::AlertEvaluation,
+        update::UpdateAlertError as ServiceError,
+    },
+    permission_validation::{Resource, ResourceAccessRequest},
+};
+
+use crate::queries::alert::{AlertConfigNode, AlertNode};
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum UpdateAlertErrorInterface {
+    RecordNotFound(RecordNotFound),
+    RecordBelongsToAnotherStore(RecordBelongsToAnotherStore),
+}
+
+#[derive(SimpleObject)]
+pub struct UpdateAlertError {
+    pub error: UpdateAlertErrorInterface,
+}
+
+#[derive(Union)]
+pub enum UpdateAlertResponse {
+    Error(UpdateAlertError),
+    Response(AlertNode),
+}
+
+/// Marks an active alert as seen
+pub fn acknowledge_alert(
+    ctx: &Context<'_>,
+    store_id: &str,
+    id: &str,
+) -> Result<UpdateAlertResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateAlert,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.alert_service.acknowledge_alert(
+        &service_context,
+        store_id,
+        &user.user_id,
+        id,
+    ))
+}
+
+/// Removes an alert from the inbox
+pub fn dismiss_alert(ctx: &Context<'_>, store_id: &str, id: &str) -> Result<UpdateAlertResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateAlert,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.alert_service.dismiss_alert(
+        &service_context,
+        store_id,
+        &user.user_id,
+        id,
+    ))
+}
+
+fn map_response(from: Result<Alert, ServiceError>) -> Result<UpdateAlertResponse> {
+    let result = match from {
+        Ok(alert) => UpdateAlertResponse::Response(AlertNode::from_domain(alert)),
+        Err(error) => UpdateAlertResponse::Error(UpdateAlertError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+fn map_error(error: ServiceError) -> Result<UpdateAlertErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::AlertDoesNotExist => {
+            return Ok(UpdateAlertErrorInterface::RecordNotFound(RecordNotFound {}))
+        }
+        ServiceError::NotThisStoreAlert => {
+            return Ok(UpdateAlertErrorInterface::RecordBelongsToAnotherStore(
+                RecordBelongsToAnotherStore {},
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::AlertAlreadyAcknowledged => BadUserInput(formatted_error),
+        ServiceError::AlertAlreadyDismissed => BadUserInput(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+pub struct AlertEvaluationNode {
+    evaluation: AlertEvaluation,
+}
+
+#[Object]
+impl AlertEvaluationNode {
+    pub async fn number_of_new_alerts(&self) -> u32 {
+        self.evaluation.number_of_new_alerts
+    }
+
+    pub async fn number_of_resolved_alerts(&self) -> u32 {
+        self.evaluation.number_of_resolved_alerts
+    }
+}
+
+/// Evaluates the alert rules of the store now, alerts don't need to be enabled
+pub fn evaluate_alerts(ctx: &Context<'_>, store_id: &str) -> Result<AlertEvaluationNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateAlert,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let evaluation = service_provider
+        .alert_service
+        .evaluate_alerts(&service_context, store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(AlertEvaluationNode { evaluation })
+}
+
+#[derive(InputObject)]
+pub struct UpdateAlertConfigInput {
+    pub is_enabled: bool,
+    pub min_months_of_stock: f64,
+    pub expiry_warning_days: i32,
+    pub inbound_shipment_overdue_days: i32,
+}
+
+impl UpdateAlertConfigInput {
+    pub fn to_domain(self) -> UpdateAlertConfig {
+        let UpdateAlertConfigInput {
+            is_enabled,
+            min_months_of_stock,
+            expiry_warning_days,
+            inbound_shipment_overdue_days,
+        } = self;
+
+        UpdateAlertConfig {
+            is_enabled,
+            min_months_of_stock,
+            expiry_warning_days,
+            inbound_shipment_overdue_days,
+        }
+    }
+}
+
+pub fn update_alert_config(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpdateAlertConfigInput,
+) -> Result<AlertConfigNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStorePreference,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let config = service_provider
+        .alert_service
+        .update_alert_config(&service_context, store_id, input.to_domain())
+        .map_err(|error| {
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                UpdateConfigServiceError::ThresholdIsNegative => {
+                    StandardGraphqlError::BadUserInput(formatted_error)
+                }
+                UpdateConfigServiceError::DatabaseError(_) => {
+                    StandardGraphqlError::InternalError(formatted_error)
+                }
+            };
+            graphql_error.extend()
+        })?;
+
+    Ok(AlertConfigNode::from_domain(config))
+}
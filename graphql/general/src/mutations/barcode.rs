@@ -0,0 +1,533 @@
+use async_graphql::*;
+use graphql_core::{
+    simple_generic_errors::{
+        ForeignKey, ForeignKeyError, RecordNotFound, UniqueValueKey, UniqueValueViolation,
+    },
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::DeleteResponse;
+use repository::BarcodeRow;
+use service::{
+    barcode::{
+        delete::{DeleteBarcode, DeleteBarcodeError as DeleteServiceError},
+        insert::{InsertBarcode, InsertBarcodeError as InsertServiceError},
+    },
+    permission_validation::{Resource, ResourceAccessRequest},
+};
+
+use crate::queries::InvalidBarcode;
+
+pub struct BarcodeNode {
+    pub barcode: BarcodeRow,
+}
+
+#[Object]
+impl BarcodeNode {
+    pub async fn id(&self) -> &str {
+        &self.barcode.id
+    }
+
+    /// GTIN normalised to 14 digits
+    pub async fn gtin(&self) -> &str {
+        &self.barcode.gtin
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.barcode.item_id
+    }
+
+    pub async fn manufacturer_id(&self) -> &Option<String> {
+        &self.barcode.manufacturer_id
+    }
+
+    pub async fn pack_size(&self) -> &Option<i32> {
+        &self.barcode.pack_size
+    }
+}
+
+#[derive(InputObject)]
+pub struct InsertBarcodeInput {
+    pub id: String,
+    /// GTIN as printed on the packaging, e.g. an EAN-13 or UPC-A number
+    pub gtin: String,
+    pub item_id: String,
+    pub manufacturer_id: Option<String>,
+    /// Number of units in the scanned pack
+    pub pack_size: Option<i32>,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum InsertBarcodeErrorInterface {
+    InvalidBarcode(InvalidBarcode),
+    UniqueValueViolation(UniqueValueViolation),
+    ForeignKeyError(ForeignKeyError),
+}
+
+#[derive(SimpleObject)]
+pub struct InsertBarcodeError {
+    pub error: InsertBarcodeErrorInterface,
+}
+
+#[derive(Union)]
+pub enum InsertBarcodeResponse {
+    Error(InsertBarcodeError),
+    Response(BarcodeNode),
+}
+
+pub fn insert_barcode(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: InsertBarcodeInput,
+) -> Result<InsertBarcodeResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateBarcode,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let result = match service_provider
+        .barcode_service
+        .insert_barcode(&service_context, input.to_domain())
+    {
+        Ok(barcode) => InsertBarcodeResponse::Response(BarcodeNode { barcode }),
+        Err(error) => InsertBarcodeResponse::Error(InsertBarcodeError {
+            error: map_insert_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl InsertBarcodeInput {
+    pub fn to_domain(self) -> InsertBarcode {
+        let InsertBarcodeInput {
+            id,
+            gtin,
+            item_id,
+            manufacturer_id,
+            pack_size,
+        } = self;
+
+        InsertBarcode {
+            id,
+            gtin,
+            item_id,
+            manufacturer_id,
+            pack_size,
+        }
+    }
+}
+
+fn map_insert_error(error: InsertServiceError) -> Result<InsertBarcodeErrorInterface> {
+    use InsertServiceError as ServiceError;
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::InvalidGtin(parse_error) => {
+            return Ok(InsertBarcodeErrorInterface::InvalidBarcode(InvalidBarcode(
+                format!("{:?}", parse_error),
+            )))
+        }
+        ServiceError::GtinAlreadyExists => {
+            return Ok(InsertBarcodeErrorInterface::UniqueValueViolation(
+                UniqueValueViolation(UniqueValueKey::Gtin),
+            ))
+        }
+        ServiceError::ItemDoesNotExist => {
+            return Ok(InsertBarcodeErrorInterface::ForeignKeyError(
+                ForeignKeyError(ForeignKey::ItemId),
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::BarcodeAlreadyExists => BadUserInput(formatted_error),
+        ServiceError::ManufacturerDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::PackSizeBelowOne => BadUserInput(formatted_error),
+        ServiceError::CreatedRecordNotFound => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum DeleteBarcodeErrorInterface {
+    RecordNotFound(RecordNotFound),
+}
+
+#[derive(SimpleObject)]
+pub struct DeleteBarcodeError {
+    pub error: DeleteBarcodeErrorInterface,
+}
+
+#[derive(Union)]
+pub enum DeleteBarcodeResponse {
+    Error(DeleteBarcodeError),
+    Response(DeleteResponse),
+}
+
+pub fn delete_barcode(
+    ctx: &Context<'_>,
+    store_id: &str,
+    id: String,
+) -> Result<DeleteBarcodeResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateBarcode,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let result = match service_provider
+        .barcode_service
+        .delete_barcode(&service_context, DeleteBarcode { id })
+    {
+        Ok(id) => DeleteBarcodeResponse::Response(DeleteResponse(id)),
+        Err(error) => DeleteBarcodeResponse::Error(DeleteBarcodeError {
+            error: map_delete_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+fn map_delete_error(error: DeleteServiceError) -> Result<DeleteBarcodeErrorInterface> {
+    use DeleteServiceError as ServiceError;
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::BarcodeDoesNotExist => {
+            return Ok(DeleteBarcodeErrorInterface::RecordNotFound(RecordNotFound))
+        }
+        // Standard Graphql Errors
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+#[cfg(test)]
+mod test {
+    use async_graphql::EmptyMutation;
+    use graphql_core::{
+        assert_graphql_query, assert_standard_graphql_error, test_helpers::setup_graphl_test,
+    };
+    use repository::{mock::MockDataInserts, BarcodeRow, StorageConnectionManager};
+    use serde_json::json;
+    use service::{
+        barcode::{
+            delete::{DeleteBarcode, DeleteBarcodeError},
+            gs1::Gs1ParseError,
+            insert::{InsertBarcode, InsertBarcodeError},
+            BarcodeServiceTrait,
+        },
+        service_provider::{ServiceContext, ServiceProvider},
+    };
+
+    use crate::GeneralMutations;
+
+    type InsertMethod =
+        dyn Fn(InsertBarcode) -> Result<BarcodeRow, InsertBarcodeError> + Sync + Send;
+    type DeleteMethod = dyn Fn(DeleteBarcode) -> Result<String, DeleteBarcodeError> + Sync + Send;
+
+    pub struct TestService {
+        insert: Option<Box<InsertMethod>>,
+        delete: Option<Box<DeleteMethod>>,
+    }
+
+    impl BarcodeServiceTrait for TestService {
+        fn insert_barcode(
+            &self,
+            _: &ServiceContext,
+            input: InsertBarcode,
+        ) -> Result<BarcodeRow, InsertBarcodeError> {
+            (self.insert.as_ref().unwrap())(input)
+        }
+
+        fn delete_barcode(
+            &self,
+            _: &ServiceContext,
+            input: DeleteBarcode,
+        ) -> Result<String, DeleteBarcodeError> {
+            (self.delete.as_ref().unwrap())(input)
+        }
+    }
+
+    fn insert_service(method: Box<InsertMethod>) -> TestService {
+        TestService {
+            insert: Some(method),
+            delete: None,
+        }
+    }
+
+    fn delete_service(method: Box<DeleteMethod>) -> TestService {
+        TestService {
+            insert: None,
+            delete: Some(method),
+        }
+    }
+
+    fn service_provider(
+        test_service: TestService,
+        connection_manager: &StorageConnectionManager,
+    ) -> ServiceProvider {
+        let mut service_provider = ServiceProvider::new(connection_manager.clone());
+        service_provider.barcode_service = Box::new(test_service);
+        service_provider
+    }
+
+    fn empty_variables() -> serde_json::Value {
+        json!({
+          "input": {
+            "id": "n/a",
+            "gtin": "n/a",
+            "itemId": "n/a"
+          }
+        })
+    }
+
+    #[actix_rt::test]
+    async fn test_graphql_insert_barcode_errors() {
+        let (_, _, connection_manager, settings) = setup_graphl_test(
+            EmptyMutation,
+            GeneralMutations,
+            "test_graphql_insert_barcode_errors",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let mutation = r#"
+        mutation ($input: InsertBarcodeInput!) {
+            insertBarcode(storeId: \"store_a\", input: $input) {
+              ... on InsertBarcodeError {
+                error {
+                  __typename
+                }
+              }
+            }
+          }
+        "#;
+
+        // InvalidGtin
+        let test_service = insert_service(Box::new(|_| {
+            Err(InsertBarcodeError::InvalidGtin(Gs1ParseError::InvalidGtin(
+                "n/a".to_string(),
+            )))
+        }));
+        let expected = json!({
+            "insertBarcode": {
+              "error": {
+                "__typename": "InvalidBarcode"
+              }
+            }
+          }
+        );
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &Some(empty_variables()),
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // GtinAlreadyExists
+        let test_service = insert_service(Box::new(|_| Err(InsertBarcodeError::GtinAlreadyExists)));
+        let expected = json!({
+            "insertBarcode": {
+              "error": {
+                "__typename": "UniqueValueViolation"
+              }
+            }
+          }
+        );
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &Some(empty_variables()),
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // ItemDoesNotExist
+        let test_service = insert_service(Box::new(|_| Err(InsertBarcodeError::ItemDoesNotExist)));
+        let expected = json!({
+            "insertBarcode": {
+              "error": {
+                "__typename": "ForeignKeyError"
+              }
+            }
+          }
+        );
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &Some(empty_variables()),
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // PackSizeBelowOne
+        let test_service = insert_service(Box::new(|_| Err(InsertBarcodeError::PackSizeBelowOne)));
+        let expected_message = "Bad user input";
+        assert_standard_graphql_error!(
+            &settings,
+            &mutation,
+            &Some(empty_variables()),
+            &expected_message,
+            None,
+            Some(service_provider(test_service, &connection_manager))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_graphql_insert_barcode_success() {
+        let (_, _, connection_manager, settings) = setup_graphl_test(
+            EmptyMutation,
+            GeneralMutations,
+            "test_graphql_insert_barcode_success",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let mutation = r#"
+        mutation ($input: InsertBarcodeInput!) {
+            insertBarcode(storeId: \"store_a\", input: $input) {
+                ... on BarcodeNode {
+                    id
+                    gtin
+                    packSize
+                }
+            }
+          }
+        "#;
+
+        let test_service = insert_service(Box::new(|input| {
+            assert_eq!(
+                input,
+                InsertBarcode {
+                    id: "id input".to_string(),
+                    gtin: "4006381333931".to_string(),
+                    item_id: "item input".to_string(),
+                    manufacturer_id: Some("manufacturer input".to_string()),
+                    pack_size: Some(10),
+                }
+            );
+            Ok(BarcodeRow {
+                id: "id input".to_string(),
+                gtin: "04006381333931".to_string(),
+                item_id: "item input".to_string(),
+                manufacturer_id: Some("manufacturer input".to_string()),
+                pack_size: Some(10),
+            })
+        }));
+
+        let variables = json!({
+          "input": {
+            "id": "id input",
+            "gtin": "4006381333931",
+            "itemId": "item input",
+            "manufacturerId": "manufacturer input",
+            "packSize": 10
+          }
+        });
+        let expected = json!({
+            "insertBarcode": {
+                "id": "id input",
+                "gtin": "04006381333931",
+                "packSize": 10
+            }
+          }
+        );
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &Some(variables),
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_graphql_delete_barcode() {
+        let (_, _, connection_manager, settings) = setup_graphl_test(
+            EmptyMutation,
+            GeneralMutations,
+            "test_graphql_delete_barcode",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let mutation = r#"
+        mutation {
+            deleteBarcode(storeId: \"store_a\", id: \"id input\") {
+              ... on DeleteBarcodeError {
+                error {
+                  __typename
+                }
+              }
+              ... on DeleteResponse {
+                id
+              }
+            }
+          }
+        "#;
+
+        // BarcodeDoesNotExist
+        let test_service =
+            delete_service(Box::new(|_| Err(DeleteBarcodeError::BarcodeDoesNotExist)));
+        let expected = json!({
+            "deleteBarcode": {
+              "error": {
+                "__typename": "RecordNotFound"
+              }
+            }
+          }
+        );
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &None,
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // Success
+        let test_service = delete_service(Box::new(|input| {
+            assert_eq!(
+                input,
+                DeleteBarcode {
+                    id: "id input".to_string()
+                }
+            );
+            Ok(input.id)
+        }));
+        let expected = json!({
+            "deleteBarcode": {
+              "id": "id input"
+            }
+          }
+        );
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &None,
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+    }
+}
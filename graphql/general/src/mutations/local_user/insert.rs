@@ -0,0 +1,316 @@
+use async_graphql::*;
+use graphql_core::{
+    simple_generic_errors::{UniqueValueKey, UniqueValueViolation},
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::UserNode;
+use repository::User;
+use service::{
+    local_user::{InsertLocalUser as ServiceInput, InsertLocalUserError as ServiceError},
+    permission_validation::{Resource, ResourceAccessRequest},
+};
+
+#[derive(InputObject)]
+#[graphql(name = "InsertLocalUserInput")]
+pub struct InsertInput {
+    pub id: String,
+    pub username: String,
+    /// Plain text password
+    pub password: String,
+    pub email: Option<String>,
+}
+
+#[derive(Interface)]
+#[graphql(name = "InsertLocalUserErrorInterface")]
+#[graphql(field(name = "description", type = "String"))]
+pub enum InsertErrorInterface {
+    UniqueValueViolation(UniqueValueViolation),
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "InsertLocalUserError")]
+pub struct InsertError {
+    pub error: InsertErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "InsertLocalUserResponse")]
+pub enum InsertResponse {
+    Error(InsertError),
+    Response(UserNode),
+}
+
+pub fn insert_local_user(ctx: &Context<'_>, input: InsertInput) -> Result<InsertResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(
+        service_provider
+            .local_user_service
+            .insert_local_user(&service_context, input.to_domain()),
+    )
+}
+
+pub fn map_response(from: Result<User, ServiceError>) -> Result<InsertResponse> {
+    let result = match from {
+        Ok(user) => InsertResponse::Response(UserNode::from_domain(user)),
+        Err(error) => InsertResponse::Error(InsertError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl InsertInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let InsertInput {
+            id,
+            username,
+            password,
+            email,
+        } = self;
+
+        ServiceInput {
+            id,
+            username,
+            password,
+            email,
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::UsernameAlreadyExists => {
+            return Ok(InsertErrorInterface::UniqueValueViolation(
+                UniqueValueViolation(UniqueValueKey::Username),
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::UserAlreadyExists => BadUserInput(formatted_error),
+        ServiceError::EmptyUsername => BadUserInput(formatted_error),
+        ServiceError::EmptyPassword => BadUserInput(formatted_error),
+        ServiceError::FailedToHashPassword(_) => InternalError(formatted_error),
+        ServiceError::NewlyCreatedUserDoesNotExist => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+#[cfg(test)]
+mod test {
+    use async_graphql::EmptyMutation;
+    use graphql_core::{
+        assert_graphql_query, assert_standard_graphql_error, test_helpers::setup_graphl_test,
+    };
+    use repository::{mock::MockDataInserts, StorageConnectionManager, User, UserAccountRow};
+    use serde_json::json;
+    use service::{
+        local_user::{
+            InsertLocalUser as ServiceInput, InsertLocalUserError as ServiceError,
+            LocalUserServiceTrait,
+        },
+        service_provider::{ServiceContext, ServiceProvider},
+    };
+    use util::inline_init;
+
+    use crate::ServerAdminMutations;
+
+    type InsertMethod = dyn Fn(ServiceInput) -> Result<User, ServiceError> + Sync + Send;
+
+    pub struct TestService(pub Box<InsertMethod>);
+
+    impl LocalUserServiceTrait for TestService {
+        fn insert_local_user(
+            &self,
+            _: &ServiceContext,
+            input: ServiceInput,
+        ) -> Result<User, ServiceError> {
+            self.0(input)
+        }
+    }
+
+    fn service_provider(
+        test_service: TestService,
+        connection_manager: &StorageConnectionManager,
+    ) -> ServiceProvider {
+        let mut service_provider = ServiceProvider::new(connection_manager.clone());
+        service_provider.local_user_service = Box::new(test_service);
+        service_provider
+    }
+
+    fn empty_variables() -> serde_json::Value {
+        json!({
+          "input": {
+            "id": "n/a",
+            "username": "n/a",
+            "password": "n/a"
+          }
+        })
+    }
+
+    #[actix_rt::test]
+    async fn test_graphql_insert_local_user_errors() {
+        let (_, _, connection_manager, settings) = setup_graphl_test(
+            EmptyMutation,
+            ServerAdminMutations,
+            "test_graphql_insert_local_user_errors",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let mutation = r#"
+        mutation ($input: InsertLocalUserInput!) {
+            insertLocalUser(input: $input) {
+              ... on InsertLocalUserError {
+                error {
+                  __typename
+                  ... on UniqueValueViolation {
+                    field
+                  }
+                }
+              }
+            }
+          }
+        "#;
+
+        // UsernameAlreadyExists
+        let test_service = TestService(Box::new(|_| Err(ServiceError::UsernameAlreadyExists)));
+
+        let expected = json!({
+            "insertLocalUser": {
+              "error": {
+                "__typename": "UniqueValueViolation",
+                "field": "username"
+              }
+            }
+          }
+        );
+
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &Some(empty_variables()),
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // UserAlreadyExists
+        let test_service = TestService(Box::new(|_| Err(ServiceError::UserAlreadyExists)));
+        let expected_message = "Bad user input";
+        assert_standard_graphql_error!(
+            &settings,
+            &mutation,
+            &Some(empty_variables()),
+            &expected_message,
+            None,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // EmptyPassword
+        let test_service = TestService(Box::new(|_| Err(ServiceError::EmptyPassword)));
+        let expected_message = "Bad user input";
+        assert_standard_graphql_error!(
+            &settings,
+            &mutation,
+            &Some(empty_variables()),
+            &expected_message,
+            None,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // NewlyCreatedUserDoesNotExist
+        let test_service = TestService(Box::new(|_| {
+            Err(ServiceError::NewlyCreatedUserDoesNotExist)
+        }));
+        let expected_message = "Internal error";
+        assert_standard_graphql_error!(
+            &settings,
+            &mutation,
+            &Some(empty_variables()),
+            &expected_message,
+            None,
+            Some(service_provider(test_service, &connection_manager))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_graphql_insert_local_user_success() {
+        let (_, _, connection_manager, settings) = setup_graphl_test(
+            EmptyMutation,
+            ServerAdminMutations,
+            "test_graphql_insert_local_user_success",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let mutation = r#"
+        mutation ($input: InsertLocalUserInput!) {
+            insertLocalUser(input: $input) {
+                ... on UserNode {
+                    userId
+                    isLocal
+                }
+            }
+          }
+        "#;
+
+        let test_service = TestService(Box::new(|input| {
+            assert_eq!(
+                input,
+                ServiceInput {
+                    id: "id input".to_string(),
+                    username: "username input".to_string(),
+                    password: "password input".to_string(),
+                    email: Some("email input".to_string()),
+                }
+            );
+            Ok(inline_init(|r: &mut User| {
+                r.user_row = inline_init(|r: &mut UserAccountRow| {
+                    r.id = "id input".to_string();
+                    r.is_local = true;
+                });
+            }))
+        }));
+
+        let variables = json!({
+          "input": {
+            "id": "id input",
+            "username": "username input",
+            "password": "password input",
+            "email": "email input"
+          }
+        });
+
+        let expected = json!({
+            "insertLocalUser": {
+                "userId": "id input",
+                "isLocal": true
+            }
+          }
+        );
+
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &Some(variables),
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+    }
+}
@@ -0,0 +1,11 @@
+mod insert;
+pub use self::insert::*;
+
+mod update;
+pub use self::update::*;
+
+mod reset_password;
+pub use self::reset_password::*;
+
+mod set_stores;
+pub use self::set_stores::*;
@@ -0,0 +1,73 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::UserNode;
+use service::{
+    local_user::{
+        ResetLocalUserPassword as ServiceInput, ResetLocalUserPasswordError as ServiceError,
+    },
+    permission_validation::{Resource, ResourceAccessRequest},
+};
+
+#[derive(InputObject)]
+#[graphql(name = "ResetLocalUserPasswordInput")]
+pub struct ResetPasswordInput {
+    pub id: String,
+    /// Plain text password
+    pub password: String,
+}
+
+#[derive(Union)]
+#[graphql(name = "ResetLocalUserPasswordResponse")]
+pub enum ResetPasswordResponse {
+    Response(UserNode),
+}
+
+pub fn reset_local_user_password(
+    ctx: &Context<'_>,
+    input: ResetPasswordInput,
+) -> Result<ResetPasswordResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    match service_provider
+        .local_user_service
+        .reset_local_user_password(&service_context, input.to_domain())
+    {
+        Ok(user) => Ok(ResetPasswordResponse::Response(UserNode::from_domain(user))),
+        Err(error) => Err(map_error(error)),
+    }
+}
+
+impl ResetPasswordInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let ResetPasswordInput { id, password } = self;
+        ServiceInput { id, password }
+    }
+}
+
+fn map_error(error: ServiceError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        ServiceError::UserDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::NotALocalUser => BadUserInput(formatted_error),
+        ServiceError::EmptyPassword => BadUserInput(formatted_error),
+        ServiceError::FailedToHashPassword(_) => InternalError(formatted_error),
+        ServiceError::UpdatedUserDoesNotExist => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
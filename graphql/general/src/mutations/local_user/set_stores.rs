@@ -0,0 +1,103 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{UserNode, UserPermission};
+use service::{
+    local_user::{
+        LocalUserStore, SetLocalUserStores as ServiceInput, SetLocalUserStoresError as ServiceError,
+    },
+    permission_validation::{Resource, ResourceAccessRequest},
+};
+
+#[derive(InputObject)]
+#[graphql(name = "LocalUserStoreInput")]
+pub struct StoreInput {
+    pub store_id: String,
+    pub is_default: bool,
+    /// Access to the store is always granted, i.e. STORE_ACCESS doesn't need to be specified
+    pub permissions: Vec<UserPermission>,
+}
+
+#[derive(InputObject)]
+#[graphql(name = "SetLocalUserStoresInput")]
+pub struct SetStoresInput {
+    pub user_id: String,
+    /// Replaces all existing stores and permissions of the user
+    pub stores: Vec<StoreInput>,
+}
+
+#[derive(Union)]
+#[graphql(name = "SetLocalUserStoresResponse")]
+pub enum SetStoresResponse {
+    Response(UserNode),
+}
+
+pub fn set_local_user_stores(
+    ctx: &Context<'_>,
+    input: SetStoresInput,
+) -> Result<SetStoresResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    match service_provider
+        .local_user_service
+        .set_local_user_stores(&service_context, input.to_domain())
+    {
+        Ok(user) => Ok(SetStoresResponse::Response(UserNode::from_domain(user))),
+        Err(error) => Err(map_error(error)),
+    }
+}
+
+impl SetStoresInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let SetStoresInput { user_id, stores } = self;
+
+        ServiceInput {
+            user_id,
+            stores: stores
+                .into_iter()
+                .map(
+                    |StoreInput {
+                         store_id,
+                         is_default,
+                         permissions,
+                     }| LocalUserStore {
+                        store_id,
+                        is_default,
+                        permissions: permissions
+                            .into_iter()
+                            .map(UserPermission::to_domain)
+                            .collect(),
+                    },
+                )
+                .collect(),
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        ServiceError::UserDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::NotALocalUser => BadUserInput(formatted_error),
+        ServiceError::StoreDoesNotExist(_) => BadUserInput(formatted_error),
+        ServiceError::DuplicateStore(_) => BadUserInput(formatted_error),
+        ServiceError::MoreThanOneDefaultStore => BadUserInput(formatted_error),
+        ServiceError::UpdatedUserDoesNotExist => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
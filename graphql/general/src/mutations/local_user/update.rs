@@ -0,0 +1,112 @@
+use async_graphql::*;
+use graphql_core::{
+    simple_generic_errors::{UniqueValueKey, UniqueValueViolation},
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::UserNode;
+use repository::User;
+use service::{
+    local_user::{UpdateLocalUser as ServiceInput, UpdateLocalUserError as ServiceError},
+    permission_validation::{Resource, ResourceAccessRequest},
+};
+
+#[derive(InputObject)]
+#[graphql(name = "UpdateLocalUserInput")]
+pub struct UpdateInput {
+    pub id: String,
+    pub username: Option<String>,
+    pub email: Option<String>,
+    /// Disabled users can't log in
+    pub is_disabled: Option<bool>,
+}
+
+#[derive(Interface)]
+#[graphql(name = "UpdateLocalUserErrorInterface")]
+#[graphql(field(name = "description", type = "String"))]
+pub enum UpdateErrorInterface {
+    UniqueValueViolation(UniqueValueViolation),
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "UpdateLocalUserError")]
+pub struct UpdateError {
+    pub error: UpdateErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "UpdateLocalUserResponse")]
+pub enum UpdateResponse {
+    Error(UpdateError),
+    Response(UserNode),
+}
+
+pub fn update_local_user(ctx: &Context<'_>, input: UpdateInput) -> Result<UpdateResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(
+        service_provider
+            .local_user_service
+            .update_local_user(&service_context, input.to_domain()),
+    )
+}
+
+pub fn map_response(from: Result<User, ServiceError>) -> Result<UpdateResponse> {
+    let result = match from {
+        Ok(user) => UpdateResponse::Response(UserNode::from_domain(user)),
+        Err(error) => UpdateResponse::Error(UpdateError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl UpdateInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let UpdateInput {
+            id,
+            username,
+            email,
+            is_disabled,
+        } = self;
+
+        ServiceInput {
+            id,
+            username,
+            email,
+            is_disabled,
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::UsernameAlreadyExists => {
+            return Ok(UpdateErrorInterface::UniqueValueViolation(
+                UniqueValueViolation(UniqueValueKey::Username),
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::UserDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::NotALocalUser => BadUserInput(formatted_error),
+        ServiceError::EmptyUsername => BadUserInput(formatted_error),
+        ServiceError::UpdatedUserDoesNotExist => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
@@ -0,0 +1,42 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+pub struct ManualSyncNode {
+    is_queued: bool,
+}
+
+#[Object]
+impl ManualSyncNode {
+    /// False if another sync was already pending, in which case no additional sync is queued
+    pub async fn is_queued(&self) -> bool {
+        self.is_queued
+    }
+}
+
+pub fn manual_sync(ctx: &Context<'_>) -> Result<ManualSyncNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let sync_sender = match ctx.sync_sender() {
+        Some(sync_sender) => sync_sender,
+        None => {
+            let graphql_error = StandardGraphqlError::InternalError(
+                "Sync is not available until the server is fully configured".to_string(),
+            );
+            return Err(graphql_error.extend());
+        }
+    };
+
+    Ok(ManualSyncNode {
+        is_queued: sync_sender.send(),
+    })
+}
@@ -1 +1,8 @@
+pub mod alert;
+pub mod barcode;
+pub mod local_user;
+pub mod manual_sync;
+pub mod revoke_user_sessions;
 pub mod server_settings;
+pub mod store_preference;
+pub mod sync_error;
@@ -0,0 +1,63 @@
+use async_graphql::*;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    token::{JWTLogoutError, TokenService},
+};
+
+pub struct RevokeUserSessionsNode {
+    user_id: String,
+}
+
+#[Object]
+impl RevokeUserSessionsNode {
+    pub async fn user_id(&self) -> &str {
+        &self.user_id
+    }
+}
+
+#[derive(Union)]
+pub enum RevokeUserSessionsResponse {
+    Response(RevokeUserSessionsNode),
+}
+
+/// Logs a user out of all its sessions
+pub fn revoke_user_sessions(
+    ctx: &Context<'_>,
+    user_id: String,
+) -> Result<RevokeUserSessionsResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let auth_data = ctx.get_auth_data();
+    let mut service = TokenService::new(
+        &auth_data.token_bucket,
+        auth_data.auth_token_secret.as_bytes(),
+    );
+    match service.logout(&user_id) {
+        Ok(_) => Ok(RevokeUserSessionsResponse::Response(
+            RevokeUserSessionsNode { user_id },
+        )),
+        Err(error) => Err(map_error(error)),
+    }
+}
+
+fn map_error(error: JWTLogoutError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        JWTLogoutError::ConcurrencyLockError(_) => InternalError(formatted_error),
+        JWTLogoutError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
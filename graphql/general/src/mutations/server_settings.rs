@@ -7,7 +7,10 @@ use graphql_core::{
 use service::{
     permission_validation::{Resource, ResourceAccessRequest},
     settings_service::{SettingsService, SettingsServiceTrait, UpdateSettingsError},
-    sync_settings::SyncSettings,
+    sync_settings::{
+        SyncSettings, DEFAULT_MAX_BACKOFF_SEC, DEFAULT_MAX_JITTER_SEC,
+        DEFAULT_PARTIAL_FAILURE_RETRY_SEC,
+    },
 };
 use util::hash::sha256;
 
@@ -30,6 +33,13 @@ pub struct UpdateSyncSettingsInput {
     pub central_server_site_id: u32,
     pub site_id: u32,
     pub site_hardware_id: String,
+    /// Upper limit of the delay between retries after consecutive sync failures in sec
+    /// (default 3600)
+    pub max_backoff_sec: Option<u64>,
+    /// Delay before retrying after a partial sync failure in sec (default 60)
+    pub partial_failure_retry_sec: Option<u64>,
+    /// Maximum random delay added to scheduled syncs in sec (default 30)
+    pub max_jitter_sec: Option<u64>,
 }
 
 #[derive(InputObject)]
@@ -104,6 +114,11 @@ impl UpdateSyncSettingsInput {
             central_server_site_id: self.central_server_site_id,
             site_id: self.site_id,
             site_hardware_id: self.site_hardware_id,
+            max_backoff_sec: self.max_backoff_sec.unwrap_or(DEFAULT_MAX_BACKOFF_SEC),
+            partial_failure_retry_sec: self
+                .partial_failure_retry_sec
+                .unwrap_or(DEFAULT_PARTIAL_FAILURE_RETRY_SEC),
+            max_jitter_sec: self.max_jitter_sec.unwrap_or(DEFAULT_MAX_JITTER_SEC),
         }
     }
 }
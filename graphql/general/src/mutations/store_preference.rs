@@ -0,0 +1,252 @@
+use async_graphql::*;
+use graphql_core::{
+    generic_inputs::NullableUpdateInput,
+    simple_generic_errors::{
+        ForeignKey, ForeignKeyError, OtherPartyNotACustomer, OtherPartyNotVisible,
+        RecordBelongsToAnotherStore,
+    },
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{
+    AllocationStrategyNode, ConsumptionCalculationMethodNode, StorePreferenceNode,
+};
+use repository::StorePreferenceRow;
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    store_preference::update::{
+        SetCustomerShelfLife, SetCustomerShelfLifeError as SetShelfLifeServiceError,
+        UpdateStorePreference, UpdateStorePreferenceError as UpdateServiceError,
+    },
+};
+
+/// Settings that are not set keep their current value
+#[derive(InputObject)]
+pub struct UpdateStorePreferenceInput {
+    pub allocation_strategy: Option<AllocationStrategyNode>,
+    pub preferred_location_id: Option<NullableUpdateInput<String>>,
+    pub minimum_shelf_life_days: Option<NullableUpdateInput<u32>>,
+    pub requisitions_require_authorisation: Option<bool>,
+    pub consumption_calculation_method: Option<ConsumptionCalculationMethodNode>,
+    pub lead_time_months: Option<f64>,
+    pub safety_stock_months: Option<f64>,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum UpdateStorePreferenceErrorInterface {
+    ForeignKeyError(ForeignKeyError),
+    RecordBelongsToAnotherStore(RecordBelongsToAnotherStore),
+}
+
+#[derive(SimpleObject)]
+pub struct UpdateStorePreferenceError {
+    pub error: UpdateStorePreferenceErrorInterface,
+}
+
+#[derive(Union)]
+pub enum UpdateStorePreferenceResponse {
+    Error(UpdateStorePreferenceError),
+    Response(StorePreferenceNode),
+}
+
+/// Updates the preferences of the store
+pub fn update_store_preference(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpdateStorePreferenceInput,
+) -> Result<UpdateStorePreferenceResponse> {
+    // Only users who can authorise requisitions can turn authorisation on or off
+    if input.requisitions_require_authorisation.is_some() {
+        validate_auth(
+            ctx,
+            &ResourceAccessRequest {
+                resource: Resource::MutateRequisitionAuthorisationPreference,
+                store_id: Some(store_id.to_string()),
+            },
+        )?;
+    }
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStorePreference,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_update_response(
+        service_provider
+            .store_preference_service
+            .update_store_preference(&service_context, store_id, input.to_domain()),
+    )
+}
+
+impl UpdateStorePreferenceInput {
+    pub fn to_domain(self) -> UpdateStorePreference {
+        let UpdateStorePreferenceInput {
+            allocation_strategy,
+            preferred_location_id,
+            minimum_shelf_life_days,
+            requisitions_require_authorisation,
+            consumption_calculation_method,
+            lead_time_months,
+            safety_stock_months,
+        } = self;
+
+        UpdateStorePreference {
+            allocation_strategy: allocation_strategy.map(AllocationStrategyNode::to_domain),
+            preferred_location_id: preferred_location_id.map(NullableUpdateInput::to_domain),
+            minimum_shelf_life_days: minimum_shelf_life_days.map(NullableUpdateInput::to_domain),
+            requisitions_require_authorisation,
+            consumption_calculation_method: consumption_calculation_method
+                .map(ConsumptionCalculationMethodNode::to_domain),
+            lead_time_months,
+            safety_stock_months,
+        }
+    }
+}
+
+fn map_update_response(
+    from: Result<StorePreferenceRow, UpdateServiceError>,
+) -> Result<UpdateStorePreferenceResponse> {
+    use StandardGraphqlError::*;
+    use UpdateServiceError as ServiceError;
+    use UpdateStorePreferenceErrorInterface as OutError;
+
+    let error = match from {
+        Ok(store_preference) => {
+            return Ok(UpdateStorePreferenceResponse::Response(
+                StorePreferenceNode::from_domain(store_preference),
+            ))
+        }
+        Err(error) => error,
+    };
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::LocationDoesNotExist => {
+            OutError::ForeignKeyError(ForeignKeyError(ForeignKey::LocationId))
+        }
+        ServiceError::LocationDoesNotBelongToCurrentStore => {
+            OutError::RecordBelongsToAnotherStore(RecordBelongsToAnotherStore {})
+        }
+        // Standard Graphql Errors
+        ServiceError::LeadTimeOrSafetyStockIsNegative => {
+            return Err(BadUserInput(formatted_error).extend())
+        }
+        ServiceError::DatabaseError(_) => return Err(InternalError(formatted_error).extend()),
+    };
+
+    Ok(UpdateStorePreferenceResponse::Error(
+        UpdateStorePreferenceError {
+            error: graphql_error,
+        },
+    ))
+}
+
+#[derive(InputObject)]
+pub struct SetCustomerShelfLifeInput {
+    pub name_id: String,
+    /// Removes the customer specific shelf life if not set
+    pub minimum_shelf_life_days: Option<u32>,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum SetCustomerShelfLifeErrorInterface {
+    OtherPartyNotACustomer(OtherPartyNotACustomer),
+    OtherPartyNotVisible(OtherPartyNotVisible),
+    ForeignKeyError(ForeignKeyError),
+}
+
+#[derive(SimpleObject)]
+pub struct SetCustomerShelfLifeError {
+    pub error: SetCustomerShelfLifeErrorInterface,
+}
+
+pub struct SetCustomerShelfLifeNode {
+    name_id: String,
+}
+
+#[Object]
+impl SetCustomerShelfLifeNode {
+    pub async fn name_id(&self) -> &str {
+        &self.name_id
+    }
+}
+
+#[derive(Union)]
+pub enum SetCustomerShelfLifeResponse {
+    Error(SetCustomerShelfLifeError),
+    Response(SetCustomerShelfLifeNode),
+}
+
+/// Sets the minimum remaining shelf life of stock issued to a customer of the store
+pub fn set_customer_shelf_life(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: SetCustomerShelfLifeInput,
+) -> Result<SetCustomerShelfLifeResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStorePreference,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let SetCustomerShelfLifeInput {
+        name_id,
+        minimum_shelf_life_days,
+    } = input;
+    let result = service_provider
+        .store_preference_service
+        .set_customer_shelf_life(
+            &service_context,
+            store_id,
+            SetCustomerShelfLife {
+                name_id: name_id.clone(),
+                minimum_shelf_life_days,
+            },
+        );
+
+    use SetCustomerShelfLifeErrorInterface as OutError;
+    use SetShelfLifeServiceError as ServiceError;
+    use StandardGraphqlError::*;
+
+    let error = match result {
+        Ok(()) => {
+            return Ok(SetCustomerShelfLifeResponse::Response(
+                SetCustomerShelfLifeNode { name_id },
+            ))
+        }
+        Err(error) => error,
+    };
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::OtherPartyDoesNotExist => {
+            OutError::ForeignKeyError(ForeignKeyError(ForeignKey::OtherPartyId))
+        }
+        ServiceError::OtherPartyNotVisible => OutError::OtherPartyNotVisible(OtherPartyNotVisible),
+        ServiceError::OtherPartyNotACustomer => {
+            OutError::OtherPartyNotACustomer(OtherPartyNotACustomer)
+        }
+        // Standard Graphql Errors
+        ServiceError::DatabaseError(_) => return Err(InternalError(formatted_error).extend()),
+    };
+
+    Ok(SetCustomerShelfLifeResponse::Error(
+        SetCustomerShelfLifeError {
+            error: graphql_error,
+        },
+    ))
+}
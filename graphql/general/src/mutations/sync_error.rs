@@ -0,0 +1,111 @@
+use async_graphql::*;
+use graphql_core::{
+    simple_generic_errors::RecordNotFound,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::DeleteResponse;
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    sync_error::update::UpdateSyncErrorError as ServiceError,
+};
+
+use crate::queries::sync_error::SyncErrorNode;
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum UpdateSyncErrorErrorInterface {
+    RecordNotFound(RecordNotFound),
+}
+
+#[derive(SimpleObject)]
+pub struct UpdateSyncErrorError {
+    pub error: UpdateSyncErrorErrorInterface,
+}
+
+#[derive(Union)]
+pub enum RetrySyncErrorResponse {
+    Error(UpdateSyncErrorError),
+    Response(SyncErrorNode),
+}
+
+#[derive(Union)]
+pub enum DiscardSyncErrorResponse {
+    Error(UpdateSyncErrorError),
+    Response(DeleteResponse),
+}
+
+/// Retries the quarantined record on the next sync, a sync is queued if possible
+pub fn retry_sync_error(ctx: &Context<'_>, id: &str) -> Result<RetrySyncErrorResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let result = match service_provider
+        .sync_error_service
+        .retry_sync_error(&service_context, id)
+    {
+        Ok(sync_error) => {
+            if let Some(sync_sender) = ctx.sync_sender() {
+                sync_sender.send();
+            }
+            RetrySyncErrorResponse::Response(SyncErrorNode::from_domain(sync_error))
+        }
+        Err(error) => RetrySyncErrorResponse::Error(UpdateSyncErrorError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+/// Drops the quarantined record without importing it
+pub fn discard_sync_error(ctx: &Context<'_>, id: &str) -> Result<DiscardSyncErrorResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let result = match service_provider
+        .sync_error_service
+        .discard_sync_error(&service_context, id)
+    {
+        Ok(id) => DiscardSyncErrorResponse::Response(DeleteResponse(id)),
+        Err(error) => DiscardSyncErrorResponse::Error(UpdateSyncErrorError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+fn map_error(error: ServiceError) -> Result<UpdateSyncErrorErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::SyncErrorDoesNotExist => {
+            return Ok(UpdateSyncErrorErrorInterface::RecordNotFound(
+                RecordNotFound {},
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
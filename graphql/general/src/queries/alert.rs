@@ -0,0 +1,355 @@
+use async_graphql::{dataloader::DataLoader, *};
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    generic_filters::{DatetimeFilterInput, EqualFilterStringInput},
+    loader::ItemLoader,
+    map_filter,
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::ItemNode;
+use repository::{
+    Alert, AlertConfigRow, AlertFilter, AlertSort, AlertSortField, AlertStatus, AlertType,
+    DatetimeFilter, EqualFilter, PaginationOption,
+};
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    ListResult,
+};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AlertNodeType {
+    /// Item has less than the minimum months of stock
+    LowStock,
+    /// Item in use has no available stock
+    StockOut,
+    /// Batch is expired or expires soon
+    ExpiringStock,
+    /// Inbound shipment was shipped but not delivered in time
+    InboundShipmentNotReceived,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AlertNodeStatus {
+    Active,
+    Acknowledged,
+    Dismissed,
+}
+
+pub struct AlertNode {
+    alert: Alert,
+}
+
+#[derive(SimpleObject)]
+pub struct AlertConnector {
+    total_count: u32,
+    nodes: Vec<AlertNode>,
+}
+
+#[Object]
+impl AlertNode {
+    pub async fn id(&self) -> &str {
+        &self.alert.id
+    }
+
+    pub async fn r#type(&self) -> AlertNodeType {
+        AlertNodeType::from_domain(&self.alert.r#type)
+    }
+
+    pub async fn status(&self) -> AlertNodeStatus {
+        AlertNodeStatus::from_domain(&self.alert.status)
+    }
+
+    pub async fn message(&self) -> &str {
+        &self.alert.message
+    }
+
+    /// Set for low stock, stock out and expiring stock alerts
+    pub async fn item_id(&self) -> &Option<String> {
+        &self.alert.item_id
+    }
+
+    /// Set for expiring stock alerts
+    pub async fn stock_line_id(&self) -> &Option<String> {
+        &self.alert.stock_line_id
+    }
+
+    /// Set for inbound shipment not received alerts
+    pub async fn invoice_id(&self) -> &Option<String> {
+        &self.alert.invoice_id
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(self.alert.created_datetime, Utc)
+    }
+
+    /// User that last acknowledged or dismissed the alert
+    pub async fn user_id(&self) -> &Option<String> {
+        &self.alert.user_id
+    }
+
+    pub async fn acknowledged_datetime(&self) -> Option<DateTime<Utc>> {
+        self.alert
+            .acknowledged_datetime
+            .map(|datetime| DateTime::<Utc>::from_utc(datetime, Utc))
+    }
+
+    pub async fn dismissed_datetime(&self) -> Option<DateTime<Utc>> {
+        self.alert
+            .dismissed_datetime
+            .map(|datetime| DateTime::<Utc>::from_utc(datetime, Utc))
+    }
+
+    /// Set once the rule of the alert no longer applies
+    pub async fn resolved_datetime(&self) -> Option<DateTime<Utc>> {
+        self.alert
+            .resolved_datetime
+            .map(|datetime| DateTime::<Utc>::from_utc(datetime, Utc))
+    }
+
+    pub async fn item(&self, ctx: &Context<'_>) -> Result<Option<ItemNode>> {
+        let item_id = match &self.alert.item_id {
+            Some(item_id) => item_id,
+            None => return Ok(None),
+        };
+        let loader = ctx.get_loader::<DataLoader<ItemLoader>>();
+        Ok(loader
+            .load_one(item_id.clone())
+            .await?
+            .map(ItemNode::from_domain))
+    }
+}
+
+impl AlertNode {
+    pub fn from_domain(alert: Alert) -> AlertNode {
+        AlertNode { alert }
+    }
+}
+
+impl AlertConnector {
+    pub fn from_domain(from: ListResult<Alert>) -> AlertConnector {
+        AlertConnector {
+            total_count: from.count,
+            nodes: from.rows.into_iter().map(AlertNode::from_domain).collect(),
+        }
+    }
+}
+
+impl AlertNodeType {
+    pub fn from_domain(from: &AlertType) -> AlertNodeType {
+        use AlertNodeType as to;
+        use AlertType as from;
+        match from {
+            from::LowStock => to::LowStock,
+            from::StockOut => to::StockOut,
+            from::ExpiringStock => to::ExpiringStock,
+            from::InboundShipmentNotReceived => to::InboundShipmentNotReceived,
+        }
+    }
+
+    pub fn to_domain(self) -> AlertType {
+        use AlertNodeType as from;
+        use AlertType as to;
+        match self {
+            from::LowStock => to::LowStock,
+            from::StockOut => to::StockOut,
+            from::ExpiringStock => to::ExpiringStock,
+            from::InboundShipmentNotReceived => to::InboundShipmentNotReceived,
+        }
+    }
+}
+
+impl AlertNodeStatus {
+    pub fn from_domain(from: &AlertStatus) -> AlertNodeStatus {
+        use AlertNodeStatus as to;
+        use AlertStatus as from;
+        match from {
+            from::Active => to::Active,
+            from::Acknowledged => to::Acknowledged,
+            from::Dismissed => to::Dismissed,
+        }
+    }
+
+    pub fn to_domain(self) -> AlertStatus {
+        use AlertNodeStatus as from;
+        use AlertStatus as to;
+        match self {
+            from::Active => to::Active,
+            from::Acknowledged => to::Acknowledged,
+            from::Dismissed => to::Dismissed,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(rename_items = "camelCase")]
+pub enum AlertSortFieldInput {
+    CreatedDatetime,
+    Type,
+    Status,
+}
+
+#[derive(InputObject)]
+pub struct AlertSortInput {
+    /// Sort query result by `key`
+    key: AlertSortFieldInput,
+    /// Sort query result is sorted descending or ascending (if not provided the default is
+    /// ascending)
+    desc: Option<bool>,
+}
+
+impl AlertSortInput {
+    pub fn to_domain(self) -> AlertSort {
+        use AlertSortField as to;
+        use AlertSortFieldInput as from;
+        let key = match self.key {
+            from::CreatedDatetime => to::CreatedDatetime,
+            from::Type => to::Type,
+            from::Status => to::Status,
+        };
+
+        AlertSort {
+            key,
+            desc: self.desc,
+        }
+    }
+}
+
+#[derive(InputObject, Clone)]
+pub struct EqualFilterAlertTypeInput {
+    pub equal_to: Option<AlertNodeType>,
+    pub equal_any: Option<Vec<AlertNodeType>>,
+    pub not_equal_to: Option<AlertNodeType>,
+}
+
+#[derive(InputObject, Clone)]
+pub struct EqualFilterAlertStatusInput {
+    pub equal_to: Option<AlertNodeStatus>,
+    pub equal_any: Option<Vec<AlertNodeStatus>>,
+    pub not_equal_to: Option<AlertNodeStatus>,
+}
+
+#[derive(InputObject, Clone)]
+pub struct AlertFilterInput {
+    pub id: Option<EqualFilterStringInput>,
+    pub r#type: Option<EqualFilterAlertTypeInput>,
+    pub status: Option<EqualFilterAlertStatusInput>,
+    pub item_id: Option<EqualFilterStringInput>,
+    pub created_datetime: Option<DatetimeFilterInput>,
+    /// Only alerts whose rule no longer applies (or still applies)
+    pub is_resolved: Option<bool>,
+}
+
+impl AlertFilterInput {
+    pub fn to_domain(self) -> AlertFilter {
+        AlertFilter {
+            id: self.id.map(EqualFilter::from),
+            // Always set to the store of the query by the service
+            store_id: None,
+            r#type: self
+                .r#type
+                .map(|t| map_filter!(t, AlertNodeType::to_domain)),
+            status: self
+                .status
+                .map(|t| map_filter!(t, AlertNodeStatus::to_domain)),
+            item_id: self.item_id.map(EqualFilter::from),
+            created_datetime: self.created_datetime.map(DatetimeFilter::from),
+            is_resolved: self.is_resolved,
+        }
+    }
+}
+
+#[derive(Union)]
+pub enum AlertsResponse {
+    Response(AlertConnector),
+}
+
+pub fn alerts(
+    ctx: &Context<'_>,
+    store_id: String,
+    page: Option<PaginationInput>,
+    filter: Option<AlertFilterInput>,
+    sort: Option<Vec<AlertSortInput>>,
+) -> Result<AlertsResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryAlert,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let alerts = service_provider
+        .alert_service
+        .get_alerts(
+            &service_context,
+            &store_id,
+            page.map(PaginationOption::from),
+            filter.map(|filter| filter.to_domain()),
+            // Currently only one sort option is supported, use the first from the list.
+            sort.and_then(|mut sort_list| sort_list.pop())
+                .map(|sort| sort.to_domain()),
+        )
+        .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(AlertsResponse::Response(AlertConnector::from_domain(
+        alerts,
+    )))
+}
+
+pub struct AlertConfigNode {
+    config: AlertConfigRow,
+}
+
+#[Object]
+impl AlertConfigNode {
+    /// Alert rules are evaluated periodically if enabled, they can always be evaluated on demand
+    pub async fn is_enabled(&self) -> bool {
+        self.config.is_enabled
+    }
+
+    /// Items with less stock than this are low on stock
+    pub async fn min_months_of_stock(&self) -> f64 {
+        self.config.min_months_of_stock
+    }
+
+    /// Batches expiring within this number of days are expiring stock
+    pub async fn expiry_warning_days(&self) -> i32 {
+        self.config.expiry_warning_days
+    }
+
+    /// Shipped inbound shipments not delivered after this number of days are overdue
+    pub async fn inbound_shipment_overdue_days(&self) -> i32 {
+        self.config.inbound_shipment_overdue_days
+    }
+}
+
+impl AlertConfigNode {
+    pub fn from_domain(config: AlertConfigRow) -> AlertConfigNode {
+        AlertConfigNode { config }
+    }
+}
+
+pub fn alert_config(ctx: &Context<'_>, store_id: String) -> Result<AlertConfigNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryAlert,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let config = service_provider
+        .alert_service
+        .get_alert_config(&service_context, &store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(AlertConfigNode::from_domain(config))
+}
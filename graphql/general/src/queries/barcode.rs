@@ -0,0 +1,138 @@
+use async_graphql::*;
+use chrono::NaiveDate;
+use graphql_core::{
+    simple_generic_errors::RecordNotFound,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{ItemNode, StockLineConnector};
+use service::{
+    barcode::query::{GetItemByBarcodeError, ItemByBarcode},
+    permission_validation::{Resource, ResourceAccessRequest},
+};
+
+type ServiceError = GetItemByBarcodeError;
+
+pub struct InvalidBarcode(pub String);
+#[Object]
+impl InvalidBarcode {
+    pub async fn description(&self) -> &'static str {
+        "Barcode could not be parsed"
+    }
+
+    pub async fn reason(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum ItemByBarcodeErrorInterface {
+    InvalidBarcode(InvalidBarcode),
+    RecordNotFound(RecordNotFound),
+}
+
+#[derive(SimpleObject)]
+pub struct ItemByBarcodeError {
+    pub error: ItemByBarcodeErrorInterface,
+}
+
+#[derive(Union)]
+pub enum ItemByBarcodeResponse {
+    Response(ItemByBarcodeNode),
+    Error(ItemByBarcodeError),
+}
+
+pub struct ItemByBarcodeNode {
+    item_by_barcode: ItemByBarcode,
+}
+
+#[Object]
+impl ItemByBarcodeNode {
+    /// Scanned GTIN normalised to 14 digits
+    pub async fn gtin(&self) -> &str {
+        &self.item_by_barcode.scanned.gtin
+    }
+
+    pub async fn batch(&self) -> &Option<String> {
+        &self.item_by_barcode.scanned.batch
+    }
+
+    pub async fn expiry_date(&self) -> &Option<NaiveDate> {
+        &self.item_by_barcode.scanned.expiry_date
+    }
+
+    pub async fn serial_number(&self) -> &Option<String> {
+        &self.item_by_barcode.scanned.serial_number
+    }
+
+    pub async fn pack_size(&self) -> &Option<i32> {
+        &self.item_by_barcode.barcode.pack_size
+    }
+
+    pub async fn manufacturer_id(&self) -> &Option<String> {
+        &self.item_by_barcode.barcode.manufacturer_id
+    }
+
+    pub async fn item(&self) -> ItemNode {
+        ItemNode::from_domain(self.item_by_barcode.item.clone())
+    }
+
+    /// Stock lines of the item in the store matching the scanned batch and expiry date
+    pub async fn stock_lines(&self) -> StockLineConnector {
+        StockLineConnector::from_vec(self.item_by_barcode.stock_lines.clone())
+    }
+}
+
+pub fn item_by_barcode(
+    ctx: &Context<'_>,
+    store_id: &str,
+    barcode: &str,
+) -> Result<ItemByBarcodeResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryItemByBarcode,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let result = match service_provider.barcode_service.get_item_by_barcode(
+        &service_context,
+        store_id,
+        barcode,
+    ) {
+        Ok(item_by_barcode) => {
+            ItemByBarcodeResponse::Response(ItemByBarcodeNode { item_by_barcode })
+        }
+        Err(error) => ItemByBarcodeResponse::Error(ItemByBarcodeError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+fn map_error(error: ServiceError) -> Result<ItemByBarcodeErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::InvalidBarcode(parse_error) => {
+            return Ok(ItemByBarcodeErrorInterface::InvalidBarcode(InvalidBarcode(
+                format!("{:?}", parse_error),
+            )))
+        }
+        ServiceError::BarcodeNotFound => {
+            return Ok(ItemByBarcodeErrorInterface::RecordNotFound(RecordNotFound))
+        }
+        // Standard Graphql Errors
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
@@ -0,0 +1,106 @@
+use async_graphql::*;
+use graphql_core::{
+    generic_filters::EqualFilterStringInput,
+    map_filter,
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{InventoryAdjustmentNodeType, InventoryAdjustmentReasonConnector};
+use repository::{
+    EqualFilter, InventoryAdjustmentReasonFilter, InventoryAdjustmentReasonSort, PaginationOption,
+};
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(remote = "repository::InventoryAdjustmentReasonSortField")]
+#[graphql(rename_items = "camelCase")]
+pub enum InventoryAdjustmentReasonSortFieldInput {
+    Id,
+    Reason,
+}
+
+#[derive(InputObject)]
+pub struct InventoryAdjustmentReasonSortInput {
+    /// Sort query result by `key`
+    key: InventoryAdjustmentReasonSortFieldInput,
+    /// Sort query result is sorted descending or ascending (if not provided the default is
+    /// ascending)
+    desc: Option<bool>,
+}
+
+impl InventoryAdjustmentReasonSortInput {
+    pub fn to_domain(self) -> InventoryAdjustmentReasonSort {
+        InventoryAdjustmentReasonSort {
+            // From trait is auto implemented by graphql(remote) in InventoryAdjustmentReasonSortFieldInput
+            key: self.key.into(),
+            desc: self.desc,
+        }
+    }
+}
+
+#[derive(InputObject, Clone)]
+pub struct EqualFilterInventoryAdjustmentTypeInput {
+    pub equal_to: Option<InventoryAdjustmentNodeType>,
+    pub equal_any: Option<Vec<InventoryAdjustmentNodeType>>,
+    pub not_equal_to: Option<InventoryAdjustmentNodeType>,
+}
+
+#[derive(InputObject, Clone)]
+pub struct InventoryAdjustmentReasonFilterInput {
+    pub id: Option<EqualFilterStringInput>,
+    pub r#type: Option<EqualFilterInventoryAdjustmentTypeInput>,
+    pub is_active: Option<bool>,
+}
+
+impl InventoryAdjustmentReasonFilterInput {
+    pub fn to_domain(self) -> InventoryAdjustmentReasonFilter {
+        InventoryAdjustmentReasonFilter {
+            id: self.id.map(EqualFilter::from),
+            r#type: self
+                .r#type
+                .map(|t| map_filter!(t, InventoryAdjustmentNodeType::to_domain)),
+            is_active: self.is_active,
+        }
+    }
+}
+
+#[derive(Union)]
+pub enum InventoryAdjustmentReasonsResponse {
+    Response(InventoryAdjustmentReasonConnector),
+}
+
+pub fn inventory_adjustment_reasons(
+    ctx: &Context<'_>,
+    store_id: String,
+    page: Option<PaginationInput>,
+    filter: Option<InventoryAdjustmentReasonFilterInput>,
+    sort: Option<Vec<InventoryAdjustmentReasonSortInput>>,
+) -> Result<InventoryAdjustmentReasonsResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryInventoryAdjustmentReasons,
+            store_id: Some(store_id),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let reasons = service_provider
+        .inventory_adjustment_reason_service
+        .get_inventory_adjustment_reasons(
+            &service_context,
+            page.map(PaginationOption::from),
+            filter.map(|filter| filter.to_domain()),
+            // Currently only one sort option is supported, use the first from the list.
+            sort.and_then(|mut sort_list| sort_list.pop())
+                .map(|sort| sort.to_domain()),
+        )
+        .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(InventoryAdjustmentReasonsResponse::Response(
+        InventoryAdjustmentReasonConnector::from_domain(reasons),
+    ))
+}
@@ -116,6 +116,13 @@ pub fn logout(ctx: &Context<'_>) -> LogoutResponse {
                     )),
                 });
             }
+            service::token::JWTLogoutError::DatabaseError(_) => {
+                return LogoutResponse::Error(LogoutError {
+                    error: LogoutErrorInterface::InternalError(InternalError(
+                        "Database error".to_string(),
+                    )),
+                });
+            }
         },
     };
 
@@ -8,18 +8,29 @@ pub mod refresh_token;
 pub use self::refresh_token::*;
 pub mod master_list;
 pub use self::master_list::*;
+pub mod inventory_adjustment_reason;
+pub use self::inventory_adjustment_reason::*;
 pub mod invoice_counts;
 pub use self::invoice_counts::*;
 pub mod names;
 pub use self::names::*;
 pub mod item;
 pub use self::item::*;
+pub mod barcode;
+pub use self::barcode::*;
 pub mod stock_counts;
 pub use self::stock_counts::*;
 pub mod store;
 pub use self::store::*;
+pub mod store_preference;
+pub use self::store_preference::*;
+pub mod sessions;
+pub use self::sessions::*;
+pub mod alert;
 pub mod requisition_line_chart;
 pub mod server_settings;
+pub mod sync_error;
+pub mod sync_status;
 
 #[cfg(test)]
 mod tests;
@@ -1,11 +1,12 @@
 use async_graphql::{Context, Enum, InputObject, Result, SimpleObject, Union};
 use graphql_core::{
     generic_filters::{EqualFilterStringInput, SimpleStringFilterInput},
+    map_filter,
     pagination::PaginationInput,
     standard_graphql_error::{validate_auth, StandardGraphqlError},
     ContextExt,
 };
-use graphql_types::types::NameNode;
+use graphql_types::types::{NameNode, NameNodeType};
 use repository::{EqualFilter, PaginationOption, SimpleStringFilter};
 use repository::{Name, NameFilter, NameSort, NameSortField};
 use service::{
@@ -29,6 +30,13 @@ pub struct NameSortInput {
     desc: Option<bool>,
 }
 
+#[derive(InputObject, Clone)]
+pub struct EqualFilterNameTypeInput {
+    pub equal_to: Option<NameNodeType>,
+    pub equal_any: Option<Vec<NameNodeType>>,
+    pub not_equal_to: Option<NameNodeType>,
+}
+
 #[derive(InputObject, Clone)]
 pub struct NameFilterInput {
     pub id: Option<EqualFilterStringInput>,
@@ -50,6 +58,8 @@ pub struct NameFilterInput {
     /// System names don't have name_store_join thus if queried with true filter, is_visible filter should also be true or null
     /// if is_visible is set to true and is_system_name is also true no system names will be returned
     pub is_system_name: Option<bool>,
+    /// Filter by the kind of name, e.g. patients
+    pub r#type: Option<EqualFilterNameTypeInput>,
 }
 
 #[derive(SimpleObject)]
@@ -110,6 +120,7 @@ impl NameFilterInput {
             store_code,
             is_visible,
             is_system_name,
+            r#type,
         } = self;
 
         NameFilter {
@@ -122,6 +133,10 @@ impl NameFilterInput {
             is_store,
             is_visible,
             is_system_name: is_system_name.or(Some(false)),
+            r#type: r#type.map(|t| map_filter!(t, NameNodeType::to_domain)),
+            search: None,
+            date_of_birth: None,
+            national_health_number: None,
         }
     }
 }
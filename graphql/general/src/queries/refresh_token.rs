@@ -121,6 +121,9 @@ pub fn refresh_token(ctx: &Context<'_>) -> RefreshTokenResponse {
                             "Lock error".to_string(),
                         ))
                     }
+                    JWTRefreshError::DatabaseError(err) => {
+                        RefreshTokenErrorInterface::DatabaseError(DatabaseError(err))
+                    }
                 },
             })
         }
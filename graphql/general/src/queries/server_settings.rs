@@ -52,6 +52,21 @@ impl SyncSettingsNode {
     pub async fn site_hardware_id(&self) -> String {
         self.settings.site_hardware_id.clone()
     }
+
+    /// Upper limit of the delay between retries after consecutive sync failures
+    pub async fn max_backoff_sec(&self) -> u64 {
+        self.settings.max_backoff_sec
+    }
+
+    /// Delay before retrying after a partial sync failure
+    pub async fn partial_failure_retry_sec(&self) -> u64 {
+        self.settings.partial_failure_retry_sec
+    }
+
+    /// Maximum random delay added to scheduled syncs
+    pub async fn max_jitter_sec(&self) -> u64 {
+        self.settings.max_jitter_sec
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,144 @@
+use async_graphql::*;
+use chrono::{DateTime, TimeZone, Utc};
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    token::{JWTSessionError, TokenService},
+    token_bucket::SessionInfo,
+};
+
+pub struct UserSessionNode {
+    session: SessionInfo,
+    is_current: bool,
+}
+
+#[Object]
+impl UserSessionNode {
+    pub async fn id(&self) -> &str {
+        &self.session.session_id
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        Utc.timestamp(self.session.created_date as i64, 0)
+    }
+
+    pub async fn last_refreshed_datetime(&self) -> DateTime<Utc> {
+        Utc.timestamp(self.session.last_refreshed_date as i64, 0)
+    }
+
+    pub async fn expiry_datetime(&self) -> DateTime<Utc> {
+        Utc.timestamp(self.session.expiry_date as i64, 0)
+    }
+
+    /// Session the current request has been made from
+    pub async fn is_current(&self) -> bool {
+        self.is_current
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct UserSessionConnector {
+    total_count: u32,
+    nodes: Vec<UserSessionNode>,
+}
+
+#[derive(Union)]
+pub enum UserSessionsResponse {
+    Response(UserSessionConnector),
+}
+
+pub struct RevokeSessionNode {
+    session_id: String,
+}
+
+#[Object]
+impl RevokeSessionNode {
+    pub async fn id(&self) -> &str {
+        &self.session_id
+    }
+}
+
+#[derive(Union)]
+pub enum RevokeSessionResponse {
+    Response(RevokeSessionNode),
+}
+
+/// Lists the active sessions of the logged in user
+pub fn sessions(ctx: &Context<'_>) -> Result<UserSessionsResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::RouteMe,
+            store_id: None,
+        },
+    )?;
+
+    let auth_data = ctx.get_auth_data();
+    let service = TokenService::new(
+        &auth_data.token_bucket,
+        auth_data.auth_token_secret.as_bytes(),
+    );
+    let current_session_id = match ctx.get_auth_token() {
+        Some(token) => service
+            .session_id(&user.user_id, &token)
+            .map_err(map_session_error)?,
+        None => None,
+    };
+    let sessions = service.sessions(&user.user_id).map_err(map_session_error)?;
+
+    Ok(UserSessionsResponse::Response(UserSessionConnector {
+        total_count: sessions.len() as u32,
+        nodes: sessions
+            .into_iter()
+            .map(|session| UserSessionNode {
+                is_current: Some(&session.session_id) == current_session_id.as_ref(),
+                session,
+            })
+            .collect(),
+    }))
+}
+
+/// Logs the logged in user out of one of its sessions
+pub fn revoke_session(ctx: &Context<'_>, session_id: String) -> Result<RevokeSessionResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::RouteMe,
+            store_id: None,
+        },
+    )?;
+
+    let auth_data = ctx.get_auth_data();
+    let mut service = TokenService::new(
+        &auth_data.token_bucket,
+        auth_data.auth_token_secret.as_bytes(),
+    );
+    let revoked = service
+        .revoke_session(&user.user_id, &session_id)
+        .map_err(map_session_error)?;
+    if !revoked {
+        return Err(StandardGraphqlError::BadUserInput(format!(
+            "Session does not exist: {}",
+            session_id
+        ))
+        .extend());
+    }
+
+    Ok(RevokeSessionResponse::Response(RevokeSessionNode {
+        session_id,
+    }))
+}
+
+fn map_session_error(error: JWTSessionError) -> async_graphql::Error {
+    let formatted_error = format!("{:#?}", error);
+    let graphql_error = match error {
+        JWTSessionError::ConcurrencyLockError(_) => {
+            StandardGraphqlError::InternalError(formatted_error)
+        }
+        JWTSessionError::DatabaseError(_) => StandardGraphqlError::InternalError(formatted_error),
+    };
+    graphql_error.extend()
+}
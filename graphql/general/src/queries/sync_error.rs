@@ -0,0 +1,129 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use repository::{PaginationOption, SyncErrorRow, SyncErrorSource};
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    ListResult,
+};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SyncErrorNodeSource {
+    /// Central data, e.g. items and names
+    Central,
+    /// Remote data and messages, e.g. invoices and requisitions
+    Remote,
+}
+
+pub struct SyncErrorNode {
+    row: SyncErrorRow,
+}
+
+#[derive(SimpleObject)]
+pub struct SyncErrorConnector {
+    total_count: u32,
+    nodes: Vec<SyncErrorNode>,
+}
+
+#[Object]
+impl SyncErrorNode {
+    pub async fn id(&self) -> &str {
+        &self.row.id
+    }
+
+    pub async fn source(&self) -> SyncErrorNodeSource {
+        SyncErrorNodeSource::from_domain(&self.row.source)
+    }
+
+    /// Table name of the record on the central server
+    pub async fn table_name(&self) -> &str {
+        &self.row.table_name
+    }
+
+    pub async fn record_id(&self) -> &str {
+        &self.row.record_id
+    }
+
+    /// Raw JSON of the sync record
+    pub async fn data(&self) -> &str {
+        &self.row.data
+    }
+
+    /// Error of the latest attempt
+    pub async fn error_message(&self) -> &str {
+        &self.row.error_message
+    }
+
+    pub async fn attempt_count(&self) -> i32 {
+        self.row.attempt_count
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(self.row.created_datetime, Utc)
+    }
+
+    pub async fn last_attempt_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(self.row.last_attempt_datetime, Utc)
+    }
+}
+
+impl SyncErrorNode {
+    pub fn from_domain(row: SyncErrorRow) -> SyncErrorNode {
+        SyncErrorNode { row }
+    }
+}
+
+impl SyncErrorConnector {
+    pub fn from_domain(from: ListResult<SyncErrorRow>) -> SyncErrorConnector {
+        SyncErrorConnector {
+            total_count: from.count,
+            nodes: from
+                .rows
+                .into_iter()
+                .map(SyncErrorNode::from_domain)
+                .collect(),
+        }
+    }
+}
+
+impl SyncErrorNodeSource {
+    pub fn from_domain(from: &SyncErrorSource) -> SyncErrorNodeSource {
+        use SyncErrorNodeSource as to;
+        use SyncErrorSource as from;
+        match from {
+            from::Central => to::Central,
+            from::Remote => to::Remote,
+        }
+    }
+}
+
+#[derive(Union)]
+pub enum SyncErrorsResponse {
+    Response(SyncErrorConnector),
+}
+
+pub fn sync_errors(ctx: &Context<'_>, page: Option<PaginationInput>) -> Result<SyncErrorsResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let sync_errors = service_provider
+        .sync_error_service
+        .get_sync_errors(&service_context, page.map(PaginationOption::from))
+        .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(SyncErrorsResponse::Response(
+        SyncErrorConnector::from_domain(sync_errors),
+    ))
+}
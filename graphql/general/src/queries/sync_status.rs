@@ -0,0 +1,278 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use repository::{PaginationOption, SyncLogPhase, SyncLogRow};
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    sync_status::{
+        initial_sync::InitialSyncSourceProgress,
+        query::{InitialSyncStatus, SyncStatus},
+    },
+    ListResult,
+};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum SyncPhaseNode {
+    /// Pushing local changes to the central server
+    Push,
+    /// Pulling remote data and messages
+    RemotePull,
+    /// Pulling central data
+    CentralPull,
+    /// Integrating the pulled records
+    Integrate,
+}
+
+pub struct SyncLogNode {
+    row: SyncLogRow,
+}
+
+#[derive(SimpleObject)]
+pub struct SyncLogConnector {
+    total_count: u32,
+    nodes: Vec<SyncLogNode>,
+}
+
+#[Object]
+impl SyncLogNode {
+    pub async fn id(&self) -> &str {
+        &self.row.id
+    }
+
+    pub async fn started_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(self.row.started_datetime, Utc)
+    }
+
+    /// Null while the sync is still running
+    pub async fn finished_datetime(&self) -> Option<DateTime<Utc>> {
+        self.row
+            .finished_datetime
+            .map(|datetime| DateTime::<Utc>::from_utc(datetime, Utc))
+    }
+
+    /// Current phase, or the phase the sync was in when it finished or failed
+    pub async fn phase(&self) -> SyncPhaseNode {
+        SyncPhaseNode::from_domain(&self.row.phase)
+    }
+
+    pub async fn push_record_count(&self) -> i32 {
+        self.row.push_record_count
+    }
+
+    pub async fn remote_pull_record_count(&self) -> i32 {
+        self.row.remote_pull_record_count
+    }
+
+    pub async fn central_pull_record_count(&self) -> i32 {
+        self.row.central_pull_record_count
+    }
+
+    pub async fn integration_record_count(&self) -> i32 {
+        self.row.integration_record_count
+    }
+
+    pub async fn error_message(&self) -> &Option<String> {
+        &self.row.error_message
+    }
+}
+
+pub struct SyncStatusNode {
+    status: SyncStatus,
+}
+
+#[Object]
+impl SyncStatusNode {
+    pub async fn is_syncing(&self) -> bool {
+        self.status.is_syncing
+    }
+
+    /// Most recent sync, null if the server never synced
+    pub async fn latest(&self) -> Option<SyncLogNode> {
+        self.status.latest.clone().map(SyncLogNode::from_domain)
+    }
+
+    /// Most recent sync that finished without an error
+    pub async fn latest_successful(&self) -> Option<SyncLogNode> {
+        self.status
+            .latest_successful
+            .clone()
+            .map(SyncLogNode::from_domain)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct InitialSyncTableProgressNode {
+    table_name: String,
+    pulled: u32,
+    integrated: u32,
+}
+
+pub struct InitialSyncSourceProgressNode {
+    progress: InitialSyncSourceProgress,
+}
+
+#[Object]
+impl InitialSyncSourceProgressNode {
+    /// Total number of records to pull, as far as known from the central server
+    pub async fn queue_length(&self) -> u32 {
+        self.progress.queue_length
+    }
+
+    pub async fn pulled(&self) -> u32 {
+        self.progress.pulled()
+    }
+
+    pub async fn integrated(&self) -> u32 {
+        self.progress.integrated()
+    }
+
+    pub async fn tables(&self) -> Vec<InitialSyncTableProgressNode> {
+        self.progress
+            .tables
+            .iter()
+            .map(|(table_name, progress)| InitialSyncTableProgressNode {
+                table_name: table_name.clone(),
+                pulled: progress.pulled,
+                integrated: progress.integrated,
+            })
+            .collect()
+    }
+}
+
+pub struct InitialSyncStatusNode {
+    status: InitialSyncStatus,
+}
+
+#[Object]
+impl InitialSyncStatusNode {
+    pub async fn is_finished(&self) -> bool {
+        self.status.is_finished
+    }
+
+    /// Central data, pulled on every server start
+    pub async fn central(&self) -> InitialSyncSourceProgressNode {
+        InitialSyncSourceProgressNode {
+            progress: self.status.progress.central.clone(),
+        }
+    }
+
+    /// Remote data belonging to this site
+    pub async fn remote(&self) -> InitialSyncSourceProgressNode {
+        InitialSyncSourceProgressNode {
+            progress: self.status.progress.remote.clone(),
+        }
+    }
+
+    /// Error of the latest attempt, the initial sync is resumed after the next server restart
+    pub async fn error_message(&self) -> &Option<String> {
+        &self.status.progress.error_message
+    }
+}
+
+impl SyncLogNode {
+    pub fn from_domain(row: SyncLogRow) -> SyncLogNode {
+        SyncLogNode { row }
+    }
+}
+
+impl SyncLogConnector {
+    pub fn from_domain(from: ListResult<SyncLogRow>) -> SyncLogConnector {
+        SyncLogConnector {
+            total_count: from.count,
+            nodes: from
+                .rows
+                .into_iter()
+                .map(SyncLogNode::from_domain)
+                .collect(),
+        }
+    }
+}
+
+impl SyncPhaseNode {
+    pub fn from_domain(from: &SyncLogPhase) -> SyncPhaseNode {
+        use SyncLogPhase as from;
+        use SyncPhaseNode as to;
+        match from {
+            from::Push => to::Push,
+            from::RemotePull => to::RemotePull,
+            from::CentralPull => to::CentralPull,
+            from::Integrate => to::Integrate,
+        }
+    }
+}
+
+#[derive(Union)]
+pub enum SyncHistoryResponse {
+    Response(SyncLogConnector),
+}
+
+pub fn sync_status(ctx: &Context<'_>) -> Result<SyncStatusNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let status = service_provider
+        .sync_status_service
+        .get_sync_status(&service_context)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(SyncStatusNode { status })
+}
+
+pub fn sync_history(
+    ctx: &Context<'_>,
+    page: Option<PaginationInput>,
+) -> Result<SyncHistoryResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let logs = service_provider
+        .sync_status_service
+        .get_sync_logs(&service_context, page.map(PaginationOption::from))
+        .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(SyncHistoryResponse::Response(
+        SyncLogConnector::from_domain(logs),
+    ))
+}
+
+pub fn initial_sync_status(ctx: &Context<'_>, stage0: bool) -> Result<InitialSyncStatusNode> {
+    if !stage0 {
+        validate_auth(
+            ctx,
+            &ResourceAccessRequest {
+                resource: Resource::ServerAdmin,
+                store_id: None,
+            },
+        )?;
+    }
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let status = service_provider
+        .sync_status_service
+        .get_initial_sync_status(&service_context)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(InitialSyncStatusNode { status })
+}
@@ -0,0 +1,75 @@
+mod graphql {
+    use async_graphql::EmptyMutation;
+    use graphql_core::{assert_graphql_query, test_helpers::setup_graphl_test};
+    use repository::mock::MockDataInserts;
+    use serde_json::json;
+
+    use crate::GeneralQueries;
+
+    #[actix_rt::test]
+    async fn test_graphql_item_by_barcode() {
+        let (_, _, _, settings) = setup_graphl_test(
+            GeneralQueries,
+            EmptyMutation,
+            "test_graphql_item_by_barcode",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let query = r#"
+        query($barcode: String!) {
+          itemByBarcode(storeId: \"store_a\", barcode: $barcode) {
+            ... on ItemByBarcodeNode {
+              gtin
+              batch
+              item {
+                id
+              }
+              stockLines {
+                nodes {
+                  id
+                }
+              }
+            }
+            ... on ItemByBarcodeError {
+              error {
+                __typename
+              }
+            }
+          }
+        }
+        "#;
+
+        let expected = json!({
+            "itemByBarcode": {
+                "gtin": "00012345678905",
+                "batch": "item_a_batch_b",
+                "item": {
+                    "id": "item_a"
+                }
+            }
+        });
+        let variables = json!({ "barcode": "(01)00012345678905(10)item_a_batch_b" });
+        assert_graphql_query!(&settings, query, &Some(variables), &expected, None);
+
+        let expected = json!({
+            "itemByBarcode": {
+                "error": {
+                    "__typename": "InvalidBarcode"
+                }
+            }
+        });
+        let variables = json!({ "barcode": "not a barcode" });
+        assert_graphql_query!(&settings, query, &Some(variables), &expected, None);
+
+        let expected = json!({
+            "itemByBarcode": {
+                "error": {
+                    "__typename": "RecordNotFound"
+                }
+            }
+        });
+        let variables = json!({ "barcode": "4006381333931" });
+        assert_graphql_query!(&settings, query, &Some(variables), &expected, None);
+    }
+}
@@ -1,3 +1,4 @@
+mod barcode;
 mod item_stats;
 mod items;
 mod master_lists;
@@ -9,7 +9,7 @@ mod graphql {
             mock_name_a, mock_name_linked_to_store, mock_name_not_linked_to_store,
             mock_store_linked_to_name, MockDataInserts,
         },
-        EqualFilter, Name, NameFilter, NameSort, NameSortField, PaginationOption,
+        EqualFilter, Name, NameFilter, NameSort, NameSortField, NameType, PaginationOption,
         SimpleStringFilter, StorageConnectionManager,
     };
     use serde_json::json;
@@ -121,7 +121,10 @@ mod graphql {
               "like": "store code like"
             },
             "isVisible": false,
-            "isSystemName": true
+            "isSystemName": true,
+            "type": {
+              "equalTo": "PATIENT"
+            }
           }
         });
 
@@ -161,6 +164,10 @@ mod graphql {
                 store_code,
                 is_visible,
                 is_system_name,
+                r#type,
+                search: _,
+                date_of_birth: _,
+                national_health_number: _,
             } = filter.unwrap();
 
             assert_eq!(id, Some(EqualFilter::not_equal_to("id_not_equal_to")));
@@ -175,6 +182,7 @@ mod graphql {
             );
             assert_eq!(is_visible, Some(false));
             assert_eq!(is_system_name, Some(true));
+            assert_eq!(r#type, Some(NameType::Patient.equal_to()));
 
             Ok(ListResult {
                 rows: vec![Name {
@@ -5,8 +5,14 @@ use graphql_types::types::*;
 mod invoice_queries;
 use self::invoice_queries::*;
 
+mod stock_ledger_queries;
+use self::stock_ledger_queries::*;
+
 pub mod mutations;
-use self::mutations::{inbound_shipment, outbound_shipment};
+use self::mutations::{
+    customer_return, inbound_shipment, inventory_adjustment, outbound_shipment, prescription,
+    supplier_return,
+};
 
 #[cfg(test)]
 mod query_tests;
@@ -46,6 +52,19 @@ impl InvoiceQueries {
     ) -> Result<InvoicesResponse> {
         get_invoices(ctx, store_id, page, filter, sort)
     }
+
+    /// Lists every stock movement of the store with a running balance per item and stock line
+    pub async fn stock_ledger(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(desc = "Pagination option (first and offset)")] page: Option<PaginationInput>,
+        #[graphql(desc = "Filter option")] filter: Option<StockLedgerFilterInput>,
+        #[graphql(desc = "Sort options (only first sort input is evaluated for this endpoint)")]
+        sort: Option<Vec<StockLedgerSortInput>>,
+    ) -> Result<StockLedgerResponse> {
+        get_stock_ledger(ctx, store_id, page, filter, sort)
+    }
 }
 
 #[derive(Default, Clone)]
@@ -106,4 +125,94 @@ impl InvoiceMutations {
     ) -> Result<inbound_shipment::DeleteResponse> {
         inbound_shipment::delete(ctx, &store_id, input)
     }
+
+    async fn insert_prescription(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: prescription::InsertInput,
+    ) -> Result<prescription::InsertResponse> {
+        prescription::insert(ctx, &store_id, input)
+    }
+
+    async fn update_prescription(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: prescription::UpdateInput,
+    ) -> Result<prescription::UpdateResponse> {
+        prescription::update(ctx, &store_id, input)
+    }
+
+    async fn delete_prescription(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<prescription::DeleteResponse> {
+        prescription::delete(ctx, &store_id, id)
+    }
+
+    async fn insert_customer_return(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: customer_return::InsertInput,
+    ) -> Result<customer_return::InsertResponse> {
+        customer_return::insert(ctx, &store_id, input)
+    }
+
+    async fn update_customer_return(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: customer_return::UpdateInput,
+    ) -> Result<customer_return::UpdateResponse> {
+        customer_return::update(ctx, &store_id, input)
+    }
+
+    async fn delete_customer_return(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<customer_return::DeleteResponse> {
+        customer_return::delete(ctx, &store_id, id)
+    }
+
+    async fn insert_supplier_return(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: supplier_return::InsertInput,
+    ) -> Result<supplier_return::InsertResponse> {
+        supplier_return::insert(ctx, &store_id, input)
+    }
+
+    async fn update_supplier_return(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: supplier_return::UpdateInput,
+    ) -> Result<supplier_return::UpdateResponse> {
+        supplier_return::update(ctx, &store_id, input)
+    }
+
+    async fn delete_supplier_return(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<supplier_return::DeleteResponse> {
+        supplier_return::delete(ctx, &store_id, id)
+    }
+
+    async fn insert_inventory_adjustment(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: inventory_adjustment::InsertInput,
+    ) -> Result<inventory_adjustment::InsertResponse> {
+        inventory_adjustment::insert(ctx, &store_id, input)
+    }
 }
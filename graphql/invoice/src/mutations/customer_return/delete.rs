@@ -0,0 +1,83 @@
+use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
+use graphql_core::{
+    simple_generic_errors::{CannotEditInvoice, RecordNotFound},
+    ContextExt,
+};
+use graphql_types::types::DeleteResponse as GenericDeleteResponse;
+
+use async_graphql::*;
+use service::invoice::customer_return::DeleteCustomerReturnError as ServiceError;
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+#[derive(SimpleObject)]
+#[graphql(name = "DeleteCustomerReturnError")]
+pub struct DeleteError {
+    pub error: DeleteErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "DeleteCustomerReturnResponse")]
+pub enum DeleteResponse {
+    Error(DeleteError),
+    Response(GenericDeleteResponse),
+}
+
+pub fn delete(ctx: &Context<'_>, store_id: &str, id: String) -> Result<DeleteResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateCustomerReturn,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.invoice_service.delete_customer_return(
+        &service_context,
+        store_id,
+        id,
+    ))
+}
+
+pub fn map_response(from: Result<String, ServiceError>) -> Result<DeleteResponse> {
+    let result = match from {
+        Ok(id) => DeleteResponse::Response(GenericDeleteResponse(id)),
+        Err(error) => DeleteResponse::Error(DeleteError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+#[derive(Interface)]
+#[graphql(name = "DeleteCustomerReturnErrorInterface")]
+#[graphql(field(name = "description", type = "&str"))]
+pub enum DeleteErrorInterface {
+    RecordNotFound(RecordNotFound),
+    CannotEditInvoice(CannotEditInvoice),
+}
+
+fn map_error(error: ServiceError) -> Result<DeleteErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::InvoiceDoesNotExist => {
+            return Ok(DeleteErrorInterface::RecordNotFound(RecordNotFound {}))
+        }
+        ServiceError::CannotEditFinalised => {
+            return Ok(DeleteErrorInterface::CannotEditInvoice(
+                CannotEditInvoice {},
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::NotACustomerReturn => BadUserInput(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
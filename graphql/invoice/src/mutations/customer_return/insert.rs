@@ -0,0 +1,287 @@
+use async_graphql::*;
+use graphql_core::simple_generic_errors::NodeError;
+use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
+use graphql_core::ContextExt;
+use graphql_types::types::InvoiceNode;
+use repository::Invoice;
+use service::invoice::customer_return::{
+    InsertCustomerReturn as ServiceInput, InsertCustomerReturnError as ServiceError,
+};
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+use crate::mutations::return_line::{InsertReturnLineInput, ReturnedQuantityExceedsAvailable};
+
+#[derive(InputObject)]
+#[graphql(name = "InsertCustomerReturnInput")]
+pub struct InsertInput {
+    /// The new invoice id provided by the client
+    pub id: String,
+    /// Outbound shipment or prescription the stock is returned from
+    pub original_invoice_id: String,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub colour: Option<String>,
+    pub lines: Vec<InsertReturnLineInput>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "InsertCustomerReturnError")]
+pub struct InsertError {
+    pub error: InsertErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "InsertCustomerReturnResponse")]
+pub enum InsertResponse {
+    Error(InsertError),
+    NodeError(NodeError),
+    Response(InvoiceNode),
+}
+
+pub fn insert(ctx: &Context<'_>, store_id: &str, input: InsertInput) -> Result<InsertResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateCustomerReturn,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.invoice_service.insert_customer_return(
+        &service_context,
+        store_id,
+        &user.user_id,
+        input.to_domain(),
+    ))
+}
+
+pub fn map_response(from: Result<Invoice, ServiceError>) -> Result<InsertResponse> {
+    let result = match from {
+        Ok(invoice) => InsertResponse::Response(InvoiceNode::from_domain(invoice)),
+        Err(error) => InsertResponse::Error(InsertError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl InsertInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let InsertInput {
+            id,
+            original_invoice_id,
+            their_reference,
+            comment,
+            colour,
+            lines,
+        }: InsertInput = self;
+
+        ServiceInput {
+            id,
+            original_invoice_id,
+            their_reference,
+            comment,
+            colour,
+            lines: lines.into_iter().map(|line| line.to_domain()).collect(),
+        }
+    }
+}
+
+#[derive(Interface)]
+#[graphql(name = "InsertCustomerReturnErrorInterface")]
+#[graphql(field(name = "description", type = "&str"))]
+pub enum InsertErrorInterface {
+    ReturnedQuantityExceedsAvailable(ReturnedQuantityExceedsAvailable),
+}
+
+fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::ReturnedQuantityExceedsIssued {
+            line_id,
+            returnable_number_of_packs,
+        } => {
+            return Ok(InsertErrorInterface::ReturnedQuantityExceedsAvailable(
+                ReturnedQuantityExceedsAvailable {
+                    line_id,
+                    max_number_of_packs: returnable_number_of_packs,
+                },
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::InvoiceAlreadyExists => BadUserInput(formatted_error),
+        ServiceError::OriginalInvoiceDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::OriginalInvoiceNotThisStore => BadUserInput(formatted_error),
+        ServiceError::NotAStockOutInvoice => BadUserInput(formatted_error),
+        ServiceError::OriginalInvoiceNotIssued => BadUserInput(formatted_error),
+        ServiceError::LineAlreadyExists(_) => BadUserInput(formatted_error),
+        ServiceError::OriginalLineDoesNotExist(_) => BadUserInput(formatted_error),
+        ServiceError::NumberOfPacksBelowOne(_) => BadUserInput(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+        ServiceError::NewlyCreatedInvoiceDoesNotExist => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+#[cfg(test)]
+mod graphql {
+    use chrono::NaiveDate;
+    use graphql_core::test_helpers::setup_graphl_test_with_data;
+    use graphql_core::{assert_graphql_query, assert_standard_graphql_error};
+    use repository::mock::{mock_name_store_b, mock_stock_line_a, MockData, MockDataInserts};
+    use repository::{
+        InvoiceLineRow, InvoiceLineRowType, InvoiceRow, InvoiceRowStatus, InvoiceRowType,
+    };
+    use serde_json::json;
+    use util::inline_init;
+
+    use crate::{InvoiceMutations, InvoiceQueries};
+
+    #[actix_rt::test]
+    async fn test_graphql_customer_return_insert() {
+        fn outbound() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "return_outbound".to_string();
+                r.name_id = mock_name_store_b().id;
+                r.store_id = "store_a".to_string();
+                r.r#type = InvoiceRowType::OutboundShipment;
+                r.status = InvoiceRowStatus::Shipped;
+                r.picked_datetime = Some(NaiveDate::from_ymd(2022, 1, 1).and_hms(0, 0, 0));
+            })
+        }
+
+        fn outbound_line() -> InvoiceLineRow {
+            inline_init(|r: &mut InvoiceLineRow| {
+                r.id = "return_outbound_line".to_string();
+                r.invoice_id = outbound().id;
+                r.item_id = mock_stock_line_a().item_id;
+                r.stock_line_id = Some(mock_stock_line_a().id);
+                r.r#type = InvoiceLineRowType::StockOut;
+                r.pack_size = 1;
+                r.number_of_packs = 10;
+            })
+        }
+
+        let (_, _, _, settings) = setup_graphl_test_with_data(
+            InvoiceQueries,
+            InvoiceMutations,
+            "omsupply-database-gql-customer_return_insert",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![outbound()];
+                r.invoice_lines = vec![outbound_line()];
+            }),
+        )
+        .await;
+
+        let query = r#"mutation InsertCustomerReturn($input: InsertCustomerReturnInput!) {
+            insertCustomerReturn(input: $input, storeId: \"store_a\") {
+                ... on InsertCustomerReturnError {
+                  error {
+                    __typename
+                    ... on ReturnedQuantityExceedsAvailable {
+                      lineId
+                      maxNumberOfPacks
+                    }
+                  }
+                }
+                ... on InvoiceNode {
+                    id
+                    otherPartyId
+                    type
+                    status
+                    lines {
+                      nodes {
+                        type
+                        numberOfPacks
+                        originalInvoiceLineId
+                        returnReason
+                      }
+                    }
+                }
+            }
+        }"#;
+
+        // ReturnedQuantityExceedsAvailable
+        let variables = Some(json!({
+          "input": {
+            "id": "customer_return_1",
+            "originalInvoiceId": outbound().id,
+            "lines": [{
+              "id": "customer_return_1_line",
+              "originalInvoiceLineId": outbound_line().id,
+              "numberOfPacks": 11
+            }]
+          }
+        }));
+        let expected = json!({
+            "insertCustomerReturn": {
+              "error": {
+                "__typename": "ReturnedQuantityExceedsAvailable",
+                "lineId": "customer_return_1_line",
+                "maxNumberOfPacks": 10
+              }
+            }
+          }
+        );
+        assert_graphql_query!(&settings, query, &variables, &expected, None);
+
+        // OriginalInvoiceDoesNotExist
+        let variables = Some(json!({
+          "input": {
+            "id": "customer_return_1",
+            "originalInvoiceId": "invalid",
+            "lines": []
+          }
+        }));
+        let expected_message = "Bad user input";
+        assert_standard_graphql_error!(
+            &settings,
+            &query,
+            &variables,
+            &expected_message,
+            None,
+            None
+        );
+
+        // Success
+        let variables = Some(json!({
+          "input": {
+            "id": "customer_return_1",
+            "originalInvoiceId": outbound().id,
+            "lines": [{
+              "id": "customer_return_1_line",
+              "originalInvoiceLineId": outbound_line().id,
+              "numberOfPacks": 4,
+              "returnReason": "Damaged"
+            }]
+          }
+        }));
+        let expected = json!({
+            "insertCustomerReturn": {
+              "id": "customer_return_1",
+              "otherPartyId": mock_name_store_b().id,
+              "type": "CUSTOMER_RETURN",
+              "status": "NEW",
+              "lines": {
+                "nodes": [{
+                  "type": "STOCK_IN",
+                  "numberOfPacks": 4,
+                  "originalInvoiceLineId": outbound_line().id,
+                  "returnReason": "Damaged"
+                }]
+              }
+            }
+          }
+        );
+        assert_graphql_query!(&settings, query, &variables, &expected, None);
+    }
+}
@@ -0,0 +1,171 @@
+use async_graphql::*;
+use graphql_core::simple_generic_errors::NodeError;
+use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
+use graphql_core::ContextExt;
+use graphql_types::types::{InventoryAdjustmentNodeType, InvoiceNode};
+use repository::Invoice;
+use service::invoice::inventory_adjustment::{
+    InsertInventoryAdjustment as ServiceInput, InsertInventoryAdjustmentError as ServiceError,
+};
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+#[derive(InputObject)]
+#[graphql(name = "InsertInventoryAdjustmentInput")]
+pub struct InsertInput {
+    /// The new invoice id provided by the client
+    pub id: String,
+    pub stock_line_id: String,
+    pub r#type: InventoryAdjustmentNodeType,
+    pub number_of_packs: u32,
+    pub inventory_adjustment_reason_id: Option<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "InsertInventoryAdjustmentError")]
+pub struct InsertError {
+    pub error: InsertErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "InsertInventoryAdjustmentResponse")]
+pub enum InsertResponse {
+    Error(InsertError),
+    NodeError(NodeError),
+    Response(InvoiceNode),
+}
+
+pub fn insert(ctx: &Context<'_>, store_id: &str, input: InsertInput) -> Result<InsertResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateInventoryAdjustment,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(
+        service_provider
+            .invoice_service
+            .insert_inventory_adjustment(
+                &service_context,
+                store_id,
+                &user.user_id,
+                input.to_domain(),
+            ),
+    )
+}
+
+pub fn map_response(from: Result<Invoice, ServiceError>) -> Result<InsertResponse> {
+    let result = match from {
+        Ok(invoice) => InsertResponse::Response(InvoiceNode::from_domain(invoice)),
+        Err(error) => InsertResponse::Error(InsertError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl InsertInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let InsertInput {
+            id,
+            stock_line_id,
+            r#type,
+            number_of_packs,
+            inventory_adjustment_reason_id,
+            comment,
+        }: InsertInput = self;
+
+        ServiceInput {
+            id,
+            stock_line_id,
+            r#type: r#type.to_domain(),
+            number_of_packs: number_of_packs as i32,
+            inventory_adjustment_reason_id,
+            comment,
+        }
+    }
+}
+
+#[derive(Interface)]
+#[graphql(name = "InsertInventoryAdjustmentErrorInterface")]
+#[graphql(field(name = "description", type = "&str"))]
+pub enum InsertErrorInterface {
+    ReductionExceedsAvailable(ReductionExceedsAvailable),
+    InventoryAdjustmentReasonNotProvided(InventoryAdjustmentReasonNotProvided),
+    InventoryAdjustmentReasonNotValid(InventoryAdjustmentReasonNotValid),
+}
+
+pub struct ReductionExceedsAvailable {
+    pub available_number_of_packs: i32,
+}
+
+#[Object]
+impl ReductionExceedsAvailable {
+    pub async fn description(&self) -> &'static str {
+        "Reduction exceeds the available number of packs of the stock line"
+    }
+
+    pub async fn available_number_of_packs(&self) -> i32 {
+        self.available_number_of_packs
+    }
+}
+
+pub struct InventoryAdjustmentReasonNotProvided;
+#[Object]
+impl InventoryAdjustmentReasonNotProvided {
+    pub async fn description(&self) -> &'static str {
+        "Adjustment reason not provided"
+    }
+}
+
+pub struct InventoryAdjustmentReasonNotValid;
+#[Object]
+impl InventoryAdjustmentReasonNotValid {
+    pub async fn description(&self) -> &'static str {
+        "Adjustment reason is not valid for this adjustment"
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::ReductionExceedsAvailable {
+            available_number_of_packs,
+        } => {
+            return Ok(InsertErrorInterface::ReductionExceedsAvailable(
+                ReductionExceedsAvailable {
+                    available_number_of_packs,
+                },
+            ))
+        }
+        ServiceError::AdjustmentReasonNotProvided => {
+            return Ok(InsertErrorInterface::InventoryAdjustmentReasonNotProvided(
+                InventoryAdjustmentReasonNotProvided,
+            ))
+        }
+        ServiceError::AdjustmentReasonNotValid => {
+            return Ok(InsertErrorInterface::InventoryAdjustmentReasonNotValid(
+                InventoryAdjustmentReasonNotValid,
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::InvoiceAlreadyExists => BadUserInput(formatted_error),
+        ServiceError::StockLineDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::StockLineDoesNotBelongToCurrentStore => BadUserInput(formatted_error),
+        ServiceError::NumberOfPacksBelowOne => BadUserInput(formatted_error),
+        ServiceError::NewlyCreatedInvoiceDoesNotExist => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+        ServiceError::InternalError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
@@ -1,2 +1,7 @@
+pub mod customer_return;
 pub mod inbound_shipment;
+pub mod inventory_adjustment;
 pub mod outbound_shipment;
+pub mod prescription;
+pub mod return_line;
+pub mod supplier_return;
@@ -177,6 +177,7 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
         ServiceError::DatabaseError(_) => InternalError(formatted_error),
         ServiceError::InvoiceLineHasNoStockLine(_) => InternalError(formatted_error),
         ServiceError::UpdatedInvoicenDoesNotExist => InternalError(formatted_error),
+        ServiceError::InboundShipmentTransferError(_) => InternalError(formatted_error),
     };
 
     Err(graphql_error.extend())
@@ -0,0 +1,232 @@
+use async_graphql::*;
+use graphql_core::simple_generic_errors::{OtherPartyNotACustomer, OtherPartyNotVisible};
+use graphql_core::standard_graphql_error::StandardGraphqlError;
+use graphql_core::ContextExt;
+use graphql_core::{simple_generic_errors::NodeError, standard_graphql_error::validate_auth};
+use graphql_types::types::InvoiceNode;
+use repository::Invoice;
+use service::invoice::prescription::{
+    InsertPrescription as ServiceInput, InsertPrescriptionError as ServiceError,
+};
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+#[derive(InputObject)]
+#[graphql(name = "InsertPrescriptionInput")]
+pub struct InsertInput {
+    /// The new invoice id provided by the client
+    pub id: String,
+    /// Name id of the patient the prescription is dispensed to
+    patient_id: String,
+    prescriber: Option<String>,
+    diagnosis: Option<String>,
+    directions: Option<String>,
+    comment: Option<String>,
+    colour: Option<String>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "InsertPrescriptionError")]
+pub struct InsertError {
+    pub error: InsertErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "InsertPrescriptionResponse")]
+pub enum InsertResponse {
+    Error(InsertError),
+    NodeError(NodeError),
+    Response(InvoiceNode),
+}
+
+pub fn insert(ctx: &Context<'_>, store_id: &str, input: InsertInput) -> Result<InsertResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutatePrescription,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.invoice_service.insert_prescription(
+        &service_context,
+        store_id,
+        &user.user_id,
+        input.to_domain(),
+    ))
+}
+
+pub fn map_response(from: Result<Invoice, ServiceError>) -> Result<InsertResponse> {
+    let result = match from {
+        Ok(invoice) => InsertResponse::Response(InvoiceNode::from_domain(invoice)),
+        Err(error) => InsertResponse::Error(InsertError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl InsertInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let InsertInput {
+            id,
+            patient_id,
+            prescriber,
+            diagnosis,
+            directions,
+            comment,
+            colour,
+        }: InsertInput = self;
+
+        ServiceInput {
+            id,
+            patient_id,
+            prescriber,
+            diagnosis,
+            directions,
+            comment,
+            colour,
+        }
+    }
+}
+
+#[derive(Interface)]
+#[graphql(name = "InsertPrescriptionErrorInterface")]
+#[graphql(field(name = "description", type = "String"))]
+pub enum InsertErrorInterface {
+    OtherPartyNotACustomer(OtherPartyNotACustomer),
+    OtherPartyNotVisible(OtherPartyNotVisible),
+}
+
+fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::PatientNotACustomer => {
+            return Ok(InsertErrorInterface::OtherPartyNotACustomer(
+                OtherPartyNotACustomer,
+            ))
+        }
+        ServiceError::PatientNotVisible => {
+            return Ok(InsertErrorInterface::OtherPartyNotVisible(
+                OtherPartyNotVisible,
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::InvoiceAlreadyExists => BadUserInput(formatted_error),
+        ServiceError::PatientDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+        ServiceError::NewlyCreatedInvoiceDoesNotExist => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+#[cfg(test)]
+mod graphql {
+    use graphql_core::test_helpers::setup_graphl_test;
+    use graphql_core::{assert_graphql_query, assert_standard_graphql_error};
+    use repository::mock::{mock_name_store_b, mock_name_store_c, MockDataInserts};
+    use repository::InvoiceRowRepository;
+    use serde_json::json;
+
+    use crate::{InvoiceMutations, InvoiceQueries};
+
+    #[actix_rt::test]
+    async fn test_graphql_prescription_insert() {
+        let (_, connection, _, settings) = setup_graphl_test(
+            InvoiceQueries,
+            InvoiceMutations,
+            "omsupply-database-gql-prescription_insert",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let query = r#"mutation InsertPrescription($input: InsertPrescriptionInput!) {
+            insertPrescription(input: $input, storeId: \"store_a\") {
+                ... on InsertPrescriptionError {
+                  error {
+                    __typename
+                  }
+                }
+                ... on InvoiceNode {
+                    id
+                    otherPartyId
+                    invoiceNumber
+                    type
+                    status
+                    prescriber
+                    diagnosis
+                    directions
+                }
+            }
+        }"#;
+
+        // OtherPartyNotACustomer
+        let variables = Some(json!({
+          "input": {
+            "id": "prescription_insert_1",
+            "patientId": mock_name_store_c().id,
+          }
+        }));
+        let expected = json!({
+            "insertPrescription": {
+              "error": {
+                "__typename": "OtherPartyNotACustomer"
+              }
+            }
+          }
+        );
+        assert_graphql_query!(&settings, query, &variables, &expected, None);
+
+        // PatientDoesNotExist
+        let variables = Some(json!({
+          "input": {
+            "id": "prescription_insert_1",
+            "patientId": "not existing",
+          }
+        }));
+        let expected_message = "Bad user input";
+        assert_standard_graphql_error!(
+            &settings,
+            &query,
+            &variables,
+            &expected_message,
+            None,
+            None
+        );
+
+        // Success
+        let variables = Some(json!({
+          "input": {
+            "id": "prescription_insert_1",
+            "patientId": mock_name_store_b().id,
+            "prescriber": "Dr Who",
+            "diagnosis": "Malaria",
+            "directions": "Two tablets daily"
+          }
+        }));
+        let expected = json!({
+            "insertPrescription": {
+              "id": "prescription_insert_1",
+              "otherPartyId": mock_name_store_b().id,
+              "invoiceNumber": 1,
+              "type": "PRESCRIPTION",
+              "status": "NEW",
+              "prescriber": "Dr Who",
+              "diagnosis": "Malaria",
+              "directions": "Two tablets daily"
+            }
+          }
+        );
+        assert_graphql_query!(&settings, query, &variables, &expected, None);
+        InvoiceRowRepository::new(&connection)
+            .find_one_by_id("prescription_insert_1")
+            .unwrap();
+    }
+}
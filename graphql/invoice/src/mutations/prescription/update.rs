@@ -0,0 +1,166 @@
+use async_graphql::*;
+use graphql_core::simple_generic_errors::{
+    CannotEditInvoice, CannotReverseInvoiceStatus, NodeError, OtherPartyNotACustomer,
+    OtherPartyNotVisible, RecordNotFound,
+};
+use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
+use graphql_core::ContextExt;
+use graphql_types::types::InvoiceNode;
+
+use repository::Invoice;
+use service::invoice::prescription::{
+    UpdatePrescription as ServiceInput, UpdatePrescriptionError as ServiceError,
+    UpdatePrescriptionStatus,
+};
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+#[derive(InputObject)]
+#[graphql(name = "UpdatePrescriptionInput")]
+pub struct UpdateInput {
+    pub id: String,
+    /// Can be used to change the patient of the prescription
+    patient_id: Option<String>,
+    /// When changing the status to PICKED or VERIFIED the total_number_of_packs of the
+    /// dispensed stock lines gets updated.
+    status: Option<UpdatePrescriptionStatusInput>,
+    prescriber: Option<String>,
+    diagnosis: Option<String>,
+    directions: Option<String>,
+    comment: Option<String>,
+    colour: Option<String>,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum UpdatePrescriptionStatusInput {
+    Picked,
+    Verified,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "UpdatePrescriptionError")]
+pub struct UpdateError {
+    pub error: UpdateErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "UpdatePrescriptionResponse")]
+pub enum UpdateResponse {
+    Error(UpdateError),
+    NodeError(NodeError),
+    Response(InvoiceNode),
+}
+
+pub fn update(ctx: &Context<'_>, store_id: &str, input: UpdateInput) -> Result<UpdateResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutatePrescription,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.invoice_service.update_prescription(
+        &service_context,
+        store_id,
+        input.to_domain(),
+    ))
+}
+
+pub fn map_response(from: Result<Invoice, ServiceError>) -> Result<UpdateResponse> {
+    let result = match from {
+        Ok(invoice) => UpdateResponse::Response(InvoiceNode::from_domain(invoice)),
+        Err(error) => UpdateResponse::Error(UpdateError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+#[derive(Interface)]
+#[graphql(name = "UpdatePrescriptionErrorInterface")]
+#[graphql(field(name = "description", type = "String"))]
+pub enum UpdateErrorInterface {
+    RecordNotFound(RecordNotFound),
+    CannotReverseInvoiceStatus(CannotReverseInvoiceStatus),
+    CannotEditInvoice(CannotEditInvoice),
+    OtherPartyNotACustomer(OtherPartyNotACustomer),
+    OtherPartyNotVisible(OtherPartyNotVisible),
+}
+
+impl UpdateInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let UpdateInput {
+            id,
+            patient_id,
+            status,
+            prescriber,
+            diagnosis,
+            directions,
+            comment,
+            colour,
+        } = self;
+
+        ServiceInput {
+            id,
+            patient_id,
+            status: status.map(|status| status.to_domain()),
+            prescriber,
+            diagnosis,
+            directions,
+            comment,
+            colour,
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::InvoiceDoesNotExist => {
+            return Ok(UpdateErrorInterface::RecordNotFound(RecordNotFound {}))
+        }
+        ServiceError::CannotReverseInvoiceStatus => {
+            return Ok(UpdateErrorInterface::CannotReverseInvoiceStatus(
+                CannotReverseInvoiceStatus,
+            ))
+        }
+        ServiceError::InvoiceIsNotEditable => {
+            return Ok(UpdateErrorInterface::CannotEditInvoice(CannotEditInvoice))
+        }
+        ServiceError::PatientNotACustomer => {
+            return Ok(UpdateErrorInterface::OtherPartyNotACustomer(
+                OtherPartyNotACustomer,
+            ))
+        }
+        ServiceError::PatientNotVisible => {
+            return Ok(UpdateErrorInterface::OtherPartyNotVisible(
+                OtherPartyNotVisible,
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::NotAPrescription => BadUserInput(formatted_error),
+        ServiceError::PatientDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+        ServiceError::InvoiceLineHasNoStockLine(_) => InternalError(formatted_error),
+        ServiceError::UpdatedInvoiceDoesNotExist => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+impl UpdatePrescriptionStatusInput {
+    pub fn to_domain(&self) -> UpdatePrescriptionStatus {
+        use UpdatePrescriptionStatus::*;
+        match self {
+            UpdatePrescriptionStatusInput::Picked => Picked,
+            UpdatePrescriptionStatusInput::Verified => Verified,
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use async_graphql::*;
+use service::invoice::InsertReturnLine;
+
+#[derive(InputObject)]
+#[graphql(name = "InsertReturnLineInput")]
+pub struct InsertReturnLineInput {
+    pub id: String,
+    /// Issued (customer return) or received (supplier return) invoice line the packs are
+    /// returned against
+    pub original_invoice_line_id: String,
+    pub number_of_packs: u32,
+    pub return_reason: Option<String>,
+    pub note: Option<String>,
+}
+
+impl InsertReturnLineInput {
+    pub fn to_domain(self) -> InsertReturnLine {
+        let InsertReturnLineInput {
+            id,
+            original_invoice_line_id,
+            number_of_packs,
+            return_reason,
+            note,
+        } = self;
+
+        InsertReturnLine {
+            id,
+            original_invoice_line_id,
+            number_of_packs: number_of_packs as i32,
+            return_reason,
+            note,
+        }
+    }
+}
+
+pub struct ReturnedQuantityExceedsAvailable {
+    pub line_id: String,
+    pub max_number_of_packs: i32,
+}
+
+#[Object]
+impl ReturnedQuantityExceedsAvailable {
+    pub async fn description(&self) -> &'static str {
+        "Returned quantity exceeds the quantity available to return"
+    }
+
+    pub async fn line_id(&self) -> &str {
+        &self.line_id
+    }
+
+    pub async fn max_number_of_packs(&self) -> i32 {
+        self.max_number_of_packs
+    }
+}
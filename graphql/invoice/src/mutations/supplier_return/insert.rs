@@ -0,0 +1,137 @@
+use async_graphql::*;
+use graphql_core::simple_generic_errors::NodeError;
+use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
+use graphql_core::ContextExt;
+use graphql_types::types::InvoiceNode;
+use repository::Invoice;
+use service::invoice::supplier_return::{
+    InsertSupplierReturn as ServiceInput, InsertSupplierReturnError as ServiceError,
+};
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+use crate::mutations::return_line::{InsertReturnLineInput, ReturnedQuantityExceedsAvailable};
+
+#[derive(InputObject)]
+#[graphql(name = "InsertSupplierReturnInput")]
+pub struct InsertInput {
+    /// The new invoice id provided by the client
+    pub id: String,
+    /// Inbound shipment the stock is returned from
+    pub original_invoice_id: String,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub colour: Option<String>,
+    pub lines: Vec<InsertReturnLineInput>,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "InsertSupplierReturnError")]
+pub struct InsertError {
+    pub error: InsertErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "InsertSupplierReturnResponse")]
+pub enum InsertResponse {
+    Error(InsertError),
+    NodeError(NodeError),
+    Response(InvoiceNode),
+}
+
+pub fn insert(ctx: &Context<'_>, store_id: &str, input: InsertInput) -> Result<InsertResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateSupplierReturn,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.invoice_service.insert_supplier_return(
+        &service_context,
+        store_id,
+        &user.user_id,
+        input.to_domain(),
+    ))
+}
+
+pub fn map_response(from: Result<Invoice, ServiceError>) -> Result<InsertResponse> {
+    let result = match from {
+        Ok(invoice) => InsertResponse::Response(InvoiceNode::from_domain(invoice)),
+        Err(error) => InsertResponse::Error(InsertError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl InsertInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let InsertInput {
+            id,
+            original_invoice_id,
+            their_reference,
+            comment,
+            colour,
+            lines,
+        }: InsertInput = self;
+
+        ServiceInput {
+            id,
+            original_invoice_id,
+            their_reference,
+            comment,
+            colour,
+            lines: lines.into_iter().map(|line| line.to_domain()).collect(),
+        }
+    }
+}
+
+#[derive(Interface)]
+#[graphql(name = "InsertSupplierReturnErrorInterface")]
+#[graphql(field(name = "description", type = "&str"))]
+pub enum InsertErrorInterface {
+    ReturnedQuantityExceedsAvailable(ReturnedQuantityExceedsAvailable),
+}
+
+fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::ReturnedQuantityExceedsReceived {
+            line_id,
+            returnable_number_of_packs: max_number_of_packs,
+        }
+        | ServiceError::ReturnedQuantityExceedsAvailable {
+            line_id,
+            available_number_of_packs: max_number_of_packs,
+        } => {
+            return Ok(InsertErrorInterface::ReturnedQuantityExceedsAvailable(
+                ReturnedQuantityExceedsAvailable {
+                    line_id,
+                    max_number_of_packs,
+                },
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::InvoiceAlreadyExists => BadUserInput(formatted_error),
+        ServiceError::OriginalInvoiceDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::OriginalInvoiceNotThisStore => BadUserInput(formatted_error),
+        ServiceError::NotAnInboundShipment => BadUserInput(formatted_error),
+        ServiceError::OriginalInvoiceNotReceived => BadUserInput(formatted_error),
+        ServiceError::LineAlreadyExists(_) => BadUserInput(formatted_error),
+        ServiceError::OriginalLineDoesNotExist(_) => BadUserInput(formatted_error),
+        ServiceError::NumberOfPacksBelowOne(_) => BadUserInput(formatted_error),
+        ServiceError::StockLineDoesNotExist(_) => BadUserInput(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+        ServiceError::NewlyCreatedInvoiceDoesNotExist => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
@@ -0,0 +1,7 @@
+pub mod delete;
+pub mod insert;
+pub mod update;
+
+pub use delete::*;
+pub use insert::*;
+pub use update::*;
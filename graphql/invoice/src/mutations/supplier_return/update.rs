@@ -0,0 +1,140 @@
+use async_graphql::*;
+use graphql_core::simple_generic_errors::{
+    CannotEditInvoice, CannotReverseInvoiceStatus, NodeError, RecordNotFound,
+};
+use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
+use graphql_core::ContextExt;
+use graphql_types::types::InvoiceNode;
+
+use repository::Invoice;
+use service::invoice::supplier_return::{
+    UpdateSupplierReturn as ServiceInput, UpdateSupplierReturnError as ServiceError,
+    UpdateSupplierReturnStatus,
+};
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+#[derive(InputObject)]
+#[graphql(name = "UpdateSupplierReturnInput")]
+pub struct UpdateInput {
+    pub id: String,
+    /// When changing the status to PICKED the returned packs are taken off the stock lines.
+    status: Option<UpdateSupplierReturnStatusInput>,
+    their_reference: Option<String>,
+    comment: Option<String>,
+    colour: Option<String>,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum UpdateSupplierReturnStatusInput {
+    Picked,
+    Verified,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "UpdateSupplierReturnError")]
+pub struct UpdateError {
+    pub error: UpdateErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "UpdateSupplierReturnResponse")]
+pub enum UpdateResponse {
+    Error(UpdateError),
+    NodeError(NodeError),
+    Response(InvoiceNode),
+}
+
+pub fn update(ctx: &Context<'_>, store_id: &str, input: UpdateInput) -> Result<UpdateResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateSupplierReturn,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.invoice_service.update_supplier_return(
+        &service_context,
+        store_id,
+        input.to_domain(),
+    ))
+}
+
+pub fn map_response(from: Result<Invoice, ServiceError>) -> Result<UpdateResponse> {
+    let result = match from {
+        Ok(invoice) => UpdateResponse::Response(InvoiceNode::from_domain(invoice)),
+        Err(error) => UpdateResponse::Error(UpdateError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+#[derive(Interface)]
+#[graphql(name = "UpdateSupplierReturnErrorInterface")]
+#[graphql(field(name = "description", type = "String"))]
+pub enum UpdateErrorInterface {
+    RecordNotFound(RecordNotFound),
+    CannotReverseInvoiceStatus(CannotReverseInvoiceStatus),
+    CannotEditInvoice(CannotEditInvoice),
+}
+
+impl UpdateInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let UpdateInput {
+            id,
+            status,
+            their_reference,
+            comment,
+            colour,
+        } = self;
+
+        ServiceInput {
+            id,
+            status: status.map(|status| status.to_domain()),
+            their_reference,
+            comment,
+            colour,
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::InvoiceDoesNotExist => {
+            return Ok(UpdateErrorInterface::RecordNotFound(RecordNotFound {}))
+        }
+        ServiceError::CannotReverseInvoiceStatus => {
+            return Ok(UpdateErrorInterface::CannotReverseInvoiceStatus(
+                CannotReverseInvoiceStatus,
+            ))
+        }
+        ServiceError::InvoiceIsNotEditable => {
+            return Ok(UpdateErrorInterface::CannotEditInvoice(CannotEditInvoice))
+        }
+        // Standard Graphql Errors
+        ServiceError::NotASupplierReturn => BadUserInput(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+        ServiceError::UpdatedInvoiceDoesNotExist => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+impl UpdateSupplierReturnStatusInput {
+    pub fn to_domain(&self) -> UpdateSupplierReturnStatus {
+        use UpdateSupplierReturnStatus::*;
+        match self {
+            UpdateSupplierReturnStatusInput::Picked => Picked,
+            UpdateSupplierReturnStatusInput::Verified => Verified,
+        }
+    }
+}
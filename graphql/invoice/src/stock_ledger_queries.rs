@@ -0,0 +1,107 @@
+use async_graphql::*;
+use graphql_core::{
+    generic_filters::{DatetimeFilterInput, EqualFilterStringInput},
+    map_filter,
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{InvoiceNodeType, StockLedgerConnector};
+use repository::{
+    DatetimeFilter, EqualFilter, PaginationOption, StockLedgerFilter, StockLedgerSort,
+};
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+use crate::invoice_queries::EqualFilterInvoiceTypeInput;
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(remote = "repository::StockLedgerSortField")]
+#[graphql(rename_items = "camelCase")]
+pub enum StockLedgerSortFieldInput {
+    Datetime,
+    InvoiceNumber,
+    Quantity,
+}
+
+#[derive(InputObject)]
+pub struct StockLedgerSortInput {
+    /// Sort query result by `key`
+    key: StockLedgerSortFieldInput,
+    /// Sort query result is sorted descending or ascending (if not provided the default is
+    /// ascending)
+    desc: Option<bool>,
+}
+
+impl StockLedgerSortInput {
+    pub fn to_domain(self) -> StockLedgerSort {
+        StockLedgerSort {
+            // From trait is auto implemented by graphql(remote) in StockLedgerSortFieldInput
+            key: self.key.into(),
+            desc: self.desc,
+        }
+    }
+}
+
+#[derive(InputObject, Clone)]
+pub struct StockLedgerFilterInput {
+    pub item_id: Option<EqualFilterStringInput>,
+    pub stock_line_id: Option<EqualFilterStringInput>,
+    pub invoice_type: Option<EqualFilterInvoiceTypeInput>,
+    pub datetime: Option<DatetimeFilterInput>,
+}
+
+impl StockLedgerFilterInput {
+    pub fn to_domain(self) -> StockLedgerFilter {
+        StockLedgerFilter {
+            item_id: self.item_id.map(EqualFilter::from),
+            // Always set from the store_id argument
+            store_id: None,
+            stock_line_id: self.stock_line_id.map(EqualFilter::from),
+            invoice_type: self
+                .invoice_type
+                .map(|t| map_filter!(t, InvoiceNodeType::to_domain)),
+            datetime: self.datetime.map(DatetimeFilter::from),
+        }
+    }
+}
+
+#[derive(Union)]
+pub enum StockLedgerResponse {
+    Response(StockLedgerConnector),
+}
+
+pub fn get_stock_ledger(
+    ctx: &Context<'_>,
+    store_id: String,
+    page: Option<PaginationInput>,
+    filter: Option<StockLedgerFilterInput>,
+    sort: Option<Vec<StockLedgerSortInput>>,
+) -> Result<StockLedgerResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStockLedger,
+            store_id: Some(store_id.clone()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let ledger = service_provider
+        .stock_ledger_service
+        .get_stock_ledger(
+            &service_context,
+            &store_id,
+            page.map(PaginationOption::from),
+            filter.map(|filter| filter.to_domain()),
+            // Currently only one sort option is supported, use the first from the list.
+            sort.and_then(|mut sort_list| sort_list.pop())
+                .map(|sort| sort.to_domain()),
+        )
+        .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(StockLedgerResponse::Response(
+        StockLedgerConnector::from_domain(ledger),
+    ))
+}
@@ -94,8 +94,14 @@ impl InvoiceLineMutations {
         ctx: &Context<'_>,
         store_id: String,
         line_id: String,
+        #[graphql(desc = "Only returns the proposed allocation")] dry_run: Option<bool>,
     ) -> Result<outbound_shipment_line::unallocated_line::AllocateResponse> {
-        outbound_shipment_line::unallocated_line::allocate(ctx, &store_id, line_id)
+        outbound_shipment_line::unallocated_line::allocate(
+            ctx,
+            &store_id,
+            line_id,
+            dry_run.unwrap_or(false),
+        )
     }
 
     // Inbound
@@ -39,10 +39,17 @@ pub struct ResponseNode {
     deletes: Vec<DeleteResponse>,
     skipped_expired_stock_lines: StockLineConnector,
     skipped_on_hold_stock_lines: StockLineConnector,
+    /// Stock lines expiring before the minimum shelf life of the store or customer
+    skipped_short_shelf_life_stock_lines: StockLineConnector,
     issued_expiring_soon_stock_lines: StockLineConnector,
 }
 
-pub fn allocate(ctx: &Context<'_>, store_id: &str, line_id: String) -> Result<AllocateResponse> {
+pub fn allocate(
+    ctx: &Context<'_>,
+    store_id: &str,
+    line_id: String,
+    dry_run: bool,
+) -> Result<AllocateResponse> {
     validate_auth(
         ctx,
         &ResourceAccessRequest {
@@ -54,11 +61,18 @@ pub fn allocate(ctx: &Context<'_>, store_id: &str, line_id: String) -> Result<Al
     let service_provider = ctx.service_provider();
     let service_context = service_provider.context()?;
 
-    map_response(
-        service_provider
-            .invoice_line_service
-            .allocate_outbound_shipment_unallocated_line(&service_context, store_id, line_id),
-    )
+    let service = &service_provider.invoice_line_service;
+    let result = if dry_run {
+        service.preview_outbound_shipment_unallocated_line_allocation(
+            &service_context,
+            store_id,
+            line_id,
+        )
+    } else {
+        service.allocate_outbound_shipment_unallocated_line(&service_context, store_id, line_id)
+    };
+
+    map_response(result)
 }
 
 pub fn map_response(from: Result<ServiceResult, ServiceError>) -> Result<AllocateResponse> {
@@ -101,6 +115,7 @@ impl ResponseNode {
             inserts,
             skipped_expired_stock_lines,
             skipped_on_hold_stock_lines,
+            skipped_short_shelf_life_stock_lines,
             issued_expiring_soon_stock_lines,
         } = from;
         ResponseNode {
@@ -109,6 +124,9 @@ impl ResponseNode {
             inserts: InvoiceLineConnector::from_vec(inserts),
             skipped_expired_stock_lines: StockLineConnector::from_vec(skipped_expired_stock_lines),
             skipped_on_hold_stock_lines: StockLineConnector::from_vec(skipped_on_hold_stock_lines),
+            skipped_short_shelf_life_stock_lines: StockLineConnector::from_vec(
+                skipped_short_shelf_life_stock_lines,
+            ),
             issued_expiring_soon_stock_lines: StockLineConnector::from_vec(
                 issued_expiring_soon_stock_lines,
             ),
@@ -284,6 +302,11 @@ mod graphql {
                             id
                         }
                     }
+                    skippedShortShelfLifeStockLines {
+                        nodes {
+                            id
+                        }
+                    }
                     issuedExpiringSoonStockLines {
                         nodes {
                             id
@@ -319,6 +342,9 @@ mod graphql {
                 skipped_on_hold_stock_lines: vec![inline_init(|r: &mut StockLine| {
                     r.stock_line_row.id = "skipped_on_hold".to_string();
                 })],
+                skipped_short_shelf_life_stock_lines: vec![inline_init(|r: &mut StockLine| {
+                    r.stock_line_row.id = "skipped_short_shelf_life".to_string();
+                })],
                 issued_expiring_soon_stock_lines: vec![inline_init(|r: &mut StockLine| {
                     r.stock_line_row.id = "expiring_soon".to_string();
                 })],
@@ -352,6 +378,11 @@ mod graphql {
                         "id": "skipped_on_hold"
                     }]
                 },
+                "skippedShortShelfLifeStockLines": {
+                    "nodes": [{
+                        "id": "skipped_short_shelf_life"
+                    }]
+                },
                 "issuedExpiringSoonStockLines": {
                     "nodes": [{
                         "id": "expiring_soon"
@@ -1,28 +1,31 @@
 #[cfg(test)]
 mod tests;
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use actix_web::web::{self, Data};
 use actix_web::HttpResponse;
 use actix_web::{guard, HttpRequest};
 use async_graphql::extensions::{
-    Extension, ExtensionContext, ExtensionFactory, Logger, NextExecute,
+    Extension, ExtensionContext, ExtensionFactory, Logger, NextExecute, NextParseQuery,
 };
 use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
-use async_graphql::MergedObject;
+use async_graphql::parser::types::{ExecutableDocument, OperationType};
 use async_graphql::{EmptySubscription, SchemaBuilder};
+use async_graphql::{MergedObject, ServerResult, Variables};
 use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
 use graphql_batch_mutations::BatchMutations;
 use graphql_core::loader::LoaderRegistry;
 use graphql_core::{auth_data_from_request, RequestUserData, SelfRequest};
 use graphql_general::{
-    GeneralQueries, ServerAdminMutations, ServerAdminQueries, ServerAdminStage0Mutations,
-    ServerAdminStage0Queries,
+    GeneralMutations, GeneralQueries, ServerAdminMutations, ServerAdminQueries,
+    ServerAdminStage0Mutations, ServerAdminStage0Queries,
 };
 use graphql_invoice::{InvoiceMutations, InvoiceQueries};
 use graphql_invoice_line::InvoiceLineMutations;
 use graphql_location::{LocationMutations, LocationQueries};
+use graphql_patient::{PatientMutations, PatientQueries};
 use graphql_reports::ReportQueries;
 use graphql_requisition::{RequisitionMutations, RequisitionQueries};
 use graphql_requisition_line::RequisitionLineMutations;
@@ -33,6 +36,7 @@ use log::info;
 use repository::StorageConnectionManager;
 use service::auth_data::AuthData;
 use service::service_provider::ServiceProvider;
+use service::sync_actor::SyncSenderActor;
 use service::sync_settings::SyncSettings;
 use tokio::sync::mpsc::Sender;
 
@@ -40,6 +44,7 @@ use tokio::sync::mpsc::Sender;
 pub struct FullQuery(
     pub InvoiceQueries,
     pub LocationQueries,
+    pub PatientQueries,
     pub StocktakeQueries,
     pub GeneralQueries,
     pub RequisitionQueries,
@@ -52,11 +57,13 @@ pub struct FullMutation(
     pub InvoiceMutations,
     pub InvoiceLineMutations,
     pub LocationMutations,
+    pub PatientMutations,
     pub StocktakeMutations,
     pub StocktakeLineMutations,
     pub BatchMutations,
     pub RequisitionMutations,
     pub RequisitionLineMutations,
+    pub GeneralMutations,
     pub ServerAdminMutations,
 );
 
@@ -67,6 +74,7 @@ pub fn full_query() -> FullQuery {
     FullQuery(
         InvoiceQueries,
         LocationQueries,
+        PatientQueries,
         StocktakeQueries,
         GeneralQueries,
         RequisitionQueries,
@@ -80,11 +88,13 @@ pub fn full_mutation() -> FullMutation {
         InvoiceMutations,
         InvoiceLineMutations,
         LocationMutations,
+        PatientMutations,
         StocktakeMutations,
         StocktakeLineMutations,
         BatchMutations,
         RequisitionMutations,
         RequisitionLineMutations,
+        GeneralMutations,
         ServerAdminMutations,
     )
 }
@@ -113,6 +123,53 @@ impl Extension for ResponseLoggerExtension {
     }
 }
 
+/// Requests a sync after successful mutations so that local changes are pushed straight away,
+/// if the central server is known to be reachable
+pub struct SyncOnMutation;
+impl ExtensionFactory for SyncOnMutation {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(SyncOnMutationExtension {
+            is_mutation: AtomicBool::new(false),
+        })
+    }
+}
+struct SyncOnMutationExtension {
+    is_mutation: AtomicBool,
+}
+#[async_trait::async_trait]
+impl Extension for SyncOnMutationExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        let is_mutation = document
+            .operations
+            .iter()
+            .any(|(_, operation)| operation.node.ty == OperationType::Mutation);
+        self.is_mutation.store(is_mutation, Ordering::Relaxed);
+        Ok(document)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> async_graphql::Response {
+        let resp = next.run(ctx, operation_name).await;
+        if self.is_mutation.load(Ordering::Relaxed) && resp.is_ok() {
+            if let Some(sync_sender) = ctx.data_opt::<Data<SyncSenderActor>>() {
+                sync_sender.send_if_online();
+            }
+        }
+        resp
+    }
+}
+
 pub fn schema_builder() -> Builder {
     Schema::build(full_query(), full_mutation(), EmptySubscription)
 }
@@ -124,6 +181,7 @@ pub fn build_schema(
     auth_data: Data<AuthData>,
     sync_settings_data: Option<Data<SyncSettings>>,
     restart_switch: Data<Sender<bool>>,
+    sync_sender: Option<Data<SyncSenderActor>>,
     self_request: Option<Data<Box<dyn SelfRequest>>>,
     include_logger: bool,
 ) -> Schema {
@@ -138,6 +196,10 @@ pub fn build_schema(
         Some(sync_settings_data) => builder = builder.data(sync_settings_data),
         None => {}
     }
+    match sync_sender {
+        Some(sync_sender) => builder = builder.data(sync_sender).extension(SyncOnMutation),
+        None => {}
+    }
     match self_request {
         Some(self_request) => builder = builder.data(self_request),
         None => {}
@@ -241,6 +303,7 @@ pub fn config(
     auth_data: Data<AuthData>,
     sync_settings_data: Option<Data<SyncSettings>>,
     restart_switch: Data<Sender<bool>>,
+    sync_sender: Option<Data<SyncSenderActor>>,
 ) -> impl FnOnce(&mut actix_web::web::ServiceConfig) {
     |cfg| {
         let self_requester: Data<Box<dyn SelfRequest>> = Data::new(Box::new(SelfRequestImpl {
@@ -251,6 +314,7 @@ pub fn config(
                 auth_data.clone(),
                 sync_settings_data.clone(),
                 restart_switch.clone(),
+                sync_sender.clone(),
                 None,
                 false,
             ),
@@ -263,6 +327,7 @@ pub fn config(
             auth_data,
             sync_settings_data,
             restart_switch,
+            sync_sender,
             Some(self_requester),
             true,
         );
@@ -92,6 +92,17 @@ impl LocationMutations {
     ) -> Result<DeleteLocationResponse> {
         delete_location(ctx, &store_id, input)
     }
+
+    /// Move packs of a stock line to another location, splitting the stock line if only part of
+    /// it is moved
+    async fn move_stock(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: MoveStockInput,
+    ) -> Result<MoveStockResponse> {
+        move_stock(ctx, &store_id, input)
+    }
 }
 
 #[cfg(test)]
@@ -189,6 +200,10 @@ mod test {
                         code: "test_code".to_owned(),
                         on_hold: true,
                         store_id: "store_a".to_owned(),
+                        parent_location_id: None,
+                        capacity: None,
+                        min_temperature: None,
+                        max_temperature: None,
                     },
                 }],
                 count: 1,
@@ -72,6 +72,7 @@ pub enum DeleteLocationErrorInterface {
     LocationNotFound(RecordNotFound),
     RecordBelongsToAnotherStore(RecordBelongsToAnotherStore),
     LocationInUse(LocationInUse),
+    LocationHasSubLocations(LocationHasSubLocations),
     DatabaseError(DatabaseError),
 }
 
@@ -95,6 +96,14 @@ impl LocationInUse {
     }
 }
 
+pub struct LocationHasSubLocations;
+#[Object]
+impl LocationHasSubLocations {
+    pub async fn description(&self) -> &'static str {
+        "Location has sub locations"
+    }
+}
+
 impl From<RepositoryError> for DeleteLocationError {
     fn from(error: RepositoryError) -> Self {
         let error = DeleteLocationErrorInterface::DatabaseError(DatabaseError(error));
@@ -117,6 +126,9 @@ impl From<InError> for DeleteLocationError {
             InError::LocationDoesNotBelongToCurrentStore => {
                 OutError::RecordBelongsToAnotherStore(RecordBelongsToAnotherStore {})
             }
+            InError::LocationHasSubLocations => {
+                OutError::LocationHasSubLocations(LocationHasSubLocations {})
+            }
             InError::DatabaseError(error) => OutError::DatabaseError(DatabaseError(error)),
         };
         DeleteLocationError { error }
@@ -1,7 +1,8 @@
 use async_graphql::*;
 use graphql_core::{
     simple_generic_errors::{
-        DatabaseError, InternalError, RecordAlreadyExist, UniqueValueKey, UniqueValueViolation,
+        DatabaseError, ForeignKey, ForeignKeyError, InternalError, RecordAlreadyExist,
+        RecordBelongsToAnotherStore, UniqueValueKey, UniqueValueViolation,
     },
     standard_graphql_error::validate_auth,
     ContextExt,
@@ -50,6 +51,11 @@ pub struct InsertLocationInput {
     pub code: String,
     pub name: Option<String>,
     pub on_hold: Option<bool>,
+    pub parent_location_id: Option<String>,
+    /// Maximum number of packs that can be stored in the location
+    pub capacity: Option<i32>,
+    pub min_temperature: Option<f64>,
+    pub max_temperature: Option<f64>,
 }
 
 impl From<InsertLocationInput> for InsertLocation {
@@ -59,6 +65,10 @@ impl From<InsertLocationInput> for InsertLocation {
             code,
             name,
             on_hold,
+            parent_location_id,
+            capacity,
+            min_temperature,
+            max_temperature,
         }: InsertLocationInput,
     ) -> Self {
         InsertLocation {
@@ -66,6 +76,10 @@ impl From<InsertLocationInput> for InsertLocation {
             code,
             name,
             on_hold,
+            parent_location_id,
+            capacity,
+            min_temperature,
+            max_temperature,
         }
     }
 }
@@ -85,10 +99,30 @@ pub enum InsertLocationResponse {
 pub enum InsertLocationErrorInterface {
     LocationAlreadyExists(RecordAlreadyExist),
     UniqueValueViolation(UniqueValueViolation),
+    ForeignKeyError(ForeignKeyError),
+    RecordBelongsToAnotherStore(RecordBelongsToAnotherStore),
+    CapacityBelowZero(CapacityBelowZero),
+    InvalidTemperatureRange(InvalidTemperatureRange),
     InternalError(InternalError),
     DatabaseError(DatabaseError),
 }
 
+pub struct CapacityBelowZero;
+#[Object]
+impl CapacityBelowZero {
+    pub async fn description(&self) -> &'static str {
+        "Capacity cannot be below zero"
+    }
+}
+
+pub struct InvalidTemperatureRange;
+#[Object]
+impl InvalidTemperatureRange {
+    pub async fn description(&self) -> &'static str {
+        "Minimum temperature is above maximum temperature"
+    }
+}
+
 impl From<RepositoryError> for InsertLocationError {
     fn from(error: RepositoryError) -> Self {
         let error = InsertLocationErrorInterface::DatabaseError(DatabaseError(error));
@@ -106,6 +140,16 @@ impl From<InError> for InsertLocationError {
             InError::LocationWithCodeAlreadyExists => {
                 OutError::UniqueValueViolation(UniqueValueViolation(UniqueValueKey::Code))
             }
+            InError::ParentLocationDoesNotExist => {
+                OutError::ForeignKeyError(ForeignKeyError(ForeignKey::ParentLocationId))
+            }
+            InError::ParentLocationDoesNotBelongToCurrentStore => {
+                OutError::RecordBelongsToAnotherStore(RecordBelongsToAnotherStore {})
+            }
+            InError::CapacityBelowZero => OutError::CapacityBelowZero(CapacityBelowZero {}),
+            InError::InvalidTemperatureRange => {
+                OutError::InvalidTemperatureRange(InvalidTemperatureRange {})
+            }
             InError::CreatedRecordNotFound => OutError::InternalError(InternalError(
                 "Could not find record after creation".to_owned(),
             )),
@@ -329,6 +373,10 @@ mod test {
                     code: "code".to_owned(),
                     on_hold: true,
                     store_id: "store_a".to_owned(),
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 },
             })
         }));
@@ -1,7 +1,9 @@
 mod delete;
 mod insert;
+mod move_stock;
 mod update;
 
 pub use delete::*;
 pub use insert::*;
+pub use move_stock::*;
 pub use update::*;
@@ -0,0 +1,204 @@
+use async_graphql::*;
+use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
+use graphql_core::ContextExt;
+use graphql_types::types::InvoiceNode;
+use repository::Invoice;
+use service::location::move_stock::{MoveStock as ServiceInput, MoveStockError as ServiceError};
+use service::permission_validation::{Resource, ResourceAccessRequest};
+
+#[derive(InputObject)]
+pub struct MoveStockInput {
+    /// The id of the invoice recording the move, provided by the client
+    pub id: String,
+    pub stock_line_id: String,
+    /// Destination location
+    pub location_id: String,
+    pub number_of_packs: u32,
+    pub comment: Option<String>,
+}
+
+#[derive(SimpleObject)]
+pub struct MoveStockError {
+    pub error: MoveStockErrorInterface,
+}
+
+#[derive(Union)]
+pub enum MoveStockResponse {
+    Error(MoveStockError),
+    Response(InvoiceNode),
+}
+
+pub fn move_stock(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: MoveStockInput,
+) -> Result<MoveStockResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateInventoryAdjustment,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.location_service.move_stock(
+        &service_context,
+        store_id,
+        &user.user_id,
+        input.to_domain(),
+    ))
+}
+
+pub fn map_response(from: Result<Invoice, ServiceError>) -> Result<MoveStockResponse> {
+    let result = match from {
+        Ok(invoice) => MoveStockResponse::Response(InvoiceNode::from_domain(invoice)),
+        Err(error) => MoveStockResponse::Error(MoveStockError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl MoveStockInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let MoveStockInput {
+            id,
+            stock_line_id,
+            location_id,
+            number_of_packs,
+            comment,
+        } = self;
+
+        ServiceInput {
+            id,
+            stock_line_id,
+            location_id,
+            number_of_packs: number_of_packs as i32,
+            comment,
+        }
+    }
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "&str"))]
+pub enum MoveStockErrorInterface {
+    StockLineLocationIsOnHold(StockLineLocationIsOnHold),
+    DestinationLocationIsOnHold(DestinationLocationIsOnHold),
+    NumberOfPacksExceedsAvailable(NumberOfPacksExceedsAvailable),
+    LocationCapacityExceeded(LocationCapacityExceeded),
+}
+
+pub struct StockLineLocationIsOnHold;
+#[Object]
+impl StockLineLocationIsOnHold {
+    pub async fn description(&self) -> &'static str {
+        "Stock line is in a location that is on hold"
+    }
+}
+
+pub struct DestinationLocationIsOnHold;
+#[Object]
+impl DestinationLocationIsOnHold {
+    pub async fn description(&self) -> &'static str {
+        "Stock can't be moved into a location that is on hold"
+    }
+}
+
+pub struct NumberOfPacksExceedsAvailable {
+    pub available_number_of_packs: i32,
+}
+
+#[Object]
+impl NumberOfPacksExceedsAvailable {
+    pub async fn description(&self) -> &'static str {
+        "Number of packs exceeds the available number of packs of the stock line"
+    }
+
+    pub async fn available_number_of_packs(&self) -> i32 {
+        self.available_number_of_packs
+    }
+}
+
+pub struct LocationCapacityExceeded {
+    pub location_id: String,
+    pub capacity: i32,
+    pub number_of_packs_in_location: i32,
+}
+
+#[Object]
+impl LocationCapacityExceeded {
+    pub async fn description(&self) -> &'static str {
+        "Moving the stock would exceed the capacity of the location"
+    }
+
+    /// The destination location or the parent location whose capacity would be exceeded
+    pub async fn location_id(&self) -> &str {
+        &self.location_id
+    }
+
+    pub async fn capacity(&self) -> i32 {
+        self.capacity
+    }
+
+    pub async fn number_of_packs_in_location(&self) -> i32 {
+        self.number_of_packs_in_location
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<MoveStockErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::StockLineLocationIsOnHold => {
+            return Ok(MoveStockErrorInterface::StockLineLocationIsOnHold(
+                StockLineLocationIsOnHold,
+            ))
+        }
+        ServiceError::LocationIsOnHold => {
+            return Ok(MoveStockErrorInterface::DestinationLocationIsOnHold(
+                DestinationLocationIsOnHold,
+            ))
+        }
+        ServiceError::NumberOfPacksExceedsAvailable {
+            available_number_of_packs,
+        } => {
+            return Ok(MoveStockErrorInterface::NumberOfPacksExceedsAvailable(
+                NumberOfPacksExceedsAvailable {
+                    available_number_of_packs,
+                },
+            ))
+        }
+        ServiceError::LocationCapacityExceeded {
+            location_id,
+            capacity,
+            number_of_packs_in_location,
+        } => {
+            return Ok(MoveStockErrorInterface::LocationCapacityExceeded(
+                LocationCapacityExceeded {
+                    location_id,
+                    capacity,
+                    number_of_packs_in_location,
+                },
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::InvoiceAlreadyExists => BadUserInput(formatted_error),
+        ServiceError::StockLineDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::StockLineDoesNotBelongToCurrentStore => BadUserInput(formatted_error),
+        ServiceError::LocationDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::LocationDoesNotBelongToCurrentStore => BadUserInput(formatted_error),
+        ServiceError::StockLineAlreadyInLocation => BadUserInput(formatted_error),
+        ServiceError::NumberOfPacksBelowOne => BadUserInput(formatted_error),
+        ServiceError::NewlyCreatedInvoiceDoesNotExist => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+        ServiceError::InternalError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
@@ -1,14 +1,17 @@
 use async_graphql::*;
 
 use graphql_core::{
+    generic_inputs::NullableUpdateInput,
     simple_generic_errors::{
-        DatabaseError, InternalError, RecordBelongsToAnotherStore, RecordNotFound, UniqueValueKey,
-        UniqueValueViolation,
+        DatabaseError, ForeignKey, ForeignKeyError, InternalError, RecordBelongsToAnotherStore,
+        RecordNotFound, UniqueValueKey, UniqueValueViolation,
     },
     standard_graphql_error::validate_auth,
     ContextExt,
 };
 use graphql_types::types::LocationNode;
+
+use super::{CapacityBelowZero, InvalidTemperatureRange};
 use repository::RepositoryError;
 use service::{
     location::update::{UpdateLocation, UpdateLocationError as InError},
@@ -52,6 +55,11 @@ pub struct UpdateLocationInput {
     pub code: Option<String>,
     pub name: Option<String>,
     pub on_hold: Option<bool>,
+    pub parent_location_id: Option<NullableUpdateInput<String>>,
+    /// Maximum number of packs that can be stored in the location, including its sub locations
+    pub capacity: Option<NullableUpdateInput<i32>>,
+    pub min_temperature: Option<NullableUpdateInput<f64>>,
+    pub max_temperature: Option<NullableUpdateInput<f64>>,
 }
 
 impl From<UpdateLocationInput> for UpdateLocation {
@@ -61,6 +69,10 @@ impl From<UpdateLocationInput> for UpdateLocation {
             code,
             name,
             on_hold,
+            parent_location_id,
+            capacity,
+            min_temperature,
+            max_temperature,
         }: UpdateLocationInput,
     ) -> Self {
         UpdateLocation {
@@ -68,6 +80,10 @@ impl From<UpdateLocationInput> for UpdateLocation {
             code,
             name,
             on_hold,
+            parent_location_id: parent_location_id.map(NullableUpdateInput::to_domain),
+            capacity: capacity.map(NullableUpdateInput::to_domain),
+            min_temperature: min_temperature.map(NullableUpdateInput::to_domain),
+            max_temperature: max_temperature.map(NullableUpdateInput::to_domain),
         }
     }
 }
@@ -89,10 +105,22 @@ pub enum UpdateLocationErrorInterface {
     LocationNotFound(RecordNotFound),
     UniqueValueViolation(UniqueValueViolation),
     RecordBelongsToAnotherStore(RecordBelongsToAnotherStore),
+    ForeignKeyError(ForeignKeyError),
+    ParentLocationCreatesCycle(ParentLocationCreatesCycle),
+    CapacityBelowZero(CapacityBelowZero),
+    InvalidTemperatureRange(InvalidTemperatureRange),
     InternalError(InternalError),
     DatabaseError(DatabaseError),
 }
 
+pub struct ParentLocationCreatesCycle;
+#[Object]
+impl ParentLocationCreatesCycle {
+    pub async fn description(&self) -> &'static str {
+        "Parent location is the location itself or one of its sub locations"
+    }
+}
+
 impl From<RepositoryError> for UpdateLocationError {
     fn from(error: RepositoryError) -> Self {
         let error = UpdateLocationErrorInterface::DatabaseError(DatabaseError(error));
@@ -111,6 +139,19 @@ impl From<InError> for UpdateLocationError {
             InError::LocationDoesNotBelongToCurrentStore => {
                 OutError::RecordBelongsToAnotherStore(RecordBelongsToAnotherStore {})
             }
+            InError::ParentLocationDoesNotExist => {
+                OutError::ForeignKeyError(ForeignKeyError(ForeignKey::ParentLocationId))
+            }
+            InError::ParentLocationDoesNotBelongToCurrentStore => {
+                OutError::RecordBelongsToAnotherStore(RecordBelongsToAnotherStore {})
+            }
+            InError::ParentLocationCreatesCycle => {
+                OutError::ParentLocationCreatesCycle(ParentLocationCreatesCycle {})
+            }
+            InError::CapacityBelowZero => OutError::CapacityBelowZero(CapacityBelowZero {}),
+            InError::InvalidTemperatureRange => {
+                OutError::InvalidTemperatureRange(InvalidTemperatureRange {})
+            }
             InError::UpdatedRecordNotFound => OutError::InternalError(InternalError(
                 "Could not find record after updating".to_owned(),
             )),
@@ -0,0 +1,306 @@
+mod mutations;
+use self::mutations::*;
+
+use async_graphql::*;
+use graphql_core::{
+    generic_filters::{DateFilterInput, EqualFilterStringInput, SimpleStringFilterInput},
+    pagination::PaginationInput,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{NameNode, NameResponse};
+use repository::{
+    DateFilter, EqualFilter, Name, NameFilter, NameSort, NameSortField, PaginationOption,
+    SimpleStringFilter,
+};
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    ListResult,
+};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(rename_items = "camelCase")]
+pub enum PatientSortFieldInput {
+    Name,
+    Code,
+    FirstName,
+    LastName,
+    DateOfBirth,
+}
+
+#[derive(InputObject)]
+pub struct PatientSortInput {
+    /// Sort query result by `key`
+    key: PatientSortFieldInput,
+    /// Sort query result is sorted descending or ascending (if not provided the default is
+    /// ascending)
+    desc: Option<bool>,
+}
+
+#[derive(InputObject, Clone)]
+pub struct PatientFilterInput {
+    pub id: Option<EqualFilterStringInput>,
+    pub code: Option<SimpleStringFilterInput>,
+    /// Fuzzy search on the patient name, every word of the search has to be part of the first,
+    /// last or full name
+    pub search: Option<String>,
+    pub date_of_birth: Option<DateFilterInput>,
+    pub national_health_number: Option<SimpleStringFilterInput>,
+}
+
+#[derive(SimpleObject)]
+pub struct PatientConnector {
+    total_count: u32,
+    nodes: Vec<NameNode>,
+}
+
+#[derive(Union)]
+pub enum PatientsResponse {
+    Response(PatientConnector),
+}
+
+#[derive(Default, Clone)]
+pub struct PatientQueries;
+
+#[Object]
+impl PatientQueries {
+    /// Query patients that are visible in the store
+    pub async fn patients(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        #[graphql(desc = "Pagination option (first and offset)")] page: Option<PaginationInput>,
+        #[graphql(desc = "Filter option")] filter: Option<PatientFilterInput>,
+        #[graphql(desc = "Sort options (only first sort input is evaluated for this endpoint)")]
+        sort: Option<Vec<PatientSortInput>>,
+    ) -> Result<PatientsResponse> {
+        validate_auth(
+            ctx,
+            &ResourceAccessRequest {
+                resource: Resource::QueryPatient,
+                store_id: Some(store_id.clone()),
+            },
+        )?;
+
+        let service_provider = ctx.service_provider();
+        let service_context = service_provider.context()?;
+
+        let patients = service_provider
+            .patient_service
+            .get_patients(
+                &service_context,
+                &store_id,
+                page.map(PaginationOption::from),
+                filter.map(|filter| filter.to_domain()),
+                // Currently only one sort option is supported, use the first from the list.
+                sort.and_then(|mut sort_list| sort_list.pop())
+                    .map(|sort| sort.to_domain()),
+            )
+            .map_err(StandardGraphqlError::from_list_error)?;
+
+        Ok(PatientsResponse::Response(PatientConnector::from_domain(
+            patients,
+        )))
+    }
+
+    pub async fn patient(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        id: String,
+    ) -> Result<NameResponse> {
+        validate_auth(
+            ctx,
+            &ResourceAccessRequest {
+                resource: Resource::QueryPatient,
+                store_id: Some(store_id.clone()),
+            },
+        )?;
+
+        let service_provider = ctx.service_provider();
+        let service_context = service_provider.context()?;
+
+        let response =
+            match service_provider
+                .patient_service
+                .get_patient(&service_context, &store_id, &id)
+            {
+                Ok(patient) => NameResponse::Response(NameNode::from_domain(patient)),
+                Err(error) => NameResponse::Error(error.into()),
+            };
+
+        Ok(response)
+    }
+}
+
+#[derive(Default, Clone)]
+pub struct PatientMutations;
+
+#[Object]
+impl PatientMutations {
+    /// Registers a new patient, the patient is visible in the store it is created in
+    async fn insert_patient(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: InsertInput,
+    ) -> Result<InsertResponse> {
+        insert_patient(ctx, &store_id, input)
+    }
+
+    async fn update_patient(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: UpdateInput,
+    ) -> Result<UpdateResponse> {
+        update_patient(ctx, &store_id, input)
+    }
+}
+
+impl PatientFilterInput {
+    pub fn to_domain(self) -> NameFilter {
+        let PatientFilterInput {
+            id,
+            code,
+            search,
+            date_of_birth,
+            national_health_number,
+        } = self;
+
+        NameFilter {
+            id: id.map(EqualFilter::from),
+            code: code.map(SimpleStringFilter::from),
+            search,
+            date_of_birth: date_of_birth.map(DateFilter::from),
+            national_health_number: national_health_number.map(SimpleStringFilter::from),
+            ..Default::default()
+        }
+    }
+}
+
+impl PatientConnector {
+    pub fn from_domain(patients: ListResult<Name>) -> PatientConnector {
+        PatientConnector {
+            total_count: patients.count,
+            nodes: patients
+                .rows
+                .into_iter()
+                .map(NameNode::from_domain)
+                .collect(),
+        }
+    }
+}
+
+impl PatientSortInput {
+    pub fn to_domain(self) -> NameSort {
+        use NameSortField as to;
+        use PatientSortFieldInput as from;
+        let key = match self.key {
+            from::Name => to::Name,
+            from::Code => to::Code,
+            from::FirstName => to::FirstName,
+            from::LastName => to::LastName,
+            from::DateOfBirth => to::DateOfBirth,
+        };
+
+        NameSort {
+            key,
+            desc: self.desc,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use graphql_core::{assert_graphql_query, test_helpers::setup_graphl_test};
+    use repository::mock::MockDataInserts;
+    use serde_json::json;
+
+    use crate::{PatientMutations, PatientQueries};
+
+    #[actix_rt::test]
+    async fn test_graphql_patients() {
+        let (_, _, _, settings) = setup_graphl_test(
+            PatientQueries,
+            PatientMutations,
+            "test_graphql_patients",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let mutation = r#"mutation InsertPatient($input: InsertPatientInput!) {
+            insertPatient(input: $input, storeId: \"store_a\") {
+                ... on NameNode {
+                    id
+                    name
+                    type
+                    gender
+                    dateOfBirth
+                }
+            }
+        }"#;
+        let variables = Some(json!({
+          "input": {
+            "id": "patient_1",
+            "code": "P1",
+            "firstName": "Jane",
+            "lastName": "Doe",
+            "gender": "FEMALE",
+            "dateOfBirth": "1990-05-21"
+          }
+        }));
+        let expected = json!({
+            "insertPatient": {
+              "id": "patient_1",
+              "name": "Doe, Jane",
+              "type": "PATIENT",
+              "gender": "FEMALE",
+              "dateOfBirth": "1990-05-21"
+            }
+          }
+        );
+        assert_graphql_query!(&settings, mutation, &variables, &expected, None);
+
+        let query = r#"query Patients($storeId: String!, $filter: PatientFilterInput) {
+            patients(storeId: $storeId, filter: $filter) {
+                ... on PatientConnector {
+                    totalCount
+                    nodes {
+                        id
+                    }
+                }
+            }
+        }"#;
+
+        // Fuzzy name and date of birth search
+        let variables = Some(json!({
+          "storeId": "store_a",
+          "filter": {
+            "search": "doe jan",
+            "dateOfBirth": { "equalTo": "1990-05-21" }
+          }
+        }));
+        let expected = json!({
+            "patients": {
+              "totalCount": 1,
+              "nodes": [{ "id": "patient_1" }]
+            }
+          }
+        );
+        assert_graphql_query!(&settings, query, &variables, &expected, None);
+
+        // Patients are only visible in the store they are registered in
+        let variables = Some(json!({
+          "storeId": "store_b",
+        }));
+        let expected = json!({
+            "patients": {
+              "totalCount": 0,
+              "nodes": []
+            }
+          }
+        );
+        assert_graphql_query!(&settings, query, &variables, &expected, None);
+    }
+}
@@ -0,0 +1,107 @@
+use async_graphql::*;
+use chrono::NaiveDate;
+use graphql_core::{
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{GenderType, NameNode};
+use repository::Name;
+use service::{
+    patient::{InsertPatient as ServiceInput, InsertPatientError as ServiceError},
+    permission_validation::{Resource, ResourceAccessRequest},
+};
+
+#[derive(InputObject)]
+#[graphql(name = "InsertPatientInput")]
+pub struct InsertInput {
+    /// The new name id provided by the client
+    pub id: String,
+    pub code: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub gender: Option<GenderType>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub national_health_number: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+}
+
+#[derive(Union)]
+#[graphql(name = "InsertPatientResponse")]
+pub enum InsertResponse {
+    Response(NameNode),
+}
+
+pub fn insert_patient(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: InsertInput,
+) -> Result<InsertResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutatePatient,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.patient_service.insert_patient(
+        &service_context,
+        store_id,
+        input.to_domain(),
+    ))
+}
+
+pub fn map_response(from: Result<Name, ServiceError>) -> Result<InsertResponse> {
+    match from {
+        Ok(patient) => Ok(InsertResponse::Response(NameNode::from_domain(patient))),
+        Err(error) => Err(map_error(error)),
+    }
+}
+
+impl InsertInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let InsertInput {
+            id,
+            code,
+            first_name,
+            last_name,
+            gender,
+            date_of_birth,
+            national_health_number,
+            phone,
+            email,
+            address,
+        } = self;
+
+        ServiceInput {
+            id,
+            code,
+            first_name,
+            last_name,
+            gender: gender.map(GenderType::to_domain),
+            date_of_birth,
+            national_health_number,
+            phone,
+            email,
+            address,
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> async_graphql::Error {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        ServiceError::PatientAlreadyExists => BadUserInput(formatted_error),
+        ServiceError::CreatedRecordNotFound => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    graphql_error.extend()
+}
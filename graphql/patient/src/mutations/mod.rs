@@ -0,0 +1,5 @@
+mod insert;
+mod update;
+
+pub use insert::*;
+pub use update::*;
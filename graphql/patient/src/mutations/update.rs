@@ -0,0 +1,129 @@
+use async_graphql::*;
+use chrono::NaiveDate;
+use graphql_core::{
+    simple_generic_errors::RecordNotFound,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{GenderType, NameNode};
+use repository::Name;
+use service::{
+    patient::{UpdatePatient as ServiceInput, UpdatePatientError as ServiceError},
+    permission_validation::{Resource, ResourceAccessRequest},
+};
+
+#[derive(InputObject)]
+#[graphql(name = "UpdatePatientInput")]
+pub struct UpdateInput {
+    pub id: String,
+    pub code: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub gender: Option<GenderType>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub national_health_number: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+}
+
+#[derive(Interface)]
+#[graphql(name = "UpdatePatientErrorInterface")]
+#[graphql(field(name = "description", type = "String"))]
+pub enum UpdateErrorInterface {
+    RecordNotFound(RecordNotFound),
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "UpdatePatientError")]
+pub struct UpdateError {
+    pub error: UpdateErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "UpdatePatientResponse")]
+pub enum UpdateResponse {
+    Error(UpdateError),
+    Response(NameNode),
+}
+
+pub fn update_patient(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpdateInput,
+) -> Result<UpdateResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutatePatient,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(service_provider.patient_service.update_patient(
+        &service_context,
+        store_id,
+        input.to_domain(),
+    ))
+}
+
+pub fn map_response(from: Result<Name, ServiceError>) -> Result<UpdateResponse> {
+    let result = match from {
+        Ok(patient) => UpdateResponse::Response(NameNode::from_domain(patient)),
+        Err(error) => UpdateResponse::Error(UpdateError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl UpdateInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let UpdateInput {
+            id,
+            code,
+            first_name,
+            last_name,
+            gender,
+            date_of_birth,
+            national_health_number,
+            phone,
+            email,
+            address,
+        } = self;
+
+        ServiceInput {
+            id,
+            code,
+            first_name,
+            last_name,
+            gender: gender.map(GenderType::to_domain),
+            date_of_birth,
+            national_health_number,
+            phone,
+            email,
+            address,
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::PatientDoesNotExist => {
+            return Ok(UpdateErrorInterface::RecordNotFound(RecordNotFound {}))
+        }
+        // Standard Graphql Errors
+        ServiceError::UpdatedRecordNotFound => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
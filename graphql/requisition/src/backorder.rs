@@ -0,0 +1,328 @@
+use async_graphql::{dataloader::DataLoader, *};
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    generic_filters::EqualFilterStringInput,
+    loader::{ItemLoader, NameByIdLoader, NameByIdLoaderInput, RequisitionsByIdLoader},
+    pagination::PaginationInput,
+    simple_generic_errors::RecordNotFound,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{InvoiceNode, ItemNode, NameNode, RequisitionNode};
+use repository::{Backorder, BackorderFilter, BackorderSort, EqualFilter, PaginationOption};
+use service::{
+    backorder::create_shipment::{
+        CreateBackorderShipment, CreateBackorderShipmentError as ServiceError,
+    },
+    permission_validation::{Resource, ResourceAccessRequest},
+    ListResult,
+};
+
+pub struct BackorderNode {
+    backorder: Backorder,
+}
+
+#[derive(SimpleObject)]
+pub struct BackorderConnector {
+    total_count: u32,
+    nodes: Vec<BackorderNode>,
+}
+
+#[Object]
+impl BackorderNode {
+    pub async fn id(&self) -> &str {
+        &self.backorder.id
+    }
+
+    pub async fn requisition_id(&self) -> &str {
+        &self.backorder.requisition_id
+    }
+
+    pub async fn requisition_line_id(&self) -> &str {
+        &self.backorder.requisition_line_id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.backorder.item_id
+    }
+
+    /// Customer the stock is owed to
+    pub async fn other_party_id(&self) -> &str {
+        &self.backorder.name_id
+    }
+
+    pub async fn created_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(self.backorder.created_datetime, Utc)
+    }
+
+    /// Quantity outstanding when the requisition was finalised
+    pub async fn backordered_quantity(&self) -> i32 {
+        self.backorder.backordered_quantity
+    }
+
+    /// Quantity not yet shipped on a follow-up shipment
+    pub async fn remaining_quantity(&self) -> i32 {
+        self.backorder.remaining_quantity
+    }
+
+    pub async fn item(&self, ctx: &Context<'_>) -> Result<ItemNode> {
+        let loader = ctx.get_loader::<DataLoader<ItemLoader>>();
+        let item_option = loader.load_one(self.backorder.item_id.clone()).await?;
+
+        item_option.map(ItemNode::from_domain).ok_or(
+            StandardGraphqlError::InternalError(format!(
+                "Cannot find item_id {} for backorder_id {}",
+                &self.backorder.item_id, &self.backorder.id
+            ))
+            .extend(),
+        )
+    }
+
+    pub async fn other_party(&self, ctx: &Context<'_>, store_id: String) -> Result<NameNode> {
+        let loader = ctx.get_loader::<DataLoader<NameByIdLoader>>();
+        let response_option = loader
+            .load_one(NameByIdLoaderInput::new(&store_id, &self.backorder.name_id))
+            .await?;
+
+        response_option.map(NameNode::from_domain).ok_or(
+            StandardGraphqlError::InternalError(format!(
+                "Cannot find name ({}) linked to backorder ({})",
+                &self.backorder.name_id, &self.backorder.id
+            ))
+            .extend(),
+        )
+    }
+
+    /// Response requisition the backorder was created from
+    pub async fn requisition(&self, ctx: &Context<'_>) -> Result<Option<RequisitionNode>> {
+        let loader = ctx.get_loader::<DataLoader<RequisitionsByIdLoader>>();
+
+        Ok(loader
+            .load_one(self.backorder.requisition_id.clone())
+            .await?
+            .map(RequisitionNode::from_domain))
+    }
+}
+
+impl BackorderConnector {
+    pub fn from_domain(from: ListResult<Backorder>) -> BackorderConnector {
+        BackorderConnector {
+            total_count: from.count,
+            nodes: from
+                .rows
+                .into_iter()
+                .map(|backorder| BackorderNode { backorder })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq)]
+#[graphql(remote = "repository::BackorderSortField")]
+#[graphql(rename_items = "camelCase")]
+pub enum BackorderSortFieldInput {
+    CreatedDatetime,
+    ItemId,
+    NameId,
+}
+
+#[derive(InputObject)]
+pub struct BackorderSortInput {
+    /// Sort query result by `key`
+    key: BackorderSortFieldInput,
+    /// Sort query result is sorted descending or ascending (if not provided the default is
+    /// ascending)
+    desc: Option<bool>,
+}
+
+impl BackorderSortInput {
+    pub fn to_domain(self) -> BackorderSort {
+        BackorderSort {
+            // From trait is auto implemented by graphql(remote) in BackorderSortFieldInput
+            key: self.key.into(),
+            desc: self.desc,
+        }
+    }
+}
+
+#[derive(InputObject, Clone)]
+pub struct BackorderFilterInput {
+    pub id: Option<EqualFilterStringInput>,
+    pub requisition_id: Option<EqualFilterStringInput>,
+    pub other_party_id: Option<EqualFilterStringInput>,
+    pub item_id: Option<EqualFilterStringInput>,
+    /// Only backorders with (or without) a remaining quantity
+    pub is_open: Option<bool>,
+}
+
+impl BackorderFilterInput {
+    pub fn to_domain(self) -> BackorderFilter {
+        BackorderFilter {
+            id: self.id.map(EqualFilter::from),
+            // Always set to the store of the query by the service
+            store_id: None,
+            requisition_id: self.requisition_id.map(EqualFilter::from),
+            name_id: self.other_party_id.map(EqualFilter::from),
+            item_id: self.item_id.map(EqualFilter::from),
+            is_open: self.is_open,
+        }
+    }
+}
+
+#[derive(Union)]
+pub enum BackordersResponse {
+    Response(BackorderConnector),
+}
+
+pub fn get_backorders(
+    ctx: &Context<'_>,
+    store_id: &str,
+    page: Option<PaginationInput>,
+    filter: Option<BackorderFilterInput>,
+    sort: Option<Vec<BackorderSortInput>>,
+) -> Result<BackordersResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let backorders = service_provider
+        .backorder_service
+        .get_backorders(
+            &service_context,
+            store_id,
+            page.map(PaginationOption::from),
+            filter.map(|filter| filter.to_domain()),
+            // Currently only one sort option is supported, use the first from the list.
+            sort.and_then(|mut sort_list| sort_list.pop())
+                .map(|sort| sort.to_domain()),
+        )
+        .map_err(StandardGraphqlError::from_list_error)?;
+
+    Ok(BackordersResponse::Response(
+        BackorderConnector::from_domain(backorders),
+    ))
+}
+
+#[derive(InputObject)]
+pub struct CreateBackorderShipmentInput {
+    /// Customer to supply
+    pub other_party_id: String,
+    /// Backorders to supply, all open backorders of the customer if not set
+    pub backorder_ids: Option<Vec<String>>,
+}
+
+impl CreateBackorderShipmentInput {
+    pub fn to_domain(self) -> CreateBackorderShipment {
+        let CreateBackorderShipmentInput {
+            other_party_id,
+            backorder_ids,
+        } = self;
+
+        CreateBackorderShipment {
+            name_id: other_party_id,
+            backorder_ids,
+        }
+    }
+}
+
+pub struct NoOpenBackorders;
+#[Object]
+impl NoOpenBackorders {
+    pub async fn description(&self) -> &'static str {
+        "Customer has no open backorders"
+    }
+}
+
+pub struct NoStockAvailable;
+#[Object]
+impl NoStockAvailable {
+    pub async fn description(&self) -> &'static str {
+        "None of the backordered items have available stock"
+    }
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum CreateBackorderShipmentErrorInterface {
+    RecordNotFound(RecordNotFound),
+    NoOpenBackorders(NoOpenBackorders),
+    NoStockAvailable(NoStockAvailable),
+}
+
+#[derive(SimpleObject)]
+pub struct CreateBackorderShipmentError {
+    pub error: CreateBackorderShipmentErrorInterface,
+}
+
+#[derive(Union)]
+pub enum CreateBackorderShipmentResponse {
+    Error(CreateBackorderShipmentError),
+    Response(InvoiceNode),
+}
+
+pub fn create_backorder_shipment(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: CreateBackorderShipmentInput,
+) -> Result<CreateBackorderShipmentResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let response = match service_provider
+        .backorder_service
+        .create_backorder_shipment(&service_context, store_id, &user.user_id, input.to_domain())
+    {
+        Ok(invoice) => CreateBackorderShipmentResponse::Response(InvoiceNode::from_domain(invoice)),
+        Err(error) => CreateBackorderShipmentResponse::Error(CreateBackorderShipmentError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(response)
+}
+
+fn map_error(error: ServiceError) -> Result<CreateBackorderShipmentErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::BackorderDoesNotExist => {
+            return Ok(CreateBackorderShipmentErrorInterface::RecordNotFound(
+                RecordNotFound {},
+            ))
+        }
+        ServiceError::NoOpenBackorders => {
+            return Ok(CreateBackorderShipmentErrorInterface::NoOpenBackorders(
+                NoOpenBackorders,
+            ))
+        }
+        ServiceError::NoStockAvailable => {
+            return Ok(CreateBackorderShipmentErrorInterface::NoStockAvailable(
+                NoStockAvailable,
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::OtherPartyDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::ProblemFindingItem => InternalError(formatted_error),
+        ServiceError::CreatedInvoiceDoesNotExist => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
@@ -1,10 +1,16 @@
+mod backorder;
 pub mod mutations;
+mod program;
+mod reorder;
 mod requisition_queries;
 use async_graphql::*;
 use graphql_core::pagination::PaginationInput;
 use graphql_types::types::RequisitionNodeType;
 
+use self::backorder::*;
 use self::mutations::{request_requisition, response_requisition};
+use self::program::*;
+use self::reorder::*;
 use self::requisition_queries::*;
 #[derive(Default, Clone)]
 pub struct RequisitionQueries;
@@ -40,6 +46,41 @@ impl RequisitionQueries {
     ) -> Result<RequisitionResponse> {
         get_requisition_by_number(ctx, &store_id, requisition_number, r#type)
     }
+
+    /// Automatic reorder settings of the store
+    pub async fn reorder_config(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<ReorderConfigNode> {
+        get_reorder_config(ctx, &store_id)
+    }
+
+    /// Outcome of the latest automatic reorder run of the store
+    pub async fn last_reorder_run(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<Option<ReorderRunNode>> {
+        get_last_reorder_run(ctx, &store_id)
+    }
+
+    /// Quantities of finalised response requisitions that are still owed to customers
+    pub async fn backorders(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        page: Option<PaginationInput>,
+        filter: Option<BackorderFilterInput>,
+        sort: Option<Vec<BackorderSortInput>>,
+    ) -> Result<BackordersResponse> {
+        get_backorders(ctx, &store_id, page, filter, sort)
+    }
+
+    /// Programmes with a master list visible to the store, with the periods of their schedule
+    pub async fn programs(&self, ctx: &Context<'_>, store_id: String) -> Result<ProgramConnector> {
+        get_programs(ctx, &store_id)
+    }
 }
 
 #[derive(Default, Clone)]
@@ -56,6 +97,17 @@ impl RequisitionMutations {
         request_requisition::insert(ctx, &store_id, input)
     }
 
+    /// Create a request requisition for a programme period, lines are added for the items of the
+    /// programme's master list with the stock balances of the period
+    async fn insert_program_request_requisition(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: request_requisition::InsertProgramInput,
+    ) -> Result<request_requisition::InsertProgramResponse> {
+        request_requisition::insert_program(ctx, &store_id, input)
+    }
+
     async fn update_request_requisition(
         &self,
         ctx: &Context<'_>,
@@ -102,6 +154,18 @@ impl RequisitionMutations {
     ) -> Result<response_requisition::UpdateResponse> {
         response_requisition::update(ctx, &store_id, input)
     }
+
+    /// Approve a new response requisition, the supply quantity of the lines is set to the
+    /// approved quantity
+    async fn authorise_response_requisition(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: response_requisition::AuthoriseResponseRequisitionInput,
+    ) -> Result<response_requisition::AuthoriseResponseRequisitionResponse> {
+        response_requisition::authorise_response_requisition(ctx, &store_id, input)
+    }
+
     /// Set supply quantity to requested quantity
     async fn supply_requested_quantity(
         &self,
@@ -124,6 +188,60 @@ impl RequisitionMutations {
     ) -> Result<response_requisition::CreateRequisitionShipmentResponse> {
         response_requisition::create_requisition_shipment(ctx, &store_id, input)
     }
+
+    /// Create Outbound Shipment with placeholder lines for open backorders of a customer
+    /// Backorders are supplied oldest first, up to the available stock of each item
+    async fn create_backorder_shipment(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: CreateBackorderShipmentInput,
+    ) -> Result<CreateBackorderShipmentResponse> {
+        create_backorder_shipment(ctx, &store_id, input)
+    }
+
+    async fn update_reorder_config(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: UpdateReorderConfigInput,
+    ) -> Result<UpdateReorderConfigResponse> {
+        update_reorder_config(ctx, &store_id, input)
+    }
+
+    /// Creates a draft request requisition for the items of the store's master lists that are
+    /// below the configured minimum months of stock
+    async fn run_reorder(&self, ctx: &Context<'_>, store_id: String) -> Result<RunReorderResponse> {
+        run_reorder(ctx, &store_id)
+    }
+
+    /// Create the missing periods of a year for a period schedule
+    async fn generate_periods(
+        &self,
+        ctx: &Context<'_>,
+        input: GeneratePeriodsInput,
+    ) -> Result<PeriodConnector> {
+        generate_periods(ctx, input)
+    }
+
+    /// Create or update a period schedule, the type can't change once periods have been generated
+    async fn upsert_period_schedule(
+        &self,
+        ctx: &Context<'_>,
+        input: UpsertPeriodScheduleInput,
+    ) -> Result<PeriodScheduleNode> {
+        upsert_period_schedule(ctx, input)
+    }
+
+    /// Create or update a programme, the programme is available to the stores that can see its
+    /// master list
+    async fn upsert_program(
+        &self,
+        ctx: &Context<'_>,
+        input: UpsertProgramInput,
+    ) -> Result<ProgramNode> {
+        upsert_program(ctx, input)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,167 @@
+use async_graphql::*;
+use graphql_core::{
+    simple_generic_errors::{OtherPartyNotASupplier, OtherPartyNotVisible},
+    standard_graphql_error::validate_auth,
+    standard_graphql_error::StandardGraphqlError,
+    ContextExt,
+};
+use graphql_types::types::RequisitionNode;
+use repository::Requisition;
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    requisition::request_requisition::{
+        InsertProgramRequestRequisition as ServiceInput,
+        InsertProgramRequestRequisitionError as ServiceError,
+    },
+};
+
+#[derive(InputObject)]
+#[graphql(name = "InsertProgramRequestRequisitionInput")]
+pub struct InsertProgramInput {
+    pub id: String,
+    pub other_party_id: String,
+    pub program_id: String,
+    pub period_id: String,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub max_months_of_stock: f64,
+    pub min_months_of_stock: f64,
+}
+
+pub struct ProgramRequisitionAlreadyExists(pub String);
+#[Object]
+impl ProgramRequisitionAlreadyExists {
+    pub async fn description(&self) -> &'static str {
+        "A request requisition already exists for the programme and period"
+    }
+
+    pub async fn requisition_id(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(Interface)]
+#[graphql(name = "InsertProgramRequestRequisitionErrorInterface")]
+#[graphql(field(name = "description", type = "String"))]
+pub enum InsertProgramErrorInterface {
+    OtherPartyNotVisible(OtherPartyNotVisible),
+    OtherPartyNotASupplier(OtherPartyNotASupplier),
+    ProgramRequisitionAlreadyExists(ProgramRequisitionAlreadyExists),
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "InsertProgramRequestRequisitionError")]
+pub struct InsertProgramError {
+    pub error: InsertProgramErrorInterface,
+}
+
+#[derive(Union)]
+#[graphql(name = "InsertProgramRequestRequisitionResponse")]
+pub enum InsertProgramResponse {
+    Error(InsertProgramError),
+    Response(RequisitionNode),
+}
+
+pub fn insert_program(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: InsertProgramInput,
+) -> Result<InsertProgramResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    map_response(
+        service_provider
+            .requisition_service
+            .insert_program_request_requisition(
+                &service_context,
+                store_id,
+                &user.user_id,
+                input.to_domain(),
+            ),
+    )
+}
+
+pub fn map_response(from: Result<Requisition, ServiceError>) -> Result<InsertProgramResponse> {
+    let result = match from {
+        Ok(requisition) => {
+            InsertProgramResponse::Response(RequisitionNode::from_domain(requisition))
+        }
+        Err(error) => InsertProgramResponse::Error(InsertProgramError {
+            error: map_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl InsertProgramInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let InsertProgramInput {
+            id,
+            other_party_id,
+            program_id,
+            period_id,
+            their_reference,
+            comment,
+            max_months_of_stock,
+            min_months_of_stock,
+        } = self;
+
+        ServiceInput {
+            id,
+            other_party_id,
+            program_id,
+            period_id,
+            their_reference,
+            comment,
+            max_months_of_stock,
+            min_months_of_stock,
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<InsertProgramErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::OtherPartyNotASupplier => {
+            return Ok(InsertProgramErrorInterface::OtherPartyNotASupplier(
+                OtherPartyNotASupplier,
+            ))
+        }
+        ServiceError::OtherPartyNotVisible => {
+            return Ok(InsertProgramErrorInterface::OtherPartyNotVisible(
+                OtherPartyNotVisible,
+            ))
+        }
+        ServiceError::ProgramRequisitionAlreadyExists(requisition_id) => {
+            return Ok(
+                InsertProgramErrorInterface::ProgramRequisitionAlreadyExists(
+                    ProgramRequisitionAlreadyExists(requisition_id),
+                ),
+            )
+        }
+        // Standard Graphql Errors
+        ServiceError::RequisitionAlreadyExists => BadUserInput(formatted_error),
+        ServiceError::ProgramDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::PeriodDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::PeriodNotInProgramSchedule => BadUserInput(formatted_error),
+        ServiceError::OtherPartyDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::OtherPartyIsNotAStore => BadUserInput(formatted_error),
+        ServiceError::NewlyCreatedRequisitionDoesNotExist => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
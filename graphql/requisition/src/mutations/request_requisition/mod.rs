@@ -1,6 +1,9 @@
 pub mod insert;
 pub use insert::*;
 
+pub mod insert_program;
+pub use insert_program::*;
+
 pub mod delete;
 pub use delete::*;
 
@@ -0,0 +1,372 @@
+use async_graphql::*;
+use graphql_core::{
+    simple_generic_errors::{CannotEditRequisition, RecordNotFound},
+    standard_graphql_error::validate_auth,
+    standard_graphql_error::StandardGraphqlError,
+    ContextExt,
+};
+use graphql_types::types::RequisitionNode;
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    requisition::response_requisition::{
+        AuthoriseResponseRequisition as ServiceInput,
+        AuthoriseResponseRequisitionError as ServiceError,
+        AuthoriseResponseRequisitionLine as ServiceLineInput,
+    },
+};
+
+#[derive(InputObject)]
+pub struct AuthoriseResponseRequisitionLineInput {
+    pub id: String,
+    pub approved_quantity: u32,
+    pub approval_comment: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct AuthoriseResponseRequisitionInput {
+    pub id: String,
+    /// Lines that are not listed are approved at their supply quantity
+    pub lines: Option<Vec<AuthoriseResponseRequisitionLineInput>>,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum AuthoriseResponseRequisitionErrorInterface {
+    RecordNotFound(RecordNotFound),
+    CannotEditRequisition(CannotEditRequisition),
+}
+
+#[derive(SimpleObject)]
+pub struct AuthoriseResponseRequisitionError {
+    pub error: AuthoriseResponseRequisitionErrorInterface,
+}
+
+#[derive(Union)]
+pub enum AuthoriseResponseRequisitionResponse {
+    Error(AuthoriseResponseRequisitionError),
+    Response(RequisitionNode),
+}
+
+pub fn authorise_response_requisition(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: AuthoriseResponseRequisitionInput,
+) -> Result<AuthoriseResponseRequisitionResponse> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::AuthoriseRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let response = match service_provider
+        .requisition_service
+        .authorise_response_requisition(
+            &service_context,
+            store_id,
+            &user.user_id,
+            input.to_domain(),
+        ) {
+        Ok(requisition) => AuthoriseResponseRequisitionResponse::Response(
+            RequisitionNode::from_domain(requisition),
+        ),
+        Err(error) => {
+            AuthoriseResponseRequisitionResponse::Error(AuthoriseResponseRequisitionError {
+                error: map_error(error)?,
+            })
+        }
+    };
+
+    Ok(response)
+}
+
+impl AuthoriseResponseRequisitionInput {
+    pub fn to_domain(self) -> ServiceInput {
+        let AuthoriseResponseRequisitionInput { id, lines } = self;
+        ServiceInput {
+            id,
+            lines: lines
+                .unwrap_or_default()
+                .into_iter()
+                .map(
+                    |AuthoriseResponseRequisitionLineInput {
+                         id,
+                         approved_quantity,
+                         approval_comment,
+                     }| ServiceLineInput {
+                        id,
+                        approved_quantity,
+                        approval_comment,
+                    },
+                )
+                .collect(),
+        }
+    }
+}
+
+fn map_error(error: ServiceError) -> Result<AuthoriseResponseRequisitionErrorInterface> {
+    use StandardGraphqlError::*;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::RequisitionDoesNotExist => {
+            return Ok(AuthoriseResponseRequisitionErrorInterface::RecordNotFound(
+                RecordNotFound {},
+            ))
+        }
+        ServiceError::CannotEditRequisition => {
+            return Ok(
+                AuthoriseResponseRequisitionErrorInterface::CannotEditRequisition(
+                    CannotEditRequisition {},
+                ),
+            )
+        }
+        // Standard Graphql Errors
+        ServiceError::NotThisStoreRequisition => BadUserInput(formatted_error),
+        ServiceError::NotAResponseRequisition => BadUserInput(formatted_error),
+        ServiceError::RequisitionLineDoesNotExist(_) => BadUserInput(formatted_error),
+        ServiceError::UpdatedRequisitionDoesNotExist => InternalError(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::RequisitionMutations;
+    use async_graphql::EmptyMutation;
+    use graphql_core::{
+        assert_graphql_query, assert_standard_graphql_error, test_helpers::setup_graphl_test,
+    };
+    use repository::{
+        mock::{mock_name_a, mock_new_response_requisition, MockDataInserts},
+        Requisition, StorageConnectionManager,
+    };
+    use serde_json::json;
+
+    use service::{
+        requisition::{
+            response_requisition::{
+                AuthoriseResponseRequisition as ServiceInput,
+                AuthoriseResponseRequisitionError as ServiceError,
+                AuthoriseResponseRequisitionLine as ServiceLineInput,
+            },
+            RequisitionServiceTrait,
+        },
+        service_provider::{ServiceContext, ServiceProvider},
+    };
+
+    type AuthoriseMethod =
+        dyn Fn(&str, ServiceInput) -> Result<Requisition, ServiceError> + Sync + Send;
+
+    pub struct TestService(pub Box<AuthoriseMethod>);
+
+    impl RequisitionServiceTrait for TestService {
+        fn authorise_response_requisition(
+            &self,
+            _: &ServiceContext,
+            store_id: &str,
+            _: &str,
+            input: ServiceInput,
+        ) -> Result<Requisition, ServiceError> {
+            self.0(store_id, input)
+        }
+    }
+
+    fn service_provider(
+        test_service: TestService,
+        connection_manager: &StorageConnectionManager,
+    ) -> ServiceProvider {
+        let mut service_provider = ServiceProvider::new(connection_manager.clone());
+        service_provider.requisition_service = Box::new(test_service);
+        service_provider
+    }
+
+    fn empty_variables() -> serde_json::Value {
+        json!({
+          "input": {
+            "id": "n/a",
+          },
+          "storeId": "n/a"
+        })
+    }
+
+    #[actix_rt::test]
+    async fn test_graphql_authorise_response_requisition_errors() {
+        let (_, _, connection_manager, settings) = setup_graphl_test(
+            EmptyMutation,
+            RequisitionMutations,
+            "test_graphql_authorise_response_requisition_errors",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let mutation = r#"
+        mutation ($input: AuthoriseResponseRequisitionInput!, $storeId: String) {
+            authoriseResponseRequisition(storeId: $storeId, input: $input) {
+              ... on AuthoriseResponseRequisitionError {
+                error {
+                  __typename
+                }
+              }
+            }
+          }
+        "#;
+
+        // RequisitionDoesNotExist
+        let test_service = TestService(Box::new(|_, _| Err(ServiceError::RequisitionDoesNotExist)));
+
+        let expected = json!({
+            "authoriseResponseRequisition": {
+              "error": {
+                "__typename": "RecordNotFound"
+              }
+            }
+          }
+        );
+
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &Some(empty_variables()),
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // CannotEditRequisition
+        let test_service = TestService(Box::new(|_, _| Err(ServiceError::CannotEditRequisition)));
+
+        let expected = json!({
+            "authoriseResponseRequisition": {
+              "error": {
+                "__typename": "CannotEditRequisition"
+              }
+            }
+          }
+        );
+
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &Some(empty_variables()),
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // NotThisStoreRequisition
+        let test_service = TestService(Box::new(|_, _| Err(ServiceError::NotThisStoreRequisition)));
+        let expected_message = "Bad user input";
+        assert_standard_graphql_error!(
+            &settings,
+            &mutation,
+            &Some(empty_variables()),
+            &expected_message,
+            None,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // RequisitionLineDoesNotExist
+        let test_service = TestService(Box::new(|_, _| {
+            Err(ServiceError::RequisitionLineDoesNotExist(
+                "line id".to_string(),
+            ))
+        }));
+        let expected_message = "Bad user input";
+        assert_standard_graphql_error!(
+            &settings,
+            &mutation,
+            &Some(empty_variables()),
+            &expected_message,
+            None,
+            Some(service_provider(test_service, &connection_manager))
+        );
+
+        // UpdatedRequisitionDoesNotExist
+        let test_service = TestService(Box::new(|_, _| {
+            Err(ServiceError::UpdatedRequisitionDoesNotExist)
+        }));
+        let expected_message = "Internal error";
+        assert_standard_graphql_error!(
+            &settings,
+            &mutation,
+            &Some(empty_variables()),
+            &expected_message,
+            None,
+            Some(service_provider(test_service, &connection_manager))
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_graphql_authorise_response_requisition_success() {
+        let (_, _, connection_manager, settings) = setup_graphl_test(
+            EmptyMutation,
+            RequisitionMutations,
+            "test_graphql_authorise_response_requisition_success",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let mutation = r#"
+        mutation ($storeId: String, $input: AuthoriseResponseRequisitionInput!) {
+            authoriseResponseRequisition(storeId: $storeId, input: $input) {
+                ... on RequisitionNode {
+                    id
+                }
+            }
+          }
+        "#;
+
+        // Success
+        let test_service = TestService(Box::new(|store_id, input| {
+            assert_eq!(store_id, "store_a");
+            assert_eq!(
+                input,
+                ServiceInput {
+                    id: "id input".to_string(),
+                    lines: vec![ServiceLineInput {
+                        id: "line id input".to_string(),
+                        approved_quantity: 5,
+                        approval_comment: Some("comment input".to_string()),
+                    }]
+                }
+            );
+            Ok(Requisition {
+                requisition_row: mock_new_response_requisition(),
+                name_row: mock_name_a(),
+            })
+        }));
+
+        let variables = json!({
+          "input": {
+            "id": "id input",
+            "lines": [{
+              "id": "line id input",
+              "approvedQuantity": 5,
+              "approvalComment": "comment input"
+            }]
+          },
+          "storeId": "store_a"
+        });
+
+        let expected = json!({
+            "authoriseResponseRequisition": {
+                "id": mock_new_response_requisition().id
+            }
+          }
+        );
+
+        assert_graphql_query!(
+            &settings,
+            mutation,
+            &Some(variables),
+            &expected,
+            Some(service_provider(test_service, &connection_manager))
+        );
+    }
+}
@@ -25,6 +25,7 @@ pub enum DeleteErrorInterface {
     RecordNotFound(RecordNotFound),
     NothingRemainingToSupply(NothingRemainingToSupply),
     CannotEditRequisition(CannotEditRequisition),
+    RequisitionNotAuthorised(RequisitionNotAuthorised),
 }
 
 #[derive(SimpleObject)]
@@ -101,6 +102,11 @@ fn map_error(error: ServiceError) -> Result<DeleteErrorInterface> {
                 NothingRemainingToSupply {},
             ))
         }
+        ServiceError::RequisitionNotAuthorised => {
+            return Ok(DeleteErrorInterface::RequisitionNotAuthorised(
+                RequisitionNotAuthorised {},
+            ))
+        }
         // Standard Graphql Errors
         ServiceError::NotThisStoreRequisition => BadUserInput(formatted_error),
         ServiceError::NotAResponseRequisition => BadUserInput(formatted_error),
@@ -121,6 +127,14 @@ impl NothingRemainingToSupply {
     }
 }
 
+pub struct RequisitionNotAuthorised;
+#[Object]
+impl RequisitionNotAuthorised {
+    pub async fn description(&self) -> &'static str {
+        "Requisition needs to be authorised before it can be supplied"
+    }
+}
+
 #[cfg(test)]
 mod test {
     use async_graphql::EmptyMutation;
@@ -6,3 +6,6 @@ pub use create_requisition_shipment::*;
 
 mod supply_requested_quantity;
 pub use supply_requested_quantity::*;
+
+mod authorise;
+pub use authorise::*;
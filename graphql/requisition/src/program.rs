@@ -0,0 +1,307 @@
+use async_graphql::*;
+use chrono::NaiveDate;
+use graphql_core::standard_graphql_error::{validate_auth, StandardGraphqlError};
+use graphql_core::ContextExt;
+use repository::{PeriodRow, PeriodScheduleRow, PeriodScheduleType};
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    program::{
+        generate_periods::{GeneratePeriods, GeneratePeriodsError as ServiceError},
+        query::Program,
+        upsert_period_schedule::{UpsertPeriodSchedule, UpsertPeriodScheduleError},
+        upsert_program::{UpsertProgram, UpsertProgramError},
+    },
+};
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PeriodScheduleNodeType {
+    Monthly,
+    Quarterly,
+}
+
+impl PeriodScheduleNodeType {
+    pub fn from_domain(from: &PeriodScheduleType) -> PeriodScheduleNodeType {
+        match from {
+            PeriodScheduleType::Monthly => PeriodScheduleNodeType::Monthly,
+            PeriodScheduleType::Quarterly => PeriodScheduleNodeType::Quarterly,
+        }
+    }
+
+    pub fn to_domain(self) -> PeriodScheduleType {
+        match self {
+            PeriodScheduleNodeType::Monthly => PeriodScheduleType::Monthly,
+            PeriodScheduleNodeType::Quarterly => PeriodScheduleType::Quarterly,
+        }
+    }
+}
+
+pub struct PeriodScheduleNode {
+    period_schedule: PeriodScheduleRow,
+}
+
+#[Object]
+impl PeriodScheduleNode {
+    pub async fn id(&self) -> &str {
+        &self.period_schedule.id
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.period_schedule.name
+    }
+
+    pub async fn r#type(&self) -> PeriodScheduleNodeType {
+        PeriodScheduleNodeType::from_domain(&self.period_schedule.r#type)
+    }
+}
+
+pub struct PeriodNode {
+    period: PeriodRow,
+}
+
+#[Object]
+impl PeriodNode {
+    pub async fn id(&self) -> &str {
+        &self.period.id
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.period.name
+    }
+
+    pub async fn start_date(&self) -> NaiveDate {
+        self.period.start_date
+    }
+
+    /// Last day of the period (inclusive)
+    pub async fn end_date(&self) -> NaiveDate {
+        self.period.end_date
+    }
+}
+
+pub struct ProgramNode {
+    program: Program,
+}
+
+#[Object]
+impl ProgramNode {
+    pub async fn id(&self) -> &str {
+        &self.program.program_row.id
+    }
+
+    pub async fn name(&self) -> &str {
+        &self.program.program_row.name
+    }
+
+    /// Items of the master list are requested in the programme requisitions
+    pub async fn master_list_id(&self) -> &str {
+        &self.program.program_row.master_list_id
+    }
+
+    pub async fn period_schedule_id(&self) -> &str {
+        &self.program.program_row.period_schedule_id
+    }
+
+    pub async fn period_schedule_type(&self) -> PeriodScheduleNodeType {
+        PeriodScheduleNodeType::from_domain(&self.program.period_schedule_row.r#type)
+    }
+
+    /// Periods of the programme's schedule, ordered by start date
+    pub async fn periods(&self) -> Vec<PeriodNode> {
+        to_period_nodes(self.program.periods.clone())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ProgramConnector {
+    total_count: u32,
+    nodes: Vec<ProgramNode>,
+}
+
+pub fn get_programs(ctx: &Context<'_>, store_id: &str) -> Result<ProgramConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let programs = service_provider
+        .program_service
+        .get_programs(&service_context, store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(ProgramConnector {
+        total_count: programs.len() as u32,
+        nodes: programs
+            .into_iter()
+            .map(|program| ProgramNode { program })
+            .collect(),
+    })
+}
+
+#[derive(InputObject)]
+pub struct GeneratePeriodsInput {
+    pub period_schedule_id: String,
+    pub year: i32,
+}
+
+#[derive(SimpleObject)]
+pub struct PeriodConnector {
+    total_count: u32,
+    nodes: Vec<PeriodNode>,
+}
+
+/// Returns all periods of the schedule
+pub fn generate_periods(ctx: &Context<'_>, input: GeneratePeriodsInput) -> Result<PeriodConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let periods = service_provider
+        .program_service
+        .generate_periods(
+            &service_context,
+            GeneratePeriods {
+                period_schedule_id: input.period_schedule_id,
+                year: input.year,
+            },
+        )
+        .map_err(|error| {
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                ServiceError::PeriodScheduleDoesNotExist => {
+                    StandardGraphqlError::BadUserInput(formatted_error)
+                }
+                ServiceError::InvalidYear => StandardGraphqlError::BadUserInput(formatted_error),
+                ServiceError::DatabaseError(_) => {
+                    StandardGraphqlError::InternalError(formatted_error)
+                }
+            };
+            graphql_error.extend()
+        })?;
+
+    Ok(PeriodConnector {
+        total_count: periods.len() as u32,
+        nodes: to_period_nodes(periods),
+    })
+}
+
+#[derive(InputObject)]
+pub struct UpsertPeriodScheduleInput {
+    pub id: String,
+    pub name: String,
+    pub r#type: PeriodScheduleNodeType,
+}
+
+pub fn upsert_period_schedule(
+    ctx: &Context<'_>,
+    input: UpsertPeriodScheduleInput,
+) -> Result<PeriodScheduleNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let period_schedule = service_provider
+        .program_service
+        .upsert_period_schedule(
+            &service_context,
+            UpsertPeriodSchedule {
+                id: input.id,
+                name: input.name,
+                r#type: input.r#type.to_domain(),
+            },
+        )
+        .map_err(|error| {
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                UpsertPeriodScheduleError::PeriodScheduleTypeCannotChange => {
+                    StandardGraphqlError::BadUserInput(formatted_error)
+                }
+                UpsertPeriodScheduleError::DatabaseError(_) => {
+                    StandardGraphqlError::InternalError(formatted_error)
+                }
+            };
+            graphql_error.extend()
+        })?;
+
+    Ok(PeriodScheduleNode { period_schedule })
+}
+
+#[derive(InputObject)]
+pub struct UpsertProgramInput {
+    pub id: String,
+    pub name: String,
+    /// Items of the master list are requested in the programme requisitions
+    pub master_list_id: String,
+    pub period_schedule_id: String,
+}
+
+pub fn upsert_program(ctx: &Context<'_>, input: UpsertProgramInput) -> Result<ProgramNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::ServerAdmin,
+            store_id: None,
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let program = service_provider
+        .program_service
+        .upsert_program(
+            &service_context,
+            UpsertProgram {
+                id: input.id,
+                name: input.name,
+                master_list_id: input.master_list_id,
+                period_schedule_id: input.period_schedule_id,
+            },
+        )
+        .map_err(|error| {
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                UpsertProgramError::MasterListDoesNotExist => {
+                    StandardGraphqlError::BadUserInput(formatted_error)
+                }
+                UpsertProgramError::PeriodScheduleDoesNotExist => {
+                    StandardGraphqlError::BadUserInput(formatted_error)
+                }
+                UpsertProgramError::UpsertedRecordNotFound => {
+                    StandardGraphqlError::InternalError(formatted_error)
+                }
+                UpsertProgramError::DatabaseError(_) => {
+                    StandardGraphqlError::InternalError(formatted_error)
+                }
+            };
+            graphql_error.extend()
+        })?;
+
+    Ok(ProgramNode { program })
+}
+
+fn to_period_nodes(periods: Vec<PeriodRow>) -> Vec<PeriodNode> {
+    periods
+        .into_iter()
+        .map(|period| PeriodNode { period })
+        .collect()
+}
@@ -178,6 +178,8 @@ mod graphql {
                 their_reference,
                 comment,
                 linked_requisition_id: _,
+                program_id: _,
+                period_id: _,
                 store_id: _,
             } = filter.unwrap();
 
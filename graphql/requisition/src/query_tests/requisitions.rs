@@ -178,6 +178,8 @@ mod graphql {
                 comment,
                 store_id: _,
                 linked_requisition_id: _,
+                program_id: _,
+                period_id: _,
             } = filter.unwrap();
 
             assert_eq!(id, Some(EqualFilter::not_equal_to("id_not_equal_to")));
@@ -0,0 +1,293 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use graphql_core::{
+    simple_generic_errors::{OtherPartyNotASupplier, OtherPartyNotVisible},
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::RequisitionNode;
+use repository::{ReorderConfigRow, ReorderRunRow, Requisition};
+use service::{
+    permission_validation::{Resource, ResourceAccessRequest},
+    reorder::{
+        config::{UpdateReorderConfig, UpdateReorderConfigError as UpdateServiceError},
+        run::RunReorderError as RunReorderServiceError,
+    },
+    service_provider::{ServiceContext, ServiceProvider},
+};
+
+pub struct ReorderConfigNode {
+    config: ReorderConfigRow,
+}
+
+#[Object]
+impl ReorderConfigNode {
+    /// Reorder runs daily if enabled, it can always be run on demand
+    pub async fn is_enabled(&self) -> bool {
+        self.config.is_enabled
+    }
+
+    pub async fn supplier_id(&self) -> &Option<String> {
+        &self.config.supplier_id
+    }
+
+    pub async fn min_months_of_stock(&self) -> f64 {
+        self.config.min_months_of_stock
+    }
+
+    pub async fn max_months_of_stock(&self) -> f64 {
+        self.config.max_months_of_stock
+    }
+}
+
+pub struct ReorderRunNode {
+    run: ReorderRunRow,
+    requisition: Option<Requisition>,
+}
+
+#[Object]
+impl ReorderRunNode {
+    pub async fn id(&self) -> &str {
+        &self.run.id
+    }
+
+    pub async fn run_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(self.run.run_datetime, Utc)
+    }
+
+    /// Number of items added to the request requisition
+    pub async fn number_of_items(&self) -> i32 {
+        self.run.number_of_items
+    }
+
+    /// Reason the run failed
+    pub async fn error(&self) -> &Option<String> {
+        &self.run.error
+    }
+
+    /// Request requisition created by the run, not set if no item needed to be reordered
+    pub async fn requisition(&self) -> Option<RequisitionNode> {
+        self.requisition.clone().map(RequisitionNode::from_domain)
+    }
+}
+
+impl ReorderRunNode {
+    fn from_domain(
+        service_provider: &ServiceProvider,
+        ctx: &ServiceContext,
+        store_id: &str,
+        run: ReorderRunRow,
+    ) -> Result<ReorderRunNode> {
+        let requisition = match &run.requisition_id {
+            Some(requisition_id) => service_provider
+                .requisition_service
+                .get_requisition(ctx, Some(store_id), requisition_id)
+                .map_err(StandardGraphqlError::from_repository_error)?,
+            None => None,
+        };
+        Ok(ReorderRunNode { run, requisition })
+    }
+}
+
+pub fn get_reorder_config(ctx: &Context<'_>, store_id: &str) -> Result<ReorderConfigNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let config = service_provider
+        .reorder_service
+        .get_reorder_config(&service_context, store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(ReorderConfigNode { config })
+}
+
+pub fn get_last_reorder_run(ctx: &Context<'_>, store_id: &str) -> Result<Option<ReorderRunNode>> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let run = service_provider
+        .reorder_service
+        .get_last_reorder_run(&service_context, store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    run.map(|run| ReorderRunNode::from_domain(service_provider, &service_context, store_id, run))
+        .transpose()
+}
+
+#[derive(InputObject)]
+pub struct UpdateReorderConfigInput {
+    pub is_enabled: bool,
+    /// Default supplier of the generated request requisitions
+    pub supplier_id: Option<String>,
+    pub min_months_of_stock: f64,
+    pub max_months_of_stock: f64,
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum UpdateReorderConfigErrorInterface {
+    OtherPartyNotVisible(OtherPartyNotVisible),
+    OtherPartyNotASupplier(OtherPartyNotASupplier),
+}
+
+#[derive(SimpleObject)]
+pub struct UpdateReorderConfigError {
+    pub error: UpdateReorderConfigErrorInterface,
+}
+
+#[derive(Union)]
+pub enum UpdateReorderConfigResponse {
+    Error(UpdateReorderConfigError),
+    Response(ReorderConfigNode),
+}
+
+pub fn update_reorder_config(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpdateReorderConfigInput,
+) -> Result<UpdateReorderConfigResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let result = match service_provider.reorder_service.update_reorder_config(
+        &service_context,
+        store_id,
+        input.to_domain(),
+    ) {
+        Ok(config) => UpdateReorderConfigResponse::Response(ReorderConfigNode { config }),
+        Err(error) => UpdateReorderConfigResponse::Error(UpdateReorderConfigError {
+            error: map_update_error(error)?,
+        }),
+    };
+
+    Ok(result)
+}
+
+impl UpdateReorderConfigInput {
+    pub fn to_domain(self) -> UpdateReorderConfig {
+        let UpdateReorderConfigInput {
+            is_enabled,
+            supplier_id,
+            min_months_of_stock,
+            max_months_of_stock,
+        } = self;
+
+        UpdateReorderConfig {
+            is_enabled,
+            supplier_id,
+            min_months_of_stock,
+            max_months_of_stock,
+        }
+    }
+}
+
+fn map_update_error(error: UpdateServiceError) -> Result<UpdateReorderConfigErrorInterface> {
+    use StandardGraphqlError::*;
+    use UpdateServiceError as ServiceError;
+    let formatted_error = format!("{:#?}", error);
+
+    let graphql_error = match error {
+        // Structured Errors
+        ServiceError::SupplierNotASupplier => {
+            return Ok(UpdateReorderConfigErrorInterface::OtherPartyNotASupplier(
+                OtherPartyNotASupplier,
+            ))
+        }
+        ServiceError::SupplierNotVisible => {
+            return Ok(UpdateReorderConfigErrorInterface::OtherPartyNotVisible(
+                OtherPartyNotVisible,
+            ))
+        }
+        // Standard Graphql Errors
+        ServiceError::SupplierDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::SupplierIsNotAStore => BadUserInput(formatted_error),
+        ServiceError::InvalidMonthsOfStock => BadUserInput(formatted_error),
+        ServiceError::DatabaseError(_) => InternalError(formatted_error),
+    };
+
+    Err(graphql_error.extend())
+}
+
+pub struct NoSupplierConfigured;
+#[Object]
+impl NoSupplierConfigured {
+    pub async fn description(&self) -> &'static str {
+        "No supplier is configured for automatic reorder"
+    }
+}
+
+#[derive(Interface)]
+#[graphql(field(name = "description", type = "String"))]
+pub enum RunReorderErrorInterface {
+    NoSupplierConfigured(NoSupplierConfigured),
+}
+
+#[derive(SimpleObject)]
+pub struct RunReorderError {
+    pub error: RunReorderErrorInterface,
+}
+
+#[derive(Union)]
+pub enum RunReorderResponse {
+    Error(RunReorderError),
+    Response(ReorderRunNode),
+}
+
+pub fn run_reorder(ctx: &Context<'_>, store_id: &str) -> Result<RunReorderResponse> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateRequisition,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let run = match service_provider
+        .reorder_service
+        .run_reorder(&service_context, store_id)
+    {
+        Ok(run) => run,
+        Err(RunReorderServiceError::NoSupplierConfigured) => {
+            return Ok(RunReorderResponse::Error(RunReorderError {
+                error: RunReorderErrorInterface::NoSupplierConfigured(NoSupplierConfigured),
+            }))
+        }
+        Err(error @ RunReorderServiceError::DatabaseError(_)) => {
+            return Err(StandardGraphqlError::InternalError(format!("{:#?}", error)).extend())
+        }
+    };
+
+    Ok(RunReorderResponse::Response(ReorderRunNode::from_domain(
+        service_provider,
+        &service_context,
+        store_id,
+        run,
+    )?))
+}
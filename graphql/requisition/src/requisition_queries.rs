@@ -67,6 +67,8 @@ pub struct RequisitionFilterInput {
     pub colour: Option<EqualFilterStringInput>,
     pub their_reference: Option<SimpleStringFilterInput>,
     pub comment: Option<SimpleStringFilterInput>,
+    pub program_id: Option<EqualFilterStringInput>,
+    pub period_id: Option<EqualFilterStringInput>,
 }
 
 #[derive(Union)]
@@ -223,6 +225,8 @@ impl RequisitionFilterInput {
             comment: self.comment.map(SimpleStringFilter::from),
             linked_requisition_id: None,
             store_id: None,
+            program_id: self.program_id.map(EqualFilter::from),
+            period_id: self.period_id.map(EqualFilter::from),
         }
     }
 }
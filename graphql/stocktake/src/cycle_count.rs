@@ -0,0 +1,287 @@
+use async_graphql::{dataloader::DataLoader, *};
+use chrono::{DateTime, NaiveDate, Utc};
+use graphql_core::{
+    loader::ItemLoader,
+    standard_graphql_error::{validate_auth, StandardGraphqlError},
+    ContextExt,
+};
+use graphql_types::types::{ItemNode, StocktakeNode};
+use repository::{AbcClass, CycleCountConfigRow, ItemCycleCountRow};
+use service::{
+    cycle_count::config::{
+        UpdateCycleCountConfig, UpdateCycleCountConfigError as UpdateServiceError,
+    },
+    permission_validation::{Resource, ResourceAccessRequest},
+};
+
+pub struct CycleCountConfigNode {
+    config: CycleCountConfigRow,
+}
+
+#[Object]
+impl CycleCountConfigNode {
+    /// Items are classified and the cycle count is generated daily if enabled, both can always be
+    /// done on demand
+    pub async fn is_enabled(&self) -> bool {
+        self.config.is_enabled
+    }
+
+    /// Items making up this share of the yearly issue value (from the highest value down) are
+    /// class A
+    pub async fn a_class_percentage(&self) -> f64 {
+        self.config.a_class_percentage
+    }
+
+    /// Items making up the next share of the yearly issue value are class B, the rest are class C
+    pub async fn b_class_percentage(&self) -> f64 {
+        self.config.b_class_percentage
+    }
+
+    pub async fn a_class_count_frequency_days(&self) -> i32 {
+        self.config.a_class_count_frequency_days
+    }
+
+    pub async fn b_class_count_frequency_days(&self) -> i32 {
+        self.config.b_class_count_frequency_days
+    }
+
+    pub async fn c_class_count_frequency_days(&self) -> i32 {
+        self.config.c_class_count_frequency_days
+    }
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum AbcClassNode {
+    A,
+    B,
+    C,
+}
+
+impl AbcClassNode {
+    pub fn from_domain(from: &AbcClass) -> AbcClassNode {
+        match from {
+            AbcClass::A => AbcClassNode::A,
+            AbcClass::B => AbcClassNode::B,
+            AbcClass::C => AbcClassNode::C,
+        }
+    }
+}
+
+pub struct ItemCycleCountNode {
+    item_cycle_count: ItemCycleCountRow,
+}
+
+#[Object]
+impl ItemCycleCountNode {
+    pub async fn id(&self) -> &str {
+        &self.item_cycle_count.id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.item_cycle_count.item_id
+    }
+
+    pub async fn abc_class(&self) -> AbcClassNode {
+        AbcClassNode::from_domain(&self.item_cycle_count.abc_class)
+    }
+
+    /// Value of the stock issued over the last year when the item was classified
+    pub async fn issue_value(&self) -> f64 {
+        self.item_cycle_count.issue_value
+    }
+
+    pub async fn classified_datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(self.item_cycle_count.classified_datetime, Utc)
+    }
+
+    /// Set when a stocktake counting the item is finalised
+    pub async fn last_counted_datetime(&self) -> Option<DateTime<Utc>> {
+        self.item_cycle_count
+            .last_counted_datetime
+            .map(|datetime| DateTime::<Utc>::from_utc(datetime, Utc))
+    }
+
+    pub async fn item(&self, ctx: &Context<'_>) -> Result<Option<ItemNode>> {
+        let loader = ctx.get_loader::<DataLoader<ItemLoader>>();
+        Ok(loader
+            .load_one(self.item_cycle_count.item_id.clone())
+            .await?
+            .map(ItemNode::from_domain))
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ItemCycleCountConnector {
+    total_count: u32,
+    nodes: Vec<ItemCycleCountNode>,
+}
+
+impl ItemCycleCountConnector {
+    fn from_domain(rows: Vec<ItemCycleCountRow>) -> ItemCycleCountConnector {
+        ItemCycleCountConnector {
+            total_count: rows.len() as u32,
+            nodes: rows
+                .into_iter()
+                .map(|item_cycle_count| ItemCycleCountNode { item_cycle_count })
+                .collect(),
+        }
+    }
+}
+
+pub fn get_cycle_count_config(ctx: &Context<'_>, store_id: &str) -> Result<CycleCountConfigNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStocktake,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let config = service_provider
+        .cycle_count_service
+        .get_cycle_count_config(&service_context, store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(CycleCountConfigNode { config })
+}
+
+pub fn get_item_cycle_counts(ctx: &Context<'_>, store_id: &str) -> Result<ItemCycleCountConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::QueryStocktake,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let rows = service_provider
+        .cycle_count_service
+        .get_item_cycle_counts(&service_context, store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(ItemCycleCountConnector::from_domain(rows))
+}
+
+#[derive(InputObject)]
+pub struct UpdateCycleCountConfigInput {
+    pub is_enabled: bool,
+    pub a_class_percentage: f64,
+    pub b_class_percentage: f64,
+    pub a_class_count_frequency_days: i32,
+    pub b_class_count_frequency_days: i32,
+    pub c_class_count_frequency_days: i32,
+}
+
+impl UpdateCycleCountConfigInput {
+    pub fn to_domain(self) -> UpdateCycleCountConfig {
+        let UpdateCycleCountConfigInput {
+            is_enabled,
+            a_class_percentage,
+            b_class_percentage,
+            a_class_count_frequency_days,
+            b_class_count_frequency_days,
+            c_class_count_frequency_days,
+        } = self;
+
+        UpdateCycleCountConfig {
+            is_enabled,
+            a_class_percentage,
+            b_class_percentage,
+            a_class_count_frequency_days,
+            b_class_count_frequency_days,
+            c_class_count_frequency_days,
+        }
+    }
+}
+
+pub fn update_cycle_count_config(
+    ctx: &Context<'_>,
+    store_id: &str,
+    input: UpdateCycleCountConfigInput,
+) -> Result<CycleCountConfigNode> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStocktake,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let config = service_provider
+        .cycle_count_service
+        .update_cycle_count_config(&service_context, store_id, input.to_domain())
+        .map_err(|error| {
+            use StandardGraphqlError::*;
+            let formatted_error = format!("{:#?}", error);
+            let graphql_error = match error {
+                UpdateServiceError::InvalidClassPercentage => BadUserInput(formatted_error),
+                UpdateServiceError::CountFrequencyNotPositive => BadUserInput(formatted_error),
+                UpdateServiceError::DatabaseError(_) => InternalError(formatted_error),
+            };
+            graphql_error.extend()
+        })?;
+
+    Ok(CycleCountConfigNode { config })
+}
+
+/// Classifies the items of the store now instead of waiting for the next scheduled run
+pub fn classify_items(ctx: &Context<'_>, store_id: &str) -> Result<ItemCycleCountConnector> {
+    validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStocktake,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let rows = service_provider
+        .cycle_count_service
+        .classify_items(&service_context, store_id)
+        .map_err(StandardGraphqlError::from_repository_error)?;
+
+    Ok(ItemCycleCountConnector::from_domain(rows))
+}
+
+/// Creates the cycle count stocktake for the date (today if not set), returns null if no item is
+/// due to be counted
+pub fn generate_cycle_count(
+    ctx: &Context<'_>,
+    store_id: &str,
+    date: Option<NaiveDate>,
+) -> Result<Option<StocktakeNode>> {
+    let user = validate_auth(
+        ctx,
+        &ResourceAccessRequest {
+            resource: Resource::MutateStocktake,
+            store_id: Some(store_id.to_string()),
+        },
+    )?;
+
+    let service_provider = ctx.service_provider();
+    let service_context = service_provider.context()?;
+
+    let stocktake = service_provider
+        .cycle_count_service
+        .generate_cycle_count(
+            &service_context,
+            store_id,
+            &user.user_id,
+            date.unwrap_or_else(|| Utc::now().naive_utc().date()),
+        )
+        // Only database and internal errors
+        .map_err(|error| StandardGraphqlError::InternalError(format!("{:#?}", error)).extend())?;
+
+    Ok(stocktake.map(StocktakeNode::from_domain))
+}
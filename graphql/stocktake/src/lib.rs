@@ -1,8 +1,12 @@
+mod cycle_count;
 pub mod mutations;
 mod stocktake_queries;
+use self::cycle_count::*;
 use self::stocktake_queries::*;
 use async_graphql::*;
+use chrono::NaiveDate;
 use graphql_core::pagination::PaginationInput;
+use graphql_types::types::StocktakeNode;
 
 #[derive(Default, Clone)]
 pub struct StocktakeQueries;
@@ -37,6 +41,24 @@ impl StocktakeQueries {
     ) -> Result<StocktakesResponse> {
         stocktakes(ctx, &store_id, page, filter, sort)
     }
+
+    /// Cycle count settings of the store
+    pub async fn cycle_count_config(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<CycleCountConfigNode> {
+        get_cycle_count_config(ctx, &store_id)
+    }
+
+    /// ABC class and last count of the classified items of the store
+    pub async fn item_cycle_counts(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<ItemCycleCountConnector> {
+        get_item_cycle_counts(ctx, &store_id)
+    }
 }
 
 #[derive(Default, Clone)]
@@ -70,4 +92,34 @@ impl StocktakeMutations {
     ) -> Result<mutations::DeleteResponse> {
         mutations::delete(ctx, &store_id, input)
     }
+
+    /// Replaces the cycle count settings of the store
+    async fn update_cycle_count_config(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        input: UpdateCycleCountConfigInput,
+    ) -> Result<CycleCountConfigNode> {
+        update_cycle_count_config(ctx, &store_id, input)
+    }
+
+    /// Classifies the items of the store now instead of waiting for the next scheduled run
+    async fn classify_items(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+    ) -> Result<ItemCycleCountConnector> {
+        classify_items(ctx, &store_id)
+    }
+
+    /// Creates the cycle count stocktake with the items due to be counted on the date (today if
+    /// not set), returns null if no item is due
+    async fn generate_cycle_count(
+        &self,
+        ctx: &Context<'_>,
+        store_id: String,
+        date: Option<NaiveDate>,
+    ) -> Result<Option<StocktakeNode>> {
+        generate_cycle_count(ctx, &store_id, date)
+    }
 }
@@ -8,7 +8,10 @@ use graphql_types::types::StocktakeNode;
 use repository::Stocktake;
 use service::{
     permission_validation::{Resource, ResourceAccessRequest},
-    stocktake::{InsertStocktake as ServiceInput, InsertStocktakeError as ServiceError},
+    stocktake::{
+        GenerateStocktakeLines, InsertStocktake as ServiceInput,
+        InsertStocktakeError as ServiceError,
+    },
 };
 
 #[derive(InputObject)]
@@ -19,6 +22,24 @@ pub struct InsertInput {
     pub description: Option<String>,
     pub is_locked: Option<bool>,
     pub stocktake_date: Option<NaiveDate>,
+    /// Snapshot quantities are hidden until the count is submitted and the stocktake has to be
+    /// finalised by a different user than the counter
+    pub is_blind_count: Option<bool>,
+    /// Blind count lines with a larger discrepancy (in packs) need a second count
+    pub recount_threshold: Option<u32>,
+    /// Adds lines for all in stock batches of the store matching the given options
+    pub generate_lines: Option<GenerateStocktakeLinesInput>,
+}
+
+#[derive(InputObject)]
+pub struct GenerateStocktakeLinesInput {
+    pub location_id: Option<String>,
+    /// Only batches of items on this master list
+    pub master_list_id: Option<String>,
+    /// Only batches expiring before this date
+    pub expires_before: Option<NaiveDate>,
+    /// Only batches of these items
+    pub item_ids: Option<Vec<String>>,
 }
 
 #[derive(Union)]
@@ -71,6 +92,8 @@ pub fn map_response(from: Result<Stocktake, ServiceError>) -> Result<InsertRespo
             let graphql_error = match error {
                 ServiceError::InvalidStore => BadUserInput(formatted_error),
                 ServiceError::StocktakeAlreadyExists => BadUserInput(formatted_error),
+                ServiceError::LocationDoesNotExist => BadUserInput(formatted_error),
+                ServiceError::MasterListNotFoundForThisStore => BadUserInput(formatted_error),
                 ServiceError::InternalError(err) => InternalError(err),
                 ServiceError::DatabaseError(_) => InternalError(formatted_error),
             };
@@ -88,6 +111,9 @@ impl InsertInput {
             description,
             stocktake_date,
             is_locked,
+            is_blind_count,
+            recount_threshold,
+            generate_lines,
         } = self;
 
         ServiceInput {
@@ -96,6 +122,27 @@ impl InsertInput {
             description,
             stocktake_date,
             is_locked,
+            is_blind_count,
+            recount_threshold,
+            generate_lines: generate_lines.map(|generate_lines| generate_lines.to_domain()),
+        }
+    }
+}
+
+impl GenerateStocktakeLinesInput {
+    pub fn to_domain(self) -> GenerateStocktakeLines {
+        let GenerateStocktakeLinesInput {
+            location_id,
+            master_list_id,
+            expires_before,
+            item_ids,
+        } = self;
+
+        GenerateStocktakeLines {
+            location_id,
+            master_list_id,
+            expires_before,
+            item_ids,
         }
     }
 }
@@ -171,7 +218,10 @@ mod test {
                     comment: Some("comment".to_string()),
                     description: Some("description".to_string()),
                     stocktake_date: Some(NaiveDate::from_ymd(2022, 01, 03)),
-                    is_locked: Some(true)
+                    is_locked: Some(true),
+                    is_blind_count: None,
+                    recount_threshold: None,
+                    generate_lines: None,
                 }
             );
             // StocktakeNode result is checked in queries
@@ -20,6 +20,12 @@ pub struct UpdateInput {
     pub status: Option<StocktakeNodeStatus>,
     pub stocktake_date: Option<NaiveDate>,
     pub is_locked: Option<bool>,
+    /// Snapshot quantities are hidden until the count is submitted
+    pub is_blind_count: Option<bool>,
+    /// Blind count lines with a larger discrepancy (in packs) need a second count
+    pub recount_threshold: Option<u32>,
+    /// Submits the count of a blind count, the stocktake then has to be finalised by another user
+    pub submit_count: Option<bool>,
 }
 
 pub struct SnapshotCountCurrentCountMismatch(Vec<StocktakeLine>);
@@ -34,11 +40,50 @@ impl SnapshotCountCurrentCountMismatch {
     }
 }
 
+pub struct AdjustmentReasonNotProvided(Vec<StocktakeLine>);
+#[Object]
+impl AdjustmentReasonNotProvided {
+    pub async fn description(&self) -> &'static str {
+        "Stocktake lines with a difference in count require an adjustment reason"
+    }
+
+    pub async fn lines(&self) -> StocktakeLineConnector {
+        StocktakeLineConnector::from_domain_vec(self.0.clone())
+    }
+}
+
+pub struct AdjustmentReasonNotValid(Vec<StocktakeLine>);
+#[Object]
+impl AdjustmentReasonNotValid {
+    pub async fn description(&self) -> &'static str {
+        "Adjustment reason is not valid for the difference in count"
+    }
+
+    pub async fn lines(&self) -> StocktakeLineConnector {
+        StocktakeLineConnector::from_domain_vec(self.0.clone())
+    }
+}
+
+pub struct RecountRequired(Vec<StocktakeLine>);
+#[Object]
+impl RecountRequired {
+    pub async fn description(&self) -> &'static str {
+        "Stocktake lines with a discrepancy above the recount threshold need a second count"
+    }
+
+    pub async fn lines(&self) -> StocktakeLineConnector {
+        StocktakeLineConnector::from_domain_vec(self.0.clone())
+    }
+}
+
 #[derive(Interface)]
 #[graphql(name = "UpdateStocktakeErrorInterface")]
 #[graphql(field(name = "description", type = "String"))]
 pub enum UpdateErrorInterface {
     SnapshotCountCurrentCountMismatch(SnapshotCountCurrentCountMismatch),
+    AdjustmentReasonNotProvided(AdjustmentReasonNotProvided),
+    AdjustmentReasonNotValid(AdjustmentReasonNotValid),
+    RecountRequired(RecountRequired),
     StocktakeIsLocked(StocktakeIsLocked),
     CannotEditStocktake(CannotEditStocktake),
 }
@@ -96,6 +141,21 @@ fn map_error(err: ServiceError) -> Result<UpdateErrorInterface> {
                 SnapshotCountCurrentCountMismatch(lines),
             ))
         }
+        ServiceError::AdjustmentReasonNotProvided(lines) => {
+            return Ok(UpdateErrorInterface::AdjustmentReasonNotProvided(
+                AdjustmentReasonNotProvided(lines),
+            ))
+        }
+        ServiceError::AdjustmentReasonNotValid(lines) => {
+            return Ok(UpdateErrorInterface::AdjustmentReasonNotValid(
+                AdjustmentReasonNotValid(lines),
+            ))
+        }
+        ServiceError::RecountRequired(lines) => {
+            return Ok(UpdateErrorInterface::RecountRequired(RecountRequired(
+                lines,
+            )))
+        }
         ServiceError::StocktakeIsLocked => {
             return Ok(UpdateErrorInterface::StocktakeIsLocked(
                 StocktakeIsLocked {},
@@ -111,6 +171,11 @@ fn map_error(err: ServiceError) -> Result<UpdateErrorInterface> {
         ServiceError::InvalidStore => BadUserInput(formatted_error),
         ServiceError::StocktakeDoesNotExist => BadUserInput(formatted_error),
         ServiceError::NoLines => BadUserInput(formatted_error),
+        ServiceError::NotABlindCount => BadUserInput(formatted_error),
+        ServiceError::CountAlreadySubmitted => BadUserInput(formatted_error),
+        ServiceError::CountAlreadyStarted => BadUserInput(formatted_error),
+        ServiceError::CountNotSubmitted => BadUserInput(formatted_error),
+        ServiceError::VerifierIsCounter => BadUserInput(formatted_error),
         ServiceError::InternalError(err) => InternalError(err),
         ServiceError::DatabaseError(_) => InternalError(formatted_error),
     };
@@ -127,6 +192,9 @@ impl UpdateInput {
             status,
             is_locked,
             stocktake_date,
+            is_blind_count,
+            recount_threshold,
+            submit_count,
         } = self;
 
         ServiceInput {
@@ -136,6 +204,9 @@ impl UpdateInput {
             status: status.map(|status| status.to_domain()),
             is_locked,
             stocktake_date,
+            is_blind_count,
+            recount_threshold,
+            submit_count,
         }
     }
 }
@@ -273,6 +344,11 @@ mod graphql {
                 finalised_datetime: Some(NaiveDate::from_ymd(2022, 1, 23).and_hms(15, 16, 0)),
                 inventory_adjustment_id: Some("inv id".to_string()),
                 is_locked: false,
+                is_blind_count: false,
+                recount_threshold: None,
+                counted_by: None,
+                count_submitted_datetime: None,
+                verified_by: None,
             })
         }));
 
@@ -29,6 +29,7 @@ pub struct InsertInput {
     pub cost_price_per_pack: Option<f64>,
     pub sell_price_per_pack: Option<f64>,
     pub note: Option<String>,
+    pub inventory_adjustment_reason_id: Option<String>,
 }
 
 #[derive(Union)]
@@ -99,7 +100,9 @@ fn map_error(error: ServiceError) -> Result<InsertErrorInterface> {
         ServiceError::StockLineDoesNotExist => BadUserInput(formatted_error),
         ServiceError::StockLineAlreadyExistsInStocktake => BadUserInput(formatted_error),
         ServiceError::LocationDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::AdjustmentReasonDoesNotExist => BadUserInput(formatted_error),
         ServiceError::StocktakeIsLocked => BadUserInput(formatted_error),
+        ServiceError::CountAlreadySubmitted => BadUserInput(formatted_error),
         ServiceError::StockLineXOrItem => BadUserInput(format!(
             "Either a stock line id or item id must be set (not both), {}",
             formatted_error
@@ -128,6 +131,7 @@ impl InsertInput {
             cost_price_per_pack,
             sell_price_per_pack,
             note,
+            inventory_adjustment_reason_id,
         } = self;
 
         ServiceInput {
@@ -144,6 +148,7 @@ impl InsertInput {
             cost_price_per_pack,
             sell_price_per_pack,
             note,
+            inventory_adjustment_reason_id,
         }
     }
 }
@@ -259,6 +264,7 @@ mod test {
                     location_id: Some("location id".to_string()),
                     snapshot_number_of_packs: 10,
                     counted_number_of_packs: Some(20),
+                    second_counted_number_of_packs: None,
                     comment: Some("comment".to_string()),
                     item_id: "item id".to_string(),
                     batch: Some("batch".to_string()),
@@ -267,6 +273,7 @@ mod test {
                     cost_price_per_pack: Some(10.0),
                     sell_price_per_pack: Some(12.0),
                     note: Some("note".to_string()),
+                    inventory_adjustment_reason_id: None,
                 },
                 stock_line: Some(mock_stock_line_a()),
                 location: Some(mock_location_1()),
@@ -21,12 +21,15 @@ pub struct UpdateInput {
     pub comment: Option<String>,
     pub snapshot_number_of_packs: Option<u32>,
     pub counted_number_of_packs: Option<u32>,
+    /// Recount of a line with a discrepancy, replaces the first count
+    pub second_counted_number_of_packs: Option<u32>,
     pub batch: Option<String>,
     pub expiry_date: Option<NaiveDate>,
     pub pack_size: Option<u32>,
     pub cost_price_per_pack: Option<f64>,
     pub sell_price_per_pack: Option<f64>,
     pub note: Option<String>,
+    pub inventory_adjustment_reason_id: Option<String>,
 }
 
 #[derive(Union)]
@@ -86,12 +89,14 @@ impl UpdateInput {
             comment,
             snapshot_number_of_packs,
             counted_number_of_packs,
+            second_counted_number_of_packs,
             batch,
             expiry_date,
             pack_size,
             cost_price_per_pack,
             sell_price_per_pack,
             note,
+            inventory_adjustment_reason_id,
         } = self;
 
         ServiceInput {
@@ -100,12 +105,14 @@ impl UpdateInput {
             comment,
             snapshot_number_of_packs,
             counted_number_of_packs,
+            second_counted_number_of_packs,
             batch,
             expiry_date,
             pack_size,
             cost_price_per_pack,
             sell_price_per_pack,
             note,
+            inventory_adjustment_reason_id,
         }
     }
 }
@@ -126,7 +133,10 @@ fn map_error(error: ServiceError) -> Result<UpdateErrorInterface> {
         ServiceError::InvalidStore => BadUserInput(formatted_error),
         ServiceError::StocktakeLineDoesNotExist => BadUserInput(formatted_error),
         ServiceError::LocationDoesNotExist => BadUserInput(formatted_error),
+        ServiceError::AdjustmentReasonDoesNotExist => BadUserInput(formatted_error),
         ServiceError::StocktakeIsLocked => BadUserInput(formatted_error),
+        ServiceError::CountAlreadySubmitted => BadUserInput(formatted_error),
+        ServiceError::CountNotSubmitted => BadUserInput(formatted_error),
         ServiceError::DatabaseError(_) => InternalError(formatted_error),
         ServiceError::InternalError(err) => InternalError(err),
     };
@@ -243,6 +253,7 @@ mod test {
                     location_id: Some("location id".to_string()),
                     snapshot_number_of_packs: 10,
                     counted_number_of_packs: Some(20),
+                    second_counted_number_of_packs: None,
                     comment: Some("comment".to_string()),
                     item_id: "item id".to_string(),
                     batch: Some("batch".to_string()),
@@ -251,6 +262,7 @@ mod test {
                     cost_price_per_pack: Some(10.0),
                     sell_price_per_pack: Some(12.0),
                     note: Some("note".to_string()),
+                    inventory_adjustment_reason_id: None,
                 },
                 stock_line: Some(mock_stock_line_a()),
                 location: Some(mock_location_1()),
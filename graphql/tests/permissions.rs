@@ -17,10 +17,13 @@ mod permission_tests {
     // lib.rs. As a workaround these defs are copied here. Hopefully this should be possible but I
     // gave up on this for now.
     use graphql_batch_mutations::BatchMutations;
-    use graphql_general::{GeneralQueries, ServerAdminMutations, ServerAdminQueries};
+    use graphql_general::{
+        GeneralMutations, GeneralQueries, ServerAdminMutations, ServerAdminQueries,
+    };
     use graphql_invoice::{InvoiceMutations, InvoiceQueries};
     use graphql_invoice_line::InvoiceLineMutations;
     use graphql_location::{LocationMutations, LocationQueries};
+    use graphql_patient::{PatientMutations, PatientQueries};
     use graphql_reports::ReportQueries;
     use graphql_requisition::{RequisitionMutations, RequisitionQueries};
     use graphql_requisition_line::RequisitionLineMutations;
@@ -31,6 +34,7 @@ mod permission_tests {
     pub struct FullQuery(
         pub InvoiceQueries,
         pub LocationQueries,
+        pub PatientQueries,
         pub StocktakeQueries,
         pub GeneralQueries,
         pub RequisitionQueries,
@@ -43,11 +47,13 @@ mod permission_tests {
         pub InvoiceMutations,
         pub InvoiceLineMutations,
         pub LocationMutations,
+        pub PatientMutations,
         pub StocktakeMutations,
         pub StocktakeLineMutations,
         pub BatchMutations,
         pub RequisitionMutations,
         pub RequisitionLineMutations,
+        pub GeneralMutations,
         pub ServerAdminMutations,
     );
 
@@ -55,6 +61,7 @@ mod permission_tests {
         FullQuery(
             InvoiceQueries,
             LocationQueries,
+            PatientQueries,
             StocktakeQueries,
             GeneralQueries,
             RequisitionQueries,
@@ -68,11 +75,13 @@ mod permission_tests {
             InvoiceMutations,
             InvoiceLineMutations,
             LocationMutations,
+            PatientMutations,
             StocktakeMutations,
             StocktakeLineMutations,
             BatchMutations,
             RequisitionMutations,
             RequisitionLineMutations,
+            GeneralMutations,
             ServerAdminMutations,
         )
     }
@@ -91,6 +100,18 @@ mod permission_tests {
 
     fn resource_mapping_query_test_data() -> Vec<TestData> {
         vec![
+            TestData {
+                name: "inventoryAdjustmentReasons",
+                query: r#"query Query {
+                  inventoryAdjustmentReasons(storeId: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryInventoryAdjustmentReasons,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "invoice",
                 query: r#"query Query {
@@ -213,6 +234,30 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "patient",
+                query: r#"query Query {
+                patient(id: "", storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryPatient,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "patients",
+                query: r#"query Query {
+                patients(storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryPatient,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "printReport",
                 query: r#"query Query {
@@ -259,6 +304,58 @@ mod permission_tests {
                     store_id: None,
                 },
             },
+            TestData {
+                name: "syncStatus",
+                query: r#"query Query {
+                syncStatus {
+                  isSyncing
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "syncHistory",
+                query: r#"query Query {
+                syncHistory {
+                  ... on SyncLogConnector {
+                    totalCount
+                  }
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "syncErrors",
+                query: r#"query Query {
+                syncErrors {
+                  ... on SyncErrorConnector {
+                    totalCount
+                  }
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "initialSyncStatus",
+                query: r#"query Query {
+                initialSyncStatus {
+                  isFinished
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
             TestData {
                 name: "requisition",
                 query: r#"query Query {
@@ -273,6 +370,78 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "backorders",
+                query: r#"query Query {
+                  backorders(storeId: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryRequisition,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "programs",
+                query: r#"query Query {
+                  programs(storeId: "") {
+                    totalCount
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryRequisition,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "reorderConfig",
+                query: r#"query Query {
+                  reorderConfig(storeId: "") {
+                    isEnabled
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryRequisition,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "lastReorderRun",
+                query: r#"query Query {
+                  lastReorderRun(storeId: "") {
+                    id
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryRequisition,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "cycleCountConfig",
+                query: r#"query Query {
+                  cycleCountConfig(storeId: "") {
+                    isEnabled
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryStocktake,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "itemCycleCounts",
+                query: r#"query Query {
+                  itemCycleCounts(storeId: "") {
+                    totalCount
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryStocktake,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "requisitionByNumber",
                 query: r#"query Query {
@@ -320,6 +489,30 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "revokeSession",
+                query: r#"query Query {
+                  revokeSession(sessionId: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::RouteMe,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "sessions",
+                query: r#"query Query {
+                  sessions {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::RouteMe,
+                    store_id: None,
+                },
+            },
             TestData {
                 name: "stockCounts",
                 query: r#"query Query {
@@ -332,6 +525,42 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "alerts",
+                query: r#"query Query {
+                  alerts(storeId: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryAlert,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "alertConfig",
+                query: r#"query Query {
+                  alertConfig(storeId: "") {
+                    isEnabled
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryAlert,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "stockLedger",
+                query: r#"query Query {
+                  stockLedger(storeId: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryStockLedger,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "stocktake",
                 query: r#"query Query {
@@ -392,6 +621,30 @@ mod permission_tests {
                     store_id: None,
                 },
             },
+            TestData {
+                name: "storePreference",
+                query: r#"query Query {
+                  storePreference(storeId: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryStorePreference,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "itemByBarcode",
+                query: r#"query Query {
+                  itemByBarcode(storeId: "", barcode: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::QueryItemByBarcode,
+                    store_id: Some("some".to_string()),
+                },
+            },
         ]
     }
 
@@ -414,71 +667,58 @@ mod permission_tests {
                 },
             },
             TestData {
-                name: "allocateOutboundShipmentUnallocatedLine",
+                name: "createBackorderShipment",
                 query: r#"mutation Mutation {
-                  allocateOutboundShipmentUnallocatedLine(lineId: "", storeId: "") {
-                    ... on AllocateOutboundShipmentUnallocatedLineError {
-                      __typename
-                      error {
-                        description
-                      }
-                    }
+                  createBackorderShipment(input: {otherPartyId: ""}, storeId: "") {
+                    __typename
                   }
                 }"#,
                 expected: ResourceAccessRequest {
-                    resource: Resource::MutateOutboundShipment,
+                    resource: Resource::MutateRequisition,
                     store_id: Some("some".to_string()),
                 },
             },
             TestData {
-                name: "batchInboundShipment",
+                name: "updateReorderConfig",
                 query: r#"mutation Mutation {
-                batchInboundShipment(storeId: "", input: {}) {
-                  updateInboundShipments {
-                    id
+                  updateReorderConfig(input: {isEnabled: false, minMonthsOfStock: 1, maxMonthsOfStock: 2}, storeId: "") {
+                    __typename
                   }
-                }
-              }"#,
+                }"#,
                 expected: ResourceAccessRequest {
-                    resource: Resource::MutateInboundShipment,
+                    resource: Resource::MutateRequisition,
                     store_id: Some("some".to_string()),
                 },
             },
             TestData {
-                name: "batchOutboundShipment",
+                name: "runReorder",
                 query: r#"mutation Mutation {
-                  batchOutboundShipment(input: {}, storeId: "") {
-                    updateOutboundShipments {
-                      id
-                    }
+                  runReorder(storeId: "") {
+                    __typename
                   }
                 }"#,
                 expected: ResourceAccessRequest {
-                    resource: Resource::MutateOutboundShipment,
+                    resource: Resource::MutateRequisition,
                     store_id: Some("some".to_string()),
                 },
             },
             TestData {
-                name: "batchRequestRequisition",
+                name: "updateCycleCountConfig",
                 query: r#"mutation Mutation {
-                  batchRequestRequisition(input: {}, storeId: "") {
-                    updateRequestRequisitions {
-                      id
-                    }
+                  updateCycleCountConfig(input: {isEnabled: false, aClassPercentage: 80, bClassPercentage: 15, aClassCountFrequencyDays: 30, bClassCountFrequencyDays: 90, cClassCountFrequencyDays: 365}, storeId: "") {
+                    isEnabled
                   }
                 }"#,
                 expected: ResourceAccessRequest {
-                    resource: Resource::MutateRequisition,
+                    resource: Resource::MutateStocktake,
                     store_id: Some("some".to_string()),
                 },
             },
             TestData {
-                name: "batchStocktake",
+                name: "classifyItems",
                 query: r#"mutation Mutation {
-                  batchStocktake(input: {}, storeId: "") {
-                    updateStocktakes {
-                      id
-                    }
+                  classifyItems(storeId: "") {
+                    totalCount
                   }
                 }"#,
                 expected: ResourceAccessRequest {
@@ -487,55 +727,200 @@ mod permission_tests {
                 },
             },
             TestData {
-                name: "createRequisitionShipment",
+                name: "generateCycleCount",
                 query: r#"mutation Mutation {
-                createRequisitionShipment(input: {responseRequisitionId: ""}, storeId: "") {
-                  ... on InvoiceNode {
+                  generateCycleCount(storeId: "") {
                     id
                   }
-                }
-              }"#,
+                }"#,
                 expected: ResourceAccessRequest {
-                    resource: Resource::MutateRequisition,
+                    resource: Resource::MutateStocktake,
                     store_id: Some("some".to_string()),
                 },
             },
             TestData {
-                name: "deleteInboundShipment",
+                name: "insertProgramRequestRequisition",
                 query: r#"mutation Mutation {
-                deleteInboundShipment(input: {id: ""}, storeId: "") {
-                  ... on DeleteResponse {
-                    id
+                  insertProgramRequestRequisition(input: {id: "", otherPartyId: "", programId: "", periodId: "", maxMonthsOfStock: 1, minMonthsOfStock: 1}, storeId: "") {
+                    __typename
                   }
-                }
-              }"#,
+                }"#,
                 expected: ResourceAccessRequest {
-                    resource: Resource::MutateInboundShipment,
+                    resource: Resource::MutateRequisition,
                     store_id: Some("some".to_string()),
                 },
             },
             TestData {
-                name: "deleteInboundShipmentLine",
+                name: "generatePeriods",
                 query: r#"mutation Mutation {
-                deleteInboundShipmentLine(input: {id: "", invoiceId: ""}, storeId: "") {
-                  ... on DeleteResponse {
-                    id
+                  generatePeriods(input: {periodScheduleId: "", year: 2021}) {
+                    totalCount
                   }
-                }
-              }"#,
+                }"#,
                 expected: ResourceAccessRequest {
-                    resource: Resource::MutateInboundShipment,
-                    store_id: Some("some".to_string()),
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
                 },
             },
             TestData {
-                name: "deleteInboundShipmentServiceLine",
+                name: "upsertPeriodSchedule",
                 query: r#"mutation Mutation {
-                deleteInboundShipmentServiceLine(input: {id: "", invoiceId: ""}, storeId: "") {
-                  ... on DeleteResponse {
+                  upsertPeriodSchedule(input: {id: "", name: "", type: MONTHLY}) {
                     id
                   }
-                }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "upsertProgram",
+                query: r#"mutation Mutation {
+                  upsertProgram(input: {id: "", name: "", masterListId: "", periodScheduleId: ""}) {
+                    id
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "allocateOutboundShipmentUnallocatedLine",
+                query: r#"mutation Mutation {
+                  allocateOutboundShipmentUnallocatedLine(lineId: "", storeId: "") {
+                    ... on AllocateOutboundShipmentUnallocatedLineError {
+                      __typename
+                      error {
+                        description
+                      }
+                    }
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateOutboundShipment,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "batchInboundShipment",
+                query: r#"mutation Mutation {
+                batchInboundShipment(storeId: "", input: {}) {
+                  updateInboundShipments {
+                    id
+                  }
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateInboundShipment,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "batchOutboundShipment",
+                query: r#"mutation Mutation {
+                  batchOutboundShipment(input: {}, storeId: "") {
+                    updateOutboundShipments {
+                      id
+                    }
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateOutboundShipment,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "batchRequestRequisition",
+                query: r#"mutation Mutation {
+                  batchRequestRequisition(input: {}, storeId: "") {
+                    updateRequestRequisitions {
+                      id
+                    }
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateRequisition,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "batchStocktake",
+                query: r#"mutation Mutation {
+                  batchStocktake(input: {}, storeId: "") {
+                    updateStocktakes {
+                      id
+                    }
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateStocktake,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "createRequisitionShipment",
+                query: r#"mutation Mutation {
+                createRequisitionShipment(input: {responseRequisitionId: ""}, storeId: "") {
+                  ... on InvoiceNode {
+                    id
+                  }
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateRequisition,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "deleteCustomerReturn",
+                query: r#"mutation Mutation {
+                deleteCustomerReturn(id: "", storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateCustomerReturn,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "deleteInboundShipment",
+                query: r#"mutation Mutation {
+                deleteInboundShipment(input: {id: ""}, storeId: "") {
+                  ... on DeleteResponse {
+                    id
+                  }
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateInboundShipment,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "deleteInboundShipmentLine",
+                query: r#"mutation Mutation {
+                deleteInboundShipmentLine(input: {id: "", invoiceId: ""}, storeId: "") {
+                  ... on DeleteResponse {
+                    id
+                  }
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateInboundShipment,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "deleteInboundShipmentServiceLine",
+                query: r#"mutation Mutation {
+                deleteInboundShipmentServiceLine(input: {id: "", invoiceId: ""}, storeId: "") {
+                  ... on DeleteResponse {
+                    id
+                  }
+                }
               }"#,
                 expected: ResourceAccessRequest {
                     resource: Resource::MutateInboundShipment,
@@ -612,6 +997,18 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "deletePrescription",
+                query: r#"mutation Mutation {
+                deletePrescription(id: "", storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutatePrescription,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "deleteRequestRequisition",
                 query: r#"mutation Mutation {
@@ -668,6 +1065,30 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "deleteSupplierReturn",
+                query: r#"mutation Mutation {
+                deleteSupplierReturn(id: "", storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateSupplierReturn,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "insertCustomerReturn",
+                query: r#"mutation Mutation {
+                insertCustomerReturn(input: {id: "", originalInvoiceId: "", lines: []}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateCustomerReturn,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "insertInboundShipment",
                 query: r#"mutation Mutation {
@@ -710,6 +1131,18 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "insertInventoryAdjustment",
+                query: r#"mutation Mutation {
+                insertInventoryAdjustment(input: {id: "", stockLineId: "", type: POSITIVE, numberOfPacks: 1}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateInventoryAdjustment,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "insertLocation",
                 query: r#"mutation Mutation {
@@ -725,6 +1158,18 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "moveStock",
+                query: r#"mutation Mutation {
+                moveStock(input: {id: "", stockLineId: "", locationId: "", numberOfPacks: 1}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateInventoryAdjustment,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "insertOutboundShipment",
                 query: r#"mutation Mutation {
@@ -781,6 +1226,30 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "insertPatient",
+                query: r#"mutation Mutation {
+                insertPatient(input: {id: "", code: "", firstName: "", lastName: ""}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutatePatient,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "insertPrescription",
+                query: r#"mutation Mutation {
+                insertPrescription(input: {id: "", patientId: ""}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutatePrescription,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "insertRequestRequisition",
                 query: r#"mutation Mutation {
@@ -837,6 +1306,42 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "insertSupplierReturn",
+                query: r#"mutation Mutation {
+                insertSupplierReturn(input: {id: "", originalInvoiceId: "", lines: []}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateSupplierReturn,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "setCustomerShelfLife",
+                query: r#"mutation Mutation {
+                setCustomerShelfLife(input: {nameId: ""}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateStorePreference,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "updateStorePreference (requisitionsRequireAuthorisation)",
+                query: r#"mutation Mutation {
+                updateStorePreference(input: {allocationStrategy: FEFO, requisitionsRequireAuthorisation: true}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateRequisitionAuthorisationPreference,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "supplyRequestedQuantity",
                 query: r#"mutation Mutation {
@@ -853,6 +1358,18 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "updateCustomerReturn",
+                query: r#"mutation Mutation {
+                updateCustomerReturn(input: {id: ""}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateCustomerReturn,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "updateInboundShipment",
                 query: r#"mutation Mutation {
@@ -966,6 +1483,30 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "updatePatient",
+                query: r#"mutation Mutation {
+                updatePatient(input: {id: ""}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutatePatient,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "updatePrescription",
+                query: r#"mutation Mutation {
+                updatePrescription(input: {id: ""}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutatePrescription,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "updateRequestRequisition",
                 query: r#"mutation Mutation {
@@ -1008,6 +1549,20 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "authoriseResponseRequisition",
+                query: r#"mutation Mutation {
+                authoriseResponseRequisition(input: {id: ""}, storeId: "") {
+                  ... on RequisitionNode {
+                    id
+                  }
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::AuthoriseRequisition,
+                    store_id: Some("some".to_string()),
+                },
+            },
             TestData {
                 name: "updateResponseRequisitionLine",
                 query: r#"mutation Mutation {
@@ -1022,6 +1577,46 @@ mod permission_tests {
                     store_id: Some("some".to_string()),
                 },
             },
+            TestData {
+                name: "manualSync",
+                query: r#"mutation Mutation {
+                manualSync {
+                  isQueued
+                }
+            }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "retrySyncError",
+                query: r#"mutation Mutation {
+                retrySyncError(id: "") {
+                  ... on SyncErrorNode {
+                    id
+                  }
+                }
+            }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "discardSyncError",
+                query: r#"mutation Mutation {
+                discardSyncError(id: "") {
+                  ... on DeleteResponse {
+                    id
+                  }
+                }
+            }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
             TestData {
                 name: "updateServerSettings",
                 query: r#"mutation Mutation {
@@ -1037,6 +1632,162 @@ mod permission_tests {
                     store_id: None,
                 },
             },
+            TestData {
+                name: "updateSupplierReturn",
+                query: r#"mutation Mutation {
+                updateSupplierReturn(input: {id: ""}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateSupplierReturn,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "updateStorePreference",
+                query: r#"mutation Mutation {
+                updateStorePreference(input: {allocationStrategy: FEFO}, storeId: "") {
+                  __typename
+                }
+              }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateStorePreference,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "acknowledgeAlert",
+                query: r#"mutation Mutation {
+                  acknowledgeAlert(id: "", storeId: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateAlert,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "dismissAlert",
+                query: r#"mutation Mutation {
+                  dismissAlert(id: "", storeId: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateAlert,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "evaluateAlerts",
+                query: r#"mutation Mutation {
+                  evaluateAlerts(storeId: "") {
+                    numberOfNewAlerts
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateAlert,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "updateAlertConfig",
+                query: r#"mutation Mutation {
+                  updateAlertConfig(input: {isEnabled: false, minMonthsOfStock: 1, expiryWarningDays: 30, inboundShipmentOverdueDays: 14}, storeId: "") {
+                    isEnabled
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateStorePreference,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "insertBarcode",
+                query: r#"mutation Mutation {
+                  insertBarcode(input: {id: "", gtin: "", itemId: ""}, storeId: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateBarcode,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "deleteBarcode",
+                query: r#"mutation Mutation {
+                  deleteBarcode(id: "", storeId: "") {
+                    __typename
+                  }
+                }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::MutateBarcode,
+                    store_id: Some("some".to_string()),
+                },
+            },
+            TestData {
+                name: "insertLocalUser",
+                query: r#"mutation Mutation {
+                insertLocalUser(input: {id: "", username: "", password: ""}) {
+                  __typename
+                }
+            }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "updateLocalUser",
+                query: r#"mutation Mutation {
+                updateLocalUser(input: {id: ""}) {
+                  __typename
+                }
+            }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "resetLocalUserPassword",
+                query: r#"mutation Mutation {
+                resetLocalUserPassword(input: {id: "", password: ""}) {
+                  __typename
+                }
+            }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "setLocalUserStores",
+                query: r#"mutation Mutation {
+                setLocalUserStores(input: {userId: "", stores: []}) {
+                  __typename
+                }
+            }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
+            TestData {
+                name: "revokeUserSessions",
+                query: r#"mutation Mutation {
+                revokeUserSessions(userId: "") {
+                  __typename
+                }
+            }"#,
+                expected: ResourceAccessRequest {
+                    resource: Resource::ServerAdmin,
+                    store_id: None,
+                },
+            },
             TestData {
                 name: "updateStocktake",
                 query: r#"mutation Mutation {
@@ -0,0 +1,82 @@
+use async_graphql::*;
+use repository::{InventoryAdjustmentReason, InventoryAdjustmentType};
+use serde::Serialize;
+use service::ListResult;
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")] // only needed to be comparable in tests
+pub enum InventoryAdjustmentNodeType {
+    /// Adjustment increasing the stock on hand
+    Positive,
+    /// Adjustment reducing the stock on hand
+    Negative,
+}
+
+impl InventoryAdjustmentNodeType {
+    pub fn to_domain(self) -> InventoryAdjustmentType {
+        match self {
+            InventoryAdjustmentNodeType::Positive => InventoryAdjustmentType::Positive,
+            InventoryAdjustmentNodeType::Negative => InventoryAdjustmentType::Negative,
+        }
+    }
+
+    pub fn from_domain(r#type: &InventoryAdjustmentType) -> InventoryAdjustmentNodeType {
+        match r#type {
+            InventoryAdjustmentType::Positive => InventoryAdjustmentNodeType::Positive,
+            InventoryAdjustmentType::Negative => InventoryAdjustmentNodeType::Negative,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct InventoryAdjustmentReasonNode {
+    pub inventory_adjustment_reason: InventoryAdjustmentReason,
+}
+
+#[Object]
+impl InventoryAdjustmentReasonNode {
+    pub async fn id(&self) -> &str {
+        &self.inventory_adjustment_reason.id
+    }
+
+    pub async fn r#type(&self) -> InventoryAdjustmentNodeType {
+        InventoryAdjustmentNodeType::from_domain(&self.inventory_adjustment_reason.r#type)
+    }
+
+    pub async fn is_active(&self) -> bool {
+        self.inventory_adjustment_reason.is_active
+    }
+
+    pub async fn reason(&self) -> &str {
+        &self.inventory_adjustment_reason.reason
+    }
+}
+
+impl InventoryAdjustmentReasonNode {
+    pub fn from_domain(inventory_adjustment_reason: InventoryAdjustmentReason) -> Self {
+        InventoryAdjustmentReasonNode {
+            inventory_adjustment_reason,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct InventoryAdjustmentReasonConnector {
+    total_count: u32,
+    nodes: Vec<InventoryAdjustmentReasonNode>,
+}
+
+impl InventoryAdjustmentReasonConnector {
+    pub fn from_domain(
+        from: ListResult<InventoryAdjustmentReason>,
+    ) -> InventoryAdjustmentReasonConnector {
+        InventoryAdjustmentReasonConnector {
+            total_count: from.count,
+            nodes: from
+                .rows
+                .into_iter()
+                .map(InventoryAdjustmentReasonNode::from_domain)
+                .collect(),
+        }
+    }
+}
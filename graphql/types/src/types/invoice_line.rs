@@ -148,6 +148,21 @@ impl InvoiceLineNode {
     pub async fn note(&self) -> &Option<String> {
         &self.row().note
     }
+
+    /// Return lines only: id of the invoice line the stock was returned against
+    pub async fn original_invoice_line_id(&self) -> &Option<String> {
+        &self.row().original_invoice_line_id
+    }
+
+    /// Return lines only
+    pub async fn return_reason(&self) -> &Option<String> {
+        &self.row().return_reason
+    }
+
+    /// Inventory adjustment lines only
+    pub async fn inventory_adjustment_reason_id(&self) -> &Option<String> {
+        &self.row().inventory_adjustment_reason_id
+    }
 }
 
 #[derive(Union)]
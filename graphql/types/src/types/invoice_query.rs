@@ -23,6 +23,9 @@ pub enum InvoiceNodeType {
     OutboundShipment,
     InboundShipment,
     InventoryAdjustment,
+    Prescription,
+    CustomerReturn,
+    SupplierReturn,
 }
 
 #[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
@@ -183,6 +186,21 @@ impl InvoiceNode {
         &self.row().colour
     }
 
+    /// Prescription only: name of the prescribing clinician
+    pub async fn prescriber(&self) -> &Option<String> {
+        &self.row().prescriber
+    }
+
+    /// Prescription only
+    pub async fn diagnosis(&self) -> &Option<String> {
+        &self.row().diagnosis
+    }
+
+    /// Prescription only: dosage directions for the patient
+    pub async fn directions(&self) -> &Option<String> {
+        &self.row().directions
+    }
+
     /// Response Requisition that is the origin of this Outbound Shipment
     /// Or Request Requisition for Inbound Shipment that Originated from Outbound Shipment (linked through Response Requisition)
     pub async fn requisition(&self, ctx: &Context<'_>) -> Result<Option<RequisitionNode>> {
@@ -342,6 +360,9 @@ impl InvoiceNodeType {
             OutboundShipment => InvoiceRowType::OutboundShipment,
             InboundShipment => InvoiceRowType::InboundShipment,
             InventoryAdjustment => InvoiceRowType::InventoryAdjustment,
+            Prescription => InvoiceRowType::Prescription,
+            CustomerReturn => InvoiceRowType::CustomerReturn,
+            SupplierReturn => InvoiceRowType::SupplierReturn,
         }
     }
 
@@ -351,6 +372,9 @@ impl InvoiceNodeType {
             OutboundShipment => InvoiceNodeType::OutboundShipment,
             InboundShipment => InvoiceNodeType::InboundShipment,
             InventoryAdjustment => InvoiceNodeType::InventoryAdjustment,
+            Prescription => InvoiceNodeType::Prescription,
+            CustomerReturn => InvoiceNodeType::CustomerReturn,
+            SupplierReturn => InvoiceNodeType::SupplierReturn,
         }
     }
 }
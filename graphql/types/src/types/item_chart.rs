@@ -1,5 +1,7 @@
+use super::ConsumptionCalculationMethodNode;
 use async_graphql::*;
 use chrono::NaiveDate;
+
 use service::requisition_line::chart::{
     ConsumptionHistory, ItemChart, StockEvolution, SuggestedQuantityCalculation,
 };
@@ -83,6 +85,15 @@ impl SuggestedQuantityCalculationNode {
     pub async fn suggested_quantity(&self) -> u32 {
         self.suggested_quantity_calculation.suggested
     }
+
+    /// Method that calculated the average monthly consumption and suggested quantity
+    pub async fn consumption_calculation_method(&self) -> ConsumptionCalculationMethodNode {
+        ConsumptionCalculationMethodNode::from_domain(
+            &self
+                .suggested_quantity_calculation
+                .consumption_calculation_method,
+        )
+    }
 }
 
 #[Object]
@@ -173,7 +184,7 @@ mod test {
     use async_graphql::{EmptyMutation, Object};
     use chrono::NaiveDate;
     use graphql_core::{assert_graphql_query, test_helpers::setup_graphl_test};
-    use repository::mock::MockDataInserts;
+    use repository::{mock::MockDataInserts, ConsumptionCalculationMethod};
     use serde_json::json;
 
     use super::*;
@@ -224,6 +235,8 @@ mod test {
                         minimum_stock_on_hand: 100.0,
                         maximum_stock_on_hand: 200.0,
                         suggested: 150,
+                        consumption_calculation_method:
+                            ConsumptionCalculationMethod::WeightedMovingAverage,
                     },
                 })
             }
@@ -257,7 +270,7 @@ mod test {
                     minimumStockOnHand
                     maximumStockOnHand
                     suggestedQuantity
-
+                    consumptionCalculationMethod
                 }
                calculationDate
             }
@@ -310,7 +323,8 @@ mod test {
                 "averageMonthlyConsumption": 10,
                 "maximumStockOnHand": 200,
                 "minimumStockOnHand": 100,
-                "suggestedQuantity": 150
+                "suggestedQuantity": 150,
+                "consumptionCalculationMethod": "WEIGHTED_MOVING_AVERAGE"
               }
             }
           }
@@ -3,7 +3,10 @@ use async_graphql::*;
 use async_graphql::{dataloader::DataLoader, Context};
 use graphql_core::generic_filters::EqualFilterStringInput;
 use graphql_core::simple_generic_errors::NodeError;
-use graphql_core::{loader::StockLineByLocationIdLoader, ContextExt};
+use graphql_core::{
+    loader::{LocationByIdLoader, StockLineByLocationIdLoader},
+    ContextExt,
+};
 use repository::{
     EqualFilter, Location, LocationFilter, LocationRow, LocationSort, LocationSortField,
 };
@@ -29,6 +32,7 @@ pub struct LocationFilterInput {
     pub name: Option<EqualFilterStringInput>,
     pub code: Option<EqualFilterStringInput>,
     pub id: Option<EqualFilterStringInput>,
+    pub parent_location_id: Option<EqualFilterStringInput>,
 }
 
 impl From<LocationFilterInput> for LocationFilter {
@@ -38,6 +42,7 @@ impl From<LocationFilterInput> for LocationFilter {
             code: f.code.map(EqualFilter::from),
             id: f.id.map(EqualFilter::from),
             store_id: None,
+            parent_location_id: f.parent_location_id.map(EqualFilter::from),
         }
     }
 }
@@ -71,6 +76,36 @@ impl LocationNode {
         self.row().on_hold
     }
 
+    pub async fn parent_location_id(&self) -> &Option<String> {
+        &self.row().parent_location_id
+    }
+
+    pub async fn parent_location(&self, ctx: &Context<'_>) -> Result<Option<LocationNode>> {
+        let loader = ctx.get_loader::<DataLoader<LocationByIdLoader>>();
+
+        let parent_location_id = match &self.row().parent_location_id {
+            None => return Ok(None),
+            Some(parent_location_id) => parent_location_id,
+        };
+
+        let result = loader.load_one(parent_location_id.clone()).await?;
+
+        Ok(result.map(LocationNode::from_domain))
+    }
+
+    /// Maximum number of packs that can be stored in the location
+    pub async fn capacity(&self) -> Option<i32> {
+        self.row().capacity
+    }
+
+    pub async fn min_temperature(&self) -> Option<f64> {
+        self.row().min_temperature
+    }
+
+    pub async fn max_temperature(&self) -> Option<f64> {
+        self.row().max_temperature
+    }
+
     pub async fn stock(&self, ctx: &Context<'_>) -> Result<StockLineConnector> {
         let loader = ctx.get_loader::<DataLoader<StockLineByLocationIdLoader>>();
         let result_option = loader.load_one(self.row().id.clone()).await?;
@@ -18,9 +18,18 @@ pub use self::requisition_line::*;
 pub mod stock_line;
 pub use self::stock_line::*;
 
+pub mod stock_ledger;
+pub use self::stock_ledger::*;
+
+pub mod store_preference;
+pub use self::store_preference::*;
+
 pub mod location;
 pub use self::location::*;
 
+pub mod inventory_adjustment_reason;
+pub use self::inventory_adjustment_reason::*;
+
 pub mod master_list;
 pub use self::master_list::*;
 
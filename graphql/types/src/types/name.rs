@@ -1,13 +1,34 @@
 use async_graphql::*;
 use chrono::NaiveDate;
 use dataloader::DataLoader;
-use repository::{Name, NameRow};
+use repository::{Gender, Name, NameRow, NameType};
 
 use graphql_core::{loader::StoreByIdLoader, simple_generic_errors::NodeError, ContextExt};
+use serde::Serialize;
 use serde_json::json;
 
 use super::StoreNode;
 
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum NameNodeType {
+    Facility,
+    Patient,
+    Build,
+    Invad,
+    Repack,
+    Store,
+    Others,
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum GenderType {
+    Female,
+    Male,
+    Other,
+}
+
 #[Object]
 impl NameNode {
     pub async fn id(&self) -> &str {
@@ -22,6 +43,10 @@ impl NameNode {
         &self.row().code
     }
 
+    pub async fn r#type(&self) -> NameNodeType {
+        NameNodeType::from_domain(&self.row().r#type)
+    }
+
     pub async fn is_customer(&self) -> bool {
         self.name.is_customer()
     }
@@ -51,10 +76,33 @@ impl NameNode {
             .map(StoreNode::from_domain))
     }
 
+    pub async fn first_name(&self) -> &Option<String> {
+        &self.row().first_name
+    }
+
+    pub async fn last_name(&self) -> &Option<String> {
+        &self.row().last_name
+    }
+
+    pub async fn gender(&self) -> Option<GenderType> {
+        self.row().gender.as_ref().map(GenderType::from_domain)
+    }
+
+    pub async fn date_of_birth(&self) -> Option<NaiveDate> {
+        self.row().date_of_birth
+    }
+
+    pub async fn national_health_number(&self) -> &Option<String> {
+        &self.row().national_health_number
+    }
+
     // Mock
 
     pub async fn phone(&self) -> String {
-        self.legacy_string("phone")
+        self.row()
+            .phone
+            .clone()
+            .unwrap_or_else(|| self.legacy_string("phone"))
     }
 
     pub async fn charge_code(&self) -> String {
@@ -70,11 +118,14 @@ impl NameNode {
     }
 
     pub async fn address(&self) -> &str {
-        ""
+        self.row().address.as_deref().unwrap_or("")
     }
 
     pub async fn email(&self) -> String {
-        self.legacy_string("email")
+        self.row()
+            .email
+            .clone()
+            .unwrap_or_else(|| self.legacy_string("email"))
     }
 
     pub async fn website(&self) -> String {
@@ -146,6 +197,56 @@ impl NameNode {
     }
 }
 
+impl NameNodeType {
+    pub fn from_domain(name_type: &NameType) -> NameNodeType {
+        use NameNodeType as to;
+        use NameType as from;
+
+        match name_type {
+            from::Facility => to::Facility,
+            from::Patient => to::Patient,
+            from::Build => to::Build,
+            from::Invad => to::Invad,
+            from::Repack => to::Repack,
+            from::Store => to::Store,
+            from::Others => to::Others,
+        }
+    }
+
+    pub fn to_domain(self) -> NameType {
+        use NameNodeType as from;
+        use NameType as to;
+
+        match self {
+            from::Facility => to::Facility,
+            from::Patient => to::Patient,
+            from::Build => to::Build,
+            from::Invad => to::Invad,
+            from::Repack => to::Repack,
+            from::Store => to::Store,
+            from::Others => to::Others,
+        }
+    }
+}
+
+impl GenderType {
+    pub fn from_domain(gender: &Gender) -> GenderType {
+        match gender {
+            Gender::Female => GenderType::Female,
+            Gender::Male => GenderType::Male,
+            Gender::Other => GenderType::Other,
+        }
+    }
+
+    pub fn to_domain(self) -> Gender {
+        match self {
+            GenderType::Female => Gender::Female,
+            GenderType::Male => Gender::Male,
+            GenderType::Other => Gender::Other,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use async_graphql::{EmptyMutation, Object};
@@ -32,6 +32,8 @@ pub enum RequisitionNodeStatus {
     Draft,
     /// New requisition when automatically created, only applicable to response requisition when it's duplicated in supplying store from request requisition
     New,
+    /// Response requisition is approved by a supervisor, only applicable to response requisition
+    Authorised,
     /// Request requisition is sent and locked for future editing, only applicable to request requisition
     Sent,
     /// Response requisition: When supplier finished fulfilling requisition, locked for future editing
@@ -90,6 +92,23 @@ impl RequisitionNode {
         Ok(Some(UserNode::from_domain(result)))
     }
 
+    /// User that authorised the response requisition, null if it hasn't been authorised
+    pub async fn approved_by(&self, ctx: &Context<'_>) -> Result<Option<UserNode>> {
+        let loader = ctx.get_loader::<DataLoader<UserLoader>>();
+
+        let user_id = match &self.row().approved_by_user_id {
+            Some(user_id) => user_id,
+            None => return Ok(None),
+        };
+
+        let result = loader
+            .load_one(user_id.clone())
+            .await?
+            .unwrap_or(unknown_user());
+
+        Ok(Some(UserNode::from_domain(result)))
+    }
+
     /// Applicable to request requisition only
     pub async fn sent_datetime(&self) -> Option<DateTime<Utc>> {
         let sent_datetime = self.row().sent_datetime.clone();
@@ -156,6 +175,16 @@ impl RequisitionNode {
         &self.row().min_months_of_stock
     }
 
+    /// Programme of a period requisition
+    pub async fn program_id(&self) -> &Option<String> {
+        &self.row().program_id
+    }
+
+    /// Reporting period of a period requisition
+    pub async fn period_id(&self) -> &Option<String> {
+        &self.row().period_id
+    }
+
     pub async fn lines(&self, ctx: &Context<'_>) -> Result<RequisitionLineConnector> {
         let loader = ctx.get_loader::<DataLoader<RequisitionLinesByRequisitionIdLoader>>();
         let result_option = loader.load_one(self.row().id.clone()).await?;
@@ -267,6 +296,7 @@ impl RequisitionNodeStatus {
         match self {
             Draft => RequisitionRowStatus::Draft,
             New => RequisitionRowStatus::New,
+            Authorised => RequisitionRowStatus::Authorised,
             Sent => RequisitionRowStatus::Sent,
             Finalised => RequisitionRowStatus::Finalised,
         }
@@ -277,6 +307,7 @@ impl RequisitionNodeStatus {
         match status {
             Draft => RequisitionNodeStatus::Draft,
             New => RequisitionNodeStatus::New,
+            Authorised => RequisitionNodeStatus::Authorised,
             Sent => RequisitionNodeStatus::Sent,
             Finalised => RequisitionNodeStatus::Finalised,
         }
@@ -66,6 +66,37 @@ impl RequisitionLineNode {
         &self.row().supply_quantity
     }
 
+    /// Quantity approved when the response requisition was authorised
+    pub async fn approved_quantity(&self) -> &i32 {
+        &self.row().approved_quantity
+    }
+
+    pub async fn approval_comment(&self) -> &Option<String> {
+        &self.row().approval_comment
+    }
+
+    /// Stock on hand at the start of the period, only set for period requisitions
+    pub async fn opening_balance(&self) -> &i32 {
+        &self.row().opening_balance
+    }
+
+    pub async fn received_quantity(&self) -> &i32 {
+        &self.row().received_quantity
+    }
+
+    pub async fn issued_quantity(&self) -> &i32 {
+        &self.row().issued_quantity
+    }
+
+    pub async fn adjusted_quantity(&self) -> &i32 {
+        &self.row().adjusted_quantity
+    }
+
+    /// Stock on hand at the end of the period, only set for period requisitions
+    pub async fn closing_balance(&self) -> &i32 {
+        &self.row().closing_balance
+    }
+
     /// Calculated quantity
     /// When months_of_stock < requisition.min_months_of_stock, calculated = average_monthy_consumption * requisition.max_months_of_stock - months_of_stock
     pub async fn suggested_quantity(&self) -> &i32 {
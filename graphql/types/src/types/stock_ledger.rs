@@ -0,0 +1,101 @@
+use async_graphql::*;
+use chrono::{DateTime, Utc};
+use repository::StockLedger;
+use service::ListResult;
+
+use super::InvoiceNodeType;
+
+#[derive(PartialEq, Debug)]
+pub struct StockLedgerNode {
+    pub ledger: StockLedger,
+}
+
+#[Object]
+impl StockLedgerNode {
+    /// Id of the invoice line that moved the stock
+    pub async fn id(&self) -> &str {
+        &self.ledger.id
+    }
+
+    pub async fn item_id(&self) -> &str {
+        &self.ledger.item_id
+    }
+
+    pub async fn store_id(&self) -> &str {
+        &self.ledger.store_id
+    }
+
+    pub async fn stock_line_id(&self) -> &Option<String> {
+        &self.ledger.stock_line_id
+    }
+
+    pub async fn invoice_id(&self) -> &str {
+        &self.ledger.invoice_id
+    }
+
+    pub async fn invoice_number(&self) -> i64 {
+        self.ledger.invoice_number
+    }
+
+    pub async fn invoice_type(&self) -> InvoiceNodeType {
+        InvoiceNodeType::from_domain(&self.ledger.invoice_type)
+    }
+
+    /// Name of the other party of the invoice
+    pub async fn name(&self) -> &str {
+        &self.ledger.name
+    }
+
+    pub async fn batch(&self) -> &Option<String> {
+        &self.ledger.batch
+    }
+
+    /// Inventory adjustment reason or return reason
+    pub async fn reason(&self) -> &Option<String> {
+        &self.ledger.reason
+    }
+
+    /// Number of units moved, negative if stock was removed
+    pub async fn quantity(&self) -> i32 {
+        self.ledger.quantity
+    }
+
+    pub async fn datetime(&self) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(self.ledger.datetime, Utc)
+    }
+
+    /// Number of units of the item in the store after this movement
+    pub async fn running_balance(&self) -> i64 {
+        self.ledger.running_balance
+    }
+
+    /// Number of units in the stock line after this movement
+    pub async fn stock_line_running_balance(&self) -> Option<i64> {
+        self.ledger.stock_line_running_balance
+    }
+}
+
+impl StockLedgerNode {
+    pub fn from_domain(ledger: StockLedger) -> StockLedgerNode {
+        StockLedgerNode { ledger }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct StockLedgerConnector {
+    total_count: u32,
+    nodes: Vec<StockLedgerNode>,
+}
+
+impl StockLedgerConnector {
+    pub fn from_domain(from: ListResult<StockLedger>) -> StockLedgerConnector {
+        StockLedgerConnector {
+            total_count: from.count,
+            nodes: from
+                .rows
+                .into_iter()
+                .map(StockLedgerNode::from_domain)
+                .collect(),
+        }
+    }
+}
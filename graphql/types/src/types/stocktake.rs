@@ -2,6 +2,7 @@ use async_graphql::{self, dataloader::DataLoader, Context, Enum, ErrorExtensions
 use chrono::{DateTime, NaiveDate, Utc};
 use repository::{unknown_user, StocktakeRow, StocktakeStatus};
 use serde::Serialize;
+use service::i32_to_u32;
 
 use graphql_core::{
     loader::{InvoiceByIdLoader, StocktakeLineByStocktakeIdLoader, UserLoader},
@@ -60,6 +61,32 @@ impl StocktakeNode {
         self.stocktake.is_locked
     }
 
+    /// Snapshot quantities are hidden until the count is submitted
+    pub async fn is_blind_count(&self) -> bool {
+        self.stocktake.is_blind_count
+    }
+
+    /// Blind count lines with a larger discrepancy (in packs) need a second count
+    pub async fn recount_threshold(&self) -> Option<u32> {
+        self.stocktake.recount_threshold.map(i32_to_u32)
+    }
+
+    /// Id of the user that submitted the count of a blind count
+    pub async fn counted_by(&self) -> &Option<String> {
+        &self.stocktake.counted_by
+    }
+
+    pub async fn count_submitted_datetime(&self) -> Option<DateTime<Utc>> {
+        self.stocktake
+            .count_submitted_datetime
+            .map(|dt| DateTime::<Utc>::from_utc(dt, Utc))
+    }
+
+    /// Id of the user that finalised a blind count
+    pub async fn verified_by(&self) -> &Option<String> {
+        &self.stocktake.verified_by
+    }
+
     pub async fn status(&self) -> StocktakeNodeStatus {
         StocktakeNodeStatus::from_domain(&self.stocktake.status)
     }
@@ -5,7 +5,7 @@ use repository::{Location, StocktakeLine};
 use service::{i32_to_u32, usize_to_u32};
 
 use graphql_core::{
-    loader::{ItemLoader, StockLineByIdLoader},
+    loader::{ItemLoader, StockLineByIdLoader, StocktakeByIdLoader},
     standard_graphql_error::StandardGraphqlError,
     ContextExt,
 };
@@ -26,7 +26,12 @@ impl StocktakeLineNode {
         &self.line.line.stocktake_id
     }
 
+    /// Hidden for blind counts until the count is submitted, the stock line quantities would give
+    /// away the snapshot
     pub async fn stock_line(&self, ctx: &Context<'_>) -> Result<Option<StockLineNode>> {
+        if self.is_snapshot_hidden(ctx).await? {
+            return Ok(None);
+        }
         if let Some(ref stock_line) = self.line.stock_line {
             let loader = ctx.get_loader::<DataLoader<StockLineByIdLoader>>();
             let stock_line = loader.load_one(stock_line.id.clone()).await?.ok_or(
@@ -53,14 +58,26 @@ impl StocktakeLineNode {
         self.line.line.comment.clone()
     }
 
-    pub async fn snapshot_number_of_packs(&self) -> u32 {
-        i32_to_u32(self.line.line.snapshot_number_of_packs)
+    /// Hidden for blind counts until the count is submitted
+    pub async fn snapshot_number_of_packs(&self, ctx: &Context<'_>) -> Result<Option<u32>> {
+        if self.is_snapshot_hidden(ctx).await? {
+            return Ok(None);
+        }
+        Ok(Some(i32_to_u32(self.line.line.snapshot_number_of_packs)))
     }
 
     pub async fn counted_number_of_packs(&self) -> Option<u32> {
         self.line.line.counted_number_of_packs.map(i32_to_u32)
     }
 
+    /// Recount of a line with a discrepancy, replaces the first count
+    pub async fn second_counted_number_of_packs(&self) -> Option<u32> {
+        self.line
+            .line
+            .second_counted_number_of_packs
+            .map(i32_to_u32)
+    }
+
     pub async fn item_id(&self) -> &str {
         &self.line.line.item_id
     }
@@ -101,6 +118,28 @@ impl StocktakeLineNode {
     pub async fn note(&self) -> &Option<String> {
         &self.line.line.note
     }
+
+    pub async fn inventory_adjustment_reason_id(&self) -> &Option<String> {
+        &self.line.line.inventory_adjustment_reason_id
+    }
+}
+
+impl StocktakeLineNode {
+    /// Blind counts don't show the snapshot to the counter until the count is submitted
+    async fn is_snapshot_hidden(&self, ctx: &Context<'_>) -> Result<bool> {
+        let loader = ctx.get_loader::<DataLoader<StocktakeByIdLoader>>();
+        let stocktake = loader
+            .load_one(self.line.line.stocktake_id.clone())
+            .await?
+            .ok_or(
+                StandardGraphqlError::InternalError(format!(
+                    "Cannot find stocktake {} for stocktake line id {}",
+                    self.line.line.stocktake_id, self.line.line.id
+                ))
+                .extend(),
+            )?;
+        Ok(stocktake.is_blind_count && stocktake.count_submitted_datetime.is_none())
+    }
 }
 
 #[derive(SimpleObject)]
@@ -133,3 +172,115 @@ impl StocktakeLineNode {
         StocktakeLineNode { line }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use async_graphql::{EmptyMutation, Object};
+    use chrono::NaiveDate;
+    use graphql_core::{assert_graphql_query, test_helpers::setup_graphl_test_with_data};
+    use repository::{
+        mock::{mock_stock_line_a, mock_store_a, MockData, MockDataInserts},
+        StocktakeLine, StocktakeLineRow, StocktakeRow,
+    };
+    use serde_json::json;
+    use util::inline_init;
+
+    use crate::types::StocktakeLineNode;
+
+    fn blind_stocktake() -> StocktakeRow {
+        inline_init(|r: &mut StocktakeRow| {
+            r.id = "blind_stocktake".to_string();
+            r.store_id = mock_store_a().id;
+            r.stocktake_number = 100;
+            r.is_blind_count = true;
+        })
+    }
+
+    fn submitted_blind_stocktake() -> StocktakeRow {
+        inline_init(|r: &mut StocktakeRow| {
+            r.id = "submitted_blind_stocktake".to_string();
+            r.store_id = mock_store_a().id;
+            r.stocktake_number = 101;
+            r.is_blind_count = true;
+            r.count_submitted_datetime = Some(NaiveDate::from_ymd(2022, 6, 1).and_hms(10, 0, 0));
+        })
+    }
+
+    fn line(stocktake_id: &str) -> StocktakeLine {
+        StocktakeLine {
+            line: inline_init(|r: &mut StocktakeLineRow| {
+                r.id = format!("{}_line", stocktake_id);
+                r.stocktake_id = stocktake_id.to_string();
+                r.stock_line_id = Some(mock_stock_line_a().id);
+                r.item_id = mock_stock_line_a().item_id;
+                r.snapshot_number_of_packs = mock_stock_line_a().total_number_of_packs;
+            }),
+            stock_line: Some(mock_stock_line_a()),
+            location: None,
+        }
+    }
+
+    #[actix_rt::test]
+    async fn graphql_test_stocktake_line_blind_count() {
+        #[derive(Clone)]
+        struct TestQuery;
+
+        let (_, _, _, settings) = setup_graphl_test_with_data(
+            TestQuery,
+            EmptyMutation,
+            "graphql_test_stocktake_line_blind_count",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.stocktakes = vec![blind_stocktake(), submitted_blind_stocktake()];
+            }),
+        )
+        .await;
+
+        #[Object]
+        impl TestQuery {
+            pub async fn counting(&self) -> StocktakeLineNode {
+                StocktakeLineNode {
+                    line: line(&blind_stocktake().id),
+                }
+            }
+            pub async fn submitted(&self) -> StocktakeLineNode {
+                StocktakeLineNode {
+                    line: line(&submitted_blind_stocktake().id),
+                }
+            }
+        }
+
+        let query = r#"
+        query {
+            counting {
+                snapshotNumberOfPacks
+                stockLine {
+                    totalNumberOfPacks
+                }
+            }
+            submitted {
+                snapshotNumberOfPacks
+                stockLine {
+                    totalNumberOfPacks
+                }
+            }
+        }
+        "#;
+
+        // Snapshot can't be read through the stock line while counting
+        let expected = json!({
+            "counting": {
+                "snapshotNumberOfPacks": null,
+                "stockLine": null
+            },
+            "submitted": {
+                "snapshotNumberOfPacks": 40,
+                "stockLine": {
+                    "totalNumberOfPacks": 40
+                }
+            }
+        });
+
+        assert_graphql_query!(&settings, &query, &None, expected, None);
+    }
+}
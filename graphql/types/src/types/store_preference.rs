@@ -0,0 +1,121 @@
+use async_graphql::*;
+use repository::{AllocationStrategy, ConsumptionCalculationMethod, StorePreferenceRow};
+use serde::Serialize;
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")] // only needed to be comparable in tests
+pub enum AllocationStrategyNode {
+    /// First expired, first out
+    Fefo,
+    /// First received, first out
+    Fifo,
+}
+
+impl AllocationStrategyNode {
+    pub fn to_domain(self) -> AllocationStrategy {
+        match self {
+            AllocationStrategyNode::Fefo => AllocationStrategy::Fefo,
+            AllocationStrategyNode::Fifo => AllocationStrategy::Fifo,
+        }
+    }
+
+    pub fn from_domain(strategy: &AllocationStrategy) -> AllocationStrategyNode {
+        match strategy {
+            AllocationStrategy::Fefo => AllocationStrategyNode::Fefo,
+            AllocationStrategy::Fifo => AllocationStrategyNode::Fifo,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")] // only needed to be comparable in tests
+pub enum ConsumptionCalculationMethodNode {
+    /// Consumption over the lookback months divided by the number of months
+    SimpleAverage,
+    /// Days the item was out of stock are not counted in the lookback period
+    ExcludeStockOutDays,
+    /// Recent months have more weight than older months
+    WeightedMovingAverage,
+    /// Simple average, with lead time and safety stock added to the months of stock
+    LeadTimeSafetyStock,
+}
+
+impl ConsumptionCalculationMethodNode {
+    pub fn to_domain(self) -> ConsumptionCalculationMethod {
+        use ConsumptionCalculationMethod as to;
+        use ConsumptionCalculationMethodNode as from;
+        match self {
+            from::SimpleAverage => to::SimpleAverage,
+            from::ExcludeStockOutDays => to::ExcludeStockOutDays,
+            from::WeightedMovingAverage => to::WeightedMovingAverage,
+            from::LeadTimeSafetyStock => to::LeadTimeSafetyStock,
+        }
+    }
+
+    pub fn from_domain(method: &ConsumptionCalculationMethod) -> ConsumptionCalculationMethodNode {
+        use ConsumptionCalculationMethod as from;
+        use ConsumptionCalculationMethodNode as to;
+        match method {
+            from::SimpleAverage => to::SimpleAverage,
+            from::ExcludeStockOutDays => to::ExcludeStockOutDays,
+            from::WeightedMovingAverage => to::WeightedMovingAverage,
+            from::LeadTimeSafetyStock => to::LeadTimeSafetyStock,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct StorePreferenceNode {
+    pub store_preference: StorePreferenceRow,
+}
+
+#[Object]
+impl StorePreferenceNode {
+    /// Id of the store
+    pub async fn id(&self) -> &str {
+        &self.store_preference.id
+    }
+
+    /// Order in which stock lines are used when allocating unallocated lines
+    pub async fn allocation_strategy(&self) -> AllocationStrategyNode {
+        AllocationStrategyNode::from_domain(&self.store_preference.allocation_strategy)
+    }
+
+    /// Stock in this location is allocated before stock in other locations
+    pub async fn preferred_location_id(&self) -> &Option<String> {
+        &self.store_preference.preferred_location_id
+    }
+
+    /// Stock expiring within this number of days is not allocated, unless a customer specific
+    /// shelf life is set
+    pub async fn minimum_shelf_life_days(&self) -> Option<i32> {
+        self.store_preference.minimum_shelf_life_days
+    }
+
+    /// Response requisitions must be authorised before shipments can be created
+    pub async fn requisitions_require_authorisation(&self) -> bool {
+        self.store_preference.requisitions_require_authorisation
+    }
+
+    /// Method used to calculate average monthly consumption and suggested quantity of requisitions
+    pub async fn consumption_calculation_method(&self) -> ConsumptionCalculationMethodNode {
+        ConsumptionCalculationMethodNode::from_domain(
+            &self.store_preference.consumption_calculation_method,
+        )
+    }
+
+    /// Added to the min and max months of stock by the lead time and safety stock method
+    pub async fn lead_time_months(&self) -> f64 {
+        self.store_preference.lead_time_months
+    }
+
+    pub async fn safety_stock_months(&self) -> f64 {
+        self.store_preference.safety_stock_months
+    }
+}
+
+impl StorePreferenceNode {
+    pub fn from_domain(store_preference: StorePreferenceRow) -> StorePreferenceNode {
+        StorePreferenceNode { store_preference }
+    }
+}
@@ -1,10 +1,10 @@
 use async_graphql::{
-    dataloader::DataLoader, Context, ErrorExtensions, Object, Result, SimpleObject,
+    dataloader::DataLoader, Context, Enum, ErrorExtensions, Object, Result, SimpleObject,
 };
 use graphql_core::{
     loader::NameRowLoader, standard_graphql_error::StandardGraphqlError, ContextExt,
 };
-use repository::{User, UserStore};
+use repository::{Permission, User, UserStore};
 
 pub struct UserStoreNode {
     user_store: UserStore,
@@ -64,6 +64,15 @@ impl UserNode {
         &self.user.user_row.username
     }
 
+    /// User has been created on this site and is not known to the central server
+    pub async fn is_local(&self) -> bool {
+        self.user.user_row.is_local
+    }
+
+    pub async fn is_disabled(&self) -> bool {
+        self.user.user_row.is_disabled
+    }
+
     pub async fn default_store(&self) -> Option<UserStoreNode> {
         self.user.default_store().map(|user_store| UserStoreNode {
             user_store: user_store.clone(),
@@ -91,3 +100,43 @@ impl UserNode {
         UserNode { user }
     }
 }
+
+#[derive(Enum, Copy, Clone, PartialEq, Eq, Debug)]
+pub enum UserPermission {
+    ServerAdmin,
+    StoreAccess,
+    LocationMutate,
+    StockLineQuery,
+    StocktakeQuery,
+    StocktakeMutate,
+    RequisitionQuery,
+    RequisitionMutate,
+    RequisitionAuthorise,
+    OutboundShipmentQuery,
+    OutboundShipmentMutate,
+    InboundShipmentQuery,
+    InboundShipmentMutate,
+    Report,
+}
+
+impl UserPermission {
+    pub fn to_domain(self) -> Permission {
+        use UserPermission::*;
+        match self {
+            ServerAdmin => Permission::ServerAdmin,
+            StoreAccess => Permission::StoreAccess,
+            LocationMutate => Permission::LocationMutate,
+            StockLineQuery => Permission::StockLineQuery,
+            StocktakeQuery => Permission::StocktakeQuery,
+            StocktakeMutate => Permission::StocktakeMutate,
+            RequisitionQuery => Permission::RequisitionQuery,
+            RequisitionMutate => Permission::RequisitionMutate,
+            RequisitionAuthorise => Permission::RequisitionAuthorise,
+            OutboundShipmentQuery => Permission::OutboundShipmentQuery,
+            OutboundShipmentMutate => Permission::OutboundShipmentMutate,
+            InboundShipmentQuery => Permission::InboundShipmentQuery,
+            InboundShipmentMutate => Permission::InboundShipmentMutate,
+            Report => Permission::Report,
+        }
+    }
+}
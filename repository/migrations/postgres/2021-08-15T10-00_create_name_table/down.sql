@@ -1,3 +1,7 @@
 -- Drop name table.
 
 DROP TABLE IF EXISTS name;
+
+DROP TYPE IF EXISTS name_type;
+
+DROP TYPE IF EXISTS gender;
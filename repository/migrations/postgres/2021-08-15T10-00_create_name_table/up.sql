@@ -1,12 +1,38 @@
 -- Create name table.
 
+CREATE TYPE name_type AS ENUM (
+    'FACILITY',
+    'PATIENT',
+    'BUILD',
+    'INVAD',
+    'REPACK',
+    'STORE',
+    'OTHERS'
+);
+
+CREATE TYPE gender AS ENUM (
+    'FEMALE',
+    'MALE',
+    'OTHER'
+);
+
 CREATE TABLE name (
     id TEXT NOT NULL PRIMARY KEY,
     -- Human-readable representation of the entity associated with the name record.
     name TEXT NOT NULL,
     code TEXT NOT NULL,
+    type name_type NOT NULL DEFAULT 'FACILITY',
     is_customer BOOLEAN NOT NULL,
     is_supplier BOOLEAN NOT NULL,
+    -- Patient details
+    first_name TEXT,
+    last_name TEXT,
+    gender gender,
+    date_of_birth DATE,
+    national_health_number TEXT,
+    phone TEXT,
+    email TEXT,
+    address TEXT,
     -- TODO, this is temporary, remove
     legacy_record TEXT NOT NULL
 )
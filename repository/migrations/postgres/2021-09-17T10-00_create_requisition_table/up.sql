@@ -1,7 +1,7 @@
 -- Create requisition table.
 
 CREATE TYPE requisition_type AS ENUM ('REQUEST', 'RESPONSE');
-CREATE TYPE requisition_status AS ENUM ('DRAFT', 'NEW', 'SENT', 'FINALISED');
+CREATE TYPE requisition_status AS ENUM ('DRAFT', 'NEW', 'AUTHORISED', 'SENT', 'FINALISED');
 
 CREATE TABLE requisition (
     id TEXT NOT NULL PRIMARY KEY,
@@ -21,5 +21,10 @@ CREATE TABLE requisition (
     their_reference TEXT,
     max_months_of_stock  DOUBLE PRECISION NOT NULL,
     min_months_of_stock DOUBLE PRECISION NOT NULL,
-    linked_requisition_id TEXT
+    linked_requisition_id TEXT,
+    -- Set for requisitions of a programme, reporting on the stock movements of the period
+    program_id TEXT,
+    period_id TEXT,
+    -- User that authorised the response requisition, user_id stays the last editor
+    approved_by_user_id TEXT
 )
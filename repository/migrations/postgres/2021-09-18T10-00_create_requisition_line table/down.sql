@@ -1,3 +1,4 @@
 -- Drop requisition_line table.
 
 DROP TABLE IF EXISTS requisition_line CASCADE;
+DROP TYPE IF EXISTS consumption_calculation_method;
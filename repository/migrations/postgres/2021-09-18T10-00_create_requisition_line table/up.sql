@@ -1,5 +1,12 @@
 -- Create requisition_line table.
 
+CREATE TYPE consumption_calculation_method AS ENUM (
+    'SIMPLE_AVERAGE',
+    'EXCLUDE_STOCK_OUT_DAYS',
+    'WEIGHTED_MOVING_AVERAGE',
+    'LEAD_TIME_SAFETY_STOCK'
+);
+
 CREATE TABLE requisition_line (
     id TEXT NOT NULL PRIMARY KEY,
     requisition_id TEXT NOT NULL REFERENCES requisition (id),
@@ -11,5 +18,16 @@ CREATE TABLE requisition_line (
     average_monthly_consumption INTEGER NOT NULL,
     -- Calculation of stock on hand and average monthly consumption
     snapshot_datetime TIMESTAMP,
-    comment TEXT
+    comment TEXT,
+    -- Set when a supervisor authorises the response requisition
+    approved_quantity INTEGER NOT NULL DEFAULT 0,
+    approval_comment TEXT,
+    -- Stock movements of the period, only set for programme requisitions
+    opening_balance INTEGER NOT NULL DEFAULT 0,
+    received_quantity INTEGER NOT NULL DEFAULT 0,
+    issued_quantity INTEGER NOT NULL DEFAULT 0,
+    adjusted_quantity INTEGER NOT NULL DEFAULT 0,
+    closing_balance INTEGER NOT NULL DEFAULT 0,
+    -- Method used to calculate average monthly consumption and suggested quantity
+    consumption_calculation_method consumption_calculation_method NOT NULL DEFAULT 'SIMPLE_AVERAGE'
 )
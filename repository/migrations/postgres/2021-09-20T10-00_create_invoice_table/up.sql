@@ -1,7 +1,10 @@
 CREATE TYPE invoice_type AS ENUM (
     'OUTBOUND_SHIPMENT',
     'INBOUND_SHIPMENT',
-    'INVENTORY_ADJUSTMENT'
+    'INVENTORY_ADJUSTMENT',
+    'PRESCRIPTION',
+    'CUSTOMER_RETURN',
+    'SUPPLIER_RETURN'
 );
 
 CREATE TYPE invoice_status AS ENUM (
@@ -17,6 +20,9 @@ CREATE TABLE invoice (
     id TEXT NOT NULL PRIMARY KEY,
     -- For outbound shipments, the id of the receiving customer.
     -- For inbound shipments, the id of the sending supplier.
+    -- For prescriptions, the id of the patient.
+    -- For customer returns, the id of the returning customer.
+    -- For supplier returns, the id of the supplier the stock is returned to.
     name_id TEXT NOT NULL REFERENCES name(id),
     name_store_id TEXT REFERENCES store (id),
     -- Change to reference user_accoun once users are syncing
@@ -39,5 +45,9 @@ CREATE TABLE invoice (
     verified_datetime TIMESTAMP,
     colour TEXT,
     requisition_id TEXT,
-    linked_invoice_id TEXT
+    linked_invoice_id TEXT,
+    -- Prescription only fields
+    prescriber TEXT,
+    diagnosis TEXT,
+    directions TEXT
 )
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS inventory_adjustment_reason CASCADE;
+
+DROP TYPE IF EXISTS inventory_adjustment_type;
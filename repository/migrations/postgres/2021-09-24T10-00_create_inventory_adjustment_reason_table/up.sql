@@ -0,0 +1,11 @@
+CREATE TYPE inventory_adjustment_type AS ENUM (
+    'POSITIVE',
+    'NEGATIVE'
+);
+
+CREATE TABLE inventory_adjustment_reason (
+    id TEXT NOT NULL PRIMARY KEY,
+    type inventory_adjustment_type NOT NULL,
+    is_active BOOLEAN NOT NULL,
+    reason TEXT NOT NULL
+);
@@ -24,6 +24,12 @@ CREATE TABLE invoice_line (
     type invoice_line_type NOT NULL,
     number_of_packs INTEGER NOT NULL,
     pack_size INTEGER NOT NULL,
-    note TEXT
+    note TEXT,
+    -- Return lines only: the outbound/prescription line (customer return) or
+    -- inbound line (supplier return) the stock is being returned against
+    original_invoice_line_id TEXT,
+    return_reason TEXT,
+    -- Inventory adjustment lines only
+    inventory_adjustment_reason_id TEXT REFERENCES inventory_adjustment_reason(id)
 );
 
@@ -4,7 +4,10 @@ CREATE TYPE number_type AS ENUM (
     'INVENTORY_ADJUSTMENT',
     'STOCKTAKE',
     'REQUEST_REQUISITION',
-    'RESPONSE_REQUISITION'
+    'RESPONSE_REQUISITION',
+    'PRESCRIPTION',
+    'CUSTOMER_RETURN',
+    'SUPPLIER_RETURN'
 );
 
 -- Numbering table holding a list of typed counters
@@ -16,5 +16,13 @@ CREATE TABLE stocktake (
     stocktake_date DATE,
     finalised_datetime TIMESTAMP,
     is_locked BOOLEAN,
-    inventory_adjustment_id TEXT REFERENCES invoice(id)
+    inventory_adjustment_id TEXT REFERENCES invoice(id),
+    -- Snapshot quantities are not shown to the counter until the count is submitted
+    is_blind_count BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Lines with a count differing from the snapshot by more packs than this need a second count
+    recount_threshold INTEGER,
+    counted_by TEXT,
+    count_submitted_datetime TIMESTAMP,
+    -- User that finalised a blind count, must not be the user that counted
+    verified_by TEXT
 )
\ No newline at end of file
@@ -6,11 +6,15 @@ CREATE TABLE stocktake_line (
     comment	TEXT,
     snapshot_number_of_packs INTEGER NOT NULL,
     counted_number_of_packs INTEGER,
+    -- Recount of a line with a discrepancy, replaces the first count
+    second_counted_number_of_packs INTEGER,
     item_id TEXT NOT NULL REFERENCES item(id),
     batch TEXT,
     expiry_date DATE,
     pack_size INTEGER,
     cost_price_per_pack DOUBLE PRECISION,
     sell_price_per_pack DOUBLE PRECISION,
-    note TEXT 
+    note TEXT,
+    -- Reason for a difference between counted and snapshot number of packs
+    inventory_adjustment_reason_id TEXT REFERENCES inventory_adjustment_reason(id)
 )
@@ -47,12 +47,61 @@ WHERE invoice.type = 'INVENTORY_ADJUSTMENT'
 	AND verified_datetime IS NOT NULL
 	AND invoice_line.number_of_packs > 0;
 		
+CREATE VIEW prescription_stock_movement AS
+SELECT 
+    'n/a' as id,
+    number_of_packs * pack_size * -1 as quantity,
+	item_id,
+	store_id,
+	picked_datetime as datetime
+FROM invoice_line 
+JOIN invoice
+	ON invoice_line.invoice_id = invoice.id
+WHERE invoice.type = 'PRESCRIPTION' 
+	AND picked_datetime IS NOT NULL
+	AND invoice_line.number_of_packs > 0
+	AND invoice_line.type = 'STOCK_OUT';
+		
+CREATE VIEW customer_return_stock_movement AS
+SELECT 
+    'n/a' as id,
+    number_of_packs * pack_size as quantity,
+	item_id,
+	store_id,
+	delivered_datetime as datetime
+FROM invoice_line 
+JOIN invoice
+	ON invoice_line.invoice_id = invoice.id
+WHERE invoice.type = 'CUSTOMER_RETURN' 
+	AND delivered_datetime IS NOT NULL
+	AND invoice_line.number_of_packs > 0
+	AND invoice_line.type = 'STOCK_IN';
+		
+CREATE VIEW supplier_return_stock_movement AS
+SELECT 
+    'n/a' as id,
+    number_of_packs * pack_size * -1 as quantity,
+	item_id,
+	store_id,
+	picked_datetime as datetime
+FROM invoice_line 
+JOIN invoice
+	ON invoice_line.invoice_id = invoice.id
+WHERE invoice.type = 'SUPPLIER_RETURN' 
+	AND picked_datetime IS NOT NULL
+	AND invoice_line.number_of_packs > 0
+	AND invoice_line.type = 'STOCK_OUT';
+		
 CREATE VIEW stock_movement AS
 SELECT * FROM outbound_shipment_stock_movement
 UNION SELECT * from inbound_shipment_stock_movement
-UNION SELECT * from inventory_adjustment_stock_movement;
+UNION SELECT * from inventory_adjustment_stock_movement
+UNION SELECT * from prescription_stock_movement
+UNION SELECT * from customer_return_stock_movement
+UNION SELECT * from supplier_return_stock_movement;
 
 -- https://github.com/sussol/msupply/blob/master/Project/Sources/Methods/aggregator_stockConsumption.4dm
+-- Issues to customers and dispensing to patients are both consumption
 -- TODO sc type ?
 CREATE VIEW consumption AS
 SELECT 
@@ -64,7 +113,9 @@ SELECT
 	stock_movement.datetime::date AS date
 FROM
    (SELECT item.id AS item_id, store.id AS store_id FROM item, store) as items_and_stores
-LEFT OUTER JOIN outbound_shipment_stock_movement as stock_movement
+LEFT OUTER JOIN 
+	(SELECT * FROM outbound_shipment_stock_movement
+	UNION ALL SELECT * FROM prescription_stock_movement) as stock_movement
 	ON stock_movement.item_id = items_and_stores.item_id 
 		AND stock_movement.store_id = items_and_stores.store_id;
 
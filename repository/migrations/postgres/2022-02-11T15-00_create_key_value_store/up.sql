@@ -4,6 +4,8 @@ CREATE TYPE key_type AS ENUM (
     'REMOTE_SYNC_INITILISATION_STARTED',
     'REMOTE_SYNC_INITILISATION_FINISHED',
     'REMOTE_SYNC_PUSH_CURSOR',
+    -- progress of the initial sync
+    'INITIAL_SYNC_PROGRESS',
     -- sync settings
     'SETTINGS_SYNC_URL',
     'SETTINGS_SYNC_USERNAME',
@@ -11,7 +13,12 @@ CREATE TYPE key_type AS ENUM (
     'SETTINGS_SYNC_INTERVAL_SEC',
     'SETTINGS_SYNC_CENTRAL_SERVER_SITE_ID',
     'SETTINGS_SYNC_SIDE_ID',
-    'SETTINGS_SYNC_SIDE_HARDWARE_ID'
+    'SETTINGS_SYNC_SIDE_HARDWARE_ID',
+    'SETTINGS_SYNC_MAX_BACKOFF_SEC',
+    'SETTINGS_SYNC_PARTIAL_FAILURE_RETRY_SEC',
+    'SETTINGS_SYNC_MAX_JITTER_SEC',
+    -- secret to sign auth tokens
+    'SERVER_AUTH_TOKEN_SECRET'
 );
 
 -- key value store, e.g. to store local server state
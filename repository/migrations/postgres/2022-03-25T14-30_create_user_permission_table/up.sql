@@ -6,6 +6,7 @@ CREATE TYPE permission_type AS ENUM (
     'STOCKTAKE_MUTATE',
     'REQUISITION_QUERY',
     'REQUISITION_MUTATE',
+    'REQUISITION_AUTHORISE',
     'OUTBOUND_SHIPMENT_QUERY',
     'OUTBOUND_SHIPMENT_MUTATE',
     'INBOUND_SHIPMENT_QUERY',
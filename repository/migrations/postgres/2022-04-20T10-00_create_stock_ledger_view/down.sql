@@ -0,0 +1 @@
+DROP VIEW IF EXISTS stock_ledger CASCADE;
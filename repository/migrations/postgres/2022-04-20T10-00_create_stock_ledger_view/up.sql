@@ -0,0 +1,64 @@
+-- Every invoice line that moved stock in or out of a store, one row per line.
+-- Dates follow the same rules as the stock_movement view (e.g. outbound stock leaves when picked).
+CREATE VIEW stock_ledger AS
+SELECT
+    movement.id,
+    movement.item_id,
+    movement.store_id,
+    movement.stock_line_id,
+    movement.invoice_id,
+    movement.invoice_number,
+    movement.invoice_type,
+    name.name AS name,
+    movement.batch,
+    COALESCE(inventory_adjustment_reason.reason, movement.return_reason) AS reason,
+    movement.quantity,
+    movement.datetime,
+    SUM(movement.quantity) OVER (
+        PARTITION BY movement.store_id, movement.item_id
+        ORDER BY movement.datetime, movement.id
+    ) AS running_balance,
+    CASE
+        WHEN movement.stock_line_id IS NULL THEN NULL
+        ELSE SUM(movement.quantity) OVER (
+            PARTITION BY movement.stock_line_id
+            ORDER BY movement.datetime, movement.id
+        )
+    END AS stock_line_running_balance
+FROM (
+    SELECT
+        invoice_line.id,
+        invoice_line.item_id,
+        invoice.store_id,
+        invoice_line.stock_line_id,
+        invoice_line.invoice_id,
+        invoice.invoice_number,
+        invoice.type AS invoice_type,
+        invoice.name_id,
+        invoice_line.batch,
+        invoice_line.inventory_adjustment_reason_id,
+        invoice_line.return_reason,
+        CASE
+            WHEN invoice_line.type = 'STOCK_IN' THEN invoice_line.number_of_packs * invoice_line.pack_size
+            ELSE invoice_line.number_of_packs * invoice_line.pack_size * -1
+        END AS quantity,
+        CASE
+            WHEN invoice.type IN ('OUTBOUND_SHIPMENT', 'PRESCRIPTION', 'SUPPLIER_RETURN') THEN invoice.picked_datetime
+            WHEN invoice.type IN ('INBOUND_SHIPMENT', 'CUSTOMER_RETURN') THEN invoice.delivered_datetime
+            WHEN invoice.type = 'INVENTORY_ADJUSTMENT' THEN invoice.verified_datetime
+        END AS datetime
+    FROM invoice_line
+    JOIN invoice
+        ON invoice_line.invoice_id = invoice.id
+    WHERE invoice_line.number_of_packs > 0
+        AND (
+            (invoice.type IN ('OUTBOUND_SHIPMENT', 'PRESCRIPTION', 'SUPPLIER_RETURN') AND invoice_line.type = 'STOCK_OUT')
+            OR (invoice.type IN ('INBOUND_SHIPMENT', 'CUSTOMER_RETURN') AND invoice_line.type = 'STOCK_IN')
+            OR (invoice.type = 'INVENTORY_ADJUSTMENT' AND invoice_line.type IN ('STOCK_IN', 'STOCK_OUT'))
+        )
+) AS movement
+JOIN name
+    ON movement.name_id = name.id
+LEFT JOIN inventory_adjustment_reason
+    ON movement.inventory_adjustment_reason_id = inventory_adjustment_reason.id
+WHERE movement.datetime IS NOT NULL;
@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS customer_shelf_life CASCADE;
+
+DROP TABLE IF EXISTS store_preference CASCADE;
+
+DROP TYPE IF EXISTS allocation_strategy;
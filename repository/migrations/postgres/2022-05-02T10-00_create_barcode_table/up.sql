@@ -0,0 +1,10 @@
+-- GS1 barcodes (GTIN) printed on the packaging of an item
+CREATE TABLE barcode (
+    id TEXT NOT NULL PRIMARY KEY,
+    -- GTIN normalised to 14 digits
+    gtin TEXT NOT NULL UNIQUE,
+    item_id TEXT NOT NULL REFERENCES item(id),
+    manufacturer_id TEXT REFERENCES name(id),
+    -- Number of units in the scanned pack
+    pack_size INTEGER
+);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS reorder_run CASCADE;
+
+DROP TABLE IF EXISTS reorder_config CASCADE;
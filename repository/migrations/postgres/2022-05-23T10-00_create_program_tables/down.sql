@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS ix_requisition_program_period;
+
+DROP TABLE IF EXISTS program CASCADE;
+
+DROP TABLE IF EXISTS period CASCADE;
+
+DROP TABLE IF EXISTS period_schedule CASCADE;
+
+DROP TYPE IF EXISTS period_schedule_type;
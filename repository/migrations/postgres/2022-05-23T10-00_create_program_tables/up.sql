@@ -0,0 +1,30 @@
+CREATE TYPE period_schedule_type AS ENUM ('MONTHLY', 'QUARTERLY');
+
+-- Reporting cycle of a programme, e.g. monthly or quarterly periods
+CREATE TABLE period_schedule (
+    id TEXT NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL,
+    type period_schedule_type NOT NULL
+);
+
+CREATE TABLE period (
+    id TEXT NOT NULL PRIMARY KEY,
+    period_schedule_id TEXT NOT NULL REFERENCES period_schedule(id),
+    name TEXT NOT NULL,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    UNIQUE (period_schedule_id, start_date)
+);
+
+-- Items of a programme are the items of its master list
+CREATE TABLE program (
+    id TEXT NOT NULL PRIMARY KEY,
+    name TEXT NOT NULL,
+    master_list_id TEXT NOT NULL REFERENCES master_list(id),
+    period_schedule_id TEXT NOT NULL REFERENCES period_schedule(id)
+);
+
+-- A store can only request once for a programme period. Response requisitions are excluded, a
+-- supplier receives one for every customer store requesting for the period.
+CREATE UNIQUE INDEX ix_requisition_program_period ON requisition (store_id, program_id, period_id)
+WHERE type = 'REQUEST';
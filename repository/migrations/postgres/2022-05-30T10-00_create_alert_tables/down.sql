@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS alert CASCADE;
+
+DROP TABLE IF EXISTS alert_config CASCADE;
+
+DROP TYPE IF EXISTS alert_status;
+
+DROP TYPE IF EXISTS alert_type;
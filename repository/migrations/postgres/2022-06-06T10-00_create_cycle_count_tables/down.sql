@@ -0,0 +1,5 @@
+DROP TABLE IF EXISTS item_cycle_count CASCADE;
+
+DROP TABLE IF EXISTS cycle_count_config CASCADE;
+
+DROP TYPE IF EXISTS abc_class;
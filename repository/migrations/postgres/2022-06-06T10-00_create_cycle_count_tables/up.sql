@@ -0,0 +1,29 @@
+CREATE TYPE abc_class AS ENUM ('A', 'B', 'C');
+
+-- Cycle count schedule of a store, id is the id of the store
+CREATE TABLE cycle_count_config (
+    id TEXT NOT NULL PRIMARY KEY REFERENCES store(id),
+    is_enabled BOOLEAN NOT NULL,
+    -- Items making up this share of the yearly issue value (from the highest value down) are class A
+    a_class_percentage DOUBLE PRECISION NOT NULL,
+    -- Items making up the next share of the yearly issue value are class B, the rest are class C
+    b_class_percentage DOUBLE PRECISION NOT NULL,
+    -- Number of days between counts of an item of each class
+    a_class_count_frequency_days INTEGER NOT NULL,
+    b_class_count_frequency_days INTEGER NOT NULL,
+    c_class_count_frequency_days INTEGER NOT NULL
+);
+
+-- ABC class and last count of an item in a store
+CREATE TABLE item_cycle_count (
+    id TEXT NOT NULL PRIMARY KEY,
+    store_id TEXT NOT NULL REFERENCES store(id),
+    item_id TEXT NOT NULL REFERENCES item(id),
+    abc_class abc_class NOT NULL,
+    -- Value of the stock issued over the last year when the item was classified
+    issue_value DOUBLE PRECISION NOT NULL,
+    classified_datetime TIMESTAMP NOT NULL,
+    -- Set when a stocktake counting the item is finalised
+    last_counted_datetime TIMESTAMP,
+    UNIQUE (store_id, item_id)
+);
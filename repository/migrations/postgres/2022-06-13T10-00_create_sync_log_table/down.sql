@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS sync_log CASCADE;
+
+DROP TYPE IF EXISTS sync_log_phase;
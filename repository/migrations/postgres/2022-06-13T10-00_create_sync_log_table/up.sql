@@ -0,0 +1,16 @@
+CREATE TYPE sync_log_phase AS ENUM ('PUSH', 'REMOTE_PULL', 'CENTRAL_PULL', 'INTEGRATE');
+
+-- One row per sync run, local to the site and not synced
+CREATE TABLE sync_log (
+    id TEXT NOT NULL PRIMARY KEY,
+    started_datetime TIMESTAMP NOT NULL,
+    -- Set once the sync run is done, successful or not
+    finished_datetime TIMESTAMP,
+    -- Phase the sync run is in, or was in when it finished or failed
+    phase sync_log_phase NOT NULL,
+    push_record_count INTEGER NOT NULL,
+    remote_pull_record_count INTEGER NOT NULL,
+    central_pull_record_count INTEGER NOT NULL,
+    integration_record_count INTEGER NOT NULL,
+    error_message TEXT
+);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS sync_error CASCADE;
+
+DROP TYPE IF EXISTS sync_error_source;
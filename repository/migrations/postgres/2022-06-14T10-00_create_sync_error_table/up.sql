@@ -0,0 +1,17 @@
+CREATE TYPE sync_error_source AS ENUM ('CENTRAL', 'REMOTE');
+
+-- Sync records that failed translation or integration, local to the site and not synced
+CREATE TABLE sync_error (
+    id TEXT NOT NULL PRIMARY KEY,
+    source sync_error_source NOT NULL,
+    table_name TEXT NOT NULL,
+    record_id TEXT NOT NULL,
+    -- Only set for remote records
+    remote_action TEXT,
+    -- Raw JSON of the sync record
+    data TEXT NOT NULL,
+    error_message TEXT NOT NULL,
+    attempt_count INTEGER NOT NULL,
+    created_datetime TIMESTAMP NOT NULL,
+    last_attempt_datetime TIMESTAMP NOT NULL
+);
@@ -0,0 +1,8 @@
+-- Quantity of a backorder placed on a follow-up outbound shipment, deducted from the remaining
+-- quantity of the backorder once the shipment is shipped
+CREATE TABLE backorder_shipment (
+    id TEXT NOT NULL PRIMARY KEY,
+    backorder_id TEXT NOT NULL REFERENCES backorder(id),
+    invoice_id TEXT NOT NULL REFERENCES invoice(id),
+    quantity INTEGER NOT NULL
+);
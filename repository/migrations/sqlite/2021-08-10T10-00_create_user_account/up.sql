@@ -5,5 +5,8 @@ CREATE TABLE user_account (
     username TEXT NOT NULL,
     -- Hashed password
     hashed_password TEXT NOT NULL,
-    email TEXT
+    email TEXT,
+    -- User is managed on this site only and is not fetched from the central server
+    is_local BOOLEAN NOT NULL DEFAULT false,
+    is_disabled BOOLEAN NOT NULL DEFAULT false
 )
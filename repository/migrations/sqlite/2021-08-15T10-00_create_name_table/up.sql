@@ -5,8 +5,18 @@ CREATE TABLE name (
     -- Human-readable representation of the entity associated with the name record.
     name TEXT NOT NULL,
     code TEXT NOT NULL,
+    type TEXT CHECK (type IN ('FACILITY', 'PATIENT', 'BUILD', 'INVAD', 'REPACK', 'STORE', 'OTHERS')) NOT NULL DEFAULT 'FACILITY',
     is_customer BOOLEAN NOT NULL,
     is_supplier BOOLEAN NOT NULL,
+    -- Patient details
+    first_name TEXT,
+    last_name TEXT,
+    gender TEXT CHECK (gender IN ('FEMALE', 'MALE', 'OTHER')),
+    date_of_birth TEXT,
+    national_health_number TEXT,
+    phone TEXT,
+    email TEXT,
+    address TEXT,
     -- TODO, this is temporary, remove
     legacy_record TEXT NOT NULL
 )
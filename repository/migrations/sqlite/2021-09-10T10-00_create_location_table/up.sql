@@ -3,6 +3,10 @@ CREATE TABLE location (
     code TEXT NOT NULL,
     name TEXT NOT NULL,
     on_hold BOOLEAN NOT NULL,
-    store_id TEXT NOT NULL REFERENCES store(id)
+    store_id TEXT NOT NULL REFERENCES store(id),
+    parent_location_id TEXT REFERENCES location(id),
+    capacity INTEGER,
+    min_temperature REAL,
+    max_temperature REAL
 );
 
@@ -8,7 +8,7 @@ CREATE TABLE requisition (
     -- Change to reference user_accoun once users are syncing
     user_id TEXT,
     type TEXT CHECK (type IN ('REQUEST', 'RESPONSE')) NOT NULL,
-    status TEXT CHECK (status IN ('DRAFT', 'NEW', 'SENT', 'FINALISED')) NOT NULL,
+    status TEXT CHECK (status IN ('DRAFT', 'NEW', 'AUTHORISED', 'SENT', 'FINALISED')) NOT NULL,
     created_datetime TEXT NOT NULL,
     sent_datetime TEXT,
     finalised_datetime TEXT,
@@ -18,5 +18,10 @@ CREATE TABLE requisition (
     their_reference TEXT,
     max_months_of_stock  DOUBLE PRECISION NOT NULL,
     min_months_of_stock DOUBLE PRECISION NOT NULL,
-    linked_requisition_id TEXT
+    linked_requisition_id TEXT,
+    -- Set for requisitions of a programme, reporting on the stock movements of the period
+    program_id TEXT,
+    period_id TEXT,
+    -- User that authorised the response requisition, user_id stays the last editor
+    approved_by_user_id TEXT
 )
@@ -11,5 +11,16 @@ CREATE TABLE requisition_line (
     average_monthly_consumption INTEGER NOT NULL,
     -- Calculation of stock on hand and average monthly consumption
     snapshot_datetime TEXT,
-    comment TEXT
+    comment TEXT,
+    -- Set when a supervisor authorises the response requisition
+    approved_quantity INTEGER NOT NULL DEFAULT 0,
+    approval_comment TEXT,
+    -- Stock movements of the period, only set for programme requisitions
+    opening_balance INTEGER NOT NULL DEFAULT 0,
+    received_quantity INTEGER NOT NULL DEFAULT 0,
+    issued_quantity INTEGER NOT NULL DEFAULT 0,
+    adjusted_quantity INTEGER NOT NULL DEFAULT 0,
+    closing_balance INTEGER NOT NULL DEFAULT 0,
+    -- Method used to calculate average monthly consumption and suggested quantity
+    consumption_calculation_method TEXT CHECK (consumption_calculation_method IN ('SIMPLE_AVERAGE', 'EXCLUDE_STOCK_OUT_DAYS', 'WEIGHTED_MOVING_AVERAGE', 'LEAD_TIME_SAFETY_STOCK')) NOT NULL DEFAULT 'SIMPLE_AVERAGE'
 )
@@ -2,6 +2,9 @@ CREATE TABLE invoice (
     id TEXT NOT NULL PRIMARY KEY,
     -- For outbound shipments, the id of the receiving customer.
     -- For inbound shipments, the id of the sending supplier.
+    -- For prescriptions, the id of the patient.
+    -- For customer returns, the id of the returning customer.
+    -- For supplier returns, the id of the supplier the stock is returned to.
     name_id TEXT NOT NULL REFERENCES name(id),
     name_store_id TEXT REFERENCES store (id),
     -- Change to reference user_accoun once users are syncing
@@ -10,7 +13,7 @@ CREATE TABLE invoice (
     -- For inbound shipments, the id of the receiving store.
     store_id TEXT NOT NULL REFERENCES store (id),
     invoice_number integer NOT NULL,
-    type TEXT CHECK (type IN ('OUTBOUND_SHIPMENT', 'INBOUND_SHIPMENT', 'INVENTORY_ADJUSTMENT')) NOT NULL,
+    type TEXT CHECK (type IN ('OUTBOUND_SHIPMENT', 'INBOUND_SHIPMENT', 'INVENTORY_ADJUSTMENT', 'PRESCRIPTION', 'CUSTOMER_RETURN', 'SUPPLIER_RETURN')) NOT NULL,
     status TEXT CHECK (status IN ('NEW','ALLOCATED', 'PICKED', 'SHIPPED',  'DELIVERED', 'VERIFIED')) NOT NULL,
     on_hold BOOLEAN NOT NULL,
     comment TEXT,
@@ -24,5 +27,9 @@ CREATE TABLE invoice (
     verified_datetime TEXT,
     colour TEXT,
     requisition_id TEXT,
-    linked_invoice_id TEXT
+    linked_invoice_id TEXT,
+    -- Prescription only fields
+    prescriber TEXT,
+    diagnosis TEXT,
+    directions TEXT
 )
@@ -0,0 +1,6 @@
+CREATE TABLE inventory_adjustment_reason (
+    id TEXT NOT NULL PRIMARY KEY,
+    type TEXT CHECK (type IN ('POSITIVE', 'NEGATIVE')) NOT NULL,
+    is_active BOOLEAN NOT NULL,
+    reason TEXT NOT NULL
+);
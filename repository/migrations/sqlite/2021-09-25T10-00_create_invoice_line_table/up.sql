@@ -17,6 +17,12 @@ CREATE TABLE invoice_line (
     type TEXT CHECK (type IN ('STOCK_IN', 'STOCK_OUT', 'UNALLOCATED_STOCK', 'SERVICE')) NOT NULL,
     number_of_packs INTEGER NOT NULL,
     pack_size INTEGER NOT NULL,
-    note TEXT
+    note TEXT,
+    -- Return lines only: the outbound/prescription line (customer return) or
+    -- inbound line (supplier return) the stock is being returned against
+    original_invoice_line_id TEXT,
+    return_reason TEXT,
+    -- Inventory adjustment lines only
+    inventory_adjustment_reason_id TEXT REFERENCES inventory_adjustment_reason(id)
 );
 
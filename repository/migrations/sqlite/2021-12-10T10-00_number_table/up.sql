@@ -4,5 +4,5 @@ CREATE TABLE number (
     -- current counter value
     value BIGINT NOT NULL,
     store_id TEXT NOT NULL REFERENCES store(id),
-    type TEXT CHECK (type IN ('INBOUND_SHIPMENT', 'OUTBOUND_SHIPMENT', 'INVENTORY_ADJUSTMENT', 'STOCKTAKE', 'REQUEST_REQUISITION', 'RESPONSE_REQUISITION')) NOT NULL
+    type TEXT CHECK (type IN ('INBOUND_SHIPMENT', 'OUTBOUND_SHIPMENT', 'INVENTORY_ADJUSTMENT', 'STOCKTAKE', 'REQUEST_REQUISITION', 'RESPONSE_REQUISITION', 'PRESCRIPTION', 'CUSTOMER_RETURN', 'SUPPLIER_RETURN')) NOT NULL
 )
\ No newline at end of file
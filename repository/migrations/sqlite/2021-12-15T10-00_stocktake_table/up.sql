@@ -11,5 +11,13 @@ CREATE TABLE stocktake (
     stocktake_date TEXT,
     finalised_datetime TEXT,
     is_locked BOOLEAN,
-    inventory_adjustment_id TEXT REFERENCES invoice(id)
+    inventory_adjustment_id TEXT REFERENCES invoice(id),
+    -- Snapshot quantities are not shown to the counter until the count is submitted
+    is_blind_count BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Lines with a count differing from the snapshot by more packs than this need a second count
+    recount_threshold INTEGER,
+    counted_by TEXT,
+    count_submitted_datetime TEXT,
+    -- User that finalised a blind count, must not be the user that counted
+    verified_by TEXT
 )
\ No newline at end of file
@@ -10,6 +10,7 @@ CREATE TABLE user_permission (
         'STOCKTAKE_MUTATE',
         'REQUISITION_QUERY',
         'REQUISITION_MUTATE',
+        'REQUISITION_AUTHORISE',
         'OUTBOUND_SHIPMENT_QUERY',
         'OUTBOUND_SHIPMENT_MUTATE',
         'INBOUND_SHIPMENT_QUERY',
@@ -0,0 +1,11 @@
+-- Issued auth and refresh tokens, persisted so that sessions survive a server restart
+CREATE TABLE auth_token (
+    -- sha256 hash of the token
+    token_hash TEXT NOT NULL PRIMARY KEY,
+    -- auth and refresh tokens issued in the same login share the same session id
+    session_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES user_account(id),
+    -- unix timestamps [s]
+    issued_date BIGINT NOT NULL,
+    expiry_date BIGINT NOT NULL
+)
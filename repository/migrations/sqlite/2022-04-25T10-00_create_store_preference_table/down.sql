@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS customer_shelf_life;
+
+DROP TABLE IF EXISTS store_preference;
@@ -0,0 +1,25 @@
+-- Local preferences of a store, id is the id of the store
+CREATE TABLE store_preference (
+    id TEXT NOT NULL PRIMARY KEY REFERENCES store(id),
+    allocation_strategy TEXT CHECK (allocation_strategy IN ('FEFO', 'FIFO')) NOT NULL,
+    -- Stock in this location is allocated first
+    preferred_location_id TEXT REFERENCES location(id),
+    -- Stock expiring within this number of days is not allocated
+    minimum_shelf_life_days INTEGER,
+    -- Response requisitions must be authorised before shipments can be created
+    requisitions_require_authorisation BOOLEAN NOT NULL DEFAULT FALSE,
+    -- Method used to calculate average monthly consumption and suggested quantity of requisitions
+    consumption_calculation_method TEXT CHECK (consumption_calculation_method IN ('SIMPLE_AVERAGE', 'EXCLUDE_STOCK_OUT_DAYS', 'WEIGHTED_MOVING_AVERAGE', 'LEAD_TIME_SAFETY_STOCK')) NOT NULL DEFAULT 'SIMPLE_AVERAGE',
+    -- Added to the months of stock of requisitions by the LEAD_TIME_SAFETY_STOCK method
+    lead_time_months DOUBLE PRECISION NOT NULL DEFAULT 0,
+    safety_stock_months DOUBLE PRECISION NOT NULL DEFAULT 0
+);
+
+-- Minimum remaining shelf life of stock issued to a customer, overrides the store preference
+CREATE TABLE customer_shelf_life (
+    id TEXT NOT NULL PRIMARY KEY,
+    store_id TEXT NOT NULL REFERENCES store(id),
+    name_id TEXT NOT NULL REFERENCES name(id),
+    minimum_shelf_life_days INTEGER NOT NULL,
+    UNIQUE (store_id, name_id)
+);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS reorder_run;
+
+DROP TABLE IF EXISTS reorder_config;
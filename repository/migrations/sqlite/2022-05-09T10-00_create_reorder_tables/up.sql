@@ -0,0 +1,20 @@
+-- Automatic reorder settings of a store, id is the id of the store
+CREATE TABLE reorder_config (
+    id TEXT NOT NULL PRIMARY KEY REFERENCES store(id),
+    is_enabled BOOLEAN NOT NULL,
+    -- Default supplier the generated request requisitions are sent to
+    supplier_id TEXT REFERENCES name(id),
+    min_months_of_stock DOUBLE PRECISION NOT NULL,
+    max_months_of_stock DOUBLE PRECISION NOT NULL
+);
+
+-- Outcome of an automatic reorder run
+CREATE TABLE reorder_run (
+    id TEXT NOT NULL PRIMARY KEY,
+    store_id TEXT NOT NULL REFERENCES store(id),
+    run_datetime TIMESTAMP NOT NULL,
+    -- Not set if no item was below the minimum months of stock or the run failed
+    requisition_id TEXT REFERENCES requisition(id),
+    number_of_items INTEGER NOT NULL,
+    error TEXT
+);
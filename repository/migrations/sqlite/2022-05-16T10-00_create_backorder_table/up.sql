@@ -0,0 +1,15 @@
+-- Quantity of a response requisition line that was still outstanding when the requisition was
+-- finalised, supplied later by follow-up outbound shipments
+CREATE TABLE backorder (
+    id TEXT NOT NULL PRIMARY KEY,
+    store_id TEXT NOT NULL REFERENCES store(id),
+    requisition_id TEXT NOT NULL REFERENCES requisition(id),
+    requisition_line_id TEXT NOT NULL REFERENCES requisition_line(id),
+    -- Customer the stock is owed to
+    name_id TEXT NOT NULL REFERENCES name(id),
+    item_id TEXT NOT NULL REFERENCES item(id),
+    created_datetime TIMESTAMP NOT NULL,
+    backordered_quantity INTEGER NOT NULL,
+    -- Quantity not yet added to a follow-up shipment, backorder is open while above zero
+    remaining_quantity INTEGER NOT NULL
+);
@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS ix_requisition_program_period;
+
+DROP TABLE IF EXISTS program;
+
+DROP TABLE IF EXISTS period;
+
+DROP TABLE IF EXISTS period_schedule;
@@ -0,0 +1,38 @@
+-- Alert rules of a store, id is the id of the store
+CREATE TABLE alert_config (
+    id TEXT NOT NULL PRIMARY KEY REFERENCES store(id),
+    is_enabled BOOLEAN NOT NULL,
+    -- Items with less stock than this are low on stock
+    min_months_of_stock DOUBLE PRECISION NOT NULL,
+    -- Batches expiring within this number of days are expiring stock
+    expiry_warning_days INTEGER NOT NULL,
+    -- Shipped inbound shipments not delivered after this number of days are overdue
+    inbound_shipment_overdue_days INTEGER NOT NULL
+);
+
+-- Alert raised by a rule, the record the alert is about is set depending on the type
+CREATE TABLE alert (
+    id TEXT NOT NULL PRIMARY KEY,
+    store_id TEXT NOT NULL REFERENCES store(id),
+    type TEXT CHECK (
+        type IN (
+            'LOW_STOCK',
+            'STOCK_OUT',
+            'EXPIRING_STOCK',
+            'INBOUND_SHIPMENT_NOT_RECEIVED'
+        )
+    ) NOT NULL,
+    status TEXT CHECK (status IN ('ACTIVE', 'ACKNOWLEDGED', 'DISMISSED')) NOT NULL,
+    item_id TEXT REFERENCES item(id),
+    -- Not referenced, stock lines and invoices can be deleted while an alert exists
+    stock_line_id TEXT,
+    invoice_id TEXT,
+    message TEXT NOT NULL,
+    created_datetime TIMESTAMP NOT NULL,
+    -- User that last acknowledged or dismissed the alert
+    user_id TEXT,
+    acknowledged_datetime TIMESTAMP,
+    dismissed_datetime TIMESTAMP,
+    -- Set once the rule no longer applies, a new alert is raised if it applies again
+    resolved_datetime TIMESTAMP
+);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS item_cycle_count;
+
+DROP TABLE IF EXISTS cycle_count_config;
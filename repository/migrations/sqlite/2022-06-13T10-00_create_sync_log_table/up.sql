@@ -0,0 +1,16 @@
+-- One row per sync run, local to the site and not synced
+CREATE TABLE sync_log (
+    id TEXT NOT NULL PRIMARY KEY,
+    started_datetime TIMESTAMP NOT NULL,
+    -- Set once the sync run is done, successful or not
+    finished_datetime TIMESTAMP,
+    -- Phase the sync run is in, or was in when it finished or failed
+    phase TEXT CHECK (
+        phase IN ('PUSH', 'REMOTE_PULL', 'CENTRAL_PULL', 'INTEGRATE')
+    ) NOT NULL,
+    push_record_count INTEGER NOT NULL,
+    remote_pull_record_count INTEGER NOT NULL,
+    central_pull_record_count INTEGER NOT NULL,
+    integration_record_count INTEGER NOT NULL,
+    error_message TEXT
+);
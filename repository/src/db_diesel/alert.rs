@@ -0,0 +1,187 @@
+use super::{
+    alert_row::{alert, alert::dsl as alert_dsl},
+    AlertRow, AlertStatus, AlertType, StorageConnection,
+};
+
+use crate::diesel_macros::{apply_date_time_filter, apply_equal_filter, apply_sort};
+use crate::{DBType, DatetimeFilter, EqualFilter, Pagination, RepositoryError, Sort};
+
+use diesel::{dsl::IntoBoxed, prelude::*};
+
+pub type Alert = AlertRow;
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct AlertFilter {
+    pub id: Option<EqualFilter<String>>,
+    pub store_id: Option<EqualFilter<String>>,
+    pub r#type: Option<EqualFilter<AlertType>>,
+    pub status: Option<EqualFilter<AlertStatus>>,
+    pub item_id: Option<EqualFilter<String>>,
+    pub created_datetime: Option<DatetimeFilter>,
+    /// Only alerts whose rule no longer applies (or still applies)
+    pub is_resolved: Option<bool>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum AlertSortField {
+    CreatedDatetime,
+    Type,
+    Status,
+}
+
+pub type AlertSort = Sort<AlertSortField>;
+
+impl AlertFilter {
+    pub fn new() -> AlertFilter {
+        AlertFilter::default()
+    }
+
+    pub fn id(mut self, filter: EqualFilter<String>) -> Self {
+        self.id = Some(filter);
+        self
+    }
+
+    pub fn store_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.store_id = Some(filter);
+        self
+    }
+
+    pub fn r#type(mut self, filter: EqualFilter<AlertType>) -> Self {
+        self.r#type = Some(filter);
+        self
+    }
+
+    pub fn status(mut self, filter: EqualFilter<AlertStatus>) -> Self {
+        self.status = Some(filter);
+        self
+    }
+
+    pub fn item_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.item_id = Some(filter);
+        self
+    }
+
+    pub fn created_datetime(mut self, filter: DatetimeFilter) -> Self {
+        self.created_datetime = Some(filter);
+        self
+    }
+
+    pub fn is_resolved(mut self, value: bool) -> Self {
+        self.is_resolved = Some(value);
+        self
+    }
+}
+
+impl AlertType {
+    pub fn equal_to(&self) -> EqualFilter<AlertType> {
+        EqualFilter {
+            equal_to: Some(self.clone()),
+            not_equal_to: None,
+            equal_any: None,
+            not_equal_all: None,
+        }
+    }
+}
+
+impl AlertStatus {
+    pub fn equal_to(&self) -> EqualFilter<AlertStatus> {
+        EqualFilter {
+            equal_to: Some(self.clone()),
+            not_equal_to: None,
+            equal_any: None,
+            not_equal_all: None,
+        }
+    }
+
+    pub fn not_equal_to(&self) -> EqualFilter<AlertStatus> {
+        EqualFilter {
+            equal_to: None,
+            not_equal_to: Some(self.clone()),
+            equal_any: None,
+            not_equal_all: None,
+        }
+    }
+}
+
+pub struct AlertRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> AlertRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        AlertRepository { connection }
+    }
+
+    pub fn count(&self, filter: Option<AlertFilter>) -> Result<i64, RepositoryError> {
+        let query = create_filtered_query(filter);
+        Ok(query.count().get_result(&self.connection.connection)?)
+    }
+
+    pub fn query_by_filter(&self, filter: AlertFilter) -> Result<Vec<Alert>, RepositoryError> {
+        self.query(Pagination::new(), Some(filter), None)
+    }
+
+    pub fn query(
+        &self,
+        pagination: Pagination,
+        filter: Option<AlertFilter>,
+        sort: Option<AlertSort>,
+    ) -> Result<Vec<Alert>, RepositoryError> {
+        let mut query = create_filtered_query(filter);
+        if let Some(sort) = sort {
+            match sort.key {
+                AlertSortField::CreatedDatetime => {
+                    apply_sort!(query, sort, alert_dsl::created_datetime);
+                }
+                AlertSortField::Type => {
+                    apply_sort!(query, sort, alert_dsl::type_);
+                }
+                AlertSortField::Status => {
+                    apply_sort!(query, sort, alert_dsl::status);
+                }
+            }
+        } else {
+            // Newest first, as shown in the alert inbox
+            query = query.order((alert_dsl::created_datetime.desc(), alert_dsl::id.asc()))
+        }
+
+        let result = query
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load::<Alert>(&self.connection.connection)?;
+
+        Ok(result)
+    }
+}
+
+type BoxedAlertQuery = IntoBoxed<'static, alert::table, DBType>;
+
+fn create_filtered_query(filter: Option<AlertFilter>) -> BoxedAlertQuery {
+    let mut query = alert_dsl::alert.into_boxed();
+
+    if let Some(f) = filter {
+        let AlertFilter {
+            id,
+            store_id,
+            r#type,
+            status,
+            item_id,
+            created_datetime,
+            is_resolved,
+        } = f;
+
+        apply_equal_filter!(query, id, alert_dsl::id);
+        apply_equal_filter!(query, store_id, alert_dsl::store_id);
+        apply_equal_filter!(query, r#type, alert_dsl::type_);
+        apply_equal_filter!(query, status, alert_dsl::status);
+        apply_equal_filter!(query, item_id, alert_dsl::item_id);
+        apply_date_time_filter!(query, created_datetime, alert_dsl::created_datetime);
+        match is_resolved {
+            Some(true) => query = query.filter(alert_dsl::resolved_datetime.is_not_null()),
+            Some(false) => query = query.filter(alert_dsl::resolved_datetime.is_null()),
+            None => {}
+        }
+    }
+
+    query
+}
@@ -0,0 +1,78 @@
+use super::{
+    alert_config_row::alert_config::dsl as alert_config_dsl, store_row::store, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+
+table! {
+    alert_config (id) {
+        id -> Text,
+        is_enabled -> Bool,
+        min_months_of_stock -> Double,
+        expiry_warning_days -> Integer,
+        inbound_shipment_overdue_days -> Integer,
+    }
+}
+
+joinable!(alert_config -> store (id));
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[table_name = "alert_config"]
+pub struct AlertConfigRow {
+    /// Id of the store
+    pub id: String,
+    /// Alert rules are evaluated periodically if enabled
+    pub is_enabled: bool,
+    /// Items with less stock than this are low on stock
+    pub min_months_of_stock: f64,
+    /// Batches expiring within this number of days are expiring stock
+    pub expiry_warning_days: i32,
+    /// Shipped inbound shipments not delivered after this number of days are overdue
+    pub inbound_shipment_overdue_days: i32,
+}
+
+pub struct AlertConfigRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> AlertConfigRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        AlertConfigRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &AlertConfigRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(alert_config_dsl::alert_config)
+            .values(row)
+            .on_conflict(alert_config_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &AlertConfigRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(alert_config_dsl::alert_config)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<AlertConfigRow>, RepositoryError> {
+        let result = alert_config_dsl::alert_config
+            .filter(alert_config_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_all_enabled(&self) -> Result<Vec<AlertConfigRow>, RepositoryError> {
+        let result = alert_config_dsl::alert_config
+            .filter(alert_config_dsl::is_enabled.eq(true))
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,110 @@
+use super::{
+    alert_row::alert::dsl as alert_dsl, item_row::item, store_row::store, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    alert (id) {
+        id -> Text,
+        store_id -> Text,
+        #[sql_name = "type"] type_ -> crate::db_diesel::alert_row::AlertTypeMapping,
+        status -> crate::db_diesel::alert_row::AlertStatusMapping,
+        item_id -> Nullable<Text>,
+        stock_line_id -> Nullable<Text>,
+        invoice_id -> Nullable<Text>,
+        message -> Text,
+        created_datetime -> Timestamp,
+        user_id -> Nullable<Text>,
+        acknowledged_datetime -> Nullable<Timestamp>,
+        dismissed_datetime -> Nullable<Timestamp>,
+        resolved_datetime -> Nullable<Timestamp>,
+    }
+}
+
+joinable!(alert -> store (store_id));
+joinable!(alert -> item (item_id));
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum AlertType {
+    /// Item has less than the minimum months of stock (item_id is set)
+    LowStock,
+    /// Item in use has no available stock (item_id is set)
+    StockOut,
+    /// Batch is expired or expires soon (item_id and stock_line_id are set)
+    ExpiringStock,
+    /// Inbound shipment was shipped but not delivered in time (invoice_id is set)
+    InboundShipmentNotReceived,
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum AlertStatus {
+    Active,
+    Acknowledged,
+    Dismissed,
+}
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
+#[changeset_options(treat_none_as_null = "true")]
+#[table_name = "alert"]
+pub struct AlertRow {
+    pub id: String,
+    pub store_id: String,
+    #[column_name = "type_"]
+    pub r#type: AlertType,
+    pub status: AlertStatus,
+    pub item_id: Option<String>,
+    pub stock_line_id: Option<String>,
+    pub invoice_id: Option<String>,
+    pub message: String,
+    pub created_datetime: NaiveDateTime,
+    /// User that last acknowledged or dismissed the alert
+    pub user_id: Option<String>,
+    pub acknowledged_datetime: Option<NaiveDateTime>,
+    pub dismissed_datetime: Option<NaiveDateTime>,
+    /// Set once the rule no longer applies, a new alert is raised if it applies again
+    pub resolved_datetime: Option<NaiveDateTime>,
+}
+
+pub struct AlertRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> AlertRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        AlertRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &AlertRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(alert_dsl::alert)
+            .values(row)
+            .on_conflict(alert_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &AlertRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(alert_dsl::alert)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<AlertRow>, RepositoryError> {
+        let result = alert_dsl::alert
+            .filter(alert_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,104 @@
+use super::{
+    auth_token_row::auth_token::dsl as auth_token_dsl, user_row::user_account, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+
+table! {
+    auth_token (token_hash) {
+        token_hash -> Text,
+        session_id -> Text,
+        user_id -> Text,
+        issued_date -> BigInt,
+        expiry_date -> BigInt,
+    }
+}
+
+joinable!(auth_token -> user_account (user_id));
+
+allow_tables_to_appear_in_same_query!(auth_token, user_account);
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Eq)]
+#[table_name = "auth_token"]
+pub struct AuthTokenRow {
+    /// Sha256 hash of the token
+    pub token_hash: String,
+    /// Auth and refresh tokens that have been issued in the same login share the same session id
+    pub session_id: String,
+    pub user_id: String,
+    /// Unix timestamp [s]
+    pub issued_date: i64,
+    /// Unix timestamp [s]
+    pub expiry_date: i64,
+}
+
+pub struct AuthTokenRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> AuthTokenRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        AuthTokenRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &AuthTokenRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(auth_token_dsl::auth_token)
+            .values(row)
+            .on_conflict(auth_token_dsl::token_hash)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &AuthTokenRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(auth_token_dsl::auth_token)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<AuthTokenRow>, RepositoryError> {
+        let result = auth_token_dsl::auth_token
+            .filter(auth_token_dsl::token_hash.eq(token_hash))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Returns all tokens that are not expired at the given unix timestamp
+    pub fn find_valid(&self, now: i64) -> Result<Vec<AuthTokenRow>, RepositoryError> {
+        let result = auth_token_dsl::auth_token
+            .filter(auth_token_dsl::expiry_date.ge(now))
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+
+    pub fn delete_by_session_id(&self, session_id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(
+            auth_token_dsl::auth_token.filter(auth_token_dsl::session_id.eq(session_id)),
+        )
+        .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn delete_by_user_id(&self, user_id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(auth_token_dsl::auth_token.filter(auth_token_dsl::user_id.eq(user_id)))
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    /// Removes all tokens that expired before the given unix timestamp
+    pub fn delete_expired(&self, now: i64) -> Result<(), RepositoryError> {
+        diesel::delete(auth_token_dsl::auth_token.filter(auth_token_dsl::expiry_date.lt(now)))
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use super::{
+    backorder_row::{backorder, backorder::dsl as backorder_dsl},
+    BackorderRow, StorageConnection,
+};
+
+use crate::diesel_macros::{apply_equal_filter, apply_sort, apply_sort_no_case};
+use crate::{DBType, EqualFilter, Pagination, RepositoryError, Sort};
+
+use diesel::{dsl::IntoBoxed, prelude::*};
+
+pub type Backorder = BackorderRow;
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct BackorderFilter {
+    pub id: Option<EqualFilter<String>>,
+    pub store_id: Option<EqualFilter<String>>,
+    pub requisition_id: Option<EqualFilter<String>>,
+    pub name_id: Option<EqualFilter<String>>,
+    pub item_id: Option<EqualFilter<String>>,
+    /// Only backorders with (or without) a remaining quantity
+    pub is_open: Option<bool>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum BackorderSortField {
+    CreatedDatetime,
+    ItemId,
+    NameId,
+}
+
+pub type BackorderSort = Sort<BackorderSortField>;
+
+impl BackorderFilter {
+    pub fn new() -> BackorderFilter {
+        BackorderFilter::default()
+    }
+
+    pub fn id(mut self, filter: EqualFilter<String>) -> Self {
+        self.id = Some(filter);
+        self
+    }
+
+    pub fn store_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.store_id = Some(filter);
+        self
+    }
+
+    pub fn requisition_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.requisition_id = Some(filter);
+        self
+    }
+
+    pub fn name_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.name_id = Some(filter);
+        self
+    }
+
+    pub fn item_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.item_id = Some(filter);
+        self
+    }
+
+    pub fn is_open(mut self, value: bool) -> Self {
+        self.is_open = Some(value);
+        self
+    }
+}
+
+pub struct BackorderRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> BackorderRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        BackorderRepository { connection }
+    }
+
+    pub fn count(&self, filter: Option<BackorderFilter>) -> Result<i64, RepositoryError> {
+        let query = create_filtered_query(filter);
+        Ok(query.count().get_result(&self.connection.connection)?)
+    }
+
+    pub fn query_by_filter(
+        &self,
+        filter: BackorderFilter,
+    ) -> Result<Vec<Backorder>, RepositoryError> {
+        self.query(Pagination::new(), Some(filter), None)
+    }
+
+    pub fn query(
+        &self,
+        pagination: Pagination,
+        filter: Option<BackorderFilter>,
+        sort: Option<BackorderSort>,
+    ) -> Result<Vec<Backorder>, RepositoryError> {
+        let mut query = create_filtered_query(filter);
+        if let Some(sort) = sort {
+            match sort.key {
+                BackorderSortField::CreatedDatetime => {
+                    apply_sort!(query, sort, backorder_dsl::created_datetime);
+                }
+                BackorderSortField::ItemId => {
+                    apply_sort_no_case!(query, sort, backorder_dsl::item_id);
+                }
+                BackorderSortField::NameId => {
+                    apply_sort_no_case!(query, sort, backorder_dsl::name_id);
+                }
+            }
+        } else {
+            // Oldest first, this is the order backorders are supplied in
+            query = query.order((
+                backorder_dsl::created_datetime.asc(),
+                backorder_dsl::id.asc(),
+            ))
+        }
+
+        let result = query
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load::<Backorder>(&self.connection.connection)?;
+
+        Ok(result)
+    }
+}
+
+type BoxedBackorderQuery = IntoBoxed<'static, backorder::table, DBType>;
+
+fn create_filtered_query(filter: Option<BackorderFilter>) -> BoxedBackorderQuery {
+    let mut query = backorder_dsl::backorder.into_boxed();
+
+    if let Some(f) = filter {
+        let BackorderFilter {
+            id,
+            store_id,
+            requisition_id,
+            name_id,
+            item_id,
+            is_open,
+        } = f;
+
+        apply_equal_filter!(query, id, backorder_dsl::id);
+        apply_equal_filter!(query, store_id, backorder_dsl::store_id);
+        apply_equal_filter!(query, requisition_id, backorder_dsl::requisition_id);
+        apply_equal_filter!(query, name_id, backorder_dsl::name_id);
+        apply_equal_filter!(query, item_id, backorder_dsl::item_id);
+        match is_open {
+            Some(true) => query = query.filter(backorder_dsl::remaining_quantity.gt(0)),
+            Some(false) => query = query.filter(backorder_dsl::remaining_quantity.le(0)),
+            None => {}
+        }
+    }
+
+    query
+}
@@ -0,0 +1,84 @@
+use super::{
+    backorder_row::backorder::dsl as backorder_dsl, item_row::item, name_row::name,
+    requisition_line_row::requisition_line, requisition_row::requisition, store_row::store,
+    StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    backorder (id) {
+        id -> Text,
+        store_id -> Text,
+        requisition_id -> Text,
+        requisition_line_id -> Text,
+        name_id -> Text,
+        item_id -> Text,
+        created_datetime -> Timestamp,
+        backordered_quantity -> Integer,
+        remaining_quantity -> Integer,
+    }
+}
+
+joinable!(backorder -> store (store_id));
+joinable!(backorder -> requisition (requisition_id));
+joinable!(backorder -> requisition_line (requisition_line_id));
+joinable!(backorder -> name (name_id));
+joinable!(backorder -> item (item_id));
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
+#[table_name = "backorder"]
+pub struct BackorderRow {
+    pub id: String,
+    pub store_id: String,
+    pub requisition_id: String,
+    pub requisition_line_id: String,
+    /// Customer the stock is owed to
+    pub name_id: String,
+    pub item_id: String,
+    pub created_datetime: NaiveDateTime,
+    /// Quantity outstanding when the requisition was finalised
+    pub backordered_quantity: i32,
+    /// Quantity not yet shipped on a follow-up shipment, backorder is open while above zero
+    pub remaining_quantity: i32,
+}
+
+pub struct BackorderRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> BackorderRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        BackorderRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &BackorderRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(backorder_dsl::backorder)
+            .values(row)
+            .on_conflict(backorder_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &BackorderRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(backorder_dsl::backorder)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<BackorderRow>, RepositoryError> {
+        let result = backorder_dsl::backorder
+            .filter(backorder_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,86 @@
+use super::{
+    backorder_row::backorder, backorder_shipment_row::backorder_shipment::dsl::*,
+    invoice_row::invoice, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+
+table! {
+    backorder_shipment (id) {
+        id -> Text,
+        backorder_id -> Text,
+        invoice_id -> Text,
+        quantity -> Integer,
+    }
+}
+
+joinable!(backorder_shipment -> backorder (backorder_id));
+joinable!(backorder_shipment -> invoice (invoice_id));
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
+#[table_name = "backorder_shipment"]
+pub struct BackorderShipmentRow {
+    pub id: String,
+    pub backorder_id: String,
+    /// Follow-up outbound shipment
+    pub invoice_id: String,
+    /// Quantity placed on the shipment, set to the shipped quantity once the shipment is shipped
+    pub quantity: i32,
+}
+
+pub struct BackorderShipmentRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> BackorderShipmentRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        BackorderShipmentRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &BackorderShipmentRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(backorder_shipment)
+            .values(row)
+            .on_conflict(id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &BackorderShipmentRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(backorder_shipment)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_many_by_backorder_ids(
+        &self,
+        backorder_ids: &[String],
+    ) -> Result<Vec<BackorderShipmentRow>, RepositoryError> {
+        let result = backorder_shipment
+            .filter(backorder_id.eq_any(backorder_ids))
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_invoice_id(
+        &self,
+        invoice_id_param: &str,
+    ) -> Result<Vec<BackorderShipmentRow>, RepositoryError> {
+        let result = backorder_shipment
+            .filter(invoice_id.eq(invoice_id_param))
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+
+    pub fn delete_by_invoice_id(&self, invoice_id_param: &str) -> Result<(), RepositoryError> {
+        diesel::delete(backorder_shipment.filter(invoice_id.eq(invoice_id_param)))
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+}
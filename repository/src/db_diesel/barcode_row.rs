@@ -0,0 +1,92 @@
+use super::{
+    barcode_row::barcode::dsl as barcode_dsl, item_row::item, name_row::name, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+
+table! {
+    barcode (id) {
+        id -> Text,
+        gtin -> Text,
+        item_id -> Text,
+        manufacturer_id -> Nullable<Text>,
+        pack_size -> Nullable<Integer>,
+    }
+}
+
+joinable!(barcode -> item (item_id));
+joinable!(barcode -> name (manufacturer_id));
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[changeset_options(treat_none_as_null = "true")]
+#[table_name = "barcode"]
+pub struct BarcodeRow {
+    pub id: String,
+    /// GTIN normalised to 14 digits
+    pub gtin: String,
+    pub item_id: String,
+    pub manufacturer_id: Option<String>,
+    /// Number of units in the scanned pack
+    pub pack_size: Option<i32>,
+}
+
+pub struct BarcodeRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> BarcodeRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        BarcodeRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &BarcodeRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(barcode_dsl::barcode)
+            .values(row)
+            .on_conflict(barcode_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &BarcodeRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(barcode_dsl::barcode)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<BarcodeRow>, RepositoryError> {
+        let result = barcode_dsl::barcode
+            .filter(barcode_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_one_by_gtin(&self, gtin: &str) -> Result<Option<BarcodeRow>, RepositoryError> {
+        let result = barcode_dsl::barcode
+            .filter(barcode_dsl::gtin.eq(gtin))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_item_id(&self, item_id: &str) -> Result<Vec<BarcodeRow>, RepositoryError> {
+        let result = barcode_dsl::barcode
+            .filter(barcode_dsl::item_id.eq(item_id))
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(barcode_dsl::barcode)
+            .filter(barcode_dsl::id.eq(id))
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,83 @@
+use super::{
+    customer_shelf_life_row::customer_shelf_life::dsl as customer_shelf_life_dsl, name_row::name,
+    store_row::store, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+
+table! {
+    customer_shelf_life (id) {
+        id -> Text,
+        store_id -> Text,
+        name_id -> Text,
+        minimum_shelf_life_days -> Integer,
+    }
+}
+
+joinable!(customer_shelf_life -> store (store_id));
+joinable!(customer_shelf_life -> name (name_id));
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[table_name = "customer_shelf_life"]
+pub struct CustomerShelfLifeRow {
+    pub id: String,
+    pub store_id: String,
+    /// Customer the stock is issued to
+    pub name_id: String,
+    /// Overrides the minimum shelf life of the store preferences
+    pub minimum_shelf_life_days: i32,
+}
+
+pub struct CustomerShelfLifeRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> CustomerShelfLifeRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        CustomerShelfLifeRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &CustomerShelfLifeRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(customer_shelf_life_dsl::customer_shelf_life)
+            .values(row)
+            .on_conflict(customer_shelf_life_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &CustomerShelfLifeRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(customer_shelf_life_dsl::customer_shelf_life)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_store_and_name(
+        &self,
+        store_id: &str,
+        name_id: &str,
+    ) -> Result<Option<CustomerShelfLifeRow>, RepositoryError> {
+        match customer_shelf_life_dsl::customer_shelf_life
+            .filter(customer_shelf_life_dsl::store_id.eq(store_id))
+            .filter(customer_shelf_life_dsl::name_id.eq(name_id))
+            .first(&self.connection.connection)
+        {
+            Ok(row) => Ok(Some(row)),
+            Err(diesel::result::Error::NotFound) => Ok(None),
+            Err(error) => Err(RepositoryError::from(error)),
+        }
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(customer_shelf_life_dsl::customer_shelf_life)
+            .filter(customer_shelf_life_dsl::id.eq(id))
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+}
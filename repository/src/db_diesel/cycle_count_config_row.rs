@@ -0,0 +1,86 @@
+use super::{
+    cycle_count_config_row::cycle_count_config::dsl as cycle_count_config_dsl, store_row::store,
+    StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+
+table! {
+    cycle_count_config (id) {
+        id -> Text,
+        is_enabled -> Bool,
+        a_class_percentage -> Double,
+        b_class_percentage -> Double,
+        a_class_count_frequency_days -> Integer,
+        b_class_count_frequency_days -> Integer,
+        c_class_count_frequency_days -> Integer,
+    }
+}
+
+joinable!(cycle_count_config -> store (id));
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[table_name = "cycle_count_config"]
+pub struct CycleCountConfigRow {
+    /// Id of the store
+    pub id: String,
+    /// Items are classified and cycle counts are generated periodically if enabled
+    pub is_enabled: bool,
+    /// Items making up this share of the yearly issue value (from the highest value down) are
+    /// class A
+    pub a_class_percentage: f64,
+    /// Items making up the next share of the yearly issue value are class B, the rest are class C
+    pub b_class_percentage: f64,
+    /// Number of days between counts of a class A item
+    pub a_class_count_frequency_days: i32,
+    /// Number of days between counts of a class B item
+    pub b_class_count_frequency_days: i32,
+    /// Number of days between counts of a class C item
+    pub c_class_count_frequency_days: i32,
+}
+
+pub struct CycleCountConfigRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> CycleCountConfigRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        CycleCountConfigRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &CycleCountConfigRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(cycle_count_config_dsl::cycle_count_config)
+            .values(row)
+            .on_conflict(cycle_count_config_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &CycleCountConfigRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(cycle_count_config_dsl::cycle_count_config)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<CycleCountConfigRow>, RepositoryError> {
+        let result = cycle_count_config_dsl::cycle_count_config
+            .filter(cycle_count_config_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_all_enabled(&self) -> Result<Vec<CycleCountConfigRow>, RepositoryError> {
+        let result = cycle_count_config_dsl::cycle_count_config
+            .filter(cycle_count_config_dsl::is_enabled.eq(true))
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,157 @@
+use super::{
+    inventory_adjustment_reason_row::{
+        inventory_adjustment_reason,
+        inventory_adjustment_reason::dsl as inventory_adjustment_reason_dsl,
+    },
+    InventoryAdjustmentReasonRow, InventoryAdjustmentType, StorageConnection,
+};
+
+use crate::diesel_macros::{apply_equal_filter, apply_sort_no_case};
+use crate::{DBType, EqualFilter, Pagination, RepositoryError, Sort};
+
+use diesel::{dsl::IntoBoxed, prelude::*};
+
+pub type InventoryAdjustmentReason = InventoryAdjustmentReasonRow;
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct InventoryAdjustmentReasonFilter {
+    pub id: Option<EqualFilter<String>>,
+    pub r#type: Option<EqualFilter<InventoryAdjustmentType>>,
+    pub is_active: Option<bool>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum InventoryAdjustmentReasonSortField {
+    Id,
+    Reason,
+}
+
+pub type InventoryAdjustmentReasonSort = Sort<InventoryAdjustmentReasonSortField>;
+
+impl InventoryAdjustmentReasonFilter {
+    pub fn new() -> InventoryAdjustmentReasonFilter {
+        InventoryAdjustmentReasonFilter::default()
+    }
+
+    pub fn id(mut self, filter: EqualFilter<String>) -> Self {
+        self.id = Some(filter);
+        self
+    }
+
+    pub fn r#type(mut self, filter: EqualFilter<InventoryAdjustmentType>) -> Self {
+        self.r#type = Some(filter);
+        self
+    }
+
+    pub fn is_active(mut self, value: bool) -> Self {
+        self.is_active = Some(value);
+        self
+    }
+}
+
+impl InventoryAdjustmentType {
+    pub fn equal_to(&self) -> EqualFilter<InventoryAdjustmentType> {
+        EqualFilter {
+            equal_to: Some(self.clone()),
+            not_equal_to: None,
+            equal_any: None,
+            not_equal_all: None,
+        }
+    }
+
+    pub fn not_equal_to(&self) -> EqualFilter<InventoryAdjustmentType> {
+        EqualFilter {
+            equal_to: None,
+            not_equal_to: Some(self.clone()),
+            equal_any: None,
+            not_equal_all: None,
+        }
+    }
+
+    pub fn equal_any(value: Vec<InventoryAdjustmentType>) -> EqualFilter<InventoryAdjustmentType> {
+        EqualFilter {
+            equal_to: None,
+            not_equal_to: None,
+            equal_any: Some(value),
+            not_equal_all: None,
+        }
+    }
+}
+
+pub struct InventoryAdjustmentReasonRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> InventoryAdjustmentReasonRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        InventoryAdjustmentReasonRepository { connection }
+    }
+
+    pub fn count(
+        &self,
+        filter: Option<InventoryAdjustmentReasonFilter>,
+    ) -> Result<i64, RepositoryError> {
+        let query = create_filtered_query(filter);
+        Ok(query.count().get_result(&self.connection.connection)?)
+    }
+
+    pub fn query_by_filter(
+        &self,
+        filter: InventoryAdjustmentReasonFilter,
+    ) -> Result<Vec<InventoryAdjustmentReason>, RepositoryError> {
+        self.query(Pagination::new(), Some(filter), None)
+    }
+
+    pub fn query(
+        &self,
+        pagination: Pagination,
+        filter: Option<InventoryAdjustmentReasonFilter>,
+        sort: Option<InventoryAdjustmentReasonSort>,
+    ) -> Result<Vec<InventoryAdjustmentReason>, RepositoryError> {
+        let mut query = create_filtered_query(filter);
+        if let Some(sort) = sort {
+            match sort.key {
+                InventoryAdjustmentReasonSortField::Id => {
+                    apply_sort_no_case!(query, sort, inventory_adjustment_reason_dsl::id);
+                }
+                InventoryAdjustmentReasonSortField::Reason => {
+                    apply_sort_no_case!(query, sort, inventory_adjustment_reason_dsl::reason);
+                }
+            }
+        } else {
+            query = query.order(inventory_adjustment_reason_dsl::reason.asc())
+        }
+
+        let result = query
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load::<InventoryAdjustmentReason>(&self.connection.connection)?;
+
+        Ok(result)
+    }
+}
+
+type BoxedInventoryAdjustmentReasonQuery =
+    IntoBoxed<'static, inventory_adjustment_reason::table, DBType>;
+
+fn create_filtered_query(
+    filter: Option<InventoryAdjustmentReasonFilter>,
+) -> BoxedInventoryAdjustmentReasonQuery {
+    let mut query = inventory_adjustment_reason_dsl::inventory_adjustment_reason.into_boxed();
+
+    if let Some(f) = filter {
+        let InventoryAdjustmentReasonFilter {
+            id,
+            r#type,
+            is_active,
+        } = f;
+
+        apply_equal_filter!(query, id, inventory_adjustment_reason_dsl::id);
+        apply_equal_filter!(query, r#type, inventory_adjustment_reason_dsl::type_);
+        if let Some(is_active) = is_active {
+            query = query.filter(inventory_adjustment_reason_dsl::is_active.eq(is_active));
+        }
+    }
+
+    query
+}
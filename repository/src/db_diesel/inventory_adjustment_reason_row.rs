@@ -0,0 +1,87 @@
+use super::{
+    inventory_adjustment_reason_row::inventory_adjustment_reason::dsl as inventory_adjustment_reason_dsl,
+    StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    inventory_adjustment_reason (id) {
+        id -> Text,
+        #[sql_name = "type"] type_ -> crate::db_diesel::inventory_adjustment_reason_row::InventoryAdjustmentTypeMapping,
+        is_active -> Bool,
+        reason -> Text,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum InventoryAdjustmentType {
+    /// Reason for adding stock
+    Positive,
+    /// Reason for removing stock
+    Negative,
+}
+
+impl Default for InventoryAdjustmentType {
+    fn default() -> Self {
+        Self::Positive
+    }
+}
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Eq, Default)]
+#[table_name = "inventory_adjustment_reason"]
+pub struct InventoryAdjustmentReasonRow {
+    pub id: String,
+    #[column_name = "type_"]
+    pub r#type: InventoryAdjustmentType,
+    /// Inactive reasons are kept for existing adjustments but can't be used for new ones
+    pub is_active: bool,
+    pub reason: String,
+}
+
+pub struct InventoryAdjustmentReasonRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> InventoryAdjustmentReasonRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        InventoryAdjustmentReasonRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &InventoryAdjustmentReasonRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(inventory_adjustment_reason_dsl::inventory_adjustment_reason)
+            .values(row)
+            .on_conflict(inventory_adjustment_reason_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &InventoryAdjustmentReasonRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(inventory_adjustment_reason_dsl::inventory_adjustment_reason)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        id: &str,
+    ) -> Result<Option<InventoryAdjustmentReasonRow>, RepositoryError> {
+        match inventory_adjustment_reason_dsl::inventory_adjustment_reason
+            .filter(inventory_adjustment_reason_dsl::id.eq(id))
+            .first(&self.connection.connection)
+        {
+            Ok(row) => Ok(Some(row)),
+            Err(diesel::result::Error::NotFound) => Ok(None),
+            Err(error) => Err(RepositoryError::from(error)),
+        }
+    }
+}
@@ -51,10 +51,12 @@ pub struct InvoiceLine {
 pub struct InvoiceLineFilter {
     pub id: Option<EqualFilter<String>>,
     pub invoice_id: Option<EqualFilter<String>>,
+    pub store_id: Option<EqualFilter<String>>,
     pub item_id: Option<EqualFilter<String>>,
     pub r#type: Option<EqualFilter<InvoiceLineRowType>>,
     pub location_id: Option<EqualFilter<String>>,
     pub requisition_id: Option<EqualFilter<String>>,
+    pub original_invoice_line_id: Option<EqualFilter<String>>,
 }
 
 impl InvoiceLineFilter {
@@ -62,10 +64,12 @@ impl InvoiceLineFilter {
         InvoiceLineFilter {
             id: None,
             invoice_id: None,
+            store_id: None,
             r#type: None,
             item_id: None,
             location_id: None,
             requisition_id: None,
+            original_invoice_line_id: None,
         }
     }
 
@@ -79,6 +83,11 @@ impl InvoiceLineFilter {
         self
     }
 
+    pub fn store_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.store_id = Some(filter);
+        self
+    }
+
     pub fn item_id(mut self, filter: EqualFilter<String>) -> Self {
         self.item_id = Some(filter);
         self
@@ -98,6 +107,11 @@ impl InvoiceLineFilter {
         self.requisition_id = Some(filter);
         self
     }
+
+    pub fn original_invoice_line_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.original_invoice_line_id = Some(filter);
+        self
+    }
 }
 
 type InvoiceLineJoin = (InvoiceLineRow, InvoiceRow, Option<LocationRow>);
@@ -173,9 +187,15 @@ fn create_filtered_query(filter: Option<InvoiceLineFilter>) -> BoxedInvoiceLineQ
         apply_equal_filter!(query, f.id, invoice_line_dsl::id);
         apply_equal_filter!(query, f.requisition_id, invoice_dsl::requisition_id);
         apply_equal_filter!(query, f.invoice_id, invoice_line_dsl::invoice_id);
+        apply_equal_filter!(query, f.store_id, invoice_dsl::store_id);
         apply_equal_filter!(query, f.location_id, invoice_line_dsl::location_id);
         apply_equal_filter!(query, f.item_id, invoice_line_dsl::item_id);
         apply_equal_filter!(query, f.r#type, invoice_line_dsl::type_);
+        apply_equal_filter!(
+            query,
+            f.original_invoice_line_id,
+            invoice_line_dsl::original_invoice_line_id
+        );
     }
 
     query
@@ -224,4 +244,13 @@ impl InvoiceLineRowType {
             not_equal_all: None,
         }
     }
+
+    pub fn equal_any(value: Vec<InvoiceLineRowType>) -> EqualFilter<InvoiceLineRowType> {
+        EqualFilter {
+            equal_to: None,
+            not_equal_to: None,
+            equal_any: Some(value),
+            not_equal_all: None,
+        }
+    }
 }
@@ -30,6 +30,9 @@ table! {
         #[sql_name = "type"] type_ -> crate::db_diesel::invoice_line_row::InvoiceLineRowTypeMapping,
         number_of_packs -> Integer,
         note -> Nullable<Text>,
+        original_invoice_line_id -> Nullable<Text>,
+        return_reason -> Nullable<Text>,
+        inventory_adjustment_reason_id -> Nullable<Text>,
     }
 }
 
@@ -77,6 +80,12 @@ pub struct InvoiceLineRow {
     pub r#type: InvoiceLineRowType,
     pub number_of_packs: i32,
     pub note: Option<String>,
+    /// Return lines only: the invoice line the stock is returned against
+    pub original_invoice_line_id: Option<String>,
+    /// Return lines only: reason code for the return
+    pub return_reason: Option<String>,
+    /// Inventory adjustment lines only: reason for the adjustment
+    pub inventory_adjustment_reason_id: Option<String>,
 }
 
 pub struct InvoiceLineRowRepository<'a> {
@@ -1,4 +1,7 @@
-use super::{invoice_row::invoice::dsl::*, StorageConnection, name_row::name, store_row::store, user_row::user_account};
+use super::{
+    invoice_row::invoice::dsl::*, name_row::name, store_row::store, user_row::user_account,
+    StorageConnection,
+};
 
 use crate::repository_error::RepositoryError;
 
@@ -32,6 +35,9 @@ table! {
         colour -> Nullable<Text>,
         requisition_id -> Nullable<Text>,
         linked_invoice_id -> Nullable<Text>,
+        prescriber -> Nullable<Text>,
+        diagnosis -> Nullable<Text>,
+        directions -> Nullable<Text>,
     }
 }
 
@@ -46,6 +52,9 @@ pub enum InvoiceRowType {
     OutboundShipment,
     InboundShipment,
     InventoryAdjustment,
+    Prescription,
+    CustomerReturn,
+    SupplierReturn,
 }
 
 #[derive(DbEnum, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -86,6 +95,12 @@ pub struct InvoiceRow {
     pub colour: Option<String>,
     pub requisition_id: Option<String>,
     pub linked_invoice_id: Option<String>,
+    /// Prescriptions only: name of the prescribing clinician
+    pub prescriber: Option<String>,
+    /// Prescriptions only
+    pub diagnosis: Option<String>,
+    /// Prescriptions only: dosage directions for the patient
+    pub directions: Option<String>,
 }
 
 impl Default for InvoiceRow {
@@ -113,6 +128,9 @@ impl Default for InvoiceRow {
             colour: Default::default(),
             requisition_id: Default::default(),
             linked_invoice_id: Default::default(),
+            prescriber: Default::default(),
+            diagnosis: Default::default(),
+            directions: Default::default(),
         }
     }
 }
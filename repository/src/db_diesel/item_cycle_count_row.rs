@@ -0,0 +1,103 @@
+use super::{
+    item_cycle_count_row::item_cycle_count::dsl as item_cycle_count_dsl, item_row::item,
+    store_row::store, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    item_cycle_count (id) {
+        id -> Text,
+        store_id -> Text,
+        item_id -> Text,
+        abc_class -> crate::db_diesel::item_cycle_count_row::AbcClassMapping,
+        issue_value -> Double,
+        classified_datetime -> Timestamp,
+        last_counted_datetime -> Nullable<Timestamp>,
+    }
+}
+
+joinable!(item_cycle_count -> store (store_id));
+joinable!(item_cycle_count -> item (item_id));
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum AbcClass {
+    /// Items making up most of the issue value of the store
+    A,
+    B,
+    /// Items making up the least of the issue value of the store, or not issued at all
+    C,
+}
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
+#[changeset_options(treat_none_as_null = "true")]
+#[table_name = "item_cycle_count"]
+pub struct ItemCycleCountRow {
+    pub id: String,
+    pub store_id: String,
+    pub item_id: String,
+    pub abc_class: AbcClass,
+    /// Value of the stock issued over the last year when the item was classified
+    pub issue_value: f64,
+    pub classified_datetime: NaiveDateTime,
+    /// Set when a stocktake counting the item is finalised
+    pub last_counted_datetime: Option<NaiveDateTime>,
+}
+
+pub struct ItemCycleCountRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ItemCycleCountRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ItemCycleCountRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &ItemCycleCountRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(item_cycle_count_dsl::item_cycle_count)
+            .values(row)
+            .on_conflict(item_cycle_count_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &ItemCycleCountRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(item_cycle_count_dsl::item_cycle_count)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_store_and_item(
+        &self,
+        store_id: &str,
+        item_id: &str,
+    ) -> Result<Option<ItemCycleCountRow>, RepositoryError> {
+        let result = item_cycle_count_dsl::item_cycle_count
+            .filter(item_cycle_count_dsl::store_id.eq(store_id))
+            .filter(item_cycle_count_dsl::item_id.eq(item_id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_many_by_store_id(
+        &self,
+        store_id: &str,
+    ) -> Result<Vec<ItemCycleCountRow>, RepositoryError> {
+        let result = item_cycle_count_dsl::item_cycle_count
+            .filter(item_cycle_count_dsl::store_id.eq(store_id))
+            .order(item_cycle_count_dsl::item_id.asc())
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+}
@@ -26,6 +26,8 @@ pub enum KeyValueType {
     /// Possible value: "true"
     RemoteSyncInitilisationFinished,
     RemoteSyncPushCursor,
+    /// Progress of the initial sync as JSON, used to report and resume the initial sync
+    InitialSyncProgress,
 
     SettingsSyncUrl,
     SettingsSyncUsername,
@@ -34,6 +36,12 @@ pub enum KeyValueType {
     SettingsSyncCentralServerSiteId,
     SettingsSyncSideId,
     SettingsSyncSideHardwareId,
+    SettingsSyncMaxBackoffSec,
+    SettingsSyncPartialFailureRetrySec,
+    SettingsSyncMaxJitterSec,
+
+    /// Secret to sign and verify auth tokens, persisted to keep users logged in across restarts
+    ServerAuthTokenSecret,
 }
 
 #[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
@@ -19,6 +19,7 @@ pub struct LocationFilter {
     pub name: Option<EqualFilter<String>>,
     pub code: Option<EqualFilter<String>>,
     pub store_id: Option<EqualFilter<String>>,
+    pub parent_location_id: Option<EqualFilter<String>>,
 }
 
 #[derive(PartialEq, Debug)]
@@ -93,6 +94,11 @@ fn create_filtered_query(filter: Option<LocationFilter>) -> BoxedLocationQuery {
         apply_equal_filter!(query, filter.name, location_dsl::name);
         apply_equal_filter!(query, filter.code, location_dsl::code);
         apply_equal_filter!(query, filter.store_id, location_dsl::store_id);
+        apply_equal_filter!(
+            query,
+            filter.parent_location_id,
+            location_dsl::parent_location_id
+        );
     }
 
     query
@@ -109,6 +115,7 @@ impl LocationFilter {
             name: None,
             code: None,
             store_id: None,
+            parent_location_id: None,
         }
     }
 
@@ -131,4 +138,9 @@ impl LocationFilter {
         self.store_id = Some(filter);
         self
     }
+
+    pub fn parent_location_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.parent_location_id = Some(filter);
+        self
+    }
 }
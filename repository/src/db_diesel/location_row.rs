@@ -1,4 +1,4 @@
-use super::{StorageConnection, location_row::location::dsl as location_dsl, store_row::store};
+use super::{location_row::location::dsl as location_dsl, store_row::store, StorageConnection};
 
 use crate::repository_error::RepositoryError;
 
@@ -11,6 +11,10 @@ table! {
         code -> Text,
         on_hold -> Bool,
         store_id -> Text,
+        parent_location_id -> Nullable<Text>,
+        capacity -> Nullable<Integer>,
+        min_temperature -> Nullable<Double>,
+        max_temperature -> Nullable<Double>,
     }
 }
 
@@ -18,12 +22,18 @@ joinable!(location -> store (store_id));
 
 #[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
 #[table_name = "location"]
+#[changeset_options(treat_none_as_null = "true")]
 pub struct LocationRow {
     pub id: String,
     pub name: String,
     pub code: String,
     pub on_hold: bool,
     pub store_id: String,
+    pub parent_location_id: Option<String>,
+    /// Maximum number of packs that can be stored in the location
+    pub capacity: Option<i32>,
+    pub min_temperature: Option<f64>,
+    pub max_temperature: Option<f64>,
 }
 
 pub struct LocationRowRepository<'a> {
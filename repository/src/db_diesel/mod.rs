@@ -1,15 +1,28 @@
 use crate::repository_error::RepositoryError;
 
+mod alert;
+mod alert_config_row;
+mod alert_row;
+mod auth_token_row;
+mod backorder;
+mod backorder_row;
+mod backorder_shipment_row;
+mod barcode_row;
 mod central_sync_buffer;
 mod changelog_row;
 mod consumption;
+mod customer_shelf_life_row;
+mod cycle_count_config_row;
 pub mod diesel_schema;
 mod filter_sort_pagination;
+mod inventory_adjustment_reason;
+mod inventory_adjustment_reason_row;
 mod invoice;
 mod invoice_line;
 mod invoice_line_row;
 mod invoice_row;
 mod item;
+mod item_cycle_count_row;
 mod item_row;
 mod key_value_store;
 mod location;
@@ -23,11 +36,17 @@ mod name;
 mod name_row;
 mod name_store_join;
 mod number_row;
+mod period_row;
+mod period_schedule_row;
+mod program_row;
 mod remote_sync_buffer;
+mod reorder_config_row;
+mod reorder_run_row;
 mod report;
 mod report_row;
 mod requisition;
 mod requisition_line;
+mod stock_ledger;
 mod stock_line;
 mod stock_line_row;
 mod stock_movement;
@@ -38,7 +57,10 @@ mod stocktake_line_row;
 mod stocktake_row;
 mod storage_connection;
 mod store;
+mod store_preference_row;
 mod store_row;
+mod sync_error_row;
+mod sync_log_row;
 mod unit_row;
 mod user;
 mod user_permission;
@@ -46,15 +68,28 @@ mod user_permission_row;
 mod user_row;
 mod user_store_join_row;
 
+pub use alert::*;
+pub use alert_config_row::*;
+pub use alert_row::*;
+pub use auth_token_row::*;
+pub use backorder::*;
+pub use backorder_row::*;
+pub use backorder_shipment_row::*;
+pub use barcode_row::*;
 pub use central_sync_buffer::*;
 pub use changelog_row::*;
 pub use consumption::*;
+pub use customer_shelf_life_row::*;
+pub use cycle_count_config_row::*;
 pub use filter_sort_pagination::*;
+pub use inventory_adjustment_reason::*;
+pub use inventory_adjustment_reason_row::*;
 pub use invoice::*;
 pub use invoice_line::*;
 pub use invoice_line_row::*;
 pub use invoice_row::*;
 pub use item::*;
+pub use item_cycle_count_row::*;
 pub use item_row::*;
 pub use key_value_store::*;
 pub use location::*;
@@ -68,11 +103,17 @@ pub use name::*;
 pub use name_row::*;
 pub use name_store_join::*;
 pub use number_row::*;
+pub use period_row::*;
+pub use period_schedule_row::*;
+pub use program_row::*;
 pub use remote_sync_buffer::*;
+pub use reorder_config_row::*;
+pub use reorder_run_row::*;
 pub use report::*;
 pub use report_row::*;
 pub use requisition::*;
 pub use requisition_line::*;
+pub use stock_ledger::*;
 pub use stock_line::*;
 pub use stock_line_row::*;
 pub use stock_movement::*;
@@ -83,7 +124,10 @@ pub use stocktake_line_row::*;
 pub use stocktake_row::*;
 pub use storage_connection::*;
 pub use store::*;
+pub use store_preference_row::*;
 pub use store_row::*;
+pub use sync_error_row::*;
+pub use sync_log_row::*;
 pub use unit_row::*;
 pub use user::*;
 pub use user_permission::*;
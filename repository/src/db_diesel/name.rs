@@ -2,13 +2,16 @@ use super::{
     name_row::{name, name::dsl as name_dsl},
     name_store_join::{name_store_join, name_store_join::dsl as name_store_join_dsl},
     store_row::{store, store::dsl as store_dsl},
-    DBType, NameRow, NameStoreJoinRow, StorageConnection, StoreRow,
+    DBType, NameRow, NameStoreJoinRow, NameType, StorageConnection, StoreRow,
 };
 
 use crate::{
-    diesel_macros::{apply_equal_filter, apply_simple_string_filter, apply_sort_no_case},
+    diesel_macros::{
+        apply_date_time_filter, apply_equal_filter, apply_simple_string_filter, apply_sort,
+        apply_sort_no_case,
+    },
     repository_error::RepositoryError,
-    EqualFilter, Pagination, SimpleStringFilter, Sort,
+    DateFilter, EqualFilter, Pagination, SimpleStringFilter, Sort,
 };
 
 use diesel::{
@@ -36,12 +39,21 @@ pub struct NameFilter {
     pub store_code: Option<SimpleStringFilter>,
     pub is_visible: Option<bool>,
     pub is_system_name: Option<bool>,
+    pub r#type: Option<EqualFilter<NameType>>,
+    /// Fuzzy name search, every whitespace separated term has to be part of the name, the first
+    /// name or the last name (case insensitive)
+    pub search: Option<String>,
+    pub date_of_birth: Option<DateFilter>,
+    pub national_health_number: Option<SimpleStringFilter>,
 }
 
 #[derive(PartialEq, Debug)]
 pub enum NameSortField {
     Name,
     Code,
+    FirstName,
+    LastName,
+    DateOfBirth,
 }
 
 pub type NameSort = Sort<NameSortField>;
@@ -102,6 +114,15 @@ impl<'a> NameRepository<'a> {
                 NameSortField::Code => {
                     apply_sort_no_case!(query, sort, name_dsl::code);
                 }
+                NameSortField::FirstName => {
+                    apply_sort_no_case!(query, sort, name_dsl::first_name);
+                }
+                NameSortField::LastName => {
+                    apply_sort_no_case!(query, sort, name_dsl::last_name);
+                }
+                NameSortField::DateOfBirth => {
+                    apply_sort!(query, sort, name_dsl::date_of_birth);
+                }
             }
         } else {
             query = query.order(name_dsl::id.asc())
@@ -166,12 +187,42 @@ fn create_filtered_query(store_id: String, filter: Option<NameFilter>) -> BoxedN
             store_code,
             is_visible,
             is_system_name,
+            r#type,
+            search,
+            date_of_birth,
+            national_health_number,
         } = f;
 
         apply_equal_filter!(query, id, name_dsl::id);
         apply_simple_string_filter!(query, code, name_dsl::code);
         apply_simple_string_filter!(query, name, name_dsl::name_);
         apply_simple_string_filter!(query, store_code, store_dsl::code);
+        apply_equal_filter!(query, r#type, name_dsl::type_);
+        apply_date_time_filter!(query, date_of_birth, name_dsl::date_of_birth);
+        apply_simple_string_filter!(
+            query,
+            national_health_number,
+            name_dsl::national_health_number
+        );
+
+        if let Some(search) = search {
+            for term in search.split_whitespace() {
+                let pattern = format!("%{}%", term);
+                // in sqlite like is case insensitive (but on only works with ASCII chars)
+                #[cfg(not(feature = "postgres"))]
+                let term_filter = name_dsl::name_
+                    .like(pattern.clone())
+                    .or(name_dsl::first_name.like(pattern.clone()))
+                    .or(name_dsl::last_name.like(pattern));
+                #[cfg(feature = "postgres")]
+                let term_filter = name_dsl::name_
+                    .ilike(pattern.clone())
+                    .or(name_dsl::first_name.ilike(pattern.clone()))
+                    .or(name_dsl::last_name.ilike(pattern));
+
+                query = query.filter(term_filter);
+            }
+        }
 
         if let Some(is_customer) = is_customer {
             query = query.filter(name_store_join_dsl::name_is_customer.eq(is_customer));
@@ -244,6 +295,41 @@ impl NameFilter {
         self.is_store = Some(value);
         self
     }
+
+    pub fn r#type(mut self, filter: EqualFilter<NameType>) -> Self {
+        self.r#type = Some(filter);
+        self
+    }
+
+    pub fn search(mut self, value: &str) -> Self {
+        self.search = Some(value.to_string());
+        self
+    }
+
+    pub fn date_of_birth(mut self, filter: DateFilter) -> Self {
+        self.date_of_birth = Some(filter);
+        self
+    }
+}
+
+impl NameType {
+    pub fn equal_to(&self) -> EqualFilter<NameType> {
+        EqualFilter {
+            equal_to: Some(self.clone()),
+            not_equal_to: None,
+            equal_any: None,
+            not_equal_all: None,
+        }
+    }
+
+    pub fn not_equal_to(&self) -> EqualFilter<NameType> {
+        EqualFilter {
+            equal_to: None,
+            not_equal_to: Some(self.clone()),
+            equal_any: None,
+            not_equal_all: None,
+        }
+    }
 }
 
 impl Name {
@@ -2,7 +2,10 @@ use super::{name_row::name::dsl::*, StorageConnection};
 
 use crate::repository_error::RepositoryError;
 
+use chrono::NaiveDate;
 use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+use serde::{Deserialize, Serialize};
 
 table! {
     #[sql_name = "name"]
@@ -10,22 +13,71 @@ table! {
         id -> Text,
         #[sql_name = "name"] name_  -> Text,
         code -> Text,
+        #[sql_name = "type"] type_ -> crate::db_diesel::name_row::NameTypeMapping,
         is_customer -> Bool,
         is_supplier -> Bool,
+        first_name -> Nullable<Text>,
+        last_name -> Nullable<Text>,
+        gender -> Nullable<crate::db_diesel::name_row::GenderMapping>,
+        date_of_birth -> Nullable<Date>,
+        national_health_number -> Nullable<Text>,
+        phone -> Nullable<Text>,
+        email -> Nullable<Text>,
+        address -> Nullable<Text>,
         // TODO, this is temporary, remove
         legacy_record -> Text,
     }
 }
 
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum NameType {
+    Facility,
+    Patient,
+    Build,
+    Invad,
+    Repack,
+    Store,
+    Others,
+}
+
+impl Default for NameType {
+    fn default() -> Self {
+        NameType::Facility
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum Gender {
+    Female,
+    Male,
+    Other,
+}
+
 #[derive(Clone, Queryable, Insertable, Debug, PartialEq, Eq, AsChangeset, Default)]
+#[changeset_options(treat_none_as_null = "true")]
 #[table_name = "name"]
 pub struct NameRow {
     pub id: String,
     #[column_name = "name_"]
     pub name: String,
     pub code: String,
+    #[column_name = "type_"]
+    pub r#type: NameType,
     pub is_customer: bool,
     pub is_supplier: bool,
+    /// Patient details, only set for names of type `Patient`
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub gender: Option<Gender>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub national_health_number: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
     // TODO, this is temporary, remove
     pub legacy_record: String,
 }
@@ -24,6 +24,9 @@ pub enum NumberRowType {
     RequestRequisition,
     ResponseRequisition,
     Stocktake,
+    Prescription,
+    CustomerReturn,
+    SupplierReturn,
 }
 
 #[derive(Clone, Insertable, Queryable, Debug, PartialEq, Eq, AsChangeset)]
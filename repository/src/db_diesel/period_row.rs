@@ -0,0 +1,94 @@
+use super::{
+    period_row::period::dsl as period_dsl, period_schedule_row::period_schedule, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDate;
+use diesel::prelude::*;
+use util::Defaults;
+
+table! {
+    period (id) {
+        id -> Text,
+        period_schedule_id -> Text,
+        name -> Text,
+        start_date -> Date,
+        end_date -> Date,
+    }
+}
+
+joinable!(period -> period_schedule (period_schedule_id));
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
+#[table_name = "period"]
+pub struct PeriodRow {
+    pub id: String,
+    pub period_schedule_id: String,
+    pub name: String,
+    pub start_date: NaiveDate,
+    /// Last day of the period (inclusive)
+    pub end_date: NaiveDate,
+}
+
+impl Default for PeriodRow {
+    fn default() -> Self {
+        Self {
+            start_date: Defaults::naive_date(),
+            end_date: Defaults::naive_date(),
+            // Default
+            id: Default::default(),
+            period_schedule_id: Default::default(),
+            name: Default::default(),
+        }
+    }
+}
+
+pub struct PeriodRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> PeriodRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        PeriodRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &PeriodRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(period_dsl::period)
+            .values(row)
+            .on_conflict(period_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &PeriodRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(period_dsl::period)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<PeriodRow>, RepositoryError> {
+        let result = period_dsl::period
+            .filter(period_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Periods of the schedule, ordered by start date
+    pub fn find_many_by_period_schedule_id(
+        &self,
+        period_schedule_id: &str,
+    ) -> Result<Vec<PeriodRow>, RepositoryError> {
+        let result = period_dsl::period
+            .filter(period_dsl::period_schedule_id.eq(period_schedule_id))
+            .order(period_dsl::start_date.asc())
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,83 @@
+use super::{period_schedule_row::period_schedule::dsl as period_schedule_dsl, StorageConnection};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    period_schedule (id) {
+        id -> Text,
+        name -> Text,
+        #[sql_name = "type"] type_ -> crate::db_diesel::period_schedule_row::PeriodScheduleTypeMapping,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum PeriodScheduleType {
+    Monthly,
+    Quarterly,
+}
+
+impl PeriodScheduleType {
+    /// Number of months in a period of the schedule
+    pub fn months(&self) -> u32 {
+        match self {
+            PeriodScheduleType::Monthly => 1,
+            PeriodScheduleType::Quarterly => 3,
+        }
+    }
+}
+
+impl Default for PeriodScheduleType {
+    fn default() -> Self {
+        Self::Monthly
+    }
+}
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[table_name = "period_schedule"]
+pub struct PeriodScheduleRow {
+    pub id: String,
+    pub name: String,
+    #[column_name = "type_"]
+    pub r#type: PeriodScheduleType,
+}
+
+pub struct PeriodScheduleRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> PeriodScheduleRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        PeriodScheduleRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &PeriodScheduleRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(period_schedule_dsl::period_schedule)
+            .values(row)
+            .on_conflict(period_schedule_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &PeriodScheduleRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(period_schedule_dsl::period_schedule)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<PeriodScheduleRow>, RepositoryError> {
+        let result = period_schedule_dsl::period_schedule
+            .filter(period_schedule_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+}
@@ -0,0 +1,74 @@
+use super::{
+    master_list_row::master_list, period_schedule_row::period_schedule,
+    program_row::program::dsl as program_dsl, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+
+table! {
+    program (id) {
+        id -> Text,
+        name -> Text,
+        master_list_id -> Text,
+        period_schedule_id -> Text,
+    }
+}
+
+joinable!(program -> master_list (master_list_id));
+joinable!(program -> period_schedule (period_schedule_id));
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[table_name = "program"]
+pub struct ProgramRow {
+    pub id: String,
+    pub name: String,
+    /// Items of the master list are requested in the programme requisitions
+    pub master_list_id: String,
+    pub period_schedule_id: String,
+}
+
+pub struct ProgramRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ProgramRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ProgramRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &ProgramRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(program_dsl::program)
+            .values(row)
+            .on_conflict(program_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &ProgramRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(program_dsl::program)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<ProgramRow>, RepositoryError> {
+        let result = program_dsl::program
+            .filter(program_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_all(&self) -> Result<Vec<ProgramRow>, RepositoryError> {
+        let result = program_dsl::program
+            .order(program_dsl::name.asc())
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+}
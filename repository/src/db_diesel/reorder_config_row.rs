@@ -0,0 +1,79 @@
+use super::{
+    name_row::name, reorder_config_row::reorder_config::dsl as reorder_config_dsl,
+    store_row::store, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+
+table! {
+    reorder_config (id) {
+        id -> Text,
+        is_enabled -> Bool,
+        supplier_id -> Nullable<Text>,
+        min_months_of_stock -> Double,
+        max_months_of_stock -> Double,
+    }
+}
+
+joinable!(reorder_config -> store (id));
+joinable!(reorder_config -> name (supplier_id));
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[changeset_options(treat_none_as_null = "true")]
+#[table_name = "reorder_config"]
+pub struct ReorderConfigRow {
+    /// Id of the store
+    pub id: String,
+    /// Reorder runs periodically if enabled, it can always be run on demand
+    pub is_enabled: bool,
+    /// Default supplier the generated request requisitions are sent to
+    pub supplier_id: Option<String>,
+    pub min_months_of_stock: f64,
+    pub max_months_of_stock: f64,
+}
+
+pub struct ReorderConfigRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ReorderConfigRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ReorderConfigRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &ReorderConfigRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(reorder_config_dsl::reorder_config)
+            .values(row)
+            .on_conflict(reorder_config_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &ReorderConfigRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(reorder_config_dsl::reorder_config)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<ReorderConfigRow>, RepositoryError> {
+        let result = reorder_config_dsl::reorder_config
+            .filter(reorder_config_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_all_enabled(&self) -> Result<Vec<ReorderConfigRow>, RepositoryError> {
+        let result = reorder_config_dsl::reorder_config
+            .filter(reorder_config_dsl::is_enabled.eq(true))
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+}
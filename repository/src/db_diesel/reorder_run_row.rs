@@ -0,0 +1,77 @@
+use super::{
+    reorder_run_row::reorder_run::dsl as reorder_run_dsl, requisition_row::requisition,
+    store_row::store, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+
+table! {
+    reorder_run (id) {
+        id -> Text,
+        store_id -> Text,
+        run_datetime -> Timestamp,
+        requisition_id -> Nullable<Text>,
+        number_of_items -> Integer,
+        error -> Nullable<Text>,
+    }
+}
+
+joinable!(reorder_run -> store (store_id));
+joinable!(reorder_run -> requisition (requisition_id));
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
+#[changeset_options(treat_none_as_null = "true")]
+#[table_name = "reorder_run"]
+pub struct ReorderRunRow {
+    pub id: String,
+    pub store_id: String,
+    pub run_datetime: NaiveDateTime,
+    /// Not set if no item was below the minimum months of stock or the run failed
+    pub requisition_id: Option<String>,
+    pub number_of_items: i32,
+    pub error: Option<String>,
+}
+
+pub struct ReorderRunRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> ReorderRunRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        ReorderRunRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &ReorderRunRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(reorder_run_dsl::reorder_run)
+            .values(row)
+            .on_conflict(reorder_run_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &ReorderRunRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(reorder_run_dsl::reorder_run)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_latest_by_store(
+        &self,
+        store_id: &str,
+    ) -> Result<Option<ReorderRunRow>, RepositoryError> {
+        let result = reorder_run_dsl::reorder_run
+            .filter(reorder_run_dsl::store_id.eq(store_id))
+            .order(reorder_run_dsl::run_datetime.desc())
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+}
@@ -25,6 +25,8 @@ pub struct RequisitionFilter {
     pub comment: Option<SimpleStringFilter>,
     pub store_id: Option<EqualFilter<String>>,
     pub linked_requisition_id: Option<EqualFilter<String>>,
+    pub program_id: Option<EqualFilter<String>>,
+    pub period_id: Option<EqualFilter<String>>,
 }
 
 #[derive(PartialEq, Debug)]
@@ -58,6 +60,8 @@ impl RequisitionFilter {
             comment: None,
             store_id: None,
             linked_requisition_id: None,
+            program_id: None,
+            period_id: None,
         }
     }
 
@@ -100,6 +104,16 @@ impl RequisitionFilter {
         self.linked_requisition_id = Some(filter);
         self
     }
+
+    pub fn program_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.program_id = Some(filter);
+        self
+    }
+
+    pub fn period_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.period_id = Some(filter);
+        self
+    }
 }
 
 impl RequisitionRowStatus {
@@ -127,6 +127,8 @@ fn create_filtered_query(
         comment,
         store_id,
         linked_requisition_id,
+        program_id,
+        period_id,
     }) = filter
     {
         apply_equal_filter!(query, id, requisition_dsl::id);
@@ -158,6 +160,8 @@ fn create_filtered_query(
         apply_simple_string_filter!(query, comment, requisition_dsl::comment);
 
         apply_equal_filter!(query, store_id, requisition_dsl::store_id);
+        apply_equal_filter!(query, program_id, requisition_dsl::program_id);
+        apply_equal_filter!(query, period_id, requisition_dsl::period_id);
     }
 
     Ok(query)
@@ -30,6 +30,9 @@ table! {
         max_months_of_stock -> Double,
         min_months_of_stock -> Double,
         linked_requisition_id -> Nullable<Text>,
+        program_id -> Nullable<Text>,
+        period_id -> Nullable<Text>,
+        approved_by_user_id -> Nullable<Text>,
     }
 }
 
@@ -49,11 +52,15 @@ pub enum RequisitionRowType {
 pub enum RequisitionRowStatus {
     Draft,
     New,
+    /// Response requisition approved by a supervisor, required before shipments can be created
+    /// if the store is configured to require authorisation
+    Authorised,
     Sent,
     Finalised,
 }
 
 #[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
+#[changeset_options(treat_none_as_null = "true")]
 #[table_name = "requisition"]
 pub struct RequisitionRow {
     pub id: String,
@@ -74,6 +81,12 @@ pub struct RequisitionRow {
     pub max_months_of_stock: f64,
     pub min_months_of_stock: f64,
     pub linked_requisition_id: Option<String>,
+    /// Programme the requisition was created for, the items are the programme's master list items
+    pub program_id: Option<String>,
+    /// Reporting period of a programme requisition
+    pub period_id: Option<String>,
+    /// User that authorised the response requisition
+    pub approved_by_user_id: Option<String>,
 }
 
 impl Default for RequisitionRow {
@@ -97,6 +110,9 @@ impl Default for RequisitionRow {
             max_months_of_stock: Default::default(),
             min_months_of_stock: Default::default(),
             linked_requisition_id: Default::default(),
+            program_id: Default::default(),
+            period_id: Default::default(),
+            approved_by_user_id: Default::default(),
         }
     }
 }
@@ -121,11 +137,19 @@ impl<'a> RequisitionRowRepository<'a> {
         Ok(())
     }
 
+    /// Not using replace_into, it would resolve a conflict on the programme period index by
+    /// deleting the other requisition instead of failing
     #[cfg(not(feature = "postgres"))]
     pub fn upsert_one(&self, row: &RequisitionRow) -> Result<(), RepositoryError> {
-        diesel::replace_into(requisition_dsl::requisition)
-            .values(row)
+        let updated = diesel::update(requisition_dsl::requisition)
+            .filter(requisition_dsl::id.eq(&row.id))
+            .set(row)
             .execute(&self.connection.connection)?;
+        if updated == 0 {
+            diesel::insert_into(requisition_dsl::requisition)
+                .values(row)
+                .execute(&self.connection.connection)?;
+        }
         Ok(())
     }
 
@@ -4,6 +4,7 @@ use crate::db_diesel::{item_row::item, requisition_row::requisition};
 use crate::repository_error::RepositoryError;
 use crate::StorageConnection;
 use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
 
 use chrono::NaiveDateTime;
 
@@ -19,12 +20,39 @@ table! {
         average_monthly_consumption -> Integer,
         snapshot_datetime -> Nullable<Timestamp>,
         comment -> Nullable<Text>,
+        approved_quantity -> Integer,
+        approval_comment -> Nullable<Text>,
+        opening_balance -> Integer,
+        received_quantity -> Integer,
+        issued_quantity -> Integer,
+        adjusted_quantity -> Integer,
+        closing_balance -> Integer,
+        consumption_calculation_method -> crate::db_diesel::requisition_line::requisition_line_row::ConsumptionCalculationMethodMapping,
     }
 }
 
 joinable!(requisition_line -> item (item_id));
 joinable!(requisition_line -> requisition (requisition_id));
 
+#[derive(DbEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum ConsumptionCalculationMethod {
+    /// Consumption over the lookback months divided by the number of months
+    SimpleAverage,
+    /// Days the item was out of stock are not counted in the lookback period
+    ExcludeStockOutDays,
+    /// Recent months have more weight than older months
+    WeightedMovingAverage,
+    /// Simple average, with lead time and safety stock added to the months of stock
+    LeadTimeSafetyStock,
+}
+
+impl Default for ConsumptionCalculationMethod {
+    fn default() -> Self {
+        Self::SimpleAverage
+    }
+}
+
 #[derive(Clone, Queryable, AsChangeset, Insertable, Debug, PartialEq, Default)]
 #[table_name = "requisition_line"]
 pub struct RequisitionLineRow {
@@ -38,6 +66,21 @@ pub struct RequisitionLineRow {
     pub average_monthly_consumption: i32,
     pub snapshot_datetime: Option<NaiveDateTime>,
     pub comment: Option<String>,
+    /// Quantity approved by the supervisor authorising the response requisition
+    pub approved_quantity: i32,
+    pub approval_comment: Option<String>,
+    /// Stock on hand at the start of the period, only set for programme requisitions
+    pub opening_balance: i32,
+    /// Stock received (inbound shipments and customer returns) during the period
+    pub received_quantity: i32,
+    /// Stock issued (outbound shipments, prescriptions and supplier returns) during the period
+    pub issued_quantity: i32,
+    /// Net inventory adjustments during the period, negative if stock was removed
+    pub adjusted_quantity: i32,
+    /// Stock on hand at the end of the period
+    pub closing_balance: i32,
+    /// Method that calculated the average monthly consumption and suggested quantity
+    pub consumption_calculation_method: ConsumptionCalculationMethod,
 }
 
 pub struct RequisitionLineRowRepository<'a> {
@@ -0,0 +1,428 @@
+use super::{
+    stock_ledger::stock_ledger::dsl as stock_ledger_dsl, InvoiceRowType, StorageConnection,
+};
+
+use crate::diesel_macros::{apply_date_time_filter, apply_equal_filter, apply_sort};
+use crate::{DBType, DatetimeFilter, EqualFilter, Pagination, RepositoryError, Sort};
+
+use chrono::NaiveDateTime;
+use diesel::{dsl::IntoBoxed, prelude::*};
+use util::Defaults;
+
+table! {
+    stock_ledger (id) {
+        id -> Text,
+        item_id -> Text,
+        store_id -> Text,
+        stock_line_id -> Nullable<Text>,
+        invoice_id -> Text,
+        invoice_number -> BigInt,
+        invoice_type -> crate::db_diesel::invoice_row::InvoiceRowTypeMapping,
+        name -> Text,
+        batch -> Nullable<Text>,
+        reason -> Nullable<Text>,
+        quantity -> Integer,
+        datetime -> Timestamp,
+        running_balance -> BigInt,
+        stock_line_running_balance -> Nullable<BigInt>,
+    }
+}
+
+/// A single stock movement (one invoice line) of an item in a store
+#[derive(Clone, Queryable, Debug, PartialEq)]
+pub struct StockLedgerRow {
+    /// Id of the invoice line that moved the stock
+    pub id: String,
+    pub item_id: String,
+    pub store_id: String,
+    pub stock_line_id: Option<String>,
+    pub invoice_id: String,
+    pub invoice_number: i64,
+    pub invoice_type: InvoiceRowType,
+    /// Name of the other party of the invoice
+    pub name: String,
+    pub batch: Option<String>,
+    /// Inventory adjustment reason or return reason
+    pub reason: Option<String>,
+    /// Number of units (packs * pack size), negative if stock was removed
+    pub quantity: i32,
+    pub datetime: NaiveDateTime,
+    /// Balance of the item in the store after this movement
+    pub running_balance: i64,
+    /// Balance of the stock line after this movement
+    pub stock_line_running_balance: Option<i64>,
+}
+
+impl Default for StockLedgerRow {
+    fn default() -> Self {
+        Self {
+            invoice_type: InvoiceRowType::InboundShipment,
+            datetime: Defaults::naive_date_time(),
+            // Default
+            id: Default::default(),
+            item_id: Default::default(),
+            store_id: Default::default(),
+            stock_line_id: Default::default(),
+            invoice_id: Default::default(),
+            invoice_number: Default::default(),
+            name: Default::default(),
+            batch: Default::default(),
+            reason: Default::default(),
+            quantity: Default::default(),
+            running_balance: Default::default(),
+            stock_line_running_balance: Default::default(),
+        }
+    }
+}
+
+pub type StockLedger = StockLedgerRow;
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct StockLedgerFilter {
+    pub item_id: Option<EqualFilter<String>>,
+    pub store_id: Option<EqualFilter<String>>,
+    pub stock_line_id: Option<EqualFilter<String>>,
+    pub invoice_type: Option<EqualFilter<InvoiceRowType>>,
+    pub datetime: Option<DatetimeFilter>,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum StockLedgerSortField {
+    Datetime,
+    InvoiceNumber,
+    Quantity,
+}
+
+pub type StockLedgerSort = Sort<StockLedgerSortField>;
+
+impl StockLedgerFilter {
+    pub fn new() -> StockLedgerFilter {
+        StockLedgerFilter::default()
+    }
+
+    pub fn item_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.item_id = Some(filter);
+        self
+    }
+
+    pub fn store_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.store_id = Some(filter);
+        self
+    }
+
+    pub fn stock_line_id(mut self, filter: EqualFilter<String>) -> Self {
+        self.stock_line_id = Some(filter);
+        self
+    }
+
+    pub fn invoice_type(mut self, filter: EqualFilter<InvoiceRowType>) -> Self {
+        self.invoice_type = Some(filter);
+        self
+    }
+
+    pub fn datetime(mut self, filter: DatetimeFilter) -> Self {
+        self.datetime = Some(filter);
+        self
+    }
+}
+
+pub struct StockLedgerRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> StockLedgerRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        StockLedgerRepository { connection }
+    }
+
+    pub fn count(&self, filter: Option<StockLedgerFilter>) -> Result<i64, RepositoryError> {
+        let query = create_filtered_query(filter);
+        Ok(query.count().get_result(&self.connection.connection)?)
+    }
+
+    pub fn query_by_filter(
+        &self,
+        filter: StockLedgerFilter,
+    ) -> Result<Vec<StockLedger>, RepositoryError> {
+        self.query(Pagination::all(), Some(filter), None)
+    }
+
+    /// Running balances are calculated over the full history of the item (or stock line), filters
+    /// only limit the movements that are returned
+    pub fn query(
+        &self,
+        pagination: Pagination,
+        filter: Option<StockLedgerFilter>,
+        sort: Option<StockLedgerSort>,
+    ) -> Result<Vec<StockLedger>, RepositoryError> {
+        let mut query = create_filtered_query(filter);
+
+        if let Some(sort) = sort {
+            match sort.key {
+                StockLedgerSortField::Datetime => {
+                    apply_sort!(query, sort, stock_ledger_dsl::datetime);
+                }
+                StockLedgerSortField::InvoiceNumber => {
+                    apply_sort!(query, sort, stock_ledger_dsl::invoice_number);
+                }
+                StockLedgerSortField::Quantity => {
+                    apply_sort!(query, sort, stock_ledger_dsl::quantity);
+                }
+            }
+        } else {
+            query = query.order(stock_ledger_dsl::datetime.asc())
+        }
+        // Same order as used for the running balance, to keep results stable
+        query = query.then_order_by(stock_ledger_dsl::id.asc());
+
+        let result = query
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load::<StockLedger>(&self.connection.connection)?;
+
+        Ok(result)
+    }
+}
+
+type BoxedStockLedgerQuery = IntoBoxed<'static, stock_ledger::table, DBType>;
+
+fn create_filtered_query(filter: Option<StockLedgerFilter>) -> BoxedStockLedgerQuery {
+    let mut query = stock_ledger_dsl::stock_ledger.into_boxed();
+
+    if let Some(f) = filter {
+        let StockLedgerFilter {
+            item_id,
+            store_id,
+            stock_line_id,
+            invoice_type,
+            datetime,
+        } = f;
+
+        apply_equal_filter!(query, item_id, stock_ledger_dsl::item_id);
+        apply_equal_filter!(query, store_id, stock_ledger_dsl::store_id);
+        apply_equal_filter!(query, stock_line_id, stock_ledger_dsl::stock_line_id);
+        apply_equal_filter!(query, invoice_type, stock_ledger_dsl::invoice_type);
+        apply_date_time_filter!(query, datetime, stock_ledger_dsl::datetime);
+    }
+
+    query
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use util::{inline_edit, inline_init};
+
+    use crate::{
+        mock::{
+            mock_item_a, mock_item_b, mock_name_a, mock_negative_inventory_adjustment_reason,
+            mock_stock_line_a, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineRowType, InvoiceRow, NameRow, StoreRow,
+    };
+
+    use super::*;
+
+    #[actix_rt::test]
+    async fn stock_ledger_repository() {
+        fn name() -> NameRow {
+            inline_init(|r: &mut NameRow| {
+                r.id = "stock_ledger_name".to_string();
+            })
+        }
+
+        fn store() -> StoreRow {
+            inline_init(|s: &mut StoreRow| {
+                s.id = "stock_ledger_store".to_string();
+                s.name_id = name().id;
+                s.code = "n/a".to_string();
+            })
+        }
+
+        fn ledger_point(id: &str) -> MockData {
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![inline_init(|r: &mut InvoiceRow| {
+                    r.id = id.to_string();
+                    r.store_id = store().id;
+                    r.name_id = mock_name_a().id;
+                    r.r#type = InvoiceRowType::OutboundShipment;
+                })];
+                r.invoice_lines = vec![inline_init(|r: &mut InvoiceLineRow| {
+                    r.id = format!("{}_line", id);
+                    r.invoice_id = id.to_string();
+                    r.item_id = mock_item_a().id;
+                    r.stock_line_id = Some(mock_stock_line_a().id);
+                    r.r#type = InvoiceLineRowType::StockOut;
+                    r.pack_size = 1;
+                })];
+            })
+        }
+
+        fn datetime(day: u32) -> NaiveDateTime {
+            NaiveDate::from_ymd(2020, 11, day).and_hms(0, 0, 0)
+        }
+
+        let (_, connection, _, _) = setup_all_with_data(
+            "stock_ledger_repository",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.names = vec![name()];
+                r.stores = vec![store()];
+                r.inventory_adjustment_reasons = vec![mock_negative_inventory_adjustment_reason()];
+            })
+            .join(inline_edit(&ledger_point("inbound"), |mut u| {
+                u.invoices[0].r#type = InvoiceRowType::InboundShipment;
+                u.invoices[0].delivered_datetime = Some(datetime(1));
+                u.invoice_lines[0].r#type = InvoiceLineRowType::StockIn;
+                u.invoice_lines[0].batch = Some("batch".to_string());
+                u.invoice_lines[0].pack_size = 10;
+                u.invoice_lines[0].number_of_packs = 10;
+                u
+            }))
+            .join(inline_edit(&ledger_point("outbound"), |mut u| {
+                u.invoices[0].picked_datetime = Some(datetime(2));
+                u.invoice_lines[0].stock_line_id = None;
+                u.invoice_lines[0].number_of_packs = 20;
+                u
+            }))
+            .join(inline_edit(
+                &ledger_point("outbound_not_picked"),
+                |mut u| {
+                    // Should not be listed
+                    u.invoices[0].picked_datetime = None;
+                    u.invoice_lines[0].number_of_packs = 20;
+                    u
+                },
+            ))
+            .join(inline_edit(&ledger_point("adjustment"), |mut u| {
+                u.invoices[0].r#type = InvoiceRowType::InventoryAdjustment;
+                u.invoices[0].verified_datetime = Some(datetime(3));
+                u.invoice_lines[0].number_of_packs = 5;
+                u.invoice_lines[0].inventory_adjustment_reason_id =
+                    Some(mock_negative_inventory_adjustment_reason().id);
+                u
+            }))
+            .join(inline_edit(&ledger_point("supplier_return"), |mut u| {
+                u.invoices[0].r#type = InvoiceRowType::SupplierReturn;
+                u.invoices[0].picked_datetime = Some(datetime(4));
+                u.invoice_lines[0].number_of_packs = 3;
+                u.invoice_lines[0].return_reason = Some("Faulty".to_string());
+                u
+            }))
+            .join(inline_edit(&ledger_point("other_item"), |mut u| {
+                // Should not be listed for item a
+                u.invoices[0].picked_datetime = Some(datetime(3));
+                u.invoice_lines[0].item_id = mock_item_b().id;
+                u.invoice_lines[0].stock_line_id = None;
+                u.invoice_lines[0].number_of_packs = 1;
+                u
+            })),
+        )
+        .await;
+
+        let repo = StockLedgerRepository::new(&connection);
+        let filter = StockLedgerFilter::new()
+            .store_id(EqualFilter::equal_to(&store().id))
+            .item_id(EqualFilter::equal_to(&mock_item_a().id));
+
+        let rows = repo.query_by_filter(filter.clone()).unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                StockLedgerRow {
+                    id: "inbound_line".to_string(),
+                    item_id: mock_item_a().id,
+                    store_id: store().id,
+                    stock_line_id: Some(mock_stock_line_a().id),
+                    invoice_id: "inbound".to_string(),
+                    invoice_number: 0,
+                    invoice_type: InvoiceRowType::InboundShipment,
+                    name: mock_name_a().name,
+                    batch: Some("batch".to_string()),
+                    reason: None,
+                    quantity: 100,
+                    datetime: datetime(1),
+                    running_balance: 100,
+                    stock_line_running_balance: Some(100),
+                },
+                inline_init(|r: &mut StockLedgerRow| {
+                    r.id = "outbound_line".to_string();
+                    r.item_id = mock_item_a().id;
+                    r.store_id = store().id;
+                    r.invoice_id = "outbound".to_string();
+                    r.invoice_type = InvoiceRowType::OutboundShipment;
+                    r.name = mock_name_a().name;
+                    r.quantity = -20;
+                    r.datetime = datetime(2);
+                    r.running_balance = 80;
+                }),
+                inline_init(|r: &mut StockLedgerRow| {
+                    r.id = "adjustment_line".to_string();
+                    r.item_id = mock_item_a().id;
+                    r.store_id = store().id;
+                    r.stock_line_id = Some(mock_stock_line_a().id);
+                    r.invoice_id = "adjustment".to_string();
+                    r.invoice_type = InvoiceRowType::InventoryAdjustment;
+                    r.name = mock_name_a().name;
+                    r.reason = Some(mock_negative_inventory_adjustment_reason().reason);
+                    r.quantity = -5;
+                    r.datetime = datetime(3);
+                    r.running_balance = 75;
+                    r.stock_line_running_balance = Some(95);
+                }),
+                inline_init(|r: &mut StockLedgerRow| {
+                    r.id = "supplier_return_line".to_string();
+                    r.item_id = mock_item_a().id;
+                    r.store_id = store().id;
+                    r.stock_line_id = Some(mock_stock_line_a().id);
+                    r.invoice_id = "supplier_return".to_string();
+                    r.invoice_type = InvoiceRowType::SupplierReturn;
+                    r.name = mock_name_a().name;
+                    r.reason = Some("Faulty".to_string());
+                    r.quantity = -3;
+                    r.datetime = datetime(4);
+                    r.running_balance = 72;
+                    r.stock_line_running_balance = Some(92);
+                }),
+            ]
+        );
+
+        // Date range only limits the returned rows, balance includes earlier movements
+        let rows = repo
+            .query_by_filter(
+                filter
+                    .clone()
+                    .datetime(DatetimeFilter::after_or_equal_to(datetime(3))),
+            )
+            .unwrap();
+        assert_eq!(
+            rows.iter()
+                .map(|r| (r.id.as_str(), r.running_balance))
+                .collect::<Vec<_>>(),
+            vec![("adjustment_line", 75), ("supplier_return_line", 72)]
+        );
+
+        // Per stock line
+        let stock_line_filter = filter
+            .clone()
+            .stock_line_id(EqualFilter::equal_to(&mock_stock_line_a().id));
+        assert_eq!(repo.count(Some(stock_line_filter.clone())).unwrap(), 3);
+
+        // Pagination and sort
+        let rows = repo
+            .query(
+                Pagination {
+                    offset: 1,
+                    limit: 1,
+                },
+                Some(filter),
+                Some(StockLedgerSort {
+                    key: StockLedgerSortField::Quantity,
+                    desc: Some(false),
+                }),
+            )
+            .unwrap();
+        assert_eq!(rows[0].id, "adjustment_line");
+    }
+}
@@ -228,6 +228,35 @@ mod test {
                 u.invoice_lines[0].r#type = InvoiceLineRowType::StockOut;
                 u.invoice_lines[0].number_of_packs = 50;
                 u
+            }))
+            .join(inline_edit(&stock_movement_point(), |mut u| {
+                u.invoices[0].r#type = InvoiceRowType::Prescription;
+                u.invoices[0].picked_datetime =
+                    Some(NaiveDate::from_ymd(2021, 02, 10).and_hms(0, 0, 0));
+                u.invoice_lines[0].number_of_packs = 5;
+                u
+            }))
+            .join(inline_edit(&stock_movement_point(), |mut u| {
+                u.invoices[0].r#type = InvoiceRowType::Prescription;
+                // Should not be counted
+                u.invoices[0].picked_datetime = None;
+                u.invoice_lines[0].number_of_packs = 5;
+                u
+            }))
+            .join(inline_edit(&stock_movement_point(), |mut u| {
+                u.invoices[0].r#type = InvoiceRowType::CustomerReturn;
+                u.invoices[0].delivered_datetime =
+                    Some(NaiveDate::from_ymd(2021, 02, 15).and_hms(0, 0, 0));
+                u.invoice_lines[0].r#type = InvoiceLineRowType::StockIn;
+                u.invoice_lines[0].number_of_packs = 3;
+                u
+            }))
+            .join(inline_edit(&stock_movement_point(), |mut u| {
+                u.invoices[0].r#type = InvoiceRowType::SupplierReturn;
+                u.invoices[0].picked_datetime =
+                    Some(NaiveDate::from_ymd(2021, 02, 20).and_hms(0, 0, 0));
+                u.invoice_lines[0].number_of_packs = 7;
+                u
             })),
         )
         .await;
@@ -281,6 +310,27 @@ mod test {
                     quantity: -50,
                     datetime: NaiveDate::from_ymd(2021, 02, 01).and_hms(0, 0, 0)
                 },
+                StockMovementRow {
+                    id: "n/a".to_string(),
+                    item_id: mock_item_a().id,
+                    store_id: store().id,
+                    quantity: -5,
+                    datetime: NaiveDate::from_ymd(2021, 02, 10).and_hms(0, 0, 0)
+                },
+                StockMovementRow {
+                    id: "n/a".to_string(),
+                    item_id: mock_item_a().id,
+                    store_id: store().id,
+                    quantity: 3,
+                    datetime: NaiveDate::from_ymd(2021, 02, 15).and_hms(0, 0, 0)
+                },
+                StockMovementRow {
+                    id: "n/a".to_string(),
+                    item_id: mock_item_a().id,
+                    store_id: store().id,
+                    quantity: -7,
+                    datetime: NaiveDate::from_ymd(2021, 02, 20).and_hms(0, 0, 0)
+                },
             ]
         )
     }
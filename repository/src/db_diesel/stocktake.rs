@@ -155,3 +155,14 @@ impl<'a> StocktakeRepository<'a> {
             .optional()?)
     }
 }
+
+impl StocktakeStatus {
+    pub fn equal_to(&self) -> EqualFilter<StocktakeStatus> {
+        EqualFilter {
+            equal_to: Some(self.clone()),
+            not_equal_to: None,
+            equal_any: None,
+            not_equal_all: None,
+        }
+    }
+}
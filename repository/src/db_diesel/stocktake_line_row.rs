@@ -19,6 +19,7 @@ table! {
         comment	-> Nullable<Text>,
         snapshot_number_of_packs -> Integer,
         counted_number_of_packs -> Nullable<Integer>,
+        second_counted_number_of_packs -> Nullable<Integer>,
 
         // stock line related fields:
         item_id -> Text,
@@ -28,6 +29,7 @@ table! {
         cost_price_per_pack -> Nullable<Double>,
         sell_price_per_pack -> Nullable<Double>,
         note -> Nullable<Text>,
+        inventory_adjustment_reason_id -> Nullable<Text>,
     }
 }
 
@@ -47,6 +49,8 @@ pub struct StocktakeLineRow {
     pub comment: Option<String>,
     pub snapshot_number_of_packs: i32,
     pub counted_number_of_packs: Option<i32>,
+    /// Recount of a line with a discrepancy, takes precedence over the first count
+    pub second_counted_number_of_packs: Option<i32>,
 
     // stock line related fields:
     /// When a creating a new stock line this field holds the required item id
@@ -57,6 +61,8 @@ pub struct StocktakeLineRow {
     pub cost_price_per_pack: Option<f64>,
     pub sell_price_per_pack: Option<f64>,
     pub note: Option<String>,
+    /// Reason for a difference between the counted and the snapshot number of packs
+    pub inventory_adjustment_reason_id: Option<String>,
 }
 
 pub struct StocktakeLineRowRepository<'a> {
@@ -24,6 +24,11 @@ table! {
         finalised_datetime -> Nullable<Timestamp>,
         inventory_adjustment_id -> Nullable<Text>,
         is_locked -> Bool,
+        is_blind_count -> Bool,
+        recount_threshold -> Nullable<Integer>,
+        counted_by -> Nullable<Text>,
+        count_submitted_datetime -> Nullable<Timestamp>,
+        verified_by -> Nullable<Text>,
     }
 }
 
@@ -52,6 +57,16 @@ pub struct StocktakeRow {
     /// reference to the inventory adjustment shipment
     pub inventory_adjustment_id: Option<String>,
     pub is_locked: bool,
+    /// Snapshot quantities are hidden from the counter until the count is submitted
+    pub is_blind_count: bool,
+    /// Lines whose count differs from the snapshot by more than this number of packs need to be
+    /// recounted before a blind count can be finalised
+    pub recount_threshold: Option<i32>,
+    /// User that submitted the count of a blind count
+    pub counted_by: Option<String>,
+    pub count_submitted_datetime: Option<NaiveDateTime>,
+    /// User that finalised a blind count, must be different to the counter
+    pub verified_by: Option<String>,
 }
 
 impl Default for StocktakeStatus {
@@ -76,6 +91,11 @@ impl Default for StocktakeRow {
             finalised_datetime: Default::default(),
             inventory_adjustment_id: Default::default(),
             is_locked: Default::default(),
+            is_blind_count: Default::default(),
+            recount_threshold: Default::default(),
+            counted_by: Default::default(),
+            count_submitted_datetime: Default::default(),
+            verified_by: Default::default(),
         }
     }
 }
@@ -0,0 +1,103 @@
+use super::{
+    location_row::location, store_preference_row::store_preference::dsl as store_preference_dsl,
+    store_row::store, ConsumptionCalculationMethod, StorageConnection,
+};
+
+use crate::repository_error::RepositoryError;
+
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    store_preference (id) {
+        id -> Text,
+        allocation_strategy -> crate::db_diesel::store_preference_row::AllocationStrategyMapping,
+        preferred_location_id -> Nullable<Text>,
+        minimum_shelf_life_days -> Nullable<Integer>,
+        requisitions_require_authorisation -> Bool,
+        consumption_calculation_method -> crate::db_diesel::requisition_line::requisition_line_row::ConsumptionCalculationMethodMapping,
+        lead_time_months -> Double,
+        safety_stock_months -> Double,
+    }
+}
+
+joinable!(store_preference -> store (id));
+joinable!(store_preference -> location (preferred_location_id));
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum AllocationStrategy {
+    /// First expired, first out
+    Fefo,
+    /// First in (received), first out
+    Fifo,
+}
+
+impl Default for AllocationStrategy {
+    fn default() -> Self {
+        Self::Fefo
+    }
+}
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Default)]
+#[changeset_options(treat_none_as_null = "true")]
+#[table_name = "store_preference"]
+pub struct StorePreferenceRow {
+    /// Id of the store
+    pub id: String,
+    pub allocation_strategy: AllocationStrategy,
+    /// Stock in this location is allocated before stock in other locations
+    pub preferred_location_id: Option<String>,
+    /// Stock expiring within this number of days is not allocated
+    pub minimum_shelf_life_days: Option<i32>,
+    /// Response requisitions must be authorised before shipments can be created
+    pub requisitions_require_authorisation: bool,
+    /// Method used to calculate average monthly consumption and suggested quantity of requisitions
+    pub consumption_calculation_method: ConsumptionCalculationMethod,
+    /// Added to the min and max months of stock by the lead time and safety stock method
+    pub lead_time_months: f64,
+    pub safety_stock_months: f64,
+}
+
+pub struct StorePreferenceRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> StorePreferenceRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        StorePreferenceRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &StorePreferenceRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(store_preference_dsl::store_preference)
+            .values(row)
+            .on_conflict(store_preference_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &StorePreferenceRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(store_preference_dsl::store_preference)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(
+        &self,
+        store_id: &str,
+    ) -> Result<Option<StorePreferenceRow>, RepositoryError> {
+        match store_preference_dsl::store_preference
+            .filter(store_preference_dsl::id.eq(store_id))
+            .first(&self.connection.connection)
+        {
+            Ok(row) => Ok(Some(row)),
+            Err(diesel::result::Error::NotFound) => Ok(None),
+            Err(error) => Err(RepositoryError::from(error)),
+        }
+    }
+}
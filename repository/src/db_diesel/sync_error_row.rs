@@ -0,0 +1,139 @@
+use super::{sync_error_row::sync_error::dsl as sync_error_dsl, StorageConnection};
+
+use crate::{repository_error::RepositoryError, Pagination, RemoteSyncBufferAction};
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    sync_error (id) {
+        id -> Text,
+        source -> crate::db_diesel::sync_error_row::SyncErrorSourceMapping,
+        table_name -> Text,
+        record_id -> Text,
+        remote_action -> Nullable<crate::db_diesel::remote_sync_buffer::RemoteSyncBufferActionMapping>,
+        data -> Text,
+        error_message -> Text,
+        attempt_count -> Integer,
+        created_datetime -> Timestamp,
+        last_attempt_datetime -> Timestamp,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum SyncErrorSource {
+    /// Record from the central sync buffer
+    Central,
+    /// Record from the remote sync buffer
+    Remote,
+}
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
+#[changeset_options(treat_none_as_null = "true")]
+#[table_name = "sync_error"]
+pub struct SyncErrorRow {
+    pub id: String,
+    pub source: SyncErrorSource,
+    pub table_name: String,
+    pub record_id: String,
+    /// Only set for remote records
+    pub remote_action: Option<RemoteSyncBufferAction>,
+    /// Raw JSON of the sync record
+    pub data: String,
+    /// Error of the latest attempt
+    pub error_message: String,
+    pub attempt_count: i32,
+    pub created_datetime: NaiveDateTime,
+    pub last_attempt_datetime: NaiveDateTime,
+}
+
+pub struct SyncErrorRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> SyncErrorRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        SyncErrorRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &SyncErrorRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(sync_error_dsl::sync_error)
+            .values(row)
+            .on_conflict(sync_error_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &SyncErrorRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(sync_error_dsl::sync_error)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<SyncErrorRow>, RepositoryError> {
+        let result = sync_error_dsl::sync_error
+            .filter(sync_error_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    pub fn find_one_by_record(
+        &self,
+        source: SyncErrorSource,
+        table_name: &str,
+        record_id: &str,
+    ) -> Result<Option<SyncErrorRow>, RepositoryError> {
+        let result = sync_error_dsl::sync_error
+            .filter(sync_error_dsl::source.eq(source))
+            .filter(sync_error_dsl::table_name.eq(table_name))
+            .filter(sync_error_dsl::record_id.eq(record_id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Records of the source that have been attempted less than `max_attempt_count` times
+    pub fn find_retryable(
+        &self,
+        source: SyncErrorSource,
+        max_attempt_count: i32,
+    ) -> Result<Vec<SyncErrorRow>, RepositoryError> {
+        let result = sync_error_dsl::sync_error
+            .filter(sync_error_dsl::source.eq(source))
+            .filter(sync_error_dsl::attempt_count.lt(max_attempt_count))
+            .order(sync_error_dsl::created_datetime.asc())
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+
+    /// Sync errors, most recently attempted first
+    pub fn query(&self, pagination: Pagination) -> Result<Vec<SyncErrorRow>, RepositoryError> {
+        let result = sync_error_dsl::sync_error
+            .order(sync_error_dsl::last_attempt_datetime.desc())
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+
+    pub fn count(&self) -> Result<i64, RepositoryError> {
+        let result = sync_error_dsl::sync_error
+            .count()
+            .get_result(&self.connection.connection)?;
+        Ok(result)
+    }
+
+    pub fn delete(&self, id: &str) -> Result<(), RepositoryError> {
+        diesel::delete(sync_error_dsl::sync_error.filter(sync_error_dsl::id.eq(id)))
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+use super::{sync_log_row::sync_log::dsl as sync_log_dsl, StorageConnection};
+
+use crate::{repository_error::RepositoryError, Pagination};
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel_derive_enum::DbEnum;
+
+table! {
+    sync_log (id) {
+        id -> Text,
+        started_datetime -> Timestamp,
+        finished_datetime -> Nullable<Timestamp>,
+        phase -> crate::db_diesel::sync_log_row::SyncLogPhaseMapping,
+        push_record_count -> Integer,
+        remote_pull_record_count -> Integer,
+        central_pull_record_count -> Integer,
+        integration_record_count -> Integer,
+        error_message -> Nullable<Text>,
+    }
+}
+
+#[derive(DbEnum, Debug, Clone, PartialEq, Eq)]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum SyncLogPhase {
+    /// Pushing local changes to the central server
+    Push,
+    /// Pulling remote data and messages into the remote sync buffer
+    RemotePull,
+    /// Pulling central data into the central sync buffer
+    CentralPull,
+    /// Integrating the pulled central and remote records
+    Integrate,
+}
+
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq)]
+#[changeset_options(treat_none_as_null = "true")]
+#[table_name = "sync_log"]
+pub struct SyncLogRow {
+    pub id: String,
+    pub started_datetime: NaiveDateTime,
+    /// Set once the sync run is done, successful or not
+    pub finished_datetime: Option<NaiveDateTime>,
+    /// Phase the sync run is in, or was in when it finished or failed
+    pub phase: SyncLogPhase,
+    pub push_record_count: i32,
+    pub remote_pull_record_count: i32,
+    pub central_pull_record_count: i32,
+    pub integration_record_count: i32,
+    pub error_message: Option<String>,
+}
+
+pub struct SyncLogRowRepository<'a> {
+    connection: &'a StorageConnection,
+}
+
+impl<'a> SyncLogRowRepository<'a> {
+    pub fn new(connection: &'a StorageConnection) -> Self {
+        SyncLogRowRepository { connection }
+    }
+
+    #[cfg(feature = "postgres")]
+    pub fn upsert_one(&self, row: &SyncLogRow) -> Result<(), RepositoryError> {
+        diesel::insert_into(sync_log_dsl::sync_log)
+            .values(row)
+            .on_conflict(sync_log_dsl::id)
+            .do_update()
+            .set(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    pub fn upsert_one(&self, row: &SyncLogRow) -> Result<(), RepositoryError> {
+        diesel::replace_into(sync_log_dsl::sync_log)
+            .values(row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
+    pub fn find_one_by_id(&self, id: &str) -> Result<Option<SyncLogRow>, RepositoryError> {
+        let result = sync_log_dsl::sync_log
+            .filter(sync_log_dsl::id.eq(id))
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Most recently started sync run
+    pub fn find_latest(&self) -> Result<Option<SyncLogRow>, RepositoryError> {
+        let result = sync_log_dsl::sync_log
+            .order(sync_log_dsl::started_datetime.desc())
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Most recently started sync run that finished without an error
+    pub fn find_latest_successful(&self) -> Result<Option<SyncLogRow>, RepositoryError> {
+        let result = sync_log_dsl::sync_log
+            .filter(sync_log_dsl::finished_datetime.is_not_null())
+            .filter(sync_log_dsl::error_message.is_null())
+            .order(sync_log_dsl::started_datetime.desc())
+            .first(&self.connection.connection)
+            .optional()?;
+        Ok(result)
+    }
+
+    /// Sync runs, newest first
+    pub fn query(&self, pagination: Pagination) -> Result<Vec<SyncLogRow>, RepositoryError> {
+        let result = sync_log_dsl::sync_log
+            .order(sync_log_dsl::started_datetime.desc())
+            .offset(pagination.offset as i64)
+            .limit(pagination.limit as i64)
+            .load(&self.connection.connection)?;
+        Ok(result)
+    }
+
+    pub fn count(&self) -> Result<i64, RepositoryError> {
+        let result = sync_log_dsl::sync_log
+            .count()
+            .get_result(&self.connection.connection)?;
+        Ok(result)
+    }
+}
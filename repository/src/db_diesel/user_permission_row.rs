@@ -36,6 +36,8 @@ pub enum Permission {
     // requisition
     RequisitionQuery,
     RequisitionMutate,
+    /// Approve response requisitions
+    RequisitionAuthorise,
     // outbound shipment
     OutboundShipmentQuery,
     OutboundShipmentMutate,
@@ -10,16 +10,22 @@ table! {
         username -> Text,
         hashed_password -> Text,
         email -> Nullable<Text>,
+        is_local -> Bool,
+        is_disabled -> Bool,
     }
 }
 
-#[derive(Clone, Queryable, Insertable, Debug, PartialEq, Eq, Default)]
+#[derive(Clone, Queryable, Insertable, AsChangeset, Debug, PartialEq, Eq, Default)]
 #[table_name = "user_account"]
+#[changeset_options(treat_none_as_null = "true")]
 pub struct UserAccountRow {
     pub id: String,
     pub username: String,
     pub hashed_password: String,
     pub email: Option<String>,
+    /// User has been created on this site and is not known to the central server
+    pub is_local: bool,
+    pub is_disabled: bool,
 }
 
 pub struct UserAccountRowRepository<'a> {
@@ -38,6 +44,14 @@ impl<'a> UserAccountRowRepository<'a> {
         Ok(())
     }
 
+    pub fn update_one(&self, user_account_row: &UserAccountRow) -> Result<(), RepositoryError> {
+        diesel::update(user_account_dsl::user_account)
+            .filter(user_account_dsl::id.eq(&user_account_row.id))
+            .set(user_account_row)
+            .execute(&self.connection.connection)?;
+        Ok(())
+    }
+
     pub fn find_one_by_id(
         &self,
         account_id: &str,
@@ -96,6 +110,8 @@ pub fn unknown_user() -> User {
             username: "unknown".to_string(),
             hashed_password: "unknown".to_string(),
             email: Some("unknown@sussol.net".to_string()),
+            is_local: false,
+            is_disabled: false,
         },
         stores: vec![],
     }
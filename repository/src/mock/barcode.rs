@@ -0,0 +1,25 @@
+use crate::BarcodeRow;
+
+pub fn mock_barcode_a() -> BarcodeRow {
+    BarcodeRow {
+        id: String::from("barcode_a"),
+        gtin: String::from("00012345678905"),
+        item_id: String::from("item_a"),
+        manufacturer_id: None,
+        pack_size: Some(1),
+    }
+}
+
+pub fn mock_barcode_b() -> BarcodeRow {
+    BarcodeRow {
+        id: String::from("barcode_b"),
+        gtin: String::from("09506000134352"),
+        item_id: String::from("item_b"),
+        manufacturer_id: Some(String::from("name_a")),
+        pack_size: Some(10),
+    }
+}
+
+pub fn mock_barcodes() -> Vec<BarcodeRow> {
+    vec![mock_barcode_a(), mock_barcode_b()]
+}
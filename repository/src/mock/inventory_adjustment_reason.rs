@@ -0,0 +1,39 @@
+use crate::{InventoryAdjustmentReasonRow, InventoryAdjustmentType};
+
+// Reasons are not part of the base mock data since configuring reasons makes them mandatory for
+// all inventory adjustments. Tests that need reasons add them explicitly.
+
+pub fn mock_positive_inventory_adjustment_reason() -> InventoryAdjustmentReasonRow {
+    InventoryAdjustmentReasonRow {
+        id: "positive_inventory_adjustment_reason".to_owned(),
+        r#type: InventoryAdjustmentType::Positive,
+        is_active: true,
+        reason: "Found".to_owned(),
+    }
+}
+
+pub fn mock_negative_inventory_adjustment_reason() -> InventoryAdjustmentReasonRow {
+    InventoryAdjustmentReasonRow {
+        id: "negative_inventory_adjustment_reason".to_owned(),
+        r#type: InventoryAdjustmentType::Negative,
+        is_active: true,
+        reason: "Damaged".to_owned(),
+    }
+}
+
+pub fn mock_inactive_inventory_adjustment_reason() -> InventoryAdjustmentReasonRow {
+    InventoryAdjustmentReasonRow {
+        id: "inactive_inventory_adjustment_reason".to_owned(),
+        r#type: InventoryAdjustmentType::Negative,
+        is_active: false,
+        reason: "Expired".to_owned(),
+    }
+}
+
+pub fn mock_inventory_adjustment_reasons() -> Vec<InventoryAdjustmentReasonRow> {
+    vec![
+        mock_positive_inventory_adjustment_reason(),
+        mock_negative_inventory_adjustment_reason(),
+        mock_inactive_inventory_adjustment_reason(),
+    ]
+}
@@ -22,6 +22,9 @@ pub fn mock_outbound_shipment_a_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockOut,
         number_of_packs: 10,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     let mock_outbound_shipment_a_invoice_line_b: InvoiceLineRow = InvoiceLineRow {
@@ -43,6 +46,9 @@ pub fn mock_outbound_shipment_a_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockOut,
         number_of_packs: 4,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     vec![
@@ -71,6 +77,9 @@ pub fn mock_outbound_shipment_b_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockOut,
         number_of_packs: 3,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     let mock_outbound_shipment_b_invoice_line_b: InvoiceLineRow = InvoiceLineRow {
@@ -92,6 +101,9 @@ pub fn mock_outbound_shipment_b_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockOut,
         number_of_packs: 5,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     vec![
@@ -120,6 +132,9 @@ pub fn mock_outbound_shipment_c_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockOut,
         number_of_packs: 3,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     let mock_outbound_shipment_c_invoice_line_b: InvoiceLineRow = InvoiceLineRow {
@@ -141,6 +156,9 @@ pub fn mock_outbound_shipment_c_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockOut,
         number_of_packs: 1,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     vec![
@@ -169,6 +187,9 @@ pub fn mock_outbound_shipment_d_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockOut,
         number_of_packs: 2,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     vec![mock_outbound_shipment_d_invoice_line_a]
@@ -194,6 +215,9 @@ pub fn mock_inbound_shipment_a_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockIn,
         number_of_packs: 1,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     let mock_inbound_shipment_a_invoice_line_b: InvoiceLineRow = InvoiceLineRow {
@@ -215,6 +239,9 @@ pub fn mock_inbound_shipment_a_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockIn,
         number_of_packs: 1,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     vec![
@@ -243,6 +270,9 @@ pub fn mock_inbound_shipment_b_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockIn,
         number_of_packs: 1,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     let mock_inbound_shipment_b_invoice_line_b: InvoiceLineRow = InvoiceLineRow {
@@ -264,6 +294,9 @@ pub fn mock_inbound_shipment_b_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockIn,
         number_of_packs: 1,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     vec![
@@ -292,6 +325,9 @@ pub fn mock_inbound_shipment_c_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockIn,
         number_of_packs: 3,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     let mock_inbound_shipment_c_invoice_line_b: InvoiceLineRow = InvoiceLineRow {
@@ -313,6 +349,9 @@ pub fn mock_inbound_shipment_c_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockIn,
         number_of_packs: 2,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     vec![
@@ -341,6 +380,9 @@ pub fn mock_inbound_shipment_d_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockIn,
         number_of_packs: 7,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     let mock_inbound_shipment_d_invoice_line_b: InvoiceLineRow = InvoiceLineRow {
@@ -362,6 +404,9 @@ pub fn mock_inbound_shipment_d_invoice_lines() -> Vec<InvoiceLineRow> {
         r#type: InvoiceLineRowType::StockIn,
         number_of_packs: 2,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     vec![
@@ -7,6 +7,10 @@ pub fn mock_location_1() -> LocationRow {
         name: "name_location_1".to_owned(),
         on_hold: false,
         store_id: "store_a".to_string(),
+        parent_location_id: None,
+        capacity: None,
+        min_temperature: None,
+        max_temperature: None,
     }
 }
 
@@ -19,6 +23,10 @@ pub fn mock_locations() -> Vec<LocationRow> {
             name: "name_location_on_hold".to_owned(),
             on_hold: true,
             store_id: "store_a".to_string(),
+            parent_location_id: None,
+            capacity: None,
+            min_temperature: None,
+            max_temperature: None,
         },
         // For case insensitive sort
         LocationRow {
@@ -27,6 +35,10 @@ pub fn mock_locations() -> Vec<LocationRow> {
             name: "name_LocAtIOn_2".to_owned(),
             on_hold: false,
             store_id: "store_a".to_string(),
+            parent_location_id: None,
+            capacity: None,
+            min_temperature: None,
+            max_temperature: None,
         },
         // Location in another store, for unique code check
         LocationRow {
@@ -35,6 +47,10 @@ pub fn mock_locations() -> Vec<LocationRow> {
             name: "store_b_location_name".to_owned(),
             on_hold: false,
             store_id: "store_b".to_string(),
+            parent_location_id: None,
+            capacity: None,
+            min_temperature: None,
+            max_temperature: None,
         },
     ]
 }
@@ -1,8 +1,10 @@
 use std::{collections::HashMap, ops::Index};
 
+mod barcode;
 pub mod common;
 mod full_invoice;
 mod full_master_list;
+mod inventory_adjustment_reason;
 mod invoice;
 mod invoice_line;
 mod item;
@@ -10,6 +12,7 @@ mod location;
 mod name;
 mod name_store_join;
 mod number;
+mod program;
 mod stock_line;
 mod stocktake;
 mod stocktake_line;
@@ -35,9 +38,11 @@ mod test_unallocated_line;
 mod unit;
 mod user_account;
 
+pub use barcode::*;
 use common::*;
 pub use full_invoice::*;
 pub use full_master_list::*;
+pub use inventory_adjustment_reason::*;
 pub use invoice::*;
 pub use invoice_line::*;
 pub use item::*;
@@ -45,6 +50,7 @@ pub use location::*;
 pub use name::*;
 pub use name_store_join::*;
 pub use number::*;
+pub use program::*;
 pub use stock_line::*;
 pub use stocktake::*;
 pub use stocktake_line::*;
@@ -68,11 +74,15 @@ pub use test_unallocated_line::*;
 pub use user_account::*;
 
 use crate::{
-    InvoiceLineRow, InvoiceLineRowRepository, InvoiceRow, ItemRow, LocationRow,
-    LocationRowRepository, NumberRow, NumberRowRepository, RequisitionLineRow,
+    BarcodeRow, BarcodeRowRepository, CustomerShelfLifeRow, CustomerShelfLifeRowRepository,
+    InventoryAdjustmentReasonRow, InventoryAdjustmentReasonRowRepository, InvoiceLineRow,
+    InvoiceLineRowRepository, InvoiceRow, ItemRow, LocationRow, LocationRowRepository, NumberRow,
+    NumberRowRepository, PeriodRow, PeriodRowRepository, PeriodScheduleRow,
+    PeriodScheduleRowRepository, ProgramRow, ProgramRowRepository, RequisitionLineRow,
     RequisitionLineRowRepository, RequisitionRow, RequisitionRowRepository, StockLineRowRepository,
-    StocktakeLineRowRepository, StocktakeRowRepository, UserAccountRow, UserAccountRowRepository,
-    UserPermissionRow, UserPermissionRowRepository, UserStoreJoinRow, UserStoreJoinRowRepository,
+    StocktakeLineRowRepository, StocktakeRowRepository, StorePreferenceRow,
+    StorePreferenceRowRepository, UserAccountRow, UserAccountRowRepository, UserPermissionRow,
+    UserPermissionRowRepository, UserStoreJoinRow, UserStoreJoinRowRepository,
 };
 
 use self::unit::mock_units;
@@ -93,6 +103,7 @@ pub struct MockData {
     pub units: Vec<UnitRow>,
     pub items: Vec<ItemRow>,
     pub locations: Vec<LocationRow>,
+    pub inventory_adjustment_reasons: Vec<InventoryAdjustmentReasonRow>,
     pub name_store_joins: Vec<NameStoreJoinRow>,
     pub full_requisitions: Vec<FullMockRequisition>,
     pub invoices: Vec<InvoiceRow>,
@@ -105,6 +116,12 @@ pub struct MockData {
     pub requisition_lines: Vec<RequisitionLineRow>,
     pub stocktakes: Vec<StocktakeRow>,
     pub stocktake_lines: Vec<StocktakeLineRow>,
+    pub store_preferences: Vec<StorePreferenceRow>,
+    pub customer_shelf_lives: Vec<CustomerShelfLifeRow>,
+    pub barcodes: Vec<BarcodeRow>,
+    pub period_schedules: Vec<PeriodScheduleRow>,
+    pub periods: Vec<PeriodRow>,
+    pub programs: Vec<ProgramRow>,
 }
 
 #[derive(Default)]
@@ -117,6 +134,7 @@ pub struct MockDataInserts {
     pub units: bool,
     pub items: bool,
     pub locations: bool,
+    pub inventory_adjustment_reasons: bool,
     pub name_store_joins: bool,
     pub full_requisitions: bool,
     pub invoices: bool,
@@ -129,6 +147,10 @@ pub struct MockDataInserts {
     pub requisition_lines: bool,
     pub stocktakes: bool,
     pub stocktake_lines: bool,
+    pub store_preferences: bool,
+    pub customer_shelf_lives: bool,
+    pub barcodes: bool,
+    pub programs: bool,
 }
 
 impl MockDataInserts {
@@ -142,6 +164,7 @@ impl MockDataInserts {
             units: true,
             items: true,
             locations: true,
+            inventory_adjustment_reasons: true,
             name_store_joins: true,
             full_requisitions: true,
             invoices: true,
@@ -154,6 +177,10 @@ impl MockDataInserts {
             requisition_lines: true,
             stocktakes: true,
             stocktake_lines: true,
+            store_preferences: true,
+            customer_shelf_lives: true,
+            barcodes: true,
+            programs: true,
         }
     }
 
@@ -201,6 +228,11 @@ impl MockDataInserts {
         self
     }
 
+    pub fn inventory_adjustment_reasons(mut self) -> Self {
+        self.inventory_adjustment_reasons = true;
+        self
+    }
+
     pub fn name_store_joins(mut self) -> Self {
         self.name_store_joins = true;
         self
@@ -240,6 +272,27 @@ impl MockDataInserts {
         self.stocktake_lines = true;
         self
     }
+
+    pub fn store_preferences(mut self) -> Self {
+        self.store_preferences = true;
+        self
+    }
+
+    pub fn customer_shelf_lives(mut self) -> Self {
+        self.customer_shelf_lives = true;
+        self
+    }
+
+    pub fn barcodes(mut self) -> Self {
+        self.barcodes = true;
+        self
+    }
+
+    /// Programmes with their period schedules and periods
+    pub fn programs(mut self) -> Self {
+        self.programs = true;
+        self
+    }
 }
 
 #[derive(Default)]
@@ -285,6 +338,7 @@ fn all_mock_data() -> MockDataCollection {
             units: mock_units(),
             items: mock_items(),
             locations: mock_locations(),
+            inventory_adjustment_reasons: vec![],
             name_store_joins: mock_name_store_joins(),
             full_requisitions: vec![],
             invoices: mock_invoices(),
@@ -297,6 +351,12 @@ fn all_mock_data() -> MockDataCollection {
             stocktake_lines: mock_stocktake_line_data(),
             requisitions: vec![],
             requisition_lines: vec![],
+            store_preferences: vec![],
+            customer_shelf_lives: vec![],
+            barcodes: mock_barcodes(),
+            period_schedules: mock_period_schedules(),
+            periods: mock_periods(),
+            programs: mock_programs(),
         },
     );
     data.insert(
@@ -408,6 +468,13 @@ pub async fn insert_mock_data(
             }
         }
 
+        if inserts.inventory_adjustment_reasons {
+            let repo = InventoryAdjustmentReasonRowRepository::new(connection);
+            for row in &mock_data.inventory_adjustment_reasons {
+                repo.upsert_one(&row).unwrap();
+            }
+        }
+
         if inserts.name_store_joins {
             let repo = NameStoreJoinRepository::new(connection);
             for row in &mock_data.name_store_joins {
@@ -488,6 +555,44 @@ pub async fn insert_mock_data(
                 repo.upsert_one(row).unwrap();
             }
         }
+
+        if inserts.store_preferences {
+            let repo = StorePreferenceRowRepository::new(connection);
+            for row in &mock_data.store_preferences {
+                repo.upsert_one(row).unwrap();
+            }
+        }
+
+        if inserts.customer_shelf_lives {
+            let repo = CustomerShelfLifeRowRepository::new(connection);
+            for row in &mock_data.customer_shelf_lives {
+                repo.upsert_one(row).unwrap();
+            }
+        }
+
+        if inserts.barcodes {
+            let repo = BarcodeRowRepository::new(connection);
+            for row in &mock_data.barcodes {
+                repo.upsert_one(row).unwrap();
+            }
+        }
+
+        if inserts.programs {
+            let repo = PeriodScheduleRowRepository::new(connection);
+            for row in &mock_data.period_schedules {
+                repo.upsert_one(row).unwrap();
+            }
+
+            let repo = PeriodRowRepository::new(connection);
+            for row in &mock_data.periods {
+                repo.upsert_one(row).unwrap();
+            }
+
+            let repo = ProgramRowRepository::new(connection);
+            for row in &mock_data.programs {
+                repo.upsert_one(row).unwrap();
+            }
+        }
     }
 
     mock_data
@@ -502,6 +607,7 @@ impl MockData {
             mut units,
             mut items,
             mut locations,
+            mut inventory_adjustment_reasons,
             mut name_store_joins,
             mut full_requisitions,
             mut invoices,
@@ -514,6 +620,12 @@ impl MockData {
             mut requisition_lines,
             mut stocktakes,
             mut stocktake_lines,
+            mut store_preferences,
+            mut customer_shelf_lives,
+            mut barcodes,
+            mut period_schedules,
+            mut periods,
+            mut programs,
             user_store_joins: _,
             user_permissions: _,
         } = other;
@@ -524,6 +636,8 @@ impl MockData {
         self.units.append(&mut units);
         self.items.append(&mut items);
         self.locations.append(&mut locations);
+        self.inventory_adjustment_reasons
+            .append(&mut inventory_adjustment_reasons);
         self.full_requisitions.append(&mut full_requisitions);
         self.invoices.append(&mut invoices);
         self.invoice_lines.append(&mut invoice_lines);
@@ -536,6 +650,12 @@ impl MockData {
         self.stocktake_lines.append(&mut stocktake_lines);
         self.name_store_joins.append(&mut name_store_joins);
         self.stock_lines.append(&mut stock_lines);
+        self.store_preferences.append(&mut store_preferences);
+        self.customer_shelf_lives.append(&mut customer_shelf_lives);
+        self.barcodes.append(&mut barcodes);
+        self.period_schedules.append(&mut period_schedules);
+        self.periods.append(&mut periods);
+        self.programs.append(&mut programs);
 
         self
     }
@@ -0,0 +1,56 @@
+use chrono::NaiveDate;
+
+use crate::{PeriodRow, PeriodScheduleRow, PeriodScheduleType, ProgramRow};
+
+use super::mock_master_list_master_list_line_filter_test;
+
+pub fn mock_period_schedule_monthly() -> PeriodScheduleRow {
+    PeriodScheduleRow {
+        id: String::from("period_schedule_monthly"),
+        name: String::from("Monthly"),
+        r#type: PeriodScheduleType::Monthly,
+    }
+}
+
+pub fn mock_period_2021_01() -> PeriodRow {
+    PeriodRow {
+        id: String::from("period_2021_01"),
+        period_schedule_id: mock_period_schedule_monthly().id,
+        name: String::from("January 2021"),
+        start_date: NaiveDate::from_ymd(2021, 1, 1),
+        end_date: NaiveDate::from_ymd(2021, 1, 31),
+    }
+}
+
+pub fn mock_period_2021_02() -> PeriodRow {
+    PeriodRow {
+        id: String::from("period_2021_02"),
+        period_schedule_id: mock_period_schedule_monthly().id,
+        name: String::from("February 2021"),
+        start_date: NaiveDate::from_ymd(2021, 2, 1),
+        end_date: NaiveDate::from_ymd(2021, 2, 28),
+    }
+}
+
+pub fn mock_program_a() -> ProgramRow {
+    ProgramRow {
+        id: String::from("program_a"),
+        name: String::from("Programme A"),
+        master_list_id: mock_master_list_master_list_line_filter_test()
+            .master_list
+            .id,
+        period_schedule_id: mock_period_schedule_monthly().id,
+    }
+}
+
+pub fn mock_period_schedules() -> Vec<PeriodScheduleRow> {
+    vec![mock_period_schedule_monthly()]
+}
+
+pub fn mock_periods() -> Vec<PeriodRow> {
+    vec![mock_period_2021_01(), mock_period_2021_02()]
+}
+
+pub fn mock_programs() -> Vec<ProgramRow> {
+    vec![mock_program_a()]
+}
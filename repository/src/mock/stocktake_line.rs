@@ -9,6 +9,7 @@ pub fn mock_stocktake_line_a() -> StocktakeLineRow {
         comment: None,
         snapshot_number_of_packs: 10,
         counted_number_of_packs: Some(8),
+        second_counted_number_of_packs: None,
         item_id: "item_a".to_string(),
         expiry_date: None,
         batch: None,
@@ -16,6 +17,7 @@ pub fn mock_stocktake_line_a() -> StocktakeLineRow {
         cost_price_per_pack: None,
         sell_price_per_pack: None,
         note: None,
+        inventory_adjustment_reason_id: None,
     }
 }
 
@@ -28,6 +30,7 @@ pub fn mock_stocktake_line_b() -> StocktakeLineRow {
         comment: None,
         snapshot_number_of_packs: 10,
         counted_number_of_packs: None,
+        second_counted_number_of_packs: None,
         item_id: "item_b".to_string(),
         expiry_date: None,
         batch: None,
@@ -35,6 +38,7 @@ pub fn mock_stocktake_line_b() -> StocktakeLineRow {
         cost_price_per_pack: None,
         sell_price_per_pack: None,
         note: None,
+        inventory_adjustment_reason_id: None,
     }
 }
 
@@ -28,6 +28,9 @@ fn mock_outbound_shipment_line_no_stock_line() -> InvoiceLineRow {
         r#type: InvoiceLineRowType::StockOut,
         number_of_packs: 1,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     }
 }
 
@@ -165,6 +165,9 @@ pub fn mock_invoice1_linked_to_requisition() -> FullMockInvoice {
                     r#type: InvoiceLineRowType::StockOut,
                     number_of_packs: 10,
                     note: None,
+                    original_invoice_line_id: None,
+                    return_reason: None,
+                    inventory_adjustment_reason_id: None,
                 },
                 stock_line: StockLineRow {
                     id: line1_id.clone(),
@@ -202,6 +205,9 @@ pub fn mock_invoice1_linked_to_requisition() -> FullMockInvoice {
                     r#type: InvoiceLineRowType::StockOut,
                     number_of_packs: 10,
                     note: None,
+                    original_invoice_line_id: None,
+                    return_reason: None,
+                    inventory_adjustment_reason_id: None,
                 },
                 stock_line: StockLineRow {
                     id: line2_id.clone(),
@@ -258,6 +264,9 @@ pub fn mock_invoice2_linked_to_requisition() -> FullMockInvoice {
                 r#type: InvoiceLineRowType::StockOut,
                 number_of_packs: 10,
                 note: None,
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             },
             stock_line: StockLineRow {
                 id: line1_id.clone(),
@@ -313,6 +322,9 @@ pub fn mock_invoice3_linked_to_requisition() -> FullMockInvoice {
                 r#type: InvoiceLineRowType::StockOut,
                 number_of_packs: 10,
                 note: None,
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             },
             stock_line: StockLineRow {
                 id: line1_id.clone(),
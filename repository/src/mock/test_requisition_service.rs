@@ -326,6 +326,9 @@ pub fn mock_new_response_requisition_test_invoice() -> FullMockInvoice {
                     note: None,
                     location_id: None,
                     stock_line_id: None,
+                    original_invoice_line_id: None,
+                    return_reason: None,
+                    inventory_adjustment_reason_id: None,
                 },
                 stock_line: mock_stock_line_a(),
             },
@@ -349,6 +352,9 @@ pub fn mock_new_response_requisition_test_invoice() -> FullMockInvoice {
                     note: None,
                     location_id: None,
                     stock_line_id: None,
+                    original_invoice_line_id: None,
+                    return_reason: None,
+                    inventory_adjustment_reason_id: None,
                 },
                 stock_line: mock_stock_line_a(),
             },
@@ -146,6 +146,9 @@ pub fn mock_picked_invoice_sync_processor() -> FullMockInvoice {
                     note: None,
                     location_id: None,
                     stock_line_id: None,
+                    original_invoice_line_id: None,
+                    return_reason: None,
+                    inventory_adjustment_reason_id: None,
                 },
                 stock_line: mock_stock_line_a(),
             },
@@ -169,6 +172,9 @@ pub fn mock_picked_invoice_sync_processor() -> FullMockInvoice {
                     note: None,
                     location_id: None,
                     stock_line_id: None,
+                    original_invoice_line_id: None,
+                    return_reason: None,
+                    inventory_adjustment_reason_id: None,
                 },
                 stock_line: mock_stock_line_a(),
             },
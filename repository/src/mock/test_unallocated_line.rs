@@ -51,6 +51,9 @@ pub fn mock_unallocated_line() -> InvoiceLineRow {
         r#type: InvoiceLineRowType::UnallocatedStock,
         number_of_packs: 1,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     }
 }
 
@@ -88,6 +91,9 @@ pub fn mock_unallocated_line2() -> InvoiceLineRow {
         r#type: InvoiceLineRowType::UnallocatedStock,
         number_of_packs: 1,
         note: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     }
 }
 
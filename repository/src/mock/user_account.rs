@@ -8,6 +8,8 @@ pub fn mock_user_account_a() -> UserAccountRow {
         username: String::from("username_a"),
         hashed_password: String::from("password_a"),
         email: Some(String::from("username_a@openmsupply.foundation")),
+        is_local: false,
+        is_disabled: false,
     }
 }
 
@@ -17,6 +19,8 @@ pub fn mock_user_account_b() -> UserAccountRow {
         username: String::from("username_b"),
         hashed_password: String::from("password_b"),
         email: Some(String::from("username_b@openmsupply.foundation")),
+        is_local: false,
+        is_disabled: false,
     }
 }
 
@@ -158,6 +158,9 @@ mod repository_test {
                 number_of_packs: 1,
                 note: None,
                 location_id: None,
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             }
         }
         pub fn invoice_line_2() -> InvoiceLineRow {
@@ -180,6 +183,9 @@ mod repository_test {
                 number_of_packs: 1,
                 note: None,
                 location_id: None,
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             }
         }
 
@@ -203,6 +209,9 @@ mod repository_test {
                 number_of_packs: 1,
                 note: None,
                 location_id: None,
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             }
         }
 
@@ -226,6 +235,9 @@ mod repository_test {
                 number_of_packs: 1,
                 note: None,
                 location_id: None,
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             }
         }
 
@@ -235,6 +247,8 @@ mod repository_test {
                 username: "user 1".to_string(),
                 hashed_password: "p1".to_string(),
                 email: Some("email".to_string()),
+                is_local: false,
+                is_disabled: false,
             }
         }
 
@@ -244,6 +258,8 @@ mod repository_test {
                 username: "user 2".to_string(),
                 hashed_password: "p2".to_string(),
                 email: None,
+                is_local: false,
+                is_disabled: false,
             }
         }
 
@@ -272,21 +288,21 @@ mod repository_test {
         mock::{
             mock_draft_request_requisition_line, mock_draft_request_requisition_line2,
             mock_inbound_shipment_number_store_a, mock_master_list_master_list_line_filter_test,
-            mock_outbound_shipment_number_store_a, mock_request_draft_requisition,
-            mock_request_draft_requisition2, mock_stocktake_a, mock_stocktake_b,
-            mock_stocktake_no_line_a, mock_stocktake_no_line_b, mock_test_master_list_name1,
-            mock_test_master_list_name2, mock_test_master_list_name_filter1,
-            mock_test_master_list_name_filter2, mock_test_master_list_name_filter3,
-            mock_test_master_list_store1, MockDataInserts,
+            mock_outbound_shipment_number_store_a, mock_period_2021_01, mock_program_a,
+            mock_request_draft_requisition, mock_request_draft_requisition2, mock_stocktake_a,
+            mock_stocktake_b, mock_stocktake_no_line_a, mock_stocktake_no_line_b,
+            mock_test_master_list_name1, mock_test_master_list_name2,
+            mock_test_master_list_name_filter1, mock_test_master_list_name_filter2,
+            mock_test_master_list_name_filter3, mock_test_master_list_store1, MockDataInserts,
         },
-        requisition_row::RequisitionRowStatus,
+        requisition_row::{RequisitionRowStatus, RequisitionRowType},
         test_db, CentralSyncBufferRepository, ChangelogAction, ChangelogRow,
         ChangelogRowRepository, ChangelogTableName, InvoiceLineRepository,
         InvoiceLineRowRepository, InvoiceRowRepository, ItemRowRepository, KeyValueStoreRepository,
         KeyValueType, MasterListFilter, MasterListLineFilter, MasterListLineRepository,
         MasterListLineRowRepository, MasterListNameJoinRepository, MasterListRepository,
         MasterListRowRepository, NameRowRepository, NumberRowRepository, NumberRowType,
-        OutboundShipmentRowRepository, RequisitionFilter, RequisitionLineFilter,
+        OutboundShipmentRowRepository, RepositoryError, RequisitionFilter, RequisitionLineFilter,
         RequisitionLineRepository, RequisitionLineRowRepository, RequisitionRepository,
         RequisitionRowRepository, StockLineFilter, StockLineRepository, StockLineRowRepository,
         StocktakeRowRepository, StoreRowRepository, UserAccountRowRepository,
@@ -1047,6 +1063,60 @@ mod repository_test {
         );
     }
 
+    #[actix_rt::test]
+    async fn test_requisition_program_period_unique() {
+        let (_, connection, _, _) = test_db::setup_all(
+            "test_requisition_program_period_unique",
+            MockDataInserts::all(),
+        )
+        .await;
+        let row_repo = RequisitionRowRepository::new(&connection);
+
+        let program_requisition = inline_edit(&mock_request_draft_requisition(), |mut r| {
+            r.program_id = Some(mock_program_a().id);
+            r.period_id = Some(mock_period_2021_01().id);
+            r
+        });
+        row_repo.upsert_one(&program_requisition).unwrap();
+        // Updating the requisition itself is fine
+        row_repo
+            .upsert_one(&inline_edit(&program_requisition, |mut r| {
+                r.comment = Some("updated".to_string());
+                r
+            }))
+            .unwrap();
+
+        // Second request requisition of the store for the programme period
+        let duplicate = inline_edit(&program_requisition, |mut r| {
+            r.id = "duplicate_program_requisition".to_string();
+            r
+        });
+        assert!(matches!(
+            row_repo.upsert_one(&duplicate),
+            Err(RepositoryError::UniqueViolation(_))
+        ));
+        // Existing requisition is kept
+        assert_eq!(
+            row_repo
+                .find_one_by_id(&program_requisition.id)
+                .unwrap()
+                .unwrap()
+                .comment,
+            Some("updated".to_string())
+        );
+
+        // Supplier store receives a response requisition from every customer for the period
+        let response = |id: &str| {
+            inline_edit(&duplicate, |mut r| {
+                r.id = id.to_string();
+                r.r#type = RequisitionRowType::Response;
+                r
+            })
+        };
+        row_repo.upsert_one(&response("response_a")).unwrap();
+        row_repo.upsert_one(&response("response_b")).unwrap();
+    }
+
     #[actix_rt::test]
     async fn test_requisition_line_repository() {
         let (_, connection, _, _) =
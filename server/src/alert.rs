@@ -0,0 +1,33 @@
+use repository::StorageConnectionManager;
+use service::alert::evaluate::evaluate_enabled_alerts;
+use tokio::time::Duration;
+
+use crate::scheduler::schedule_store_job;
+
+/// How often the alert rules of stores with enabled alerts are evaluated
+const ALERT_EVALUATION_INTERVAL_SEC: u64 = 60 * 60;
+
+/// Periodically evaluates the alert rules of stores that have alerts enabled (not suppose to
+/// return)
+pub async fn schedule_alerts(connection_manager: StorageConnectionManager) {
+    schedule_store_job(
+        connection_manager,
+        "Alert evaluation",
+        Duration::from_secs(ALERT_EVALUATION_INTERVAL_SEC),
+        |ctx, now| {
+            Ok(evaluate_enabled_alerts(ctx, now)?
+                .into_iter()
+                .map(|(store_id, result)| {
+                    let message = result.map(|evaluation| {
+                        format!(
+                            "raised {} and resolved {} alerts",
+                            evaluation.number_of_new_alerts, evaluation.number_of_resolved_alerts
+                        )
+                    });
+                    (store_id, message)
+                })
+                .collect())
+        },
+    )
+    .await
+}
@@ -0,0 +1,35 @@
+use repository::{unknown_user, StorageConnectionManager};
+use service::cycle_count::generate::run_cycle_counts;
+use tokio::time::Duration;
+
+use crate::scheduler::schedule_store_job;
+
+/// How often the items of stores with enabled cycle counting are classified and the cycle count
+/// is generated (the first run is on startup)
+const CYCLE_COUNT_INTERVAL_SEC: u64 = 24 * 60 * 60;
+
+/// Periodically generates the cycle count of stores that have cycle counting enabled (not suppose
+/// to return)
+pub async fn schedule_cycle_counts(connection_manager: StorageConnectionManager) {
+    let user_id = unknown_user().user_row.id;
+    schedule_store_job(
+        connection_manager,
+        "Cycle count",
+        Duration::from_secs(CYCLE_COUNT_INTERVAL_SEC),
+        |ctx, now| {
+            Ok(run_cycle_counts(ctx, &user_id, now)?
+                .into_iter()
+                .map(|(store_id, result)| {
+                    let message = result.map(|stocktake| match stocktake {
+                        Some(stocktake) => {
+                            format!("created stocktake {}", stocktake.stocktake_number)
+                        }
+                        None => "found no items due".to_string(),
+                    });
+                    (store_id, message)
+                })
+                .collect())
+        },
+    )
+    .await
+}
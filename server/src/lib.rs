@@ -1,9 +1,12 @@
 use crate::static_files::config_static_files;
 
 use self::{
+    alert::schedule_alerts,
+    cycle_count::schedule_cycle_counts,
     middleware::{compress as compress_middleware, logger as logger_middleware},
+    reorder::schedule_reorders,
     settings::Settings,
-    sync::Synchroniser,
+    sync::{get_sync_actors, Synchroniser},
 };
 use graphql_core::loader::{get_loaders, LoaderRegistry};
 use openssl::ssl::{SslAcceptor, SslAcceptorBuilder, SslFiletype, SslMethod};
@@ -15,11 +18,12 @@ use service::{
     auth_data::AuthData,
     service_provider::ServiceProvider,
     settings_service::{SettingsService, SettingsServiceTrait},
+    token::load_or_create_token_secret,
     token_bucket::TokenBucket,
 };
 
 use actix_cors::Cors;
-use actix_web::{web::Data, App, HttpServer};
+use actix_web::{dev::ServerHandle, web::Data, App, HttpServer};
 use std::{
     io::ErrorKind,
     net::TcpListener,
@@ -27,12 +31,15 @@ use std::{
     path::Path,
     sync::{Arc, RwLock},
 };
-use tokio::sync::{oneshot, Mutex};
-use util::uuid::uuid;
+use tokio::sync::{mpsc, oneshot, Mutex};
 
+pub mod alert;
 pub mod configuration;
+pub mod cycle_count;
 pub mod environment;
 pub mod middleware;
+pub mod reorder;
+pub mod scheduler;
 pub mod settings;
 pub mod static_files;
 pub mod sync;
@@ -47,6 +54,22 @@ async fn run_stage0(
 ) -> std::io::Result<bool> {
     warn!("Starting server in bootstrap mode. Please use API to configure the server.");
 
+    let (server_handle, mut restart_switch_receiver) =
+        start_stage0_server(&settings, token_bucket, token_secret, connection_manager).await?;
+    let restart = wait_for_restart_or_stop(&off_switch, &mut restart_switch_receiver).await;
+    // gracefully shutdown the server
+    server_handle.stop(true).await;
+    Ok(restart)
+}
+
+/// Starts serving the bootstrap (stage0) API, returns the handle of the running server and the
+/// receiver for restart requests
+async fn start_stage0_server(
+    settings: &Settings,
+    token_bucket: Arc<RwLock<TokenBucket>>,
+    token_secret: String,
+    connection_manager: StorageConnectionManager,
+) -> std::io::Result<(ServerHandle, mpsc::Receiver<bool>)> {
     let cert_type = find_certs();
     let auth_data = Data::new(AuthData {
         auth_token_secret: token_secret,
@@ -55,7 +78,7 @@ async fn run_stage0(
         debug_no_access_control: settings.server.develop && settings.server.debug_no_access_control,
     });
 
-    let (restart_switch, mut restart_switch_receiver) = tokio::sync::mpsc::channel::<bool>(1);
+    let (restart_switch, restart_switch_receiver) = mpsc::channel::<bool>(1);
     let connection_manager_data_app = Data::new(connection_manager.clone());
 
     let service_provider = ServiceProvider::new(connection_manager.clone());
@@ -106,20 +129,25 @@ async fn run_stage0(
     }
     let running_sever = http_server.run();
     let server_handle = running_sever.handle();
-    // run server in another task so that we can handle restart/off events here
+    // run server in another task so that we can handle restart/off events in the caller
     actix_web::rt::spawn(running_sever);
 
+    Ok((server_handle, restart_switch_receiver))
+}
+
+/// Return true if restart has been requested
+async fn wait_for_restart_or_stop(
+    off_switch: &Mutex<oneshot::Receiver<()>>,
+    restart_switch_receiver: &mut mpsc::Receiver<bool>,
+) -> bool {
     let mut off_switch = off_switch.lock().await;
     let off_switch = off_switch.deref_mut();
     let ctrl_c = tokio::signal::ctrl_c();
-    let restart = tokio::select! {
+    tokio::select! {
         _ = ctrl_c => false,
         _ = off_switch => false,
         _ = restart_switch_receiver.recv() => true,
-    };
-    // gracefully shutdown the server
-    server_handle.stop(true).await;
-    Ok(restart)
+    }
 }
 
 /// Return true if restart has been requested
@@ -178,25 +206,38 @@ async fn run_server(
 
     let restart_switch = Data::new(restart_switch);
 
-    let mut synchroniser = Synchroniser::new(sync_settings, connection_manager.clone()).unwrap();
-    // Do the initial pull before doing anything else
-    match synchroniser.initial_pull().await {
-        Ok(_) => {}
-        Err(err) => {
-            error!("Failed to perform the initial sync: {}", err);
-            if !config_settings.server.develop {
-                warn!("Falling back to bootstrap mode");
-                return run_stage0(
-                    config_settings,
-                    off_switch,
-                    token_bucket,
-                    token_secret,
-                    connection_manager,
-                )
-                .await;
-            }
+    let (sync_sender, sync_receiver) = get_sync_actors();
+    let sync_sender_data = Some(Data::new(sync_sender));
+
+    let synchroniser = Synchroniser::new(sync_settings, connection_manager.clone()).unwrap();
+    // Do the initial pull before doing anything else. Meanwhile the bootstrap API is served, e.g. to
+    // report the initial sync progress.
+    let (stage0_server_handle, mut stage0_restart_switch_receiver) = start_stage0_server(
+        &config_settings,
+        token_bucket.clone(),
+        token_secret.clone(),
+        connection_manager.clone(),
+    )
+    .await?;
+    let initial_pull_result = tokio::select! {
+        result = synchroniser.initial_pull() => result,
+        // An interrupted initial pull is resumed after the restart
+        restart = wait_for_restart_or_stop(&off_switch, &mut stage0_restart_switch_receiver) => {
+            stage0_server_handle.stop(true).await;
+            return Ok(restart);
         }
     };
+    if let Err(err) = initial_pull_result {
+        error!("Failed to perform the initial sync: {}", err);
+        if !config_settings.server.develop {
+            warn!("Falling back to bootstrap mode");
+            let restart =
+                wait_for_restart_or_stop(&off_switch, &mut stage0_restart_switch_receiver).await;
+            stage0_server_handle.stop(true).await;
+            return Ok(restart);
+        }
+    }
+    stage0_server_handle.stop(true).await;
 
     let mut http_server = HttpServer::new(move || {
         App::new()
@@ -210,6 +251,7 @@ async fn run_server(
                 auth_data.clone(),
                 sync_settings_data.clone(),
                 restart_switch.clone(),
+                sync_sender_data.clone(),
             ))
             .configure(config_static_files)
     })
@@ -253,8 +295,11 @@ async fn run_server(
         _ = off_switch => false,
         _ = restart_switch_receiver.recv() => true,
         () = async {
-            synchroniser.run().await;
+            synchroniser.run(sync_receiver).await;
         } => unreachable!("Synchroniser unexpectedly died!?"),
+        () = schedule_reorders(connection_manager.clone()) => unreachable!("Reorder scheduler unexpectedly died!?"),
+        () = schedule_alerts(connection_manager.clone()) => unreachable!("Alert scheduler unexpectedly died!?"),
+        () = schedule_cycle_counts(connection_manager.clone()) => unreachable!("Cycle count scheduler unexpectedly died!?"),
     };
 
     server_handle.stop(true).await;
@@ -283,8 +328,26 @@ pub async fn start_server(
     // allow the off_switch to be passed around during multiple server stages
     let off_switch = Arc::new(Mutex::new(off_switch));
     let mut prefer_config_settings = true;
-    let token_bucket = Arc::new(RwLock::new(TokenBucket::new()));
-    let token_secret = uuid();
+    // Restore tokens and the token secret so that users stay logged in across restarts
+    let token_bucket = match TokenBucket::load(connection_manager.clone()) {
+        Ok(token_bucket) => Arc::new(RwLock::new(token_bucket)),
+        Err(err) => {
+            let msg = format!("Failed to load auth tokens: {}", err);
+            error!("{}", msg);
+            return Err(std::io::Error::new(ErrorKind::Other, msg));
+        }
+    };
+    let token_secret = match connection_manager
+        .connection()
+        .and_then(|connection| load_or_create_token_secret(&connection))
+    {
+        Ok(token_secret) => token_secret,
+        Err(err) => {
+            let msg = format!("Failed to load auth token secret: {}", err);
+            error!("{}", msg);
+            return Err(std::io::Error::new(ErrorKind::Other, msg));
+        }
+    };
     loop {
         match run_server(
             config_settings.clone(),
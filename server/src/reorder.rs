@@ -0,0 +1,27 @@
+use repository::StorageConnectionManager;
+use service::reorder::run::run_due_reorders;
+use tokio::time::Duration;
+
+use crate::scheduler::schedule_store_job;
+
+/// How often to check for stores that are due for an automatic reorder
+const REORDER_CHECK_INTERVAL_SEC: u64 = 60 * 60;
+
+/// Periodically runs the automatic reorder of stores that have it enabled (not suppose to return)
+pub async fn schedule_reorders(connection_manager: StorageConnectionManager) {
+    schedule_store_job(
+        connection_manager,
+        "Automatic reorder",
+        Duration::from_secs(REORDER_CHECK_INTERVAL_SEC),
+        |ctx, now| {
+            Ok(run_due_reorders(ctx, now)?
+                .into_iter()
+                .map(|(store_id, result)| {
+                    let message = result.map(|run| format!("added {} items", run.number_of_items));
+                    (store_id, message)
+                })
+                .collect())
+        },
+    )
+    .await
+}
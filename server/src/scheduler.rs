@@ -0,0 +1,51 @@
+use std::fmt::Debug;
+
+use chrono::{NaiveDateTime, Utc};
+use log::{error, info};
+use repository::{RepositoryError, StorageConnectionManager};
+use service::service_provider::{ServiceContext, ServiceProvider};
+use tokio::time::{self, Duration};
+
+/// Result of a scheduled job for a single store, on success a message describing what was done
+pub type StoreJobResult<E> = (String, Result<String, E>);
+
+/// Runs a job for all stores that have it enabled every `interval` (not suppose to return)
+///
+/// `job_name` is used in the log messages, e.g. "automatic reorder". Failures are logged and the
+/// job is retried with the next tick.
+pub async fn schedule_store_job<F, E>(
+    connection_manager: StorageConnectionManager,
+    job_name: &str,
+    interval: Duration,
+    job: F,
+) where
+    F: Fn(&ServiceContext, NaiveDateTime) -> Result<Vec<StoreJobResult<E>>, RepositoryError>,
+    E: Debug,
+{
+    let service_provider = ServiceProvider::new(connection_manager);
+    let mut interval = time::interval(interval);
+    loop {
+        interval.tick().await;
+
+        let ctx = match service_provider.context() {
+            Ok(ctx) => ctx,
+            Err(err) => {
+                error!("Failed to get a DB connection for {}: {:?}", job_name, err);
+                continue;
+            }
+        };
+        let results = match job(&ctx, Utc::now().naive_utc()) {
+            Ok(results) => results,
+            Err(err) => {
+                error!("Failed to run {}: {:?}", job_name, err);
+                continue;
+            }
+        };
+        for (store_id, result) in results {
+            match result {
+                Ok(message) => info!("{} for store {}: {}", job_name, store_id, message),
+                Err(err) => error!("{} for store {} failed: {:?}", job_name, store_id, err),
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::sync::{
+    quarantine::SyncQuarantine,
     sync_api_v5::CentralSyncBatchV5,
     translation_central::{import_sync_records, TRANSLATION_RECORDS},
     SyncApiV5, SyncConnectionError,
@@ -8,6 +9,7 @@ use repository::{
     CentralSyncBufferRepository, CentralSyncBufferRow, KeyValueStoreRepository, KeyValueType,
     RepositoryError, StorageConnection, TransactionError,
 };
+use service::sync_status::initial_sync::{InitialSyncLogger, InitialSyncSource};
 use thiserror::Error;
 
 use super::{sync_api_v5::CentralSyncRecordV5, SyncImportError};
@@ -22,12 +24,16 @@ pub enum CentralSyncError {
     GetCentralSyncCursorRecordError { source: RepositoryError },
     #[error("Failed to get central sync buffer records")]
     GetCentralSyncBufferRecordsError { source: RepositoryError },
+    #[error("Failed to get quarantined central sync records")]
+    GetQuarantinedSyncRecordsError { source: RepositoryError },
     #[error("Failed to import central sync buffer records")]
     ImportCentralSyncRecordsError { source: SyncImportError },
     #[error("Failed to remove central sync buffer records")]
     RemoveCentralSyncBufferRecordsError { source: RepositoryError },
     #[error("Failed to connect to DB")]
     DBConnectionError { source: RepositoryError },
+    #[error("Failed to update initial sync progress")]
+    UpdateInitialSyncProgressError { source: RepositoryError },
 }
 
 pub struct CentralDataSynchroniser {
@@ -35,10 +41,12 @@ pub struct CentralDataSynchroniser {
 }
 
 impl CentralDataSynchroniser {
-    async fn pull_central_records(
+    /// Pulls central records into the central sync buffer, returns the number of pulled records
+    pub(crate) async fn pull_central_records(
         &self,
         connection: &StorageConnection,
-    ) -> Result<(), CentralSyncError> {
+        mut initial_sync_logger: Option<&mut InitialSyncLogger<'_>>,
+    ) -> Result<u32, CentralSyncError> {
         let central_sync_cursor = CentralSyncPullCursor::new(&connection);
         let mut cursor: u32 = central_sync_cursor.get_cursor().unwrap_or_else(|_| {
             info!("Initialising new central sync cursor...");
@@ -47,6 +55,7 @@ impl CentralDataSynchroniser {
 
         // Arbitrary batch size.
         const BATCH_SIZE: u32 = 500;
+        let mut total_pulled_records = 0;
 
         loop {
             info!("Pulling central sync records...");
@@ -70,8 +79,9 @@ impl CentralDataSynchroniser {
                 central_sync_records.len()
             );
 
-            for central_sync_record in central_sync_records {
-                Self::insert_one_and_update_cursor(&connection, &central_sync_record)
+            total_pulled_records += central_sync_records.len() as u32;
+            for central_sync_record in &central_sync_records {
+                Self::insert_one_and_update_cursor(&connection, central_sync_record)
                     .await
                     .map_err(
                         |source| CentralSyncError::UpdateCentralSyncBufferRecordsError { source },
@@ -83,12 +93,25 @@ impl CentralDataSynchroniser {
                 .get_cursor()
                 .map_err(|source| CentralSyncError::GetCentralSyncCursorRecordError { source })?;
 
+            if let Some(logger) = initial_sync_logger.as_mut() {
+                let table_names = central_sync_records
+                    .iter()
+                    .map(|record| record.table_name.as_str());
+                let remaining = sync_batch.max_cursor.saturating_sub(cursor + 1);
+                logger
+                    .pulled(InitialSyncSource::Central, table_names)
+                    .and_then(|_| logger.set_remaining(InitialSyncSource::Central, remaining))
+                    .map_err(|source| CentralSyncError::UpdateInitialSyncProgressError {
+                        source,
+                    })?;
+            }
+
             if cursor >= sync_batch.max_cursor - 1 {
                 info!("All central sync records pulled successfully");
                 break;
             }
         }
-        Ok(())
+        Ok(total_pulled_records)
     }
 
     /// insert row and update cursor in a single transaction
@@ -110,10 +133,15 @@ impl CentralDataSynchroniser {
         Ok(result?)
     }
 
-    async fn integrate_central_records(
+    /// Integrates the central sync buffer and retries quarantined central records, returns the
+    /// number of integrated records
+    ///
+    /// Records are integrated table by table in topological order.
+    pub(crate) async fn integrate_central_records(
         &self,
         connection: &StorageConnection,
-    ) -> Result<(), CentralSyncError> {
+        mut initial_sync_logger: Option<&mut InitialSyncLogger<'_>>,
+    ) -> Result<u32, CentralSyncError> {
         let central_sync_buffer_repository = CentralSyncBufferRepository::new(&connection);
 
         let mut records: Vec<CentralSyncBufferRow> = Vec::new();
@@ -137,10 +165,39 @@ impl CentralDataSynchroniser {
             records.append(&mut buffer_rows);
         }
 
+        let mut quarantined_records = SyncQuarantine::central(connection)
+            .retryable_central(&records)
+            .map_err(|source| CentralSyncError::GetQuarantinedSyncRecordsError { source })?;
+        info!(
+            "Retrying {} quarantined central sync records",
+            quarantined_records.len()
+        );
+        records.append(&mut quarantined_records);
+
         info!("Importing {} central sync buffer records...", records.len());
-        import_sync_records(connection, &records)
-            .await
-            .map_err(|source| CentralSyncError::ImportCentralSyncRecordsError { source })?;
+        let mut number_of_integrated_records = 0;
+        for table_name in TRANSLATION_RECORDS {
+            let (table_records, remaining_records): (Vec<_>, Vec<_>) = records
+                .into_iter()
+                .partition(|record| record.table_name == *table_name);
+            records = remaining_records;
+            if table_records.is_empty() {
+                continue;
+            }
+
+            let integrated = import_sync_records(connection, &table_records)
+                .await
+                .map_err(|source| CentralSyncError::ImportCentralSyncRecordsError { source })?;
+            number_of_integrated_records += integrated;
+
+            if let Some(logger) = initial_sync_logger.as_mut() {
+                logger
+                    .integrated(InitialSyncSource::Central, table_name, integrated)
+                    .map_err(|source| CentralSyncError::UpdateInitialSyncProgressError {
+                        source,
+                    })?;
+            }
+        }
         info!("Successfully Imported central sync buffer records",);
 
         info!("Clearing central sync buffer");
@@ -150,19 +207,22 @@ impl CentralDataSynchroniser {
             .map_err(|source| CentralSyncError::RemoveCentralSyncBufferRecordsError { source })?;
         info!("Successfully cleared central sync buffer");
 
-        Ok(())
+        Ok(number_of_integrated_records)
     }
 
     pub async fn pull_and_integrate_records(
         &self,
         connection: &StorageConnection,
+        mut initial_sync_logger: Option<&mut InitialSyncLogger<'_>>,
     ) -> Result<(), CentralSyncError> {
         info!("Syncing central records...");
-        self.pull_central_records(connection).await?;
+        self.pull_central_records(connection, initial_sync_logger.as_deref_mut())
+            .await?;
         info!("Successfully synced central records");
 
         info!("Integrating central records...");
-        self.integrate_central_records(connection).await?;
+        self.integrate_central_records(connection, initial_sync_logger)
+            .await?;
         info!("Successfully integrated central records");
 
         Ok(())
@@ -264,7 +324,7 @@ mod tests {
         let sync_api_v5 = SyncApiV5::new(url, credentials, client);
 
         let sync = CentralDataSynchroniser { sync_api_v5 };
-        sync.integrate_central_records(&connection)
+        sync.integrate_central_records(&connection, None)
             .await
             .expect("Failed to integrate central records");
 
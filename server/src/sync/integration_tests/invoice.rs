@@ -25,6 +25,10 @@ impl SyncRecordTester<Vec<FullInvoice>> for InvoiceRecordTester {
             code: "TestLocationCode".to_string(),
             on_hold: false,
             store_id: store_id.to_string(),
+            parent_location_id: None,
+            capacity: None,
+            min_temperature: None,
+            max_temperature: None,
         };
         LocationRowRepository::new(connection)
             .upsert_one(&location)
@@ -62,6 +66,9 @@ impl SyncRecordTester<Vec<FullInvoice>> for InvoiceRecordTester {
             colour: None,
             requisition_id: None,
             linked_invoice_id: None,
+            prescriber: None,
+            diagnosis: None,
+            directions: None,
         };
         let invoice_line_row = InvoiceLineRow {
             id: uuid(),
@@ -82,6 +89,9 @@ impl SyncRecordTester<Vec<FullInvoice>> for InvoiceRecordTester {
             tax: Some(10.0),
             number_of_packs: 10,
             note: None,
+            original_invoice_line_id: None,
+            return_reason: None,
+            inventory_adjustment_reason_id: None,
         };
         let invoice_row_id_1 = uuid();
         let rows = vec![
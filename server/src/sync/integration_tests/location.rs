@@ -12,6 +12,10 @@ impl SyncRecordTester<Vec<LocationRow>> for LocationSyncRecordTester {
             code: "LocationCode".to_string(),
             on_hold: false,
             store_id: store_id.to_string(),
+            parent_location_id: None,
+            capacity: None,
+            min_temperature: None,
+            max_temperature: None,
         }];
         let repo = LocationRowRepository::new(connection);
         for row in &rows {
@@ -0,0 +1,461 @@
+use std::{collections::HashMap, net::TcpListener, sync::Mutex};
+
+use actix_web::{
+    dev::ServerHandle,
+    http::header::AUTHORIZATION,
+    web::{self, Bytes, Data},
+    App, HttpRequest, HttpResponse, HttpServer,
+};
+use chrono::{NaiveDateTime, NaiveTime, Timelike};
+use serde::Deserialize;
+use serde_json::json;
+use service::sync_settings::{
+    SyncSettings, DEFAULT_MAX_BACKOFF_SEC, DEFAULT_MAX_JITTER_SEC,
+    DEFAULT_PARTIAL_FAILURE_RETRY_SEC,
+};
+use util::uuid::uuid;
+
+use crate::sync::{
+    sync_api_v3::{RemotePostRecordV3, SyncTypeV3},
+    sync_api_v5::{
+        CentralSyncBatchV5, CentralSyncRecordV5, RemoteSyncAckV5, RemoteSyncActionV5,
+        RemoteSyncBatchV5, RemoteSyncRecordV5,
+    },
+    translation_central::TRANSLATION_RECORD_STORE,
+    translation_remote::{TRANSLATION_RECORD_REQUISITION_LINE, TRANSLATION_RECORD_TRANS_LINE},
+};
+
+pub const MOCK_CENTRAL_SERVER_SITE_ID: u32 = 1;
+
+/// Line records are sent to the same sites as their parent record: (table name, parent id field)
+const LINE_RECORDS: &[(&str, &str)] = &[
+    (TRANSLATION_RECORD_TRANS_LINE, "transaction_ID"),
+    (TRANSLATION_RECORD_REQUISITION_LINE, "requisition_ID"),
+];
+
+struct MockSite {
+    site_id: u32,
+    password_sha256: String,
+}
+
+/// Remote record as stored on the central server
+struct StoredRemoteRecord {
+    /// Site the record belongs to, i.e. the site that pushed it
+    site_id: u32,
+    table_name: String,
+    record_id: String,
+    data: serde_json::Value,
+    /// Other sites the record has been sent to as a message
+    recipients: Vec<u32>,
+}
+
+#[derive(Default)]
+struct MockCentralServerState {
+    /// Sites by username
+    sites: HashMap<String, MockSite>,
+    central_records: Vec<CentralSyncRecordV5>,
+    remote_records: Vec<StoredRemoteRecord>,
+    /// Remote records queued for a site, by site id
+    queues: HashMap<u32, Vec<RemoteSyncRecordV5>>,
+    /// Pushed records are rejected, e.g. to simulate a central server error
+    fail_push: bool,
+}
+
+/// In-process stand-in for the central server, implementing the sync API endpoints used by the
+/// remote server. Data is only kept in memory.
+///
+/// Central data is added through [MockCentralServer::add_central_record]. Remote data pushed by a
+/// site belongs to this site, i.e. it is queued for the site when the site (re-)initialises its
+/// sync queue. Remote records referring to a store of another site through their `name_ID` (e.g.
+/// a requisition to a supplying store) are sent to this site as messages, together with their
+/// lines.
+pub struct MockCentralServer {
+    url: String,
+    state: Data<Mutex<MockCentralServerState>>,
+    server_handle: ServerHandle,
+}
+
+impl MockCentralServer {
+    /// Starts the server on a free local port, must be called from within an actix runtime
+    pub fn start() -> MockCentralServer {
+        let state = Data::new(Mutex::new(MockCentralServerState::default()));
+        let app_state = state.clone();
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = HttpServer::new(move || {
+            App::new()
+                .app_data(app_state.clone())
+                .route("/sync/v5/initialise", web::post().to(initialise))
+                .route("/sync/v5/queued_records", web::get().to(queued_records))
+                .route(
+                    "/sync/v5/acknowledged_records",
+                    web::post().to(acknowledged_records),
+                )
+                .route("/sync/v5/central_records", web::get().to(central_records))
+                .route(
+                    "/sync/v3/queued_records",
+                    web::post().to(post_queued_records),
+                )
+        })
+        .workers(1)
+        .disable_signals()
+        .listen(listener)
+        .expect("Failed to start mock server")
+        .run();
+        let server_handle = server.handle();
+        actix_web::rt::spawn(server);
+
+        MockCentralServer {
+            url,
+            state,
+            server_handle,
+        }
+    }
+
+    /// Registers a remote site, returns the sync settings for the site to sync with this server
+    pub fn add_site(&self, site_id: u32) -> SyncSettings {
+        let username = format!("site_{}", site_id);
+        let password_sha256 = format!("{}_password", username);
+        self.state.lock().unwrap().sites.insert(
+            username.clone(),
+            MockSite {
+                site_id,
+                password_sha256: password_sha256.clone(),
+            },
+        );
+
+        SyncSettings {
+            url: self.url.clone(),
+            username,
+            password_sha256,
+            interval_sec: 60 * 60,
+            central_server_site_id: MOCK_CENTRAL_SERVER_SITE_ID,
+            site_id,
+            site_hardware_id: uuid(),
+            max_backoff_sec: DEFAULT_MAX_BACKOFF_SEC,
+            partial_failure_retry_sec: DEFAULT_PARTIAL_FAILURE_RETRY_SEC,
+            max_jitter_sec: DEFAULT_MAX_JITTER_SEC,
+        }
+    }
+
+    /// Adds a central record in the legacy format, e.g. a name, store or item
+    pub fn add_central_record(&self, table_name: &str, record_id: &str, data: serde_json::Value) {
+        let mut state = self.state.lock().unwrap();
+        let id = state.central_records.len() as i32 + 1;
+        state.central_records.push(CentralSyncRecordV5 {
+            id,
+            table_name: table_name.to_string(),
+            record_id: record_id.to_string(),
+            data,
+        });
+    }
+
+    /// Queues a remote record for a site, as if the record was edited on the central server
+    pub fn queue_remote_record(
+        &self,
+        site_id: u32,
+        table_name: &str,
+        record_id: &str,
+        data: serde_json::Value,
+    ) {
+        self.state
+            .lock()
+            .unwrap()
+            .queues
+            .entry(site_id)
+            .or_default()
+            .push(RemoteSyncRecordV5 {
+                sync_id: uuid(),
+                table: table_name.to_string(),
+                record_id: record_id.to_string(),
+                action: RemoteSyncActionV5::Update,
+                data: Some(data),
+            });
+    }
+
+    /// Number of remote records queued for a site that haven't been acknowledged yet
+    pub fn queue_length(&self, site_id: u32) -> usize {
+        self.state
+            .lock()
+            .unwrap()
+            .queues
+            .get(&site_id)
+            .map(|queue| queue.len())
+            .unwrap_or(0)
+    }
+
+    /// Makes pushes fail with a server error until reset
+    pub fn set_fail_push(&self, fail_push: bool) {
+        self.state.lock().unwrap().fail_push = fail_push;
+    }
+
+    pub async fn stop(self) {
+        self.server_handle.stop(true).await;
+    }
+}
+
+impl MockCentralServerState {
+    /// Returns the site id for valid basic auth credentials
+    fn authenticate(&self, request: &HttpRequest) -> Option<u32> {
+        let header = request.headers().get(AUTHORIZATION)?.to_str().ok()?;
+        let credentials = base64::decode(header.strip_prefix("Basic ")?).ok()?;
+        let credentials = String::from_utf8(credentials).ok()?;
+        let (username, password_sha256) = credentials.split_once(':')?;
+        let site = self.sites.get(username)?;
+        if site.password_sha256 != password_sha256 {
+            return None;
+        }
+        Some(site.site_id)
+    }
+
+    fn store_site_by_name_id(&self, name_id: &str) -> Option<u32> {
+        self.central_records
+            .iter()
+            .filter(|record| record.table_name == TRANSLATION_RECORD_STORE)
+            .find(|record| record.data["name_ID"].as_str() == Some(name_id))
+            .and_then(|record| record.data["sync_id_remote_site"].as_u64())
+            .map(|site_id| site_id as u32)
+    }
+
+    /// Other sites a record pushed by `site_id` is sent to
+    fn recipients(&self, site_id: u32, table_name: &str, data: &serde_json::Value) -> Vec<u32> {
+        let mut recipients = Vec::new();
+        if let Some(store_site_id) = data["name_ID"]
+            .as_str()
+            .and_then(|name_id| self.store_site_by_name_id(name_id))
+        {
+            recipients.push(store_site_id);
+        }
+
+        let parent_id_field = LINE_RECORDS
+            .iter()
+            .find(|(line_table_name, _)| *line_table_name == table_name)
+            .map(|(_, parent_id_field)| *parent_id_field);
+        if let Some(parent) = parent_id_field
+            .and_then(|field| data[field].as_str())
+            .and_then(|parent_id| {
+                self.remote_records
+                    .iter()
+                    .find(|record| record.record_id == parent_id)
+            })
+        {
+            recipients.extend(parent.recipients.iter());
+        }
+
+        recipients.retain(|recipient| *recipient != site_id);
+        recipients.sort();
+        recipients.dedup();
+        recipients
+    }
+
+    fn enqueue(
+        &mut self,
+        site_id: u32,
+        record: &StoredRemoteRecord,
+        action: RemoteSyncActionV5,
+        data: Option<serde_json::Value>,
+    ) {
+        self.queues
+            .entry(site_id)
+            .or_default()
+            .push(RemoteSyncRecordV5 {
+                sync_id: uuid(),
+                table: record.table_name.clone(),
+                record_id: record.record_id.clone(),
+                action,
+                data,
+            });
+    }
+
+    fn receive(&mut self, site_id: u32, record: RemotePostRecordV3) {
+        let existing = self.remote_records.iter().position(|existing| {
+            existing.table_name == record.record_type && existing.record_id == record.record_id
+        });
+
+        let (action, data) = match record.sync_type {
+            SyncTypeV3::Delete => {
+                if let Some(index) = existing {
+                    let deleted = self.remote_records.remove(index);
+                    for recipient in deleted.recipients.clone() {
+                        self.enqueue(recipient, &deleted, RemoteSyncActionV5::Delete, None);
+                    }
+                }
+                return;
+            }
+            SyncTypeV3::Insert => (RemoteSyncActionV5::Create, record.data),
+            SyncTypeV3::Update => (RemoteSyncActionV5::Update, record.data),
+            SyncTypeV3::Merge => (RemoteSyncActionV5::Merge, record.data),
+        };
+        let data = to_v5_data(data.unwrap_or_default());
+        let stored = StoredRemoteRecord {
+            site_id,
+            recipients: self.recipients(site_id, &record.record_type, &data),
+            table_name: record.record_type,
+            record_id: record.record_id,
+            data,
+        };
+
+        for recipient in stored.recipients.clone() {
+            self.enqueue(
+                recipient,
+                &stored,
+                action.clone(),
+                Some(stored.data.clone()),
+            );
+        }
+        match existing {
+            Some(index) => self.remote_records[index] = stored,
+            None => self.remote_records.push(stored),
+        }
+    }
+}
+
+/// Converts pushed record data to the format returned by the v5 API, as done by the central
+/// server: dates are pushed as date times and times as time strings, while the v5 API returns
+/// dates as dates and times in seconds. Legacy date time fields (`*_datetime`) are kept as is.
+fn to_v5_data(mut data: serde_json::Value) -> serde_json::Value {
+    let fields = match data.as_object_mut() {
+        Some(fields) => fields,
+        None => return data,
+    };
+    for (field, value) in fields.iter_mut() {
+        if field.ends_with("_datetime") {
+            continue;
+        }
+        let string = match value.as_str() {
+            Some(string) => string,
+            None => continue,
+        };
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(string, "%Y-%m-%dT%H:%M:%S") {
+            *value = json!(datetime.date().format("%Y-%m-%d").to_string());
+        } else if let Ok(time) = NaiveTime::parse_from_str(string, "%H:%M:%S") {
+            *value = json!(time.num_seconds_from_midnight());
+        }
+    }
+    data
+}
+
+type State = Data<Mutex<MockCentralServerState>>;
+
+#[derive(Deserialize)]
+struct LimitQuery {
+    limit: usize,
+}
+
+#[derive(Deserialize)]
+struct CursorQuery {
+    cursor: u32,
+    limit: usize,
+}
+
+/// Queues all remote records of the site, i.e. its own records and the records it received as
+/// messages
+async fn initialise(state: State, request: HttpRequest) -> HttpResponse {
+    let mut state = state.lock().unwrap();
+    let site_id = match state.authenticate(&request) {
+        Some(site_id) => site_id,
+        None => return HttpResponse::Unauthorized().finish(),
+    };
+
+    let queue: Vec<RemoteSyncRecordV5> = state
+        .remote_records
+        .iter()
+        .filter(|record| record.site_id == site_id || record.recipients.contains(&site_id))
+        .map(|record| RemoteSyncRecordV5 {
+            sync_id: uuid(),
+            table: record.table_name.clone(),
+            record_id: record.record_id.clone(),
+            action: RemoteSyncActionV5::Create,
+            data: Some(record.data.clone()),
+        })
+        .collect();
+    let queue_length = queue.len() as u32;
+    state.queues.insert(site_id, queue);
+
+    HttpResponse::Ok().json(RemoteSyncBatchV5 {
+        queue_length,
+        data: None,
+    })
+}
+
+async fn queued_records(
+    state: State,
+    request: HttpRequest,
+    query: web::Query<LimitQuery>,
+) -> HttpResponse {
+    let mut state = state.lock().unwrap();
+    let site_id = match state.authenticate(&request) {
+        Some(site_id) => site_id,
+        None => return HttpResponse::Unauthorized().finish(),
+    };
+
+    let queue = state.queues.entry(site_id).or_default();
+    HttpResponse::Ok().json(RemoteSyncBatchV5 {
+        queue_length: queue.len() as u32,
+        data: Some(queue.iter().take(query.limit).cloned().collect()),
+    })
+}
+
+async fn acknowledged_records(state: State, request: HttpRequest, body: Bytes) -> HttpResponse {
+    let mut state = state.lock().unwrap();
+    let site_id = match state.authenticate(&request) {
+        Some(site_id) => site_id,
+        None => return HttpResponse::Unauthorized().finish(),
+    };
+    let ack = match serde_json::from_slice::<RemoteSyncAckV5>(&body) {
+        Ok(ack) => ack,
+        Err(_) => return HttpResponse::BadRequest().finish(),
+    };
+
+    state
+        .queues
+        .entry(site_id)
+        .or_default()
+        .retain(|record| !ack.sync_ids.contains(&record.sync_id));
+    HttpResponse::Ok().finish()
+}
+
+async fn central_records(
+    state: State,
+    request: HttpRequest,
+    query: web::Query<CursorQuery>,
+) -> HttpResponse {
+    let state = state.lock().unwrap();
+    if state.authenticate(&request).is_none() {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    HttpResponse::Ok().json(CentralSyncBatchV5 {
+        max_cursor: state.central_records.len() as u32 + 1,
+        data: Some(
+            state
+                .central_records
+                .iter()
+                .filter(|record| record.id as u32 > query.cursor)
+                .take(query.limit)
+                .cloned()
+                .collect(),
+        ),
+    })
+}
+
+async fn post_queued_records(state: State, request: HttpRequest, body: Bytes) -> HttpResponse {
+    let mut state = state.lock().unwrap();
+    let site_id = match state.authenticate(&request) {
+        Some(site_id) => site_id,
+        None => return HttpResponse::Unauthorized().finish(),
+    };
+    if state.fail_push {
+        return HttpResponse::InternalServerError().finish();
+    }
+    let records = match serde_json::from_slice::<Vec<RemotePostRecordV3>>(&body) {
+        Ok(records) => records,
+        Err(_) => return HttpResponse::BadRequest().finish(),
+    };
+
+    for record in records {
+        state.receive(site_id, record);
+    }
+    HttpResponse::Ok().json(json!({ "error": "" }))
+}
@@ -1,5 +1,6 @@
 mod invoice;
 mod location;
+mod mock_central_server;
 mod number;
 mod remote_sync_integration_test;
 mod requisition;
@@ -20,24 +20,38 @@ pub fn gen_i64() -> i64 {
 #[cfg(test)]
 mod remote_sync_integration_tests {
 
+    use chrono::NaiveDate;
     use repository::{
-        mock::MockDataInserts, test_db::setup_all, EqualFilter, StorageConnection, StoreFilter,
-        StoreRepository,
+        mock::MockDataInserts,
+        requisition_row::{RequisitionRowStatus, RequisitionRowType},
+        test_db::setup_all,
+        EqualFilter, ItemRowRepository, LocationRow, LocationRowRepository, RequisitionLineRow,
+        RequisitionLineRowRepository, RequisitionRow, RequisitionRowRepository, StorageConnection,
+        StoreFilter, StoreRepository,
     };
-    use service::sync_settings::SyncSettings;
+    use serde_json::json;
+    use service::{
+        sync_actor::SyncOutcome,
+        sync_settings::{
+            SyncSettings, DEFAULT_MAX_BACKOFF_SEC, DEFAULT_MAX_JITTER_SEC,
+            DEFAULT_PARTIAL_FAILURE_RETRY_SEC,
+        },
+    };
+
+    use util::{inline_init, uuid::uuid};
 
     use crate::sync::{
         integration_tests::{
             invoice::InvoiceRecordTester, location::LocationSyncRecordTester,
-            number::NumberSyncRecordTester, requisition::RequisitionRecordTester,
-            stock_line::StockLineRecordTester, stocktake::StocktakeRecordTester,
+            mock_central_server::MockCentralServer, number::NumberSyncRecordTester,
+            requisition::RequisitionRecordTester, stock_line::StockLineRecordTester,
+            stocktake::StocktakeRecordTester,
         },
         Synchroniser,
     };
 
     use super::SyncRecordTester;
 
-    #[allow(dead_code)]
     async fn init_db(
         sync_settings: &SyncSettings,
         step: &str,
@@ -51,7 +65,7 @@ mod remote_sync_integration_tests {
         let synchroniser = Synchroniser::new(sync_settings.clone(), connection_manager).unwrap();
         synchroniser
             .central_data
-            .pull_and_integrate_records(&connection)
+            .pull_and_integrate_records(&connection, None)
             .await
             .unwrap();
 
@@ -63,12 +77,11 @@ mod remote_sync_integration_tests {
     /// 2) Reset local data and pull. Then validate that the pulled data is correct
     /// 3) Mutate the previously inserted data and push the changes
     /// 4) Reset, pull and validate as in step 2)
-    #[allow(dead_code)]
     async fn test_sync_record<T>(sync_settings: &SyncSettings, tester: &dyn SyncRecordTester<T>) {
         let (connection, synchroniser) = init_db(sync_settings, "step0").await;
         synchroniser
             .remote_data
-            .initial_pull(&connection)
+            .initial_pull(&connection, None)
             .await
             .unwrap();
         let store_id = StoreRepository::new(&connection)
@@ -92,7 +105,7 @@ mod remote_sync_integration_tests {
         let (connection, synchroniser) = init_db(sync_settings, "step1").await;
         synchroniser
             .remote_data
-            .initial_pull(&connection)
+            .initial_pull(&connection, None)
             .await
             .unwrap();
         // validate we pulled the same data we inserted
@@ -109,7 +122,7 @@ mod remote_sync_integration_tests {
         let (connection, synchroniser) = init_db(sync_settings, "step2").await;
         synchroniser
             .remote_data
-            .initial_pull(&connection)
+            .initial_pull(&connection, None)
             .await
             .unwrap();
         // validate we pulled the same data we inserted
@@ -134,6 +147,9 @@ mod remote_sync_integration_tests {
             central_server_site_id: 1,
             site_id: 7,
             site_hardware_id: "49149896-E713-4535-9DA8-C30AB06F9D5E".to_string(),
+            max_backoff_sec: DEFAULT_MAX_BACKOFF_SEC,
+            partial_failure_retry_sec: DEFAULT_PARTIAL_FAILURE_RETRY_SEC,
+            max_jitter_sec: DEFAULT_MAX_JITTER_SEC,
         };
 
         println!("number:");
@@ -160,4 +176,214 @@ mod remote_sync_integration_tests {
         let requisition_tester = RequisitionRecordTester {};
         test_sync_record(&sync_settings, &requisition_tester).await;
     }
+
+    const SITE_A: u32 = 2;
+    const SITE_B: u32 = 3;
+
+    /// Adds a store for each of the two sites and some items to the mock central server
+    fn add_central_data(central_server: &MockCentralServer) {
+        for (store_id, name_id, site_id) in [
+            ("store_a", "name_store_a", SITE_A),
+            ("store_b", "name_store_b", SITE_B),
+        ]
+        .iter()
+        {
+            central_server.add_central_record(
+                "name",
+                name_id,
+                json!({
+                    "ID": name_id,
+                    "name": name_id,
+                    "code": name_id,
+                    "type": "store",
+                    "customer": true,
+                    "supplier": true,
+                }),
+            );
+            central_server.add_central_record(
+                "store",
+                store_id,
+                json!({
+                    "ID": store_id,
+                    "name_ID": name_id,
+                    "code": store_id,
+                    "sync_id_remote_site": site_id,
+                }),
+            );
+        }
+        for item_id in ["item_a", "item_b"].iter() {
+            central_server.add_central_record(
+                "item",
+                item_id,
+                json!({
+                    "ID": item_id,
+                    "item_name": item_id,
+                    "code": item_id,
+                    "unit_ID": "",
+                    "type_of": "general",
+                }),
+            );
+        }
+    }
+
+    /// Same as [test_remote_syncing] but runs against the in-process mock central server, i.e.
+    /// without network access
+    #[actix_rt::test]
+    async fn test_remote_syncing_with_mock_central_server() {
+        let central_server = MockCentralServer::start();
+        add_central_data(&central_server);
+        let sync_settings = central_server.add_site(SITE_A);
+
+        test_sync_record(&sync_settings, &NumberSyncRecordTester {}).await;
+        test_sync_record(&sync_settings, &LocationSyncRecordTester {}).await;
+        test_sync_record(&sync_settings, &StockLineRecordTester {}).await;
+        // the stocktake tester uses an inventory adjustment from the invoice tester
+        test_sync_record(&sync_settings, &InvoiceRecordTester {}).await;
+        test_sync_record(&sync_settings, &StocktakeRecordTester {}).await;
+        test_sync_record(&sync_settings, &RequisitionRecordTester {}).await;
+
+        central_server.stop().await;
+    }
+
+    /// A requisition from site A to the store of site B is sent to site B as a message, while
+    /// other data of site A is not
+    #[actix_rt::test]
+    async fn test_sync_messages_between_sites() {
+        let central_server = MockCentralServer::start();
+        add_central_data(&central_server);
+        let settings_a = central_server.add_site(SITE_A);
+        let settings_b = central_server.add_site(SITE_B);
+
+        let (connection_a, synchroniser_a) = init_db(&settings_a, "messages_site_a").await;
+        synchroniser_a.initial_pull().await.unwrap();
+        let (connection_b, synchroniser_b) = init_db(&settings_b, "messages_site_b").await;
+        synchroniser_b.initial_pull().await.unwrap();
+
+        let requisition = inline_init(|r: &mut RequisitionRow| {
+            r.id = uuid();
+            r.store_id = "store_a".to_string();
+            r.name_id = "name_store_b".to_string();
+            r.r#type = RequisitionRowType::Request;
+            r.status = RequisitionRowStatus::Sent;
+            r.created_datetime = NaiveDate::from_ymd(2022, 03, 23).and_hms(8, 53, 0);
+            r.sent_datetime = Some(NaiveDate::from_ymd(2022, 03, 24).and_hms(8, 53, 0));
+        });
+        let requisition_line = inline_init(|r: &mut RequisitionLineRow| {
+            r.id = uuid();
+            r.requisition_id = requisition.id.clone();
+            r.item_id = "item_a".to_string();
+            r.requested_quantity = 20;
+        });
+        let location = inline_init(|r: &mut LocationRow| {
+            r.id = uuid();
+            r.name = "location_a".to_string();
+            r.code = "location_a".to_string();
+            r.store_id = "store_a".to_string();
+        });
+        RequisitionRowRepository::new(&connection_a)
+            .upsert_one(&requisition)
+            .unwrap();
+        RequisitionLineRowRepository::new(&connection_a)
+            .upsert_one(&requisition_line)
+            .unwrap();
+        LocationRowRepository::new(&connection_a)
+            .upsert_one(&location)
+            .unwrap();
+
+        assert_eq!(synchroniser_a.sync().await.unwrap(), SyncOutcome::Success);
+        assert_eq!(synchroniser_b.sync().await.unwrap(), SyncOutcome::Success);
+
+        let received = RequisitionRowRepository::new(&connection_b)
+            .find_one_by_id(&requisition.id)
+            .unwrap()
+            .expect("Requisition not received by site B");
+        assert_eq!(received.store_id, requisition.store_id);
+        assert_eq!(received.name_id, requisition.name_id);
+        assert!(RequisitionLineRowRepository::new(&connection_b)
+            .find_one_by_id(&requisition_line.id)
+            .unwrap()
+            .is_some());
+        assert_eq!(
+            LocationRowRepository::new(&connection_b)
+                .find_one_by_id(&location.id)
+                .unwrap(),
+            None
+        );
+
+        central_server.stop().await;
+    }
+
+    /// Remote records aren't pulled while local changes can't be pushed, they would overwrite the
+    /// unpushed changes. Central data is still pulled.
+    #[actix_rt::test]
+    async fn test_push_failure_skips_remote_pull() {
+        let central_server = MockCentralServer::start();
+        add_central_data(&central_server);
+        let settings = central_server.add_site(SITE_A);
+
+        let (connection, synchroniser) = init_db(&settings, "push_failure").await;
+        synchroniser.initial_pull().await.unwrap();
+
+        let location = inline_init(|r: &mut LocationRow| {
+            r.id = uuid();
+            r.name = "location_a".to_string();
+            r.code = "location_a".to_string();
+            r.store_id = "store_a".to_string();
+        });
+        let location_repository = LocationRowRepository::new(&connection);
+        location_repository.upsert_one(&location).unwrap();
+        assert_eq!(synchroniser.sync().await.unwrap(), SyncOutcome::Success);
+
+        // Local change that can't be pushed and a remote change of the same row
+        let local_edit = inline_init(|r: &mut LocationRow| {
+            *r = location.clone();
+            r.name = "local edit".to_string();
+        });
+        location_repository.upsert_one(&local_edit).unwrap();
+        central_server.set_fail_push(true);
+        central_server.queue_remote_record(
+            SITE_A,
+            "Location",
+            &location.id,
+            json!({
+                "ID": location.id,
+                "Description": "remote edit",
+                "code": location.code,
+                "hold": false,
+                "store_ID": location.store_id,
+            }),
+        );
+        central_server.add_central_record(
+            "item",
+            "item_c",
+            json!({
+                "ID": "item_c",
+                "item_name": "item_c",
+                "code": "item_c",
+                "unit_ID": "",
+                "type_of": "general",
+            }),
+        );
+
+        assert_eq!(
+            synchroniser.sync().await.unwrap(),
+            SyncOutcome::PartialFailure
+        );
+        assert_eq!(
+            location_repository.find_one_by_id(&location.id).unwrap(),
+            Some(local_edit.clone())
+        );
+        assert_eq!(central_server.queue_length(SITE_A), 1);
+        assert!(ItemRowRepository::new(&connection)
+            .find_one_by_id("item_c")
+            .unwrap()
+            .is_some());
+
+        // Local change is pushed before the remote record is pulled
+        central_server.set_fail_push(false);
+        assert_eq!(synchroniser.sync().await.unwrap(), SyncOutcome::Success);
+        assert_eq!(central_server.queue_length(SITE_A), 0);
+
+        central_server.stop().await;
+    }
 }
@@ -2,8 +2,9 @@ use chrono::NaiveDate;
 use repository::{
     mock::mock_request_draft_requisition,
     requisition_row::{RequisitionRowStatus, RequisitionRowType},
-    EqualFilter, ItemFilter, ItemRepository, NameFilter, NameRepository, RequisitionLineRow,
-    RequisitionLineRowRepository, RequisitionRow, RequisitionRowRepository, StorageConnection,
+    ConsumptionCalculationMethod, EqualFilter, ItemFilter, ItemRepository, NameFilter,
+    NameRepository, RequisitionLineRow, RequisitionLineRowRepository, RequisitionRow,
+    RequisitionRowRepository, StorageConnection,
 };
 use util::{inline_edit, uuid::uuid};
 
@@ -45,6 +46,9 @@ impl SyncRecordTester<Vec<FullRequisition>> for RequisitionRecordTester {
             max_months_of_stock: 10.0,
             min_months_of_stock: 5.0,
             linked_requisition_id: None,
+            program_id: None,
+            period_id: None,
+            approved_by_user_id: None,
         };
         let rows = vec![
             FullRequisition {
@@ -84,6 +88,14 @@ impl SyncRecordTester<Vec<FullRequisition>> for RequisitionRecordTester {
                     average_monthly_consumption: 15,
                     comment: None,
                     snapshot_datetime: None,
+                    approved_quantity: 0,
+                    approval_comment: None,
+                    opening_balance: 0,
+                    received_quantity: 0,
+                    issued_quantity: 0,
+                    adjusted_quantity: 0,
+                    closing_balance: 0,
+                    consumption_calculation_method: ConsumptionCalculationMethod::SimpleAverage,
                 }],
                 row,
             },
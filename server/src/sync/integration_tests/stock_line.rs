@@ -17,6 +17,10 @@ impl SyncRecordTester<Vec<StockLineRow>> for StockLineRecordTester {
             code: "TestLocationCode".to_string(),
             on_hold: false,
             store_id: store_id.to_string(),
+            parent_location_id: None,
+            capacity: None,
+            min_temperature: None,
+            max_temperature: None,
         };
         LocationRowRepository::new(connection)
             .upsert_one(&location)
@@ -24,6 +24,10 @@ impl SyncRecordTester<Vec<FullStocktake>> for StocktakeRecordTester {
             code: "TestLocationCode".to_string(),
             on_hold: false,
             store_id: store_id.to_string(),
+            parent_location_id: None,
+            capacity: None,
+            min_temperature: None,
+            max_temperature: None,
         };
         LocationRowRepository::new(connection)
             .upsert_one(&location)
@@ -49,6 +53,11 @@ impl SyncRecordTester<Vec<FullStocktake>> for StocktakeRecordTester {
                 finalised_datetime: None,
                 inventory_adjustment_id: None,
                 is_locked: true,
+                is_blind_count: false,
+                recount_threshold: None,
+                counted_by: None,
+                count_submitted_datetime: None,
+                verified_by: None,
             },
             lines: vec![StocktakeLineRow {
                 id: uuid(),
@@ -58,6 +67,7 @@ impl SyncRecordTester<Vec<FullStocktake>> for StocktakeRecordTester {
                 comment: None,
                 snapshot_number_of_packs: 100,
                 counted_number_of_packs: None,
+                second_counted_number_of_packs: None,
                 item_id: item.item_row.id,
                 batch: None,
                 expiry_date: None,
@@ -65,6 +75,7 @@ impl SyncRecordTester<Vec<FullStocktake>> for StocktakeRecordTester {
                 cost_price_per_pack: Some(0.0),
                 sell_price_per_pack: Some(0.0),
                 note: None,
+                inventory_adjustment_reason_id: None,
             }],
         }];
         let repo = StocktakeRowRepository::new(connection);
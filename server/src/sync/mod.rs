@@ -1,5 +1,5 @@
-mod actor;
 mod central_data_synchroniser;
+mod quarantine;
 mod remote_data_synchroniser;
 mod sync_api_credentials;
 mod sync_api_v3;
@@ -11,7 +11,7 @@ mod translation_remote;
 #[cfg(test)]
 mod integration_tests;
 
-pub use actor::{get_sync_actors, SyncReceiverActor, SyncSenderActor};
+pub use service::sync_actor::{get_sync_actors, SyncReceiverActor, SyncSenderActor};
 use repository::RepositoryError;
 pub use sync_api_credentials::SyncCredentials;
 pub use sync_api_v5::{SyncApiV5, SyncConnectionError};
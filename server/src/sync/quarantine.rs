@@ -0,0 +1,172 @@
+use chrono::Utc;
+use log::warn;
+use repository::{
+    CentralSyncBufferRow, RemoteSyncBufferAction, RemoteSyncBufferRow, RepositoryError,
+    StorageConnection, SyncErrorRow, SyncErrorRowRepository, SyncErrorSource,
+};
+use service::sync_error::MAX_AUTOMATIC_ATTEMPTS;
+use util::uuid::uuid;
+
+/// Keeps sync records that failed translation or integration in the sync_error table, so they
+/// are not lost when the sync buffer is cleared and can be retried on later syncs.
+pub(crate) struct SyncQuarantine<'a> {
+    repository: SyncErrorRowRepository<'a>,
+    source: SyncErrorSource,
+}
+
+impl<'a> SyncQuarantine<'a> {
+    pub fn central(connection: &'a StorageConnection) -> Self {
+        SyncQuarantine {
+            repository: SyncErrorRowRepository::new(connection),
+            source: SyncErrorSource::Central,
+        }
+    }
+
+    pub fn remote(connection: &'a StorageConnection) -> Self {
+        SyncQuarantine {
+            repository: SyncErrorRowRepository::new(connection),
+            source: SyncErrorSource::Remote,
+        }
+    }
+
+    pub fn quarantine_central(
+        &self,
+        record: &CentralSyncBufferRow,
+        error: String,
+    ) -> Result<(), RepositoryError> {
+        self.quarantine(
+            &record.table_name,
+            &record.record_id,
+            None,
+            &record.data,
+            error,
+        )
+    }
+
+    pub fn quarantine_remote(
+        &self,
+        record: &RemoteSyncBufferRow,
+        error: String,
+    ) -> Result<(), RepositoryError> {
+        self.quarantine(
+            &record.table_name,
+            &record.record_id,
+            Some(record.action.clone()),
+            &record.data,
+            error,
+        )
+    }
+
+    /// Removes a previously quarantined record after it has been imported successfully
+    pub fn release(&self, table_name: &str, record_id: &str) -> Result<(), RepositoryError> {
+        if let Some(sync_error) =
+            self.repository
+                .find_one_by_record(self.source.clone(), table_name, record_id)?
+        {
+            self.repository.delete(&sync_error.id)?;
+        }
+        Ok(())
+    }
+
+    /// Quarantined central records that should be retried, records that are also in `records`
+    /// are skipped since the buffer holds the newer version
+    pub fn retryable_central(
+        &self,
+        records: &[CentralSyncBufferRow],
+    ) -> Result<Vec<CentralSyncBufferRow>, RepositoryError> {
+        let retryable = self
+            .retryable()?
+            .into_iter()
+            .filter(|sync_error| {
+                !records.iter().any(|record| {
+                    record.table_name == sync_error.table_name
+                        && record.record_id == sync_error.record_id
+                })
+            })
+            .map(|sync_error| CentralSyncBufferRow {
+                // The cursor of the original record is not needed to translate it
+                id: 0,
+                table_name: sync_error.table_name,
+                record_id: sync_error.record_id,
+                data: sync_error.data,
+            })
+            .collect();
+        Ok(retryable)
+    }
+
+    /// Quarantined remote records that should be retried, records that are also in `records`
+    /// are skipped since the buffer holds the newer version
+    pub fn retryable_remote(
+        &self,
+        records: &[RemoteSyncBufferRow],
+    ) -> Result<Vec<RemoteSyncBufferRow>, RepositoryError> {
+        let mut retryable = Vec::new();
+        for sync_error in self.retryable()? {
+            if records.iter().any(|record| {
+                record.table_name == sync_error.table_name
+                    && record.record_id == sync_error.record_id
+            }) {
+                continue;
+            }
+            let action = match sync_error.remote_action {
+                Some(action) => action,
+                None => {
+                    warn!("Quarantined remote record without action: {:?}", sync_error);
+                    continue;
+                }
+            };
+            retryable.push(RemoteSyncBufferRow {
+                id: sync_error.id,
+                table_name: sync_error.table_name,
+                record_id: sync_error.record_id,
+                action,
+                data: sync_error.data,
+            });
+        }
+        Ok(retryable)
+    }
+
+    fn retryable(&self) -> Result<Vec<SyncErrorRow>, RepositoryError> {
+        self.repository
+            .find_retryable(self.source.clone(), MAX_AUTOMATIC_ATTEMPTS)
+    }
+
+    fn quarantine(
+        &self,
+        table_name: &str,
+        record_id: &str,
+        remote_action: Option<RemoteSyncBufferAction>,
+        data: &str,
+        error: String,
+    ) -> Result<(), RepositoryError> {
+        warn!("Quarantined {} record {}: {}", table_name, record_id, error);
+        let now = Utc::now().naive_utc();
+        let existing =
+            self.repository
+                .find_one_by_record(self.source.clone(), table_name, record_id)?;
+
+        let sync_error = match existing {
+            Some(existing) => SyncErrorRow {
+                remote_action,
+                data: data.to_string(),
+                error_message: error,
+                attempt_count: existing.attempt_count + 1,
+                last_attempt_datetime: now,
+                ..existing
+            },
+            None => SyncErrorRow {
+                id: uuid(),
+                source: self.source.clone(),
+                table_name: table_name.to_string(),
+                record_id: record_id.to_string(),
+                remote_action,
+                data: data.to_string(),
+                error_message: error,
+                attempt_count: 1,
+                created_datetime: now,
+                last_attempt_datetime: now,
+            },
+        };
+        self.repository.upsert_one(&sync_error)
+    }
+}
@@ -4,9 +4,11 @@ use repository::{
     RemoteSyncBufferAction, RemoteSyncBufferRepository, RemoteSyncBufferRow, RepositoryError,
     StorageConnection,
 };
+use service::sync_status::initial_sync::{InitialSyncLogger, InitialSyncSource};
 use thiserror::Error;
 
 use crate::sync::{
+    quarantine::SyncQuarantine,
     sync_api_v3::{RemotePostRecordV3, SyncTypeV3},
     translation_remote::{
         pull::import_sync_pull_records,
@@ -47,9 +49,13 @@ pub struct RemoteDataSynchroniser {
 #[allow(unused_assignments)]
 impl RemoteDataSynchroniser {
     /// Performs the initial remote data sync (pull) from the central server
+    ///
+    /// The initial pull can be resumed after an interruption: the sync queue is only initialised
+    /// once and pulled records are kept in the remote sync buffer until they are integrated.
     pub async fn initial_pull(
         &self,
         connection: &StorageConnection,
+        mut initial_sync_logger: Option<&mut InitialSyncLogger<'_>>,
     ) -> Result<(), RemoteSyncError> {
         let state = RemoteSyncState::new(connection);
         if state.initial_remote_data_synced()? {
@@ -69,8 +75,10 @@ impl RemoteDataSynchroniser {
             info!("Initialised remote sync records");
         }
 
-        self.pull(connection).await?;
-        self.integrate_records(connection).await?;
+        self.pull(connection, initial_sync_logger.as_deref_mut())
+            .await?;
+        self.integrate_records(connection, initial_sync_logger)
+            .await?;
 
         // Update push cursor after initial sync, i.e. set it to the end of the just received data
         // so we only push new data to the central server
@@ -84,10 +92,22 @@ impl RemoteDataSynchroniser {
         Ok(())
     }
 
-    /// Pull all records from the central server
-    pub async fn pull(&self, connection: &StorageConnection) -> Result<(), RemoteSyncError> {
+    pub fn is_initial_pull_finished(
+        &self,
+        connection: &StorageConnection,
+    ) -> Result<bool, RepositoryError> {
+        RemoteSyncState::new(connection).initial_remote_data_synced()
+    }
+
+    /// Pull all records from the central server, returns the number of pulled records
+    pub async fn pull(
+        &self,
+        connection: &StorageConnection,
+        initial_sync_logger: Option<&mut InitialSyncLogger<'_>>,
+    ) -> Result<u32, RemoteSyncError> {
         info!("Pull remote records...");
-        self.pull_records(connection)
+        let number_of_pulled_records = self
+            .pull_records(connection, initial_sync_logger)
             .await
             .map_err(|error| RemoteSyncError {
                 msg: "Failed to pull remote records",
@@ -95,27 +115,37 @@ impl RemoteDataSynchroniser {
             })?;
         info!("Successfully pulled remote records");
 
-        Ok(())
+        Ok(number_of_pulled_records)
     }
 
-    /// Integrate previously pulled records
+    /// Integrate previously pulled records, returns the number of integrated records
     pub async fn integrate_records(
         &self,
         connection: &StorageConnection,
-    ) -> Result<(), RemoteSyncError> {
+        initial_sync_logger: Option<&mut InitialSyncLogger<'_>>,
+    ) -> Result<u32, RemoteSyncError> {
         info!("Integrate remote records...");
-        self.do_integrate_records(connection)
+        let number_of_integrated_records = self
+            .do_integrate_records(connection, initial_sync_logger)
             .map_err(|error| RemoteSyncError {
                 msg: "Failed to integrate remote records",
                 source: error,
             })?;
         info!("Successfully integrate remote records");
 
-        Ok(())
+        Ok(number_of_integrated_records)
     }
 
     /// Pulls all records and stores them in the RemoteSyncBufferRepository
-    async fn pull_records(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+    ///
+    /// Records are only acknowledged after they have been stored, i.e. unacknowledged records are
+    /// pulled again if the pull is interrupted.
+    async fn pull_records(
+        &self,
+        connection: &StorageConnection,
+        mut initial_sync_logger: Option<&mut InitialSyncLogger<'_>>,
+    ) -> anyhow::Result<u32> {
+        let mut total_pulled_records = 0;
         loop {
             info!("Pulling remote sync records...");
             let sync_batch = self.sync_api_v5.get_queued_records().await?;
@@ -124,33 +154,52 @@ impl RemoteDataSynchroniser {
                 .as_deref()
                 .map(|ref it| it.len())
                 .unwrap_or(0) as u32;
-            let remaining = sync_batch.queue_length - number_of_pulled_records;
+            let remaining = sync_batch
+                .queue_length
+                .saturating_sub(number_of_pulled_records);
             info!(
                 "Pulled {} remote sync records ({} remaining)",
                 number_of_pulled_records, remaining
             );
+            total_pulled_records += number_of_pulled_records;
 
             if let Some(data) = sync_batch.data {
                 let sync_ids: Vec<String> =
                     data.iter().map(|record| record.sync_id.clone()).collect();
 
-                let _ = RemoteSyncBufferRepository::new(connection)
-                    .upsert_many(&remote_sync_batch_records_to_buffer_rows(data)?);
+                let buffer_rows = remote_sync_batch_records_to_buffer_rows(data)?;
+                RemoteSyncBufferRepository::new(connection).upsert_many(&buffer_rows)?;
 
                 info!("Acknowledging remote sync records...");
                 self.sync_api_v5.post_acknowledge_records(sync_ids).await?;
                 info!("Acknowledged remote sync records");
+
+                if let Some(logger) = initial_sync_logger.as_mut() {
+                    logger.pulled(
+                        InitialSyncSource::Remote,
+                        buffer_rows.iter().map(|row| row.table_name.as_str()),
+                    )?;
+                }
+            }
+
+            if let Some(logger) = initial_sync_logger.as_mut() {
+                logger.set_remaining(InitialSyncSource::Remote, remaining)?;
             }
 
-            if remaining <= 0 {
+            if remaining == 0 {
                 break;
             }
         }
 
-        Ok(())
+        Ok(total_pulled_records)
     }
 
-    fn do_integrate_records(&self, connection: &StorageConnection) -> anyhow::Result<()> {
+    /// Records are integrated table by table in topological order
+    fn do_integrate_records(
+        &self,
+        connection: &StorageConnection,
+        mut initial_sync_logger: Option<&mut InitialSyncLogger<'_>>,
+    ) -> anyhow::Result<u32> {
         let remote_sync_buffer_repository = RemoteSyncBufferRepository::new(&connection);
 
         let mut records: Vec<RemoteSyncBufferRow> = Vec::new();
@@ -168,21 +217,47 @@ impl RemoteDataSynchroniser {
             records.append(&mut buffer_rows);
         }
 
+        let mut quarantined_records =
+            SyncQuarantine::remote(connection).retryable_remote(&records)?;
+        info!(
+            "Retrying {} quarantined remote sync records",
+            quarantined_records.len()
+        );
+        records.append(&mut quarantined_records);
+
         info!("Importing {} remote sync buffer records...", records.len());
-        import_sync_pull_records(connection, &records)?;
+        let mut number_of_integrated_records = 0;
+        for table_name in REMOTE_TRANSLATION_RECORDS {
+            let (table_records, remaining_records): (Vec<_>, Vec<_>) = records
+                .into_iter()
+                .partition(|record| record.table_name == *table_name);
+            records = remaining_records;
+            if table_records.is_empty() {
+                continue;
+            }
+
+            let integrated = import_sync_pull_records(connection, &table_records)?;
+            number_of_integrated_records += integrated;
+
+            if let Some(logger) = initial_sync_logger.as_mut() {
+                logger.integrated(InitialSyncSource::Remote, table_name, integrated)?;
+            }
+        }
         info!("Successfully Imported remote sync buffer records",);
 
         info!("Clearing remote sync buffer");
         remote_sync_buffer_repository.remove_all()?;
         info!("Successfully cleared remote sync buffer");
 
-        Ok(())
+        Ok(number_of_integrated_records)
     }
 
     // push
 
-    pub async fn push_changes(&self, connection: &StorageConnection) -> Result<(), anyhow::Error> {
+    /// Returns the number of pushed records
+    pub async fn push_changes(&self, connection: &StorageConnection) -> Result<u32, anyhow::Error> {
         let changelog = ChangelogRowRepository::new(connection);
+        let mut total_pushed_records = 0;
 
         const BATCH_SIZE: u32 = 1000;
         let state = RemoteSyncState::new(connection);
@@ -209,9 +284,10 @@ impl RemoteDataSynchroniser {
                 "Remote push: {} records pushed to central server",
                 records.len()
             );
+            total_pushed_records += records.len() as u32;
         }
 
-        Ok(())
+        Ok(total_pushed_records)
     }
 }
 
@@ -373,7 +449,7 @@ mod tests {
             site_id,
             central_server_site_id,
         };
-        sync.do_integrate_records(&connection)
+        sync.do_integrate_records(&connection, None)
             .expect("Failed to integrate remote records");
 
         check_records_against_database(&connection, test_records);
@@ -1,16 +1,18 @@
-use std::time::Duration;
-
-use repository::StorageConnectionManager;
+use repository::{StorageConnection, StorageConnectionManager, SyncLogPhase};
 
+use log::warn;
 use reqwest::{Client, Url};
-use service::sync_settings::SyncSettings;
+use service::{
+    sync_actor::{SyncOutcome, SyncSchedule},
+    sync_settings::SyncSettings,
+    sync_status::{initial_sync::InitialSyncLogger, logger::SyncLogger},
+};
 
 use super::{
     central_data_synchroniser::{CentralDataSynchroniser, CentralSyncError},
-    get_sync_actors,
     remote_data_synchroniser::RemoteDataSynchroniser,
     sync_api_v3::SyncApiV3,
-    SyncApiV5, SyncCredentials, SyncReceiverActor, SyncSenderActor,
+    SyncApiV5, SyncCredentials, SyncReceiverActor,
 };
 
 pub struct Synchroniser {
@@ -73,56 +75,136 @@ impl Synchroniser {
         })
     }
 
+    /// Pulls central data and, if not done yet, the initial remote data
+    ///
+    /// The progress of the initial remote data sync is recorded (see [InitialSyncLogger]). If the
+    /// initial sync is interrupted, e.g. by an error or a restart, it resumes where it stopped.
     pub async fn initial_pull(&self) -> anyhow::Result<()> {
         let connection = self
             .connection_manager
             .connection()
             .map_err(|source| CentralSyncError::DBConnectionError { source })?;
 
+        if self.remote_data.is_initial_pull_finished(&connection)? {
+            self.central_data
+                .pull_and_integrate_records(&connection, None)
+                .await?;
+            return Ok(());
+        }
+
+        let mut logger = InitialSyncLogger::start(&connection)?;
+        match self.initial_pull_phases(&connection, &mut logger).await {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                logger.error(format!("{:#}", error))?;
+                Err(error)
+            }
+        }
+    }
+
+    async fn initial_pull_phases(
+        &self,
+        connection: &StorageConnection,
+        logger: &mut InitialSyncLogger<'_>,
+    ) -> anyhow::Result<()> {
         // first pull data from the central server
         self.central_data
-            .pull_and_integrate_records(&connection)
+            .pull_and_integrate_records(connection, Some(&mut *logger))
             .await?;
 
-        self.remote_data.initial_pull(&connection).await?;
+        self.remote_data
+            .initial_pull(connection, Some(logger))
+            .await?;
 
         Ok(())
     }
 
     /// Sync must not be called concurrently (e.g. sync cursors are fetched/updated without DB tx)
-    pub async fn sync(&self) -> anyhow::Result<()> {
+    ///
+    /// Progress, record counts and errors of the sync run are recorded in the sync log.
+    /// A failed push doesn't abort the sync, central data is still pulled and integrated but remote
+    /// data isn't. In this case a partial failure is returned.
+    pub async fn sync(&self) -> anyhow::Result<SyncOutcome> {
         let connection = self
             .connection_manager
             .connection()
             .map_err(|source| CentralSyncError::DBConnectionError { source })?;
 
+        let mut logger = SyncLogger::start(&connection)?;
+        match self.sync_phases(&connection, &mut logger).await {
+            Ok(None) => {
+                logger.success()?;
+                Ok(SyncOutcome::Success)
+            }
+            Ok(Some(push_error)) => {
+                logger.error(format!("Failed to push: {:#}", push_error))?;
+                Ok(SyncOutcome::PartialFailure)
+            }
+            Err(error) => {
+                logger.error(format!("{:#}", error))?;
+                Err(error)
+            }
+        }
+    }
+
+    /// Returns the push error if only the push failed
+    async fn sync_phases(
+        &self,
+        connection: &StorageConnection,
+        logger: &mut SyncLogger<'_>,
+    ) -> anyhow::Result<Option<anyhow::Error>> {
         // First push before pulling. This avoids problems with the existing central server
         // implementation...
-        self.remote_data.push_changes(&connection).await?;
-        self.remote_data.pull(&connection).await?;
+        logger.start_phase(SyncLogPhase::Push)?;
+        let push_error = match self.remote_data.push_changes(connection).await {
+            Ok(pushed) => {
+                logger.finish_phase(pushed)?;
+                None
+            }
+            Err(error) => {
+                warn!("Failed to push, only pulling central data: {:#}", error);
+                Some(error)
+            }
+        };
+
+        // Remote data is only pulled once all local changes are pushed. Otherwise pulled remote
+        // records could overwrite unpushed local changes, which would then be lost. Remote records
+        // stay queued on the central server until the push succeeds.
+        if push_error.is_none() {
+            logger.start_phase(SyncLogPhase::RemotePull)?;
+            let pulled = self.remote_data.pull(connection, None).await?;
+            logger.finish_phase(pulled)?;
+        }
 
         // Check if there is new data on the central server. Do this after pulling the remote data
         // in case the just pulled remote data requires the new central data.
-        self.central_data
-            .pull_and_integrate_records(&connection)
+        logger.start_phase(SyncLogPhase::CentralPull)?;
+        let pulled = self
+            .central_data
+            .pull_central_records(connection, None)
             .await?;
+        logger.finish_phase(pulled)?;
 
-        self.remote_data.integrate_records(&connection).await?;
-        Ok(())
+        logger.start_phase(SyncLogPhase::Integrate)?;
+        let mut integrated = self
+            .central_data
+            .integrate_central_records(connection, None)
+            .await?;
+        if push_error.is_none() {
+            integrated += self.remote_data.integrate_records(connection, None).await?;
+        }
+        logger.finish_phase(integrated)?;
+
+        Ok(push_error)
     }
 
     /// Runs the continues sync process (not suppose to return)
-    pub async fn run(&mut self) {
-        let (mut sync_sender, mut sync_receiver): (SyncSenderActor, SyncReceiverActor) =
-            get_sync_actors();
-
-        tokio::select! {
-            () = async {
-              sync_sender.schedule_send(Duration::from_secs(self.settings.interval_sec)).await;
-            } => unreachable!("Sync receiver unexpectedly died!?"),
-            () = async {
-                sync_receiver.listen(self).await;
-            } => unreachable!("Sync scheduler unexpectedly died!?"),
-        };
+    ///
+    /// Syncs are scheduled according to the outcome of the previous sync (see [SyncSchedule]).
+    /// Besides the scheduled syncs, a sync can be requested through the sync sender, e.g. a manual
+    /// sync triggered from the API.
+    pub async fn run(&self, mut sync_receiver: SyncReceiverActor) {
+        let schedule = SyncSchedule::new(&self.settings);
+        sync_receiver.listen(schedule, || self.sync()).await;
     }
 }
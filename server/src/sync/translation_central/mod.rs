@@ -3,6 +3,7 @@ mod list_master;
 mod list_master_line;
 mod list_master_name_join;
 mod name;
+mod options;
 mod store;
 pub mod test_data;
 mod unit;
@@ -12,20 +13,21 @@ use crate::sync::translation_central::{
     list_master_name_join::MasterListNameJoinTranslation,
 };
 use repository::{
-    CentralSyncBufferRow, ItemRow, ItemRowRepository, MasterListLineRow,
-    MasterListLineRowRepository, MasterListNameJoinRepository, MasterListNameJoinRow,
-    MasterListRow, MasterListRowRepository, NameRow, NameRowRepository, RepositoryError,
-    StorageConnection, StoreRow, StoreRowRepository, TransactionError, UnitRow, UnitRowRepository,
+    CentralSyncBufferRow, InventoryAdjustmentReasonRow, InventoryAdjustmentReasonRowRepository,
+    ItemRow, ItemRowRepository, MasterListLineRow, MasterListLineRowRepository,
+    MasterListNameJoinRepository, MasterListNameJoinRow, MasterListRow, MasterListRowRepository,
+    NameRow, NameRowRepository, RepositoryError, StorageConnection, StoreRow, StoreRowRepository,
+    TransactionError, UnitRow, UnitRowRepository,
 };
 
-use log::{info, warn};
+use log::info;
 
 use self::{
-    list_master::MasterListTranslation, name::NameTranslation, store::StoreTranslation,
-    unit::UnitTranslation,
+    list_master::MasterListTranslation, name::NameTranslation, options::OptionsTranslation,
+    store::StoreTranslation, unit::UnitTranslation,
 };
 
-use super::{SyncImportError, SyncTranslationError};
+use super::{quarantine::SyncQuarantine, SyncImportError, SyncTranslationError};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum IntegrationUpsertRecord {
@@ -36,11 +38,14 @@ pub enum IntegrationUpsertRecord {
     MasterList(MasterListRow),
     MasterListLine(MasterListLineRow),
     MasterListNameJoin(MasterListNameJoinRow),
+    InventoryAdjustmentReason(InventoryAdjustmentReasonRow),
 }
 
+/// Translated record together with the sync record it has been translated from
 #[derive(Debug)]
-struct IntegrationRecord {
-    pub upserts: Vec<IntegrationUpsertRecord>,
+struct TranslatedRecord<'a> {
+    pub sync_record: &'a CentralSyncBufferRow,
+    pub upsert: IntegrationUpsertRecord,
 }
 
 pub trait CentralPushTranslation {
@@ -50,12 +55,11 @@ pub trait CentralPushTranslation {
     ) -> Result<Option<IntegrationUpsertRecord>, anyhow::Error>;
 }
 
-/// Translates sync records into the local DB schema.
-/// Translated records are added to integration_records.
+/// Translates a sync record into the local DB schema.
+/// Returns None if the record is not translated, i.e. if it is ignored.
 fn do_translation(
     sync_record: &CentralSyncBufferRow,
-    records: &mut IntegrationRecord,
-) -> Result<(), SyncTranslationError> {
+) -> Result<Option<IntegrationUpsertRecord>, SyncTranslationError> {
     let translations: Vec<Box<dyn CentralPushTranslation>> = vec![
         Box::new(NameTranslation {}),
         Box::new(UnitTranslation {}),
@@ -64,22 +68,18 @@ fn do_translation(
         Box::new(MasterListTranslation {}),
         Box::new(MasterListLineTranslation {}),
         Box::new(MasterListNameJoinTranslation {}),
+        Box::new(OptionsTranslation {}),
     ];
     for translation in translations {
         match translation.try_translate(sync_record) {
-            Ok(Some(result)) => {
-                records.upserts.push(result);
-                return Ok(());
-            }
-            Err(error) => warn!(
-                "Failed to translate ({}): {:?}",
-                SyncTranslationError {
+            Ok(Some(result)) => return Ok(Some(result)),
+            Err(error) => {
+                return Err(SyncTranslationError {
                     table_name: sync_record.table_name.clone(),
                     source: error,
                     record: format!("{:?}", sync_record.data),
-                },
-                sync_record
-            ),
+                })
+            }
             _ => {
                 log::info!(
                     "Ignore central record: table: \"{}\", record id: {}",
@@ -90,7 +90,7 @@ fn do_translation(
         };
     }
 
-    Ok(())
+    Ok(None)
 }
 
 pub const TRANSLATION_RECORD_NAME: &str = "name";
@@ -100,6 +100,7 @@ pub const TRANSLATION_RECORD_STORE: &str = "store";
 pub const TRANSLATION_RECORD_LIST_MASTER: &str = "list_master";
 pub const TRANSLATION_RECORD_LIST_MASTER_LINE: &str = "list_master_line";
 pub const TRANSLATION_RECORD_LIST_MASTER_NAME_JOIN: &str = "list_master_name_join";
+pub const TRANSLATION_RECORD_OPTIONS: &str = "options";
 
 /// Returns a list of records that can be translated. The list is topologically sorted, i.e. items
 /// at the beginning of the list don't rely on later items to be translated first.
@@ -111,32 +112,44 @@ pub const TRANSLATION_RECORDS: &[&str] = &[
     TRANSLATION_RECORD_LIST_MASTER,
     TRANSLATION_RECORD_LIST_MASTER_LINE,
     TRANSLATION_RECORD_LIST_MASTER_NAME_JOIN,
+    TRANSLATION_RECORD_OPTIONS,
 ];
 
 /// Imports sync records and writes them to the DB
 /// If needed data records are translated to the local DB schema.
+/// Records that fail translation or integration are quarantined, returns the number of integrated
+/// records.
 pub async fn import_sync_records(
     connection: &StorageConnection,
     records: &Vec<CentralSyncBufferRow>,
-) -> Result<(), SyncImportError> {
-    let mut integration_records = IntegrationRecord {
-        upserts: Vec::new(),
-    };
+) -> Result<u32, SyncImportError> {
+    let quarantine = SyncQuarantine::central(connection);
+    let mut translated_records = Vec::new();
 
     info!(
         "Translating {} central sync buffer records...",
         records.len()
     );
     for record in records {
-        do_translation(&record, &mut integration_records)?;
+        match do_translation(&record) {
+            Ok(Some(upsert)) => translated_records.push(TranslatedRecord {
+                sync_record: record,
+                upsert,
+            }),
+            Ok(None) => {}
+            Err(error) => quarantine
+                .quarantine_central(record, format!("Failed to translate: {:#}", error.source))
+                .map_err(|error| SyncImportError::as_integration_error(error, ""))?,
+        }
     }
     info!("Succesfully translated central sync buffer records");
 
     info!("Storing integration records...");
-    store_integration_records(connection, &integration_records).await?;
+    let number_of_integrated_records =
+        store_integration_records(connection, &translated_records).await?;
     info!("Successfully stored integration records");
 
-    Ok(())
+    Ok(number_of_integrated_records)
 }
 
 fn integrate_record(
@@ -157,33 +170,43 @@ fn integrate_record(
         IntegrationUpsertRecord::MasterListNameJoin(record) => {
             MasterListNameJoinRepository::new(con).upsert_one(record)
         }
+        IntegrationUpsertRecord::InventoryAdjustmentReason(record) => {
+            InventoryAdjustmentReasonRowRepository::new(con).upsert_one(record)
+        }
     }
 }
 
 async fn store_integration_records(
     connection: &StorageConnection,
-    integration_records: &IntegrationRecord,
-) -> Result<(), SyncImportError> {
+    translated_records: &Vec<TranslatedRecord<'_>>,
+) -> Result<u32, SyncImportError> {
     connection
         .transaction_sync(|con| {
-            for record in &integration_records.upserts {
+            let quarantine = SyncQuarantine::central(con);
+            let mut number_of_integrated_records = 0;
+            for record in translated_records {
+                let sync_record = record.sync_record;
                 // Integrate every record in a sub transaction. This is mainly for Postgres where the
                 // whole transaction fails when there is a DB error (not a problem in sqlite).
-                let sub_result =
-                    con.transaction_sync_etc(|sub_tx| integrate_record(record, sub_tx), false);
+                let sub_result = con
+                    .transaction_sync_etc(|sub_tx| integrate_record(&record.upsert, sub_tx), false);
                 match sub_result {
-                    Ok(_) => Ok(()),
+                    Ok(_) => {
+                        number_of_integrated_records += 1;
+                        quarantine.release(&sync_record.table_name, &sync_record.record_id)
+                    }
+                    // e.g. the central record this record refers to hasn't been synced yet
                     Err(TransactionError::Inner(err @ RepositoryError::ForeignKeyViolation(_))) => {
-                        warn!("Failed to import ({}): {:?}", err, record);
-                        Ok(())
+                        quarantine.quarantine_central(
+                            sync_record,
+                            format!("Failed to integrate: {}", err),
+                        )
                     }
-                    Err(err) => Err(SyncImportError::as_integration_error(
-                        RepositoryError::from(err),
-                        "",
-                    )),
-                }?;
+                    Err(err) => Err(RepositoryError::from(err)),
+                }
+                .map_err(|error| SyncImportError::as_integration_error(error, ""))?;
             }
-            Ok(())
+            Ok(number_of_integrated_records)
         })
         .map_err(|error| match error {
             TransactionError::Transaction { msg, level } => SyncImportError::as_integration_error(
@@ -196,10 +219,11 @@ async fn store_integration_records(
 
 #[cfg(test)]
 mod tests {
-    use crate::sync::translation_central::{
-        import_sync_records, test_data::store::get_test_store_records,
+    use crate::sync::{
+        quarantine::SyncQuarantine,
+        translation_central::{import_sync_records, test_data::store::get_test_store_records},
     };
-    use repository::test_db;
+    use repository::{test_db, CentralSyncBufferRow, SyncErrorRowRepository, SyncErrorSource};
 
     use super::test_data::{
         check_records_against_database, extract_sync_buffer_rows,
@@ -208,6 +232,7 @@ mod tests {
         master_list_line::get_test_master_list_line_records,
         master_list_name_join::get_test_master_list_name_join_records,
         name::{get_test_name_records, get_test_name_upsert_records},
+        options::{get_test_options_records, get_test_options_upsert_records},
         unit::{get_test_unit_records, get_test_unit_upsert_records},
     };
 
@@ -226,6 +251,7 @@ mod tests {
         records.append(&mut get_test_master_list_records());
         records.append(&mut get_test_master_list_line_records());
         records.append(&mut get_test_master_list_name_join_records());
+        records.append(&mut get_test_options_records());
 
         import_sync_records(&connection, &extract_sync_buffer_rows(&records))
             .await
@@ -246,11 +272,13 @@ mod tests {
         init_records.append(&mut get_test_unit_records());
         init_records.append(&mut get_test_item_records());
         init_records.append(&mut get_test_master_list_records());
+        init_records.append(&mut get_test_options_records());
         let mut upsert_records = Vec::new();
         upsert_records.append(&mut get_test_unit_upsert_records());
         upsert_records.append(&mut get_test_item_upsert_records());
         upsert_records.append(&mut get_test_name_upsert_records());
         upsert_records.append(&mut get_test_master_list_upsert_records());
+        upsert_records.append(&mut get_test_options_upsert_records());
 
         let mut records = Vec::new();
         records.append(&mut init_records.iter().cloned().collect());
@@ -263,4 +291,53 @@ mod tests {
         // Asserts inside this method, to avoid repetition
         check_records_against_database(&connection, upsert_records).await;
     }
+
+    #[actix_rt::test]
+    async fn test_quarantine_failed_records() {
+        let settings = test_db::get_test_db_settings("omsupply-database-translation-quarantine");
+        let connection_manager = test_db::setup(&settings).await;
+        let connection = connection_manager.connection().unwrap();
+        let repository = SyncErrorRowRepository::new(&connection);
+
+        let mut records = Vec::new();
+        records.append(&mut get_test_unit_records());
+        records.append(&mut get_test_item_records());
+        let records = extract_sync_buffer_rows(&records);
+        let item = records
+            .iter()
+            .find(|record| record.table_name == "item")
+            .unwrap()
+            .clone();
+        let broken_item = CentralSyncBufferRow {
+            data: "{}".to_string(),
+            ..item.clone()
+        };
+
+        // Failed translation is quarantined
+        let integrated = import_sync_records(&connection, &vec![broken_item.clone()])
+            .await
+            .unwrap();
+        assert_eq!(integrated, 0);
+        let sync_error = repository
+            .find_one_by_record(SyncErrorSource::Central, "item", &item.record_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(sync_error.data, "{}");
+        assert_eq!(sync_error.attempt_count, 1);
+        assert!(sync_error.error_message.starts_with("Failed to translate"));
+
+        // Quarantined record is retried
+        let quarantine = SyncQuarantine::central(&connection);
+        let retryable = quarantine.retryable_central(&Vec::new()).unwrap();
+        assert_eq!(retryable.len(), 1);
+        import_sync_records(&connection, &retryable).await.unwrap();
+        let sync_error = repository.find_one_by_id(&sync_error.id).unwrap().unwrap();
+        assert_eq!(sync_error.attempt_count, 2);
+
+        // Newer version in the sync buffer replaces the quarantined record
+        assert_eq!(quarantine.retryable_central(&records).unwrap(), Vec::new());
+        let integrated = import_sync_records(&connection, &records).await.unwrap();
+        assert_eq!(integrated, records.len() as u32);
+        assert_eq!(repository.find_one_by_id(&sync_error.id).unwrap(), None);
+    }
 }
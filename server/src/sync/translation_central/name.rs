@@ -1,18 +1,70 @@
-use crate::sync::translation_central::TRANSLATION_RECORD_NAME;
-use repository::{CentralSyncBufferRow, NameRow};
+use crate::sync::{
+    translation_central::TRANSLATION_RECORD_NAME,
+    translation_remote::{empty_str_as_option, zero_date_as_option},
+};
+use chrono::NaiveDate;
+use repository::{CentralSyncBufferRow, Gender, NameRow, NameType};
 
 use serde::Deserialize;
 
 use super::{CentralPushTranslation, IntegrationUpsertRecord};
 
+#[derive(Deserialize, Debug, PartialEq)]
+pub enum LegacyNameType {
+    #[serde(rename = "facility")]
+    Facility,
+    #[serde(rename = "patient")]
+    Patient,
+    #[serde(rename = "build")]
+    Build,
+    #[serde(rename = "invad")]
+    Invad,
+    #[serde(rename = "repack")]
+    Repack,
+    #[serde(rename = "store")]
+    Store,
+    #[serde(other)]
+    Others,
+}
+
 #[allow(non_snake_case)]
 #[derive(Deserialize)]
 pub struct LegacyNameRow {
     ID: String,
     name: String,
     code: String,
+    #[serde(rename = "type")]
+    #[serde(default = "default_name_type")]
+    r#type: LegacyNameType,
     customer: bool,
     supplier: bool,
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    first: Option<String>,
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    last: Option<String>,
+    #[serde(default)]
+    female: bool,
+    #[serde(deserialize_with = "zero_date_as_option")]
+    #[serde(default)]
+    date_of_birth: Option<NaiveDate>,
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    national_health_number: Option<String>,
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    phone: Option<String>,
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    email: Option<String>,
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    bill_address1: Option<String>,
+}
+
+fn default_name_type() -> LegacyNameType {
+    LegacyNameType::Facility
 }
 
 pub struct NameTranslation {}
@@ -27,12 +79,40 @@ impl CentralPushTranslation for NameTranslation {
         }
 
         let data = serde_json::from_str::<LegacyNameRow>(&sync_record.data)?;
+        let r#type = match data.r#type {
+            LegacyNameType::Facility => NameType::Facility,
+            LegacyNameType::Patient => NameType::Patient,
+            LegacyNameType::Build => NameType::Build,
+            LegacyNameType::Invad => NameType::Invad,
+            LegacyNameType::Repack => NameType::Repack,
+            LegacyNameType::Store => NameType::Store,
+            LegacyNameType::Others => NameType::Others,
+        };
+        // The legacy record only has a female flag, which is only meaningful for patients
+        let gender = match r#type {
+            NameType::Patient => Some(if data.female {
+                Gender::Female
+            } else {
+                Gender::Male
+            }),
+            _ => None,
+        };
+
         Ok(Some(IntegrationUpsertRecord::Name(NameRow {
             id: data.ID.to_string(),
             name: data.name.to_string(),
             code: data.code.to_string(),
+            r#type,
             is_customer: data.customer,
             is_supplier: data.supplier,
+            first_name: data.first,
+            last_name: data.last,
+            gender,
+            date_of_birth: data.date_of_birth,
+            national_health_number: data.national_health_number,
+            phone: data.phone,
+            email: data.email,
+            address: data.bill_address1,
             legacy_record: sync_record.data.clone(),
         })))
     }
@@ -0,0 +1,141 @@
+use crate::sync::translation_central::TRANSLATION_RECORD_OPTIONS;
+use repository::{CentralSyncBufferRow, InventoryAdjustmentReasonRow, InventoryAdjustmentType};
+
+use serde::Deserialize;
+
+use super::{CentralPushTranslation, IntegrationUpsertRecord};
+
+#[derive(Deserialize, Debug, PartialEq)]
+pub enum LegacyOptionsType {
+    #[serde(rename = "positiveInventoryAdjustment")]
+    PositiveInventoryAdjustment,
+    #[serde(rename = "negativeInventoryAdjustment")]
+    NegativeInventoryAdjustment,
+    /// Other options, e.g. return or requisition variance reasons, aren't used (yet)
+    #[serde(other)]
+    Others,
+}
+
+#[allow(non_snake_case)]
+#[derive(Deserialize)]
+pub struct LegacyOptionsRow {
+    ID: String,
+    #[serde(rename = "type")]
+    r#type: LegacyOptionsType,
+    isActive: bool,
+    title: String,
+}
+
+/// The legacy options table holds the central lists of reasons, only the inventory adjustment
+/// reasons are translated
+pub struct OptionsTranslation {}
+impl CentralPushTranslation for OptionsTranslation {
+    fn try_translate(
+        &self,
+        sync_record: &CentralSyncBufferRow,
+    ) -> Result<Option<IntegrationUpsertRecord>, anyhow::Error> {
+        let table_name = TRANSLATION_RECORD_OPTIONS;
+        if sync_record.table_name != table_name {
+            return Ok(None);
+        }
+
+        let data = serde_json::from_str::<LegacyOptionsRow>(&sync_record.data)?;
+        let r#type = match data.r#type {
+            LegacyOptionsType::PositiveInventoryAdjustment => InventoryAdjustmentType::Positive,
+            LegacyOptionsType::NegativeInventoryAdjustment => InventoryAdjustmentType::Negative,
+            LegacyOptionsType::Others => return Ok(None),
+        };
+
+        Ok(Some(IntegrationUpsertRecord::InventoryAdjustmentReason(
+            InventoryAdjustmentReasonRow {
+                id: data.ID,
+                r#type,
+                is_active: data.isActive,
+                reason: data.title,
+            },
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CentralPushTranslation;
+    use crate::sync::translation_central::{
+        import_sync_records,
+        options::OptionsTranslation,
+        test_data::{
+            extract_sync_buffer_rows,
+            options::{get_test_options_records, get_test_options_upsert_records},
+            TestSyncDataRecord,
+        },
+        IntegrationUpsertRecord,
+    };
+    use repository::{test_db, InventoryAdjustmentType};
+    use service::inventory_adjustment_reason::validate::{
+        check_reason_is_required, check_reason_is_valid,
+    };
+
+    #[test]
+    fn test_options_translation() {
+        for record in get_test_options_records() {
+            match record.translated_record {
+                TestSyncDataRecord::InventoryAdjustmentReason(translated_record) => {
+                    assert_eq!(
+                        OptionsTranslation {}
+                            .try_translate(&record.central_sync_buffer_row)
+                            .unwrap(),
+                        translated_record
+                            .map(|r| (IntegrationUpsertRecord::InventoryAdjustmentReason(r))),
+                        "{}",
+                        record.identifier
+                    )
+                }
+                _ => panic!("Testing wrong record type {:#?}", record.translated_record),
+            }
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_synced_reasons_are_required() {
+        let settings = test_db::get_test_db_settings("omsupply-database-options-reasons");
+        let connection_manager = test_db::setup(&settings).await;
+        let connection = connection_manager.connection().unwrap();
+
+        // No reasons configured
+        assert!(
+            !check_reason_is_required(&connection, &InventoryAdjustmentType::Positive).unwrap()
+        );
+        assert!(
+            !check_reason_is_required(&connection, &InventoryAdjustmentType::Negative).unwrap()
+        );
+
+        let records = get_test_options_records();
+        import_sync_records(&connection, &extract_sync_buffer_rows(&records))
+            .await
+            .unwrap();
+        assert!(check_reason_is_required(&connection, &InventoryAdjustmentType::Positive).unwrap());
+        assert!(check_reason_is_required(&connection, &InventoryAdjustmentType::Negative).unwrap());
+        assert!(check_reason_is_valid(
+            &connection,
+            "POSITIVE_REASON",
+            &InventoryAdjustmentType::Positive
+        )
+        .unwrap());
+
+        // Deactivating the only negative reason on central removes the requirement
+        let records = get_test_options_upsert_records();
+        import_sync_records(&connection, &extract_sync_buffer_rows(&records))
+            .await
+            .unwrap();
+        assert!(check_reason_is_required(&connection, &InventoryAdjustmentType::Positive).unwrap());
+        assert!(
+            !check_reason_is_required(&connection, &InventoryAdjustmentType::Negative).unwrap()
+        );
+        assert!(!check_reason_is_valid(
+            &connection,
+            "NEGATIVE_REASON",
+            &InventoryAdjustmentType::Negative
+        )
+        .unwrap());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::sync::translation_central::test_data::{TestSyncDataRecord, TestSyncRecord};
-use repository::{CentralSyncBufferRow, NameRow};
+use chrono::NaiveDate;
+use repository::{CentralSyncBufferRow, Gender, NameRow, NameType};
 
 const NAME_1: (&'static str, &'static str) = (
     "1FB32324AF8049248D929CFB35F255BA",
@@ -283,6 +284,100 @@ const NAME_3: (&'static str, &'static str) = (
 }"#,
 );
 
+const NAME_4: (&'static str, &'static str) = (
+    "A5D4A7B5F3F44D7CBE5F1E1D5A0E6C01",
+    r#"{
+    "ID": "A5D4A7B5F3F44D7CBE5F1E1D5A0E6C01",
+    "name": "Doe, Jane",
+    "fax": "",
+    "phone": "0211234567",
+    "customer": true,
+    "bill_address1": "1 Main Street",
+    "bill_address2": "",
+    "supplier": false,
+    "charge code": "",
+    "margin": 0,
+    "comment": "",
+    "currency_ID": "",
+    "country": "",
+    "freightfac": 0,
+    "email": "jane@doe.com",
+    "custom1": "",
+    "code": "PAT1",
+    "last": "Doe",
+    "first": "Jane",
+    "title": "",
+    "female": true,
+    "date_of_birth": "1990-05-21",
+    "overpayment": 0,
+    "group_ID": "",
+    "hold": false,
+    "ship_address1": "",
+    "ship_address2": "",
+    "url": "",
+    "barcode": "",
+    "postal_address1": "",
+    "postal_address2": "",
+    "category1_ID": "",
+    "region_ID": "",
+    "type": "patient",
+    "price_category": "",
+    "flag": "",
+    "manufacturer": false,
+    "print_invoice_alphabetical": false,
+    "custom2": "",
+    "custom3": "",
+    "default_order_days": 0,
+    "connection_type": 0,
+    "PATIENT_PHOTO": "[object Picture]",
+    "NEXT_OF_KIN_ID": "",
+    "POBOX": "",
+    "ZIP": 0,
+    "middle": "",
+    "preferred": false,
+    "Blood_Group": "",
+    "marital_status": "",
+    "Benchmark": false,
+    "next_of_kin_relative": "",
+    "mother_id": "",
+    "postal_address3": "",
+    "postal_address4": "",
+    "bill_address3": "",
+    "bill_address4": "",
+    "ship_address3": "",
+    "ship_address4": "",
+    "ethnicity_ID": "",
+    "occupation_ID": "",
+    "religion_ID": "",
+    "national_health_number": "NHN123",
+    "Master_RTM_Supplier_Code": 0,
+    "ordering_method": "",
+    "donor": false,
+    "latitude": 0,
+    "longitude": 0,
+    "Master_RTM_Supplier_name": "",
+    "category2_ID": "",
+    "category3_ID": "",
+    "category4_ID": "",
+    "category5_ID": "",
+    "category6_ID": "",
+    "bill_address5": "",
+    "bill_postal_zip_code": "",
+    "postal_address5": "",
+    "postal_zip_code": "",
+    "ship_address5": "",
+    "ship_postal_zip_code": "",
+    "supplying_store_id": "",
+    "license_number": "",
+    "license_expiry": "0000-00-00",
+    "has_current_license": false,
+    "custom_data": null,
+    "maximum_credit": 0,
+    "nationality_ID": "",
+    "created_date": "0000-00-00"
+}"#,
+);
+
 const NAME_UPSERT_1: (&'static str, &'static str) = (
     "1FB32324AF8049248D929CFB35F255BA",
     r#"{
@@ -387,8 +482,17 @@ pub fn get_test_name_records() -> Vec<TestSyncRecord> {
                 id: NAME_1.0.to_owned(),
                 name: "General".to_owned(),
                 code: "GEN".to_owned(),
+                r#type: NameType::Store,
                 is_supplier: true,
                 is_customer: true,
+                first_name: None,
+                last_name: None,
+                gender: None,
+                date_of_birth: None,
+                national_health_number: None,
+                phone: None,
+                email: None,
+                address: None,
                 legacy_record: NAME_1.1.to_owned(),
             })),
             identifier: "General",
@@ -404,8 +508,17 @@ pub fn get_test_name_records() -> Vec<TestSyncRecord> {
                 id: NAME_2.0.to_owned(),
                 name: "Birch Store".to_owned(),
                 code: "SNA".to_owned(),
+                r#type: NameType::Facility,
                 is_customer: true,
                 is_supplier: false,
+                first_name: None,
+                last_name: None,
+                gender: None,
+                date_of_birth: None,
+                national_health_number: None,
+                phone: None,
+                email: None,
+                address: Some("234 Evil Street".to_owned()),
                 legacy_record: NAME_2.1.to_owned(),
             })),
             identifier: "Birch Store",
@@ -421,8 +534,17 @@ pub fn get_test_name_records() -> Vec<TestSyncRecord> {
                 id: NAME_3.0.to_owned(),
                 name: "Birch Store 2".to_owned(),
                 code: "SNA".to_owned(),
+                r#type: NameType::Facility,
                 is_customer: true,
                 is_supplier: false,
+                first_name: None,
+                last_name: None,
+                gender: None,
+                date_of_birth: None,
+                national_health_number: None,
+                phone: None,
+                email: None,
+                address: Some("234 Evil Street".to_owned()),
                 legacy_record: NAME_3.1.to_owned(),
             })),
             identifier: "Birch Store",
@@ -433,6 +555,32 @@ pub fn get_test_name_records() -> Vec<TestSyncRecord> {
                 data: NAME_3.1.to_owned(),
             },
         },
+        TestSyncRecord {
+            translated_record: TestSyncDataRecord::Name(Some(NameRow {
+                id: NAME_4.0.to_owned(),
+                name: "Doe, Jane".to_owned(),
+                code: "PAT1".to_owned(),
+                r#type: NameType::Patient,
+                is_customer: true,
+                is_supplier: false,
+                first_name: Some("Jane".to_owned()),
+                last_name: Some("Doe".to_owned()),
+                gender: Some(Gender::Female),
+                date_of_birth: Some(NaiveDate::from_ymd(1990, 5, 21)),
+                national_health_number: Some("NHN123".to_owned()),
+                phone: Some("0211234567".to_owned()),
+                email: Some("jane@doe.com".to_owned()),
+                address: Some("1 Main Street".to_owned()),
+                legacy_record: NAME_4.1.to_owned(),
+            })),
+            identifier: "Patient",
+            central_sync_buffer_row: CentralSyncBufferRow {
+                id: 203,
+                table_name: RECORD_TYPE.to_owned(),
+                record_id: NAME_4.0.to_owned(),
+                data: NAME_4.1.to_owned(),
+            },
+        },
     ]
 }
 #[allow(dead_code)]
@@ -442,8 +590,17 @@ pub fn get_test_name_upsert_records() -> Vec<TestSyncRecord> {
             id: NAME_UPSERT_1.0.to_owned(),
             name: "General2".to_owned(),
             code: "GEN".to_owned(),
+            r#type: NameType::Store,
             is_customer: true,
             is_supplier: true,
+            first_name: None,
+            last_name: None,
+            gender: None,
+            date_of_birth: None,
+            national_health_number: None,
+            phone: None,
+            email: None,
+            address: None,
             legacy_record: NAME_UPSERT_1.1.to_owned(),
         })),
         identifier: "General2",
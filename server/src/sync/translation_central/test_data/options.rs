@@ -0,0 +1,119 @@
+use crate::sync::translation_central::test_data::{TestSyncDataRecord, TestSyncRecord};
+use repository::{CentralSyncBufferRow, InventoryAdjustmentReasonRow, InventoryAdjustmentType};
+
+const POSITIVE_REASON: (&'static str, &'static str) = (
+    "POSITIVE_REASON",
+    r#"{
+    "ID": "POSITIVE_REASON",
+    "code": "POS",
+    "type": "positiveInventoryAdjustment",
+    "isActive": true,
+    "title": "Found"
+}"#,
+);
+
+const NEGATIVE_REASON: (&'static str, &'static str) = (
+    "NEGATIVE_REASON",
+    r#"{
+    "ID": "NEGATIVE_REASON",
+    "code": "NEG",
+    "type": "negativeInventoryAdjustment",
+    "isActive": true,
+    "title": "Broken"
+}"#,
+);
+
+const RETURN_REASON: (&'static str, &'static str) = (
+    "RETURN_REASON",
+    r#"{
+    "ID": "RETURN_REASON",
+    "code": "RET",
+    "type": "returnReason",
+    "isActive": true,
+    "title": "Damaged"
+}"#,
+);
+
+const NEGATIVE_REASON_UPSERT: (&'static str, &'static str) = (
+    "NEGATIVE_REASON",
+    r#"{
+    "ID": "NEGATIVE_REASON",
+    "code": "NEG",
+    "type": "negativeInventoryAdjustment",
+    "isActive": false,
+    "title": "Broken or damaged"
+}"#,
+);
+
+#[allow(dead_code)]
+const RECORD_TYPE: &'static str = "options";
+#[allow(dead_code)]
+pub fn get_test_options_records() -> Vec<TestSyncRecord> {
+    vec![
+        TestSyncRecord {
+            translated_record: TestSyncDataRecord::InventoryAdjustmentReason(Some(
+                InventoryAdjustmentReasonRow {
+                    id: POSITIVE_REASON.0.to_owned(),
+                    r#type: InventoryAdjustmentType::Positive,
+                    is_active: true,
+                    reason: "Found".to_owned(),
+                },
+            )),
+            identifier: "Positive inventory adjustment reason",
+            central_sync_buffer_row: CentralSyncBufferRow {
+                id: 600,
+                table_name: RECORD_TYPE.to_owned(),
+                record_id: POSITIVE_REASON.0.to_owned(),
+                data: POSITIVE_REASON.1.to_owned(),
+            },
+        },
+        TestSyncRecord {
+            translated_record: TestSyncDataRecord::InventoryAdjustmentReason(Some(
+                InventoryAdjustmentReasonRow {
+                    id: NEGATIVE_REASON.0.to_owned(),
+                    r#type: InventoryAdjustmentType::Negative,
+                    is_active: true,
+                    reason: "Broken".to_owned(),
+                },
+            )),
+            identifier: "Negative inventory adjustment reason",
+            central_sync_buffer_row: CentralSyncBufferRow {
+                id: 601,
+                table_name: RECORD_TYPE.to_owned(),
+                record_id: NEGATIVE_REASON.0.to_owned(),
+                data: NEGATIVE_REASON.1.to_owned(),
+            },
+        },
+        TestSyncRecord {
+            translated_record: TestSyncDataRecord::InventoryAdjustmentReason(None),
+            identifier: "Return reason is ignored",
+            central_sync_buffer_row: CentralSyncBufferRow {
+                id: 602,
+                table_name: RECORD_TYPE.to_owned(),
+                record_id: RETURN_REASON.0.to_owned(),
+                data: RETURN_REASON.1.to_owned(),
+            },
+        },
+    ]
+}
+
+#[allow(dead_code)]
+pub fn get_test_options_upsert_records() -> Vec<TestSyncRecord> {
+    vec![TestSyncRecord {
+        translated_record: TestSyncDataRecord::InventoryAdjustmentReason(Some(
+            InventoryAdjustmentReasonRow {
+                id: NEGATIVE_REASON_UPSERT.0.to_owned(),
+                r#type: InventoryAdjustmentType::Negative,
+                is_active: false,
+                reason: "Broken or damaged".to_owned(),
+            },
+        )),
+        identifier: "Deactivated negative inventory adjustment reason",
+        central_sync_buffer_row: CentralSyncBufferRow {
+            id: 603,
+            table_name: RECORD_TYPE.to_owned(),
+            record_id: NEGATIVE_REASON_UPSERT.0.to_owned(),
+            data: NEGATIVE_REASON_UPSERT.1.to_owned(),
+        },
+    }]
+}
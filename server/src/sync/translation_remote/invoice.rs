@@ -1,8 +1,8 @@
 use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
 use repository::{
-    RemoteSyncBufferRow, ChangelogRow, ChangelogTableName, InvoiceRow,
-    InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType, NameRow, NameRowRepository,
-    StorageConnection, StoreRowRepository,
+    ChangelogRow, ChangelogTableName, InvoiceRow, InvoiceRowRepository, InvoiceRowStatus,
+    InvoiceRowType, NameRow, NameRowRepository, RemoteSyncBufferRow, StorageConnection,
+    StoreRowRepository,
 };
 
 use serde::{Deserialize, Serialize};
@@ -24,8 +24,14 @@ pub enum LegacyTransactType {
     /// Customer invoice
     #[serde(rename = "ci")]
     Ci,
+    /// Customer credit
+    #[serde(rename = "cc")]
+    Cc,
+    /// Supplier credit
+    #[serde(rename = "sc")]
+    Sc,
     /// Bucket to catch all other variants
-    /// E.g. "cc" (customer credit), "sc" (supplier credit), "sr" (repack), "bu" (build),
+    /// E.g. "sr" (repack), "bu" (build),
     /// "rc" (cash receipt), "ps" (cash payment)
     #[serde(other)]
     Others,
@@ -148,6 +154,16 @@ pub struct LegacyTransactRow {
     #[serde(deserialize_with = "empty_str_as_option")]
     #[serde(default)]
     pub om_colour: Option<String>,
+
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub om_prescriber: Option<String>,
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub om_diagnosis: Option<String>,
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub om_directions: Option<String>,
 }
 
 pub struct InvoiceTranslation {}
@@ -177,10 +193,9 @@ impl RemotePullTranslation for InvoiceTranslation {
             .find_one_by_name_id(&data.name_ID)?
             .map(|store_row| store_row.id);
 
-        let invoice_type = invoice_type(&data._type, &name).ok_or(anyhow::Error::msg(format!(
-            "Unsupported invoice type: {:?}",
-            data._type
-        )))?;
+        let invoice_type = invoice_type(&data._type, &data.mode, &name).ok_or(
+            anyhow::Error::msg(format!("Unsupported invoice type: {:?}", data._type)),
+        )?;
         let invoice_status = invoice_status(&invoice_type, &data).ok_or(anyhow::Error::msg(
             format!("Unsupported invoice type: {:?}", data._type),
         ))?;
@@ -212,18 +227,30 @@ impl RemotePullTranslation for InvoiceTranslation {
                 requisition_id: data.requisition_ID,
                 linked_invoice_id: data.linked_transaction_id,
                 transport_reference: data.transport_reference,
+                prescriber: data.om_prescriber,
+                diagnosis: data.om_diagnosis,
+                directions: data.om_directions,
             }),
         )))
     }
 }
 
-fn invoice_type(_type: &LegacyTransactType, name: &NameRow) -> Option<InvoiceRowType> {
+fn invoice_type(
+    _type: &LegacyTransactType,
+    mode: &TransactMode,
+    name: &NameRow,
+) -> Option<InvoiceRowType> {
     if name.code == INVENTORY_ADJUSTMENT_NAME_CODE {
         return Some(InvoiceRowType::InventoryAdjustment);
     }
     match _type {
         LegacyTransactType::Si => Some(InvoiceRowType::InboundShipment),
+        LegacyTransactType::Ci if mode == &TransactMode::Dispensary => {
+            Some(InvoiceRowType::Prescription)
+        }
         LegacyTransactType::Ci => Some(InvoiceRowType::OutboundShipment),
+        LegacyTransactType::Cc => Some(InvoiceRowType::CustomerReturn),
+        LegacyTransactType::Sc => Some(InvoiceRowType::SupplierReturn),
         _ => return None,
     }
 }
@@ -304,6 +331,26 @@ fn map_legacy(invoice_type: &InvoiceRowType, data: &LegacyTransactRow) -> Legacy
             }
             _ => {}
         },
+        InvoiceRowType::Prescription | InvoiceRowType::SupplierReturn => match data.status {
+            LegacyTransactStatus::Cn => {
+                mapping.picked_datetime = confirm_datetime;
+            }
+            LegacyTransactStatus::Fn => {
+                mapping.picked_datetime = confirm_datetime;
+                mapping.verified_datetime = confirm_datetime;
+            }
+            _ => {}
+        },
+        InvoiceRowType::CustomerReturn => match data.status {
+            LegacyTransactStatus::Cn => {
+                mapping.delivered_datetime = confirm_datetime;
+            }
+            LegacyTransactStatus::Fn => {
+                mapping.delivered_datetime = confirm_datetime;
+                mapping.verified_datetime = confirm_datetime;
+            }
+            _ => {}
+        },
     };
     mapping
 }
@@ -316,6 +363,9 @@ fn to_legacy_confirm_time(
     let datetime = match invoice_type {
         InvoiceRowType::OutboundShipment => picked_datetime,
         InvoiceRowType::InboundShipment => delivered_datetime,
+        InvoiceRowType::Prescription => picked_datetime,
+        InvoiceRowType::CustomerReturn => delivered_datetime,
+        InvoiceRowType::SupplierReturn => picked_datetime,
         InvoiceRowType::InventoryAdjustment => None,
     };
 
@@ -355,6 +405,22 @@ fn invoice_status(
             LegacyTransactStatus::Fn => InvoiceRowStatus::Verified,
             _ => return None,
         },
+
+        InvoiceRowType::Prescription | InvoiceRowType::SupplierReturn => match data.status {
+            LegacyTransactStatus::Nw => InvoiceRowStatus::New,
+            LegacyTransactStatus::Sg => InvoiceRowStatus::New,
+            LegacyTransactStatus::Cn => InvoiceRowStatus::Picked,
+            LegacyTransactStatus::Fn => InvoiceRowStatus::Verified,
+            _ => return None,
+        },
+
+        InvoiceRowType::CustomerReturn => match data.status {
+            LegacyTransactStatus::Nw => InvoiceRowStatus::New,
+            LegacyTransactStatus::Sg => InvoiceRowStatus::New,
+            LegacyTransactStatus::Cn => InvoiceRowStatus::Delivered,
+            LegacyTransactStatus::Fn => InvoiceRowStatus::Verified,
+            _ => return None,
+        },
     };
     Some(status)
 }
@@ -392,6 +458,9 @@ impl RemotePushUpsertTranslation for InvoiceTranslation {
             requisition_id,
             linked_invoice_id,
             transport_reference,
+            prescriber,
+            diagnosis,
+            directions,
         } = InvoiceRowRepository::new(connection).find_one_by_id(&changelog.row_id)?;
 
         let _type = legacy_invoice_type(&r#type).ok_or(anyhow::Error::msg(format!(
@@ -424,7 +493,11 @@ impl RemotePushUpsertTranslation for InvoiceTranslation {
             confirm_date: confirm_datetime.0,
             confirm_time: confirm_datetime.1,
 
-            mode: TransactMode::Store,
+            mode: if r#type == InvoiceRowType::Prescription {
+                TransactMode::Dispensary
+            } else {
+                TransactMode::Store
+            },
             transport_reference,
             created_datetime: Some(created_datetime),
             allocated_datetime,
@@ -435,6 +508,9 @@ impl RemotePushUpsertTranslation for InvoiceTranslation {
             om_status: Some(status),
             om_type: Some(r#type),
             om_colour: colour,
+            om_prescriber: prescriber,
+            om_diagnosis: diagnosis,
+            om_directions: directions,
         };
 
         Ok(Some(vec![PushUpsertRecord {
@@ -454,6 +530,9 @@ fn legacy_invoice_type(_type: &InvoiceRowType) -> Option<LegacyTransactType> {
         // Always use supplier invoice. omSupply can contain incoming and outgoing lines so there is
         // no clear mapping to Ci or Si here.
         InvoiceRowType::InventoryAdjustment => LegacyTransactType::Si,
+        InvoiceRowType::Prescription => LegacyTransactType::Ci,
+        InvoiceRowType::CustomerReturn => LegacyTransactType::Cc,
+        InvoiceRowType::SupplierReturn => LegacyTransactType::Sc,
     };
     return Some(t);
 }
@@ -487,6 +566,22 @@ fn legacy_invoice_status(
             InvoiceRowStatus::Delivered => LegacyTransactStatus::Nw,
             InvoiceRowStatus::Verified => LegacyTransactStatus::Fn,
         },
+        InvoiceRowType::Prescription | InvoiceRowType::SupplierReturn => match status {
+            InvoiceRowStatus::New => LegacyTransactStatus::Nw,
+            InvoiceRowStatus::Allocated => LegacyTransactStatus::Nw,
+            InvoiceRowStatus::Picked => LegacyTransactStatus::Cn,
+            InvoiceRowStatus::Shipped => LegacyTransactStatus::Cn,
+            InvoiceRowStatus::Delivered => LegacyTransactStatus::Cn,
+            InvoiceRowStatus::Verified => LegacyTransactStatus::Fn,
+        },
+        InvoiceRowType::CustomerReturn => match status {
+            InvoiceRowStatus::New => LegacyTransactStatus::Nw,
+            InvoiceRowStatus::Allocated => LegacyTransactStatus::Nw,
+            InvoiceRowStatus::Picked => LegacyTransactStatus::Nw,
+            InvoiceRowStatus::Shipped => LegacyTransactStatus::Nw,
+            InvoiceRowStatus::Delivered => LegacyTransactStatus::Cn,
+            InvoiceRowStatus::Verified => LegacyTransactStatus::Fn,
+        },
     };
     Some(status)
 }
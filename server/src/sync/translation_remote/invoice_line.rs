@@ -1,7 +1,7 @@
 use chrono::NaiveDate;
 use repository::{
-    RemoteSyncBufferRow, ChangelogRow, ChangelogTableName, InvoiceLineRow,
-    InvoiceLineRowRepository, InvoiceLineRowType, ItemRowRepository, StorageConnection,
+    ChangelogRow, ChangelogTableName, InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType,
+    ItemRowRepository, RemoteSyncBufferRow, StorageConnection,
 };
 
 use serde::{Deserialize, Serialize};
@@ -55,6 +55,11 @@ pub struct LegacyTransLineRow {
     pub number_of_packs: i32,
     #[serde(deserialize_with = "empty_str_as_option")]
     pub note: Option<String>,
+    /// Inventory adjustment reason
+    #[serde(rename = "optionID")]
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub option_id: Option<String>,
 
     #[serde(rename = "om_item_code")]
     #[serde(deserialize_with = "empty_str_as_option")]
@@ -66,6 +71,14 @@ pub struct LegacyTransLineRow {
     pub total_before_tax: Option<f64>,
     #[serde(rename = "om_total_after_tax")]
     pub total_after_tax: Option<f64>,
+    #[serde(rename = "om_original_invoice_line_id")]
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub original_invoice_line_id: Option<String>,
+    #[serde(rename = "om_return_reason")]
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub return_reason: Option<String>,
 }
 
 pub struct InvoiceLineTranslation {}
@@ -132,6 +145,9 @@ impl RemotePullTranslation for InvoiceLineTranslation {
                 r#type: line_type,
                 number_of_packs: data.number_of_packs,
                 note: data.note,
+                original_invoice_line_id: data.original_invoice_line_id,
+                return_reason: data.return_reason,
+                inventory_adjustment_reason_id: data.option_id,
             }),
         )))
     }
@@ -186,6 +202,9 @@ impl RemotePushUpsertTranslation for InvoiceLineTranslation {
             r#type,
             number_of_packs,
             note,
+            original_invoice_line_id,
+            return_reason,
+            inventory_adjustment_reason_id,
         } = InvoiceLineRowRepository::new(connection).find_one_by_id(&changelog.row_id)?;
 
         let legacy_row = LegacyTransLineRow {
@@ -203,10 +222,13 @@ impl RemotePushUpsertTranslation for InvoiceLineTranslation {
             _type: to_legacy_invoice_line_type(&r#type),
             number_of_packs,
             note,
+            option_id: inventory_adjustment_reason_id,
             item_code: Some(item_code),
             tax,
             total_before_tax: Some(total_before_tax),
             total_after_tax: Some(total_after_tax),
+            original_invoice_line_id,
+            return_reason,
         };
 
         Ok(Some(vec![PushUpsertRecord {
@@ -1,6 +1,6 @@
 use repository::{
-    RemoteSyncBufferRow, ChangelogRow, ChangelogTableName, LocationRow,
-    LocationRowRepository, StorageConnection,
+    ChangelogRow, ChangelogTableName, LocationRow, LocationRowRepository, RemoteSyncBufferRow,
+    StorageConnection,
 };
 use serde::{Deserialize, Serialize};
 
@@ -27,7 +27,7 @@ pub struct LocationTranslation {}
 impl RemotePullTranslation for LocationTranslation {
     fn try_translate_pull(
         &self,
-        _: &StorageConnection,
+        connection: &StorageConnection,
         sync_record: &RemoteSyncBufferRow,
     ) -> Result<Option<IntegrationRecord>, anyhow::Error> {
         let table_name = TRANSLATION_RECORD_LOCATION;
@@ -43,6 +43,11 @@ impl RemotePullTranslation for LocationTranslation {
             store_id,
         } = serde_json::from_str::<LegacyLocationRow>(&sync_record.data)?;
 
+        // Hierarchy and storage attributes are not synced to the central server, keep local values
+        let existing = LocationRowRepository::new(connection)
+            .find_one_by_id(&id)?
+            .unwrap_or_default();
+
         Ok(Some(IntegrationRecord::from_upsert(
             IntegrationUpsertRecord::Location(LocationRow {
                 id,
@@ -50,6 +55,10 @@ impl RemotePullTranslation for LocationTranslation {
                 code,
                 on_hold,
                 store_id,
+                parent_location_id: existing.parent_location_id,
+                capacity: existing.capacity,
+                min_temperature: existing.min_temperature,
+                max_temperature: existing.max_temperature,
             }),
         )))
     }
@@ -72,6 +81,10 @@ impl RemotePushUpsertTranslation for LocationTranslation {
             code,
             on_hold,
             store_id,
+            parent_location_id: _,
+            capacity: _,
+            min_temperature: _,
+            max_temperature: _,
         } = LocationRowRepository::new(connection)
             .find_one_by_id(&changelog.row_id)?
             .ok_or(anyhow::Error::msg(format!(
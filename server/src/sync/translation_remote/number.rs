@@ -1,6 +1,6 @@
 use repository::{
-    RemoteSyncBufferRow, ChangelogRow, ChangelogTableName, NumberRow, NumberRowRepository,
-    NumberRowType, StorageConnection,
+    ChangelogRow, ChangelogTableName, NumberRow, NumberRowRepository, NumberRowType,
+    RemoteSyncBufferRow, StorageConnection,
 };
 
 use serde::{Deserialize, Serialize};
@@ -101,6 +101,9 @@ fn parse_number_name(value: String) -> Option<(NumberRowType, String)> {
         // new for omSupply
         "request_requisition" => NumberRowType::RequestRequisition,
         "response_requisition" => NumberRowType::ResponseRequisition,
+        "prescription" => NumberRowType::Prescription,
+        "customer_return" => NumberRowType::CustomerReturn,
+        "supplier_return" => NumberRowType::SupplierReturn,
         _ => return None,
     };
     let store = split.next()?.to_string();
@@ -116,6 +119,9 @@ fn to_number_name(number_type: &NumberRowType, store_id: &str) -> Option<String>
         // new for omSupply
         NumberRowType::RequestRequisition => "request_requisition",
         NumberRowType::ResponseRequisition => "response_requisition",
+        NumberRowType::Prescription => "prescription",
+        NumberRowType::CustomerReturn => "customer_return",
+        NumberRowType::SupplierReturn => "supplier_return",
     };
     Some(format!("{}_for_store_{}", number_str, store_id))
 }
@@ -9,6 +9,7 @@ use repository::{
 };
 
 use crate::sync::{
+    quarantine::SyncQuarantine,
     translation_remote::{
         invoice::InvoiceTranslation, invoice_line::InvoiceLineTranslation,
         location::LocationTranslation, name_store_join::NameStoreJoinTranslation,
@@ -53,37 +54,55 @@ pub trait RemotePullTranslation {
     ) -> Result<Option<IntegrationRecord>, anyhow::Error>;
 }
 
+/// Translated records together with the sync record they have been translated from
+#[derive(Debug)]
+struct TranslatedRecord<'a> {
+    pub sync_record: &'a RemoteSyncBufferRow,
+    pub integration_record: IntegrationRecord,
+}
+
 /// Imports sync records and writes them to the DB
 /// If needed data records are translated to the local DB schema.
+/// Records that fail translation or integration are quarantined, returns the number of integrated
+/// records.
 pub fn import_sync_pull_records(
     connection: &StorageConnection,
     records: &Vec<RemoteSyncBufferRow>,
-) -> Result<(), SyncImportError> {
-    let mut integration_records = IntegrationRecord {
-        upserts: Vec::new(),
-    };
+) -> Result<u32, SyncImportError> {
+    let quarantine = SyncQuarantine::remote(connection);
+    let mut translated_records = Vec::new();
 
     info!(
         "Translating {} remote sync buffer records...",
         records.len()
     );
     for record in records {
-        do_translation(connection, &record, &mut integration_records)?;
+        match do_translation(connection, &record) {
+            Ok(Some(integration_record)) => translated_records.push(TranslatedRecord {
+                sync_record: record,
+                integration_record,
+            }),
+            Ok(None) => {}
+            Err(error) => quarantine
+                .quarantine_remote(record, format!("Failed to translate: {:#}", error.source))
+                .map_err(|error| SyncImportError::as_integration_error(error, ""))?,
+        }
     }
     info!("Succesfully translated remote sync buffer records");
 
     info!("Storing integration remote records...");
-    store_integration_records(connection, &integration_records)?;
+    let number_of_integrated_records = store_integration_records(connection, &translated_records)?;
     info!("Successfully stored integration remote records");
 
-    Ok(())
+    Ok(number_of_integrated_records)
 }
 
+/// Translates a sync record into the local DB schema.
+/// Returns None if the record is not translated, i.e. if it is unhandled.
 fn do_translation(
     connection: &StorageConnection,
     sync_record: &RemoteSyncBufferRow,
-    records: &mut IntegrationRecord,
-) -> Result<(), SyncTranslationError> {
+) -> Result<Option<IntegrationRecord>, SyncTranslationError> {
     let translations: Vec<Box<dyn RemotePullTranslation>> = vec![
         Box::new(NumberTranslation {}),
         Box::new(LocationTranslation {}),
@@ -99,24 +118,19 @@ fn do_translation(
     ];
     for translation in translations {
         match translation.try_translate_pull(connection, sync_record) {
-            Ok(Some(mut result)) => {
-                records.upserts.append(&mut result.upserts);
-                return Ok(());
-            }
-            Err(error) => warn!(
-                "Failed to translate ({}): {:?}",
-                SyncTranslationError {
+            Ok(Some(result)) => return Ok(Some(result)),
+            Err(error) => {
+                return Err(SyncTranslationError {
                     table_name: sync_record.table_name.clone(),
                     source: error,
                     record: format!("{:?}", sync_record),
-                },
-                sync_record
-            ),
+                })
+            }
             _ => {}
         };
     }
     warn!("Unhandled remote pull record: {:?}", sync_record);
-    Ok(())
+    Ok(None)
 }
 
 fn integrate_record(
@@ -157,28 +171,43 @@ fn integrate_record(
 
 fn store_integration_records(
     connection: &StorageConnection,
-    integration_records: &IntegrationRecord,
-) -> Result<(), SyncImportError> {
+    translated_records: &Vec<TranslatedRecord<'_>>,
+) -> Result<u32, SyncImportError> {
     connection
         .transaction_sync(|con| {
-            for record in &integration_records.upserts {
-                // Integrate every record in a sub transaction. This is mainly for Postgres where the
-                // whole transaction fails when there is a DB error (not a problem in sqlite).
-                let sub_result =
-                    con.transaction_sync_etc(|sub_tx| integrate_record(record, sub_tx), false);
-                match sub_result {
-                    Ok(_) => Ok(()),
-                    Err(TransactionError::Inner(err @ RepositoryError::ForeignKeyViolation(_))) => {
-                        warn!("Failed to import ({}): {:?}", err, record);
+            let quarantine = SyncQuarantine::remote(con);
+            let mut number_of_integrated_records = 0;
+            for record in translated_records {
+                let sync_record = record.sync_record;
+                // Integrate every sync record in a sub transaction. This is mainly for Postgres
+                // where the whole transaction fails when there is a DB error (not a problem in
+                // sqlite).
+                let sub_result = con.transaction_sync_etc(
+                    |sub_tx| {
+                        for upsert in &record.integration_record.upserts {
+                            integrate_record(upsert, sub_tx)?;
+                        }
                         Ok(())
+                    },
+                    false,
+                );
+                match sub_result {
+                    Ok(_) => {
+                        number_of_integrated_records += 1;
+                        quarantine.release(&sync_record.table_name, &sync_record.record_id)
                     }
-                    Err(err) => Err(SyncImportError::as_integration_error(
-                        RepositoryError::from(err),
-                        "",
-                    )),
-                }?;
+                    // e.g. the central record this record refers to hasn't been synced yet, or a
+                    // second request requisition of a store for the same programme period
+                    Err(TransactionError::Inner(
+                        err @ (RepositoryError::ForeignKeyViolation(_)
+                        | RepositoryError::UniqueViolation(_)),
+                    )) => quarantine
+                        .quarantine_remote(sync_record, format!("Failed to integrate: {}", err)),
+                    Err(err) => Err(RepositoryError::from(err)),
+                }
+                .map_err(|error| SyncImportError::as_integration_error(error, ""))?;
             }
-            Ok(())
+            Ok(number_of_integrated_records)
         })
         .map_err(|error| match error {
             TransactionError::Transaction { msg, level } => SyncImportError::as_integration_error(
@@ -1,8 +1,8 @@
 use chrono::{NaiveDate, NaiveDateTime};
 use repository::{
     requisition_row::{RequisitionRowStatus, RequisitionRowType},
-    RemoteSyncBufferRow,
-    ChangelogRow, ChangelogTableName, RequisitionRow, RequisitionRowRepository, StorageConnection,
+    ChangelogRow, ChangelogTableName, RemoteSyncBufferRow, RequisitionRow,
+    RequisitionRowRepository, StorageConnection,
 };
 
 use serde::{Deserialize, Serialize};
@@ -61,6 +61,22 @@ pub enum LegacyRequisitionStatus {
     Others,
 }
 
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub enum LegacyAuthorisationStatus {
+    /// authorisation not required
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "pending")]
+    Pending,
+    #[serde(rename = "authorised")]
+    Authorised,
+    #[serde(rename = "denied")]
+    Denied,
+    /// Bucket to catch all other variants, e.g. an empty string
+    #[serde(other)]
+    Others,
+}
+
 #[allow(non_snake_case)]
 #[derive(Deserialize, Serialize)]
 pub struct LegacyRequisitionRow {
@@ -87,6 +103,8 @@ pub struct LegacyRequisitionRow {
     pub thresholdMOS: f64,
     /// relates to max_months_of_stock
     pub daysToSupply: i64,
+    #[serde(default)]
+    pub authorisationStatus: Option<LegacyAuthorisationStatus>,
 
     #[serde(deserialize_with = "empty_str_as_option")]
     pub comment: Option<String>,
@@ -117,6 +135,10 @@ pub struct LegacyRequisitionRow {
     #[serde(deserialize_with = "empty_str_as_option")]
     #[serde(default)]
     pub om_colour: Option<String>,
+    #[serde(rename = "om_approved_by_user_id")]
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub approved_by_user_id: Option<String>,
 }
 
 pub struct RequisitionTranslation {}
@@ -161,9 +183,12 @@ impl RemotePullTranslation for RequisitionTranslation {
                 from_legacy_sent_datetime(data.last_modified_at, &r#type),
                 from_legacy_finalised_datetime(data.last_modified_at, &r#type),
                 data.daysToSupply as f64 / NUMBER_OF_DAYS_IN_A_MONTH,
-                from_legacy_status(&data.r#type, &data.status).ok_or(anyhow::Error::msg(
-                    format!("Unsupported requisition status: {:?}", data.status),
-                ))?,
+                from_legacy_status(&data.r#type, &data.status, &data.authorisationStatus).ok_or(
+                    anyhow::Error::msg(format!(
+                        "Unsupported requisition status: {:?}",
+                        data.status
+                    )),
+                )?,
                 None,
             ),
         };
@@ -187,6 +212,10 @@ impl RemotePullTranslation for RequisitionTranslation {
                 min_months_of_stock: data.thresholdMOS,
                 linked_requisition_id: data.linked_requisition_id,
                 expected_delivery_date: data.expected_delivery_date,
+                // Programmes are not synced with the legacy server
+                program_id: None,
+                period_id: None,
+                approved_by_user_id: data.approved_by_user_id,
             }),
         )))
     }
@@ -253,6 +282,9 @@ impl RemotePushUpsertTranslation for RequisitionTranslation {
             min_months_of_stock,
             linked_requisition_id,
             expected_delivery_date,
+            program_id: _,
+            period_id: _,
+            approved_by_user_id,
         } = RequisitionRowRepository::new(connection)
             .find_one_by_id(&changelog.row_id)?
             .ok_or(anyhow::Error::msg(format!(
@@ -271,6 +303,10 @@ impl RemotePushUpsertTranslation for RequisitionTranslation {
                 "Unexpected row requisition status {:?} (type: {:?}), row id:{}",
                 status, r#type, changelog.row_id
             )))?,
+            authorisationStatus: Some(to_legacy_authorisation_status(
+                &status,
+                &approved_by_user_id,
+            )),
             om_status: Some(status),
             date_entered: date_from_date_time(&created_datetime),
             created_datetime: Some(created_datetime),
@@ -289,6 +325,7 @@ impl RemotePushUpsertTranslation for RequisitionTranslation {
             max_months_of_stock: Some(max_months_of_stock),
             om_colour: colour.clone(),
             comment,
+            approved_by_user_id,
         };
 
         Ok(Some(vec![PushUpsertRecord {
@@ -333,6 +370,7 @@ fn to_legacy_type(t: &RequisitionRowType) -> LegacyRequisitionType {
 fn from_legacy_status(
     r#type: &LegacyRequisitionType,
     status: &LegacyRequisitionStatus,
+    authorisation_status: &Option<LegacyAuthorisationStatus>,
 ) -> Option<RequisitionRowStatus> {
     let status = match r#type {
         LegacyRequisitionType::Request => match status {
@@ -343,7 +381,10 @@ fn from_legacy_status(
         },
         LegacyRequisitionType::Response => match status {
             LegacyRequisitionStatus::Sg => return None,
-            &LegacyRequisitionStatus::Cn => RequisitionRowStatus::New,
+            &LegacyRequisitionStatus::Cn => match authorisation_status {
+                Some(LegacyAuthorisationStatus::Authorised) => RequisitionRowStatus::Authorised,
+                _ => RequisitionRowStatus::New,
+            },
             LegacyRequisitionStatus::Fn => RequisitionRowStatus::Finalised,
             _ => return None,
         },
@@ -365,6 +406,7 @@ fn to_legacy_status(
         },
         RequisitionRowType::Response => match status {
             RequisitionRowStatus::New => LegacyRequisitionStatus::Cn,
+            RequisitionRowStatus::Authorised => LegacyRequisitionStatus::Cn,
             RequisitionRowStatus::Finalised => LegacyRequisitionStatus::Fn,
             _ => return None,
         },
@@ -372,6 +414,18 @@ fn to_legacy_status(
     Some(status)
 }
 
+fn to_legacy_authorisation_status(
+    status: &RequisitionRowStatus,
+    approved_by_user_id: &Option<String>,
+) -> LegacyAuthorisationStatus {
+    // A finalised requisition stays authorised if it was approved before
+    if status == &RequisitionRowStatus::Authorised || approved_by_user_id.is_some() {
+        LegacyAuthorisationStatus::Authorised
+    } else {
+        LegacyAuthorisationStatus::None
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use repository::{mock::MockDataInserts, test_db::setup_all};
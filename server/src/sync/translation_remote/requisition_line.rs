@@ -1,7 +1,7 @@
 use chrono::NaiveDateTime;
 use repository::{
-    RemoteSyncBufferRow, ChangelogRow, ChangelogTableName, RequisitionLineRow,
-    RequisitionLineRowRepository, StorageConnection,
+    ChangelogRow, ChangelogTableName, ConsumptionCalculationMethod, RemoteSyncBufferRow,
+    RequisitionLineRow, RequisitionLineRowRepository, StorageConnection,
 };
 
 use serde::{Deserialize, Serialize};
@@ -34,6 +34,13 @@ pub struct LegacyRequisitionLineRow {
     #[serde(deserialize_with = "empty_str_as_option")]
     pub comment: Option<String>,
 
+    #[serde(default)]
+    pub approved_quantity: i32,
+    #[serde(rename = "authoriser_comment")]
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub approval_comment: Option<String>,
+
     #[serde(rename = "om_snapshot_datetime")]
     #[serde(default)]
     #[serde(deserialize_with = "empty_date_time_as_option")]
@@ -66,6 +73,15 @@ impl RemotePullTranslation for RequisitionLineTranslation {
                 average_monthly_consumption: (data.daily_usage * NUMBER_OF_DAYS_IN_A_MONTH) as i32,
                 comment: data.comment,
                 snapshot_datetime: data.snapshot_datetime,
+                approved_quantity: data.approved_quantity,
+                approval_comment: data.approval_comment,
+                // Period balances and calculation method are not synced with the legacy server
+                opening_balance: 0,
+                received_quantity: 0,
+                issued_quantity: 0,
+                adjusted_quantity: 0,
+                closing_balance: 0,
+                consumption_calculation_method: ConsumptionCalculationMethod::SimpleAverage,
             }),
         )))
     }
@@ -93,6 +109,14 @@ impl RemotePushUpsertTranslation for RequisitionLineTranslation {
             average_monthly_consumption,
             comment,
             snapshot_datetime,
+            approved_quantity,
+            approval_comment,
+            opening_balance: _,
+            received_quantity: _,
+            issued_quantity: _,
+            adjusted_quantity: _,
+            closing_balance: _,
+            consumption_calculation_method: _,
         } = RequisitionLineRowRepository::new(connection)
             .find_one_by_id(&changelog.row_id)?
             .ok_or(anyhow::Error::msg(format!(
@@ -111,6 +135,8 @@ impl RemotePushUpsertTranslation for RequisitionLineTranslation {
             daily_usage: average_monthly_consumption as f64 / NUMBER_OF_DAYS_IN_A_MONTH,
             comment,
             snapshot_datetime,
+            approved_quantity,
+            approval_comment,
         };
 
         Ok(Some(vec![PushUpsertRecord {
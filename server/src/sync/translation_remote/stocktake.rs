@@ -110,6 +110,12 @@ impl RemotePullTranslation for StocktakeTranslation {
                 inventory_adjustment_id: data.invad_additions_ID,
                 stocktake_date: data.stocktake_date,
                 is_locked: data.is_locked,
+                // Not synced to the central server
+                is_blind_count: false,
+                recount_threshold: None,
+                counted_by: None,
+                count_submitted_datetime: None,
+                verified_by: None,
             }),
         )))
     }
@@ -148,6 +154,11 @@ impl RemotePushUpsertTranslation for StocktakeTranslation {
             inventory_adjustment_id,
             is_locked,
             stocktake_date,
+            is_blind_count: _,
+            recount_threshold: _,
+            counted_by: _,
+            count_submitted_datetime: _,
+            verified_by: _,
         } = StocktakeRowRepository::new(connection)
             .find_one_by_id(&changelog.row_id)?
             .ok_or(anyhow::Error::msg("Stocktake row not found"))?;
@@ -1,6 +1,6 @@
 use chrono::NaiveDate;
 use repository::{
-    RemoteSyncBufferRow, ChangelogRow, ChangelogTableName, StockLineRowRepository,
+    ChangelogRow, ChangelogTableName, RemoteSyncBufferRow, StockLineRowRepository,
     StocktakeLineRow, StocktakeLineRowRepository, StorageConnection,
 };
 use serde::{Deserialize, Serialize};
@@ -36,6 +36,11 @@ pub struct LegacyStocktakeLineRow {
     pub expiry: Option<NaiveDate>,
     pub cost_price: f64,
     pub sell_price: f64,
+    /// Inventory adjustment reason
+    #[serde(rename = "optionID")]
+    #[serde(deserialize_with = "empty_str_as_option")]
+    #[serde(default)]
+    pub option_id: Option<String>,
 
     #[serde(rename = "om_note")]
     #[serde(deserialize_with = "empty_str_as_option")]
@@ -72,6 +77,7 @@ impl RemotePullTranslation for StocktakeLineTranslation {
             comment: data.comment,
             snapshot_number_of_packs: data.snapshot_qty,
             counted_number_of_packs,
+            second_counted_number_of_packs: None,
             item_id: data.item_ID,
             batch: data.Batch,
             expiry_date: data.expiry,
@@ -79,6 +85,7 @@ impl RemotePullTranslation for StocktakeLineTranslation {
             cost_price_per_pack: Some(data.cost_price),
             sell_price_per_pack: Some(data.sell_price),
             note: data.note,
+            inventory_adjustment_reason_id: data.option_id,
         };
         Ok(Some(IntegrationRecord::from_upsert(
             IntegrationUpsertRecord::StocktakeLine(row),
@@ -105,6 +112,7 @@ impl RemotePushUpsertTranslation for StocktakeLineTranslation {
             comment,
             snapshot_number_of_packs,
             counted_number_of_packs,
+            second_counted_number_of_packs,
             item_id,
             batch,
             expiry_date,
@@ -112,6 +120,7 @@ impl RemotePushUpsertTranslation for StocktakeLineTranslation {
             cost_price_per_pack,
             sell_price_per_pack,
             note,
+            inventory_adjustment_reason_id,
         } = StocktakeLineRowRepository::new(connection)
             .find_one_by_id(&changelog.row_id)?
             .ok_or(anyhow::Error::msg("Stocktake row not found"))?;
@@ -122,6 +131,8 @@ impl RemotePushUpsertTranslation for StocktakeLineTranslation {
             }
             None => None,
         };
+        // a recount replaces the first count
+        let counted_number_of_packs = second_counted_number_of_packs.or(counted_number_of_packs);
         let legacy_row = LegacyStocktakeLineRow {
             ID: id.clone(),
             stock_take_ID: stocktake_id,
@@ -138,6 +149,7 @@ impl RemotePushUpsertTranslation for StocktakeLineTranslation {
             expiry: expiry_date,
             cost_price: cost_price_per_pack.unwrap_or(0.0),
             sell_price: sell_price_per_pack.unwrap_or(0.0),
+            option_id: inventory_adjustment_reason_id,
             note,
         };
 
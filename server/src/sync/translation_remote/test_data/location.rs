@@ -43,6 +43,10 @@ fn location_pull_record() -> TestSyncRecord {
                 code: "Red.02".to_string(),
                 on_hold: false,
                 store_id: "store_a".to_string(),
+                parent_location_id: None,
+                capacity: None,
+                min_temperature: None,
+                max_temperature: None,
             }),
         )),
         identifier: "Location 1",
@@ -8,7 +8,10 @@ use serde_json::json;
 
 use crate::sync::translation_remote::{
     pull::{IntegrationRecord, IntegrationUpsertRecord},
-    requisition::{LegacyRequisitionRow, LegacyRequisitionStatus, LegacyRequisitionType},
+    requisition::{
+        LegacyAuthorisationStatus, LegacyRequisitionRow, LegacyRequisitionStatus,
+        LegacyRequisitionType,
+    },
     TRANSLATION_RECORD_REQUISITION,
 };
 
@@ -67,6 +70,9 @@ fn requisition_request_pull_record() -> TestSyncRecord {
                 min_months_of_stock: 3.0,
                 linked_requisition_id: Some("mock_request_draft_requisition2".to_string()),
                 expected_delivery_date: None,
+                program_id: None,
+                period_id: None,
+                approved_by_user_id: None,
             }),
         )),
         identifier: "Requisition request",
@@ -100,6 +106,7 @@ fn requisition_request_push_record() -> TestSyncPushRecord {
             linked_requisition_id: Some("mock_request_draft_requisition2".to_string()),
             thresholdMOS: 3.0,
             daysToSupply: 150,
+            authorisationStatus: Some(LegacyAuthorisationStatus::None),
             comment: Some("comment 1".to_string()),
             created_datetime: Some(NaiveDate::from_ymd(2020, 7, 10).and_hms(0, 0, 0)),
             last_modified_at: 1594273006,
@@ -109,6 +116,7 @@ fn requisition_request_push_record() -> TestSyncPushRecord {
             om_status: Some(RequisitionRowStatus::Sent),
             om_colour: None,
             expected_delivery_date: None,
+            approved_by_user_id: None,
         }),
     }
 }
@@ -166,6 +174,9 @@ fn requisition_response_pull_record() -> TestSyncRecord {
                 min_months_of_stock: 3.0,
                 linked_requisition_id: Some("mock_request_draft_requisition2".to_string()),
                 expected_delivery_date: None,
+                program_id: None,
+                period_id: None,
+                approved_by_user_id: None,
             }),
         )),
         identifier: "Requisition response",
@@ -199,6 +210,7 @@ fn requisition_response_push_record() -> TestSyncPushRecord {
             linked_requisition_id: Some("mock_request_draft_requisition2".to_string()),
             thresholdMOS: 3.0,
             daysToSupply: 300,
+            authorisationStatus: Some(LegacyAuthorisationStatus::None),
             comment: Some("From request requisition 3".to_string()),
             created_datetime: Some(NaiveDate::from_ymd(2020, 7, 9).and_hms(0, 0, 0)),
             last_modified_at: 1594271180,
@@ -208,6 +220,7 @@ fn requisition_response_push_record() -> TestSyncPushRecord {
             om_status: Some(RequisitionRowStatus::Finalised),
             om_colour: None,
             expected_delivery_date: None,
+            approved_by_user_id: None,
         }),
     }
 }
@@ -272,6 +285,9 @@ fn requisition_om_fields_pull_record() -> TestSyncRecord {
                 max_months_of_stock: 10.0,
                 min_months_of_stock: 3.0,
                 linked_requisition_id: Some("mock_request_draft_requisition2".to_string()),
+                program_id: None,
+                period_id: None,
+                approved_by_user_id: None,
             }),
         )),
         identifier: "Requisition om_fields",
@@ -305,6 +321,7 @@ fn requisition_om_fields_push_record() -> TestSyncPushRecord {
             linked_requisition_id: Some("mock_request_draft_requisition2".to_string()),
             thresholdMOS: 3.0,
             daysToSupply: 300,
+            authorisationStatus: Some(LegacyAuthorisationStatus::None),
             comment: Some("From request requisition 3".to_string()),
             last_modified_at: 1648219680,
             created_datetime: Some(NaiveDate::from_ymd(2020, 07, 09).and_hms(0, 0, 0)),
@@ -314,6 +331,112 @@ fn requisition_om_fields_push_record() -> TestSyncPushRecord {
             max_months_of_stock: Some(10.0),
             om_status: Some(RequisitionRowStatus::New),
             om_colour: Some("Colour".to_string()),
+            approved_by_user_id: None,
+        }),
+    }
+}
+
+const REQUISITION_AUTHORISED: (&'static str, &'static str) = (
+    "CC5AA2238EE14654B11B86D52B435FF3",
+    r#"{
+      "ID": "CC5AA2238EE14654B11B86D52B435FF3",
+      "date_stock_take": "2020-06-09",
+      "user_ID": "0763E2E3053D4C478E1E6B6B03FEC207",
+      "name_ID": "name_store_b",
+      "status": "cn",
+      "date_entered": "2020-07-09",
+      "nsh_custInv_ID": "",
+      "daysToSupply": 300,
+      "store_ID": "store_b",
+      "type": "response",
+      "date_order_received": "2020-06-11",
+      "previous_csh_id": "",
+      "serial_number": 2,
+      "requester_reference": "",
+      "comment": "",
+      "colour": 1,
+      "custom_data": null,
+      "linked_requisition_id": "",
+      "linked_purchase_order_ID": "",
+      "authorisationStatus": "authorised",
+      "thresholdMOS": 3,
+      "orderType": "Normal",
+      "periodID": "",
+      "programID": "",
+      "lastModifiedAt": 0,
+      "is_emergency": false,
+      "isRemoteOrder": false,
+      "om_approved_by_user_id": "5A7C04F3F07E4BA8AB8658E70E0C1E22"
+    }"#,
+);
+fn requisition_authorised_pull_record() -> TestSyncRecord {
+    TestSyncRecord {
+        translated_record: Some(IntegrationRecord::from_upsert(
+            IntegrationUpsertRecord::Requisition(RequisitionRow {
+                id: REQUISITION_AUTHORISED.0.to_string(),
+                user_id: Some("0763E2E3053D4C478E1E6B6B03FEC207".to_string()),
+                requisition_number: 2,
+                name_id: "name_store_b".to_string(),
+                store_id: "store_b".to_string(),
+                r#type: RequisitionRowType::Response,
+                status: RequisitionRowStatus::Authorised,
+                created_datetime: NaiveDate::from_ymd(2020, 7, 9).and_hms(0, 0, 0),
+                sent_datetime: None,
+                finalised_datetime: None,
+                colour: None,
+                comment: None,
+                their_reference: None,
+                max_months_of_stock: 10.0,
+                min_months_of_stock: 3.0,
+                linked_requisition_id: None,
+                expected_delivery_date: None,
+                program_id: None,
+                period_id: None,
+                approved_by_user_id: Some("5A7C04F3F07E4BA8AB8658E70E0C1E22".to_string()),
+            }),
+        )),
+        identifier: "Requisition authorised",
+        remote_sync_buffer_row: RemoteSyncBufferRow {
+            id: "Requisition_40".to_string(),
+            table_name: TRANSLATION_RECORD_REQUISITION.to_string(),
+            record_id: REQUISITION_AUTHORISED.0.to_string(),
+            data: REQUISITION_AUTHORISED.1.to_string(),
+            action: RemoteSyncBufferAction::Update,
+        },
+    }
+}
+fn requisition_authorised_push_record() -> TestSyncPushRecord {
+    TestSyncPushRecord {
+        change_log: ChangelogRow {
+            id: 2,
+            table_name: ChangelogTableName::Requisition,
+            row_id: REQUISITION_AUTHORISED.0.to_string(),
+            row_action: ChangelogAction::Upsert,
+        },
+        push_data: json!(LegacyRequisitionRow {
+            ID: REQUISITION_AUTHORISED.0.to_string(),
+            user_id: Some("0763E2E3053D4C478E1E6B6B03FEC207".to_string()),
+            serial_number: 2,
+            name_ID: "name_store_b".to_string(),
+            store_ID: "store_b".to_string(),
+            r#type: LegacyRequisitionType::Response,
+            status: LegacyRequisitionStatus::Cn,
+            date_entered: NaiveDate::from_ymd(2020, 7, 9),
+            requester_reference: None,
+            linked_requisition_id: None,
+            thresholdMOS: 3.0,
+            daysToSupply: 300,
+            authorisationStatus: Some(LegacyAuthorisationStatus::Authorised),
+            comment: None,
+            created_datetime: Some(NaiveDate::from_ymd(2020, 7, 9).and_hms(0, 0, 0)),
+            last_modified_at: 0,
+            sent_datetime: None,
+            finalised_datetime: None,
+            max_months_of_stock: Some(10.0),
+            om_status: Some(RequisitionRowStatus::Authorised),
+            om_colour: None,
+            expected_delivery_date: None,
+            approved_by_user_id: Some("5A7C04F3F07E4BA8AB8658E70E0C1E22".to_string()),
         }),
     }
 }
@@ -323,6 +446,7 @@ pub fn get_test_requisition_records() -> Vec<TestSyncRecord> {
         requisition_request_pull_record(),
         requisition_response_pull_record(),
         requisition_om_fields_pull_record(),
+        requisition_authorised_pull_record(),
     ]
 }
 
@@ -331,5 +455,6 @@ pub fn get_test_push_requisition_records() -> Vec<TestSyncPushRecord> {
         requisition_request_push_record(),
         requisition_response_push_record(),
         requisition_om_fields_push_record(),
+        requisition_authorised_push_record(),
     ]
 }
@@ -1,7 +1,7 @@
 use chrono::NaiveDate;
 use repository::{
-    RemoteSyncBufferAction, RemoteSyncBufferRow,
-    ChangelogAction, ChangelogRow, ChangelogTableName, RequisitionLineRow,
+    ChangelogAction, ChangelogRow, ChangelogTableName, ConsumptionCalculationMethod,
+    RemoteSyncBufferAction, RemoteSyncBufferRow, RequisitionLineRow,
 };
 use serde_json::json;
 use util::constants::NUMBER_OF_DAYS_IN_A_MONTH;
@@ -63,6 +63,14 @@ fn requisition_line_request_pull_record() -> TestSyncRecord {
                 average_monthly_consumption: 3 * NUMBER_OF_DAYS_IN_A_MONTH as i32,
                 comment: None,
                 snapshot_datetime: None,
+                approved_quantity: 0,
+                approval_comment: None,
+                opening_balance: 0,
+                received_quantity: 0,
+                issued_quantity: 0,
+                adjusted_quantity: 0,
+                closing_balance: 0,
+                consumption_calculation_method: ConsumptionCalculationMethod::SimpleAverage,
             }),
         )),
         identifier: "Requisition line 1",
@@ -94,6 +102,8 @@ fn requisition_line_request_push_record() -> TestSyncPushRecord {
             daily_usage: 3.0,
             comment: None,
             snapshot_datetime: None,
+            approved_quantity: 0,
+            approval_comment: None,
         }),
     }
 }
@@ -148,6 +158,14 @@ fn requisition_line_om_fields_pull_record() -> TestSyncRecord {
                 average_monthly_consumption: 3 * NUMBER_OF_DAYS_IN_A_MONTH as i32,
                 comment: Some("Some comment".to_string()),
                 snapshot_datetime: Some(NaiveDate::from_ymd(2022, 04, 04).and_hms(14, 48, 11)),
+                approved_quantity: 0,
+                approval_comment: None,
+                opening_balance: 0,
+                received_quantity: 0,
+                issued_quantity: 0,
+                adjusted_quantity: 0,
+                closing_balance: 0,
+                consumption_calculation_method: ConsumptionCalculationMethod::SimpleAverage,
             }),
         )),
         identifier: "Requisition line om fields",
@@ -179,6 +197,102 @@ fn requisition_line_om_fields_push_record() -> TestSyncPushRecord {
             daily_usage: 3.0,
             comment: Some("Some comment".to_string()),
             snapshot_datetime: Some(NaiveDate::from_ymd(2022, 04, 04).and_hms(14, 48, 11)),
+            approved_quantity: 0,
+            approval_comment: None,
+        }),
+    }
+}
+
+const REQUISITION_LINE_AUTHORISED: (&'static str, &'static str) = (
+    "CCCB0A41C95441ABBBC7905857466089",
+    r#"{
+        "ID": "CCCB0A41C95441ABBBC7905857466089",
+        "requisition_ID": "CC5AA2238EE14654B11B86D52B435FF3",
+        "item_ID": "item_a",
+        "stock_on_hand": 10,
+        "actualQuan": 20,
+        "imprest_or_prev_quantity": 0,
+        "colour": -255,
+        "line_number": 1,
+        "Cust_prev_stock_balance": 0,
+        "Cust_stock_received": 0,
+        "Cust_stock_order": 102,
+        "comment": "",
+        "Cust_loss_adjust": 0,
+        "days_out_or_new_demand": 0,
+        "previous_stock_on_hand": 0,
+        "daily_usage": 3,
+        "suggested_quantity": 101,
+        "adjusted_consumption": 0,
+        "linked_requisition_line_ID": "",
+        "purchase_order_line_ID": "",
+        "optionID": "",
+        "Cust_stock_issued": 0,
+        "itemName": "Ibuprofen 200mg tablets",
+        "stockLosses": 0,
+        "stockAdditions": 0,
+        "stockExpiring": 0,
+        "DOSforAMCadjustment": 0,
+        "requestedPackSize": 0,
+        "approved_quantity": 20,
+        "authoriser_comment": "Reduced, low stock"
+    }"#,
+);
+fn requisition_line_authorised_pull_record() -> TestSyncRecord {
+    TestSyncRecord {
+        translated_record: Some(IntegrationRecord::from_upsert(
+            IntegrationUpsertRecord::RequisitionLine(RequisitionLineRow {
+                id: REQUISITION_LINE_AUTHORISED.0.to_string(),
+                requisition_id: "CC5AA2238EE14654B11B86D52B435FF3".to_string(),
+                item_id: "item_a".to_string(),
+                requested_quantity: 102,
+                suggested_quantity: 101,
+                supply_quantity: 20,
+                available_stock_on_hand: 10,
+                average_monthly_consumption: 3 * NUMBER_OF_DAYS_IN_A_MONTH as i32,
+                comment: None,
+                snapshot_datetime: None,
+                approved_quantity: 20,
+                approval_comment: Some("Reduced, low stock".to_string()),
+                opening_balance: 0,
+                received_quantity: 0,
+                issued_quantity: 0,
+                adjusted_quantity: 0,
+                closing_balance: 0,
+                consumption_calculation_method: ConsumptionCalculationMethod::SimpleAverage,
+            }),
+        )),
+        identifier: "Requisition line authorised",
+        remote_sync_buffer_row: RemoteSyncBufferRow {
+            id: "Requisition_line_30".to_string(),
+            table_name: TRANSLATION_RECORD_REQUISITION_LINE.to_string(),
+            record_id: REQUISITION_LINE_AUTHORISED.0.to_string(),
+            data: REQUISITION_LINE_AUTHORISED.1.to_string(),
+            action: RemoteSyncBufferAction::Update,
+        },
+    }
+}
+fn requisition_line_authorised_push_record() -> TestSyncPushRecord {
+    TestSyncPushRecord {
+        change_log: ChangelogRow {
+            id: 4,
+            table_name: ChangelogTableName::RequisitionLine,
+            row_id: REQUISITION_LINE_AUTHORISED.0.to_string(),
+            row_action: ChangelogAction::Upsert,
+        },
+        push_data: json!(LegacyRequisitionLineRow {
+            ID: REQUISITION_LINE_AUTHORISED.0.to_string(),
+            requisition_ID: "CC5AA2238EE14654B11B86D52B435FF3".to_string(),
+            item_ID: "item_a".to_string(),
+            Cust_stock_order: 102,
+            suggested_quantity: 101,
+            actualQuan: 20,
+            stock_on_hand: 10,
+            daily_usage: 3.0,
+            comment: None,
+            snapshot_datetime: None,
+            approved_quantity: 20,
+            approval_comment: Some("Reduced, low stock".to_string()),
         }),
     }
 }
@@ -187,6 +301,7 @@ pub fn get_test_requisition_line_records() -> Vec<TestSyncRecord> {
     vec![
         requisition_line_request_pull_record(),
         requisition_line_om_fields_pull_record(),
+        requisition_line_authorised_pull_record(),
     ]
 }
 
@@ -195,5 +310,6 @@ pub fn get_test_push_requisition_line_records() -> Vec<TestSyncPushRecord> {
     vec![
         requisition_line_request_push_record(),
         requisition_line_om_fields_push_record(),
+        requisition_line_authorised_push_record(),
     ]
 }
@@ -51,6 +51,11 @@ fn stocktake_pull_record() -> TestSyncRecord {
                 finalised_datetime: None,
                 inventory_adjustment_id: Some("inbound_shipment_a".to_string()),
                 is_locked: false,
+                is_blind_count: false,
+                recount_threshold: None,
+                counted_by: None,
+                count_submitted_datetime: None,
+                verified_by: None,
                 stocktake_date: Some(NaiveDate::from_ymd(2021, 07, 30)),
             }),
         )),
@@ -132,6 +137,11 @@ fn stocktake_om_field_pull_record() -> TestSyncRecord {
                 finalised_datetime: Some(NaiveDate::from_ymd(2021, 07, 31).and_hms(15, 15, 15)),
                 inventory_adjustment_id: Some("inbound_shipment_a".to_string()),
                 is_locked: false,
+                is_blind_count: false,
+                recount_threshold: None,
+                counted_by: None,
+                count_submitted_datetime: None,
+                verified_by: None,
                 stocktake_date: Some(NaiveDate::from_ymd(2021, 07, 30)),
             }),
         )),
@@ -49,6 +49,7 @@ fn stocktake_line_pull_record() -> TestSyncRecord {
                 comment: None,
                 snapshot_number_of_packs: 10,
                 counted_number_of_packs: Some(700),
+                second_counted_number_of_packs: None,
                 item_id: "item_a".to_string(),
                 batch: Some("item_c_batch_a".to_string()),
                 expiry_date: None,
@@ -56,6 +57,7 @@ fn stocktake_line_pull_record() -> TestSyncRecord {
                 cost_price_per_pack: Some(12.0),
                 sell_price_per_pack: Some(15.0),
                 note: None,
+                inventory_adjustment_reason_id: None,
             }),
         )),
         identifier: "Stocktake 1",
@@ -92,6 +94,7 @@ fn stocktake_line_push_record() -> TestSyncPushRecord {
             cost_price: 12.0,
             sell_price: 15.0,
             note: None,
+            option_id: None,
         }),
     }
 }
@@ -133,6 +136,7 @@ fn stocktake_line_om_field_pull_record() -> TestSyncRecord {
                 comment: None,
                 snapshot_number_of_packs: 10,
                 counted_number_of_packs: Some(700),
+                second_counted_number_of_packs: None,
                 item_id: "item_a".to_string(),
                 batch: Some("item_c_batch_a".to_string()),
                 expiry_date: None,
@@ -140,6 +144,7 @@ fn stocktake_line_om_field_pull_record() -> TestSyncRecord {
                 cost_price_per_pack: Some(12.0),
                 sell_price_per_pack: Some(15.0),
                 note: Some("om note".to_string()),
+                inventory_adjustment_reason_id: None,
             }),
         )),
         identifier: "Stocktake om field",
@@ -176,6 +181,7 @@ fn stocktake_line_om_field_push_record() -> TestSyncPushRecord {
             cost_price: 12.0,
             sell_price: 15.0,
             note: Some("om note".to_string()),
+            option_id: None,
         }),
     }
 }
@@ -94,6 +94,9 @@ fn trans_line_1_pull_record() -> TestSyncRecord {
                 r#type: InvoiceLineRowType::StockIn,
                 number_of_packs: 700,
                 note: None,
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             }),
         )),
         identifier: "Transact line 1",
@@ -133,6 +136,9 @@ fn trans_line_1_push_record() -> TestSyncPushRecord {
             tax: None,
             total_before_tax: Some(10.0 * 700.0),
             total_after_tax: Some(10.0 * 700.0),
+            original_invoice_line_id: None,
+            return_reason: None,
+            option_id: None,
         }),
     }
 }
@@ -216,6 +222,9 @@ fn trans_line_2_pull_record() -> TestSyncRecord {
                 r#type: InvoiceLineRowType::StockOut,
                 number_of_packs: 1000,
                 note: Some("every FOUR to SIX hours when necessary ".to_string()),
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             }),
         )),
         identifier: "Transact line (Placeholder)",
@@ -255,6 +264,9 @@ fn trans_line_2_push_record() -> TestSyncPushRecord {
             tax: None,
             total_before_tax: Some(2.0 * 1000.0),
             total_after_tax: Some(2.0 * 1000.0),
+            original_invoice_line_id: None,
+            return_reason: None,
+            option_id: None,
         }),
     }
 }
@@ -341,6 +353,9 @@ fn trans_line_om_fields_pull_record() -> TestSyncRecord {
                 r#type: InvoiceLineRowType::StockOut,
                 number_of_packs: 1000,
                 note: Some("every FOUR to SIX hours when necessary ".to_string()),
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             }),
         )),
         identifier: "Transact line (om fields))",
@@ -380,6 +395,9 @@ fn trans_line_om_fields_push_record() -> TestSyncPushRecord {
             tax: Some(33.3),
             total_before_tax: Some(105.4),
             total_after_tax: Some(130.5),
+            original_invoice_line_id: None,
+            return_reason: None,
+            option_id: None,
         }),
     }
 }
@@ -466,6 +484,9 @@ fn trans_line_om_fields_unset_tax_pull_record() -> TestSyncRecord {
                 r#type: InvoiceLineRowType::StockOut,
                 number_of_packs: 1000,
                 note: Some("every FOUR to SIX hours when necessary ".to_string()),
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             }),
         )),
         identifier: "Transact line (om fields))",
@@ -505,6 +526,9 @@ fn trans_line_om_fields_unset_tax_push_record() -> TestSyncPushRecord {
             tax: None,
             total_before_tax: Some(105.4),
             total_after_tax: Some(130.5),
+            original_invoice_line_id: None,
+            return_reason: None,
+            option_id: None,
         }),
     }
 }
@@ -118,6 +118,9 @@ fn transact_1_pull_record() -> TestSyncRecord {
                 colour: None,
                 requisition_id: None,
                 linked_invoice_id: None,
+                prescriber: None,
+                diagnosis: None,
+                directions: None,
             }),
         )),
         identifier: "Transact 1",
@@ -169,7 +172,10 @@ fn transact_1_push_record() -> TestSyncPushRecord {
             verified_datetime: None,
             om_status: Some(InvoiceRowStatus::Delivered),
             om_type: Some(InvoiceRowType::InboundShipment),
-            om_colour: None
+            om_colour: None,
+            om_prescriber: None,
+            om_diagnosis: None,
+            om_directions: None
         }),
     }
 }
@@ -277,6 +283,9 @@ fn transact_2_pull_record() -> TestSyncRecord {
                 colour: None,
                 requisition_id: None,
                 linked_invoice_id: None,
+                prescriber: None,
+                diagnosis: None,
+                directions: None,
             }),
         )),
         identifier: "Transact 2",
@@ -329,7 +338,10 @@ fn transact_2_push_record() -> TestSyncPushRecord {
             verified_datetime: None,
             om_status: Some(InvoiceRowStatus::Shipped),
             om_type: Some(InvoiceRowType::OutboundShipment),
-            om_colour: None
+            om_colour: None,
+            om_prescriber: None,
+            om_diagnosis: None,
+            om_directions: None
         }),
     }
 }
@@ -445,6 +457,9 @@ fn transact_om_fields_pull_record() -> TestSyncRecord {
                 colour: Some("SomeColour".to_string()),
                 requisition_id: None,
                 linked_invoice_id: None,
+                prescriber: None,
+                diagnosis: None,
+                directions: None,
             }),
         )),
         identifier: "Transact om fields",
@@ -495,7 +510,10 @@ fn transact_om_fields_push_record() -> TestSyncPushRecord {
             verified_datetime: Some(NaiveDate::from_ymd(2022, 8, 29).and_hms(14, 33, 0)),
             om_status: Some(InvoiceRowStatus::Shipped),
             om_type: Some(InvoiceRowType::InventoryAdjustment),
-            om_colour: Some("SomeColour".to_string())
+            om_colour: Some("SomeColour".to_string()),
+            om_prescriber: None,
+            om_diagnosis: None,
+            om_directions: None
         }),
     }
 }
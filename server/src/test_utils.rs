@@ -1,5 +1,7 @@
 use repository::test_db::get_test_db_settings;
-use service::sync_settings::SyncSettings;
+use service::sync_settings::{
+    SyncSettings, DEFAULT_MAX_BACKOFF_SEC, DEFAULT_MAX_JITTER_SEC, DEFAULT_PARTIAL_FAILURE_RETRY_SEC,
+};
 
 use super::settings::{ServerSettings, Settings};
 
@@ -22,6 +24,9 @@ pub fn get_test_settings(db_name: &str) -> Settings {
             central_server_site_id: 0,
             site_id: 1,
             site_hardware_id: "".to_string(),
+            max_backoff_sec: DEFAULT_MAX_BACKOFF_SEC,
+            partial_failure_retry_sec: DEFAULT_PARTIAL_FAILURE_RETRY_SEC,
+            max_jitter_sec: DEFAULT_MAX_JITTER_SEC,
         }),
     }
 }
@@ -0,0 +1,90 @@
+use crate::service_provider::ServiceContext;
+use repository::{AlertConfigRow, AlertConfigRowRepository, RepositoryError, StorageConnection};
+
+pub const DEFAULT_MIN_MONTHS_OF_STOCK: f64 = 3.0;
+pub const DEFAULT_EXPIRY_WARNING_DAYS: i32 = 30;
+pub const DEFAULT_INBOUND_SHIPMENT_OVERDUE_DAYS: i32 = 14;
+
+/// Returns the stored alert config or a disabled config if none has been set for the store
+pub fn get_alert_config(
+    connection: &StorageConnection,
+    store_id: &str,
+) -> Result<AlertConfigRow, RepositoryError> {
+    let config = AlertConfigRowRepository::new(connection)
+        .find_one_by_id(store_id)?
+        .unwrap_or_else(|| AlertConfigRow {
+            id: store_id.to_string(),
+            is_enabled: false,
+            min_months_of_stock: DEFAULT_MIN_MONTHS_OF_STOCK,
+            expiry_warning_days: DEFAULT_EXPIRY_WARNING_DAYS,
+            inbound_shipment_overdue_days: DEFAULT_INBOUND_SHIPMENT_OVERDUE_DAYS,
+        });
+    Ok(config)
+}
+
+#[derive(PartialEq, Debug)]
+pub enum UpdateAlertConfigError {
+    /// Months of stock or number of days are negative
+    ThresholdIsNegative,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct UpdateAlertConfig {
+    pub is_enabled: bool,
+    pub min_months_of_stock: f64,
+    pub expiry_warning_days: i32,
+    pub inbound_shipment_overdue_days: i32,
+}
+
+pub fn update_alert_config(
+    ctx: &ServiceContext,
+    store_id: &str,
+    input: UpdateAlertConfig,
+) -> Result<AlertConfigRow, UpdateAlertConfigError> {
+    let config = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(&input)?;
+            let row = generate(store_id, input);
+            AlertConfigRowRepository::new(connection).upsert_one(&row)?;
+
+            get_alert_config(connection, store_id).map_err(UpdateAlertConfigError::from)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(config)
+}
+
+fn validate(input: &UpdateAlertConfig) -> Result<(), UpdateAlertConfigError> {
+    if input.min_months_of_stock < 0.0
+        || input.expiry_warning_days < 0
+        || input.inbound_shipment_overdue_days < 0
+    {
+        return Err(UpdateAlertConfigError::ThresholdIsNegative);
+    }
+    Ok(())
+}
+
+fn generate(
+    store_id: &str,
+    UpdateAlertConfig {
+        is_enabled,
+        min_months_of_stock,
+        expiry_warning_days,
+        inbound_shipment_overdue_days,
+    }: UpdateAlertConfig,
+) -> AlertConfigRow {
+    AlertConfigRow {
+        id: store_id.to_string(),
+        is_enabled,
+        min_months_of_stock,
+        expiry_warning_days,
+        inbound_shipment_overdue_days,
+    }
+}
+
+impl From<RepositoryError> for UpdateAlertConfigError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateAlertConfigError::DatabaseError(error)
+    }
+}
@@ -0,0 +1,706 @@
+use std::collections::HashMap;
+
+use super::config::get_alert_config;
+use crate::{
+    consumption_calculator::get_consumption_calculator, item_stats::get_item_stats_with_calculator,
+    service_provider::ServiceContext,
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use repository::{
+    AlertConfigRow, AlertConfigRowRepository, AlertFilter, AlertRepository, AlertRow,
+    AlertRowRepository, AlertStatus, AlertType, DateFilter, DatetimeFilter, EqualFilter,
+    InvoiceFilter, InvoiceRepository, InvoiceRowStatus, InvoiceRowType, ItemRowRepository,
+    RepositoryError, StockLineFilter, StockLineRepository, StorageConnection,
+};
+use util::uuid::uuid;
+
+#[derive(Debug, PartialEq, Default)]
+pub struct AlertEvaluation {
+    pub number_of_new_alerts: u32,
+    pub number_of_resolved_alerts: u32,
+}
+
+/// Condition found by an alert rule, matched with the unresolved alerts of the store by type and
+/// the record it is about
+#[derive(Debug, PartialEq)]
+struct AlertCondition {
+    r#type: AlertType,
+    item_id: Option<String>,
+    stock_line_id: Option<String>,
+    invoice_id: Option<String>,
+    message: String,
+}
+
+impl AlertCondition {
+    fn is_raised_by(&self, alert: &AlertRow) -> bool {
+        self.r#type == alert.r#type
+            && self.item_id == alert.item_id
+            && self.stock_line_id == alert.stock_line_id
+            && self.invoice_id == alert.invoice_id
+    }
+}
+
+/// Evaluates the alert rules of the store, even if alerts are not enabled for the store.
+///
+/// New alerts are raised for rules that apply, unresolved alerts of rules that no longer apply
+/// are resolved. Alerts that are still unresolved keep their status, i.e. a dismissed alert is not
+/// raised again until its rule stopped applying.
+pub fn evaluate_alerts(
+    ctx: &ServiceContext,
+    store_id: &str,
+) -> Result<AlertEvaluation, RepositoryError> {
+    evaluate_alerts_at(ctx, store_id, Utc::now().naive_utc())
+}
+
+/// Evaluates the alert rules of every store with enabled alerts, returns the outcome per store
+pub fn evaluate_enabled_alerts(
+    ctx: &ServiceContext,
+    now: NaiveDateTime,
+) -> Result<Vec<(String, Result<AlertEvaluation, RepositoryError>)>, RepositoryError> {
+    let configs = AlertConfigRowRepository::new(&ctx.connection).find_all_enabled()?;
+
+    Ok(configs
+        .into_iter()
+        .map(|config| {
+            let result = evaluate_alerts_at(ctx, &config.id, now);
+            (config.id, result)
+        })
+        .collect())
+}
+
+fn evaluate_alerts_at(
+    ctx: &ServiceContext,
+    store_id: &str,
+    now: NaiveDateTime,
+) -> Result<AlertEvaluation, RepositoryError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            let config = get_alert_config(connection, store_id)?;
+            let mut conditions = generate_conditions(connection, &config, now)?;
+
+            let repository = AlertRowRepository::new(connection);
+            let unresolved_alerts = AlertRepository::new(connection).query_by_filter(
+                AlertFilter::new()
+                    .store_id(EqualFilter::equal_to(store_id))
+                    .is_resolved(false),
+            )?;
+
+            let mut result = AlertEvaluation::default();
+            for alert in unresolved_alerts {
+                let updated_alert = match conditions
+                    .iter()
+                    .position(|condition| condition.is_raised_by(&alert))
+                {
+                    Some(index) => {
+                        let condition = conditions.remove(index);
+                        if condition.message == alert.message {
+                            continue;
+                        }
+                        AlertRow {
+                            message: condition.message,
+                            ..alert
+                        }
+                    }
+                    None => {
+                        result.number_of_resolved_alerts += 1;
+                        AlertRow {
+                            resolved_datetime: Some(now),
+                            ..alert
+                        }
+                    }
+                };
+                repository.upsert_one(&updated_alert)?;
+            }
+
+            for condition in conditions {
+                repository.upsert_one(&AlertRow {
+                    id: uuid(),
+                    store_id: store_id.to_string(),
+                    r#type: condition.r#type,
+                    status: AlertStatus::Active,
+                    item_id: condition.item_id,
+                    stock_line_id: condition.stock_line_id,
+                    invoice_id: condition.invoice_id,
+                    message: condition.message,
+                    created_datetime: now,
+                    user_id: None,
+                    acknowledged_datetime: None,
+                    dismissed_datetime: None,
+                    resolved_datetime: None,
+                })?;
+                result.number_of_new_alerts += 1;
+            }
+
+            Ok(result)
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+fn generate_conditions(
+    connection: &StorageConnection,
+    config: &AlertConfigRow,
+    now: NaiveDateTime,
+) -> Result<Vec<AlertCondition>, RepositoryError> {
+    let store_id = &config.id;
+    let mut conditions = Vec::new();
+
+    // Low stock and stock out, only for items that are in use (have consumption)
+    let calculator = get_consumption_calculator(connection, store_id)?;
+    let item_stats: Vec<_> =
+        get_item_stats_with_calculator(connection, store_id, calculator.as_ref(), None, None)?
+            .into_iter()
+            .filter(|item_stats| item_stats.average_monthly_consumption > 0.0)
+            .collect();
+    let item_ids: Vec<String> = item_stats
+        .iter()
+        .map(|item_stats| item_stats.item_id.clone())
+        .collect();
+    let item_names = get_item_names(connection, &item_ids)?;
+
+    for item_stats in item_stats {
+        let item_name = item_names
+            .get(&item_stats.item_id)
+            .unwrap_or(&item_stats.item_id);
+        let months_of_stock =
+            item_stats.available_stock_on_hand as f64 / item_stats.average_monthly_consumption;
+
+        let (r#type, message) = if item_stats.available_stock_on_hand == 0 {
+            (
+                AlertType::StockOut,
+                format!("{} is out of stock", item_name),
+            )
+        } else if months_of_stock < config.min_months_of_stock {
+            (
+                AlertType::LowStock,
+                format!(
+                    "{} has {:.1} months of stock, below the minimum of {}",
+                    item_name, months_of_stock, config.min_months_of_stock
+                ),
+            )
+        } else {
+            continue;
+        };
+
+        conditions.push(AlertCondition {
+            r#type,
+            item_id: Some(item_stats.item_id),
+            stock_line_id: None,
+            invoice_id: None,
+            message,
+        });
+    }
+
+    // Expired and expiring batches that are still in stock
+    let today = now.date();
+    let stock_lines: Vec<_> = StockLineRepository::new(connection)
+        .query_by_filter(
+            StockLineFilter::new()
+                .store_id(EqualFilter::equal_to(store_id))
+                .expiry_date(DateFilter::before_or_equal_to(
+                    today + Duration::days(config.expiry_warning_days as i64),
+                )),
+        )?
+        .into_iter()
+        .map(|stock_line| stock_line.stock_line_row)
+        .filter(|stock_line| stock_line.total_number_of_packs > 0)
+        .collect();
+    let item_ids: Vec<String> = stock_lines
+        .iter()
+        .map(|stock_line| stock_line.item_id.clone())
+        .collect();
+    let item_names = get_item_names(connection, &item_ids)?;
+
+    for stock_line in stock_lines {
+        let expiry_date = match stock_line.expiry_date {
+            Some(expiry_date) => expiry_date,
+            None => continue,
+        };
+        let item_name = item_names
+            .get(&stock_line.item_id)
+            .unwrap_or(&stock_line.item_id);
+        let batch = stock_line
+            .batch
+            .as_deref()
+            .unwrap_or("without batch number");
+        let message = if expiry_date < today {
+            format!(
+                "Batch {} of {} expired on {}",
+                batch, item_name, expiry_date
+            )
+        } else {
+            format!(
+                "Batch {} of {} expires on {}",
+                batch, item_name, expiry_date
+            )
+        };
+
+        conditions.push(AlertCondition {
+            r#type: AlertType::ExpiringStock,
+            item_id: Some(stock_line.item_id),
+            stock_line_id: Some(stock_line.id),
+            invoice_id: None,
+            message,
+        });
+    }
+
+    // Inbound shipments that were shipped by the supplier but not received in time
+    let overdue_invoices = InvoiceRepository::new(connection).query_by_filter(
+        InvoiceFilter::new()
+            .store_id(EqualFilter::equal_to(store_id))
+            .r#type(InvoiceRowType::InboundShipment.equal_to())
+            .status(InvoiceRowStatus::Shipped.equal_to())
+            .shipped_datetime(DatetimeFilter::before_or_equal_to(
+                now - Duration::days(config.inbound_shipment_overdue_days as i64),
+            )),
+    )?;
+
+    for invoice in overdue_invoices {
+        let shipped_date = invoice
+            .invoice_row
+            .shipped_datetime
+            .map(|datetime| datetime.date().to_string())
+            .unwrap_or_default();
+        conditions.push(AlertCondition {
+            r#type: AlertType::InboundShipmentNotReceived,
+            item_id: None,
+            stock_line_id: None,
+            invoice_id: Some(invoice.invoice_row.id.clone()),
+            message: format!(
+                "Inbound shipment {} from {} was shipped on {} and has not been received",
+                invoice.invoice_row.invoice_number,
+                invoice.other_party_name(),
+                shipped_date
+            ),
+        });
+    }
+
+    Ok(conditions)
+}
+
+fn get_item_names(
+    connection: &StorageConnection,
+    item_ids: &[String],
+) -> Result<HashMap<String, String>, RepositoryError> {
+    Ok(ItemRowRepository::new(connection)
+        .find_many_by_id(item_ids)?
+        .into_iter()
+        .map(|item| (item.id, item.name))
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, Utc};
+    use repository::{
+        mock::{
+            mock_name_a, mock_store_a, mock_store_b, test_item_stats, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        AlertFilter, AlertRow, AlertStatus, AlertType, EqualFilter, InvoiceRow, InvoiceRowStatus,
+        InvoiceRowType, ItemRow, ItemRowType, StockLineRow, StockLineRowRepository,
+    };
+    use util::inline_init;
+
+    use crate::{
+        alert::{
+            config::{UpdateAlertConfig, UpdateAlertConfigError},
+            evaluate::evaluate_enabled_alerts,
+            update::UpdateAlertError,
+        },
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn evaluate_alerts() {
+        fn expiring_stock_line() -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = "expiring_stock_line".to_string();
+                r.item_id = test_item_stats::item().id;
+                r.store_id = mock_store_a().id;
+                r.batch = Some("B1".to_string());
+                r.pack_size = 1;
+                r.available_number_of_packs = 0;
+                r.total_number_of_packs = 10;
+                r.expiry_date = Some(Utc::now().naive_utc().date() + Duration::days(10));
+            })
+        }
+
+        fn overdue_inbound_shipment() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "overdue_inbound_shipment".to_string();
+                r.name_id = mock_name_a().id;
+                r.store_id = mock_store_a().id;
+                r.r#type = InvoiceRowType::InboundShipment;
+                r.status = InvoiceRowStatus::Shipped;
+                r.shipped_datetime = Some(Utc::now().naive_utc() - Duration::days(20));
+            })
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "evaluate_alerts",
+            MockDataInserts::all(),
+            test_item_stats::mock_item_stats().join(inline_init(|r: &mut MockData| {
+                r.stock_lines = vec![expiring_stock_line()];
+                r.invoices = vec![overdue_inbound_shipment()];
+            })),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.alert_service;
+        let store_id = mock_store_a().id;
+
+        // Alerts are raised for the rules that apply
+        let evaluation = service.evaluate_alerts(&context, &store_id).unwrap();
+        assert!(evaluation.number_of_new_alerts > 0);
+        let alerts = service
+            .get_alerts(
+                &context,
+                &store_id,
+                None,
+                Some(AlertFilter::new().is_resolved(false)),
+                None,
+            )
+            .unwrap()
+            .rows;
+        let low_stock = alerts
+            .iter()
+            .find(|alert| {
+                alert.r#type == AlertType::LowStock
+                    && alert.item_id == Some(test_item_stats::item().id)
+            })
+            .unwrap();
+        assert_eq!(low_stock.status, AlertStatus::Active);
+        let expiring_stock = alerts
+            .iter()
+            .find(|alert| alert.stock_line_id == Some(expiring_stock_line().id))
+            .unwrap();
+        assert_eq!(expiring_stock.r#type, AlertType::ExpiringStock);
+        assert!(expiring_stock.message.contains("B1"));
+        let not_received = alerts
+            .iter()
+            .find(|alert| alert.invoice_id == Some(overdue_inbound_shipment().id))
+            .unwrap();
+        assert_eq!(not_received.r#type, AlertType::InboundShipmentNotReceived);
+
+        // Alerts are not raised again while the rules still apply
+        assert_eq!(
+            service
+                .evaluate_alerts(&context, &store_id)
+                .unwrap()
+                .number_of_new_alerts,
+            0
+        );
+
+        // Acknowledge and dismiss
+        assert_eq!(
+            service.acknowledge_alert(&context, &store_id, "user", "invalid"),
+            Err(UpdateAlertError::AlertDoesNotExist)
+        );
+        assert_eq!(
+            service.dismiss_alert(&context, &mock_store_b().id, "user", &low_stock.id),
+            Err(UpdateAlertError::NotThisStoreAlert)
+        );
+        let acknowledged = service
+            .acknowledge_alert(&context, &store_id, "user", &low_stock.id)
+            .unwrap();
+        assert_eq!(acknowledged.status, AlertStatus::Acknowledged);
+        assert_eq!(acknowledged.user_id, Some("user".to_string()));
+        assert!(acknowledged.acknowledged_datetime.is_some());
+        assert_eq!(
+            service.acknowledge_alert(&context, &store_id, "user", &low_stock.id),
+            Err(UpdateAlertError::AlertAlreadyAcknowledged)
+        );
+        let dismissed = service
+            .dismiss_alert(&context, &store_id, "user", &expiring_stock.id)
+            .unwrap();
+        assert_eq!(dismissed.status, AlertStatus::Dismissed);
+        assert_eq!(
+            service.dismiss_alert(&context, &store_id, "user", &expiring_stock.id),
+            Err(UpdateAlertError::AlertAlreadyDismissed)
+        );
+        assert_eq!(
+            service
+                .evaluate_alerts(&context, &store_id)
+                .unwrap()
+                .number_of_new_alerts,
+            0
+        );
+
+        // Low stock is resolved once the item is out of stock
+        let stock_line_repo = StockLineRowRepository::new(&connection);
+        for stock_line in [
+            test_item_stats::stock_line1(),
+            test_item_stats::stock_line2(),
+            test_item_stats::stock_line3(),
+        ] {
+            stock_line_repo
+                .upsert_one(&StockLineRow {
+                    available_number_of_packs: 0,
+                    ..stock_line
+                })
+                .unwrap();
+        }
+        let evaluation = service.evaluate_alerts(&context, &store_id).unwrap();
+        assert_eq!(evaluation.number_of_new_alerts, 1);
+        assert_eq!(evaluation.number_of_resolved_alerts, 1);
+        let item_alerts = service
+            .get_alerts(
+                &context,
+                &store_id,
+                None,
+                Some(
+                    AlertFilter::new().item_id(EqualFilter::equal_to(&test_item_stats::item().id)),
+                ),
+                None,
+            )
+            .unwrap()
+            .rows;
+        let resolved_low_stock = item_alerts
+            .iter()
+            .find(|alert| alert.id == low_stock.id)
+            .unwrap();
+        assert!(resolved_low_stock.resolved_datetime.is_some());
+        assert_eq!(resolved_low_stock.status, AlertStatus::Acknowledged);
+        let stock_out = item_alerts
+            .iter()
+            .find(|alert| alert.r#type == AlertType::StockOut)
+            .unwrap();
+        assert_eq!(stock_out.status, AlertStatus::Active);
+        assert_eq!(stock_out.resolved_datetime, None);
+
+        // Only stores with enabled alerts are evaluated by the schedule
+        assert_eq!(
+            evaluate_enabled_alerts(&context, Utc::now().naive_utc())
+                .unwrap()
+                .len(),
+            0
+        );
+        assert_eq!(
+            service.update_alert_config(
+                &context,
+                &store_id,
+                UpdateAlertConfig {
+                    is_enabled: true,
+                    min_months_of_stock: 3.0,
+                    expiry_warning_days: -1,
+                    inbound_shipment_overdue_days: 14,
+                },
+            ),
+            Err(UpdateAlertConfigError::ThresholdIsNegative)
+        );
+        service
+            .update_alert_config(
+                &context,
+                &store_id,
+                UpdateAlertConfig {
+                    is_enabled: true,
+                    min_months_of_stock: 3.0,
+                    expiry_warning_days: 30,
+                    inbound_shipment_overdue_days: 14,
+                },
+            )
+            .unwrap();
+        let results = evaluate_enabled_alerts(&context, Utc::now().naive_utc()).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, store_id);
+    }
+
+    #[actix_rt::test]
+    async fn evaluate_alert_thresholds() {
+        fn item_no_stats() -> ItemRow {
+            inline_init(|r: &mut ItemRow| {
+                r.id = "alert_item_no_stats".to_string();
+                r.name = r.id.clone();
+                r.code = r.id.clone();
+                r.r#type = ItemRowType::Stock;
+            })
+        }
+        fn item_zero_consumption() -> ItemRow {
+            inline_init(|r: &mut ItemRow| {
+                r.id = "alert_item_zero_consumption".to_string();
+                r.name = r.id.clone();
+                r.code = r.id.clone();
+                r.r#type = ItemRowType::Stock;
+            })
+        }
+        fn stock_line_zero_consumption() -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = "alert_stock_line_zero_consumption".to_string();
+                r.item_id = item_zero_consumption().id;
+                r.store_id = mock_store_a().id;
+                r.pack_size = 1;
+                r.available_number_of_packs = 5;
+                r.total_number_of_packs = 5;
+            })
+        }
+        fn expiring_stock_line() -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = "alert_expiring_stock_line".to_string();
+                r.item_id = item_zero_consumption().id;
+                r.store_id = mock_store_a().id;
+                r.pack_size = 1;
+                r.total_number_of_packs = 10;
+                r.expiry_date = Some(Utc::now().naive_utc().date() + Duration::days(10));
+            })
+        }
+        fn expired_empty_stock_line() -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = "alert_expired_empty_stock_line".to_string();
+                r.item_id = item_zero_consumption().id;
+                r.store_id = mock_store_a().id;
+                r.pack_size = 1;
+                r.total_number_of_packs = 0;
+                r.expiry_date = Some(Utc::now().naive_utc().date() - Duration::days(10));
+            })
+        }
+        fn shipped_inbound_shipment() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "alert_shipped_inbound_shipment".to_string();
+                r.name_id = mock_name_a().id;
+                r.store_id = mock_store_a().id;
+                r.r#type = InvoiceRowType::InboundShipment;
+                r.status = InvoiceRowStatus::Shipped;
+                r.shipped_datetime = Some(Utc::now().naive_utc() - Duration::days(10));
+            })
+        }
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "evaluate_alert_thresholds",
+            MockDataInserts::all(),
+            test_item_stats::mock_item_stats().join(inline_init(|r: &mut MockData| {
+                r.items = vec![item_no_stats(), item_zero_consumption()];
+                r.stock_lines = vec![
+                    stock_line_zero_consumption(),
+                    expiring_stock_line(),
+                    expired_empty_stock_line(),
+                ];
+                r.invoices = vec![shipped_inbound_shipment()];
+            })),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.alert_service;
+        let store_id = mock_store_a().id;
+        let unresolved_alerts = || -> Vec<AlertRow> {
+            service
+                .get_alerts(
+                    &context,
+                    &store_id,
+                    None,
+                    Some(AlertFilter::new().is_resolved(false)),
+                    None,
+                )
+                .unwrap()
+                .rows
+        };
+        // Expiring stock and shipment alerts are matched by their stock line or invoice
+        let has_record_alert = |alerts: &[AlertRow], record_id: &str| {
+            alerts.iter().any(|alert| {
+                alert.stock_line_id.as_deref() == Some(record_id)
+                    || alert.invoice_id.as_deref() == Some(record_id)
+            })
+        };
+        let has_alert = |alerts: &[AlertRow], r#type: AlertType, item_id: &str| {
+            alerts
+                .iter()
+                .any(|alert| alert.r#type == r#type && alert.item_id.as_deref() == Some(item_id))
+        };
+
+        // item: ~0.65 months of stock, item2: 2.2 months of stock, the expiring batch is outside
+        // of the warning period and the shipment isn't overdue yet
+        let config = UpdateAlertConfig {
+            is_enabled: false,
+            min_months_of_stock: 2.0,
+            expiry_warning_days: 5,
+            inbound_shipment_overdue_days: 14,
+        };
+        service
+            .update_alert_config(&context, &store_id, config.clone())
+            .unwrap();
+        service.evaluate_alerts(&context, &store_id).unwrap();
+        let alerts = unresolved_alerts();
+        assert!(has_alert(
+            &alerts,
+            AlertType::LowStock,
+            &test_item_stats::item().id
+        ));
+        assert!(!has_alert(
+            &alerts,
+            AlertType::LowStock,
+            &test_item_stats::item2().id
+        ));
+        // Items without stats or consumption are not in use and don't raise stock alerts
+        assert!(!has_alert(
+            &alerts,
+            AlertType::StockOut,
+            &item_no_stats().id
+        ));
+        assert!(!has_alert(
+            &alerts,
+            AlertType::LowStock,
+            &item_zero_consumption().id
+        ));
+        assert!(!has_record_alert(&alerts, &expiring_stock_line().id));
+        assert!(!has_record_alert(&alerts, &shipped_inbound_shipment().id));
+
+        // Disabled stores are not evaluated by the schedule
+        assert_eq!(
+            evaluate_enabled_alerts(&context, Utc::now().naive_utc())
+                .unwrap()
+                .len(),
+            0
+        );
+
+        // Thresholds are raised: item2 is low on stock, the batch is in the warning period (the
+        // empty expired batch is ignored) and the shipment is overdue
+        let raised_config = UpdateAlertConfig {
+            min_months_of_stock: 3.0,
+            expiry_warning_days: 30,
+            inbound_shipment_overdue_days: 7,
+            ..config.clone()
+        };
+        service
+            .update_alert_config(&context, &store_id, raised_config)
+            .unwrap();
+        service.evaluate_alerts(&context, &store_id).unwrap();
+        let alerts = unresolved_alerts();
+        assert!(has_alert(
+            &alerts,
+            AlertType::LowStock,
+            &test_item_stats::item2().id
+        ));
+        assert!(has_record_alert(&alerts, &expiring_stock_line().id));
+        assert!(!has_record_alert(&alerts, &expired_empty_stock_line().id));
+        assert!(has_record_alert(&alerts, &shipped_inbound_shipment().id));
+
+        // The open low stock alert of item is updated with the new minimum, not raised again
+        let low_stock_item = alerts
+            .iter()
+            .filter(|alert| {
+                alert.r#type == AlertType::LowStock
+                    && alert.item_id == Some(test_item_stats::item().id)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(low_stock_item.len(), 1);
+        assert!(low_stock_item[0].message.contains("minimum of 3"));
+
+        // Lowering the thresholds again resolves the alerts
+        service
+            .update_alert_config(&context, &store_id, config)
+            .unwrap();
+        let evaluation = service.evaluate_alerts(&context, &store_id).unwrap();
+        assert_eq!(evaluation.number_of_new_alerts, 0);
+        let alerts = unresolved_alerts();
+        assert!(!has_alert(
+            &alerts,
+            AlertType::LowStock,
+            &test_item_stats::item2().id
+        ));
+        assert!(!has_record_alert(&alerts, &expiring_stock_line().id));
+        assert!(!has_record_alert(&alerts, &shipped_inbound_shipment().id));
+    }
+}
@@ -0,0 +1,77 @@
+use self::{
+    config::{get_alert_config, update_alert_config, UpdateAlertConfig, UpdateAlertConfigError},
+    evaluate::{evaluate_alerts, AlertEvaluation},
+    query::get_alerts,
+    update::{acknowledge_alert, dismiss_alert, UpdateAlertError},
+};
+
+use crate::{service_provider::ServiceContext, ListError, ListResult};
+use repository::{
+    Alert, AlertConfigRow, AlertFilter, AlertSort, PaginationOption, RepositoryError,
+};
+
+pub mod config;
+pub mod evaluate;
+pub mod query;
+pub mod update;
+
+pub trait AlertServiceTrait: Sync + Send {
+    fn get_alerts(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        pagination: Option<PaginationOption>,
+        filter: Option<AlertFilter>,
+        sort: Option<AlertSort>,
+    ) -> Result<ListResult<Alert>, ListError> {
+        get_alerts(ctx, store_id, pagination, filter, sort)
+    }
+
+    fn acknowledge_alert(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        id: &str,
+    ) -> Result<Alert, UpdateAlertError> {
+        acknowledge_alert(ctx, store_id, user_id, id)
+    }
+
+    fn dismiss_alert(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        id: &str,
+    ) -> Result<Alert, UpdateAlertError> {
+        dismiss_alert(ctx, store_id, user_id, id)
+    }
+
+    fn evaluate_alerts(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<AlertEvaluation, RepositoryError> {
+        evaluate_alerts(ctx, store_id)
+    }
+
+    fn get_alert_config(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<AlertConfigRow, RepositoryError> {
+        get_alert_config(&ctx.connection, store_id)
+    }
+
+    fn update_alert_config(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        input: UpdateAlertConfig,
+    ) -> Result<AlertConfigRow, UpdateAlertConfigError> {
+        update_alert_config(ctx, store_id, input)
+    }
+}
+
+pub struct AlertService {}
+impl AlertServiceTrait for AlertService {}
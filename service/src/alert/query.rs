@@ -0,0 +1,27 @@
+use repository::{Alert, AlertFilter, AlertRepository, AlertSort, EqualFilter, PaginationOption};
+
+use crate::{
+    get_default_pagination, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
+};
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+pub fn get_alerts(
+    ctx: &ServiceContext,
+    store_id: &str,
+    pagination: Option<PaginationOption>,
+    filter: Option<AlertFilter>,
+    sort: Option<AlertSort>,
+) -> Result<ListResult<Alert>, ListError> {
+    let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+    let filter = filter
+        .unwrap_or_default()
+        .store_id(EqualFilter::equal_to(store_id));
+    let repository = AlertRepository::new(&ctx.connection);
+
+    Ok(ListResult {
+        rows: repository.query(pagination, Some(filter.clone()), sort)?,
+        count: i64_to_u32(repository.count(Some(filter))?),
+    })
+}
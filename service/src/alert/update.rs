@@ -0,0 +1,90 @@
+use crate::service_provider::ServiceContext;
+use chrono::Utc;
+use repository::{Alert, AlertRow, AlertRowRepository, AlertStatus, RepositoryError};
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateAlertError {
+    AlertDoesNotExist,
+    NotThisStoreAlert,
+    AlertAlreadyAcknowledged,
+    AlertAlreadyDismissed,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = UpdateAlertError;
+
+/// Marks an active alert as seen, it stays in the inbox until it is dismissed
+pub fn acknowledge_alert(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    id: &str,
+) -> Result<Alert, OutError> {
+    update_alert(ctx, store_id, id, |alert| {
+        match alert.status {
+            AlertStatus::Active => {}
+            AlertStatus::Acknowledged => return Err(OutError::AlertAlreadyAcknowledged),
+            AlertStatus::Dismissed => return Err(OutError::AlertAlreadyDismissed),
+        }
+        Ok(AlertRow {
+            status: AlertStatus::Acknowledged,
+            user_id: Some(user_id.to_string()),
+            acknowledged_datetime: Some(Utc::now().naive_utc()),
+            ..alert
+        })
+    })
+}
+
+/// Removes an alert from the inbox, the alert isn't raised again while its rule still applies
+pub fn dismiss_alert(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    id: &str,
+) -> Result<Alert, OutError> {
+    update_alert(ctx, store_id, id, |alert| {
+        if alert.status == AlertStatus::Dismissed {
+            return Err(OutError::AlertAlreadyDismissed);
+        }
+        Ok(AlertRow {
+            status: AlertStatus::Dismissed,
+            user_id: Some(user_id.to_string()),
+            dismissed_datetime: Some(Utc::now().naive_utc()),
+            ..alert
+        })
+    })
+}
+
+fn update_alert<F>(
+    ctx: &ServiceContext,
+    store_id: &str,
+    id: &str,
+    generate: F,
+) -> Result<Alert, OutError>
+where
+    F: Fn(AlertRow) -> Result<AlertRow, OutError>,
+{
+    let alert = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let repository = AlertRowRepository::new(connection);
+            let alert = repository
+                .find_one_by_id(id)?
+                .ok_or(OutError::AlertDoesNotExist)?;
+            if alert.store_id != store_id {
+                return Err(OutError::NotThisStoreAlert);
+            }
+
+            let updated_alert = generate(alert)?;
+            repository.upsert_one(&updated_alert)?;
+            Ok(updated_alert)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(alert)
+}
+
+impl From<RepositoryError> for UpdateAlertError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateAlertError::DatabaseError(error)
+    }
+}
@@ -0,0 +1,663 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use crate::{number::next_number, service_provider::ServiceContext, validate::get_other_party};
+use chrono::Utc;
+use repository::{
+    Backorder, BackorderFilter, BackorderRepository, BackorderShipmentRow,
+    BackorderShipmentRowRepository, EqualFilter, Invoice, InvoiceFilter, InvoiceLineFilter,
+    InvoiceLineRepository, InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType,
+    InvoiceRepository, InvoiceRow, InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType,
+    ItemRowRepository, Name, NumberRowType, Pagination, RepositoryError, StockLineFilter,
+    StockLineRepository, StorageConnection,
+};
+use util::uuid::uuid;
+
+#[derive(Debug, PartialEq)]
+pub struct CreateBackorderShipment {
+    /// Customer to supply
+    pub name_id: String,
+    /// Backorders to supply, all open backorders of the customer if not set
+    pub backorder_ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CreateBackorderShipmentError {
+    OtherPartyDoesNotExist,
+    /// Backorder doesn't exist in this store or isn't for this customer
+    BackorderDoesNotExist,
+    NoOpenBackorders,
+    /// None of the backordered items have available stock in the store
+    NoStockAvailable,
+    ProblemFindingItem,
+    CreatedInvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = CreateBackorderShipmentError;
+
+/// Creates an outbound shipment to the customer with placeholder lines for its open backorders.
+///
+/// Backorders are supplied oldest first, limited by the available stock of the item in the store.
+/// Quantity already placed on follow-up shipments that haven't been shipped is not supplied again.
+/// The backorders are linked to the shipment and their remaining quantity is reduced once the
+/// shipment is shipped, deleting the shipment before then releases the quantity.
+pub fn create_backorder_shipment(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    input: CreateBackorderShipment,
+) -> Result<Invoice, OutError> {
+    let invoice = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (other_party, backorders) = validate(connection, store_id, &input)?;
+            let (invoice_row, invoice_line_rows, backorder_shipment_rows) =
+                generate(connection, store_id, user_id, other_party, backorders)?;
+
+            InvoiceRowRepository::new(connection).upsert_one(&invoice_row)?;
+
+            let invoice_line_repository = InvoiceLineRowRepository::new(connection);
+            for row in invoice_line_rows {
+                invoice_line_repository.upsert_one(&row)?;
+            }
+
+            let backorder_shipment_repository = BackorderShipmentRowRepository::new(connection);
+            for row in backorder_shipment_rows {
+                backorder_shipment_repository.upsert_one(&row)?;
+            }
+
+            let mut result = InvoiceRepository::new(connection)
+                .query_by_filter(InvoiceFilter::new().id(EqualFilter::equal_to(&invoice_row.id)))?;
+
+            result.pop().ok_or(OutError::CreatedInvoiceDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(invoice)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &CreateBackorderShipment,
+) -> Result<(Name, Vec<(Backorder, i32)>), OutError> {
+    let other_party = get_other_party(connection, store_id, &input.name_id)?
+        .ok_or(OutError::OtherPartyDoesNotExist)?;
+
+    let mut filter = BackorderFilter::new()
+        .store_id(EqualFilter::equal_to(store_id))
+        .name_id(EqualFilter::equal_to(&input.name_id));
+    if let Some(backorder_ids) = &input.backorder_ids {
+        filter = filter.id(EqualFilter::equal_any(backorder_ids.clone()));
+    }
+    let backorders =
+        BackorderRepository::new(connection).query(Pagination::all(), Some(filter), None)?;
+
+    if let Some(backorder_ids) = &input.backorder_ids {
+        let all_found = backorder_ids
+            .iter()
+            .all(|id| backorders.iter().any(|backorder| &backorder.id == id));
+        if !all_found {
+            return Err(OutError::BackorderDoesNotExist);
+        }
+    }
+
+    let pending_quantities = get_pending_quantities(connection, &backorders)?;
+    let open_backorders: Vec<(Backorder, i32)> = backorders
+        .into_iter()
+        .map(|backorder| {
+            let pending = pending_quantities.get(&backorder.id).copied().unwrap_or(0);
+            let open_quantity = backorder.remaining_quantity - pending;
+            (backorder, open_quantity)
+        })
+        .filter(|(_, open_quantity)| *open_quantity > 0)
+        .collect();
+    if open_backorders.is_empty() {
+        return Err(OutError::NoOpenBackorders);
+    }
+
+    Ok((other_party, open_backorders))
+}
+
+fn generate(
+    connection: &StorageConnection,
+    store_id: &str,
+    user_id: &str,
+    other_party: Name,
+    backorders: Vec<(Backorder, i32)>,
+) -> Result<(InvoiceRow, Vec<InvoiceLineRow>, Vec<BackorderShipmentRow>), OutError> {
+    let invoice_id = uuid();
+
+    // Quantity per item, keeping the order in which items were first backordered
+    let mut supplied_quantities: Vec<(String, i32)> = Vec::new();
+    let mut available_stock: HashMap<String, i32> = HashMap::new();
+    let mut backorder_shipment_rows = Vec::new();
+    for (backorder, open_quantity) in backorders {
+        let available = match available_stock.entry(backorder.item_id.clone()) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(get_available_stock(
+                connection,
+                store_id,
+                &backorder.item_id,
+            )?),
+        };
+
+        let quantity = open_quantity.min(*available);
+        if quantity <= 0 {
+            continue;
+        }
+        *available -= quantity;
+
+        match supplied_quantities
+            .iter_mut()
+            .find(|(item_id, _)| item_id == &backorder.item_id)
+        {
+            Some((_, supplied)) => *supplied += quantity,
+            None => supplied_quantities.push((backorder.item_id.clone(), quantity)),
+        }
+        backorder_shipment_rows.push(BackorderShipmentRow {
+            id: uuid(),
+            backorder_id: backorder.id,
+            invoice_id: invoice_id.clone(),
+            quantity,
+        });
+    }
+
+    if supplied_quantities.is_empty() {
+        return Err(OutError::NoStockAvailable);
+    }
+
+    let invoice_row = InvoiceRow {
+        id: invoice_id.clone(),
+        user_id: Some(user_id.to_string()),
+        name_id: other_party.name_row.id.clone(),
+        name_store_id: other_party.store_id().map(|id| id.to_string()),
+        store_id: store_id.to_owned(),
+        invoice_number: next_number(connection, &NumberRowType::OutboundShipment, store_id)?,
+        r#type: InvoiceRowType::OutboundShipment,
+        status: InvoiceRowStatus::New,
+        created_datetime: Utc::now().naive_utc(),
+        comment: Some("Backorder".to_string()),
+
+        // Default
+        requisition_id: None,
+        on_hold: false,
+        their_reference: None,
+        transport_reference: None,
+        allocated_datetime: None,
+        picked_datetime: None,
+        shipped_datetime: None,
+        delivered_datetime: None,
+        verified_datetime: None,
+        colour: None,
+        linked_invoice_id: None,
+        prescriber: None,
+        diagnosis: None,
+        directions: None,
+    };
+
+    let mut invoice_line_rows = Vec::new();
+    for (item_id, quantity) in supplied_quantities {
+        let item_row = ItemRowRepository::new(connection)
+            .find_one_by_id(&item_id)?
+            .ok_or(OutError::ProblemFindingItem)?;
+
+        invoice_line_rows.push(InvoiceLineRow {
+            id: uuid(),
+            invoice_id: invoice_id.clone(),
+            pack_size: 1,
+            number_of_packs: quantity,
+            item_id: item_row.id,
+            item_code: item_row.code,
+            item_name: item_row.name,
+            r#type: InvoiceLineRowType::UnallocatedStock,
+
+            // Default
+            total_before_tax: 0.0,
+            total_after_tax: 0.0,
+            tax: None,
+            note: None,
+            location_id: None,
+            batch: None,
+            expiry_date: None,
+            sell_price_per_pack: 0.0,
+            cost_price_per_pack: 0.0,
+            stock_line_id: None,
+            original_invoice_line_id: None,
+            return_reason: None,
+            inventory_adjustment_reason_id: None,
+        });
+    }
+
+    Ok((invoice_row, invoice_line_rows, backorder_shipment_rows))
+}
+
+/// Quantity of each backorder on follow-up shipments that haven't been shipped yet
+fn get_pending_quantities(
+    connection: &StorageConnection,
+    backorders: &[Backorder],
+) -> Result<HashMap<String, i32>, RepositoryError> {
+    let backorder_ids: Vec<String> = backorders
+        .iter()
+        .map(|backorder| backorder.id.clone())
+        .collect();
+    let backorder_shipments = BackorderShipmentRowRepository::new(connection)
+        .find_many_by_backorder_ids(&backorder_ids)?;
+
+    let invoice_ids: Vec<String> = backorder_shipments
+        .iter()
+        .map(|row| row.invoice_id.clone())
+        .collect();
+    let unshipped_invoice_ids: Vec<String> = InvoiceRowRepository::new(connection)
+        .find_many_by_id(&invoice_ids)?
+        .into_iter()
+        .filter(|invoice| invoice.status.index() < InvoiceRowStatus::Shipped.index())
+        .map(|invoice| invoice.id)
+        .collect();
+
+    let mut pending_quantities = HashMap::new();
+    for row in backorder_shipments {
+        if unshipped_invoice_ids.contains(&row.invoice_id) {
+            *pending_quantities.entry(row.backorder_id).or_insert(0) += row.quantity;
+        }
+    }
+    Ok(pending_quantities)
+}
+
+/// Available number of units of the item in the store, less the units already on placeholder
+/// lines of outbound shipments
+fn get_available_stock(
+    connection: &StorageConnection,
+    store_id: &str,
+    item_id: &str,
+) -> Result<i32, RepositoryError> {
+    let stock_lines = StockLineRepository::new(connection).query(
+        Pagination::all(),
+        Some(
+            StockLineFilter::new()
+                .store_id(EqualFilter::equal_to(store_id))
+                .item_id(EqualFilter::equal_to(item_id))
+                .is_available(true),
+        ),
+        None,
+    )?;
+    let available = stock_lines.iter().fold(0, |sum, stock_line| {
+        let row = &stock_line.stock_line_row;
+        sum + row.available_number_of_packs * row.pack_size
+    });
+
+    let placeholder_lines = InvoiceLineRepository::new(connection).query(
+        Pagination::all(),
+        Some(
+            InvoiceLineFilter::new()
+                .item_id(EqualFilter::equal_to(item_id))
+                .r#type(InvoiceLineRowType::UnallocatedStock.equal_to()),
+        ),
+    )?;
+    let promised = placeholder_lines
+        .iter()
+        .filter(|line| {
+            line.invoice_row.store_id == store_id
+                && line.invoice_row.r#type == InvoiceRowType::OutboundShipment
+        })
+        .fold(0, |sum, line| {
+            sum + line.invoice_line_row.number_of_packs * line.invoice_line_row.pack_size
+        });
+
+    Ok(available - promised)
+}
+
+impl From<RepositoryError> for CreateBackorderShipmentError {
+    fn from(error: RepositoryError) -> Self {
+        CreateBackorderShipmentError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            common::FullMockRequisition, mock_name_a, mock_name_store_b, mock_store_a, MockData,
+            MockDataInserts,
+        },
+        requisition_row::{RequisitionRow, RequisitionRowStatus, RequisitionRowType},
+        test_db::setup_all_with_data,
+        BackorderFilter, BackorderRowRepository, BackorderShipmentRowRepository, EqualFilter,
+        InvoiceLineFilter, InvoiceLineRepository, ItemRow, ItemRowType, RequisitionLineRow,
+        StockLineRow,
+    };
+    use util::inline_init;
+
+    use crate::{
+        backorder::create_shipment::{
+            CreateBackorderShipment, CreateBackorderShipmentError as ServiceError,
+        },
+        invoice::outbound_shipment::{UpdateOutboundShipment, UpdateOutboundShipmentStatus},
+        invoice_line::outbound_shipment_line::UpdateOutboundShipmentLine,
+        requisition::response_requisition::{
+            UpdateResponseRequisition, UpdateResponseRequstionStatus,
+        },
+        service_provider::ServiceProvider,
+    };
+
+    fn item_in_stock() -> ItemRow {
+        inline_init(|r: &mut ItemRow| {
+            r.id = "backorder_item_in_stock".to_owned();
+            r.name = r.id.clone();
+            r.code = r.id.clone();
+            r.r#type = ItemRowType::Stock;
+        })
+    }
+
+    fn item_out_of_stock() -> ItemRow {
+        inline_init(|r: &mut ItemRow| {
+            r.id = "backorder_item_out_of_stock".to_owned();
+            r.name = r.id.clone();
+            r.code = r.id.clone();
+            r.r#type = ItemRowType::Stock;
+        })
+    }
+
+    // 20 units available
+    fn stock_line() -> StockLineRow {
+        inline_init(|r: &mut StockLineRow| {
+            r.id = "backorder_stock_line".to_owned();
+            r.item_id = item_in_stock().id;
+            r.store_id = mock_store_a().id;
+            r.pack_size = 5;
+            r.available_number_of_packs = 4;
+            r.total_number_of_packs = 4;
+        })
+    }
+
+    fn response_requisition() -> FullMockRequisition {
+        let requisition_id = "backorder_response_requisition".to_owned();
+        FullMockRequisition {
+            requisition: inline_init(|r: &mut RequisitionRow| {
+                r.id = requisition_id.clone();
+                r.requisition_number = 1;
+                r.name_id = mock_name_a().id;
+                r.store_id = mock_store_a().id;
+                r.r#type = RequisitionRowType::Response;
+                r.status = RequisitionRowStatus::New;
+            }),
+            lines: vec![
+                inline_init(|r: &mut RequisitionLineRow| {
+                    r.id = format!("{}1", requisition_id);
+                    r.requisition_id = requisition_id.clone();
+                    r.item_id = item_in_stock().id;
+                    r.requested_quantity = 30;
+                    r.supply_quantity = 30;
+                }),
+                inline_init(|r: &mut RequisitionLineRow| {
+                    r.id = format!("{}2", requisition_id);
+                    r.requisition_id = requisition_id.clone();
+                    r.item_id = item_out_of_stock().id;
+                    r.requested_quantity = 20;
+                    r.supply_quantity = 20;
+                }),
+            ],
+        }
+    }
+
+    #[actix_rt::test]
+    async fn create_backorder_shipment() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "create_backorder_shipment",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.items = vec![item_in_stock(), item_out_of_stock()];
+                r.stock_lines = vec![stock_line()];
+                r.full_requisitions = vec![response_requisition()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.backorder_service;
+        let store_id = mock_store_a().id;
+        let requisition_filter = || {
+            BackorderFilter::new().requisition_id(EqualFilter::equal_to(
+                &response_requisition().requisition.id,
+            ))
+        };
+
+        // Finalising the response requisition backorders the unsupplied quantities
+        service_provider
+            .requisition_service
+            .update_response_requisition(
+                &context,
+                &store_id,
+                "n/a",
+                UpdateResponseRequisition {
+                    id: response_requisition().requisition.id,
+                    colour: None,
+                    their_reference: None,
+                    comment: None,
+                    status: Some(UpdateResponseRequstionStatus::Finalised),
+                },
+            )
+            .unwrap();
+
+        let backorders = service
+            .get_backorders(&context, &store_id, None, Some(requisition_filter()), None)
+            .unwrap();
+        assert_eq!(backorders.count, 2);
+        let in_stock_backorder = backorders
+            .rows
+            .iter()
+            .find(|backorder| backorder.item_id == item_in_stock().id)
+            .unwrap()
+            .clone();
+        let out_of_stock_backorder = backorders
+            .rows
+            .iter()
+            .find(|backorder| backorder.item_id == item_out_of_stock().id)
+            .unwrap()
+            .clone();
+        assert_eq!(in_stock_backorder.name_id, mock_name_a().id);
+        assert_eq!(in_stock_backorder.backordered_quantity, 30);
+        assert_eq!(in_stock_backorder.remaining_quantity, 30);
+        assert_eq!(out_of_stock_backorder.remaining_quantity, 20);
+
+        // Not visible in other stores
+        assert_eq!(
+            service
+                .get_backorders(&context, "store_b", None, Some(requisition_filter()), None)
+                .unwrap()
+                .count,
+            0
+        );
+
+        // OtherPartyDoesNotExist
+        assert_eq!(
+            service.create_backorder_shipment(
+                &context,
+                &store_id,
+                "n/a",
+                CreateBackorderShipment {
+                    name_id: "invalid".to_owned(),
+                    backorder_ids: None,
+                },
+            ),
+            Err(ServiceError::OtherPartyDoesNotExist)
+        );
+
+        // BackorderDoesNotExist
+        assert_eq!(
+            service.create_backorder_shipment(
+                &context,
+                &store_id,
+                "n/a",
+                CreateBackorderShipment {
+                    name_id: mock_name_a().id,
+                    backorder_ids: Some(vec!["invalid".to_owned()]),
+                },
+            ),
+            Err(ServiceError::BackorderDoesNotExist)
+        );
+
+        // NoOpenBackorders
+        assert_eq!(
+            service.create_backorder_shipment(
+                &context,
+                &store_id,
+                "n/a",
+                CreateBackorderShipment {
+                    name_id: mock_name_store_b().id,
+                    backorder_ids: None,
+                },
+            ),
+            Err(ServiceError::NoOpenBackorders)
+        );
+
+        // NoStockAvailable
+        assert_eq!(
+            service.create_backorder_shipment(
+                &context,
+                &store_id,
+                "n/a",
+                CreateBackorderShipment {
+                    name_id: mock_name_a().id,
+                    backorder_ids: Some(vec![out_of_stock_backorder.id.clone()]),
+                },
+            ),
+            Err(ServiceError::NoStockAvailable)
+        );
+
+        // Success, supplied up to the available stock
+        let invoice = service
+            .create_backorder_shipment(
+                &context,
+                &store_id,
+                "n/a",
+                CreateBackorderShipment {
+                    name_id: mock_name_a().id,
+                    backorder_ids: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(invoice.invoice_row.name_id, mock_name_a().id);
+
+        let lines = InvoiceLineRepository::new(&connection)
+            .query_by_filter(
+                InvoiceLineFilter::new().invoice_id(EqualFilter::equal_to(&invoice.invoice_row.id)),
+            )
+            .unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].invoice_line_row.item_id, item_in_stock().id);
+        assert_eq!(lines[0].invoice_line_row.number_of_packs, 20);
+
+        // Remaining quantity is only reduced once the shipment is shipped
+        let remaining_quantity = || {
+            BackorderRowRepository::new(&connection)
+                .find_one_by_id(&in_stock_backorder.id)
+                .unwrap()
+                .unwrap()
+                .remaining_quantity
+        };
+        assert_eq!(remaining_quantity(), 30);
+
+        // Available stock is already on the placeholder line of the first shipment
+        assert_eq!(
+            service.create_backorder_shipment(
+                &context,
+                &store_id,
+                "n/a",
+                CreateBackorderShipment {
+                    name_id: mock_name_a().id,
+                    backorder_ids: Some(vec![in_stock_backorder.id.clone()]),
+                },
+            ),
+            Err(ServiceError::NoStockAvailable)
+        );
+
+        // Deleting the shipment releases the backorder and the stock
+        service_provider
+            .invoice_service
+            .delete_outbound_shipment(&context, &store_id, invoice.invoice_row.id.clone())
+            .unwrap();
+        assert_eq!(remaining_quantity(), 30);
+
+        let invoice = service
+            .create_backorder_shipment(
+                &context,
+                &store_id,
+                "n/a",
+                CreateBackorderShipment {
+                    name_id: mock_name_a().id,
+                    backorder_ids: Some(vec![in_stock_backorder.id.clone()]),
+                },
+            )
+            .unwrap();
+        let invoice_id = invoice.invoice_row.id;
+        let lines = InvoiceLineRepository::new(&connection)
+            .query_by_filter(
+                InvoiceLineFilter::new().invoice_id(EqualFilter::equal_to(&invoice_id)),
+            )
+            .unwrap();
+        assert_eq!(lines[0].invoice_line_row.number_of_packs, 20);
+
+        // Ship 15 of the 20 units placed on the shipment
+        let allocated = service_provider
+            .invoice_line_service
+            .allocate_outbound_shipment_unallocated_line(
+                &context,
+                &store_id,
+                lines[0].invoice_line_row.id.clone(),
+            )
+            .unwrap();
+        service_provider
+            .invoice_line_service
+            .update_outbound_shipment_line(
+                &context,
+                &store_id,
+                inline_init(|r: &mut UpdateOutboundShipmentLine| {
+                    r.id = allocated.inserts[0].invoice_line_row.id.clone();
+                    r.invoice_id = invoice_id.clone();
+                    r.number_of_packs = Some(3);
+                }),
+            )
+            .unwrap();
+        service_provider
+            .invoice_service
+            .update_outbound_shipment(
+                &context,
+                &store_id,
+                inline_init(|r: &mut UpdateOutboundShipment| {
+                    r.id = invoice_id.clone();
+                    r.status = Some(UpdateOutboundShipmentStatus::Shipped);
+                }),
+            )
+            .unwrap();
+
+        assert_eq!(remaining_quantity(), 15);
+        assert_eq!(
+            BackorderShipmentRowRepository::new(&connection)
+                .find_many_by_invoice_id(&invoice_id)
+                .unwrap()[0]
+                .quantity,
+            15
+        );
+
+        // Shipped quantity is no longer pending, the rest of the stock can be supplied
+        let invoice = service
+            .create_backorder_shipment(
+                &context,
+                &store_id,
+                "n/a",
+                CreateBackorderShipment {
+                    name_id: mock_name_a().id,
+                    backorder_ids: Some(vec![in_stock_backorder.id.clone()]),
+                },
+            )
+            .unwrap();
+        let lines = InvoiceLineRepository::new(&connection)
+            .query_by_filter(
+                InvoiceLineFilter::new().invoice_id(EqualFilter::equal_to(&invoice.invoice_row.id)),
+            )
+            .unwrap();
+        assert_eq!(lines[0].invoice_line_row.number_of_packs, 5);
+    }
+}
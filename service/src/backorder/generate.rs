@@ -0,0 +1,44 @@
+use chrono::Utc;
+use repository::{
+    requisition_row::RequisitionRow, BackorderRow, RepositoryError, StorageConnection,
+};
+use util::uuid::uuid;
+
+use crate::requisition::requisition_supply_status::{
+    get_requisitions_supply_statuses, RequisitionLineSupplyStatus,
+};
+
+/// Backorders for the lines of a response requisition that are not fully supplied, i.e. where the
+/// supply quantity exceeds the quantity on linked outbound shipments.
+///
+/// Called when the response requisition is finalised, after which no more shipments can be
+/// created from the requisition itself
+pub fn generate_backorders(
+    connection: &StorageConnection,
+    requisition_row: &RequisitionRow,
+) -> Result<Vec<BackorderRow>, RepositoryError> {
+    let supply_statuses =
+        get_requisitions_supply_statuses(connection, vec![requisition_row.id.clone()])?;
+    let created_datetime = Utc::now().naive_utc();
+
+    let backorders = RequisitionLineSupplyStatus::lines_remaining_to_supply(supply_statuses)
+        .into_iter()
+        .map(|status| {
+            let remaining_quantity = status.remaining_quantity();
+            let line = status.requisition_line.requisition_line_row;
+            BackorderRow {
+                id: uuid(),
+                store_id: requisition_row.store_id.clone(),
+                requisition_id: requisition_row.id.clone(),
+                requisition_line_id: line.id,
+                name_id: requisition_row.name_id.clone(),
+                item_id: line.item_id,
+                created_datetime,
+                backordered_quantity: remaining_quantity,
+                remaining_quantity,
+            }
+        })
+        .collect();
+
+    Ok(backorders)
+}
@@ -0,0 +1,40 @@
+use self::{
+    create_shipment::{
+        create_backorder_shipment, CreateBackorderShipment, CreateBackorderShipmentError,
+    },
+    query::get_backorders,
+};
+
+use crate::{service_provider::ServiceContext, ListError, ListResult};
+use repository::{Backorder, BackorderFilter, BackorderSort, Invoice, PaginationOption};
+
+pub mod create_shipment;
+pub mod generate;
+pub mod query;
+pub mod ship;
+
+pub trait BackorderServiceTrait: Sync + Send {
+    fn get_backorders(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        pagination: Option<PaginationOption>,
+        filter: Option<BackorderFilter>,
+        sort: Option<BackorderSort>,
+    ) -> Result<ListResult<Backorder>, ListError> {
+        get_backorders(ctx, store_id, pagination, filter, sort)
+    }
+
+    fn create_backorder_shipment(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        input: CreateBackorderShipment,
+    ) -> Result<Invoice, CreateBackorderShipmentError> {
+        create_backorder_shipment(ctx, store_id, user_id, input)
+    }
+}
+
+pub struct BackorderService {}
+impl BackorderServiceTrait for BackorderService {}
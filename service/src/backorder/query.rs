@@ -0,0 +1,29 @@
+use repository::{
+    Backorder, BackorderFilter, BackorderRepository, BackorderSort, EqualFilter, PaginationOption,
+};
+
+use crate::{
+    get_default_pagination, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
+};
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+pub fn get_backorders(
+    ctx: &ServiceContext,
+    store_id: &str,
+    pagination: Option<PaginationOption>,
+    filter: Option<BackorderFilter>,
+    sort: Option<BackorderSort>,
+) -> Result<ListResult<Backorder>, ListError> {
+    let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+    let filter = filter
+        .unwrap_or_default()
+        .store_id(EqualFilter::equal_to(store_id));
+    let repository = BackorderRepository::new(&ctx.connection);
+
+    Ok(ListResult {
+        rows: repository.query(pagination, Some(filter.clone()), sort)?,
+        count: i64_to_u32(repository.count(Some(filter))?),
+    })
+}
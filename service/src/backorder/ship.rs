@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use repository::{
+    BackorderFilter, BackorderRepository, BackorderRowRepository, BackorderShipmentRowRepository,
+    EqualFilter, InvoiceLineRowRepository, InvoiceLineRowType, Pagination, RepositoryError,
+    StorageConnection,
+};
+
+/// Deducts the quantity shipped on a follow-up shipment from the remaining quantity of the
+/// backorders it supplies.
+///
+/// The shipped quantity of an item is given to its backorders oldest first, up to the quantity
+/// placed on the shipment for each backorder. Quantity removed from the shipment before it was
+/// shipped stays on the backorders.
+pub fn ship_backorders(
+    connection: &StorageConnection,
+    invoice_id: &str,
+) -> Result<(), RepositoryError> {
+    let backorder_shipment_repository = BackorderShipmentRowRepository::new(connection);
+    let backorder_shipments = backorder_shipment_repository.find_many_by_invoice_id(invoice_id)?;
+    if backorder_shipments.is_empty() {
+        return Ok(());
+    }
+
+    let mut shipped_quantities: HashMap<String, i32> = HashMap::new();
+    for line in InvoiceLineRowRepository::new(connection).find_many_by_invoice_id(invoice_id)? {
+        if line.r#type != InvoiceLineRowType::StockOut {
+            continue;
+        }
+        *shipped_quantities.entry(line.item_id).or_insert(0) +=
+            line.number_of_packs * line.pack_size;
+    }
+
+    let backorder_ids: Vec<String> = backorder_shipments
+        .iter()
+        .map(|row| row.backorder_id.clone())
+        .collect();
+    // Oldest first
+    let backorders = BackorderRepository::new(connection).query(
+        Pagination::all(),
+        Some(BackorderFilter::new().id(EqualFilter::equal_any(backorder_ids))),
+        None,
+    )?;
+
+    let backorder_repository = BackorderRowRepository::new(connection);
+    for mut backorder in backorders {
+        let mut backorder_shipment = backorder_shipments
+            .iter()
+            .find(|row| row.backorder_id == backorder.id)
+            .ok_or(RepositoryError::NotFound)?
+            .clone();
+        let shipped = shipped_quantities
+            .entry(backorder.item_id.clone())
+            .or_insert(0);
+
+        let quantity = backorder_shipment.quantity.min(*shipped);
+        *shipped -= quantity;
+        backorder.remaining_quantity -= quantity;
+        backorder_shipment.quantity = quantity;
+
+        backorder_repository.upsert_one(&backorder)?;
+        backorder_shipment_repository.upsert_one(&backorder_shipment)?;
+    }
+
+    Ok(())
+}
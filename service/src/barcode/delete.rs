@@ -0,0 +1,97 @@
+use crate::service_provider::ServiceContext;
+use repository::{BarcodeRowRepository, RepositoryError};
+
+#[derive(PartialEq, Debug)]
+pub enum DeleteBarcodeError {
+    BarcodeDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct DeleteBarcode {
+    pub id: String,
+}
+
+/// Removes a barcode, e.g. when it has been registered for the wrong item
+pub fn delete_barcode(
+    ctx: &ServiceContext,
+    input: DeleteBarcode,
+) -> Result<String, DeleteBarcodeError> {
+    let barcode_id = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let repository = BarcodeRowRepository::new(connection);
+            if repository.find_one_by_id(&input.id)?.is_none() {
+                return Err(DeleteBarcodeError::BarcodeDoesNotExist);
+            }
+            repository.delete(&input.id)?;
+            Ok(input.id)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(barcode_id)
+}
+
+impl From<RepositoryError> for DeleteBarcodeError {
+    fn from(error: RepositoryError) -> Self {
+        DeleteBarcodeError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_barcode_a, mock_store_a, MockDataInserts},
+        test_db::setup_all,
+        BarcodeRowRepository,
+    };
+
+    use crate::{
+        barcode::{
+            delete::{DeleteBarcode, DeleteBarcodeError as ServiceError},
+            query::GetItemByBarcodeError,
+        },
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn delete_barcode() {
+        let (_, connection, connection_manager, _) =
+            setup_all("delete_barcode", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.barcode_service;
+
+        // BarcodeDoesNotExist
+        assert_eq!(
+            service.delete_barcode(
+                &context,
+                DeleteBarcode {
+                    id: "invalid".to_string(),
+                }
+            ),
+            Err(ServiceError::BarcodeDoesNotExist)
+        );
+
+        // Success
+        assert_eq!(
+            service.delete_barcode(
+                &context,
+                DeleteBarcode {
+                    id: mock_barcode_a().id,
+                }
+            ),
+            Ok(mock_barcode_a().id)
+        );
+        assert_eq!(
+            BarcodeRowRepository::new(&connection)
+                .find_one_by_id(&mock_barcode_a().id)
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            service.get_item_by_barcode(&context, &mock_store_a().id, "012345678905"),
+            Err(GetItemByBarcodeError::BarcodeNotFound)
+        );
+    }
+}
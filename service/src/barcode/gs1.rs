@@ -0,0 +1,392 @@
+use chrono::{Datelike, NaiveDate, Utc};
+
+/// Group separator, terminates variable length element strings (FNC1 in GS1-128)
+const GROUP_SEPARATOR: char = '\u{1d}';
+
+/// Data extracted from a scanned GS1 barcode
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Gs1Barcode {
+    /// GTIN normalised to 14 digits
+    pub gtin: String,
+    pub batch: Option<String>,
+    pub expiry_date: Option<NaiveDate>,
+    pub serial_number: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Gs1ParseError {
+    Empty,
+    MissingGtin,
+    InvalidGtin(String),
+    InvalidCheckDigit(String),
+    /// Element string with an unsupported application identifier
+    UnknownApplicationIdentifier(String),
+    /// Value of the application identifier is missing or has the wrong length
+    InvalidValue(String),
+    InvalidDate(String),
+}
+
+enum ValueLength {
+    Fixed(usize),
+    Variable(usize),
+}
+
+/// Application identifiers that may appear on medical product packaging
+fn value_length(ai: &str) -> Option<ValueLength> {
+    use ValueLength::*;
+    let length = match ai {
+        // SSCC
+        "00" => Fixed(18),
+        // GTIN, GTIN of contained trade items
+        "01" | "02" => Fixed(14),
+        // Batch or lot number
+        "10" => Variable(20),
+        // Production, packaging, best before and expiry date
+        "11" | "13" | "15" | "16" | "17" => Fixed(6),
+        // Internal product variant
+        "20" => Fixed(2),
+        // Serial number
+        "21" => Variable(20),
+        // Count of items
+        "30" | "37" => Variable(8),
+        // Additional product identification, customer part number
+        "240" | "241" => Variable(30),
+        // Secondary serial number
+        "250" => Variable(30),
+        // National healthcare reimbursement number
+        "710" | "711" | "712" | "713" | "714" => Variable(20),
+        _ => return None,
+    };
+    Some(length)
+}
+
+/// Parses the content of a scanned barcode.
+///
+/// Supports plain EAN-8, UPC-A, EAN-13 and GTIN-14 numbers as well as GS1 element strings
+/// encoded in GS1-128 or GS1 DataMatrix, either raw (with group separators and optional
+/// symbology identifier) or in the human readable form, e.g. `(01)09506000134352(17)250331(10)A1`
+pub fn parse_gs1(input: &str) -> Result<Gs1Barcode, Gs1ParseError> {
+    let data = strip_symbology_identifier(input.trim()).trim_start_matches(GROUP_SEPARATOR);
+    if data.is_empty() {
+        return Err(Gs1ParseError::Empty);
+    }
+
+    if data.chars().all(|c| c.is_ascii_digit()) && matches!(data.len(), 8 | 12 | 13 | 14) {
+        return Ok(Gs1Barcode {
+            gtin: normalise_gtin(data)?,
+            ..Default::default()
+        });
+    }
+
+    let elements = if data.starts_with('(') {
+        split_human_readable(data)?
+    } else {
+        split_element_string(data)?
+    };
+
+    let mut result = Gs1Barcode::default();
+    let mut contained_gtin = None;
+    for (ai, value) in elements {
+        match ai.as_str() {
+            "01" => result.gtin = normalise_gtin(&value)?,
+            "02" => contained_gtin = Some(normalise_gtin(&value)?),
+            "10" => result.batch = Some(value),
+            "17" => result.expiry_date = Some(parse_date(&value)?),
+            "21" => result.serial_number = Some(value),
+            _ => {}
+        }
+    }
+
+    if result.gtin.is_empty() {
+        result.gtin = contained_gtin.ok_or(Gs1ParseError::MissingGtin)?;
+    }
+
+    Ok(result)
+}
+
+/// Left pads the GTIN to 14 digits and validates its check digit
+pub fn normalise_gtin(gtin: &str) -> Result<String, Gs1ParseError> {
+    if !gtin.chars().all(|c| c.is_ascii_digit()) || !matches!(gtin.len(), 8 | 12 | 13 | 14) {
+        return Err(Gs1ParseError::InvalidGtin(gtin.to_string()));
+    }
+    let gtin = format!("{:0>14}", gtin);
+
+    let (body, check_digit) = gtin.split_at(13);
+    let sum: u32 = body
+        .chars()
+        .rev()
+        .enumerate()
+        .map(|(index, c)| {
+            let digit = c.to_digit(10).unwrap_or_default();
+            if index % 2 == 0 {
+                digit * 3
+            } else {
+                digit
+            }
+        })
+        .sum();
+    if check_digit != ((10 - sum % 10) % 10).to_string() {
+        return Err(Gs1ParseError::InvalidCheckDigit(gtin));
+    }
+
+    Ok(gtin)
+}
+
+/// e.g. `]C1` for GS1-128, `]d2` for GS1 DataMatrix or `]Q3` for GS1 QR Code
+fn strip_symbology_identifier(data: &str) -> &str {
+    match data.strip_prefix(']') {
+        Some(rest) => rest.get(2..).unwrap_or_default(),
+        None => data,
+    }
+}
+
+/// The brackets delimit the values, so element strings with application identifiers missing from
+/// [value_length] are kept as they are
+fn split_human_readable(data: &str) -> Result<Vec<(String, String)>, Gs1ParseError> {
+    let mut elements = Vec::new();
+    for element in data.split('(').skip(1) {
+        let (ai, value) = element
+            .split_once(')')
+            .ok_or_else(|| Gs1ParseError::InvalidValue(element.to_string()))?;
+        if !(2..=4).contains(&ai.len()) || !ai.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Gs1ParseError::UnknownApplicationIdentifier(ai.to_string()));
+        }
+        let value = value.trim().to_string();
+        match value_length(ai) {
+            Some(ValueLength::Fixed(length)) if value.len() != length => {
+                return Err(Gs1ParseError::InvalidValue(ai.to_string()))
+            }
+            Some(ValueLength::Variable(max)) if value.is_empty() || value.len() > max => {
+                return Err(Gs1ParseError::InvalidValue(ai.to_string()))
+            }
+            Some(_) | None => {}
+        }
+        elements.push((ai.to_string(), value));
+    }
+    Ok(elements)
+}
+
+fn split_element_string(data: &str) -> Result<Vec<(String, String)>, Gs1ParseError> {
+    let mut elements = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        // Application identifiers are two to four digits long
+        let (ai, length) = (2..=4)
+            .filter_map(|ai_length| {
+                let ai = rest.get(..ai_length)?;
+                value_length(ai).map(|length| (ai, length))
+            })
+            .next()
+            .ok_or_else(|| {
+                Gs1ParseError::UnknownApplicationIdentifier(rest.chars().take(4).collect())
+            })?;
+        rest = &rest[ai.len()..];
+
+        let value = match length {
+            ValueLength::Fixed(length) => {
+                let value = rest
+                    .get(..length)
+                    .ok_or_else(|| Gs1ParseError::InvalidValue(ai.to_string()))?;
+                rest = &rest[length..];
+                value
+            }
+            ValueLength::Variable(max) => {
+                let end = rest.find(GROUP_SEPARATOR).unwrap_or(rest.len());
+                let value = &rest[..end];
+                if value.is_empty() || value.len() > max {
+                    return Err(Gs1ParseError::InvalidValue(ai.to_string()));
+                }
+                rest = &rest[end..];
+                value
+            }
+        };
+        // Separators are allowed after fixed length values as well
+        rest = rest.trim_start_matches(GROUP_SEPARATOR);
+
+        elements.push((ai.to_string(), value.to_string()));
+    }
+    Ok(elements)
+}
+
+/// Parses a YYMMDD date, a day of 00 means the last day of the month
+fn parse_date(value: &str) -> Result<NaiveDate, Gs1ParseError> {
+    parse_date_in_year(value, Utc::now().year())
+}
+
+/// Resolves the century of a two digit year as in the GS1 General Specifications: a year more
+/// than 49 years in the past is in the next century, a year more than 50 years in the future is
+/// in the previous century
+fn full_year(year: i32, current_year: i32) -> i32 {
+    let current_century = current_year - current_year.rem_euclid(100);
+    let century = match year - current_year.rem_euclid(100) {
+        difference if difference >= 51 => current_century - 100,
+        difference if difference <= -50 => current_century + 100,
+        _ => current_century,
+    };
+    century + year
+}
+
+fn parse_date_in_year(value: &str, current_year: i32) -> Result<NaiveDate, Gs1ParseError> {
+    let invalid = || Gs1ParseError::InvalidDate(value.to_string());
+    let number = |range: std::ops::Range<usize>| -> Result<u32, Gs1ParseError> {
+        value
+            .get(range)
+            .and_then(|part| part.parse().ok())
+            .ok_or_else(invalid)
+    };
+    let year = full_year(number(0..2)? as i32, current_year);
+    let month = number(2..4)?;
+    let day = number(4..6)?;
+
+    if day != 0 {
+        return NaiveDate::from_ymd_opt(year, month, day).ok_or_else(invalid);
+    }
+
+    let (next_year, next_month) = match month {
+        1..=11 => (year, month + 1),
+        12 => (year + 1, 1),
+        _ => return Err(invalid()),
+    };
+    NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|first_of_next_month| first_of_next_month.pred_opt())
+        .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+
+    use super::{parse_date_in_year, parse_gs1, Gs1Barcode, Gs1ParseError};
+
+    #[test]
+    fn parse_gs1_barcodes() {
+        // Plain EAN-13 and UPC-A
+        assert_eq!(
+            parse_gs1("9506000134352"),
+            Ok(Gs1Barcode {
+                gtin: "09506000134352".to_string(),
+                ..Default::default()
+            })
+        );
+        assert_eq!(
+            parse_gs1("012345678905").map(|barcode| barcode.gtin),
+            Ok("00012345678905".to_string())
+        );
+        assert_eq!(
+            parse_gs1("9506000134353"),
+            Err(Gs1ParseError::InvalidCheckDigit(
+                "09506000134353".to_string()
+            ))
+        );
+
+        // GS1-128 with symbology identifier, variable length batch terminated by separator
+        assert_eq!(
+            parse_gs1("]C1010950600013435210A1B2\u{1d}17250331"),
+            Ok(Gs1Barcode {
+                gtin: "09506000134352".to_string(),
+                batch: Some("A1B2".to_string()),
+                expiry_date: Some(NaiveDate::from_ymd(2025, 3, 31)),
+                serial_number: None,
+            })
+        );
+
+        // GS1 DataMatrix, expiry day 00 is the end of the month
+        assert_eq!(
+            parse_gs1("]d201095060001343521724020021SN123\u{1d}10LOT-7"),
+            Ok(Gs1Barcode {
+                gtin: "09506000134352".to_string(),
+                batch: Some("LOT-7".to_string()),
+                expiry_date: Some(NaiveDate::from_ymd(2024, 2, 29)),
+                serial_number: Some("SN123".to_string()),
+            })
+        );
+
+        // Human readable form
+        assert_eq!(
+            parse_gs1("(01)00012345678905(17)261231(10)B 12"),
+            Ok(Gs1Barcode {
+                gtin: "00012345678905".to_string(),
+                batch: Some("B 12".to_string()),
+                expiry_date: Some(NaiveDate::from_ymd(2026, 12, 31)),
+                serial_number: None,
+            })
+        );
+
+        // Errors
+        assert_eq!(parse_gs1(" "), Err(Gs1ParseError::Empty));
+        assert_eq!(parse_gs1("10A1B2"), Err(Gs1ParseError::MissingGtin));
+        assert_eq!(
+            parse_gs1("(01)09506000134352(17)251301"),
+            Err(Gs1ParseError::InvalidDate("251301".to_string()))
+        );
+        assert_eq!(
+            parse_gs1("0109506000134352(99)X"),
+            Err(Gs1ParseError::UnknownApplicationIdentifier(
+                "(99)".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_gs1("0109506000134352991234"),
+            Err(Gs1ParseError::UnknownApplicationIdentifier(
+                "9912".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_gs1("(01)09506000134352(9X)1"),
+            Err(Gs1ParseError::UnknownApplicationIdentifier(
+                "9X".to_string()
+            ))
+        );
+        assert_eq!(
+            parse_gs1("01095060001"),
+            Err(Gs1ParseError::InvalidValue("01".to_string()))
+        );
+        assert_eq!(
+            parse_gs1("(01)9506000134"),
+            Err(Gs1ParseError::InvalidValue("01".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_gs1_human_readable_unknown_application_identifiers() {
+        // Company internal (91-99) and unlisted identifiers are skipped in the bracketed form
+        assert_eq!(
+            parse_gs1("(01)09506000134352(91)INTERNAL-42(17)250331(8008)2503311200(10)A1"),
+            Ok(Gs1Barcode {
+                gtin: "09506000134352".to_string(),
+                batch: Some("A1".to_string()),
+                expiry_date: Some(NaiveDate::from_ymd(2025, 3, 31)),
+                serial_number: None,
+            })
+        );
+        // Known identifiers are still validated
+        assert_eq!(
+            parse_gs1("(01)09506000134352(91)X(17)2503"),
+            Err(Gs1ParseError::InvalidValue("17".to_string()))
+        );
+    }
+
+    #[test]
+    fn parse_date_century() {
+        let date = |value: &str, current_year: i32| parse_date_in_year(value, current_year);
+
+        // 50 years ahead is still in the current century, 51 years ahead is in the previous one
+        assert_eq!(date("760101", 2026), Ok(NaiveDate::from_ymd(2076, 1, 1)));
+        assert_eq!(date("770101", 2026), Ok(NaiveDate::from_ymd(1977, 1, 1)));
+        assert_eq!(date("991231", 2026), Ok(NaiveDate::from_ymd(1999, 12, 31)));
+        assert_eq!(date("000101", 2026), Ok(NaiveDate::from_ymd(2000, 1, 1)));
+        assert_eq!(date("260615", 2026), Ok(NaiveDate::from_ymd(2026, 6, 15)));
+
+        // 49 years in the past is still in the current century, 50 years in the past is in the
+        // next one
+        assert_eq!(date("110101", 2060), Ok(NaiveDate::from_ymd(2011, 1, 1)));
+        assert_eq!(date("100101", 2060), Ok(NaiveDate::from_ymd(2110, 1, 1)));
+        assert_eq!(date("020200", 2098), Ok(NaiveDate::from_ymd(2102, 2, 28)));
+
+        // Near the end of a century
+        assert_eq!(date("480101", 1998), Ok(NaiveDate::from_ymd(2048, 1, 1)));
+        assert_eq!(date("470101", 1998), Ok(NaiveDate::from_ymd(2047, 1, 1)));
+        assert_eq!(date("500101", 1999), Ok(NaiveDate::from_ymd(1950, 1, 1)));
+        assert_eq!(date("490101", 1999), Ok(NaiveDate::from_ymd(2049, 1, 1)));
+    }
+}
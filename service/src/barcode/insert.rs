@@ -0,0 +1,254 @@
+use super::gs1::{normalise_gtin, Gs1ParseError};
+use crate::service_provider::ServiceContext;
+use repository::{
+    BarcodeRow, BarcodeRowRepository, ItemRowRepository, NameRowRepository, RepositoryError,
+    StorageConnection,
+};
+
+#[derive(PartialEq, Debug)]
+pub enum InsertBarcodeError {
+    BarcodeAlreadyExists,
+    InvalidGtin(Gs1ParseError),
+    /// Another barcode with the same GTIN already exists
+    GtinAlreadyExists,
+    ItemDoesNotExist,
+    ManufacturerDoesNotExist,
+    PackSizeBelowOne,
+    CreatedRecordNotFound,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq, Default, Clone)]
+pub struct InsertBarcode {
+    pub id: String,
+    /// GTIN as printed on the packaging, e.g. an EAN-13 or UPC-A number
+    pub gtin: String,
+    pub item_id: String,
+    pub manufacturer_id: Option<String>,
+    pub pack_size: Option<i32>,
+}
+
+/// Registers the GTIN of an item so that scans of it can be resolved to the item
+pub fn insert_barcode(
+    ctx: &ServiceContext,
+    input: InsertBarcode,
+) -> Result<BarcodeRow, InsertBarcodeError> {
+    let barcode = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let gtin = validate(connection, &input)?;
+            let new_barcode = generate(gtin, input);
+            let repository = BarcodeRowRepository::new(connection);
+            repository.upsert_one(&new_barcode)?;
+
+            repository
+                .find_one_by_id(&new_barcode.id)?
+                .ok_or(InsertBarcodeError::CreatedRecordNotFound)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(barcode)
+}
+
+/// Returns the normalised GTIN
+fn validate(
+    connection: &StorageConnection,
+    input: &InsertBarcode,
+) -> Result<String, InsertBarcodeError> {
+    let repository = BarcodeRowRepository::new(connection);
+    if repository.find_one_by_id(&input.id)?.is_some() {
+        return Err(InsertBarcodeError::BarcodeAlreadyExists);
+    }
+    let gtin = normalise_gtin(input.gtin.trim()).map_err(InsertBarcodeError::InvalidGtin)?;
+    if repository.find_one_by_gtin(&gtin)?.is_some() {
+        return Err(InsertBarcodeError::GtinAlreadyExists);
+    }
+    if ItemRowRepository::new(connection)
+        .find_one_by_id(&input.item_id)?
+        .is_none()
+    {
+        return Err(InsertBarcodeError::ItemDoesNotExist);
+    }
+    if let Some(manufacturer_id) = &input.manufacturer_id {
+        if NameRowRepository::new(connection)
+            .find_one_by_id(manufacturer_id)?
+            .is_none()
+        {
+            return Err(InsertBarcodeError::ManufacturerDoesNotExist);
+        }
+    }
+    if input.pack_size.unwrap_or(1) < 1 {
+        return Err(InsertBarcodeError::PackSizeBelowOne);
+    }
+
+    Ok(gtin)
+}
+
+fn generate(
+    gtin: String,
+    InsertBarcode {
+        id,
+        gtin: _,
+        item_id,
+        manufacturer_id,
+        pack_size,
+    }: InsertBarcode,
+) -> BarcodeRow {
+    BarcodeRow {
+        id,
+        gtin,
+        item_id,
+        manufacturer_id,
+        pack_size,
+    }
+}
+
+impl From<RepositoryError> for InsertBarcodeError {
+    fn from(error: RepositoryError) -> Self {
+        InsertBarcodeError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_barcode_a, mock_item_b, mock_name_a, mock_store_a, MockDataInserts},
+        test_db::setup_all,
+        BarcodeRow,
+    };
+    use util::inline_init;
+
+    use crate::{
+        barcode::{
+            gs1::Gs1ParseError,
+            insert::{InsertBarcode, InsertBarcodeError as ServiceError},
+        },
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn insert_barcode_errors() {
+        let (_, _, connection_manager, _) =
+            setup_all("insert_barcode_errors", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.barcode_service;
+
+        fn input() -> InsertBarcode {
+            inline_init(|r: &mut InsertBarcode| {
+                r.id = "new_barcode".to_string();
+                r.gtin = "4006381333931".to_string();
+                r.item_id = mock_item_b().id;
+            })
+        }
+
+        // BarcodeAlreadyExists
+        assert_eq!(
+            service.insert_barcode(
+                &context,
+                inline_init(|r: &mut InsertBarcode| {
+                    *r = input();
+                    r.id = mock_barcode_a().id;
+                })
+            ),
+            Err(ServiceError::BarcodeAlreadyExists)
+        );
+        // InvalidGtin
+        assert_eq!(
+            service.insert_barcode(
+                &context,
+                inline_init(|r: &mut InsertBarcode| {
+                    *r = input();
+                    r.gtin = "4006381333932".to_string();
+                })
+            ),
+            Err(ServiceError::InvalidGtin(Gs1ParseError::InvalidCheckDigit(
+                "04006381333932".to_string()
+            )))
+        );
+        // GtinAlreadyExists, GTIN is compared after normalisation
+        assert_eq!(
+            service.insert_barcode(
+                &context,
+                inline_init(|r: &mut InsertBarcode| {
+                    *r = input();
+                    r.gtin = "012345678905".to_string();
+                })
+            ),
+            Err(ServiceError::GtinAlreadyExists)
+        );
+        // ItemDoesNotExist
+        assert_eq!(
+            service.insert_barcode(
+                &context,
+                inline_init(|r: &mut InsertBarcode| {
+                    *r = input();
+                    r.item_id = "invalid".to_string();
+                })
+            ),
+            Err(ServiceError::ItemDoesNotExist)
+        );
+        // ManufacturerDoesNotExist
+        assert_eq!(
+            service.insert_barcode(
+                &context,
+                inline_init(|r: &mut InsertBarcode| {
+                    *r = input();
+                    r.manufacturer_id = Some("invalid".to_string());
+                })
+            ),
+            Err(ServiceError::ManufacturerDoesNotExist)
+        );
+        // PackSizeBelowOne
+        assert_eq!(
+            service.insert_barcode(
+                &context,
+                inline_init(|r: &mut InsertBarcode| {
+                    *r = input();
+                    r.pack_size = Some(0);
+                })
+            ),
+            Err(ServiceError::PackSizeBelowOne)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn insert_barcode_success() {
+        let (_, _, connection_manager, _) =
+            setup_all("insert_barcode_success", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.barcode_service;
+
+        let result = service
+            .insert_barcode(
+                &context,
+                InsertBarcode {
+                    id: "new_barcode".to_string(),
+                    gtin: "4006381333931".to_string(),
+                    item_id: mock_item_b().id,
+                    manufacturer_id: Some(mock_name_a().id),
+                    pack_size: Some(20),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            result,
+            BarcodeRow {
+                id: "new_barcode".to_string(),
+                gtin: "04006381333931".to_string(),
+                item_id: mock_item_b().id,
+                manufacturer_id: Some(mock_name_a().id),
+                pack_size: Some(20),
+            }
+        );
+
+        // New barcode can be scanned straight away
+        let scanned = service
+            .get_item_by_barcode(&context, &mock_store_a().id, "4006381333931")
+            .unwrap();
+        assert_eq!(scanned.item.item_row.id, mock_item_b().id);
+        assert_eq!(scanned.barcode.pack_size, Some(20));
+    }
+}
@@ -0,0 +1,43 @@
+use self::{
+    delete::{delete_barcode, DeleteBarcode, DeleteBarcodeError},
+    insert::{insert_barcode, InsertBarcode, InsertBarcodeError},
+    query::{get_item_by_barcode, GetItemByBarcodeError, ItemByBarcode},
+};
+
+use crate::service_provider::ServiceContext;
+use repository::BarcodeRow;
+
+pub mod delete;
+pub mod gs1;
+pub mod insert;
+pub mod query;
+
+pub trait BarcodeServiceTrait: Sync + Send {
+    fn get_item_by_barcode(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        barcode: &str,
+    ) -> Result<ItemByBarcode, GetItemByBarcodeError> {
+        get_item_by_barcode(ctx, store_id, barcode)
+    }
+
+    fn insert_barcode(
+        &self,
+        ctx: &ServiceContext,
+        input: InsertBarcode,
+    ) -> Result<BarcodeRow, InsertBarcodeError> {
+        insert_barcode(ctx, input)
+    }
+
+    fn delete_barcode(
+        &self,
+        ctx: &ServiceContext,
+        input: DeleteBarcode,
+    ) -> Result<String, DeleteBarcodeError> {
+        delete_barcode(ctx, input)
+    }
+}
+
+pub struct BarcodeService {}
+impl BarcodeServiceTrait for BarcodeService {}
@@ -0,0 +1,142 @@
+use super::gs1::{parse_gs1, Gs1Barcode, Gs1ParseError};
+use crate::service_provider::ServiceContext;
+use repository::{
+    BarcodeRow, BarcodeRowRepository, EqualFilter, Item, ItemFilter, ItemRepository,
+    RepositoryError, StockLine, StockLineFilter, StockLineRepository,
+};
+
+#[derive(Debug, PartialEq)]
+pub enum GetItemByBarcodeError {
+    InvalidBarcode(Gs1ParseError),
+    BarcodeNotFound,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ItemByBarcode {
+    /// Data extracted from the scan
+    pub scanned: Gs1Barcode,
+    pub barcode: BarcodeRow,
+    pub item: Item,
+    /// Stock lines of the item in the store, restricted to the scanned batch and expiry date
+    pub stock_lines: Vec<StockLine>,
+}
+
+/// Resolves a scanned barcode to the item and the matching stock lines of the store
+pub fn get_item_by_barcode(
+    ctx: &ServiceContext,
+    store_id: &str,
+    barcode: &str,
+) -> Result<ItemByBarcode, GetItemByBarcodeError> {
+    let scanned = parse_gs1(barcode).map_err(GetItemByBarcodeError::InvalidBarcode)?;
+
+    let barcode = BarcodeRowRepository::new(&ctx.connection)
+        .find_one_by_gtin(&scanned.gtin)?
+        .ok_or(GetItemByBarcodeError::BarcodeNotFound)?;
+
+    let item = ItemRepository::new(&ctx.connection)
+        .query_one(ItemFilter::new().id(EqualFilter::equal_to(&barcode.item_id)))?
+        .ok_or(RepositoryError::NotFound)?;
+
+    let stock_lines = StockLineRepository::new(&ctx.connection)
+        .query_by_filter(
+            StockLineFilter::new()
+                .store_id(EqualFilter::equal_to(store_id))
+                .item_id(EqualFilter::equal_to(&barcode.item_id)),
+        )?
+        .into_iter()
+        .filter(|stock_line| {
+            let row = &stock_line.stock_line_row;
+            let batch_matches = scanned.batch.is_none() || row.batch == scanned.batch;
+            let expiry_matches =
+                scanned.expiry_date.is_none() || row.expiry_date == scanned.expiry_date;
+            batch_matches && expiry_matches
+        })
+        .collect();
+
+    Ok(ItemByBarcode {
+        scanned,
+        barcode,
+        item,
+        stock_lines,
+    })
+}
+
+impl From<RepositoryError> for GetItemByBarcodeError {
+    fn from(error: RepositoryError) -> Self {
+        GetItemByBarcodeError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_barcode_a, mock_item_a, mock_stock_line_a, mock_stock_line_b, mock_store_a,
+            MockDataInserts,
+        },
+        test_db::setup_all,
+    };
+
+    use crate::{
+        barcode::{gs1::Gs1ParseError, query::GetItemByBarcodeError},
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn get_item_by_barcode() {
+        let (_, _, connection_manager, _) =
+            setup_all("get_item_by_barcode", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.barcode_service;
+
+        // InvalidBarcode
+        assert_eq!(
+            service.get_item_by_barcode(&context, &mock_store_a().id, ""),
+            Err(GetItemByBarcodeError::InvalidBarcode(Gs1ParseError::Empty))
+        );
+
+        // BarcodeNotFound
+        assert_eq!(
+            service.get_item_by_barcode(&context, &mock_store_a().id, "4006381333931"),
+            Err(GetItemByBarcodeError::BarcodeNotFound)
+        );
+
+        // Plain UPC-A returns all stock lines of the item in the store
+        let result = service
+            .get_item_by_barcode(&context, &mock_store_a().id, "012345678905")
+            .unwrap();
+        assert_eq!(result.barcode, mock_barcode_a());
+        assert_eq!(result.item.item_row, mock_item_a());
+        assert!(result.stock_lines.iter().all(|stock_line| {
+            stock_line.stock_line_row.item_id == mock_item_a().id
+                && stock_line.stock_line_row.store_id == mock_store_a().id
+        }));
+        let stock_line_ids: Vec<String> = result
+            .stock_lines
+            .into_iter()
+            .map(|stock_line| stock_line.stock_line_row.id)
+            .collect();
+        assert!(stock_line_ids.contains(&mock_stock_line_a().id));
+        assert!(stock_line_ids.contains(&mock_stock_line_b().id));
+
+        // Scanned batch narrows down the stock lines
+        let result = service
+            .get_item_by_barcode(
+                &context,
+                &mock_store_a().id,
+                "(01)00012345678905(10)item_a_batch_b",
+            )
+            .unwrap();
+        assert_eq!(result.scanned.batch, Some("item_a_batch_b".to_string()));
+        assert!(result.stock_lines.iter().all(|stock_line| {
+            stock_line.stock_line_row.batch == Some("item_a_batch_b".to_string())
+        }));
+        assert!(result
+            .stock_lines
+            .iter()
+            .any(|stock_line| stock_line.stock_line_row.id == mock_stock_line_b().id));
+    }
+}
@@ -0,0 +1,373 @@
+use std::{cmp::Reverse, collections::HashMap};
+
+use chrono::{Duration, NaiveDate};
+use repository::{
+    ConsumptionCalculationMethod, ConsumptionRow, DatetimeFilter, EqualFilter, RepositoryError,
+    StockLedgerFilter, StockLedgerRepository, StorageConnection, StorePreferenceRow,
+};
+use util::constants::NUMBER_OF_DAYS_IN_A_MONTH;
+
+use crate::{
+    requisition::request_requisition::{generate_suggested_quantity, GenerateSuggestedQuantity},
+    store_preference::query::get_store_preference,
+};
+
+/// Consumption of an item during the lookback period of the average monthly consumption
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemConsumption {
+    pub consumption_rows: Vec<ConsumptionRow>,
+    pub lookback_months: u32,
+    /// Date the lookback period ends
+    pub reference_date: NaiveDate,
+    /// Days of the lookback period the item was out of stock, only counted for calculators that
+    /// use stock out days
+    pub stock_out_days: u32,
+}
+
+impl ItemConsumption {
+    pub fn total(&self) -> i32 {
+        self.consumption_rows.iter().map(|row| row.quantity).sum()
+    }
+}
+
+pub fn lookback_days(lookback_months: u32) -> i64 {
+    (lookback_months as f64 * NUMBER_OF_DAYS_IN_A_MONTH) as i64
+}
+
+/// Calculates average monthly consumption and suggested quantity of requisition lines, the
+/// calculator of a store is selected by its store preference
+pub trait ConsumptionCalculator: Sync + Send {
+    fn method(&self) -> ConsumptionCalculationMethod;
+
+    /// Counting stock out days requires the stock ledger of the lookback period
+    fn uses_stock_out_days(&self) -> bool {
+        false
+    }
+
+    fn average_monthly_consumption(&self, consumption: &ItemConsumption) -> f64 {
+        consumption.total() as f64 / consumption.lookback_months as f64
+    }
+
+    /// Min and max months of stock the suggested quantity is calculated with
+    fn months_of_stock(&self, min_months_of_stock: f64, max_months_of_stock: f64) -> (f64, f64) {
+        (min_months_of_stock, max_months_of_stock)
+    }
+
+    fn suggested_quantity(&self, input: GenerateSuggestedQuantity) -> i32 {
+        let (min_months_of_stock, max_months_of_stock) =
+            self.months_of_stock(input.min_months_of_stock, input.max_months_of_stock);
+        generate_suggested_quantity(GenerateSuggestedQuantity {
+            min_months_of_stock,
+            max_months_of_stock,
+            ..input
+        })
+    }
+}
+
+pub struct SimpleAverage;
+impl ConsumptionCalculator for SimpleAverage {
+    fn method(&self) -> ConsumptionCalculationMethod {
+        ConsumptionCalculationMethod::SimpleAverage
+    }
+}
+
+/// Consumption is averaged over the days the item was in stock, so that stock outs don't
+/// understate demand
+pub struct ExcludeStockOutDays;
+impl ConsumptionCalculator for ExcludeStockOutDays {
+    fn method(&self) -> ConsumptionCalculationMethod {
+        ConsumptionCalculationMethod::ExcludeStockOutDays
+    }
+
+    fn uses_stock_out_days(&self) -> bool {
+        true
+    }
+
+    fn average_monthly_consumption(&self, consumption: &ItemConsumption) -> f64 {
+        let days_in_stock =
+            lookback_days(consumption.lookback_months) - consumption.stock_out_days as i64;
+        if days_in_stock <= 0 {
+            return 0.0;
+        }
+        consumption.total() as f64 / days_in_stock as f64 * NUMBER_OF_DAYS_IN_A_MONTH
+    }
+}
+
+/// The most recent month has a weight of the number of lookback months, the oldest a weight of 1
+pub struct WeightedMovingAverage;
+impl ConsumptionCalculator for WeightedMovingAverage {
+    fn method(&self) -> ConsumptionCalculationMethod {
+        ConsumptionCalculationMethod::WeightedMovingAverage
+    }
+
+    fn average_monthly_consumption(&self, consumption: &ItemConsumption) -> f64 {
+        let months = consumption.lookback_months as usize;
+        if months == 0 {
+            return 0.0;
+        }
+
+        let mut monthly_consumption = vec![0; months];
+        for row in consumption.consumption_rows.iter() {
+            let days_ago = (consumption.reference_date - row.date).num_days().max(0);
+            let index = (days_ago as f64 / NUMBER_OF_DAYS_IN_A_MONTH) as usize;
+            monthly_consumption[index.min(months - 1)] += row.quantity;
+        }
+
+        let (weighted_total, total_weight) = monthly_consumption.iter().enumerate().fold(
+            (0.0, 0.0),
+            |(weighted_total, total_weight), (index, consumption)| {
+                let weight = (months - index) as f64;
+                (
+                    weighted_total + weight * *consumption as f64,
+                    total_weight + weight,
+                )
+            },
+        );
+
+        weighted_total / total_weight
+    }
+}
+
+/// Simple average, stock is also held to cover the lead time of the supplier and as safety stock
+pub struct LeadTimeSafetyStock {
+    pub lead_time_months: f64,
+    pub safety_stock_months: f64,
+}
+impl ConsumptionCalculator for LeadTimeSafetyStock {
+    fn method(&self) -> ConsumptionCalculationMethod {
+        ConsumptionCalculationMethod::LeadTimeSafetyStock
+    }
+
+    fn months_of_stock(&self, min_months_of_stock: f64, max_months_of_stock: f64) -> (f64, f64) {
+        let additional_months = self.lead_time_months + self.safety_stock_months;
+        (
+            min_months_of_stock + additional_months,
+            max_months_of_stock + additional_months,
+        )
+    }
+}
+
+pub fn consumption_calculator(
+    method: ConsumptionCalculationMethod,
+    preference: &StorePreferenceRow,
+) -> Box<dyn ConsumptionCalculator> {
+    match method {
+        ConsumptionCalculationMethod::SimpleAverage => Box::new(SimpleAverage),
+        ConsumptionCalculationMethod::ExcludeStockOutDays => Box::new(ExcludeStockOutDays),
+        ConsumptionCalculationMethod::WeightedMovingAverage => Box::new(WeightedMovingAverage),
+        ConsumptionCalculationMethod::LeadTimeSafetyStock => Box::new(LeadTimeSafetyStock {
+            lead_time_months: preference.lead_time_months,
+            safety_stock_months: preference.safety_stock_months,
+        }),
+    }
+}
+
+/// Calculator selected in the preferences of the store
+pub fn get_consumption_calculator(
+    connection: &StorageConnection,
+    store_id: &str,
+) -> Result<Box<dyn ConsumptionCalculator>, RepositoryError> {
+    let preference = get_store_preference(connection, store_id)?;
+    Ok(consumption_calculator(
+        preference.consumption_calculation_method,
+        &preference,
+    ))
+}
+
+/// Number of days from start_date up to the day before reference_date where the item had no stock
+/// at the end of the day. Balances are worked out backwards from the current stock on hand.
+pub fn get_stock_out_days(
+    connection: &StorageConnection,
+    store_id: &str,
+    stock_on_hand: &HashMap<String, i64>,
+    start_date: NaiveDate,
+    reference_date: NaiveDate,
+) -> Result<HashMap<String, u32>, RepositoryError> {
+    let item_ids: Vec<String> = stock_on_hand.keys().cloned().collect();
+    let mut movements = StockLedgerRepository::new(connection).query_by_filter(
+        StockLedgerFilter::new()
+            .store_id(EqualFilter::equal_to(store_id))
+            .item_id(EqualFilter::equal_any(item_ids))
+            .datetime(DatetimeFilter::after_or_equal_to(
+                start_date.and_hms(0, 0, 0),
+            )),
+    )?;
+    // Most recent first
+    movements.sort_by_key(|movement| Reverse(movement.datetime));
+
+    let mut result = HashMap::new();
+    for (item_id, available_stock_on_hand) in stock_on_hand.iter() {
+        let mut item_movements = movements
+            .iter()
+            .filter(|movement| &movement.item_id == item_id)
+            .peekable();
+        let mut balance = *available_stock_on_hand;
+        let mut stock_out_days = 0;
+
+        let mut date = reference_date;
+        while date > start_date {
+            let end_of_day = date.and_hms(0, 0, 0);
+            date -= Duration::days(1);
+            // Undo movements that happened after the day
+            while let Some(movement) = item_movements.next_if(|m| m.datetime >= end_of_day) {
+                balance -= movement.quantity as i64;
+            }
+            if balance <= 0 {
+                stock_out_days += 1;
+            }
+        }
+
+        result.insert(item_id.clone(), stock_out_days);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use chrono::{Duration, NaiveDate, Utc};
+    use repository::{
+        mock::{mock_name_a, mock_store_a, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        ConsumptionCalculationMethod, ConsumptionRow, InvoiceLineRow, InvoiceLineRowType,
+        InvoiceRow, InvoiceRowType, ItemRow, ItemRowType, StockLineRow, StorePreferenceRow,
+    };
+    use util::{constants::NUMBER_OF_DAYS_IN_A_MONTH, inline_init};
+
+    use crate::requisition::request_requisition::GenerateSuggestedQuantity;
+
+    use super::{consumption_calculator, get_stock_out_days, ItemConsumption};
+
+    #[test]
+    fn consumption_calculators() {
+        let reference_date = NaiveDate::from_ymd(2021, 6, 30);
+        let consumption = ItemConsumption {
+            consumption_rows: vec![(5, 30), (40, 60), (70, 90)]
+                .into_iter()
+                .map(|(days_ago, quantity)| ConsumptionRow {
+                    quantity,
+                    date: reference_date - Duration::days(days_ago),
+                    ..Default::default()
+                })
+                .collect(),
+            lookback_months: 3,
+            reference_date,
+            stock_out_days: 31,
+        };
+        let preference = StorePreferenceRow {
+            lead_time_months: 1.0,
+            safety_stock_months: 0.5,
+            ..Default::default()
+        };
+
+        let calculator =
+            consumption_calculator(ConsumptionCalculationMethod::SimpleAverage, &preference);
+        assert_eq!(calculator.average_monthly_consumption(&consumption), 60.0);
+
+        // 180 over the 59 (90 - 31) days in stock
+        let calculator = consumption_calculator(
+            ConsumptionCalculationMethod::ExcludeStockOutDays,
+            &preference,
+        );
+        assert_eq!(
+            calculator.average_monthly_consumption(&consumption),
+            180.0 / 59.0 * NUMBER_OF_DAYS_IN_A_MONTH
+        );
+
+        // (30 * 3 + 60 * 2 + 90 * 1) / (3 + 2 + 1)
+        let calculator = consumption_calculator(
+            ConsumptionCalculationMethod::WeightedMovingAverage,
+            &preference,
+        );
+        assert_eq!(calculator.average_monthly_consumption(&consumption), 50.0);
+
+        // 2 months of stock is below min of 1 + 1.5, ordered up to max of 2 + 1.5
+        let calculator = consumption_calculator(
+            ConsumptionCalculationMethod::LeadTimeSafetyStock,
+            &preference,
+        );
+        assert_eq!(calculator.average_monthly_consumption(&consumption), 60.0);
+        assert_eq!(
+            calculator.suggested_quantity(GenerateSuggestedQuantity {
+                average_monthly_consumption: 10,
+                available_stock_on_hand: 20,
+                min_months_of_stock: 1.0,
+                max_months_of_stock: 2.0,
+            }),
+            15
+        );
+        let calculator =
+            consumption_calculator(ConsumptionCalculationMethod::SimpleAverage, &preference);
+        assert_eq!(
+            calculator.suggested_quantity(GenerateSuggestedQuantity {
+                average_monthly_consumption: 10,
+                available_stock_on_hand: 20,
+                min_months_of_stock: 1.0,
+                max_months_of_stock: 2.0,
+            }),
+            0
+        );
+    }
+
+    #[actix_rt::test]
+    async fn stock_out_days() {
+        fn item() -> ItemRow {
+            inline_init(|r: &mut ItemRow| {
+                r.id = "stock_out_item".to_string();
+                r.name = "stock_out_item".to_string();
+                r.code = "stock_out_item".to_string();
+                r.r#type = ItemRowType::Stock;
+            })
+        }
+
+        let reference_date = Utc::now().naive_utc().date();
+
+        let (_, connection, _, _) = setup_all_with_data(
+            "stock_out_days",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.items = vec![item()];
+                r.stock_lines = vec![inline_init(|r: &mut StockLineRow| {
+                    r.id = "stock_out_stock_line".to_string();
+                    r.item_id = item().id;
+                    r.store_id = mock_store_a().id;
+                    r.pack_size = 1;
+                    r.available_number_of_packs = 10;
+                    r.total_number_of_packs = 10;
+                })];
+                // Only stock of the item was received 5 days ago
+                r.invoices = vec![inline_init(|r: &mut InvoiceRow| {
+                    r.id = "stock_out_inbound".to_string();
+                    r.store_id = mock_store_a().id;
+                    r.name_id = mock_name_a().id;
+                    r.r#type = InvoiceRowType::InboundShipment;
+                    r.delivered_datetime =
+                        Some((reference_date - Duration::days(5)).and_hms(12, 0, 0));
+                })];
+                r.invoice_lines = vec![inline_init(|r: &mut InvoiceLineRow| {
+                    r.id = "stock_out_inbound_line".to_string();
+                    r.invoice_id = "stock_out_inbound".to_string();
+                    r.item_id = item().id;
+                    r.stock_line_id = Some("stock_out_stock_line".to_string());
+                    r.r#type = InvoiceLineRowType::StockIn;
+                    r.pack_size = 1;
+                    r.number_of_packs = 10;
+                })];
+            }),
+        )
+        .await;
+
+        let stock_on_hand: HashMap<String, i64> = vec![(item().id, 10)].into_iter().collect();
+        let result = get_stock_out_days(
+            &connection,
+            &mock_store_a().id,
+            &stock_on_hand,
+            reference_date - Duration::days(10),
+            reference_date,
+        )
+        .unwrap();
+        assert_eq!(result.get(&item().id), Some(&5));
+    }
+}
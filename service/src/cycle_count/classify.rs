@@ -0,0 +1,182 @@
+use super::config::get_cycle_count_config;
+use crate::service_provider::ServiceContext;
+use chrono::{Duration, NaiveDateTime, Utc};
+use repository::{
+    AbcClass, CycleCountConfigRow, DatetimeFilter, EqualFilter, InvoiceLineFilter,
+    InvoiceLineRepository, InvoiceLineRowType, InvoiceRowType, ItemCycleCountRow,
+    ItemCycleCountRowRepository, Pagination, RepositoryError, StockLedgerFilter,
+    StockLedgerRepository, StockLineFilter, StockLineRepository, StorageConnection,
+};
+use std::collections::BTreeMap;
+use util::uuid::uuid;
+
+/// Period of stock issues used to classify the items
+pub const CLASSIFICATION_PERIOD_DAYS: i64 = 365;
+
+/// Classifies the items of the store into A, B and C classes by the value of the stock issued
+/// over the last [CLASSIFICATION_PERIOD_DAYS].
+///
+/// Items are ranked from the highest issue value down, items ranked within the A class percentage
+/// of the total issue value are class A, the ones within the next B class percentage are class B
+/// and the rest (including items that weren't issued) are class C. Items of the store are the
+/// items with stock lines or issues in the store. The last count of already classified
+/// items is kept.
+pub fn classify_items(
+    ctx: &ServiceContext,
+    store_id: &str,
+) -> Result<Vec<ItemCycleCountRow>, RepositoryError> {
+    classify_items_at(ctx, store_id, Utc::now().naive_utc())
+}
+
+pub(crate) fn classify_items_at(
+    ctx: &ServiceContext,
+    store_id: &str,
+    now: NaiveDateTime,
+) -> Result<Vec<ItemCycleCountRow>, RepositoryError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            let config = get_cycle_count_config(connection, store_id)?;
+            let issue_values = get_issue_values(connection, store_id, now)?;
+            let repo = ItemCycleCountRowRepository::new(connection);
+
+            let mut rows = Vec::new();
+            for (item_id, issue_value, abc_class) in rank(&config, issue_values) {
+                let existing = repo.find_one_by_store_and_item(store_id, &item_id)?;
+                let row = ItemCycleCountRow {
+                    id: existing
+                        .as_ref()
+                        .map(|row| row.id.clone())
+                        .unwrap_or_else(uuid),
+                    store_id: store_id.to_string(),
+                    item_id,
+                    abc_class,
+                    issue_value,
+                    classified_datetime: now,
+                    last_counted_datetime: existing.and_then(|row| row.last_counted_datetime),
+                };
+                repo.upsert_one(&row)?;
+                rows.push(row);
+            }
+            Ok(rows)
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+/// Returns the issue value of every item of the store.
+///
+/// Issues are the stock dispensed or shipped to customers, i.e. outbound shipment and prescription
+/// lines. Other stock leaving the store (adjustments, supplier returns) isn't consumption.
+fn get_issue_values(
+    connection: &StorageConnection,
+    store_id: &str,
+    now: NaiveDateTime,
+) -> Result<BTreeMap<String, f64>, RepositoryError> {
+    // Issued quantity (in units) per item
+    let mut issued_quantities: BTreeMap<String, i64> = BTreeMap::new();
+    for stock_line in StockLineRepository::new(connection).query(
+        Pagination::all(),
+        Some(StockLineFilter::new().store_id(EqualFilter::equal_to(store_id))),
+        None,
+    )? {
+        issued_quantities
+            .entry(stock_line.stock_line_row.item_id)
+            .or_insert(0);
+    }
+    let issues = StockLedgerRepository::new(connection).query_by_filter(
+        StockLedgerFilter::new()
+            .store_id(EqualFilter::equal_to(store_id))
+            .invoice_type(InvoiceRowType::equal_any(vec![
+                InvoiceRowType::OutboundShipment,
+                InvoiceRowType::Prescription,
+            ]))
+            .datetime(DatetimeFilter::date_range(
+                now - Duration::days(CLASSIFICATION_PERIOD_DAYS),
+                now,
+            )),
+    )?;
+    for issue in issues {
+        *issued_quantities.entry(issue.item_id).or_insert(0) += -issue.quantity as i64;
+    }
+
+    let mut issue_values = BTreeMap::new();
+    for (item_id, issued_quantity) in issued_quantities {
+        let issue_value = if issued_quantity > 0 {
+            issued_quantity as f64 * get_unit_cost(connection, store_id, &item_id)?
+        } else {
+            0.0
+        };
+        issue_values.insert(item_id, issue_value);
+    }
+    Ok(issue_values)
+}
+
+/// Average cost price per unit of the stock of the item that came in or left the store
+fn get_unit_cost(
+    connection: &StorageConnection,
+    store_id: &str,
+    item_id: &str,
+) -> Result<f64, RepositoryError> {
+    let lines = InvoiceLineRepository::new(connection).query(
+        Pagination::all(),
+        Some(
+            InvoiceLineFilter::new()
+                .store_id(EqualFilter::equal_to(store_id))
+                .item_id(EqualFilter::equal_to(item_id))
+                .r#type(InvoiceLineRowType::equal_any(vec![
+                    InvoiceLineRowType::StockIn,
+                    InvoiceLineRowType::StockOut,
+                ])),
+        ),
+    )?;
+
+    let (total_cost, total_units) =
+        lines
+            .into_iter()
+            .fold((0.0, 0.0), |(total_cost, total_units), line| {
+                let line = line.invoice_line_row;
+                (
+                    total_cost + line.cost_price_per_pack * line.number_of_packs as f64,
+                    total_units + (line.pack_size * line.number_of_packs) as f64,
+                )
+            });
+    Ok(if total_units > 0.0 {
+        total_cost / total_units
+    } else {
+        0.0
+    })
+}
+
+/// Ranks the items by issue value and returns the class of every item
+fn rank(
+    config: &CycleCountConfigRow,
+    issue_values: BTreeMap<String, f64>,
+) -> Vec<(String, f64, AbcClass)> {
+    let total_value: f64 = issue_values.values().sum();
+    let mut ranked: Vec<(String, f64)> = issue_values.into_iter().collect();
+    // Highest value first, ties are kept in item id order
+    ranked.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut value_ranked_above = 0.0;
+    ranked
+        .into_iter()
+        .map(|(item_id, issue_value)| {
+            // Share of the total issue value of the items ranked above this item
+            let percentage_above = if total_value > 0.0 {
+                value_ranked_above / total_value * 100.0
+            } else {
+                100.0
+            };
+            let abc_class = if issue_value <= 0.0 {
+                AbcClass::C
+            } else if percentage_above < config.a_class_percentage {
+                AbcClass::A
+            } else if percentage_above < config.a_class_percentage + config.b_class_percentage {
+                AbcClass::B
+            } else {
+                AbcClass::C
+            };
+            value_ranked_above += issue_value;
+            (item_id, issue_value, abc_class)
+        })
+        .collect()
+}
@@ -0,0 +1,110 @@
+use crate::service_provider::ServiceContext;
+use repository::{
+    CycleCountConfigRow, CycleCountConfigRowRepository, RepositoryError, StorageConnection,
+};
+
+pub const DEFAULT_A_CLASS_PERCENTAGE: f64 = 80.0;
+pub const DEFAULT_B_CLASS_PERCENTAGE: f64 = 15.0;
+pub const DEFAULT_A_CLASS_COUNT_FREQUENCY_DAYS: i32 = 30;
+pub const DEFAULT_B_CLASS_COUNT_FREQUENCY_DAYS: i32 = 90;
+pub const DEFAULT_C_CLASS_COUNT_FREQUENCY_DAYS: i32 = 365;
+
+/// Returns the stored cycle count config or a disabled config if none has been set for the store
+pub fn get_cycle_count_config(
+    connection: &StorageConnection,
+    store_id: &str,
+) -> Result<CycleCountConfigRow, RepositoryError> {
+    let config = CycleCountConfigRowRepository::new(connection)
+        .find_one_by_id(store_id)?
+        .unwrap_or_else(|| CycleCountConfigRow {
+            id: store_id.to_string(),
+            is_enabled: false,
+            a_class_percentage: DEFAULT_A_CLASS_PERCENTAGE,
+            b_class_percentage: DEFAULT_B_CLASS_PERCENTAGE,
+            a_class_count_frequency_days: DEFAULT_A_CLASS_COUNT_FREQUENCY_DAYS,
+            b_class_count_frequency_days: DEFAULT_B_CLASS_COUNT_FREQUENCY_DAYS,
+            c_class_count_frequency_days: DEFAULT_C_CLASS_COUNT_FREQUENCY_DAYS,
+        });
+    Ok(config)
+}
+
+#[derive(PartialEq, Debug)]
+pub enum UpdateCycleCountConfigError {
+    /// Class percentages are negative or add up to more than 100
+    InvalidClassPercentage,
+    /// Items need to be counted at least every day
+    CountFrequencyNotPositive,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct UpdateCycleCountConfig {
+    pub is_enabled: bool,
+    pub a_class_percentage: f64,
+    pub b_class_percentage: f64,
+    pub a_class_count_frequency_days: i32,
+    pub b_class_count_frequency_days: i32,
+    pub c_class_count_frequency_days: i32,
+}
+
+pub fn update_cycle_count_config(
+    ctx: &ServiceContext,
+    store_id: &str,
+    input: UpdateCycleCountConfig,
+) -> Result<CycleCountConfigRow, UpdateCycleCountConfigError> {
+    let config = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(&input)?;
+            let row = generate(store_id, input);
+            CycleCountConfigRowRepository::new(connection).upsert_one(&row)?;
+
+            get_cycle_count_config(connection, store_id).map_err(UpdateCycleCountConfigError::from)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(config)
+}
+
+fn validate(input: &UpdateCycleCountConfig) -> Result<(), UpdateCycleCountConfigError> {
+    if input.a_class_percentage < 0.0
+        || input.b_class_percentage < 0.0
+        || input.a_class_percentage + input.b_class_percentage > 100.0
+    {
+        return Err(UpdateCycleCountConfigError::InvalidClassPercentage);
+    }
+    if input.a_class_count_frequency_days <= 0
+        || input.b_class_count_frequency_days <= 0
+        || input.c_class_count_frequency_days <= 0
+    {
+        return Err(UpdateCycleCountConfigError::CountFrequencyNotPositive);
+    }
+    Ok(())
+}
+
+fn generate(
+    store_id: &str,
+    UpdateCycleCountConfig {
+        is_enabled,
+        a_class_percentage,
+        b_class_percentage,
+        a_class_count_frequency_days,
+        b_class_count_frequency_days,
+        c_class_count_frequency_days,
+    }: UpdateCycleCountConfig,
+) -> CycleCountConfigRow {
+    CycleCountConfigRow {
+        id: store_id.to_string(),
+        is_enabled,
+        a_class_percentage,
+        b_class_percentage,
+        a_class_count_frequency_days,
+        b_class_count_frequency_days,
+        c_class_count_frequency_days,
+    }
+}
+
+impl From<RepositoryError> for UpdateCycleCountConfigError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateCycleCountConfigError::DatabaseError(error)
+    }
+}
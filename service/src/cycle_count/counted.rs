@@ -0,0 +1,115 @@
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+use repository::{
+    AbcClass, CycleCountConfigRow, ItemCycleCountRow, ItemCycleCountRowRepository, RepositoryError,
+    StorageConnection,
+};
+use util::uuid::uuid;
+
+/// Returns true if the item has never been counted or if its last count is at least the count
+/// frequency of its class before the date
+pub fn is_due_for_count(
+    config: &CycleCountConfigRow,
+    row: &ItemCycleCountRow,
+    date: NaiveDate,
+) -> bool {
+    let frequency_days = match row.abc_class {
+        AbcClass::A => config.a_class_count_frequency_days,
+        AbcClass::B => config.b_class_count_frequency_days,
+        AbcClass::C => config.c_class_count_frequency_days,
+    };
+    match row.last_counted_datetime {
+        Some(last_counted) => last_counted.date() + Duration::days(frequency_days as i64) <= date,
+        None => true,
+    }
+}
+
+/// Records the count of the items, e.g. when a stocktake is finalised.
+///
+/// Items that haven't been classified yet are added as class C items until the next
+/// classification.
+pub fn update_last_counted(
+    connection: &StorageConnection,
+    store_id: &str,
+    item_ids: &[String],
+    counted_datetime: NaiveDateTime,
+) -> Result<(), RepositoryError> {
+    let repo = ItemCycleCountRowRepository::new(connection);
+    for item_id in item_ids {
+        let row = match repo.find_one_by_store_and_item(store_id, item_id)? {
+            Some(mut row) => {
+                row.last_counted_datetime = Some(counted_datetime);
+                row
+            }
+            None => ItemCycleCountRow {
+                id: uuid(),
+                store_id: store_id.to_string(),
+                item_id: item_id.clone(),
+                abc_class: AbcClass::C,
+                issue_value: 0.0,
+                classified_datetime: counted_datetime,
+                last_counted_datetime: Some(counted_datetime),
+            },
+        };
+        repo.upsert_one(&row)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, NaiveDate};
+    use repository::{AbcClass, CycleCountConfigRow, ItemCycleCountRow};
+    use util::inline_init;
+
+    use super::is_due_for_count;
+
+    #[test]
+    fn due_for_count() {
+        let config = inline_init(|r: &mut CycleCountConfigRow| {
+            r.a_class_count_frequency_days = 30;
+            r.b_class_count_frequency_days = 90;
+            r.c_class_count_frequency_days = 365;
+        });
+        let counted_date = NaiveDate::from_ymd(2022, 1, 1);
+        let row = |abc_class: AbcClass, is_counted: bool| ItemCycleCountRow {
+            id: "item_cycle_count".to_string(),
+            store_id: "store".to_string(),
+            item_id: "item".to_string(),
+            abc_class,
+            issue_value: 0.0,
+            classified_datetime: counted_date.and_hms(0, 0, 0),
+            last_counted_datetime: if is_counted {
+                Some(counted_date.and_hms(10, 0, 0))
+            } else {
+                None
+            },
+        };
+
+        // Never counted items are always due
+        assert!(is_due_for_count(
+            &config,
+            &row(AbcClass::C, false),
+            counted_date
+        ));
+
+        // Items are due on the day the count frequency of their class has passed
+        for (abc_class, frequency_days) in
+            [(AbcClass::A, 30), (AbcClass::B, 90), (AbcClass::C, 365)]
+                .iter()
+                .cloned()
+        {
+            let row = row(abc_class, true);
+            assert!(!is_due_for_count(&config, &row, counted_date));
+            assert!(!is_due_for_count(
+                &config,
+                &row,
+                counted_date + Duration::days(frequency_days - 1)
+            ));
+            assert!(is_due_for_count(
+                &config,
+                &row,
+                counted_date + Duration::days(frequency_days)
+            ));
+        }
+    }
+}
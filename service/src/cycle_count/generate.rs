@@ -0,0 +1,530 @@
+use super::{
+    classify::classify_items_at, config::get_cycle_count_config, counted::is_due_for_count,
+};
+use crate::{
+    service_provider::ServiceContext,
+    stocktake::{insert_stocktake, GenerateStocktakeLines, InsertStocktake, InsertStocktakeError},
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use repository::{
+    CycleCountConfigRowRepository, EqualFilter, ItemCycleCountRowRepository, Pagination,
+    RepositoryError, StockLineFilter, StockLineRepository, Stocktake, StocktakeFilter,
+    StocktakeLineFilter, StocktakeLineRepository, StocktakeRepository, StocktakeStatus,
+    StorageConnection,
+};
+use std::collections::BTreeSet;
+use util::uuid::uuid;
+
+#[derive(Debug, PartialEq)]
+pub enum GenerateCycleCountError {
+    DatabaseError(RepositoryError),
+    InternalError(String),
+}
+
+/// Creates the cycle count stocktake of the store for the date, with a line for every stock line
+/// in stock of the items that are due to be counted.
+///
+/// Items are due if they have never been counted or if their last count is at least the count
+/// frequency of their class before the date. Items on a new stocktake of the store are skipped,
+/// they are counted once that stocktake is finalised. Returns None if no item in stock is due.
+pub fn generate_cycle_count(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    date: NaiveDate,
+) -> Result<Option<Stocktake>, GenerateCycleCountError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            let item_ids = get_items_due(connection, store_id, date)?;
+            if item_ids.is_empty() {
+                return Ok(None);
+            }
+
+            let stocktake = insert_stocktake(
+                ctx,
+                store_id,
+                user_id,
+                InsertStocktake {
+                    id: uuid(),
+                    description: Some(format!("Cycle count {}", date)),
+                    stocktake_date: Some(date),
+                    generate_lines: Some(GenerateStocktakeLines {
+                        item_ids: Some(item_ids),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+            )
+            .map_err(|error| match error {
+                InsertStocktakeError::DatabaseError(error) => {
+                    GenerateCycleCountError::DatabaseError(error)
+                }
+                error => GenerateCycleCountError::InternalError(format!("{:?}", error)),
+            })?;
+            Ok(Some(stocktake))
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+/// Classifies the items and generates the cycle count of every store with enabled cycle counting,
+/// returns the outcome per store
+pub fn run_cycle_counts(
+    ctx: &ServiceContext,
+    user_id: &str,
+    now: NaiveDateTime,
+) -> Result<Vec<(String, Result<Option<Stocktake>, GenerateCycleCountError>)>, RepositoryError> {
+    let configs = CycleCountConfigRowRepository::new(&ctx.connection).find_all_enabled()?;
+
+    let mut results = Vec::new();
+    for config in configs {
+        let result = match classify_items_at(ctx, &config.id, now) {
+            Ok(_) => generate_cycle_count(ctx, &config.id, user_id, now.date()),
+            Err(error) => Err(GenerateCycleCountError::DatabaseError(error)),
+        };
+        results.push((config.id, result));
+    }
+    Ok(results)
+}
+
+/// Returns the classified items of the store that are due for a count and in stock
+fn get_items_due(
+    connection: &StorageConnection,
+    store_id: &str,
+    date: NaiveDate,
+) -> Result<Vec<String>, RepositoryError> {
+    let config = get_cycle_count_config(connection, store_id)?;
+    let items_on_new_stocktakes = get_items_on_new_stocktakes(connection, store_id)?;
+    let due_item_ids: Vec<String> = ItemCycleCountRowRepository::new(connection)
+        .find_many_by_store_id(store_id)?
+        .into_iter()
+        .filter(|row| is_due_for_count(&config, row, date))
+        .filter(|row| !items_on_new_stocktakes.contains(&row.item_id))
+        .map(|row| row.item_id)
+        .collect();
+    if due_item_ids.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let items_in_stock: BTreeSet<String> = StockLineRepository::new(connection)
+        .query(
+            Pagination::all(),
+            Some(
+                StockLineFilter::new()
+                    .store_id(EqualFilter::equal_to(store_id))
+                    .item_id(EqualFilter::equal_any(due_item_ids)),
+            ),
+            None,
+        )?
+        .into_iter()
+        .map(|stock_line| stock_line.stock_line_row)
+        .filter(|stock_line| stock_line.total_number_of_packs > 0)
+        .map(|stock_line| stock_line.item_id)
+        .collect();
+    Ok(items_in_stock.into_iter().collect())
+}
+
+fn get_items_on_new_stocktakes(
+    connection: &StorageConnection,
+    store_id: &str,
+) -> Result<BTreeSet<String>, RepositoryError> {
+    let stocktake_ids = StocktakeRepository::new(connection)
+        .query(
+            Pagination::all(),
+            Some(
+                StocktakeFilter::new()
+                    .store_id(EqualFilter::equal_to(store_id))
+                    .status(StocktakeStatus::New.equal_to()),
+            ),
+            None,
+        )?
+        .into_iter()
+        .map(|stocktake| stocktake.id)
+        .collect();
+    let item_ids = StocktakeLineRepository::new(connection)
+        .query_by_filter(
+            StocktakeLineFilter::new().stocktake_id(EqualFilter::equal_any(stocktake_ids)),
+        )?
+        .into_iter()
+        .map(|line| line.line.item_id)
+        .collect();
+    Ok(item_ids)
+}
+
+impl From<RepositoryError> for GenerateCycleCountError {
+    fn from(error: RepositoryError) -> Self {
+        GenerateCycleCountError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, Utc};
+    use repository::{
+        mock::{
+            mock_item_a, mock_item_b, mock_item_c, mock_name_a, mock_user_account_a, MockData,
+            MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        AbcClass, InvoiceLineRow, InvoiceLineRowType, InvoiceRow, InvoiceRowType, NameRow,
+        StockLineRow, StocktakeLineFilter, StocktakeLineRepository, StocktakeStatus, StoreRow,
+    };
+    use util::inline_init;
+
+    use crate::{
+        cycle_count::{
+            config::{UpdateCycleCountConfig, UpdateCycleCountConfigError},
+            generate::run_cycle_counts,
+        },
+        service_provider::ServiceProvider,
+        stocktake::UpdateStocktake,
+    };
+
+    #[actix_rt::test]
+    async fn generate_cycle_count() {
+        fn name() -> NameRow {
+            inline_init(|r: &mut NameRow| {
+                r.id = "cycle_count_name".to_string();
+            })
+        }
+
+        fn store() -> StoreRow {
+            inline_init(|s: &mut StoreRow| {
+                s.id = "cycle_count_store".to_string();
+                s.name_id = name().id;
+                s.code = "n/a".to_string();
+            })
+        }
+
+        // Item in stock with the number of units issued at a cost of 1 per unit
+        fn issued_item(item_id: &str, issued_quantity: i32) -> MockData {
+            let invoice_id = format!("cycle_count_{}", item_id);
+            inline_init(|r: &mut MockData| {
+                r.stock_lines = vec![inline_init(|r: &mut StockLineRow| {
+                    r.id = format!("{}_stock_line", invoice_id);
+                    r.item_id = item_id.to_string();
+                    r.store_id = store().id;
+                    r.pack_size = 1;
+                    r.available_number_of_packs = 10;
+                    r.total_number_of_packs = 10;
+                })];
+                r.invoices = vec![inline_init(|r: &mut InvoiceRow| {
+                    r.id = invoice_id.clone();
+                    r.store_id = store().id;
+                    r.name_id = mock_name_a().id;
+                    r.r#type = InvoiceRowType::OutboundShipment;
+                    r.picked_datetime = Some(Utc::now().naive_utc() - Duration::days(10));
+                })];
+                r.invoice_lines = vec![inline_init(|r: &mut InvoiceLineRow| {
+                    r.id = format!("{}_line", invoice_id);
+                    r.invoice_id = invoice_id.clone();
+                    r.item_id = item_id.to_string();
+                    r.r#type = InvoiceLineRowType::StockOut;
+                    r.pack_size = 1;
+                    r.number_of_packs = issued_quantity;
+                    r.cost_price_per_pack = 1.0;
+                })];
+            })
+        }
+
+        // Stock that left the store without being issued to a customer
+        fn stock_out(item_id: &str, r#type: InvoiceRowType, quantity: i32) -> MockData {
+            let invoice_id = format!("cycle_count_{}_{:?}", item_id, r#type);
+            let datetime = Some(Utc::now().naive_utc() - Duration::days(10));
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![inline_init(|i: &mut InvoiceRow| {
+                    i.id = invoice_id.clone();
+                    i.store_id = store().id;
+                    i.name_id = mock_name_a().id;
+                    i.r#type = r#type;
+                    i.picked_datetime = datetime;
+                    i.verified_datetime = datetime;
+                })];
+                r.invoice_lines = vec![inline_init(|l: &mut InvoiceLineRow| {
+                    l.id = format!("{}_line", invoice_id);
+                    l.invoice_id = invoice_id.clone();
+                    l.item_id = item_id.to_string();
+                    l.r#type = InvoiceLineRowType::StockOut;
+                    l.pack_size = 1;
+                    l.number_of_packs = quantity;
+                    l.cost_price_per_pack = 1.0;
+                })];
+            })
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "generate_cycle_count",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.names = vec![name()];
+                r.stores = vec![store()];
+            })
+            .join(issued_item(&mock_item_a().id, 80))
+            .join(issued_item(&mock_item_b().id, 15))
+            .join(issued_item(&mock_item_c().id, 5))
+            .join(stock_out(
+                &mock_item_c().id,
+                InvoiceRowType::InventoryAdjustment,
+                500,
+            ))
+            .join(stock_out(
+                &mock_item_c().id,
+                InvoiceRowType::SupplierReturn,
+                300,
+            )),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.cycle_count_service;
+        let store_id = store().id;
+        let user_id = mock_user_account_a().id;
+        let today = Utc::now().naive_utc().date();
+
+        // InvalidClassPercentage
+        assert_eq!(
+            service.update_cycle_count_config(
+                &context,
+                &store_id,
+                UpdateCycleCountConfig {
+                    a_class_percentage: 80.0,
+                    b_class_percentage: 30.0,
+                    a_class_count_frequency_days: 30,
+                    b_class_count_frequency_days: 90,
+                    c_class_count_frequency_days: 365,
+                    ..Default::default()
+                },
+            ),
+            Err(UpdateCycleCountConfigError::InvalidClassPercentage)
+        );
+
+        // CountFrequencyNotPositive
+        assert_eq!(
+            service.update_cycle_count_config(
+                &context,
+                &store_id,
+                UpdateCycleCountConfig {
+                    a_class_percentage: 80.0,
+                    b_class_percentage: 15.0,
+                    a_class_count_frequency_days: 0,
+                    b_class_count_frequency_days: 90,
+                    c_class_count_frequency_days: 365,
+                    ..Default::default()
+                },
+            ),
+            Err(UpdateCycleCountConfigError::CountFrequencyNotPositive)
+        );
+
+        // Classification with the default config, adjustments and supplier returns aren't issues
+        let classes: Vec<(String, f64, AbcClass)> = service
+            .classify_items(&context, &store_id)
+            .unwrap()
+            .into_iter()
+            .map(|row| (row.item_id, row.issue_value, row.abc_class))
+            .collect();
+        assert_eq!(
+            classes,
+            vec![
+                (mock_item_a().id, 80.0, AbcClass::A),
+                (mock_item_b().id, 15.0, AbcClass::B),
+                (mock_item_c().id, 5.0, AbcClass::C),
+            ]
+        );
+
+        // All items are due when never counted
+        let stocktake = service
+            .generate_cycle_count(&context, &store_id, &user_id, today)
+            .unwrap()
+            .unwrap();
+        let counted_items = |stocktake_id: &str| {
+            let mut item_ids: Vec<String> = StocktakeLineRepository::new(&connection)
+                .query_by_filter(
+                    StocktakeLineFilter::new()
+                        .stocktake_id(repository::EqualFilter::equal_to(stocktake_id)),
+                )
+                .unwrap()
+                .into_iter()
+                .map(|line| line.line.item_id)
+                .collect();
+            item_ids.sort();
+            item_ids
+        };
+        assert_eq!(
+            counted_items(&stocktake.id),
+            vec![mock_item_a().id, mock_item_b().id, mock_item_c().id]
+        );
+        assert_eq!(stocktake.stocktake_date, Some(today));
+
+        // Items on the new stocktake are not due again
+        assert_eq!(
+            service.generate_cycle_count(&context, &store_id, &user_id, today),
+            Ok(None)
+        );
+
+        // Finalising the stocktake records the count
+        service_provider
+            .stocktake_service
+            .update_stocktake(
+                &context,
+                &store_id,
+                &user_id,
+                inline_init(|i: &mut UpdateStocktake| {
+                    i.id = stocktake.id.clone();
+                    i.status = Some(StocktakeStatus::Finalised);
+                }),
+            )
+            .unwrap();
+        let item_cycle_counts = service.get_item_cycle_counts(&context, &store_id).unwrap();
+        assert!(item_cycle_counts
+            .iter()
+            .all(|row| row.last_counted_datetime.is_some()));
+
+        // Counted items are due after the count frequency of their class
+        assert_eq!(
+            service.generate_cycle_count(&context, &store_id, &user_id, today + Duration::days(29)),
+            Ok(None)
+        );
+        let stocktake = service
+            .generate_cycle_count(&context, &store_id, &user_id, today + Duration::days(30))
+            .unwrap()
+            .unwrap();
+        assert_eq!(counted_items(&stocktake.id), vec![mock_item_a().id]);
+    }
+
+    #[actix_rt::test]
+    async fn run_cycle_counts_edge_cases() {
+        fn name() -> NameRow {
+            inline_init(|r: &mut NameRow| {
+                r.id = "cycle_count_edge_name".to_string();
+            })
+        }
+
+        fn store() -> StoreRow {
+            inline_init(|s: &mut StoreRow| {
+                s.id = "cycle_count_edge_store".to_string();
+                s.name_id = name().id;
+                s.code = "n/a".to_string();
+            })
+        }
+
+        fn empty_name() -> NameRow {
+            inline_init(|r: &mut NameRow| {
+                r.id = "cycle_count_empty_name".to_string();
+            })
+        }
+
+        // Store without any stock or stock movements
+        fn empty_store() -> StoreRow {
+            inline_init(|s: &mut StoreRow| {
+                s.id = "cycle_count_empty_store".to_string();
+                s.name_id = empty_name().id;
+                s.code = "n/a".to_string();
+            })
+        }
+
+        fn stock_line(item_id: &str, total_number_of_packs: i32) -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = format!("cycle_count_edge_{}", item_id);
+                r.item_id = item_id.to_string();
+                r.store_id = store().id;
+                r.pack_size = 1;
+                r.available_number_of_packs = total_number_of_packs;
+                r.total_number_of_packs = total_number_of_packs;
+            })
+        }
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "run_cycle_counts_edge_cases",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.names = vec![name(), empty_name()];
+                r.stores = vec![store(), empty_store()];
+                // item a is in stock but never issued, item b is out of stock
+                r.stock_lines = vec![
+                    stock_line(&mock_item_a().id, 10),
+                    stock_line(&mock_item_b().id, 0),
+                ];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.cycle_count_service;
+        let user_id = mock_user_account_a().id;
+        let now = Utc::now().naive_utc();
+        let config = UpdateCycleCountConfig {
+            is_enabled: false,
+            a_class_percentage: 80.0,
+            b_class_percentage: 15.0,
+            a_class_count_frequency_days: 30,
+            b_class_count_frequency_days: 90,
+            c_class_count_frequency_days: 365,
+        };
+
+        // Disabled stores are not part of the scheduled run
+        service
+            .update_cycle_count_config(&context, &store().id, config.clone())
+            .unwrap();
+        assert_eq!(run_cycle_counts(&context, &user_id, now).unwrap().len(), 0);
+
+        for store_id in [store().id, empty_store().id].iter() {
+            service
+                .update_cycle_count_config(
+                    &context,
+                    store_id,
+                    UpdateCycleCountConfig {
+                        is_enabled: true,
+                        ..config.clone()
+                    },
+                )
+                .unwrap();
+        }
+        let mut results = run_cycle_counts(&context, &user_id, now).unwrap();
+        results.sort_by(|(a, _), (b, _)| a.cmp(b));
+        assert_eq!(results.len(), 2);
+        let (empty_store_id, empty_store_result) = results.pop().unwrap();
+        let (store_id, store_result) = results.pop().unwrap();
+
+        // Store without stats has nothing to classify or count
+        assert_eq!(empty_store_id, empty_store().id);
+        assert_eq!(empty_store_result, Ok(None));
+        assert_eq!(
+            service
+                .get_item_cycle_counts(&context, &empty_store().id)
+                .unwrap(),
+            vec![]
+        );
+
+        // Items without issues are class C, only items in stock are counted
+        let classes: Vec<(String, f64, AbcClass)> = service
+            .get_item_cycle_counts(&context, &store().id)
+            .unwrap()
+            .into_iter()
+            .map(|row| (row.item_id, row.issue_value, row.abc_class))
+            .collect();
+        assert_eq!(
+            classes,
+            vec![
+                (mock_item_a().id, 0.0, AbcClass::C),
+                (mock_item_b().id, 0.0, AbcClass::C),
+            ]
+        );
+        assert_eq!(store_id, store().id);
+        let stocktake = store_result.unwrap().unwrap();
+        let item_ids: Vec<String> = StocktakeLineRepository::new(&context.connection)
+            .query_by_filter(
+                StocktakeLineFilter::new()
+                    .stocktake_id(repository::EqualFilter::equal_to(&stocktake.id)),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|line| line.line.item_id)
+            .collect();
+        assert_eq!(item_ids, vec![mock_item_a().id]);
+
+        // The open cycle count isn't duplicated by the next run
+        let results = run_cycle_counts(&context, &user_id, now + Duration::days(1)).unwrap();
+        assert!(results.iter().all(|(_, result)| result == &Ok(None)));
+    }
+}
@@ -0,0 +1,67 @@
+use self::{
+    classify::classify_items,
+    config::{
+        get_cycle_count_config, update_cycle_count_config, UpdateCycleCountConfig,
+        UpdateCycleCountConfigError,
+    },
+    generate::{generate_cycle_count, GenerateCycleCountError},
+};
+
+use crate::service_provider::ServiceContext;
+use chrono::NaiveDate;
+use repository::{
+    CycleCountConfigRow, ItemCycleCountRow, ItemCycleCountRowRepository, RepositoryError, Stocktake,
+};
+
+pub mod classify;
+pub mod config;
+pub mod counted;
+pub mod generate;
+
+pub trait CycleCountServiceTrait: Sync + Send {
+    fn get_cycle_count_config(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<CycleCountConfigRow, RepositoryError> {
+        get_cycle_count_config(&ctx.connection, store_id)
+    }
+
+    fn update_cycle_count_config(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        input: UpdateCycleCountConfig,
+    ) -> Result<CycleCountConfigRow, UpdateCycleCountConfigError> {
+        update_cycle_count_config(ctx, store_id, input)
+    }
+
+    fn get_item_cycle_counts(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Vec<ItemCycleCountRow>, RepositoryError> {
+        ItemCycleCountRowRepository::new(&ctx.connection).find_many_by_store_id(store_id)
+    }
+
+    fn classify_items(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Vec<ItemCycleCountRow>, RepositoryError> {
+        classify_items(ctx, store_id)
+    }
+
+    fn generate_cycle_count(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        date: NaiveDate,
+    ) -> Result<Option<Stocktake>, GenerateCycleCountError> {
+        generate_cycle_count(ctx, store_id, user_id, date)
+    }
+}
+
+pub struct CycleCountService {}
+impl CycleCountServiceTrait for CycleCountService {}
@@ -0,0 +1,25 @@
+use self::query::get_inventory_adjustment_reasons;
+
+use crate::{service_provider::ServiceContext, ListError, ListResult};
+use repository::{
+    InventoryAdjustmentReason, InventoryAdjustmentReasonFilter, InventoryAdjustmentReasonSort,
+    PaginationOption,
+};
+
+pub mod query;
+pub mod validate;
+
+pub trait InventoryAdjustmentReasonServiceTrait: Sync + Send {
+    fn get_inventory_adjustment_reasons(
+        &self,
+        ctx: &ServiceContext,
+        pagination: Option<PaginationOption>,
+        filter: Option<InventoryAdjustmentReasonFilter>,
+        sort: Option<InventoryAdjustmentReasonSort>,
+    ) -> Result<ListResult<InventoryAdjustmentReason>, ListError> {
+        get_inventory_adjustment_reasons(ctx, pagination, filter, sort)
+    }
+}
+
+pub struct InventoryAdjustmentReasonService {}
+impl InventoryAdjustmentReasonServiceTrait for InventoryAdjustmentReasonService {}
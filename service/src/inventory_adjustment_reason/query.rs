@@ -0,0 +1,26 @@
+use repository::{
+    InventoryAdjustmentReason, InventoryAdjustmentReasonFilter,
+    InventoryAdjustmentReasonRepository, InventoryAdjustmentReasonSort, PaginationOption,
+};
+
+use crate::{
+    get_default_pagination, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
+};
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+pub fn get_inventory_adjustment_reasons(
+    ctx: &ServiceContext,
+    pagination: Option<PaginationOption>,
+    filter: Option<InventoryAdjustmentReasonFilter>,
+    sort: Option<InventoryAdjustmentReasonSort>,
+) -> Result<ListResult<InventoryAdjustmentReason>, ListError> {
+    let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+    let repository = InventoryAdjustmentReasonRepository::new(&ctx.connection);
+
+    Ok(ListResult {
+        rows: repository.query(pagination, filter.clone(), sort)?,
+        count: i64_to_u32(repository.count(filter)?),
+    })
+}
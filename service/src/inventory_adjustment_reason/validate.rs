@@ -0,0 +1,55 @@
+use repository::{
+    InventoryAdjustmentReasonFilter, InventoryAdjustmentReasonRepository,
+    InventoryAdjustmentReasonRowRepository, InventoryAdjustmentType, RepositoryError,
+    StorageConnection,
+};
+
+/// Returns the adjustment type matching the change in number of packs, or None if there is no
+/// change
+pub fn adjustment_type_for_delta(delta: i32) -> Option<InventoryAdjustmentType> {
+    if delta > 0 {
+        Some(InventoryAdjustmentType::Positive)
+    } else if delta < 0 {
+        Some(InventoryAdjustmentType::Negative)
+    } else {
+        None
+    }
+}
+
+/// A reason is only required if the reason list for the adjustment type has been configured,
+/// i.e. if there is at least one active reason of that type.
+pub fn check_reason_is_required(
+    connection: &StorageConnection,
+    adjustment_type: &InventoryAdjustmentType,
+) -> Result<bool, RepositoryError> {
+    let count = InventoryAdjustmentReasonRepository::new(connection).count(Some(
+        InventoryAdjustmentReasonFilter::new()
+            .r#type(adjustment_type.equal_to())
+            .is_active(true),
+    ))?;
+    Ok(count > 0)
+}
+
+/// Checks that the reason exists, is active and matches the adjustment type
+pub fn check_reason_is_valid(
+    connection: &StorageConnection,
+    inventory_adjustment_reason_id: &str,
+    adjustment_type: &InventoryAdjustmentType,
+) -> Result<bool, RepositoryError> {
+    let reason = InventoryAdjustmentReasonRowRepository::new(connection)
+        .find_one_by_id(inventory_adjustment_reason_id)?;
+    Ok(match reason {
+        Some(reason) => reason.is_active && &reason.r#type == adjustment_type,
+        None => false,
+    })
+}
+
+/// Checks that the reason exists and is active, used when the adjustment direction isn't known yet
+pub fn check_reason_exists(
+    connection: &StorageConnection,
+    inventory_adjustment_reason_id: &str,
+) -> Result<bool, RepositoryError> {
+    let reason = InventoryAdjustmentReasonRowRepository::new(connection)
+        .find_one_by_id(inventory_adjustment_reason_id)?;
+    Ok(reason.map(|reason| reason.is_active).unwrap_or(false))
+}
@@ -0,0 +1,106 @@
+use repository::{
+    InvoiceLineRowRepository, InvoiceRow, InvoiceRowRepository, InvoiceRowType, RepositoryError,
+    StorageConnection, TransactionError,
+};
+
+use crate::invoice::{
+    check_invoice_exists, check_invoice_is_editable, check_invoice_type, InvoiceDoesNotExist,
+    InvoiceIsNotEditable, WrongInvoiceRowType,
+};
+use crate::{service_provider::ServiceContext, WithDBError};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DeleteCustomerReturnError {
+    InvoiceDoesNotExist,
+    NotACustomerReturn,
+    /// Stock of delivered returns has already been put back into the store
+    CannotEditFinalised,
+    DatabaseError(RepositoryError),
+}
+
+/// Deletes a customer return that hasn't been delivered yet, including its lines.
+pub fn delete_customer_return(
+    ctx: &ServiceContext,
+    _store_id: &str,
+    id: String,
+) -> Result<String, DeleteCustomerReturnError> {
+    let invoice_id = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(&id, connection)?;
+
+            let line_repository = InvoiceLineRowRepository::new(connection);
+            for line in line_repository.find_many_by_invoice_id(&id)? {
+                line_repository.delete(&line.id)?;
+            }
+            InvoiceRowRepository::new(connection).delete(&id)?;
+
+            Ok(id) as Result<String, DeleteCustomerReturnError>
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(invoice_id)
+}
+
+fn validate(
+    id: &str,
+    connection: &StorageConnection,
+) -> Result<InvoiceRow, DeleteCustomerReturnError> {
+    let invoice = check_invoice_exists(id, connection)?;
+    check_invoice_type(&invoice, InvoiceRowType::CustomerReturn)?;
+    check_invoice_is_editable(&invoice)?;
+    if invoice.delivered_datetime.is_some() {
+        return Err(DeleteCustomerReturnError::CannotEditFinalised);
+    }
+
+    Ok(invoice)
+}
+
+impl From<WrongInvoiceRowType> for DeleteCustomerReturnError {
+    fn from(_: WrongInvoiceRowType) -> Self {
+        DeleteCustomerReturnError::NotACustomerReturn
+    }
+}
+
+impl From<InvoiceIsNotEditable> for DeleteCustomerReturnError {
+    fn from(_: InvoiceIsNotEditable) -> Self {
+        DeleteCustomerReturnError::CannotEditFinalised
+    }
+}
+
+impl From<InvoiceDoesNotExist> for DeleteCustomerReturnError {
+    fn from(_: InvoiceDoesNotExist) -> Self {
+        DeleteCustomerReturnError::InvoiceDoesNotExist
+    }
+}
+
+impl From<RepositoryError> for DeleteCustomerReturnError {
+    fn from(error: RepositoryError) -> Self {
+        DeleteCustomerReturnError::DatabaseError(error)
+    }
+}
+
+impl From<TransactionError<DeleteCustomerReturnError>> for DeleteCustomerReturnError {
+    fn from(error: TransactionError<DeleteCustomerReturnError>) -> Self {
+        match error {
+            TransactionError::Transaction { msg, level } => {
+                DeleteCustomerReturnError::DatabaseError(RepositoryError::TransactionError {
+                    msg,
+                    level,
+                })
+            }
+            TransactionError::Inner(e) => e,
+        }
+    }
+}
+
+impl<ERR> From<WithDBError<ERR>> for DeleteCustomerReturnError
+where
+    ERR: Into<DeleteCustomerReturnError>,
+{
+    fn from(result: WithDBError<ERR>) -> Self {
+        match result {
+            WithDBError::DatabaseError(error) => error.into(),
+            WithDBError::Error(error) => error.into(),
+        }
+    }
+}
@@ -0,0 +1,492 @@
+use chrono::Utc;
+use repository::{
+    Invoice, InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRow,
+    InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType, NumberRowType, RepositoryError,
+    StorageConnection, TransactionError,
+};
+
+use crate::invoice::{
+    check_invoice_exists_option, get_returnable_number_of_packs,
+    number_of_packs_in_preceding_lines, InsertReturnLine,
+};
+use crate::number::next_number;
+use crate::{invoice::query::get_invoice, service_provider::ServiceContext};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InsertCustomerReturn {
+    pub id: String,
+    /// Outbound shipment or prescription the stock was issued with
+    pub original_invoice_id: String,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub colour: Option<String>,
+    pub lines: Vec<InsertReturnLine>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum InsertCustomerReturnError {
+    InvoiceAlreadyExists,
+    OriginalInvoiceDoesNotExist,
+    OriginalInvoiceNotThisStore,
+    /// Original invoice is not an outbound shipment or prescription
+    NotAStockOutInvoice,
+    /// Stock of the original invoice hasn't been issued yet
+    OriginalInvoiceNotIssued,
+    // Line validation, holds the id of the return line
+    LineAlreadyExists(String),
+    OriginalLineDoesNotExist(String),
+    NumberOfPacksBelowOne(String),
+    ReturnedQuantityExceedsIssued {
+        line_id: String,
+        returnable_number_of_packs: i32,
+    },
+    // Internal
+    NewlyCreatedInvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = InsertCustomerReturnError;
+
+/// Creates a customer return for stock issued by an outbound shipment or prescription. Stock is
+/// only put back into the store once the return is delivered.
+pub fn insert_customer_return(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    input: InsertCustomerReturn,
+) -> Result<Invoice, OutError> {
+    let invoice = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (original_invoice, original_lines) = validate(connection, store_id, &input)?;
+            let (new_invoice, new_lines) = generate(
+                connection,
+                store_id,
+                user_id,
+                original_invoice,
+                original_lines,
+                input,
+            )?;
+
+            InvoiceRowRepository::new(connection).upsert_one(&new_invoice)?;
+            let line_repository = InvoiceLineRowRepository::new(connection);
+            for line in new_lines {
+                line_repository.upsert_one(&line)?;
+            }
+
+            get_invoice(ctx, None, &new_invoice.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::NewlyCreatedInvoiceDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(invoice)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &InsertCustomerReturn,
+) -> Result<(InvoiceRow, Vec<InvoiceLineRow>), OutError> {
+    use InsertCustomerReturnError::*;
+
+    if check_invoice_exists_option(&input.id, connection)?.is_some() {
+        return Err(InvoiceAlreadyExists);
+    }
+
+    let original_invoice = check_invoice_exists_option(&input.original_invoice_id, connection)?
+        .ok_or(OriginalInvoiceDoesNotExist)?;
+    if original_invoice.store_id != store_id {
+        return Err(OriginalInvoiceNotThisStore);
+    }
+    match original_invoice.r#type {
+        InvoiceRowType::OutboundShipment | InvoiceRowType::Prescription => {}
+        _ => return Err(NotAStockOutInvoice),
+    }
+    if original_invoice.picked_datetime.is_none() {
+        return Err(OriginalInvoiceNotIssued);
+    }
+
+    let line_repository = InvoiceLineRowRepository::new(connection);
+    let mut original_lines = Vec::new();
+    for line in &input.lines {
+        if line_repository.find_one_by_id_option(&line.id)?.is_some() {
+            return Err(LineAlreadyExists(line.id.clone()));
+        }
+        let original_line = line_repository
+            .find_one_by_id_option(&line.original_invoice_line_id)?
+            .filter(|original_line| {
+                original_line.invoice_id == original_invoice.id
+                    && original_line.r#type == InvoiceLineRowType::StockOut
+            })
+            .ok_or(OriginalLineDoesNotExist(line.id.clone()))?;
+        if line.number_of_packs < 1 {
+            return Err(NumberOfPacksBelowOne(line.id.clone()));
+        }
+
+        let returnable_number_of_packs =
+            get_returnable_number_of_packs(connection, &original_line)?
+                - number_of_packs_in_preceding_lines(&input.lines, line);
+        if line.number_of_packs > returnable_number_of_packs {
+            return Err(ReturnedQuantityExceedsIssued {
+                line_id: line.id.clone(),
+                returnable_number_of_packs,
+            });
+        }
+        original_lines.push(original_line);
+    }
+
+    Ok((original_invoice, original_lines))
+}
+
+fn generate(
+    connection: &StorageConnection,
+    store_id: &str,
+    user_id: &str,
+    original_invoice: InvoiceRow,
+    original_lines: Vec<InvoiceLineRow>,
+    InsertCustomerReturn {
+        id,
+        original_invoice_id: _,
+        their_reference,
+        comment,
+        colour,
+        lines,
+    }: InsertCustomerReturn,
+) -> Result<(InvoiceRow, Vec<InvoiceLineRow>), RepositoryError> {
+    let invoice = InvoiceRow {
+        id: id.clone(),
+        user_id: Some(user_id.to_string()),
+        name_id: original_invoice.name_id,
+        name_store_id: original_invoice.name_store_id,
+        store_id: store_id.to_string(),
+        invoice_number: next_number(connection, &NumberRowType::CustomerReturn, store_id)?,
+        r#type: InvoiceRowType::CustomerReturn,
+        status: InvoiceRowStatus::New,
+        their_reference,
+        comment,
+        colour,
+        created_datetime: Utc::now().naive_utc(),
+        ..Default::default()
+    };
+
+    let lines = lines
+        .into_iter()
+        .zip(original_lines)
+        .map(|(line, original_line)| {
+            let total = original_line.sell_price_per_pack * line.number_of_packs as f64;
+            InvoiceLineRow {
+                id: line.id,
+                invoice_id: id.clone(),
+                r#type: InvoiceLineRowType::StockIn,
+                number_of_packs: line.number_of_packs,
+                total_before_tax: total,
+                total_after_tax: total,
+                tax: None,
+                note: line.note,
+                original_invoice_line_id: Some(original_line.id.clone()),
+                return_reason: line.return_reason,
+                ..original_line
+            }
+        })
+        .collect();
+
+    Ok((invoice, lines))
+}
+
+impl From<RepositoryError> for InsertCustomerReturnError {
+    fn from(error: RepositoryError) -> Self {
+        InsertCustomerReturnError::DatabaseError(error)
+    }
+}
+
+impl From<TransactionError<InsertCustomerReturnError>> for InsertCustomerReturnError {
+    fn from(error: TransactionError<InsertCustomerReturnError>) -> Self {
+        match error {
+            TransactionError::Transaction { msg, level } => {
+                InsertCustomerReturnError::DatabaseError(RepositoryError::TransactionError {
+                    msg,
+                    level,
+                })
+            }
+            TransactionError::Inner(e) => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{
+            mock_inbound_shipment_a, mock_name_store_b, mock_stock_line_a, mock_store_a,
+            mock_store_b, mock_user_account_a, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRow,
+        InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType,
+    };
+    use util::{inline_edit, inline_init};
+
+    use crate::{
+        invoice::{customer_return::InsertCustomerReturn, InsertReturnLine},
+        service_provider::ServiceProvider,
+    };
+
+    use super::InsertCustomerReturnError;
+
+    type ServiceError = InsertCustomerReturnError;
+
+    fn outbound() -> InvoiceRow {
+        inline_init(|r: &mut InvoiceRow| {
+            r.id = "return_outbound".to_string();
+            r.name_id = mock_name_store_b().id;
+            r.store_id = mock_store_a().id;
+            r.r#type = InvoiceRowType::OutboundShipment;
+            r.status = InvoiceRowStatus::Shipped;
+            r.picked_datetime = Some(NaiveDate::from_ymd(2022, 1, 1).and_hms(0, 0, 0));
+        })
+    }
+
+    fn outbound_line() -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = "return_outbound_line".to_string();
+            r.invoice_id = outbound().id;
+            r.item_id = mock_stock_line_a().item_id;
+            r.stock_line_id = Some(mock_stock_line_a().id);
+            r.r#type = InvoiceLineRowType::StockOut;
+            r.pack_size = 1;
+            r.sell_price_per_pack = 2.0;
+            r.number_of_packs = 10;
+        })
+    }
+
+    fn return_line(id: &str, number_of_packs: i32) -> InsertReturnLine {
+        inline_init(|r: &mut InsertReturnLine| {
+            r.id = id.to_string();
+            r.original_invoice_line_id = outbound_line().id;
+            r.number_of_packs = number_of_packs;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn insert_customer_return_errors() {
+        fn not_picked() -> InvoiceRow {
+            inline_edit(&outbound(), |mut u| {
+                u.id = "not_picked".to_string();
+                u.status = InvoiceRowStatus::New;
+                u.picked_datetime = None;
+                u
+            })
+        }
+
+        fn other_store() -> InvoiceRow {
+            inline_edit(&outbound(), |mut u| {
+                u.id = "other_store".to_string();
+                u.store_id = mock_store_b().id;
+                u
+            })
+        }
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "insert_customer_return_errors",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![outbound(), not_picked(), other_store()];
+                r.invoice_lines = vec![outbound_line()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        let input = |original_invoice_id: &str, lines: Vec<InsertReturnLine>| {
+            inline_init(|r: &mut InsertCustomerReturn| {
+                r.id = "customer_return".to_string();
+                r.original_invoice_id = original_invoice_id.to_string();
+                r.lines = lines;
+            })
+        };
+
+        // InvoiceAlreadyExists
+        assert_eq!(
+            service.insert_customer_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                inline_edit(&input(&outbound().id, vec![]), |mut u| {
+                    u.id = outbound().id;
+                    u
+                })
+            ),
+            Err(ServiceError::InvoiceAlreadyExists)
+        );
+        // OriginalInvoiceDoesNotExist
+        assert_eq!(
+            service.insert_customer_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                input("invalid", vec![])
+            ),
+            Err(ServiceError::OriginalInvoiceDoesNotExist)
+        );
+        // OriginalInvoiceNotThisStore
+        assert_eq!(
+            service.insert_customer_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                input(&other_store().id, vec![])
+            ),
+            Err(ServiceError::OriginalInvoiceNotThisStore)
+        );
+        // NotAStockOutInvoice
+        assert_eq!(
+            service.insert_customer_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                input(&mock_inbound_shipment_a().id, vec![])
+            ),
+            Err(ServiceError::NotAStockOutInvoice)
+        );
+        // OriginalInvoiceNotIssued
+        assert_eq!(
+            service.insert_customer_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                input(&not_picked().id, vec![])
+            ),
+            Err(ServiceError::OriginalInvoiceNotIssued)
+        );
+        // OriginalLineDoesNotExist
+        assert_eq!(
+            service.insert_customer_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                input(
+                    &outbound().id,
+                    vec![inline_edit(&return_line("line1", 1), |mut u| {
+                        u.original_invoice_line_id = "invalid".to_string();
+                        u
+                    })]
+                )
+            ),
+            Err(ServiceError::OriginalLineDoesNotExist("line1".to_string()))
+        );
+        // NumberOfPacksBelowOne
+        assert_eq!(
+            service.insert_customer_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                input(&outbound().id, vec![return_line("line1", 0)])
+            ),
+            Err(ServiceError::NumberOfPacksBelowOne("line1".to_string()))
+        );
+        // ReturnedQuantityExceedsIssued
+        assert_eq!(
+            service.insert_customer_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                input(
+                    &outbound().id,
+                    vec![return_line("line1", 6), return_line("line2", 5)]
+                )
+            ),
+            Err(ServiceError::ReturnedQuantityExceedsIssued {
+                line_id: "line2".to_string(),
+                returnable_number_of_packs: 4
+            })
+        );
+    }
+
+    #[actix_rt::test]
+    async fn insert_customer_return_success() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "insert_customer_return_success",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![outbound()];
+                r.invoice_lines = vec![outbound_line()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        service
+            .insert_customer_return(
+                &context,
+                &mock_store_a().id,
+                &mock_user_account_a().id,
+                inline_init(|r: &mut InsertCustomerReturn| {
+                    r.id = "customer_return".to_string();
+                    r.original_invoice_id = outbound().id;
+                    r.lines = vec![inline_edit(&return_line("line1", 4), |mut u| {
+                        u.return_reason = Some("Damaged".to_string());
+                        u
+                    })];
+                }),
+            )
+            .unwrap();
+
+        let invoice = InvoiceRowRepository::new(&connection)
+            .find_one_by_id("customer_return")
+            .unwrap();
+        assert_eq!(
+            invoice,
+            inline_edit(&invoice, |mut u| {
+                u.name_id = mock_name_store_b().id;
+                u.user_id = Some(mock_user_account_a().id);
+                u.r#type = InvoiceRowType::CustomerReturn;
+                u.status = InvoiceRowStatus::New;
+                u
+            })
+        );
+
+        let line = InvoiceLineRowRepository::new(&connection)
+            .find_one_by_id("line1")
+            .unwrap();
+        assert_eq!(
+            line,
+            inline_edit(&outbound_line(), |mut u| {
+                u.id = "line1".to_string();
+                u.invoice_id = "customer_return".to_string();
+                u.r#type = InvoiceLineRowType::StockIn;
+                u.number_of_packs = 4;
+                u.total_before_tax = 8.0;
+                u.total_after_tax = 8.0;
+                u.original_invoice_line_id = Some(outbound_line().id);
+                u.return_reason = Some("Damaged".to_string());
+                u
+            })
+        );
+
+        // Packs already being returned can't be returned again
+        assert_eq!(
+            service.insert_customer_return(
+                &context,
+                &mock_store_a().id,
+                &mock_user_account_a().id,
+                inline_init(|r: &mut InsertCustomerReturn| {
+                    r.id = "customer_return2".to_string();
+                    r.original_invoice_id = outbound().id;
+                    r.lines = vec![return_line("line2", 7)];
+                }),
+            ),
+            Err(ServiceError::ReturnedQuantityExceedsIssued {
+                line_id: "line2".to_string(),
+                returnable_number_of_packs: 6
+            })
+        );
+    }
+}
@@ -0,0 +1,626 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, Utc};
+use repository::{
+    Invoice, InvoiceLineRow, InvoiceLineRowRepository, InvoiceRow, InvoiceRowRepository,
+    InvoiceRowStatus, InvoiceRowType, RepositoryError, StockLineRow, StockLineRowRepository,
+    StorageConnection, TransactionError,
+};
+use util::uuid::uuid;
+
+use crate::invoice::{
+    check_invoice_exists, check_invoice_is_editable, check_invoice_status, check_invoice_type,
+    query::get_invoice, InvoiceDoesNotExist, InvoiceIsNotEditable, InvoiceRowStatusError,
+    WrongInvoiceRowType,
+};
+use crate::{service_provider::ServiceContext, WithDBError};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UpdateCustomerReturnStatus {
+    Delivered,
+    Verified,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct UpdateCustomerReturn {
+    pub id: String,
+    pub status: Option<UpdateCustomerReturnStatus>,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub colour: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateCustomerReturnError {
+    CannotReverseInvoiceStatus,
+    InvoiceDoesNotExist,
+    InvoiceIsNotEditable,
+    NotACustomerReturn,
+    NotThisStoreInvoice,
+    // Internal
+    UpdatedInvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = UpdateCustomerReturnError;
+
+/// Updates a customer return, the returned stock is put back into the store when the return is
+/// delivered.
+pub fn update_customer_return(
+    ctx: &ServiceContext,
+    store_id: &str,
+    patch: UpdateCustomerReturn,
+) -> Result<Invoice, OutError> {
+    let invoice = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let invoice = validate(connection, store_id, &patch)?;
+            let (update_invoice, stock_lines_option) = generate(connection, invoice, patch)?;
+
+            InvoiceRowRepository::new(connection).upsert_one(&update_invoice)?;
+            if let Some((stock_lines, invoice_lines)) = stock_lines_option {
+                let stock_line_repository = StockLineRowRepository::new(connection);
+                for stock_line in stock_lines {
+                    stock_line_repository.upsert_one(&stock_line)?;
+                }
+                let invoice_line_repository = InvoiceLineRowRepository::new(connection);
+                for invoice_line in invoice_lines {
+                    invoice_line_repository.upsert_one(&invoice_line)?;
+                }
+            }
+
+            get_invoice(ctx, None, &update_invoice.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::UpdatedInvoiceDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(invoice)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    patch: &UpdateCustomerReturn,
+) -> Result<InvoiceRow, OutError> {
+    let invoice = check_invoice_exists(&patch.id, connection)?;
+    check_invoice_type(&invoice, InvoiceRowType::CustomerReturn)?;
+    if invoice.store_id != store_id {
+        return Err(OutError::NotThisStoreInvoice);
+    }
+    check_invoice_is_editable(&invoice)?;
+    check_invoice_status(&invoice, patch.full_status(), &None)?;
+
+    Ok(invoice)
+}
+
+fn generate(
+    connection: &StorageConnection,
+    existing_invoice: InvoiceRow,
+    UpdateCustomerReturn {
+        id: _,
+        status,
+        their_reference,
+        comment,
+        colour,
+    }: UpdateCustomerReturn,
+) -> Result<(InvoiceRow, Option<(Vec<StockLineRow>, Vec<InvoiceLineRow>)>), RepositoryError> {
+    let new_status = status.map(|status| status.full_status());
+    let is_delivering = match &new_status {
+        Some(new_status) => {
+            new_status.index() >= InvoiceRowStatus::Delivered.index()
+                && existing_invoice.status.index() < InvoiceRowStatus::Delivered.index()
+        }
+        None => false,
+    };
+
+    let mut update_invoice = existing_invoice;
+    update_invoice.their_reference = their_reference.or(update_invoice.their_reference);
+    update_invoice.comment = comment.or(update_invoice.comment);
+    update_invoice.colour = colour.or(update_invoice.colour);
+
+    if let Some(new_status) = new_status {
+        let current_datetime = Utc::now().naive_utc();
+        if is_delivering {
+            update_invoice.delivered_datetime = Some(current_datetime);
+        }
+        if new_status == InvoiceRowStatus::Verified
+            && update_invoice.status != InvoiceRowStatus::Verified
+        {
+            update_invoice.verified_datetime = Some(current_datetime);
+        }
+        update_invoice.status = new_status;
+    }
+
+    if !is_delivering {
+        return Ok((update_invoice, None));
+    }
+    let batches = generate_batches(connection, &update_invoice)?;
+    Ok((update_invoice, Some(batches)))
+}
+
+/// Batch of a return line whose stock line no longer exists
+#[derive(PartialEq, Eq, Hash)]
+struct RemovedBatch {
+    stock_line_id: Option<String>,
+    item_id: String,
+    batch: Option<String>,
+    expiry_date: Option<NaiveDate>,
+    pack_size: i32,
+    location_id: Option<String>,
+}
+
+impl RemovedBatch {
+    fn new(invoice_line: &InvoiceLineRow) -> Self {
+        RemovedBatch {
+            stock_line_id: invoice_line.stock_line_id.clone(),
+            item_id: invoice_line.item_id.clone(),
+            batch: invoice_line.batch.clone(),
+            expiry_date: invoice_line.expiry_date,
+            pack_size: invoice_line.pack_size,
+            location_id: invoice_line.location_id.clone(),
+        }
+    }
+}
+
+/// Puts the returned packs back onto the stock line they were issued from. If that stock line no
+/// longer exists a single new stock line is created for all return lines of the removed batch and
+/// linked to them, later calls find the new stock line through the return lines.
+fn generate_batches(
+    connection: &StorageConnection,
+    invoice: &InvoiceRow,
+) -> Result<(Vec<StockLineRow>, Vec<InvoiceLineRow>), RepositoryError> {
+    let stock_line_repository = StockLineRowRepository::new(connection);
+    let mut stock_lines: Vec<StockLineRow> = Vec::new();
+    let mut invoice_lines = Vec::new();
+    // Id of the stock line created in place of a removed batch
+    let mut replacements: HashMap<RemovedBatch, String> = HashMap::new();
+
+    for mut invoice_line in
+        InvoiceLineRowRepository::new(connection).find_many_by_invoice_id(&invoice.id)?
+    {
+        if let Some(replacement_id) = replacements.get(&RemovedBatch::new(&invoice_line)).cloned() {
+            if let Some(stock_line) = stock_lines
+                .iter_mut()
+                .find(|other| other.id == replacement_id)
+            {
+                stock_line.available_number_of_packs += invoice_line.number_of_packs;
+                stock_line.total_number_of_packs += invoice_line.number_of_packs;
+            }
+            invoice_line.stock_line_id = Some(replacement_id);
+            invoice_lines.push(invoice_line);
+            continue;
+        }
+
+        if let Some(stock_line_id) = &invoice_line.stock_line_id {
+            // Several return lines can put packs back onto the same stock line
+            if let Some(stock_line) = stock_lines
+                .iter_mut()
+                .find(|other| &other.id == stock_line_id)
+            {
+                stock_line.available_number_of_packs += invoice_line.number_of_packs;
+                stock_line.total_number_of_packs += invoice_line.number_of_packs;
+                continue;
+            }
+        }
+
+        let existing_stock_line = match &invoice_line.stock_line_id {
+            Some(stock_line_id) => match stock_line_repository.find_one_by_id(stock_line_id) {
+                Ok(stock_line) => Some(stock_line),
+                Err(RepositoryError::NotFound) => None,
+                Err(error) => return Err(error),
+            },
+            None => None,
+        };
+
+        let stock_line = match existing_stock_line {
+            Some(mut stock_line) => {
+                stock_line.available_number_of_packs += invoice_line.number_of_packs;
+                stock_line.total_number_of_packs += invoice_line.number_of_packs;
+                stock_line
+            }
+            None => {
+                let stock_line = StockLineRow {
+                    id: uuid(),
+                    item_id: invoice_line.item_id.clone(),
+                    store_id: invoice.store_id.clone(),
+                    location_id: invoice_line.location_id.clone(),
+                    batch: invoice_line.batch.clone(),
+                    pack_size: invoice_line.pack_size,
+                    cost_price_per_pack: invoice_line.cost_price_per_pack,
+                    sell_price_per_pack: invoice_line.sell_price_per_pack,
+                    available_number_of_packs: invoice_line.number_of_packs,
+                    total_number_of_packs: invoice_line.number_of_packs,
+                    expiry_date: invoice_line.expiry_date,
+                    on_hold: false,
+                    note: invoice_line.note.clone(),
+                };
+                replacements.insert(RemovedBatch::new(&invoice_line), stock_line.id.clone());
+                invoice_line.stock_line_id = Some(stock_line.id.clone());
+                invoice_lines.push(invoice_line);
+                stock_line
+            }
+        };
+        stock_lines.push(stock_line);
+    }
+
+    Ok((stock_lines, invoice_lines))
+}
+
+impl UpdateCustomerReturnStatus {
+    pub fn full_status(&self) -> InvoiceRowStatus {
+        match self {
+            UpdateCustomerReturnStatus::Delivered => InvoiceRowStatus::Delivered,
+            UpdateCustomerReturnStatus::Verified => InvoiceRowStatus::Verified,
+        }
+    }
+}
+
+impl UpdateCustomerReturn {
+    pub fn full_status(&self) -> Option<InvoiceRowStatus> {
+        self.status.as_ref().map(|status| status.full_status())
+    }
+}
+
+impl From<WrongInvoiceRowType> for UpdateCustomerReturnError {
+    fn from(_: WrongInvoiceRowType) -> Self {
+        UpdateCustomerReturnError::NotACustomerReturn
+    }
+}
+
+impl From<InvoiceDoesNotExist> for UpdateCustomerReturnError {
+    fn from(_: InvoiceDoesNotExist) -> Self {
+        UpdateCustomerReturnError::InvoiceDoesNotExist
+    }
+}
+
+impl From<InvoiceIsNotEditable> for UpdateCustomerReturnError {
+    fn from(_: InvoiceIsNotEditable) -> Self {
+        UpdateCustomerReturnError::InvoiceIsNotEditable
+    }
+}
+
+impl From<InvoiceRowStatusError> for UpdateCustomerReturnError {
+    fn from(error: InvoiceRowStatusError) -> Self {
+        match error {
+            // Returns can't be put on hold
+            InvoiceRowStatusError::CannotChangeStatusOfInvoiceOnHold
+            | InvoiceRowStatusError::CannotReverseInvoiceStatus => {
+                UpdateCustomerReturnError::CannotReverseInvoiceStatus
+            }
+        }
+    }
+}
+
+impl From<RepositoryError> for UpdateCustomerReturnError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateCustomerReturnError::DatabaseError(error)
+    }
+}
+
+impl From<TransactionError<UpdateCustomerReturnError>> for UpdateCustomerReturnError {
+    fn from(error: TransactionError<UpdateCustomerReturnError>) -> Self {
+        match error {
+            TransactionError::Transaction { msg, level } => {
+                UpdateCustomerReturnError::DatabaseError(RepositoryError::TransactionError {
+                    msg,
+                    level,
+                })
+            }
+            TransactionError::Inner(e) => e,
+        }
+    }
+}
+
+impl<ERR> From<WithDBError<ERR>> for UpdateCustomerReturnError
+where
+    ERR: Into<UpdateCustomerReturnError>,
+{
+    fn from(result: WithDBError<ERR>) -> Self {
+        match result {
+            WithDBError::DatabaseError(error) => error.into(),
+            WithDBError::Error(error) => error.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_item_b, mock_item_c, mock_name_store_b, mock_outbound_shipment_a,
+            mock_stock_line_a, mock_store_a, mock_store_b, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        EqualFilter, InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRow,
+        InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType, StockLineFilter,
+        StockLineRepository, StockLineRowRepository,
+    };
+    use util::inline_init;
+
+    use crate::{
+        invoice::customer_return::{UpdateCustomerReturn, UpdateCustomerReturnStatus},
+        service_provider::ServiceProvider,
+    };
+
+    use super::UpdateCustomerReturnError;
+
+    type ServiceError = UpdateCustomerReturnError;
+
+    fn customer_return() -> InvoiceRow {
+        inline_init(|r: &mut InvoiceRow| {
+            r.id = "customer_return".to_string();
+            r.name_id = mock_name_store_b().id;
+            r.store_id = mock_store_a().id;
+            r.r#type = InvoiceRowType::CustomerReturn;
+            r.status = InvoiceRowStatus::New;
+        })
+    }
+
+    fn existing_stock_line_return() -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = "existing_stock_line_return".to_string();
+            r.invoice_id = customer_return().id;
+            r.item_id = mock_stock_line_a().item_id;
+            r.stock_line_id = Some(mock_stock_line_a().id);
+            r.r#type = InvoiceLineRowType::StockIn;
+            r.pack_size = 1;
+            r.number_of_packs = 3;
+        })
+    }
+
+    fn second_existing_stock_line_return() -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = "second_existing_stock_line_return".to_string();
+            r.invoice_id = customer_return().id;
+            r.item_id = mock_stock_line_a().item_id;
+            r.stock_line_id = Some(mock_stock_line_a().id);
+            r.r#type = InvoiceLineRowType::StockIn;
+            r.pack_size = 1;
+            r.number_of_packs = 4;
+        })
+    }
+
+    fn removed_stock_line_return() -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = "removed_stock_line_return".to_string();
+            r.invoice_id = customer_return().id;
+            r.item_id = mock_item_b().id;
+            r.r#type = InvoiceLineRowType::StockIn;
+            r.batch = Some("returned".to_string());
+            r.pack_size = 2;
+            r.number_of_packs = 5;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn update_customer_return_errors() {
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "update_customer_return_errors",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![inline_init(|r: &mut InvoiceRow| {
+                    *r = customer_return();
+                    r.status = InvoiceRowStatus::Verified;
+                })];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        // InvoiceDoesNotExist
+        assert_eq!(
+            service.update_customer_return(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdateCustomerReturn| {
+                    r.id = "invalid".to_string();
+                })
+            ),
+            Err(ServiceError::InvoiceDoesNotExist)
+        );
+        // NotACustomerReturn
+        assert_eq!(
+            service.update_customer_return(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdateCustomerReturn| {
+                    r.id = mock_outbound_shipment_a().id;
+                })
+            ),
+            Err(ServiceError::NotACustomerReturn)
+        );
+        // NotThisStoreInvoice
+        assert_eq!(
+            service.update_customer_return(
+                &context,
+                &mock_store_b().id,
+                inline_init(|r: &mut UpdateCustomerReturn| {
+                    r.id = customer_return().id;
+                })
+            ),
+            Err(ServiceError::NotThisStoreInvoice)
+        );
+        // InvoiceIsNotEditable
+        assert_eq!(
+            service.update_customer_return(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdateCustomerReturn| {
+                    r.id = customer_return().id;
+                })
+            ),
+            Err(ServiceError::InvoiceIsNotEditable)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn update_customer_return_success() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "update_customer_return_success",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![customer_return()];
+                r.invoice_lines = vec![
+                    existing_stock_line_return(),
+                    second_existing_stock_line_return(),
+                    removed_stock_line_return(),
+                ];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        service
+            .update_customer_return(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdateCustomerReturn| {
+                    r.id = customer_return().id;
+                    r.status = Some(UpdateCustomerReturnStatus::Delivered);
+                }),
+            )
+            .unwrap();
+
+        let invoice = InvoiceRowRepository::new(&connection)
+            .find_one_by_id(&customer_return().id)
+            .unwrap();
+        assert_eq!(invoice.status, InvoiceRowStatus::Delivered);
+        assert!(invoice.delivered_datetime.is_some());
+
+        // Stock of both lines is put back onto the original stock line
+        let stock_line_repository = StockLineRowRepository::new(&connection);
+        let stock_line = stock_line_repository
+            .find_one_by_id(&mock_stock_line_a().id)
+            .unwrap();
+        assert_eq!(
+            stock_line.available_number_of_packs,
+            mock_stock_line_a().available_number_of_packs + 3 + 4
+        );
+        assert_eq!(
+            stock_line.total_number_of_packs,
+            mock_stock_line_a().total_number_of_packs + 3 + 4
+        );
+
+        // Or onto a new stock line
+        let line = InvoiceLineRowRepository::new(&connection)
+            .find_one_by_id(&removed_stock_line_return().id)
+            .unwrap();
+        let stock_line = stock_line_repository
+            .find_one_by_id(&line.stock_line_id.unwrap())
+            .unwrap();
+        assert_eq!(stock_line.item_id, mock_item_b().id);
+        assert_eq!(stock_line.store_id, mock_store_a().id);
+        assert_eq!(stock_line.batch, Some("returned".to_string()));
+        assert_eq!(stock_line.pack_size, 2);
+        assert_eq!(stock_line.total_number_of_packs, 5);
+
+        // Verifying doesn't return the stock again
+        service
+            .update_customer_return(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdateCustomerReturn| {
+                    r.id = customer_return().id;
+                    r.status = Some(UpdateCustomerReturnStatus::Verified);
+                }),
+            )
+            .unwrap();
+
+        let invoice = InvoiceRowRepository::new(&connection)
+            .find_one_by_id(&customer_return().id)
+            .unwrap();
+        assert_eq!(invoice.status, InvoiceRowStatus::Verified);
+        assert!(invoice.verified_datetime.is_some());
+        let stock_line = stock_line_repository
+            .find_one_by_id(&mock_stock_line_a().id)
+            .unwrap();
+        assert_eq!(
+            stock_line.total_number_of_packs,
+            mock_stock_line_a().total_number_of_packs + 3 + 4
+        );
+    }
+
+    fn removed_batch_return(id: &str, number_of_packs: i32) -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = id.to_string();
+            r.invoice_id = customer_return().id;
+            r.item_id = mock_item_c().id;
+            r.r#type = InvoiceLineRowType::StockIn;
+            r.batch = Some("removed".to_string());
+            r.pack_size = 1;
+            r.number_of_packs = number_of_packs;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn update_customer_return_removed_stock_line() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "update_customer_return_removed_stock_line",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![customer_return()];
+                r.invoice_lines = vec![
+                    removed_batch_return("removed_batch_return_a", 5),
+                    removed_batch_return("removed_batch_return_b", 6),
+                ];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        let item_c_stock_lines = || {
+            StockLineRepository::new(&connection)
+                .query_by_filter(
+                    StockLineFilter::new()
+                        .item_id(EqualFilter::equal_to(&mock_item_c().id))
+                        .store_id(EqualFilter::equal_to(&mock_store_a().id)),
+                )
+                .unwrap()
+        };
+        let stock_line_count = item_c_stock_lines().len();
+
+        for status in [
+            UpdateCustomerReturnStatus::Delivered,
+            UpdateCustomerReturnStatus::Verified,
+        ] {
+            service
+                .update_customer_return(
+                    &context,
+                    &mock_store_a().id,
+                    inline_init(|r: &mut UpdateCustomerReturn| {
+                        r.id = customer_return().id;
+                        r.status = Some(status);
+                    }),
+                )
+                .unwrap();
+
+            // Both lines are returned onto a single new stock line
+            let stock_lines = item_c_stock_lines();
+            assert_eq!(stock_lines.len(), stock_line_count + 1);
+            let line_repository = InvoiceLineRowRepository::new(&connection);
+            let line_a = line_repository
+                .find_one_by_id("removed_batch_return_a")
+                .unwrap();
+            let line_b = line_repository
+                .find_one_by_id("removed_batch_return_b")
+                .unwrap();
+            assert_eq!(line_a.stock_line_id, line_b.stock_line_id);
+            let stock_line = StockLineRowRepository::new(&connection)
+                .find_one_by_id(&line_a.stock_line_id.unwrap())
+                .unwrap();
+            assert_eq!(stock_line.batch, Some("removed".to_string()));
+            assert_eq!(stock_line.available_number_of_packs, 5 + 6);
+            assert_eq!(stock_line.total_number_of_packs, 5 + 6);
+        }
+    }
+}
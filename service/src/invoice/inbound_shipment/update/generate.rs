@@ -120,6 +120,9 @@ pub fn generate_lines_and_stock_lines(
             r#type: _,
             number_of_packs,
             note,
+            original_invoice_line_id: _,
+            return_reason: _,
+            inventory_adjustment_reason_id: _,
         }: InvoiceLineRow = invoice_lines;
 
         let stock_line = StockLineRow {
@@ -0,0 +1,453 @@
+use chrono::Utc;
+use repository::{
+    InventoryAdjustmentType, Invoice, InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType,
+    InvoiceRow, InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType, ItemRowRepository,
+    NameRowRepository, NumberRowType, RepositoryError, StockLineRow, StockLineRowRepository,
+    StorageConnection, TransactionError,
+};
+use util::{constants::INVENTORY_ADJUSTMENT_NAME_CODE, uuid::uuid};
+
+use crate::inventory_adjustment_reason::validate::{
+    check_reason_is_required, check_reason_is_valid,
+};
+use crate::invoice::check_invoice_exists_option;
+use crate::number::next_number;
+use crate::{invoice::query::get_invoice, service_provider::ServiceContext};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InsertInventoryAdjustment {
+    pub id: String,
+    pub stock_line_id: String,
+    /// Positive adds stock to the stock line, negative removes stock from it
+    pub r#type: InventoryAdjustmentType,
+    pub number_of_packs: i32,
+    pub inventory_adjustment_reason_id: Option<String>,
+    pub comment: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum InsertInventoryAdjustmentError {
+    InvoiceAlreadyExists,
+    StockLineDoesNotExist,
+    StockLineDoesNotBelongToCurrentStore,
+    NumberOfPacksBelowOne,
+    /// Only available stock can be removed, i.e. stock that isn't allocated to other invoices
+    ReductionExceedsAvailable {
+        available_number_of_packs: i32,
+    },
+    /// Reasons are configured for this adjustment type but no reason was provided
+    AdjustmentReasonNotProvided,
+    /// Reason doesn't exist, is inactive or is for the opposite adjustment type
+    AdjustmentReasonNotValid,
+    // Internal
+    NewlyCreatedInvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+    InternalError(String),
+}
+
+type OutError = InsertInventoryAdjustmentError;
+
+/// Adjusts the stock of a single stock line (e.g. stock found, damaged or expired). The adjustment
+/// is recorded as a verified inventory adjustment invoice with a single line.
+pub fn insert_inventory_adjustment(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    input: InsertInventoryAdjustment,
+) -> Result<Invoice, OutError> {
+    let invoice = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let stock_line = validate(connection, store_id, &input)?;
+            let (new_invoice, new_line, updated_stock_line) =
+                generate(connection, store_id, user_id, stock_line, input)?;
+
+            InvoiceRowRepository::new(connection).upsert_one(&new_invoice)?;
+            InvoiceLineRowRepository::new(connection).upsert_one(&new_line)?;
+            StockLineRowRepository::new(connection).upsert_one(&updated_stock_line)?;
+
+            get_invoice(ctx, None, &new_invoice.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::NewlyCreatedInvoiceDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(invoice)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &InsertInventoryAdjustment,
+) -> Result<StockLineRow, OutError> {
+    use InsertInventoryAdjustmentError::*;
+
+    if check_invoice_exists_option(&input.id, connection)?.is_some() {
+        return Err(InvoiceAlreadyExists);
+    }
+
+    let stock_line =
+        match StockLineRowRepository::new(connection).find_one_by_id(&input.stock_line_id) {
+            Ok(stock_line) => stock_line,
+            Err(RepositoryError::NotFound) => return Err(StockLineDoesNotExist),
+            Err(error) => return Err(error.into()),
+        };
+    if stock_line.store_id != store_id {
+        return Err(StockLineDoesNotBelongToCurrentStore);
+    }
+
+    if input.number_of_packs < 1 {
+        return Err(NumberOfPacksBelowOne);
+    }
+    if input.r#type == InventoryAdjustmentType::Negative
+        && input.number_of_packs > stock_line.available_number_of_packs
+    {
+        return Err(ReductionExceedsAvailable {
+            available_number_of_packs: stock_line.available_number_of_packs,
+        });
+    }
+
+    match &input.inventory_adjustment_reason_id {
+        Some(reason_id) => {
+            if !check_reason_is_valid(connection, reason_id, &input.r#type)? {
+                return Err(AdjustmentReasonNotValid);
+            }
+        }
+        None => {
+            if check_reason_is_required(connection, &input.r#type)? {
+                return Err(AdjustmentReasonNotProvided);
+            }
+        }
+    }
+
+    Ok(stock_line)
+}
+
+fn generate(
+    connection: &StorageConnection,
+    store_id: &str,
+    user_id: &str,
+    stock_line: StockLineRow,
+    InsertInventoryAdjustment {
+        id,
+        stock_line_id: _,
+        r#type,
+        number_of_packs,
+        inventory_adjustment_reason_id,
+        comment,
+    }: InsertInventoryAdjustment,
+) -> Result<(InvoiceRow, InvoiceLineRow, StockLineRow), OutError> {
+    let inventory_adjustment_name = NameRowRepository::new(connection)
+        .find_one_by_code(INVENTORY_ADJUSTMENT_NAME_CODE)?
+        .ok_or(OutError::InternalError(
+            "Missing inventory adjustment name".to_string(),
+        ))?;
+    let item = ItemRowRepository::new(connection)
+        .find_one_by_id(&stock_line.item_id)?
+        .ok_or(OutError::InternalError(format!(
+            "Can't find item {} for stock line {}",
+            stock_line.item_id, stock_line.id
+        )))?;
+
+    let now = Utc::now().naive_utc();
+    let invoice = InvoiceRow {
+        id: id.clone(),
+        user_id: Some(user_id.to_string()),
+        name_id: inventory_adjustment_name.id,
+        store_id: store_id.to_string(),
+        invoice_number: next_number(connection, &NumberRowType::InventoryAdjustment, store_id)?,
+        r#type: InvoiceRowType::InventoryAdjustment,
+        status: InvoiceRowStatus::Verified,
+        comment,
+        created_datetime: now,
+        verified_datetime: Some(now),
+        ..Default::default()
+    };
+
+    let (line_type, delta) = match r#type {
+        InventoryAdjustmentType::Positive => (InvoiceLineRowType::StockIn, number_of_packs),
+        InventoryAdjustmentType::Negative => (InvoiceLineRowType::StockOut, -number_of_packs),
+    };
+    let line = InvoiceLineRow {
+        id: uuid(),
+        invoice_id: id,
+        r#type: line_type,
+        item_id: stock_line.item_id.clone(),
+        item_name: item.name,
+        item_code: item.code,
+        stock_line_id: Some(stock_line.id.clone()),
+        location_id: stock_line.location_id.clone(),
+        batch: stock_line.batch.clone(),
+        expiry_date: stock_line.expiry_date,
+        pack_size: stock_line.pack_size,
+        cost_price_per_pack: stock_line.cost_price_per_pack,
+        sell_price_per_pack: stock_line.sell_price_per_pack,
+        total_before_tax: 0.0,
+        total_after_tax: 0.0,
+        tax: None,
+        number_of_packs,
+        note: stock_line.note.clone(),
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id,
+    };
+
+    let updated_stock_line = StockLineRow {
+        available_number_of_packs: stock_line.available_number_of_packs + delta,
+        total_number_of_packs: stock_line.total_number_of_packs + delta,
+        ..stock_line
+    };
+
+    Ok((invoice, line, updated_stock_line))
+}
+
+impl From<RepositoryError> for InsertInventoryAdjustmentError {
+    fn from(error: RepositoryError) -> Self {
+        InsertInventoryAdjustmentError::DatabaseError(error)
+    }
+}
+
+impl From<TransactionError<InsertInventoryAdjustmentError>> for InsertInventoryAdjustmentError {
+    fn from(error: TransactionError<InsertInventoryAdjustmentError>) -> Self {
+        match error {
+            TransactionError::Transaction { msg, level } => {
+                InsertInventoryAdjustmentError::DatabaseError(RepositoryError::TransactionError {
+                    msg,
+                    level,
+                })
+            }
+            TransactionError::Inner(e) => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_inactive_inventory_adjustment_reason, mock_inventory_adjustment_reasons,
+            mock_item_b_lines, mock_negative_inventory_adjustment_reason, mock_outbound_shipment_a,
+            mock_positive_inventory_adjustment_reason, mock_stock_line_a, mock_store_a, MockData,
+            MockDataInserts,
+        },
+        test_db::{setup_all, setup_all_with_data},
+        InventoryAdjustmentType, InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRowStatus,
+        InvoiceRowType, StockLineRowRepository,
+    };
+    use util::{inline_edit, inline_init};
+
+    use crate::{
+        invoice::inventory_adjustment::InsertInventoryAdjustment, service_provider::ServiceProvider,
+    };
+
+    use super::InsertInventoryAdjustmentError;
+
+    type ServiceError = InsertInventoryAdjustmentError;
+
+    fn reduction(number_of_packs: i32) -> InsertInventoryAdjustment {
+        inline_init(|r: &mut InsertInventoryAdjustment| {
+            r.id = "inventory_adjustment".to_string();
+            r.stock_line_id = mock_stock_line_a().id;
+            r.r#type = InventoryAdjustmentType::Negative;
+            r.number_of_packs = number_of_packs;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn insert_inventory_adjustment_errors() {
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "insert_inventory_adjustment_errors",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.inventory_adjustment_reasons = mock_inventory_adjustment_reasons();
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+        let store_id = mock_store_a().id;
+
+        // InvoiceAlreadyExists
+        assert_eq!(
+            service.insert_inventory_adjustment(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&reduction(1), |mut u| {
+                    u.id = mock_outbound_shipment_a().id;
+                    u
+                })
+            ),
+            Err(ServiceError::InvoiceAlreadyExists)
+        );
+        // StockLineDoesNotExist
+        assert_eq!(
+            service.insert_inventory_adjustment(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&reduction(1), |mut u| {
+                    u.stock_line_id = "invalid".to_string();
+                    u
+                })
+            ),
+            Err(ServiceError::StockLineDoesNotExist)
+        );
+        // StockLineDoesNotBelongToCurrentStore
+        assert_eq!(
+            service.insert_inventory_adjustment(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&reduction(1), |mut u| {
+                    u.stock_line_id = mock_item_b_lines()[0].id.clone();
+                    u
+                })
+            ),
+            Err(ServiceError::StockLineDoesNotBelongToCurrentStore)
+        );
+        // NumberOfPacksBelowOne
+        assert_eq!(
+            service.insert_inventory_adjustment(&context, &store_id, "n/a", reduction(0)),
+            Err(ServiceError::NumberOfPacksBelowOne)
+        );
+        // ReductionExceedsAvailable
+        assert_eq!(
+            service.insert_inventory_adjustment(
+                &context,
+                &store_id,
+                "n/a",
+                reduction(mock_stock_line_a().available_number_of_packs + 1)
+            ),
+            Err(ServiceError::ReductionExceedsAvailable {
+                available_number_of_packs: mock_stock_line_a().available_number_of_packs
+            })
+        );
+        // AdjustmentReasonNotProvided
+        assert_eq!(
+            service.insert_inventory_adjustment(&context, &store_id, "n/a", reduction(1)),
+            Err(ServiceError::AdjustmentReasonNotProvided)
+        );
+        // AdjustmentReasonNotValid (reason for the opposite adjustment type)
+        assert_eq!(
+            service.insert_inventory_adjustment(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&reduction(1), |mut u| {
+                    u.inventory_adjustment_reason_id =
+                        Some(mock_positive_inventory_adjustment_reason().id);
+                    u
+                })
+            ),
+            Err(ServiceError::AdjustmentReasonNotValid)
+        );
+        // AdjustmentReasonNotValid (inactive reason)
+        assert_eq!(
+            service.insert_inventory_adjustment(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&reduction(1), |mut u| {
+                    u.inventory_adjustment_reason_id =
+                        Some(mock_inactive_inventory_adjustment_reason().id);
+                    u
+                })
+            ),
+            Err(ServiceError::AdjustmentReasonNotValid)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn insert_inventory_adjustment_success() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "insert_inventory_adjustment_success",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.inventory_adjustment_reasons = mock_inventory_adjustment_reasons();
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        let invoice = service
+            .insert_inventory_adjustment(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                inline_edit(&reduction(5), |mut u| {
+                    u.inventory_adjustment_reason_id =
+                        Some(mock_negative_inventory_adjustment_reason().id);
+                    u
+                }),
+            )
+            .unwrap();
+        assert_eq!(
+            invoice.invoice_row.r#type,
+            InvoiceRowType::InventoryAdjustment
+        );
+        assert_eq!(invoice.invoice_row.status, InvoiceRowStatus::Verified);
+
+        let lines = InvoiceLineRowRepository::new(&connection)
+            .find_many_by_invoice_id(&invoice.invoice_row.id)
+            .unwrap();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].r#type, InvoiceLineRowType::StockOut);
+        assert_eq!(lines[0].number_of_packs, 5);
+        assert_eq!(
+            lines[0].inventory_adjustment_reason_id,
+            Some(mock_negative_inventory_adjustment_reason().id)
+        );
+
+        let stock_line = StockLineRowRepository::new(&connection)
+            .find_one_by_id(&mock_stock_line_a().id)
+            .unwrap();
+        assert_eq!(
+            stock_line.available_number_of_packs,
+            mock_stock_line_a().available_number_of_packs - 5
+        );
+        assert_eq!(
+            stock_line.total_number_of_packs,
+            mock_stock_line_a().total_number_of_packs - 5
+        );
+    }
+
+    #[actix_rt::test]
+    async fn insert_inventory_adjustment_without_reasons() {
+        let (_, connection, connection_manager, _) = setup_all(
+            "insert_inventory_adjustment_without_reasons",
+            MockDataInserts::all(),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        // No reasons configured, reason is optional
+        service
+            .insert_inventory_adjustment(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                inline_edit(&reduction(10), |mut u| {
+                    u.r#type = InventoryAdjustmentType::Positive;
+                    u
+                }),
+            )
+            .unwrap();
+
+        let stock_line = StockLineRowRepository::new(&connection)
+            .find_one_by_id(&mock_stock_line_a().id)
+            .unwrap();
+        assert_eq!(
+            stock_line.total_number_of_packs,
+            mock_stock_line_a().total_number_of_packs + 10
+        );
+    }
+}
@@ -18,6 +18,21 @@ use self::outbound_shipment::*;
 pub mod inbound_shipment;
 use self::inbound_shipment::*;
 
+pub mod prescription;
+use self::prescription::*;
+
+pub mod customer_return;
+use self::customer_return::*;
+
+pub mod supplier_return;
+use self::supplier_return::*;
+
+pub mod return_line;
+pub use self::return_line::*;
+
+pub mod inventory_adjustment;
+use self::inventory_adjustment::*;
+
 pub mod validate;
 pub use self::validate::*;
 
@@ -112,6 +127,100 @@ pub trait InvoiceServiceTrait: Sync + Send {
         delete_outbound_shipment(ctx, store_id, id)
     }
 
+    fn insert_prescription(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        input: InsertPrescription,
+    ) -> Result<Invoice, InsertPrescriptionError> {
+        insert_prescription(ctx, store_id, user_id, input)
+    }
+
+    fn update_prescription(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        input: UpdatePrescription,
+    ) -> Result<Invoice, UpdatePrescriptionError> {
+        update_prescription(ctx, store_id, input)
+    }
+
+    fn delete_prescription(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        id: String,
+    ) -> Result<String, DeletePrescriptionError> {
+        delete_prescription(ctx, store_id, id)
+    }
+
+    fn insert_customer_return(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        input: InsertCustomerReturn,
+    ) -> Result<Invoice, InsertCustomerReturnError> {
+        insert_customer_return(ctx, store_id, user_id, input)
+    }
+
+    fn update_customer_return(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        input: UpdateCustomerReturn,
+    ) -> Result<Invoice, UpdateCustomerReturnError> {
+        update_customer_return(ctx, store_id, input)
+    }
+
+    fn delete_customer_return(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        id: String,
+    ) -> Result<String, DeleteCustomerReturnError> {
+        delete_customer_return(ctx, store_id, id)
+    }
+
+    fn insert_supplier_return(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        input: InsertSupplierReturn,
+    ) -> Result<Invoice, InsertSupplierReturnError> {
+        insert_supplier_return(ctx, store_id, user_id, input)
+    }
+
+    fn update_supplier_return(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        input: UpdateSupplierReturn,
+    ) -> Result<Invoice, UpdateSupplierReturnError> {
+        update_supplier_return(ctx, store_id, input)
+    }
+
+    fn delete_supplier_return(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        id: String,
+    ) -> Result<String, DeleteSupplierReturnError> {
+        delete_supplier_return(ctx, store_id, id)
+    }
+
+    fn insert_inventory_adjustment(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        input: InsertInventoryAdjustment,
+    ) -> Result<Invoice, InsertInventoryAdjustmentError> {
+        insert_inventory_adjustment(ctx, store_id, user_id, input)
+    }
+
     fn batch_inbound_shipment(
         &self,
         ctx: &ServiceContext,
@@ -1,6 +1,6 @@
 use repository::{
-    EqualFilter, InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, InvoiceRowRepository,
-    RepositoryError, TransactionError,
+    BackorderShipmentRowRepository, EqualFilter, InvoiceLine, InvoiceLineFilter,
+    InvoiceLineRepository, InvoiceRowRepository, RepositoryError, TransactionError,
 };
 
 pub mod validate;
@@ -45,6 +45,9 @@ pub fn delete_outbound_shipment(
                 })?;
             }
 
+            // Releases the backorder quantity placed on the shipment
+            BackorderShipmentRowRepository::new(&connection).delete_by_invoice_id(&id)?;
+
             InvoiceRowRepository::new(&connection).delete(&id)?;
             // End TODO
 
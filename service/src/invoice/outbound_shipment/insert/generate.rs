@@ -40,6 +40,9 @@ pub fn generate(
         delivered_datetime: None,
         verified_datetime: None,
         linked_invoice_id: None,
+        prescriber: None,
+        diagnosis: None,
+        directions: None,
         requisition_id: None,
     };
 
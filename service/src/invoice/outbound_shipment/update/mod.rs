@@ -9,9 +9,10 @@ pub mod validate;
 use generate::generate;
 use validate::validate;
 
+use crate::backorder::ship::ship_backorders;
 use crate::invoice::query::get_invoice;
 use crate::service_provider::ServiceContext;
-use crate::sync_processor::{process_records, Record};
+use crate::sync_processor::{process_records, ProcessRecordError, Record};
 #[derive(Clone, Debug, PartialEq)]
 pub enum UpdateOutboundShipmentStatus {
     Allocated,
@@ -47,6 +48,8 @@ pub enum UpdateOutboundShipmentError {
     DatabaseError(RepositoryError),
     /// Holds the id of the invalid invoice line
     InvoiceLineHasNoStockLine(String),
+    /// Failed to create or update the inbound shipment of a store on the same site
+    InboundShipmentTransferError(String),
 }
 
 type OutError = UpdateOutboundShipmentError;
@@ -56,10 +59,12 @@ pub fn update_outbound_shipment(
     store_id: &str,
     patch: UpdateOutboundShipment,
 ) -> Result<Invoice, OutError> {
-    let invoice = ctx
+    let update_invoice = ctx
         .connection
         .transaction_sync(|connection| {
             let (invoice, other_party_option) = validate(connection, store_id, &patch)?;
+            let is_shipping = patch.full_status() == Some(InvoiceRowStatus::Shipped)
+                && invoice.status.index() < InvoiceRowStatus::Shipped.index();
             let (stock_lines_option, update_invoice) =
                 generate(invoice, other_party_option, patch, connection)?;
 
@@ -70,23 +75,21 @@ pub fn update_outbound_shipment(
                     repository.upsert_one(&stock_line)?;
                 }
             }
+            if is_shipping {
+                ship_backorders(connection, &update_invoice.id)?;
+            }
+
+            // Create or update the linked inbound shipment straight away if the receiving store
+            // is on this site, i.e. the receiving store doesn't need to wait for a sync round trip
+            process_records(connection, vec![Record::InvoiceRow(update_invoice.clone())])?;
 
-            get_invoice(ctx, None, &update_invoice.id)
-                .map_err(|error| OutError::DatabaseError(error))?
-                .ok_or(OutError::UpdatedInvoicenDoesNotExist)
+            Ok(update_invoice)
         })
-        .map_err(|error| error.to_inner_error())?;
-
-    // TODO use change log (and maybe ask sync porcessor actor to retrigger here)
-    println!(
-        "{:#?}",
-        process_records(
-            &ctx.connection,
-            vec![Record::InvoiceRow(invoice.invoice_row.clone())],
-        )
-    );
+        .map_err(|error: TransactionError<OutError>| error.to_inner_error())?;
 
-    Ok(invoice)
+    get_invoice(ctx, None, &update_invoice.id)
+        .map_err(|error| OutError::DatabaseError(error))?
+        .ok_or(OutError::UpdatedInvoicenDoesNotExist)
 }
 
 impl From<RepositoryError> for UpdateOutboundShipmentError {
@@ -95,6 +98,17 @@ impl From<RepositoryError> for UpdateOutboundShipmentError {
     }
 }
 
+impl From<ProcessRecordError> for UpdateOutboundShipmentError {
+    fn from(error: ProcessRecordError) -> Self {
+        match error {
+            ProcessRecordError::DatabaseError(error) => {
+                UpdateOutboundShipmentError::DatabaseError(error)
+            }
+            _ => UpdateOutboundShipmentError::InboundShipmentTransferError(format!("{:#?}", error)),
+        }
+    }
+}
+
 impl From<TransactionError<UpdateOutboundShipmentError>> for UpdateOutboundShipmentError {
     fn from(error: TransactionError<UpdateOutboundShipmentError>) -> Self {
         match error {
@@ -139,15 +153,22 @@ impl UpdateOutboundShipment {
 
 #[cfg(test)]
 mod test {
+    use diesel::connection::SimpleConnection;
     use repository::{
-        mock::{mock_name_a, mock_outbound_shipment_a, mock_store_a, MockData, MockDataInserts},
+        mock::{
+            mock_name_a, mock_name_store_b, mock_outbound_shipment_a, mock_stock_line_a,
+            mock_store_a, mock_store_b, MockData, MockDataInserts,
+        },
         test_db::setup_all_with_data,
-        InvoiceRow, InvoiceRowRepository, InvoiceRowType, NameRow, NameStoreJoinRow,
+        EqualFilter, InvoiceFilter, InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType,
+        InvoiceRepository, InvoiceRow, InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType,
+        KeyValueStoreRepository, KeyValueType, NameRow, NameStoreJoinRow, StoreRowRepository,
     };
     use util::{inline_edit, inline_init};
 
     use crate::{
-        invoice::outbound_shipment::UpdateOutboundShipment, service_provider::ServiceProvider,
+        invoice::outbound_shipment::{UpdateOutboundShipment, UpdateOutboundShipmentStatus},
+        service_provider::ServiceProvider,
     };
 
     use super::UpdateOutboundShipmentError;
@@ -317,4 +338,165 @@ mod test {
             })
         );
     }
+
+    fn transfer_invoice() -> InvoiceRow {
+        inline_init(|r: &mut InvoiceRow| {
+            r.id = "transfer_invoice".to_string();
+            r.name_id = mock_name_store_b().id;
+            r.store_id = mock_store_a().id;
+            r.r#type = InvoiceRowType::OutboundShipment;
+            r.status = InvoiceRowStatus::Allocated;
+        })
+    }
+
+    fn transfer_invoice_line() -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = "transfer_invoice_line".to_string();
+            r.invoice_id = transfer_invoice().id;
+            r.item_id = mock_stock_line_a().item_id;
+            r.stock_line_id = Some(mock_stock_line_a().id);
+            r.r#type = InvoiceLineRowType::StockOut;
+            r.pack_size = 1;
+            r.number_of_packs = 2;
+        })
+    }
+
+    fn transfer_mock_data() -> MockData {
+        inline_init(|r: &mut MockData| {
+            r.invoices = vec![transfer_invoice()];
+            r.invoice_lines = vec![transfer_invoice_line()];
+        })
+    }
+
+    fn ship_transfer_invoice() -> UpdateOutboundShipment {
+        inline_init(|r: &mut UpdateOutboundShipment| {
+            r.id = transfer_invoice().id;
+            r.status = Some(UpdateOutboundShipmentStatus::Shipped);
+        })
+    }
+
+    #[actix_rt::test]
+    async fn update_outbound_shipment_same_site_transfer() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "update_outbound_shipment_same_site_transfer",
+            MockDataInserts::all(),
+            transfer_mock_data(),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        // store_b is on the same site, inbound shipment is created in the same update
+        let result = service
+            .update_outbound_shipment(&context, &mock_store_a().id, ship_transfer_invoice())
+            .unwrap();
+
+        let inbound_shipment = InvoiceRepository::new(&connection)
+            .query_one(
+                InvoiceFilter::new()
+                    .linked_invoice_id(EqualFilter::equal_to(&transfer_invoice().id)),
+            )
+            .unwrap()
+            .unwrap()
+            .invoice_row;
+        assert_eq!(inbound_shipment.store_id, mock_store_b().id);
+        assert_eq!(inbound_shipment.r#type, InvoiceRowType::InboundShipment);
+        assert_eq!(inbound_shipment.status, InvoiceRowStatus::Shipped);
+        assert_eq!(
+            result.invoice_row.linked_invoice_id,
+            Some(inbound_shipment.id.clone())
+        );
+
+        let inbound_lines = InvoiceLineRowRepository::new(&connection)
+            .find_many_by_invoice_id(&inbound_shipment.id)
+            .unwrap();
+        assert_eq!(inbound_lines.len(), 1);
+        assert_eq!(inbound_lines[0].r#type, InvoiceLineRowType::StockIn);
+        assert_eq!(
+            inbound_lines[0].number_of_packs,
+            transfer_invoice_line().number_of_packs
+        );
+    }
+
+    #[actix_rt::test]
+    async fn update_outbound_shipment_same_site_transfer_error() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "update_outbound_shipment_same_site_transfer_error",
+            MockDataInserts::all(),
+            transfer_mock_data(),
+        )
+        .await;
+        // Inbound shipment can't be numbered, i.e. creating the inbound shipment fails
+        connection
+            .connection
+            .batch_execute("DROP TABLE number")
+            .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        let result =
+            service.update_outbound_shipment(&context, &mock_store_a().id, ship_transfer_invoice());
+        assert!(
+            matches!(result, Err(ServiceError::DatabaseError(_))),
+            "Not DatabaseError {:#?}",
+            result
+        );
+
+        // Update is rolled back
+        let outbound_shipment = InvoiceRowRepository::new(&connection)
+            .find_one_by_id(&transfer_invoice().id)
+            .unwrap();
+        assert_eq!(outbound_shipment.status, InvoiceRowStatus::Allocated);
+        let inbound_shipment = InvoiceRepository::new(&connection)
+            .query_one(
+                InvoiceFilter::new()
+                    .linked_invoice_id(EqualFilter::equal_to(&transfer_invoice().id)),
+            )
+            .unwrap();
+        assert_eq!(inbound_shipment, None);
+    }
+
+    #[actix_rt::test]
+    async fn update_outbound_shipment_other_site_transfer() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "update_outbound_shipment_other_site_transfer",
+            MockDataInserts::all(),
+            transfer_mock_data(),
+        )
+        .await;
+        // store_b is on another site and receives the shipment through sync
+        KeyValueStoreRepository::new(&connection)
+            .set_i32(
+                KeyValueType::SettingsSyncSideId,
+                Some(mock_store_a().site_id),
+            )
+            .unwrap();
+        StoreRowRepository::new(&connection)
+            .upsert_one(&inline_edit(&mock_store_b(), |mut u| {
+                u.site_id = mock_store_a().site_id + 1;
+                u
+            }))
+            .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        let result = service
+            .update_outbound_shipment(&context, &mock_store_a().id, ship_transfer_invoice())
+            .unwrap();
+
+        let inbound_shipment = InvoiceRepository::new(&connection)
+            .query_one(
+                InvoiceFilter::new()
+                    .linked_invoice_id(EqualFilter::equal_to(&transfer_invoice().id)),
+            )
+            .unwrap();
+        assert_eq!(inbound_shipment, None);
+        assert_eq!(result.invoice_row.linked_invoice_id, None);
+    }
 }
@@ -0,0 +1,199 @@
+use repository::{
+    EqualFilter, InvoiceLineFilter, InvoiceLineRepository, InvoiceRowRepository, RepositoryError,
+    TransactionError,
+};
+
+mod validate;
+
+use validate::validate;
+
+use crate::{
+    invoice_line::outbound_shipment_line::{
+        delete_outbound_shipment_line, DeleteOutboundShipmentLine, DeleteOutboundShipmentLineError,
+    },
+    service_provider::ServiceContext,
+    WithDBError,
+};
+
+/// Deletes a prescription including its lines, stock that has been reserved or issued by the
+/// lines is returned to the stock lines.
+pub fn delete_prescription(
+    ctx: &ServiceContext,
+    store_id: &str,
+    id: String,
+) -> Result<String, DeletePrescriptionError> {
+    let invoice_id = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(&id, connection)?;
+
+            let lines = InvoiceLineRepository::new(connection)
+                .query_by_filter(InvoiceLineFilter::new().invoice_id(EqualFilter::equal_to(&id)))?;
+            for line in lines {
+                delete_outbound_shipment_line(
+                    ctx,
+                    store_id,
+                    DeleteOutboundShipmentLine {
+                        id: line.invoice_line_row.id.clone(),
+                        invoice_id: id.clone(),
+                    },
+                )
+                .map_err(|error| DeletePrescriptionError::LineDeleteError {
+                    line_id: line.invoice_line_row.id,
+                    error,
+                })?;
+            }
+
+            InvoiceRowRepository::new(connection).delete(&id)?;
+            Ok(id) as Result<String, DeletePrescriptionError>
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(invoice_id)
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DeletePrescriptionError {
+    InvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+    NotThisStoreInvoice,
+    CannotEditFinalised,
+    LineDeleteError {
+        line_id: String,
+        error: DeleteOutboundShipmentLineError,
+    },
+    NotAPrescription,
+}
+
+impl From<RepositoryError> for DeletePrescriptionError {
+    fn from(error: RepositoryError) -> Self {
+        DeletePrescriptionError::DatabaseError(error)
+    }
+}
+
+impl From<TransactionError<DeletePrescriptionError>> for DeletePrescriptionError {
+    fn from(error: TransactionError<DeletePrescriptionError>) -> Self {
+        match error {
+            TransactionError::Transaction { msg, level } => {
+                DeletePrescriptionError::DatabaseError(RepositoryError::TransactionError {
+                    msg,
+                    level,
+                })
+            }
+            TransactionError::Inner(e) => e,
+        }
+    }
+}
+
+impl<ERR> From<WithDBError<ERR>> for DeletePrescriptionError
+where
+    ERR: Into<DeletePrescriptionError>,
+{
+    fn from(result: WithDBError<ERR>) -> Self {
+        match result {
+            WithDBError::DatabaseError(error) => error.into(),
+            WithDBError::Error(error) => error.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_name_store_b, mock_outbound_shipment_a, mock_stock_line_a, mock_store_a, MockData,
+            MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineRowType, InvoiceRow, InvoiceRowRepository, InvoiceRowStatus,
+        InvoiceRowType, RepositoryError, StockLineRow, StockLineRowRepository,
+    };
+    use util::inline_init;
+
+    use crate::service_provider::ServiceProvider;
+
+    use super::DeletePrescriptionError;
+
+    type ServiceError = DeletePrescriptionError;
+
+    #[actix_rt::test]
+    async fn delete_prescription() {
+        fn prescription() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "prescription".to_string();
+                r.name_id = mock_name_store_b().id;
+                r.store_id = mock_store_a().id;
+                r.r#type = InvoiceRowType::Prescription;
+                r.status = InvoiceRowStatus::Picked;
+            })
+        }
+
+        fn prescription_line() -> InvoiceLineRow {
+            inline_init(|r: &mut InvoiceLineRow| {
+                r.id = "prescription_line".to_string();
+                r.invoice_id = prescription().id;
+                r.item_id = mock_stock_line_a().item_id;
+                r.stock_line_id = Some(mock_stock_line_a().id);
+                r.r#type = InvoiceLineRowType::StockOut;
+                r.pack_size = 1;
+                r.number_of_packs = 2;
+            })
+        }
+
+        // Stock line as it would be after picking the prescription line
+        fn issued_stock_line() -> StockLineRow {
+            let mut stock_line = mock_stock_line_a();
+            stock_line.available_number_of_packs -= prescription_line().number_of_packs;
+            stock_line.total_number_of_packs -= prescription_line().number_of_packs;
+            stock_line
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "delete_prescription",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![prescription()];
+                r.invoice_lines = vec![prescription_line()];
+            }),
+        )
+        .await;
+        StockLineRowRepository::new(&connection)
+            .upsert_one(&issued_stock_line())
+            .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        // InvoiceDoesNotExist
+        assert_eq!(
+            service.delete_prescription(&context, &mock_store_a().id, "invalid".to_string()),
+            Err(ServiceError::InvoiceDoesNotExist)
+        );
+        // NotAPrescription
+        assert_eq!(
+            service.delete_prescription(
+                &context,
+                &mock_store_a().id,
+                mock_outbound_shipment_a().id
+            ),
+            Err(ServiceError::NotAPrescription)
+        );
+
+        // Success
+        assert_eq!(
+            service.delete_prescription(&context, &mock_store_a().id, prescription().id),
+            Ok(prescription().id)
+        );
+        assert_eq!(
+            InvoiceRowRepository::new(&connection).find_one_by_id(&prescription().id),
+            Err(RepositoryError::NotFound)
+        );
+        // Stock is returned
+        assert_eq!(
+            StockLineRowRepository::new(&connection)
+                .find_one_by_id(&mock_stock_line_a().id)
+                .unwrap(),
+            mock_stock_line_a()
+        );
+    }
+}
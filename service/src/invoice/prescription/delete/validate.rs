@@ -0,0 +1,37 @@
+use super::DeletePrescriptionError;
+use crate::invoice::{
+    check_invoice_exists, check_invoice_is_editable, check_invoice_type, InvoiceDoesNotExist,
+    InvoiceIsNotEditable, WrongInvoiceRowType,
+};
+use repository::{InvoiceRow, InvoiceRowType, StorageConnection};
+
+pub fn validate(
+    id: &str,
+    connection: &StorageConnection,
+) -> Result<InvoiceRow, DeletePrescriptionError> {
+    let invoice = check_invoice_exists(id, connection)?;
+
+    // check_store(invoice, connection)?; InvoiceDoesNotBelongToCurrentStore
+    check_invoice_type(&invoice, InvoiceRowType::Prescription)?;
+    check_invoice_is_editable(&invoice)?;
+
+    Ok(invoice)
+}
+
+impl From<WrongInvoiceRowType> for DeletePrescriptionError {
+    fn from(_: WrongInvoiceRowType) -> Self {
+        DeletePrescriptionError::NotAPrescription
+    }
+}
+
+impl From<InvoiceIsNotEditable> for DeletePrescriptionError {
+    fn from(_: InvoiceIsNotEditable) -> Self {
+        DeletePrescriptionError::CannotEditFinalised
+    }
+}
+
+impl From<InvoiceDoesNotExist> for DeletePrescriptionError {
+    fn from(_: InvoiceDoesNotExist) -> Self {
+        DeletePrescriptionError::InvoiceDoesNotExist
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::Utc;
+
+use repository::{
+    InvoiceRow, InvoiceRowStatus, InvoiceRowType, NumberRowType, RepositoryError, StorageConnection,
+};
+
+use crate::number::next_number;
+
+use super::InsertPrescription;
+
+pub fn generate(
+    connection: &StorageConnection,
+    store_id: &str,
+    user_id: &str,
+    input: InsertPrescription,
+) -> Result<InvoiceRow, RepositoryError> {
+    let current_datetime = Utc::now().naive_utc();
+
+    let result = InvoiceRow {
+        id: input.id,
+        user_id: Some(user_id.to_string()),
+        name_id: input.patient_id,
+        r#type: InvoiceRowType::Prescription,
+        comment: input.comment,
+        invoice_number: next_number(connection, &NumberRowType::Prescription, store_id)?,
+        store_id: store_id.to_string(),
+        created_datetime: current_datetime,
+        status: InvoiceRowStatus::New,
+        colour: input.colour,
+        prescriber: input.prescriber,
+        diagnosis: input.diagnosis,
+        directions: input.directions,
+        // Default
+        name_store_id: None,
+        on_hold: false,
+        their_reference: None,
+        transport_reference: None,
+        allocated_datetime: None,
+        picked_datetime: None,
+        shipped_datetime: None,
+        delivered_datetime: None,
+        verified_datetime: None,
+        linked_invoice_id: None,
+        requisition_id: None,
+    };
+
+    Ok(result)
+}
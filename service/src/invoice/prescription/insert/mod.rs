@@ -0,0 +1,223 @@
+use repository::{Invoice, InvoiceRowRepository};
+use repository::{RepositoryError, TransactionError};
+
+mod generate;
+mod validate;
+
+use generate::generate;
+use validate::validate;
+
+use crate::invoice::query::get_invoice;
+use crate::service_provider::ServiceContext;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InsertPrescription {
+    pub id: String,
+    pub patient_id: String,
+    pub prescriber: Option<String>,
+    pub diagnosis: Option<String>,
+    pub directions: Option<String>,
+    pub comment: Option<String>,
+    pub colour: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum InsertPrescriptionError {
+    InvoiceAlreadyExists,
+    // Name validation
+    PatientNotACustomer,
+    PatientNotVisible,
+    PatientDoesNotExist,
+    // Internal
+    NewlyCreatedInvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = InsertPrescriptionError;
+
+/// Insert a new prescription and returns the invoice when successful.
+pub fn insert_prescription(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    input: InsertPrescription,
+) -> Result<Invoice, OutError> {
+    let invoice = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(connection, store_id, &input)?;
+            let new_invoice = generate(connection, store_id, user_id, input)?;
+
+            InvoiceRowRepository::new(connection).upsert_one(&new_invoice)?;
+
+            get_invoice(ctx, None, &new_invoice.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::NewlyCreatedInvoiceDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(invoice)
+}
+
+impl From<RepositoryError> for InsertPrescriptionError {
+    fn from(error: RepositoryError) -> Self {
+        InsertPrescriptionError::DatabaseError(error)
+    }
+}
+
+impl From<TransactionError<InsertPrescriptionError>> for InsertPrescriptionError {
+    fn from(error: TransactionError<InsertPrescriptionError>) -> Self {
+        match error {
+            TransactionError::Transaction { msg, level } => {
+                InsertPrescriptionError::DatabaseError(RepositoryError::TransactionError {
+                    msg,
+                    level,
+                })
+            }
+            TransactionError::Inner(e) => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_outbound_shipment_a, mock_store_a, mock_user_account_a, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceRowRepository, InvoiceRowType, NameRow, NameStoreJoinRow,
+    };
+    use util::{inline_edit, inline_init};
+
+    use crate::{invoice::prescription::InsertPrescription, service_provider::ServiceProvider};
+
+    use super::InsertPrescriptionError;
+
+    type ServiceError = InsertPrescriptionError;
+
+    fn patient() -> NameRow {
+        inline_init(|r: &mut NameRow| {
+            r.id = "patient".to_string();
+        })
+    }
+
+    fn patient_join() -> NameStoreJoinRow {
+        inline_init(|r: &mut NameStoreJoinRow| {
+            r.id = "patient_join".to_string();
+            r.name_id = patient().id;
+            r.store_id = mock_store_a().id;
+            r.name_is_customer = true;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn insert_prescription_errors() {
+        fn not_visible() -> NameRow {
+            inline_init(|r: &mut NameRow| {
+                r.id = "not_visible".to_string();
+            })
+        }
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "insert_prescription_errors",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.names = vec![not_visible()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        // InvoiceAlreadyExists
+        assert_eq!(
+            service.insert_prescription(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                inline_init(|r: &mut InsertPrescription| {
+                    r.id = mock_outbound_shipment_a().id;
+                })
+            ),
+            Err(ServiceError::InvoiceAlreadyExists)
+        );
+        // PatientDoesNotExist
+        assert_eq!(
+            service.insert_prescription(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                inline_init(|r: &mut InsertPrescription| {
+                    r.id = "new_id".to_string();
+                    r.patient_id = "invalid".to_string();
+                })
+            ),
+            Err(ServiceError::PatientDoesNotExist)
+        );
+        // PatientNotVisible
+        assert_eq!(
+            service.insert_prescription(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                inline_init(|r: &mut InsertPrescription| {
+                    r.id = "new_id".to_string();
+                    r.patient_id = not_visible().id;
+                })
+            ),
+            Err(ServiceError::PatientNotVisible)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn insert_prescription_success() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "insert_prescription_success",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.names = vec![patient()];
+                r.name_store_joins = vec![patient_join()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        service
+            .insert_prescription(
+                &context,
+                &mock_store_a().id,
+                &mock_user_account_a().id,
+                inline_init(|r: &mut InsertPrescription| {
+                    r.id = "new_id".to_string();
+                    r.patient_id = patient().id;
+                    r.prescriber = Some("Dr Who".to_string());
+                    r.diagnosis = Some("Malaria".to_string());
+                    r.directions = Some("Two tablets daily".to_string());
+                }),
+            )
+            .unwrap();
+
+        let invoice = InvoiceRowRepository::new(&connection)
+            .find_one_by_id("new_id")
+            .unwrap();
+
+        assert_eq!(
+            invoice,
+            inline_edit(&invoice, |mut u| {
+                u.name_id = patient().id;
+                u.user_id = Some(mock_user_account_a().id);
+                u.r#type = InvoiceRowType::Prescription;
+                u.prescriber = Some("Dr Who".to_string());
+                u.diagnosis = Some("Malaria".to_string());
+                u.directions = Some("Two tablets daily".to_string());
+                u
+            })
+        )
+    }
+}
@@ -0,0 +1,44 @@
+use repository::{InvoiceRowRepository, RepositoryError, StorageConnection};
+
+use crate::validate::{check_other_party, CheckOtherPartyType, OtherPartyErrors};
+
+use super::{InsertPrescription, InsertPrescriptionError};
+
+pub fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &InsertPrescription,
+) -> Result<(), InsertPrescriptionError> {
+    use InsertPrescriptionError::*;
+    check_invoice_does_not_exists(&input.id, connection)?;
+
+    check_other_party(
+        connection,
+        store_id,
+        &input.patient_id,
+        CheckOtherPartyType::Customer,
+    )
+    .map_err(|e| match e {
+        OtherPartyErrors::OtherPartyDoesNotExist => PatientDoesNotExist,
+        OtherPartyErrors::OtherPartyNotVisible => PatientNotVisible,
+        OtherPartyErrors::TypeMismatched => PatientNotACustomer,
+        OtherPartyErrors::DatabaseError(repository_error) => DatabaseError(repository_error),
+    })?;
+
+    Ok(())
+}
+
+fn check_invoice_does_not_exists(
+    id: &str,
+    connection: &StorageConnection,
+) -> Result<(), InsertPrescriptionError> {
+    let result = InvoiceRowRepository::new(connection).find_one_by_id(id);
+
+    if let Err(RepositoryError::NotFound) = &result {
+        Ok(())
+    } else if let Err(error) = result {
+        Err(error.into())
+    } else {
+        Err(InsertPrescriptionError::InvoiceAlreadyExists)
+    }
+}
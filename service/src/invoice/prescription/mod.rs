@@ -0,0 +1,8 @@
+pub mod insert;
+pub use self::insert::*;
+
+pub mod delete;
+pub use self::delete::*;
+
+pub mod update;
+pub use self::update::*;
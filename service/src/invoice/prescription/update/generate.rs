@@ -0,0 +1,119 @@
+use chrono::Utc;
+
+use repository::{
+    InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRow, InvoiceRowStatus,
+    StockLineRow, StockLineRowRepository, StorageConnection,
+};
+
+use super::{UpdatePrescription, UpdatePrescriptionError};
+
+pub fn generate(
+    existing_invoice: InvoiceRow,
+    UpdatePrescription {
+        id: _,
+        patient_id: input_patient_id,
+        status: input_status,
+        prescriber: input_prescriber,
+        diagnosis: input_diagnosis,
+        directions: input_directions,
+        comment: input_comment,
+        colour: input_colour,
+    }: UpdatePrescription,
+    connection: &StorageConnection,
+) -> Result<(Option<Vec<StockLineRow>>, InvoiceRow), UpdatePrescriptionError> {
+    let new_status = input_status.map(|status| status.full_status());
+    let should_update_batches = should_update_batches(&existing_invoice, &new_status);
+    let mut update_invoice = existing_invoice;
+
+    set_new_status_datetime(&mut update_invoice, &new_status);
+
+    update_invoice.name_id = input_patient_id.unwrap_or(update_invoice.name_id);
+    update_invoice.prescriber = input_prescriber.or(update_invoice.prescriber);
+    update_invoice.diagnosis = input_diagnosis.or(update_invoice.diagnosis);
+    update_invoice.directions = input_directions.or(update_invoice.directions);
+    update_invoice.comment = input_comment.or(update_invoice.comment);
+    update_invoice.colour = input_colour.or(update_invoice.colour);
+
+    if let Some(status) = new_status {
+        update_invoice.status = status;
+    }
+
+    if !should_update_batches {
+        Ok((None, update_invoice))
+    } else {
+        Ok((
+            Some(generate_batches(&update_invoice.id, connection)?),
+            update_invoice,
+        ))
+    }
+}
+
+/// Stock leaves the store once the prescription is picked, i.e. the stock lines total number of
+/// packs only needs to be reduced when moving from new to picked (or straight to verified).
+fn should_update_batches(invoice: &InvoiceRow, status: &Option<InvoiceRowStatus>) -> bool {
+    match status {
+        Some(new_status) => {
+            new_status.index() >= InvoiceRowStatus::Picked.index()
+                && invoice.status.index() < InvoiceRowStatus::Picked.index()
+        }
+        None => false,
+    }
+}
+
+fn set_new_status_datetime(invoice: &mut InvoiceRow, status: &Option<InvoiceRowStatus>) {
+    if let Some(new_invoice_status) = status {
+        let current_datetime = Utc::now().naive_utc();
+        let invoice_status_index = invoice.status.index();
+        let new_invoice_status_index = new_invoice_status.index();
+
+        let is_status_update = |status: InvoiceRowStatus| {
+            new_invoice_status_index >= status.index() && invoice_status_index < status.index()
+        };
+
+        if is_status_update(InvoiceRowStatus::Picked) {
+            invoice.picked_datetime = Some(current_datetime);
+        }
+
+        if is_status_update(InvoiceRowStatus::Verified) {
+            invoice.verified_datetime = Some(current_datetime);
+        }
+    }
+}
+
+// Returns a list of stock lines that need to be updated
+fn generate_batches(
+    id: &str,
+    connection: &StorageConnection,
+) -> Result<Vec<StockLineRow>, UpdatePrescriptionError> {
+    let invoice_lines: Vec<InvoiceLineRow> = InvoiceLineRowRepository::new(connection)
+        .find_many_by_invoice_id(id)?
+        .into_iter()
+        .filter(|line| line.r#type == InvoiceLineRowType::StockOut)
+        .collect();
+
+    let stock_line_ids = invoice_lines
+        .iter()
+        .filter_map(|line| line.stock_line_id.clone())
+        .collect::<Vec<String>>();
+    let stock_lines = StockLineRowRepository::new(connection).find_many_by_ids(&stock_line_ids)?;
+
+    let mut result = Vec::new();
+    for invoice_line in invoice_lines {
+        let mut stock_line = invoice_line
+            .stock_line_id
+            .as_ref()
+            .and_then(|stock_line_id| {
+                stock_lines
+                    .iter()
+                    .find(|stock_line| stock_line_id == &stock_line.id)
+            })
+            .ok_or(UpdatePrescriptionError::InvoiceLineHasNoStockLine(
+                invoice_line.id.to_owned(),
+            ))?
+            .clone();
+
+        stock_line.total_number_of_packs -= invoice_line.number_of_packs;
+        result.push(stock_line);
+    }
+    Ok(result)
+}
@@ -0,0 +1,296 @@
+use repository::{
+    Invoice, InvoiceRowRepository, InvoiceRowStatus, RepositoryError, StockLineRowRepository,
+    TransactionError,
+};
+
+mod generate;
+mod validate;
+
+use generate::generate;
+use validate::validate;
+
+use crate::invoice::query::get_invoice;
+use crate::service_provider::ServiceContext;
+use crate::WithDBError;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UpdatePrescriptionStatus {
+    Picked,
+    Verified,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct UpdatePrescription {
+    pub id: String,
+    pub patient_id: Option<String>,
+    pub status: Option<UpdatePrescriptionStatus>,
+    pub prescriber: Option<String>,
+    pub diagnosis: Option<String>,
+    pub directions: Option<String>,
+    pub comment: Option<String>,
+    pub colour: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpdatePrescriptionError {
+    CannotReverseInvoiceStatus,
+    InvoiceDoesNotExist,
+    InvoiceIsNotEditable,
+    NotAPrescription,
+    // Name validation
+    PatientNotACustomer,
+    PatientNotVisible,
+    PatientDoesNotExist,
+    // Internal
+    UpdatedInvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+    /// Holds the id of the invalid invoice line
+    InvoiceLineHasNoStockLine(String),
+}
+
+type OutError = UpdatePrescriptionError;
+
+pub fn update_prescription(
+    ctx: &ServiceContext,
+    store_id: &str,
+    patch: UpdatePrescription,
+) -> Result<Invoice, OutError> {
+    let invoice = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let invoice = validate(connection, store_id, &patch)?;
+            let (stock_lines_option, update_invoice) = generate(invoice, patch, connection)?;
+
+            InvoiceRowRepository::new(connection).upsert_one(&update_invoice)?;
+            if let Some(stock_lines) = stock_lines_option {
+                let repository = StockLineRowRepository::new(connection);
+                for stock_line in stock_lines {
+                    repository.upsert_one(&stock_line)?;
+                }
+            }
+
+            get_invoice(ctx, None, &update_invoice.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::UpdatedInvoiceDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(invoice)
+}
+
+impl From<RepositoryError> for UpdatePrescriptionError {
+    fn from(error: RepositoryError) -> Self {
+        UpdatePrescriptionError::DatabaseError(error)
+    }
+}
+
+impl From<TransactionError<UpdatePrescriptionError>> for UpdatePrescriptionError {
+    fn from(error: TransactionError<UpdatePrescriptionError>) -> Self {
+        match error {
+            TransactionError::Transaction { msg, level } => {
+                UpdatePrescriptionError::DatabaseError(RepositoryError::TransactionError {
+                    msg,
+                    level,
+                })
+            }
+            TransactionError::Inner(e) => e,
+        }
+    }
+}
+
+impl<ERR> From<WithDBError<ERR>> for UpdatePrescriptionError
+where
+    ERR: Into<UpdatePrescriptionError>,
+{
+    fn from(result: WithDBError<ERR>) -> Self {
+        match result {
+            WithDBError::DatabaseError(error) => error.into(),
+            WithDBError::Error(error) => error.into(),
+        }
+    }
+}
+
+impl UpdatePrescriptionStatus {
+    pub fn full_status(&self) -> InvoiceRowStatus {
+        match self {
+            UpdatePrescriptionStatus::Picked => InvoiceRowStatus::Picked,
+            UpdatePrescriptionStatus::Verified => InvoiceRowStatus::Verified,
+        }
+    }
+}
+
+impl UpdatePrescription {
+    pub fn full_status(&self) -> Option<InvoiceRowStatus> {
+        self.status.as_ref().map(|status| status.full_status())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_name_store_b, mock_outbound_shipment_a, mock_stock_line_a, mock_store_a, MockData,
+            MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineRowType, InvoiceRow, InvoiceRowRepository, InvoiceRowStatus,
+        InvoiceRowType, StockLineRowRepository,
+    };
+    use util::inline_init;
+
+    use crate::{
+        invoice::prescription::{UpdatePrescription, UpdatePrescriptionStatus},
+        service_provider::ServiceProvider,
+    };
+
+    use super::UpdatePrescriptionError;
+
+    type ServiceError = UpdatePrescriptionError;
+
+    fn prescription() -> InvoiceRow {
+        inline_init(|r: &mut InvoiceRow| {
+            r.id = "prescription".to_string();
+            r.name_id = mock_name_store_b().id;
+            r.store_id = mock_store_a().id;
+            r.r#type = InvoiceRowType::Prescription;
+            r.status = InvoiceRowStatus::New;
+        })
+    }
+
+    fn prescription_line() -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = "prescription_line".to_string();
+            r.invoice_id = prescription().id;
+            r.item_id = mock_stock_line_a().item_id;
+            r.stock_line_id = Some(mock_stock_line_a().id);
+            r.r#type = InvoiceLineRowType::StockOut;
+            r.pack_size = 1;
+            r.number_of_packs = 2;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn update_prescription_errors() {
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "update_prescription_errors",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![inline_init(|r: &mut InvoiceRow| {
+                    *r = prescription();
+                    r.id = "verified_prescription".to_string();
+                    r.status = InvoiceRowStatus::Verified;
+                })];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        // InvoiceDoesNotExist
+        assert_eq!(
+            service.update_prescription(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdatePrescription| {
+                    r.id = "invalid".to_string();
+                })
+            ),
+            Err(ServiceError::InvoiceDoesNotExist)
+        );
+        // NotAPrescription
+        assert_eq!(
+            service.update_prescription(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdatePrescription| {
+                    r.id = mock_outbound_shipment_a().id;
+                })
+            ),
+            Err(ServiceError::NotAPrescription)
+        );
+        // InvoiceIsNotEditable
+        assert_eq!(
+            service.update_prescription(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdatePrescription| {
+                    r.id = "verified_prescription".to_string();
+                })
+            ),
+            Err(ServiceError::InvoiceIsNotEditable)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn update_prescription_success() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "update_prescription_success",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![prescription()];
+                r.invoice_lines = vec![prescription_line()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        service
+            .update_prescription(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdatePrescription| {
+                    r.id = prescription().id;
+                    r.directions = Some("Once daily".to_string());
+                    r.status = Some(UpdatePrescriptionStatus::Picked);
+                }),
+            )
+            .unwrap();
+
+        let invoice = InvoiceRowRepository::new(&connection)
+            .find_one_by_id(&prescription().id)
+            .unwrap();
+        assert_eq!(invoice.status, InvoiceRowStatus::Picked);
+        assert_eq!(invoice.directions, Some("Once daily".to_string()));
+        assert!(invoice.picked_datetime.is_some());
+
+        // Stock is issued when picked
+        let stock_line = StockLineRowRepository::new(&connection)
+            .find_one_by_id(&mock_stock_line_a().id)
+            .unwrap();
+        assert_eq!(
+            stock_line.total_number_of_packs,
+            mock_stock_line_a().total_number_of_packs - prescription_line().number_of_packs
+        );
+
+        // Verifying doesn't issue the stock again
+        service
+            .update_prescription(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdatePrescription| {
+                    r.id = prescription().id;
+                    r.status = Some(UpdatePrescriptionStatus::Verified);
+                }),
+            )
+            .unwrap();
+
+        let invoice = InvoiceRowRepository::new(&connection)
+            .find_one_by_id(&prescription().id)
+            .unwrap();
+        assert_eq!(invoice.status, InvoiceRowStatus::Verified);
+        assert!(invoice.verified_datetime.is_some());
+
+        let stock_line = StockLineRowRepository::new(&connection)
+            .find_one_by_id(&mock_stock_line_a().id)
+            .unwrap();
+        assert_eq!(
+            stock_line.total_number_of_packs,
+            mock_stock_line_a().total_number_of_packs - prescription_line().number_of_packs
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use crate::invoice::{
+    check_invoice_exists, check_invoice_is_editable, check_invoice_status, check_invoice_type,
+    InvoiceDoesNotExist, InvoiceIsNotEditable, InvoiceRowStatusError, WrongInvoiceRowType,
+};
+use crate::validate::{check_other_party, CheckOtherPartyType, OtherPartyErrors};
+use repository::{InvoiceRow, InvoiceRowType, StorageConnection};
+
+use super::{UpdatePrescription, UpdatePrescriptionError};
+
+pub fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    patch: &UpdatePrescription,
+) -> Result<InvoiceRow, UpdatePrescriptionError> {
+    use UpdatePrescriptionError::*;
+    let invoice = check_invoice_exists(&patch.id, connection)?;
+    // TODO check_store(invoice, connection)?; InvoiceDoesNotBelongToCurrentStore
+    check_invoice_type(&invoice, InvoiceRowType::Prescription)?;
+    check_invoice_is_editable(&invoice)?;
+    check_invoice_status(&invoice, patch.full_status(), &None)?;
+
+    if let Some(patient_id) = &patch.patient_id {
+        check_other_party(
+            connection,
+            store_id,
+            patient_id,
+            CheckOtherPartyType::Customer,
+        )
+        .map_err(|e| match e {
+            OtherPartyErrors::OtherPartyDoesNotExist => PatientDoesNotExist,
+            OtherPartyErrors::OtherPartyNotVisible => PatientNotVisible,
+            OtherPartyErrors::TypeMismatched => PatientNotACustomer,
+            OtherPartyErrors::DatabaseError(repository_error) => DatabaseError(repository_error),
+        })?;
+    }
+
+    Ok(invoice)
+}
+
+impl From<WrongInvoiceRowType> for UpdatePrescriptionError {
+    fn from(_: WrongInvoiceRowType) -> Self {
+        UpdatePrescriptionError::NotAPrescription
+    }
+}
+
+impl From<InvoiceDoesNotExist> for UpdatePrescriptionError {
+    fn from(_: InvoiceDoesNotExist) -> Self {
+        UpdatePrescriptionError::InvoiceDoesNotExist
+    }
+}
+
+impl From<InvoiceIsNotEditable> for UpdatePrescriptionError {
+    fn from(_: InvoiceIsNotEditable) -> Self {
+        UpdatePrescriptionError::InvoiceIsNotEditable
+    }
+}
+
+impl From<InvoiceRowStatusError> for UpdatePrescriptionError {
+    fn from(error: InvoiceRowStatusError) -> Self {
+        match error {
+            // Prescriptions can't be put on hold
+            InvoiceRowStatusError::CannotChangeStatusOfInvoiceOnHold
+            | InvoiceRowStatusError::CannotReverseInvoiceStatus => {
+                UpdatePrescriptionError::CannotReverseInvoiceStatus
+            }
+        }
+    }
+}
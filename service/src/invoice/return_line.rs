@@ -0,0 +1,22 @@
+/// Line of a customer or supplier return, returning packs of an issued or received invoice line
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InsertReturnLine {
+    pub id: String,
+    pub original_invoice_line_id: String,
+    pub number_of_packs: i32,
+    pub return_reason: Option<String>,
+    pub note: Option<String>,
+}
+
+/// Packs returned against the same original line by the lines preceding `line`
+pub fn number_of_packs_in_preceding_lines(
+    lines: &[InsertReturnLine],
+    line: &InsertReturnLine,
+) -> i32 {
+    lines
+        .iter()
+        .take_while(|other| other.id != line.id)
+        .filter(|other| other.original_invoice_line_id == line.original_invoice_line_id)
+        .map(|other| other.number_of_packs)
+        .sum()
+}
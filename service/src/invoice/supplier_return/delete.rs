@@ -0,0 +1,209 @@
+use repository::{
+    InvoiceLineRowRepository, InvoiceRow, InvoiceRowRepository, InvoiceRowType, RepositoryError,
+    StockLineRowRepository, StorageConnection, TransactionError,
+};
+
+use crate::invoice::{
+    check_invoice_exists, check_invoice_is_editable, check_invoice_type, InvoiceDoesNotExist,
+    InvoiceIsNotEditable, WrongInvoiceRowType,
+};
+use crate::{service_provider::ServiceContext, WithDBError};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum DeleteSupplierReturnError {
+    InvoiceDoesNotExist,
+    NotASupplierReturn,
+    /// Stock of picked returns has already left the store
+    CannotEditFinalised,
+    DatabaseError(RepositoryError),
+}
+
+/// Deletes a supplier return that hasn't been picked yet, including its lines. Packs reserved by
+/// the lines are made available again.
+pub fn delete_supplier_return(
+    ctx: &ServiceContext,
+    _store_id: &str,
+    id: String,
+) -> Result<String, DeleteSupplierReturnError> {
+    let invoice_id = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(&id, connection)?;
+
+            let line_repository = InvoiceLineRowRepository::new(connection);
+            let stock_line_repository = StockLineRowRepository::new(connection);
+            for line in line_repository.find_many_by_invoice_id(&id)? {
+                if let Some(stock_line_id) = &line.stock_line_id {
+                    let mut stock_line = stock_line_repository.find_one_by_id(stock_line_id)?;
+                    stock_line.available_number_of_packs += line.number_of_packs;
+                    stock_line_repository.upsert_one(&stock_line)?;
+                }
+                line_repository.delete(&line.id)?;
+            }
+            InvoiceRowRepository::new(connection).delete(&id)?;
+
+            Ok(id) as Result<String, DeleteSupplierReturnError>
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(invoice_id)
+}
+
+fn validate(
+    id: &str,
+    connection: &StorageConnection,
+) -> Result<InvoiceRow, DeleteSupplierReturnError> {
+    let invoice = check_invoice_exists(id, connection)?;
+    check_invoice_type(&invoice, InvoiceRowType::SupplierReturn)?;
+    check_invoice_is_editable(&invoice)?;
+    if invoice.picked_datetime.is_some() {
+        return Err(DeleteSupplierReturnError::CannotEditFinalised);
+    }
+
+    Ok(invoice)
+}
+
+impl From<WrongInvoiceRowType> for DeleteSupplierReturnError {
+    fn from(_: WrongInvoiceRowType) -> Self {
+        DeleteSupplierReturnError::NotASupplierReturn
+    }
+}
+
+impl From<InvoiceIsNotEditable> for DeleteSupplierReturnError {
+    fn from(_: InvoiceIsNotEditable) -> Self {
+        DeleteSupplierReturnError::CannotEditFinalised
+    }
+}
+
+impl From<InvoiceDoesNotExist> for DeleteSupplierReturnError {
+    fn from(_: InvoiceDoesNotExist) -> Self {
+        DeleteSupplierReturnError::InvoiceDoesNotExist
+    }
+}
+
+impl From<RepositoryError> for DeleteSupplierReturnError {
+    fn from(error: RepositoryError) -> Self {
+        DeleteSupplierReturnError::DatabaseError(error)
+    }
+}
+
+impl From<TransactionError<DeleteSupplierReturnError>> for DeleteSupplierReturnError {
+    fn from(error: TransactionError<DeleteSupplierReturnError>) -> Self {
+        match error {
+            TransactionError::Transaction { msg, level } => {
+                DeleteSupplierReturnError::DatabaseError(RepositoryError::TransactionError {
+                    msg,
+                    level,
+                })
+            }
+            TransactionError::Inner(e) => e,
+        }
+    }
+}
+
+impl<ERR> From<WithDBError<ERR>> for DeleteSupplierReturnError
+where
+    ERR: Into<DeleteSupplierReturnError>,
+{
+    fn from(result: WithDBError<ERR>) -> Self {
+        match result {
+            WithDBError::DatabaseError(error) => error.into(),
+            WithDBError::Error(error) => error.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_inbound_shipment_a, mock_name_store_b, mock_stock_line_a, mock_store_a, MockData,
+            MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineRowType, InvoiceRow, InvoiceRowRepository, InvoiceRowStatus,
+        InvoiceRowType, RepositoryError, StockLineRow, StockLineRowRepository,
+    };
+    use util::inline_init;
+
+    use crate::service_provider::ServiceProvider;
+
+    use super::DeleteSupplierReturnError;
+
+    type ServiceError = DeleteSupplierReturnError;
+
+    #[actix_rt::test]
+    async fn delete_supplier_return() {
+        fn supplier_return() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "supplier_return".to_string();
+                r.name_id = mock_name_store_b().id;
+                r.store_id = mock_store_a().id;
+                r.r#type = InvoiceRowType::SupplierReturn;
+                r.status = InvoiceRowStatus::New;
+            })
+        }
+
+        fn supplier_return_line() -> InvoiceLineRow {
+            inline_init(|r: &mut InvoiceLineRow| {
+                r.id = "supplier_return_line".to_string();
+                r.invoice_id = supplier_return().id;
+                r.item_id = mock_stock_line_a().item_id;
+                r.stock_line_id = Some(mock_stock_line_a().id);
+                r.r#type = InvoiceLineRowType::StockOut;
+                r.pack_size = 1;
+                r.number_of_packs = 2;
+            })
+        }
+
+        // Stock line as it would be after adding the return line
+        fn reserved_stock_line() -> StockLineRow {
+            let mut stock_line = mock_stock_line_a();
+            stock_line.available_number_of_packs -= supplier_return_line().number_of_packs;
+            stock_line
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "delete_supplier_return",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![supplier_return()];
+                r.invoice_lines = vec![supplier_return_line()];
+            }),
+        )
+        .await;
+        StockLineRowRepository::new(&connection)
+            .upsert_one(&reserved_stock_line())
+            .unwrap();
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        // NotASupplierReturn
+        assert_eq!(
+            service.delete_supplier_return(
+                &context,
+                &mock_store_a().id,
+                mock_inbound_shipment_a().id
+            ),
+            Err(ServiceError::NotASupplierReturn)
+        );
+
+        // Success
+        assert_eq!(
+            service.delete_supplier_return(&context, &mock_store_a().id, supplier_return().id),
+            Ok(supplier_return().id)
+        );
+        assert_eq!(
+            InvoiceRowRepository::new(&connection).find_one_by_id(&supplier_return().id),
+            Err(RepositoryError::NotFound)
+        );
+        // Reserved packs are available again
+        assert_eq!(
+            StockLineRowRepository::new(&connection)
+                .find_one_by_id(&mock_stock_line_a().id)
+                .unwrap(),
+            mock_stock_line_a()
+        );
+    }
+}
@@ -0,0 +1,475 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use repository::{
+    Invoice, InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRow,
+    InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType, NumberRowType, RepositoryError,
+    StockLineRow, StockLineRowRepository, StorageConnection, TransactionError,
+};
+
+use crate::invoice::{
+    check_invoice_exists_option, get_returnable_number_of_packs,
+    number_of_packs_in_preceding_lines, InsertReturnLine,
+};
+use crate::number::next_number;
+use crate::{invoice::query::get_invoice, service_provider::ServiceContext};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InsertSupplierReturn {
+    pub id: String,
+    /// Inbound shipment the stock was received with
+    pub original_invoice_id: String,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub colour: Option<String>,
+    pub lines: Vec<InsertReturnLine>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum InsertSupplierReturnError {
+    InvoiceAlreadyExists,
+    OriginalInvoiceDoesNotExist,
+    OriginalInvoiceNotThisStore,
+    NotAnInboundShipment,
+    /// Stock of the original invoice hasn't been received yet
+    OriginalInvoiceNotReceived,
+    // Line validation, holds the id of the return line
+    LineAlreadyExists(String),
+    OriginalLineDoesNotExist(String),
+    NumberOfPacksBelowOne(String),
+    /// Stock line the original line was received into no longer exists
+    StockLineDoesNotExist(String),
+    ReturnedQuantityExceedsReceived {
+        line_id: String,
+        returnable_number_of_packs: i32,
+    },
+    ReturnedQuantityExceedsAvailable {
+        line_id: String,
+        available_number_of_packs: i32,
+    },
+    // Internal
+    NewlyCreatedInvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = InsertSupplierReturnError;
+
+/// Creates a supplier return for stock received by an inbound shipment. Returned packs are
+/// reserved straight away (available number of packs is reduced), the stock leaves the store when
+/// the return is picked.
+pub fn insert_supplier_return(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    input: InsertSupplierReturn,
+) -> Result<Invoice, OutError> {
+    let invoice = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (original_invoice, original_lines) = validate(connection, store_id, &input)?;
+            let (new_invoice, new_lines, stock_lines) = generate(
+                connection,
+                store_id,
+                user_id,
+                original_invoice,
+                original_lines,
+                input,
+            )?;
+
+            InvoiceRowRepository::new(connection).upsert_one(&new_invoice)?;
+            let line_repository = InvoiceLineRowRepository::new(connection);
+            for line in new_lines {
+                line_repository.upsert_one(&line)?;
+            }
+            let stock_line_repository = StockLineRowRepository::new(connection);
+            for stock_line in stock_lines {
+                stock_line_repository.upsert_one(&stock_line)?;
+            }
+
+            get_invoice(ctx, None, &new_invoice.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::NewlyCreatedInvoiceDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(invoice)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &InsertSupplierReturn,
+) -> Result<(InvoiceRow, Vec<(InvoiceLineRow, StockLineRow)>), OutError> {
+    use InsertSupplierReturnError::*;
+
+    if check_invoice_exists_option(&input.id, connection)?.is_some() {
+        return Err(InvoiceAlreadyExists);
+    }
+
+    let original_invoice = check_invoice_exists_option(&input.original_invoice_id, connection)?
+        .ok_or(OriginalInvoiceDoesNotExist)?;
+    if original_invoice.store_id != store_id {
+        return Err(OriginalInvoiceNotThisStore);
+    }
+    if original_invoice.r#type != InvoiceRowType::InboundShipment {
+        return Err(NotAnInboundShipment);
+    }
+    if original_invoice.delivered_datetime.is_none() {
+        return Err(OriginalInvoiceNotReceived);
+    }
+
+    let line_repository = InvoiceLineRowRepository::new(connection);
+    let stock_line_repository = StockLineRowRepository::new(connection);
+    let mut original_lines = Vec::new();
+    let mut reserved = HashMap::new();
+    for line in &input.lines {
+        if line_repository.find_one_by_id_option(&line.id)?.is_some() {
+            return Err(LineAlreadyExists(line.id.clone()));
+        }
+        let original_line = line_repository
+            .find_one_by_id_option(&line.original_invoice_line_id)?
+            .filter(|original_line| {
+                original_line.invoice_id == original_invoice.id
+                    && original_line.r#type == InvoiceLineRowType::StockIn
+            })
+            .ok_or(OriginalLineDoesNotExist(line.id.clone()))?;
+        if line.number_of_packs < 1 {
+            return Err(NumberOfPacksBelowOne(line.id.clone()));
+        }
+
+        let returnable_number_of_packs =
+            get_returnable_number_of_packs(connection, &original_line)?
+                - number_of_packs_in_preceding_lines(&input.lines, line);
+        if line.number_of_packs > returnable_number_of_packs {
+            return Err(ReturnedQuantityExceedsReceived {
+                line_id: line.id.clone(),
+                returnable_number_of_packs,
+            });
+        }
+
+        let stock_line = match &original_line.stock_line_id {
+            Some(stock_line_id) => match stock_line_repository.find_one_by_id(stock_line_id) {
+                Ok(stock_line) => stock_line,
+                Err(RepositoryError::NotFound) => {
+                    return Err(StockLineDoesNotExist(line.id.clone()))
+                }
+                Err(error) => return Err(error.into()),
+            },
+            None => return Err(StockLineDoesNotExist(line.id.clone())),
+        };
+        // Packs already reserved from the same stock line by preceding lines
+        let reserved_number_of_packs = reserved.entry(stock_line.id.clone()).or_insert(0);
+        let available_number_of_packs =
+            stock_line.available_number_of_packs - *reserved_number_of_packs;
+        if line.number_of_packs > available_number_of_packs {
+            return Err(ReturnedQuantityExceedsAvailable {
+                line_id: line.id.clone(),
+                available_number_of_packs,
+            });
+        }
+
+        *reserved_number_of_packs += line.number_of_packs;
+        original_lines.push((original_line, stock_line));
+    }
+
+    Ok((original_invoice, original_lines))
+}
+
+fn generate(
+    connection: &StorageConnection,
+    store_id: &str,
+    user_id: &str,
+    original_invoice: InvoiceRow,
+    original_lines: Vec<(InvoiceLineRow, StockLineRow)>,
+    InsertSupplierReturn {
+        id,
+        original_invoice_id: _,
+        their_reference,
+        comment,
+        colour,
+        lines,
+    }: InsertSupplierReturn,
+) -> Result<(InvoiceRow, Vec<InvoiceLineRow>, Vec<StockLineRow>), RepositoryError> {
+    let invoice = InvoiceRow {
+        id: id.clone(),
+        user_id: Some(user_id.to_string()),
+        name_id: original_invoice.name_id,
+        name_store_id: original_invoice.name_store_id,
+        store_id: store_id.to_string(),
+        invoice_number: next_number(connection, &NumberRowType::SupplierReturn, store_id)?,
+        r#type: InvoiceRowType::SupplierReturn,
+        status: InvoiceRowStatus::New,
+        their_reference,
+        comment,
+        colour,
+        created_datetime: Utc::now().naive_utc(),
+        ..Default::default()
+    };
+
+    let mut invoice_lines = Vec::new();
+    let mut stock_lines: Vec<StockLineRow> = Vec::new();
+    for (line, (original_line, stock_line)) in lines.into_iter().zip(original_lines) {
+        // The same stock line can be returned by several lines
+        let stock_line = match stock_lines
+            .iter_mut()
+            .find(|other| other.id == stock_line.id)
+        {
+            Some(existing) => existing,
+            None => {
+                stock_lines.push(stock_line);
+                stock_lines.last_mut().unwrap()
+            }
+        };
+        stock_line.available_number_of_packs -= line.number_of_packs;
+
+        let total = original_line.cost_price_per_pack * line.number_of_packs as f64;
+        invoice_lines.push(InvoiceLineRow {
+            id: line.id,
+            invoice_id: id.clone(),
+            r#type: InvoiceLineRowType::StockOut,
+            number_of_packs: line.number_of_packs,
+            total_before_tax: total,
+            total_after_tax: total,
+            tax: None,
+            note: line.note,
+            original_invoice_line_id: Some(original_line.id.clone()),
+            return_reason: line.return_reason,
+            ..original_line
+        });
+    }
+
+    Ok((invoice, invoice_lines, stock_lines))
+}
+
+impl From<RepositoryError> for InsertSupplierReturnError {
+    fn from(error: RepositoryError) -> Self {
+        InsertSupplierReturnError::DatabaseError(error)
+    }
+}
+
+impl From<TransactionError<InsertSupplierReturnError>> for InsertSupplierReturnError {
+    fn from(error: TransactionError<InsertSupplierReturnError>) -> Self {
+        match error {
+            TransactionError::Transaction { msg, level } => {
+                InsertSupplierReturnError::DatabaseError(RepositoryError::TransactionError {
+                    msg,
+                    level,
+                })
+            }
+            TransactionError::Inner(e) => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{
+            mock_name_store_b, mock_outbound_shipment_a, mock_stock_line_a, mock_store_a,
+            mock_user_account_a, MockData, MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRow,
+        InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType, StockLineRow,
+        StockLineRowRepository,
+    };
+    use util::{inline_edit, inline_init};
+
+    use crate::{
+        invoice::{supplier_return::InsertSupplierReturn, InsertReturnLine},
+        service_provider::ServiceProvider,
+    };
+
+    use super::InsertSupplierReturnError;
+
+    type ServiceError = InsertSupplierReturnError;
+
+    fn inbound() -> InvoiceRow {
+        inline_init(|r: &mut InvoiceRow| {
+            r.id = "return_inbound".to_string();
+            r.name_id = mock_name_store_b().id;
+            r.store_id = mock_store_a().id;
+            r.r#type = InvoiceRowType::InboundShipment;
+            r.status = InvoiceRowStatus::Delivered;
+            r.delivered_datetime = Some(NaiveDate::from_ymd(2022, 1, 1).and_hms(0, 0, 0));
+        })
+    }
+
+    fn stock_line() -> StockLineRow {
+        inline_init(|r: &mut StockLineRow| {
+            r.id = "return_stock_line".to_string();
+            r.item_id = mock_stock_line_a().item_id;
+            r.store_id = mock_store_a().id;
+            r.pack_size = 1;
+            r.available_number_of_packs = 8;
+            r.total_number_of_packs = 10;
+        })
+    }
+
+    fn inbound_line() -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = "return_inbound_line".to_string();
+            r.invoice_id = inbound().id;
+            r.item_id = stock_line().item_id;
+            r.stock_line_id = Some(stock_line().id);
+            r.r#type = InvoiceLineRowType::StockIn;
+            r.pack_size = 1;
+            r.cost_price_per_pack = 3.0;
+            r.number_of_packs = 10;
+        })
+    }
+
+    fn return_line(id: &str, number_of_packs: i32) -> InsertReturnLine {
+        inline_init(|r: &mut InsertReturnLine| {
+            r.id = id.to_string();
+            r.original_invoice_line_id = inbound_line().id;
+            r.number_of_packs = number_of_packs;
+        })
+    }
+
+    fn input(lines: Vec<InsertReturnLine>) -> InsertSupplierReturn {
+        inline_init(|r: &mut InsertSupplierReturn| {
+            r.id = "supplier_return".to_string();
+            r.original_invoice_id = inbound().id;
+            r.lines = lines;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn insert_supplier_return_errors() {
+        fn not_delivered() -> InvoiceRow {
+            inline_edit(&inbound(), |mut u| {
+                u.id = "not_delivered".to_string();
+                u.status = InvoiceRowStatus::New;
+                u.delivered_datetime = None;
+                u
+            })
+        }
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "insert_supplier_return_errors",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![inbound(), not_delivered()];
+                r.stock_lines = vec![stock_line()];
+                r.invoice_lines = vec![inbound_line()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        // NotAnInboundShipment
+        assert_eq!(
+            service.insert_supplier_return(
+                &context,
+                &mock_outbound_shipment_a().store_id,
+                "n/a",
+                inline_edit(&input(vec![]), |mut u| {
+                    u.original_invoice_id = mock_outbound_shipment_a().id;
+                    u
+                })
+            ),
+            Err(ServiceError::NotAnInboundShipment)
+        );
+        // OriginalInvoiceNotReceived
+        assert_eq!(
+            service.insert_supplier_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                inline_edit(&input(vec![]), |mut u| {
+                    u.original_invoice_id = not_delivered().id;
+                    u
+                })
+            ),
+            Err(ServiceError::OriginalInvoiceNotReceived)
+        );
+        // ReturnedQuantityExceedsReceived
+        assert_eq!(
+            service.insert_supplier_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                input(vec![return_line("line1", 11)])
+            ),
+            Err(ServiceError::ReturnedQuantityExceedsReceived {
+                line_id: "line1".to_string(),
+                returnable_number_of_packs: 10
+            })
+        );
+        // ReturnedQuantityExceedsAvailable
+        assert_eq!(
+            service.insert_supplier_return(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                input(vec![return_line("line1", 5), return_line("line2", 4)])
+            ),
+            Err(ServiceError::ReturnedQuantityExceedsAvailable {
+                line_id: "line2".to_string(),
+                available_number_of_packs: 3
+            })
+        );
+    }
+
+    #[actix_rt::test]
+    async fn insert_supplier_return_success() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "insert_supplier_return_success",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![inbound()];
+                r.stock_lines = vec![stock_line()];
+                r.invoice_lines = vec![inbound_line()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        service
+            .insert_supplier_return(
+                &context,
+                &mock_store_a().id,
+                &mock_user_account_a().id,
+                input(vec![return_line("line1", 2), return_line("line2", 3)]),
+            )
+            .unwrap();
+
+        let invoice = InvoiceRowRepository::new(&connection)
+            .find_one_by_id("supplier_return")
+            .unwrap();
+        assert_eq!(invoice.r#type, InvoiceRowType::SupplierReturn);
+        assert_eq!(invoice.name_id, mock_name_store_b().id);
+
+        let line = InvoiceLineRowRepository::new(&connection)
+            .find_one_by_id("line2")
+            .unwrap();
+        assert_eq!(
+            line,
+            inline_edit(&inbound_line(), |mut u| {
+                u.id = "line2".to_string();
+                u.invoice_id = "supplier_return".to_string();
+                u.r#type = InvoiceLineRowType::StockOut;
+                u.number_of_packs = 3;
+                u.total_before_tax = 9.0;
+                u.total_after_tax = 9.0;
+                u.original_invoice_line_id = Some(inbound_line().id);
+                u
+            })
+        );
+
+        // Returned packs are reserved
+        let stock_line = StockLineRowRepository::new(&connection)
+            .find_one_by_id(&stock_line().id)
+            .unwrap();
+        assert_eq!(stock_line.available_number_of_packs, 3);
+        assert_eq!(stock_line.total_number_of_packs, 10);
+    }
+}
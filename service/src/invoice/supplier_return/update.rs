@@ -0,0 +1,357 @@
+use chrono::Utc;
+use repository::{
+    Invoice, InvoiceLineRowRepository, InvoiceRow, InvoiceRowRepository, InvoiceRowStatus,
+    InvoiceRowType, RepositoryError, StockLineRow, StockLineRowRepository, StorageConnection,
+    TransactionError,
+};
+
+use crate::invoice::{
+    check_invoice_exists, check_invoice_is_editable, check_invoice_status, check_invoice_type,
+    query::get_invoice, InvoiceDoesNotExist, InvoiceIsNotEditable, InvoiceRowStatusError,
+    WrongInvoiceRowType,
+};
+use crate::{service_provider::ServiceContext, WithDBError};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum UpdateSupplierReturnStatus {
+    Picked,
+    Verified,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct UpdateSupplierReturn {
+    pub id: String,
+    pub status: Option<UpdateSupplierReturnStatus>,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub colour: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateSupplierReturnError {
+    CannotReverseInvoiceStatus,
+    InvoiceDoesNotExist,
+    InvoiceIsNotEditable,
+    NotASupplierReturn,
+    // Internal
+    UpdatedInvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = UpdateSupplierReturnError;
+
+/// Updates a supplier return, the returned stock leaves the store when the return is picked.
+pub fn update_supplier_return(
+    ctx: &ServiceContext,
+    _store_id: &str,
+    patch: UpdateSupplierReturn,
+) -> Result<Invoice, OutError> {
+    let invoice = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let invoice = validate(connection, &patch)?;
+            let (update_invoice, stock_lines_option) = generate(connection, invoice, patch)?;
+
+            InvoiceRowRepository::new(connection).upsert_one(&update_invoice)?;
+            if let Some(stock_lines) = stock_lines_option {
+                let stock_line_repository = StockLineRowRepository::new(connection);
+                for stock_line in stock_lines {
+                    stock_line_repository.upsert_one(&stock_line)?;
+                }
+            }
+
+            get_invoice(ctx, None, &update_invoice.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::UpdatedInvoiceDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(invoice)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    patch: &UpdateSupplierReturn,
+) -> Result<InvoiceRow, OutError> {
+    let invoice = check_invoice_exists(&patch.id, connection)?;
+    check_invoice_type(&invoice, InvoiceRowType::SupplierReturn)?;
+    check_invoice_is_editable(&invoice)?;
+    check_invoice_status(&invoice, patch.full_status(), &None)?;
+
+    Ok(invoice)
+}
+
+fn generate(
+    connection: &StorageConnection,
+    existing_invoice: InvoiceRow,
+    UpdateSupplierReturn {
+        id: _,
+        status,
+        their_reference,
+        comment,
+        colour,
+    }: UpdateSupplierReturn,
+) -> Result<(InvoiceRow, Option<Vec<StockLineRow>>), RepositoryError> {
+    let new_status = status.map(|status| status.full_status());
+    let is_picking = match &new_status {
+        Some(new_status) => {
+            new_status.index() >= InvoiceRowStatus::Picked.index()
+                && existing_invoice.status.index() < InvoiceRowStatus::Picked.index()
+        }
+        None => false,
+    };
+
+    let mut update_invoice = existing_invoice;
+    update_invoice.their_reference = their_reference.or(update_invoice.their_reference);
+    update_invoice.comment = comment.or(update_invoice.comment);
+    update_invoice.colour = colour.or(update_invoice.colour);
+
+    if let Some(new_status) = new_status {
+        let current_datetime = Utc::now().naive_utc();
+        if is_picking {
+            update_invoice.picked_datetime = Some(current_datetime);
+        }
+        if new_status == InvoiceRowStatus::Verified
+            && update_invoice.status != InvoiceRowStatus::Verified
+        {
+            update_invoice.verified_datetime = Some(current_datetime);
+        }
+        update_invoice.status = new_status;
+    }
+
+    if !is_picking {
+        return Ok((update_invoice, None));
+    }
+    let batches = generate_batches(connection, &update_invoice.id)?;
+    Ok((update_invoice, Some(batches)))
+}
+
+/// Takes the returned packs off the stock lines, available packs were already reduced when the
+/// lines were added.
+fn generate_batches(
+    connection: &StorageConnection,
+    invoice_id: &str,
+) -> Result<Vec<StockLineRow>, RepositoryError> {
+    let stock_line_repository = StockLineRowRepository::new(connection);
+    let mut stock_lines: Vec<StockLineRow> = Vec::new();
+
+    for invoice_line in
+        InvoiceLineRowRepository::new(connection).find_many_by_invoice_id(invoice_id)?
+    {
+        let stock_line_id = match invoice_line.stock_line_id {
+            Some(stock_line_id) => stock_line_id,
+            None => continue,
+        };
+        match stock_lines
+            .iter_mut()
+            .find(|other| other.id == stock_line_id)
+        {
+            Some(stock_line) => stock_line.total_number_of_packs -= invoice_line.number_of_packs,
+            None => {
+                let mut stock_line = stock_line_repository.find_one_by_id(&stock_line_id)?;
+                stock_line.total_number_of_packs -= invoice_line.number_of_packs;
+                stock_lines.push(stock_line);
+            }
+        }
+    }
+
+    Ok(stock_lines)
+}
+
+impl UpdateSupplierReturnStatus {
+    pub fn full_status(&self) -> InvoiceRowStatus {
+        match self {
+            UpdateSupplierReturnStatus::Picked => InvoiceRowStatus::Picked,
+            UpdateSupplierReturnStatus::Verified => InvoiceRowStatus::Verified,
+        }
+    }
+}
+
+impl UpdateSupplierReturn {
+    pub fn full_status(&self) -> Option<InvoiceRowStatus> {
+        self.status.as_ref().map(|status| status.full_status())
+    }
+}
+
+impl From<WrongInvoiceRowType> for UpdateSupplierReturnError {
+    fn from(_: WrongInvoiceRowType) -> Self {
+        UpdateSupplierReturnError::NotASupplierReturn
+    }
+}
+
+impl From<InvoiceDoesNotExist> for UpdateSupplierReturnError {
+    fn from(_: InvoiceDoesNotExist) -> Self {
+        UpdateSupplierReturnError::InvoiceDoesNotExist
+    }
+}
+
+impl From<InvoiceIsNotEditable> for UpdateSupplierReturnError {
+    fn from(_: InvoiceIsNotEditable) -> Self {
+        UpdateSupplierReturnError::InvoiceIsNotEditable
+    }
+}
+
+impl From<InvoiceRowStatusError> for UpdateSupplierReturnError {
+    fn from(error: InvoiceRowStatusError) -> Self {
+        match error {
+            // Returns can't be put on hold
+            InvoiceRowStatusError::CannotChangeStatusOfInvoiceOnHold
+            | InvoiceRowStatusError::CannotReverseInvoiceStatus => {
+                UpdateSupplierReturnError::CannotReverseInvoiceStatus
+            }
+        }
+    }
+}
+
+impl From<RepositoryError> for UpdateSupplierReturnError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateSupplierReturnError::DatabaseError(error)
+    }
+}
+
+impl From<TransactionError<UpdateSupplierReturnError>> for UpdateSupplierReturnError {
+    fn from(error: TransactionError<UpdateSupplierReturnError>) -> Self {
+        match error {
+            TransactionError::Transaction { msg, level } => {
+                UpdateSupplierReturnError::DatabaseError(RepositoryError::TransactionError {
+                    msg,
+                    level,
+                })
+            }
+            TransactionError::Inner(e) => e,
+        }
+    }
+}
+
+impl<ERR> From<WithDBError<ERR>> for UpdateSupplierReturnError
+where
+    ERR: Into<UpdateSupplierReturnError>,
+{
+    fn from(result: WithDBError<ERR>) -> Self {
+        match result {
+            WithDBError::DatabaseError(error) => error.into(),
+            WithDBError::Error(error) => error.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_name_store_b, mock_stock_line_a, mock_store_a, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineRowType, InvoiceRow, InvoiceRowRepository, InvoiceRowStatus,
+        InvoiceRowType, StockLineRowRepository,
+    };
+    use util::inline_init;
+
+    use crate::{
+        invoice::supplier_return::{UpdateSupplierReturn, UpdateSupplierReturnStatus},
+        service_provider::ServiceProvider,
+    };
+
+    use super::UpdateSupplierReturnError;
+
+    type ServiceError = UpdateSupplierReturnError;
+
+    fn supplier_return() -> InvoiceRow {
+        inline_init(|r: &mut InvoiceRow| {
+            r.id = "supplier_return".to_string();
+            r.name_id = mock_name_store_b().id;
+            r.store_id = mock_store_a().id;
+            r.r#type = InvoiceRowType::SupplierReturn;
+            r.status = InvoiceRowStatus::New;
+        })
+    }
+
+    fn supplier_return_line() -> InvoiceLineRow {
+        inline_init(|r: &mut InvoiceLineRow| {
+            r.id = "supplier_return_line".to_string();
+            r.invoice_id = supplier_return().id;
+            r.item_id = mock_stock_line_a().item_id;
+            r.stock_line_id = Some(mock_stock_line_a().id);
+            r.r#type = InvoiceLineRowType::StockOut;
+            r.pack_size = 1;
+            r.number_of_packs = 2;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn update_supplier_return() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "update_supplier_return",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![supplier_return()];
+                r.invoice_lines = vec![supplier_return_line()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_service;
+
+        // InvoiceDoesNotExist
+        assert_eq!(
+            service.update_supplier_return(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdateSupplierReturn| {
+                    r.id = "invalid".to_string();
+                })
+            ),
+            Err(ServiceError::InvoiceDoesNotExist)
+        );
+
+        // Success
+        service
+            .update_supplier_return(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdateSupplierReturn| {
+                    r.id = supplier_return().id;
+                    r.status = Some(UpdateSupplierReturnStatus::Picked);
+                }),
+            )
+            .unwrap();
+
+        let invoice = InvoiceRowRepository::new(&connection)
+            .find_one_by_id(&supplier_return().id)
+            .unwrap();
+        assert_eq!(invoice.status, InvoiceRowStatus::Picked);
+        assert!(invoice.picked_datetime.is_some());
+
+        // Stock leaves the store when picked
+        let stock_line = StockLineRowRepository::new(&connection)
+            .find_one_by_id(&mock_stock_line_a().id)
+            .unwrap();
+        assert_eq!(
+            stock_line.total_number_of_packs,
+            mock_stock_line_a().total_number_of_packs - supplier_return_line().number_of_packs
+        );
+
+        // InvoiceIsNotEditable once verified
+        service
+            .update_supplier_return(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdateSupplierReturn| {
+                    r.id = supplier_return().id;
+                    r.status = Some(UpdateSupplierReturnStatus::Verified);
+                }),
+            )
+            .unwrap();
+        assert_eq!(
+            service.update_supplier_return(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdateSupplierReturn| {
+                    r.id = supplier_return().id;
+                    r.status = Some(UpdateSupplierReturnStatus::Picked);
+                })
+            ),
+            Err(ServiceError::InvoiceIsNotEditable)
+        );
+    }
+}
@@ -1,8 +1,8 @@
 use crate::WithDBError;
 use repository::EqualFilter;
 use repository::{
-    InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, InvoiceRow, InvoiceRowRepository,
-    InvoiceRowStatus, InvoiceRowType, RepositoryError, StorageConnection,
+    InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, InvoiceLineRow, InvoiceRow,
+    InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType, RepositoryError, StorageConnection,
 };
 
 pub struct WrongInvoiceRowType;
@@ -18,6 +18,14 @@ pub fn check_invoice_type(
     }
 }
 
+/// Invoices that issue stock through outbound shipment lines
+pub fn check_invoice_is_stock_out(invoice: &InvoiceRow) -> Result<(), WrongInvoiceRowType> {
+    match invoice.r#type {
+        InvoiceRowType::OutboundShipment | InvoiceRowType::Prescription => Ok(()),
+        _ => Err(WrongInvoiceRowType {}),
+    }
+}
+
 pub struct InvoiceIsNotEditable;
 
 pub fn check_invoice_is_editable(invoice: &InvoiceRow) -> Result<(), InvoiceIsNotEditable> {
@@ -40,6 +48,30 @@ pub fn check_invoice_is_editable(invoice: &InvoiceRow) -> Result<(), InvoiceIsNo
             InvoiceRowStatus::Verified => false,
         },
         InvoiceRowType::InventoryAdjustment => false,
+        InvoiceRowType::Prescription => match status {
+            InvoiceRowStatus::New => true,
+            InvoiceRowStatus::Picked => true,
+            InvoiceRowStatus::Allocated => false,
+            InvoiceRowStatus::Shipped => false,
+            InvoiceRowStatus::Delivered => false,
+            InvoiceRowStatus::Verified => false,
+        },
+        InvoiceRowType::CustomerReturn => match status {
+            InvoiceRowStatus::New => true,
+            InvoiceRowStatus::Delivered => true,
+            InvoiceRowStatus::Allocated => false,
+            InvoiceRowStatus::Picked => false,
+            InvoiceRowStatus::Shipped => false,
+            InvoiceRowStatus::Verified => false,
+        },
+        InvoiceRowType::SupplierReturn => match status {
+            InvoiceRowStatus::New => true,
+            InvoiceRowStatus::Picked => true,
+            InvoiceRowStatus::Allocated => false,
+            InvoiceRowStatus::Shipped => false,
+            InvoiceRowStatus::Delivered => false,
+            InvoiceRowStatus::Verified => false,
+        },
     };
 
     if is_editable {
@@ -120,3 +152,21 @@ pub fn check_invoice_is_empty(
         Ok(())
     }
 }
+
+/// Number of packs of an issued or received invoice line that have not been returned yet.
+/// Lines of returns that are still in progress count as returned.
+pub fn get_returnable_number_of_packs(
+    connection: &StorageConnection,
+    original_line: &InvoiceLineRow,
+) -> Result<i32, RepositoryError> {
+    let returned_number_of_packs: i32 = InvoiceLineRepository::new(connection)
+        .query_by_filter(
+            InvoiceLineFilter::new()
+                .original_invoice_line_id(EqualFilter::equal_to(&original_line.id)),
+        )?
+        .iter()
+        .map(|line| line.invoice_line_row.number_of_packs)
+        .sum();
+
+    Ok(original_line.number_of_packs - returned_number_of_packs)
+}
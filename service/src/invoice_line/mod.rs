@@ -189,6 +189,15 @@ pub trait InvoiceLineServiceTrait: Sync + Send {
     ) -> Result<AllocateLineResult, AllocateOutboundShipmentUnallocatedLineError> {
         allocate_outbound_shipment_unallocated_line(ctx, store_id, line_id)
     }
+
+    fn preview_outbound_shipment_unallocated_line_allocation(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        line_id: String,
+    ) -> Result<AllocateLineResult, AllocateOutboundShipmentUnallocatedLineError> {
+        preview_outbound_shipment_unallocated_line_allocation(ctx, store_id, line_id)
+    }
 }
 
 pub struct InvoiceLineService {}
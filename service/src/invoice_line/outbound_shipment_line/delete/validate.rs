@@ -1,13 +1,13 @@
 use crate::{
     invoice::{
-        check_invoice_exists, check_invoice_is_editable, check_invoice_type,
+        check_invoice_exists, check_invoice_is_editable, check_invoice_is_stock_out,
         validate::InvoiceIsNotEditable, InvoiceDoesNotExist, WrongInvoiceRowType,
     },
     invoice_line::validate::{
         check_line_belongs_to_invoice, check_line_exists, LineDoesNotExist, NotInvoiceLine,
     },
 };
-use repository::{InvoiceLineRow, InvoiceRowType, StorageConnection};
+use repository::{InvoiceLineRow, StorageConnection};
 
 use super::{DeleteOutboundShipmentLine, DeleteOutboundShipmentLineError};
 
@@ -19,7 +19,7 @@ pub fn validate(
     let invoice = check_invoice_exists(&input.invoice_id, connection)?;
 
     check_line_belongs_to_invoice(&line, &invoice)?;
-    check_invoice_type(&invoice, InvoiceRowType::OutboundShipment)?;
+    check_invoice_is_stock_out(&invoice)?;
     check_invoice_is_editable(&invoice)?;
 
     Ok(line)
@@ -82,5 +82,8 @@ fn generate_line(
         total_after_tax,
         tax,
         note,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     }
 }
@@ -1,7 +1,7 @@
 use crate::{
     invoice::{
-        check_invoice_exists, check_invoice_is_editable, check_invoice_type, InvoiceDoesNotExist,
-        InvoiceIsNotEditable, WrongInvoiceRowType,
+        check_invoice_exists, check_invoice_is_editable, check_invoice_is_stock_out,
+        InvoiceDoesNotExist, InvoiceIsNotEditable, WrongInvoiceRowType,
     },
     invoice_line::{
         check_batch_exists, check_batch_on_hold, check_item_matches_batch, check_location_on_hold,
@@ -15,7 +15,7 @@ use crate::{
     },
     u32_to_i32,
 };
-use repository::{InvoiceRow, InvoiceRowType, ItemRow, StockLineRow, StorageConnection};
+use repository::{InvoiceRow, ItemRow, StockLineRow, StorageConnection};
 
 use super::{InsertOutboundShipmentLine, InsertOutboundShipmentLineError};
 
@@ -36,7 +36,7 @@ pub fn validate(
         connection,
     )?;
     // check_store(invoice, connection)?; InvoiceDoesNotBelongToCurrentStore
-    check_invoice_type(&invoice, InvoiceRowType::OutboundShipment)?;
+    check_invoice_is_stock_out(&invoice)?;
     check_invoice_is_editable(&invoice)?;
 
     check_batch_on_hold(&batch)?;
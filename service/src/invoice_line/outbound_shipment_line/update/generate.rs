@@ -119,6 +119,9 @@ fn generate_line(
         tax,
         r#type,
         note,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     if let Some(number_of_packs) = input.number_of_packs {
@@ -1,7 +1,7 @@
 use crate::{
     invoice::{
-        check_invoice_exists, check_invoice_is_editable, check_invoice_type, InvoiceDoesNotExist,
-        InvoiceIsNotEditable, WrongInvoiceRowType,
+        check_invoice_exists, check_invoice_is_editable, check_invoice_is_stock_out,
+        InvoiceDoesNotExist, InvoiceIsNotEditable, WrongInvoiceRowType,
     },
     invoice_line::{
         check_batch_exists, check_batch_on_hold, check_item_matches_batch, check_location_on_hold,
@@ -14,7 +14,7 @@ use crate::{
         StockLineAlreadyExistsInInvoice, StockLineNotFound,
     },
 };
-use repository::{InvoiceLineRow, InvoiceRow, InvoiceRowType, ItemRow, StorageConnection};
+use repository::{InvoiceLineRow, InvoiceRow, ItemRow, StorageConnection};
 
 use super::{BatchPair, UpdateOutboundShipmentLine, UpdateOutboundShipmentLineError};
 
@@ -35,7 +35,7 @@ pub fn validate(
     // check batch belongs to store
 
     check_line_belongs_to_invoice(&line, &invoice)?;
-    check_invoice_type(&invoice, InvoiceRowType::OutboundShipment)?;
+    check_invoice_is_stock_out(&invoice)?;
     check_invoice_is_editable(&invoice)?;
 
     check_number_of_packs(input.number_of_packs.clone())?;
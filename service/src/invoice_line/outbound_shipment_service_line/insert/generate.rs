@@ -35,5 +35,8 @@ pub fn generate(
         cost_price_per_pack: 0.0,
         sell_price_per_pack: 0.0,
         number_of_packs: 0,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     })
 }
@@ -1,20 +1,25 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::HashMap};
 
+use chrono::{Duration, NaiveDateTime};
 use repository::{
-    EqualFilter, InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, InvoiceLineRow,
-    InvoiceLineRowType, Pagination, RepositoryError, StockLine, StockLineFilter,
-    StockLineRepository, StockLineSort, StockLineSortField, StorageConnection,
+    AllocationStrategy, EqualFilter, InvoiceLine, InvoiceLineFilter, InvoiceLineRepository,
+    InvoiceLineRow, InvoiceLineRowType, InvoiceRowRepository, Pagination, RepositoryError,
+    StockLedgerFilter, StockLedgerRepository, StockLine, StockLineFilter, StockLineRepository,
+    StockLineSort, StockLineSortField, StorageConnection,
 };
 use util::{
     constants::stock_line_expiring_soon_offset, date_now, date_now_with_offset,
     fraction_is_integer, uuid,
 };
 
-use crate::invoice_line::{
-    outbound_shipment_line::{InsertOutboundShipmentLine, UpdateOutboundShipmentLine},
-    outbound_shipment_unallocated_line::{
-        DeleteOutboundShipmentUnallocatedLine, UpdateOutboundShipmentUnallocatedLine,
+use crate::{
+    invoice_line::{
+        outbound_shipment_line::{InsertOutboundShipmentLine, UpdateOutboundShipmentLine},
+        outbound_shipment_unallocated_line::{
+            DeleteOutboundShipmentUnallocatedLine, UpdateOutboundShipmentUnallocatedLine,
+        },
     },
+    store_preference::query::{get_allocation_preference, AllocationPreference},
 };
 
 #[derive(Default)]
@@ -25,6 +30,7 @@ pub struct GenerateOutput {
     pub delete_unallocated_line: Option<DeleteOutboundShipmentUnallocatedLine>,
     pub skipped_expired_stock_lines: Vec<StockLine>,
     pub skipped_on_hold_stock_lines: Vec<StockLine>,
+    pub skipped_short_shelf_life_stock_lines: Vec<StockLine>,
     pub issued_expiring_soon_stock_lines: Vec<StockLine>,
 }
 
@@ -44,12 +50,13 @@ pub fn generate(
         });
         return Ok(result);
     }
-    // Asc, by expiry date, nulls last
+    let invoice =
+        InvoiceRowRepository::new(connection).find_one_by_id(&unallocated_line.invoice_id)?;
+    let preference = get_allocation_preference(connection, store_id, &invoice.name_id)?;
     let sorted_available_stock_lines =
-        get_sorted_available_stock_lines(connection, store_id, &unallocated_line)?;
-    // Use FEFO to allocate
+        get_sorted_available_stock_lines(connection, store_id, &unallocated_line, &preference)?;
     for stock_line in sorted_available_stock_lines {
-        let can_use = get_stock_line_eligibility(&stock_line)
+        let can_use = get_stock_line_eligibility(&stock_line, &preference)
             .map(|eligibility| match eligibility {
                 StockLineAlert::OnHold => {
                     result.skipped_on_hold_stock_lines.push(stock_line.clone());
@@ -59,6 +66,12 @@ pub fn generate(
                     result.skipped_expired_stock_lines.push(stock_line.clone());
                     false
                 }
+                StockLineAlert::ShortShelfLife => {
+                    result
+                        .skipped_short_shelf_life_stock_lines
+                        .push(stock_line.clone());
+                    false
+                }
                 StockLineAlert::ExpiringSoon => {
                     result
                         .issued_expiring_soon_stock_lines
@@ -115,10 +128,15 @@ pub fn generate(
 enum StockLineAlert {
     OnHold,
     Expired,
+    /// Expires before the minimum shelf life required by the store or customer
+    ShortShelfLife,
     ExpiringSoon,
 }
 
-fn get_stock_line_eligibility(stock_line: &StockLine) -> Option<StockLineAlert> {
+fn get_stock_line_eligibility(
+    stock_line: &StockLine,
+    preference: &AllocationPreference,
+) -> Option<StockLineAlert> {
     use StockLineAlert::*;
     let stock_line_row = &stock_line.stock_line_row;
     // Expired
@@ -135,6 +153,14 @@ fn get_stock_line_eligibility(stock_line: &StockLine) -> Option<StockLineAlert>
         return Some(Expired);
     }
 
+    if let Some(minimum_shelf_life_days) = preference.minimum_shelf_life_days {
+        if let Ordering::Less = expiry_date.cmp(&date_now_with_offset(Duration::days(
+            minimum_shelf_life_days as i64,
+        ))) {
+            return Some(ShortShelfLife);
+        }
+    }
+
     if let Ordering::Less =
         expiry_date.cmp(&date_now_with_offset(stock_line_expiring_soon_offset()))
     {
@@ -201,10 +227,15 @@ fn packs_to_allocate_from_stock_line(remaining_to_allocated: i32, line: &StockLi
     fractional_number_of_packs.floor() as i32 + 1
 }
 
+/// Stock lines in the order they should be allocated:
+/// * FEFO: asc by expiry date, nulls last
+/// * FIFO: asc by received date, stock lines without receipt last (then by expiry date)
+/// Stock lines in the preferred location are always allocated first
 fn get_sorted_available_stock_lines(
     connection: &StorageConnection,
     store_id: &str,
     unallocated_line: &InvoiceLineRow,
+    preference: &AllocationPreference,
 ) -> Result<Vec<StockLine>, RepositoryError> {
     let filter = StockLineFilter::new()
         .item_id(EqualFilter::equal_to(&unallocated_line.item_id))
@@ -217,7 +248,56 @@ fn get_sorted_available_stock_lines(
         desc: Some(false),
     };
 
-    StockLineRepository::new(connection).query(Pagination::new(), Some(filter), Some(sort))
+    let mut stock_lines =
+        StockLineRepository::new(connection).query(Pagination::new(), Some(filter), Some(sort))?;
+
+    // Sorts below are stable, keeping the expiry date order for equal keys
+    if let AllocationStrategy::Fifo = preference.strategy {
+        let received_datetimes =
+            get_received_datetimes(connection, store_id, &unallocated_line.item_id)?;
+        stock_lines.sort_by_key(|stock_line| {
+            let received_datetime = received_datetimes
+                .get(&stock_line.stock_line_row.id)
+                .copied();
+            (received_datetime.is_none(), received_datetime)
+        });
+    }
+
+    if let Some(preferred_location_id) = &preference.preferred_location_id {
+        stock_lines.sort_by_key(|stock_line| {
+            stock_line.stock_line_row.location_id.as_ref() != Some(preferred_location_id)
+        });
+    }
+
+    Ok(stock_lines)
+}
+
+/// Datetime of the first movement into each stock line of the item, from the stock ledger
+fn get_received_datetimes(
+    connection: &StorageConnection,
+    store_id: &str,
+    item_id: &str,
+) -> Result<HashMap<String, NaiveDateTime>, RepositoryError> {
+    let movements = StockLedgerRepository::new(connection).query_by_filter(
+        StockLedgerFilter::new()
+            .store_id(EqualFilter::equal_to(store_id))
+            .item_id(EqualFilter::equal_to(item_id)),
+    )?;
+
+    let mut received_datetimes = HashMap::new();
+    // Movements are sorted by datetime, keep the first one per stock line
+    for movement in movements
+        .into_iter()
+        .filter(|movement| movement.quantity > 0)
+    {
+        if let Some(stock_line_id) = movement.stock_line_id {
+            received_datetimes
+                .entry(stock_line_id)
+                .or_insert(movement.datetime);
+        }
+    }
+
+    Ok(received_datetimes)
 }
 
 fn get_allocated_lines(
@@ -11,6 +11,7 @@ use crate::{
 };
 use repository::{
     InvoiceLine, InvoiceLineRow, InvoiceLineRowType, RepositoryError, StockLine, StorageConnection,
+    TransactionError,
 };
 
 use super::{
@@ -69,6 +70,7 @@ pub struct AllocateLineResult {
     pub updates: Vec<InvoiceLine>,
     pub skipped_expired_stock_lines: Vec<StockLine>,
     pub skipped_on_hold_stock_lines: Vec<StockLine>,
+    pub skipped_short_shelf_life_stock_lines: Vec<StockLine>,
     pub issued_expiring_soon_stock_lines: Vec<StockLine>,
 }
 
@@ -90,6 +92,7 @@ pub fn allocate_outbound_shipment_unallocated_line(
                 delete_unallocated_line,
                 skipped_expired_stock_lines,
                 skipped_on_hold_stock_lines,
+                skipped_short_shelf_life_stock_lines,
                 issued_expiring_soon_stock_lines,
             } = generate(&connection, &store_id, unallocated_line)?;
 
@@ -99,6 +102,7 @@ pub fn allocate_outbound_shipment_unallocated_line(
                 updates: vec![],
                 skipped_expired_stock_lines,
                 skipped_on_hold_stock_lines,
+                skipped_short_shelf_life_stock_lines,
                 issued_expiring_soon_stock_lines,
             };
 
@@ -152,6 +156,35 @@ pub fn allocate_outbound_shipment_unallocated_line(
     Ok(line)
 }
 
+/// Dry run of `allocate_outbound_shipment_unallocated_line`, returns the lines that would be
+/// inserted, updated and deleted without saving them
+pub fn preview_outbound_shipment_unallocated_line_allocation(
+    ctx: &ServiceContext,
+    store_id: &str,
+    line_id: String,
+) -> Result<ServiceResult, OutError> {
+    // Allocate in a new (nested) transaction and always roll it back
+    let preview: Result<(), _> = ctx.connection.transaction_sync_etc(
+        |_| {
+            Err(allocate_outbound_shipment_unallocated_line(
+                ctx, store_id, line_id,
+            ))
+        },
+        false,
+    );
+
+    match preview {
+        Err(TransactionError::Inner(result)) => result,
+        Err(TransactionError::Transaction { msg, level }) => {
+            Err(OutError::DatabaseError(RepositoryError::TransactionError {
+                msg,
+                level,
+            }))
+        }
+        Ok(()) => unreachable!("Allocation preview is always rolled back"),
+    }
+}
+
 fn validate(connection: &StorageConnection, line_id: &str) -> Result<InvoiceLineRow, OutError> {
     let invoice_line =
         check_line_exists_option(connection, line_id)?.ok_or(OutError::LineDoesNotExist)?;
@@ -4,12 +4,14 @@ mod test {
     use chrono::{Duration, NaiveDate};
     use repository::{
         mock::{
-            mock_item_a, mock_item_b, mock_name_a, mock_outbound_shipment_a_invoice_lines,
-            mock_store_a, mock_store_b, MockData, MockDataInserts,
+            mock_item_a, mock_item_b, mock_location_1, mock_name_a,
+            mock_outbound_shipment_a_invoice_lines, mock_store_a, mock_store_b, MockData,
+            MockDataInserts,
         },
         test_db::{setup_all, setup_all_with_data},
-        InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRow, InvoiceRowType,
-        RepositoryError, StockLine, StockLineRow,
+        AllocationStrategy, CustomerShelfLifeRow, CustomerShelfLifeRowRepository, InvoiceLineRow,
+        InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRow, InvoiceRowType, RepositoryError,
+        StockLine, StockLineRow, StorePreferenceRow,
     };
     use util::{
         constants::stock_line_expiring_soon_offset, date_now, date_now_with_offset, inline_edit,
@@ -17,8 +19,13 @@ mod test {
     };
 
     use crate::{
-        invoice_line::AllocateOutboundShipmentUnallocatedLineError as ServiceError,
+        invoice_line::{
+            AllocateLineResult as ServiceResult,
+            AllocateOutboundShipmentUnallocatedLineError as ServiceError,
+        },
         service_provider::ServiceProvider,
+        store_preference::update::UpdateStorePreference,
+        NullableUpdate,
     };
 
     #[actix_rt::test]
@@ -650,4 +657,192 @@ mod test {
             })
         );
     }
+
+    #[actix_rt::test]
+    async fn allocate_unallocated_line_preferences_and_preview() {
+        fn invoice() -> InvoiceRow {
+            inline_init(|r: &mut InvoiceRow| {
+                r.id = "invoice".to_string();
+                r.store_id = mock_store_a().id;
+                r.name_id = mock_name_a().id;
+                r.r#type = InvoiceRowType::OutboundShipment;
+            })
+        }
+
+        fn line() -> InvoiceLineRow {
+            inline_init(|r: &mut InvoiceLineRow| {
+                r.id = "line".to_string();
+                r.invoice_id = invoice().id;
+                r.item_id = mock_item_a().id;
+                r.r#type = InvoiceLineRowType::UnallocatedStock;
+                r.number_of_packs = 3;
+                r.pack_size = 1;
+            })
+        }
+
+        fn base_stock_line(id: &str, expiry_in_days: i64) -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = id.to_string();
+                r.store_id = mock_store_a().id;
+                r.item_id = mock_item_a().id;
+                r.pack_size = 1;
+                r.available_number_of_packs = 1;
+                r.expiry_date = Some(date_now_with_offset(Duration::days(expiry_in_days)));
+            })
+        }
+
+        // Received first but expires last
+        fn stock_line_old() -> StockLineRow {
+            base_stock_line("stock_line_old", 500)
+        }
+
+        fn stock_line_new() -> StockLineRow {
+            inline_edit(&base_stock_line("stock_line_new", 400), |mut u| {
+                u.location_id = Some(mock_location_1().id);
+                u
+            })
+        }
+
+        // Not received through an invoice, expires first
+        fn stock_line_unknown() -> StockLineRow {
+            base_stock_line("stock_line_unknown", 10)
+        }
+
+        fn receipt(stock_line: StockLineRow, day: u32) -> MockData {
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![inline_init(|r: &mut InvoiceRow| {
+                    r.id = format!("{}_receipt", stock_line.id);
+                    r.store_id = mock_store_a().id;
+                    r.name_id = mock_name_a().id;
+                    r.r#type = InvoiceRowType::InboundShipment;
+                    r.delivered_datetime =
+                        Some(NaiveDate::from_ymd(2021, 01, day).and_hms(0, 0, 0));
+                })];
+                r.invoice_lines = vec![inline_init(|r: &mut InvoiceLineRow| {
+                    r.id = format!("{}_receipt_line", stock_line.id);
+                    r.invoice_id = format!("{}_receipt", stock_line.id);
+                    r.item_id = mock_item_a().id;
+                    r.stock_line_id = Some(stock_line.id.clone());
+                    r.r#type = InvoiceLineRowType::StockIn;
+                    r.number_of_packs = 1;
+                    r.pack_size = 1;
+                })];
+            })
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "allocate_unallocated_line_preferences_and_preview",
+            MockDataInserts::none()
+                .stores()
+                .items()
+                .names()
+                .units()
+                .locations()
+                .store_preferences(),
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![invoice()];
+                r.invoice_lines = vec![line()];
+                r.stock_lines = vec![stock_line_unknown(), stock_line_new(), stock_line_old()];
+                r.store_preferences = vec![inline_init(|r: &mut StorePreferenceRow| {
+                    r.id = mock_store_a().id;
+                    r.allocation_strategy = AllocationStrategy::Fifo;
+                })];
+            })
+            .join(receipt(stock_line_old(), 1))
+            .join(receipt(stock_line_new(), 2)),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager.clone());
+        let context = service_provider.context().unwrap();
+        let service = service_provider.invoice_line_service;
+
+        fn allocated_stock_lines(result: &ServiceResult) -> Vec<String> {
+            result
+                .inserts
+                .iter()
+                .map(|line| line.invoice_line_row.stock_line_id.clone().unwrap())
+                .collect()
+        }
+
+        // FIFO, preview doesn't save anything
+        let result = service
+            .preview_outbound_shipment_unallocated_line_allocation(
+                &context,
+                &mock_store_a().id,
+                line().id,
+            )
+            .unwrap();
+        assert_eq!(
+            allocated_stock_lines(&result),
+            vec![
+                stock_line_old().id,
+                stock_line_new().id,
+                stock_line_unknown().id
+            ]
+        );
+        assert_eq!(result.deletes, vec![line().id]);
+
+        let repo = InvoiceLineRowRepository::new(&connection);
+        assert_eq!(repo.find_one_by_id(&line().id), Ok(line()));
+        assert_eq!(
+            repo.find_one_by_id(&result.inserts[0].invoice_line_row.id),
+            Err(RepositoryError::NotFound)
+        );
+
+        // Preferred location first
+        service_provider
+            .store_preference_service
+            .update_store_preference(
+                &context,
+                &mock_store_a().id,
+                UpdateStorePreference {
+                    allocation_strategy: Some(AllocationStrategy::Fifo),
+                    preferred_location_id: Some(NullableUpdate {
+                        value: Some(mock_location_1().id),
+                    }),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        let result = service
+            .preview_outbound_shipment_unallocated_line_allocation(
+                &context,
+                &mock_store_a().id,
+                line().id,
+            )
+            .unwrap();
+        assert_eq!(
+            allocated_stock_lines(&result),
+            vec![
+                stock_line_new().id,
+                stock_line_old().id,
+                stock_line_unknown().id
+            ]
+        );
+
+        // Minimum shelf life of the customer
+        CustomerShelfLifeRowRepository::new(&connection)
+            .upsert_one(&CustomerShelfLifeRow {
+                id: "customer_shelf_life".to_string(),
+                store_id: mock_store_a().id,
+                name_id: mock_name_a().id,
+                minimum_shelf_life_days: 30,
+            })
+            .unwrap();
+        let result = service
+            .allocate_outbound_shipment_unallocated_line(&context, &mock_store_a().id, line().id)
+            .unwrap();
+        assert_eq!(
+            allocated_stock_lines(&result),
+            vec![stock_line_new().id, stock_line_old().id]
+        );
+        assert_eq!(
+            result.skipped_short_shelf_life_stock_lines,
+            vec![inline_init(|r: &mut StockLine| {
+                r.stock_line_row = stock_line_unknown();
+            })]
+        );
+        assert_eq!(repo.find_one_by_id(&line().id).unwrap().number_of_packs, 1);
+    }
 }
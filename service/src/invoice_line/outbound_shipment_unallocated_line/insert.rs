@@ -121,6 +121,9 @@ fn generate(
         sell_price_per_pack: 0.0,
         cost_price_per_pack: 0.0,
         stock_line_id: None,
+        original_invoice_line_id: None,
+        return_reason: None,
+        inventory_adjustment_reason_id: None,
     };
 
     Ok(new_line)
@@ -344,6 +347,9 @@ mod test_insert {
                 sell_price_per_pack: 0.0,
                 cost_price_per_pack: 0.0,
                 stock_line_id: None,
+                original_invoice_line_id: None,
+                return_reason: None,
+                inventory_adjustment_reason_id: None,
             }
         )
     }
@@ -1,16 +1,20 @@
 use std::{collections::HashMap, ops::Neg};
 
-use crate::{i64_to_u32, service_provider::ServiceContext};
-use chrono::Duration;
+use crate::{
+    consumption_calculator::{
+        get_consumption_calculator, get_stock_out_days, lookback_days, ConsumptionCalculator,
+        ItemConsumption,
+    },
+    i64_to_u32,
+    service_provider::ServiceContext,
+};
+use chrono::{Duration, NaiveDate, Utc};
 use repository::{
     ConsumptionFilter, ConsumptionRepository, ConsumptionRow, DateFilter, EqualFilter,
     RepositoryError, RequisitionLineRow, StockOnHandFilter, StockOnHandRepository, StockOnHandRow,
     StorageConnection,
 };
-use util::{
-    constants::{DEFAULT_AMC_LOOKBACK_MONTHS, NUMBER_OF_DAYS_IN_A_MONTH},
-    date_now_with_offset,
-};
+use util::{constants::DEFAULT_AMC_LOOKBACK_MONTHS, date_now_with_offset};
 
 #[derive(Clone, Debug, PartialEq, Default)]
 pub struct ItemStatsFilter {
@@ -44,22 +48,55 @@ pub fn get_item_stats(
     store_id: &str,
     amc_lookback_months: Option<u32>,
     filter: Option<ItemStatsFilter>,
+) -> Result<Vec<ItemStats>, RepositoryError> {
+    let calculator = get_consumption_calculator(&ctx.connection, store_id)?;
+    get_item_stats_with_calculator(
+        &ctx.connection,
+        store_id,
+        calculator.as_ref(),
+        amc_lookback_months,
+        filter,
+    )
+}
+
+/// Item stats with the average monthly consumption calculated by the given calculator
+pub fn get_item_stats_with_calculator(
+    connection: &StorageConnection,
+    store_id: &str,
+    calculator: &dyn ConsumptionCalculator,
+    amc_lookback_months: Option<u32>,
+    filter: Option<ItemStatsFilter>,
 ) -> Result<Vec<ItemStats>, RepositoryError> {
     let ItemStatsFilter {
         item_id: item_id_filter,
     } = filter.unwrap_or_default();
 
     let amc_lookback_months = amc_lookback_months.unwrap_or(DEFAULT_AMC_LOOKBACK_MONTHS);
+    let reference_date = Utc::now().naive_utc().date();
 
-    Ok(ItemStats::new_vec(
-        get_consumption_rows(
-            &ctx.connection,
+    let stock_on_hand_rows = get_stock_on_hand_rows(connection, store_id, item_id_filter.clone())?;
+    let stock_out_days = if calculator.uses_stock_out_days() {
+        get_stock_out_days(
+            connection,
             store_id,
-            item_id_filter.clone(),
-            amc_lookback_months,
-        )?,
-        get_stock_on_hand_rows(&ctx.connection, store_id, item_id_filter)?,
+            &stock_on_hand_rows
+                .iter()
+                .map(|row| (row.item_id.clone(), row.available_stock_on_hand))
+                .collect(),
+            reference_date - Duration::days(lookback_days(amc_lookback_months)),
+            reference_date,
+        )?
+    } else {
+        HashMap::new()
+    };
+
+    Ok(ItemStats::new_vec(
+        calculator,
+        get_consumption_rows(connection, store_id, item_id_filter, amc_lookback_months)?,
+        stock_on_hand_rows,
+        stock_out_days,
         amc_lookback_months,
+        reference_date,
     ))
 }
 
@@ -69,9 +106,7 @@ pub fn get_consumption_rows(
     item_id_filter: Option<EqualFilter<String>>,
     amc_lookback_months: u32,
 ) -> Result<Vec<ConsumptionRow>, RepositoryError> {
-    let start_date = date_now_with_offset(Duration::days(
-        (amc_lookback_months as f64 * NUMBER_OF_DAYS_IN_A_MONTH).neg() as i64,
-    ));
+    let start_date = date_now_with_offset(Duration::days(lookback_days(amc_lookback_months).neg()));
 
     let filter = ConsumptionFilter {
         item_id: item_id_filter,
@@ -97,27 +132,40 @@ pub fn get_stock_on_hand_rows(
 
 impl ItemStats {
     fn new_vec(
+        calculator: &dyn ConsumptionCalculator,
         consumption_rows: Vec<ConsumptionRow>,
         stock_on_hand_rows: Vec<StockOnHandRow>,
+        stock_out_days: HashMap<String, u32>,
         amc_lookback_months: u32,
+        reference_date: NaiveDate,
     ) -> Vec<Self> {
-        let mut consumption_map = HashMap::new();
+        let mut consumption_map: HashMap<String, Vec<ConsumptionRow>> = HashMap::new();
         for consumption_row in consumption_rows.into_iter() {
-            let item_total_consumption = consumption_map
+            consumption_map
                 .entry(consumption_row.item_id.clone())
-                .or_insert(0);
-            *item_total_consumption += consumption_row.quantity;
+                .or_default()
+                .push(consumption_row);
         }
 
         stock_on_hand_rows
             .into_iter()
             .map(|stock_on_hand| ItemStats {
                 available_stock_on_hand: i64_to_u32(stock_on_hand.available_stock_on_hand),
-                item_id: stock_on_hand.item_id.clone(),
-                average_monthly_consumption: consumption_map
-                    .get(&stock_on_hand.item_id)
-                    .map(|consumption| *consumption as f64 / amc_lookback_months as f64)
-                    .unwrap_or_default(),
+                average_monthly_consumption: match consumption_map.remove(&stock_on_hand.item_id) {
+                    Some(consumption_rows) => {
+                        calculator.average_monthly_consumption(&ItemConsumption {
+                            consumption_rows,
+                            lookback_months: amc_lookback_months,
+                            reference_date,
+                            stock_out_days: stock_out_days
+                                .get(&stock_on_hand.item_id)
+                                .copied()
+                                .unwrap_or_default(),
+                        })
+                    }
+                    None => 0.0,
+                },
+                item_id: stock_on_hand.item_id,
             })
             .collect()
     }
@@ -3,31 +3,46 @@ use repository::{Pagination, PaginationOption, DEFAULT_PAGINATION_LIMIT};
 use service_provider::ServiceContext;
 use std::convert::TryInto;
 
+pub mod alert;
 pub mod apis;
 pub mod auth_data;
+pub mod backorder;
+pub mod barcode;
+pub mod consumption_calculator;
+pub mod cycle_count;
 pub mod dashboard;
+pub mod inventory_adjustment_reason;
 pub mod invoice;
 pub mod invoice_line;
 pub mod item;
 pub mod item_stats;
+pub mod local_user;
 pub mod location;
 pub mod login;
 pub mod master_list;
 pub mod name;
 pub mod number;
+pub mod patient;
 pub mod permission_validation;
+pub mod program;
+pub mod reorder;
 pub mod report;
 pub mod requisition;
 pub mod requisition_line;
 pub mod service_provider;
 pub mod settings_service;
 pub mod static_files;
+pub mod stock_ledger;
 pub mod stock_line;
 pub mod stocktake;
 pub mod stocktake_line;
 pub mod store;
+pub mod store_preference;
+pub mod sync_actor;
 pub mod sync_processor;
 pub mod sync_settings;
+pub mod sync_error;
+pub mod sync_status;
 pub mod token;
 pub mod token_bucket;
 pub mod user_account;
@@ -63,6 +78,14 @@ pub enum SingleRecordError {
     NotFound(String),
 }
 
+/// Update of an optional field of a patch input, the field is kept as is if the patch doesn't
+/// contain an update for it
+#[derive(Clone, PartialEq, Debug)]
+pub struct NullableUpdate<T> {
+    /// Set or unset the value
+    pub value: Option<T>,
+}
+
 pub enum WithDBError<T> {
     DatabaseError(RepositoryError),
     Error(T),
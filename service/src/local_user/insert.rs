@@ -0,0 +1,207 @@
+use super::validate::{check_user_exists, check_username_is_unique, get_user};
+use crate::{service_provider::ServiceContext, user_account::UserAccountService};
+use repository::{
+    RepositoryError, StorageConnection, User, UserAccountRow, UserAccountRowRepository,
+};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct InsertLocalUser {
+    pub id: String,
+    pub username: String,
+    /// Plain text password
+    pub password: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InsertLocalUserError {
+    UserAlreadyExists,
+    UsernameAlreadyExists,
+    EmptyUsername,
+    EmptyPassword,
+    FailedToHashPassword(String),
+    NewlyCreatedUserDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = InsertLocalUserError;
+
+pub fn insert_local_user(ctx: &ServiceContext, input: InsertLocalUser) -> Result<User, OutError> {
+    let user = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(connection, &input)?;
+            let new_user = generate(input)?;
+            UserAccountRowRepository::new(connection).insert_one(&new_user)?;
+
+            get_user(connection, &new_user.id)?.ok_or(OutError::NewlyCreatedUserDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(user)
+}
+
+fn validate(connection: &StorageConnection, input: &InsertLocalUser) -> Result<(), OutError> {
+    if input.username.trim().is_empty() {
+        return Err(OutError::EmptyUsername);
+    }
+    if input.password.is_empty() {
+        return Err(OutError::EmptyPassword);
+    }
+    if let Some(_) = check_user_exists(connection, &input.id)? {
+        return Err(OutError::UserAlreadyExists);
+    }
+    if !check_username_is_unique(connection, &input.id, &input.username)? {
+        return Err(OutError::UsernameAlreadyExists);
+    }
+
+    Ok(())
+}
+
+fn generate(
+    InsertLocalUser {
+        id,
+        username,
+        password,
+        email,
+    }: InsertLocalUser,
+) -> Result<UserAccountRow, OutError> {
+    let hashed_password = UserAccountService::hash_password(&password)
+        .map_err(|err| OutError::FailedToHashPassword(format!("{:?}", err)))?;
+
+    Ok(UserAccountRow {
+        id,
+        username,
+        hashed_password,
+        email,
+        is_local: true,
+        is_disabled: false,
+    })
+}
+
+impl From<RepositoryError> for InsertLocalUserError {
+    fn from(error: RepositoryError) -> Self {
+        InsertLocalUserError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_user_account_a, MockDataInserts},
+        test_db::setup_all,
+        UserAccountRowRepository,
+    };
+
+    use crate::{
+        local_user::{InsertLocalUser, InsertLocalUserError as ServiceError},
+        service_provider::ServiceProvider,
+        user_account::UserAccountService,
+    };
+
+    #[actix_rt::test]
+    async fn insert_local_user_errors() {
+        let (_, _, connection_manager, _) = setup_all(
+            "insert_local_user_errors",
+            MockDataInserts::none().user_accounts(),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.local_user_service;
+
+        // UserAlreadyExists
+        assert_eq!(
+            service.insert_local_user(
+                &context,
+                InsertLocalUser {
+                    id: mock_user_account_a().id,
+                    username: "new_username".to_owned(),
+                    password: "password".to_owned(),
+                    email: None,
+                },
+            ),
+            Err(ServiceError::UserAlreadyExists)
+        );
+
+        // UsernameAlreadyExists
+        assert_eq!(
+            service.insert_local_user(
+                &context,
+                InsertLocalUser {
+                    id: "new_id".to_owned(),
+                    username: mock_user_account_a().username,
+                    password: "password".to_owned(),
+                    email: None,
+                },
+            ),
+            Err(ServiceError::UsernameAlreadyExists)
+        );
+
+        // EmptyUsername
+        assert_eq!(
+            service.insert_local_user(
+                &context,
+                InsertLocalUser {
+                    id: "new_id".to_owned(),
+                    username: " ".to_owned(),
+                    password: "password".to_owned(),
+                    email: None,
+                },
+            ),
+            Err(ServiceError::EmptyUsername)
+        );
+
+        // EmptyPassword
+        assert_eq!(
+            service.insert_local_user(
+                &context,
+                InsertLocalUser {
+                    id: "new_id".to_owned(),
+                    username: "new_username".to_owned(),
+                    password: "".to_owned(),
+                    email: None,
+                },
+            ),
+            Err(ServiceError::EmptyPassword)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn insert_local_user_success() {
+        let (_, connection, connection_manager, _) = setup_all(
+            "insert_local_user_success",
+            MockDataInserts::none().user_accounts(),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.local_user_service;
+
+        let result = service
+            .insert_local_user(
+                &context,
+                InsertLocalUser {
+                    id: "new_id".to_owned(),
+                    username: "new_username".to_owned(),
+                    password: "password".to_owned(),
+                    email: Some("new@openmsupply.foundation".to_owned()),
+                },
+            )
+            .unwrap();
+        assert_eq!(result.user_row.id, "new_id");
+        assert_eq!(result.stores, vec![]);
+
+        let user_row = UserAccountRowRepository::new(&connection)
+            .find_one_by_id("new_id")
+            .unwrap()
+            .unwrap();
+        assert!(user_row.is_local);
+        assert!(!user_row.is_disabled);
+
+        UserAccountService::new(&connection)
+            .verify_password("new_username", "password")
+            .unwrap();
+    }
+}
@@ -0,0 +1,58 @@
+use repository::User;
+
+use crate::service_provider::ServiceContext;
+
+pub mod insert;
+pub use self::insert::*;
+
+pub mod update;
+pub use self::update::*;
+
+pub mod reset_password;
+pub use self::reset_password::*;
+
+pub mod set_stores;
+pub use self::set_stores::*;
+
+mod validate;
+
+/// Management of users that are created on this site.
+///
+/// Local users are not known to the central server, i.e. they can log in while the site is offline
+/// and they are not replaced by the user data fetched from the central server during login.
+pub trait LocalUserServiceTrait: Sync + Send {
+    fn insert_local_user(
+        &self,
+        ctx: &ServiceContext,
+        input: InsertLocalUser,
+    ) -> Result<User, InsertLocalUserError> {
+        insert_local_user(ctx, input)
+    }
+
+    fn update_local_user(
+        &self,
+        ctx: &ServiceContext,
+        input: UpdateLocalUser,
+    ) -> Result<User, UpdateLocalUserError> {
+        update_local_user(ctx, input)
+    }
+
+    fn reset_local_user_password(
+        &self,
+        ctx: &ServiceContext,
+        input: ResetLocalUserPassword,
+    ) -> Result<User, ResetLocalUserPasswordError> {
+        reset_local_user_password(ctx, input)
+    }
+
+    fn set_local_user_stores(
+        &self,
+        ctx: &ServiceContext,
+        input: SetLocalUserStores,
+    ) -> Result<User, SetLocalUserStoresError> {
+        set_local_user_stores(ctx, input)
+    }
+}
+
+pub struct LocalUserService {}
+impl LocalUserServiceTrait for LocalUserService {}
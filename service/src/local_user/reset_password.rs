@@ -0,0 +1,156 @@
+use super::validate::{check_user_exists, get_user};
+use crate::{service_provider::ServiceContext, user_account::UserAccountService};
+use repository::{
+    RepositoryError, StorageConnection, User, UserAccountRow, UserAccountRowRepository,
+};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct ResetLocalUserPassword {
+    pub id: String,
+    /// Plain text password
+    pub password: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ResetLocalUserPasswordError {
+    UserDoesNotExist,
+    NotALocalUser,
+    EmptyPassword,
+    FailedToHashPassword(String),
+    UpdatedUserDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = ResetLocalUserPasswordError;
+
+pub fn reset_local_user_password(
+    ctx: &ServiceContext,
+    input: ResetLocalUserPassword,
+) -> Result<User, OutError> {
+    let user = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let user_row = validate(connection, &input)?;
+            let updated_user_row = generate(input, user_row)?;
+            UserAccountRowRepository::new(connection).update_one(&updated_user_row)?;
+
+            get_user(connection, &updated_user_row.id)?.ok_or(OutError::UpdatedUserDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(user)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    input: &ResetLocalUserPassword,
+) -> Result<UserAccountRow, OutError> {
+    let user_row = check_user_exists(connection, &input.id)?.ok_or(OutError::UserDoesNotExist)?;
+    if !user_row.is_local {
+        return Err(OutError::NotALocalUser);
+    }
+    if input.password.is_empty() {
+        return Err(OutError::EmptyPassword);
+    }
+
+    Ok(user_row)
+}
+
+fn generate(
+    ResetLocalUserPassword { id: _, password }: ResetLocalUserPassword,
+    mut user_row: UserAccountRow,
+) -> Result<UserAccountRow, OutError> {
+    user_row.hashed_password = UserAccountService::hash_password(&password)
+        .map_err(|err| OutError::FailedToHashPassword(format!("{:?}", err)))?;
+    Ok(user_row)
+}
+
+impl From<RepositoryError> for ResetLocalUserPasswordError {
+    fn from(error: RepositoryError) -> Self {
+        ResetLocalUserPasswordError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_user_account_a, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        UserAccountRow,
+    };
+    use util::inline_init;
+
+    use crate::{
+        local_user::{ResetLocalUserPassword, ResetLocalUserPasswordError as ServiceError},
+        service_provider::ServiceProvider,
+        user_account::{UserAccountService, VerifyPasswordError},
+    };
+
+    fn local_user() -> UserAccountRow {
+        inline_init(|r: &mut UserAccountRow| {
+            r.id = "local_user".to_owned();
+            r.username = "local_username".to_owned();
+            r.hashed_password = UserAccountService::hash_password("old_password").unwrap();
+            r.is_local = true;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn reset_local_user_password() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "reset_local_user_password",
+            MockDataInserts::none().user_accounts(),
+            inline_init(|r: &mut MockData| {
+                r.user_accounts = vec![local_user()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.local_user_service;
+
+        // NotALocalUser
+        assert_eq!(
+            service.reset_local_user_password(
+                &context,
+                ResetLocalUserPassword {
+                    id: mock_user_account_a().id,
+                    password: "password".to_owned(),
+                },
+            ),
+            Err(ServiceError::NotALocalUser)
+        );
+
+        // EmptyPassword
+        assert_eq!(
+            service.reset_local_user_password(
+                &context,
+                ResetLocalUserPassword {
+                    id: local_user().id,
+                    password: "".to_owned(),
+                },
+            ),
+            Err(ServiceError::EmptyPassword)
+        );
+
+        // Success
+        service
+            .reset_local_user_password(
+                &context,
+                ResetLocalUserPassword {
+                    id: local_user().id,
+                    password: "new_password".to_owned(),
+                },
+            )
+            .unwrap();
+
+        let user_service = UserAccountService::new(&connection);
+        user_service
+            .verify_password(&local_user().username, "new_password")
+            .unwrap();
+        assert!(matches!(
+            user_service.verify_password(&local_user().username, "old_password"),
+            Err(VerifyPasswordError::InvalidCredentials)
+        ));
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::HashSet;
+
+use super::validate::{check_user_exists, get_user};
+use crate::{service_provider::ServiceContext, validate::check_store_exists};
+use repository::{
+    Permission, RepositoryError, StorageConnection, User, UserPermissionRow,
+    UserPermissionRowRepository, UserStoreJoinRow, UserStoreJoinRowRepository,
+};
+use util::uuid::uuid;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct LocalUserStore {
+    pub store_id: String,
+    pub is_default: bool,
+    /// Permissions of the user in this store. The user always gets access to the store, i.e. the
+    /// StoreAccess permission is added automatically.
+    pub permissions: Vec<Permission>,
+}
+
+/// Replaces all store joins and permissions of a local user
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct SetLocalUserStores {
+    pub user_id: String,
+    pub stores: Vec<LocalUserStore>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SetLocalUserStoresError {
+    UserDoesNotExist,
+    NotALocalUser,
+    StoreDoesNotExist(String),
+    DuplicateStore(String),
+    MoreThanOneDefaultStore,
+    UpdatedUserDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = SetLocalUserStoresError;
+
+pub fn set_local_user_stores(
+    ctx: &ServiceContext,
+    input: SetLocalUserStores,
+) -> Result<User, OutError> {
+    let user = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(connection, &input)?;
+            let user_id = input.user_id.clone();
+            let (user_store_joins, permissions) = generate(input);
+
+            let permission_repo = UserPermissionRowRepository::new(connection);
+            let user_store_repo = UserStoreJoinRowRepository::new(connection);
+            permission_repo.delete_by_user_id(&user_id)?;
+            user_store_repo.delete_by_user_id(&user_id)?;
+            for user_store_join in user_store_joins {
+                user_store_repo.upsert_one(&user_store_join)?;
+            }
+            for permission in permissions {
+                permission_repo.upsert_one(&permission)?;
+            }
+
+            get_user(connection, &user_id)?.ok_or(OutError::UpdatedUserDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(user)
+}
+
+fn validate(connection: &StorageConnection, input: &SetLocalUserStores) -> Result<(), OutError> {
+    let user_row =
+        check_user_exists(connection, &input.user_id)?.ok_or(OutError::UserDoesNotExist)?;
+    if !user_row.is_local {
+        return Err(OutError::NotALocalUser);
+    }
+
+    let mut store_ids = HashSet::new();
+    for store in &input.stores {
+        if !store_ids.insert(store.store_id.clone()) {
+            return Err(OutError::DuplicateStore(store.store_id.clone()));
+        }
+        if !check_store_exists(connection, &store.store_id)? {
+            return Err(OutError::StoreDoesNotExist(store.store_id.clone()));
+        }
+    }
+
+    if input.stores.iter().filter(|store| store.is_default).count() > 1 {
+        return Err(OutError::MoreThanOneDefaultStore);
+    }
+
+    Ok(())
+}
+
+fn generate(
+    SetLocalUserStores { user_id, stores }: SetLocalUserStores,
+) -> (Vec<UserStoreJoinRow>, Vec<UserPermissionRow>) {
+    let mut user_store_joins = Vec::new();
+    let mut permission_rows = Vec::new();
+    for LocalUserStore {
+        store_id,
+        is_default,
+        permissions,
+    } in stores
+    {
+        let mut permission_set: HashSet<Permission> = permissions.into_iter().collect();
+        permission_set.insert(Permission::StoreAccess);
+        permission_rows.extend(
+            permission_set
+                .into_iter()
+                .map(|permission| UserPermissionRow {
+                    id: uuid(),
+                    user_id: user_id.clone(),
+                    store_id: Some(store_id.clone()),
+                    permission,
+                }),
+        );
+
+        user_store_joins.push(UserStoreJoinRow {
+            id: uuid(),
+            user_id: user_id.clone(),
+            store_id,
+            is_default,
+        });
+    }
+
+    (user_store_joins, permission_rows)
+}
+
+impl From<RepositoryError> for SetLocalUserStoresError {
+    fn from(error: RepositoryError) -> Self {
+        SetLocalUserStoresError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_store_a, mock_store_b, mock_user_account_a, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        EqualFilter, Permission, UserAccountRow, UserPermissionFilter, UserPermissionRepository,
+    };
+    use util::inline_init;
+
+    use crate::{
+        local_user::{LocalUserStore, SetLocalUserStores, SetLocalUserStoresError as ServiceError},
+        service_provider::ServiceProvider,
+    };
+
+    fn local_user() -> UserAccountRow {
+        inline_init(|r: &mut UserAccountRow| {
+            r.id = "local_user".to_owned();
+            r.username = "local_username".to_owned();
+            r.hashed_password = "n/a".to_owned();
+            r.is_local = true;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn set_local_user_stores_errors() {
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "set_local_user_stores_errors",
+            MockDataInserts::none().names().stores().user_accounts(),
+            inline_init(|r: &mut MockData| {
+                r.user_accounts = vec![local_user()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.local_user_service;
+
+        // NotALocalUser
+        assert_eq!(
+            service.set_local_user_stores(
+                &context,
+                SetLocalUserStores {
+                    user_id: mock_user_account_a().id,
+                    stores: vec![],
+                },
+            ),
+            Err(ServiceError::NotALocalUser)
+        );
+
+        // StoreDoesNotExist
+        assert_eq!(
+            service.set_local_user_stores(
+                &context,
+                SetLocalUserStores {
+                    user_id: local_user().id,
+                    stores: vec![inline_init(|r: &mut LocalUserStore| {
+                        r.store_id = "invalid".to_owned();
+                    })],
+                },
+            ),
+            Err(ServiceError::StoreDoesNotExist("invalid".to_owned()))
+        );
+
+        // DuplicateStore
+        assert_eq!(
+            service.set_local_user_stores(
+                &context,
+                SetLocalUserStores {
+                    user_id: local_user().id,
+                    stores: vec![
+                        inline_init(|r: &mut LocalUserStore| {
+                            r.store_id = mock_store_a().id;
+                        }),
+                        inline_init(|r: &mut LocalUserStore| {
+                            r.store_id = mock_store_a().id;
+                        })
+                    ],
+                },
+            ),
+            Err(ServiceError::DuplicateStore(mock_store_a().id))
+        );
+
+        // MoreThanOneDefaultStore
+        assert_eq!(
+            service.set_local_user_stores(
+                &context,
+                SetLocalUserStores {
+                    user_id: local_user().id,
+                    stores: vec![
+                        inline_init(|r: &mut LocalUserStore| {
+                            r.store_id = mock_store_a().id;
+                            r.is_default = true;
+                        }),
+                        inline_init(|r: &mut LocalUserStore| {
+                            r.store_id = mock_store_b().id;
+                            r.is_default = true;
+                        })
+                    ],
+                },
+            ),
+            Err(ServiceError::MoreThanOneDefaultStore)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn set_local_user_stores_success() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "set_local_user_stores_success",
+            MockDataInserts::none().names().stores().user_accounts(),
+            inline_init(|r: &mut MockData| {
+                r.user_accounts = vec![local_user()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.local_user_service;
+
+        let user = service
+            .set_local_user_stores(
+                &context,
+                SetLocalUserStores {
+                    user_id: local_user().id,
+                    stores: vec![
+                        LocalUserStore {
+                            store_id: mock_store_a().id,
+                            is_default: true,
+                            permissions: vec![Permission::StocktakeQuery],
+                        },
+                        LocalUserStore {
+                            store_id: mock_store_b().id,
+                            is_default: false,
+                            permissions: vec![],
+                        },
+                    ],
+                },
+            )
+            .unwrap();
+        assert_eq!(user.stores.len(), 2);
+        assert_eq!(
+            user.default_store().unwrap().store_row.id,
+            mock_store_a().id
+        );
+
+        let permission_repo = UserPermissionRepository::new(&connection);
+        let mut store_a_permissions: Vec<Permission> = permission_repo
+            .query_by_filter(
+                UserPermissionFilter::new()
+                    .user_id(EqualFilter::equal_to(&local_user().id))
+                    .store_id(EqualFilter::equal_to(&mock_store_a().id)),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|row| row.permission)
+            .collect();
+        store_a_permissions.sort_by_key(|permission| format!("{:?}", permission));
+        assert_eq!(
+            store_a_permissions,
+            vec![Permission::StocktakeQuery, Permission::StoreAccess]
+        );
+
+        // Existing stores are replaced
+        let user = service
+            .set_local_user_stores(
+                &context,
+                SetLocalUserStores {
+                    user_id: local_user().id,
+                    stores: vec![LocalUserStore {
+                        store_id: mock_store_b().id,
+                        is_default: true,
+                        permissions: vec![],
+                    }],
+                },
+            )
+            .unwrap();
+        assert_eq!(user.stores.len(), 1);
+        let permissions = permission_repo
+            .query_by_filter(
+                UserPermissionFilter::new().user_id(EqualFilter::equal_to(&local_user().id)),
+            )
+            .unwrap();
+        assert_eq!(permissions.len(), 1);
+        assert_eq!(permissions[0].store_id, Some(mock_store_b().id));
+    }
+}
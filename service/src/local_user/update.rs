@@ -0,0 +1,201 @@
+use super::validate::{check_user_exists, check_username_is_unique, get_user};
+use crate::service_provider::ServiceContext;
+use repository::{
+    RepositoryError, StorageConnection, User, UserAccountRow, UserAccountRowRepository,
+};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct UpdateLocalUser {
+    pub id: String,
+    pub username: Option<String>,
+    pub email: Option<String>,
+    /// Disabled users can't log in and existing tokens of the user are rejected
+    pub is_disabled: Option<bool>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateLocalUserError {
+    UserDoesNotExist,
+    NotALocalUser,
+    UsernameAlreadyExists,
+    EmptyUsername,
+    UpdatedUserDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = UpdateLocalUserError;
+
+pub fn update_local_user(ctx: &ServiceContext, input: UpdateLocalUser) -> Result<User, OutError> {
+    let user = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let user_row = validate(connection, &input)?;
+            let updated_user_row = generate(input, user_row);
+            UserAccountRowRepository::new(connection).update_one(&updated_user_row)?;
+
+            get_user(connection, &updated_user_row.id)?.ok_or(OutError::UpdatedUserDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(user)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    input: &UpdateLocalUser,
+) -> Result<UserAccountRow, OutError> {
+    let user_row = check_user_exists(connection, &input.id)?.ok_or(OutError::UserDoesNotExist)?;
+    if !user_row.is_local {
+        return Err(OutError::NotALocalUser);
+    }
+
+    if let Some(username) = &input.username {
+        if username.trim().is_empty() {
+            return Err(OutError::EmptyUsername);
+        }
+        if !check_username_is_unique(connection, &input.id, username)? {
+            return Err(OutError::UsernameAlreadyExists);
+        }
+    }
+
+    Ok(user_row)
+}
+
+fn generate(
+    UpdateLocalUser {
+        id: _,
+        username,
+        email,
+        is_disabled,
+    }: UpdateLocalUser,
+    mut user_row: UserAccountRow,
+) -> UserAccountRow {
+    user_row.username = username.unwrap_or(user_row.username);
+    user_row.email = email.or(user_row.email);
+    user_row.is_disabled = is_disabled.unwrap_or(user_row.is_disabled);
+    user_row
+}
+
+impl From<RepositoryError> for UpdateLocalUserError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateLocalUserError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_user_account_a, mock_user_account_b, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        UserAccountRow, UserAccountRowRepository,
+    };
+    use util::inline_init;
+
+    use crate::{
+        local_user::{UpdateLocalUser, UpdateLocalUserError as ServiceError},
+        service_provider::ServiceProvider,
+    };
+
+    fn local_user() -> UserAccountRow {
+        inline_init(|r: &mut UserAccountRow| {
+            r.id = "local_user".to_owned();
+            r.username = "local_username".to_owned();
+            r.hashed_password = "n/a".to_owned();
+            r.is_local = true;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn update_local_user_errors() {
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "update_local_user_errors",
+            MockDataInserts::none().user_accounts(),
+            inline_init(|r: &mut MockData| {
+                r.user_accounts = vec![local_user()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.local_user_service;
+
+        // UserDoesNotExist
+        assert_eq!(
+            service.update_local_user(
+                &context,
+                inline_init(|r: &mut UpdateLocalUser| {
+                    r.id = "invalid".to_owned();
+                }),
+            ),
+            Err(ServiceError::UserDoesNotExist)
+        );
+
+        // NotALocalUser
+        assert_eq!(
+            service.update_local_user(
+                &context,
+                inline_init(|r: &mut UpdateLocalUser| {
+                    r.id = mock_user_account_a().id;
+                    r.is_disabled = Some(true);
+                }),
+            ),
+            Err(ServiceError::NotALocalUser)
+        );
+
+        // UsernameAlreadyExists
+        assert_eq!(
+            service.update_local_user(
+                &context,
+                inline_init(|r: &mut UpdateLocalUser| {
+                    r.id = local_user().id;
+                    r.username = Some(mock_user_account_b().username);
+                }),
+            ),
+            Err(ServiceError::UsernameAlreadyExists)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn update_local_user_success() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "update_local_user_success",
+            MockDataInserts::none().user_accounts(),
+            inline_init(|r: &mut MockData| {
+                r.user_accounts = vec![local_user()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.local_user_service;
+
+        service
+            .update_local_user(
+                &context,
+                UpdateLocalUser {
+                    id: local_user().id,
+                    username: Some("new_username".to_owned()),
+                    email: Some("new@openmsupply.foundation".to_owned()),
+                    is_disabled: Some(true),
+                },
+            )
+            .unwrap();
+
+        let user_row = UserAccountRowRepository::new(&connection)
+            .find_one_by_id(&local_user().id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            user_row,
+            inline_init(|r: &mut UserAccountRow| {
+                r.id = local_user().id;
+                r.username = "new_username".to_owned();
+                r.hashed_password = local_user().hashed_password;
+                r.email = Some("new@openmsupply.foundation".to_owned());
+                r.is_local = true;
+                r.is_disabled = true;
+            })
+        );
+    }
+}
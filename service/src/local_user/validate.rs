@@ -0,0 +1,28 @@
+use repository::{
+    EqualFilter, RepositoryError, StorageConnection, User, UserAccountRow,
+    UserAccountRowRepository, UserFilter, UserRepository,
+};
+
+pub fn check_user_exists(
+    connection: &StorageConnection,
+    id: &str,
+) -> Result<Option<UserAccountRow>, RepositoryError> {
+    UserAccountRowRepository::new(connection).find_one_by_id(id)
+}
+
+/// Usernames are used to log in and thus need to be unique across local and central users
+pub fn check_username_is_unique(
+    connection: &StorageConnection,
+    id: &str,
+    username: &str,
+) -> Result<bool, RepositoryError> {
+    let existing = UserAccountRowRepository::new(connection).find_one_by_user_name(username)?;
+    Ok(match existing {
+        Some(user) => user.id == id,
+        None => true,
+    })
+}
+
+pub fn get_user(connection: &StorageConnection, id: &str) -> Result<Option<User>, RepositoryError> {
+    UserRepository::new(connection).query_one(UserFilter::new().id(EqualFilter::equal_to(id)))
+}
@@ -2,8 +2,9 @@ use super::validate::check_location_exists;
 use crate::service_provider::ServiceContext;
 use repository::EqualFilter;
 use repository::{
-    InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, LocationRowRepository, RepositoryError,
-    StockLine, StockLineFilter, StockLineRepository, StorageConnection,
+    InvoiceLine, InvoiceLineFilter, InvoiceLineRepository, LocationFilter, LocationRepository,
+    LocationRowRepository, RepositoryError, StockLine, StockLineFilter, StockLineRepository,
+    StorageConnection,
 };
 #[derive(PartialEq, Debug)]
 pub struct LocationInUse {
@@ -16,6 +17,7 @@ pub enum DeleteLocationError {
     LocationDoesNotExist,
     LocationDoesNotBelongToCurrentStore,
     LocationInUse(LocationInUse),
+    LocationHasSubLocations,
     DatabaseError(RepositoryError),
 }
 
@@ -56,6 +58,9 @@ pub fn validate(
     if let Some(location_in_use) = check_location_in_use(&input.id, connection)? {
         return Err(DeleteLocationError::LocationInUse(location_in_use));
     }
+    if check_location_has_sub_locations(&input.id, connection)? {
+        return Err(DeleteLocationError::LocationHasSubLocations);
+    }
 
     Ok(())
 }
@@ -79,6 +84,17 @@ pub fn check_location_in_use(
     }
 }
 
+pub fn check_location_has_sub_locations(
+    id: &str,
+    connection: &StorageConnection,
+) -> Result<bool, RepositoryError> {
+    let count = LocationRepository::new(connection).count(Some(
+        LocationFilter::new().parent_location_id(EqualFilter::equal_to(id)),
+    ))?;
+
+    Ok(count > 0)
+}
+
 impl From<RepositoryError> for DeleteLocationError {
     fn from(error: RepositoryError) -> Self {
         DeleteLocationError::DatabaseError(error)
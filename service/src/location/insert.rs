@@ -1,4 +1,7 @@
-use super::{query::get_location, validate::check_location_code_is_unique};
+use super::{
+    query::get_location,
+    validate::{check_location_code_is_unique, check_location_exists, check_temperature_range},
+};
 use crate::{service_provider::ServiceContext, SingleRecordError};
 use repository::EqualFilter;
 use repository::{
@@ -10,6 +13,10 @@ use repository::{
 pub enum InsertLocationError {
     LocationAlreadyExists,
     LocationWithCodeAlreadyExists,
+    ParentLocationDoesNotExist,
+    ParentLocationDoesNotBelongToCurrentStore,
+    CapacityBelowZero,
+    InvalidTemperatureRange,
     CreatedRecordNotFound,
     DatabaseError(RepositoryError),
 }
@@ -19,6 +26,11 @@ pub struct InsertLocation {
     pub code: String,
     pub name: Option<String>,
     pub on_hold: Option<bool>,
+    pub parent_location_id: Option<String>,
+    /// Maximum number of packs that can be stored in the location
+    pub capacity: Option<i32>,
+    pub min_temperature: Option<f64>,
+    pub max_temperature: Option<f64>,
 }
 
 pub fn insert_location(
@@ -29,7 +41,7 @@ pub fn insert_location(
     let location = ctx
         .connection
         .transaction_sync(|connection| {
-            validate(&input, connection)?;
+            validate(&input, store_id, connection)?;
             let new_location = generate(store_id, input);
             LocationRowRepository::new(&connection).upsert_one(&new_location)?;
 
@@ -41,6 +53,7 @@ pub fn insert_location(
 
 pub fn validate(
     input: &InsertLocation,
+    store_id: &str,
     connection: &StorageConnection,
 ) -> Result<(), InsertLocationError> {
     if !check_location_does_not_exist(&input.id, connection)? {
@@ -49,6 +62,21 @@ pub fn validate(
     if !check_location_code_is_unique(&input.id, Some(input.code.clone()), connection)? {
         return Err(InsertLocationError::LocationWithCodeAlreadyExists);
     }
+    if let Some(parent_location_id) = &input.parent_location_id {
+        let parent = match check_location_exists(parent_location_id, connection)? {
+            Some(parent) => parent,
+            None => return Err(InsertLocationError::ParentLocationDoesNotExist),
+        };
+        if parent.store_id != store_id {
+            return Err(InsertLocationError::ParentLocationDoesNotBelongToCurrentStore);
+        }
+    }
+    if input.capacity.unwrap_or(0) < 0 {
+        return Err(InsertLocationError::CapacityBelowZero);
+    }
+    if !check_temperature_range(input.min_temperature, input.max_temperature) {
+        return Err(InsertLocationError::InvalidTemperatureRange);
+    }
 
     Ok(())
 }
@@ -60,6 +88,10 @@ pub fn generate(
         code,
         name,
         on_hold,
+        parent_location_id,
+        capacity,
+        min_temperature,
+        max_temperature,
     }: InsertLocation,
 ) -> LocationRow {
     LocationRow {
@@ -68,6 +100,10 @@ pub fn generate(
         code,
         on_hold: on_hold.unwrap_or(false),
         store_id: store_id.to_string(),
+        parent_location_id,
+        capacity,
+        min_temperature,
+        max_temperature,
     }
 }
 
@@ -1,6 +1,7 @@
 use self::{
     delete::{delete_location, DeleteLocation, DeleteLocationError},
     insert::{insert_location, InsertLocation, InsertLocationError},
+    move_stock::{move_stock, MoveStock, MoveStockError},
     query::{get_location, get_locations},
     update::{update_location, UpdateLocation, UpdateLocationError},
 };
@@ -8,10 +9,11 @@ use self::{
 use super::{ListError, ListResult};
 use crate::{service_provider::ServiceContext, SingleRecordError};
 use repository::PaginationOption;
-use repository::{Location, LocationFilter, LocationSort};
+use repository::{Invoice, Location, LocationFilter, LocationSort};
 
 pub mod delete;
 pub mod insert;
+pub mod move_stock;
 pub mod query;
 pub mod update;
 mod validate;
@@ -61,6 +63,16 @@ pub trait LocationServiceTrait: Sync + Send {
     ) -> Result<Location, UpdateLocationError> {
         update_location(ctx, store_id, input)
     }
+
+    fn move_stock(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        input: MoveStock,
+    ) -> Result<Invoice, MoveStockError> {
+        move_stock(ctx, store_id, user_id, input)
+    }
 }
 
 pub struct LocationService {}
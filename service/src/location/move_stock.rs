@@ -0,0 +1,713 @@
+use super::validate::{check_location_exists, location_and_sub_location_ids};
+use crate::invoice::{check_invoice_exists_option, query::get_invoice};
+use crate::number::next_number;
+use crate::service_provider::ServiceContext;
+use chrono::Utc;
+use repository::{
+    EqualFilter, Invoice, InvoiceLineRow, InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRow,
+    InvoiceRowRepository, InvoiceRowStatus, InvoiceRowType, ItemRowRepository, LocationRow,
+    NameRowRepository, NumberRowType, Pagination, RepositoryError, StockLineFilter,
+    StockLineRepository, StockLineRow, StockLineRowRepository, StorageConnection,
+};
+use std::collections::HashSet;
+use util::{constants::INVENTORY_ADJUSTMENT_NAME_CODE, uuid::uuid};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MoveStock {
+    /// The id of the invoice recording the move
+    pub id: String,
+    pub stock_line_id: String,
+    pub location_id: String,
+    pub number_of_packs: i32,
+    pub comment: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MoveStockError {
+    InvoiceAlreadyExists,
+    StockLineDoesNotExist,
+    StockLineDoesNotBelongToCurrentStore,
+    LocationDoesNotExist,
+    LocationDoesNotBelongToCurrentStore,
+    StockLineAlreadyInLocation,
+    /// Stock can't be moved into a location that is on hold
+    LocationIsOnHold,
+    /// Stock in a location that is on hold can't be moved out of it
+    StockLineLocationIsOnHold,
+    NumberOfPacksBelowOne,
+    /// Only available stock can be moved, i.e. stock that isn't allocated to other invoices
+    NumberOfPacksExceedsAvailable {
+        available_number_of_packs: i32,
+    },
+    /// The capacity of a location covers the stock in the location and all of its sub locations,
+    /// so the exceeded location can be the destination or one of its parents
+    LocationCapacityExceeded {
+        location_id: String,
+        capacity: i32,
+        number_of_packs_in_location: i32,
+    },
+    // Internal
+    NewlyCreatedInvoiceDoesNotExist,
+    DatabaseError(RepositoryError),
+    InternalError(String),
+}
+
+type OutError = MoveStockError;
+
+struct GenerateResult {
+    invoice: InvoiceRow,
+    lines: Vec<InvoiceLineRow>,
+    stock_lines: Vec<StockLineRow>,
+}
+
+/// Moves packs of a stock line to another location. Moving part of the available packs splits the
+/// stock line, with the moved packs going to a new stock line in the destination location.
+/// The move is recorded as a verified inventory adjustment invoice with a stock out line for the
+/// source location and a stock in line for the destination, so it shows up in the stock ledger.
+pub fn move_stock(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    input: MoveStock,
+) -> Result<Invoice, OutError> {
+    let invoice = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (stock_line, location) = validate(connection, store_id, &input)?;
+            let GenerateResult {
+                invoice,
+                lines,
+                stock_lines,
+            } = generate(connection, store_id, user_id, stock_line, location, input)?;
+
+            InvoiceRowRepository::new(connection).upsert_one(&invoice)?;
+            let stock_line_repository = StockLineRowRepository::new(connection);
+            for stock_line in stock_lines {
+                stock_line_repository.upsert_one(&stock_line)?;
+            }
+            let invoice_line_repository = InvoiceLineRowRepository::new(connection);
+            for line in lines {
+                invoice_line_repository.upsert_one(&line)?;
+            }
+
+            get_invoice(ctx, None, &invoice.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::NewlyCreatedInvoiceDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(invoice)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &MoveStock,
+) -> Result<(StockLineRow, LocationRow), OutError> {
+    use MoveStockError::*;
+
+    if check_invoice_exists_option(&input.id, connection)?.is_some() {
+        return Err(InvoiceAlreadyExists);
+    }
+
+    let stock_line =
+        match StockLineRowRepository::new(connection).find_one_by_id(&input.stock_line_id) {
+            Ok(stock_line) => stock_line,
+            Err(RepositoryError::NotFound) => return Err(StockLineDoesNotExist),
+            Err(error) => return Err(error.into()),
+        };
+    if stock_line.store_id != store_id {
+        return Err(StockLineDoesNotBelongToCurrentStore);
+    }
+
+    let location = match check_location_exists(&input.location_id, connection)? {
+        Some(location) => location,
+        None => return Err(LocationDoesNotExist),
+    };
+    if location.store_id != store_id {
+        return Err(LocationDoesNotBelongToCurrentStore);
+    }
+    if stock_line.location_id.as_ref() == Some(&location.id) {
+        return Err(StockLineAlreadyInLocation);
+    }
+    if location.on_hold {
+        return Err(LocationIsOnHold);
+    }
+    if let Some(source_location_id) = &stock_line.location_id {
+        let source_location_on_hold = check_location_exists(source_location_id, connection)?
+            .map(|location| location.on_hold)
+            .unwrap_or(false);
+        if source_location_on_hold {
+            return Err(StockLineLocationIsOnHold);
+        }
+    }
+
+    if input.number_of_packs < 1 {
+        return Err(NumberOfPacksBelowOne);
+    }
+    if input.number_of_packs > stock_line.available_number_of_packs {
+        return Err(NumberOfPacksExceedsAvailable {
+            available_number_of_packs: stock_line.available_number_of_packs,
+        });
+    }
+
+    check_location_capacity(connection, &stock_line, &location, input.number_of_packs)?;
+
+    Ok((stock_line, location))
+}
+
+/// Checks the capacity of the destination and of each of its parents. Parents that already hold
+/// the stock line in one of their sub locations are skipped, their total doesn't change.
+fn check_location_capacity(
+    connection: &StorageConnection,
+    stock_line: &StockLineRow,
+    destination: &LocationRow,
+    number_of_packs: i32,
+) -> Result<(), OutError> {
+    let mut visited = HashSet::new();
+    let mut current = Some(destination.clone());
+
+    while let Some(location) = current {
+        // Guard against existing cycles in the data
+        if !visited.insert(location.id.clone()) {
+            break;
+        }
+
+        if let Some(capacity) = location.capacity {
+            let location_ids = location_and_sub_location_ids(&location.id, connection)?;
+            let already_in_location = stock_line
+                .location_id
+                .as_ref()
+                .map(|location_id| location_ids.contains(location_id))
+                .unwrap_or(false);
+
+            if !already_in_location {
+                let number_of_packs_in_location: i32 = StockLineRepository::new(connection)
+                    .query(
+                        Pagination::all(),
+                        Some(
+                            StockLineFilter::new()
+                                .location_id(EqualFilter::equal_any(location_ids)),
+                        ),
+                        None,
+                    )?
+                    .iter()
+                    .map(|stock_line| stock_line.stock_line_row.total_number_of_packs)
+                    .sum();
+                if number_of_packs_in_location + number_of_packs > capacity {
+                    return Err(OutError::LocationCapacityExceeded {
+                        location_id: location.id,
+                        capacity,
+                        number_of_packs_in_location,
+                    });
+                }
+            }
+        }
+
+        current = match &location.parent_location_id {
+            Some(parent_id) => check_location_exists(parent_id, connection)?,
+            None => None,
+        };
+    }
+
+    Ok(())
+}
+
+fn generate(
+    connection: &StorageConnection,
+    store_id: &str,
+    user_id: &str,
+    stock_line: StockLineRow,
+    location: LocationRow,
+    MoveStock {
+        id,
+        stock_line_id: _,
+        location_id: _,
+        number_of_packs,
+        comment,
+    }: MoveStock,
+) -> Result<GenerateResult, OutError> {
+    let inventory_adjustment_name = NameRowRepository::new(connection)
+        .find_one_by_code(INVENTORY_ADJUSTMENT_NAME_CODE)?
+        .ok_or(OutError::InternalError(
+            "Missing inventory adjustment name".to_string(),
+        ))?;
+    let item = ItemRowRepository::new(connection)
+        .find_one_by_id(&stock_line.item_id)?
+        .ok_or(OutError::InternalError(format!(
+            "Can't find item {} for stock line {}",
+            stock_line.item_id, stock_line.id
+        )))?;
+
+    let now = Utc::now().naive_utc();
+    let invoice = InvoiceRow {
+        id: id.clone(),
+        user_id: Some(user_id.to_string()),
+        name_id: inventory_adjustment_name.id,
+        store_id: store_id.to_string(),
+        invoice_number: next_number(connection, &NumberRowType::InventoryAdjustment, store_id)?,
+        r#type: InvoiceRowType::InventoryAdjustment,
+        status: InvoiceRowStatus::Verified,
+        comment: comment.or(Some(format!("Stock moved to location {}", location.code))),
+        created_datetime: now,
+        verified_datetime: Some(now),
+        ..Default::default()
+    };
+
+    // Moving all of the stock line relocates it, otherwise the moved packs are split off into a
+    // new stock line
+    let stock_lines = if number_of_packs == stock_line.total_number_of_packs {
+        vec![StockLineRow {
+            location_id: Some(location.id.clone()),
+            ..stock_line.clone()
+        }]
+    } else {
+        vec![
+            StockLineRow {
+                available_number_of_packs: stock_line.available_number_of_packs - number_of_packs,
+                total_number_of_packs: stock_line.total_number_of_packs - number_of_packs,
+                ..stock_line.clone()
+            },
+            StockLineRow {
+                id: uuid(),
+                location_id: Some(location.id.clone()),
+                available_number_of_packs: number_of_packs,
+                total_number_of_packs: number_of_packs,
+                ..stock_line.clone()
+            },
+        ]
+    };
+    let destination_stock_line_id = stock_lines[stock_lines.len() - 1].id.clone();
+
+    let line = |r#type: InvoiceLineRowType,
+                stock_line_id: String,
+                location_id: Option<String>|
+     -> InvoiceLineRow {
+        InvoiceLineRow {
+            id: uuid(),
+            invoice_id: id.clone(),
+            r#type,
+            item_id: stock_line.item_id.clone(),
+            item_name: item.name.clone(),
+            item_code: item.code.clone(),
+            stock_line_id: Some(stock_line_id),
+            location_id,
+            batch: stock_line.batch.clone(),
+            expiry_date: stock_line.expiry_date,
+            pack_size: stock_line.pack_size,
+            cost_price_per_pack: stock_line.cost_price_per_pack,
+            sell_price_per_pack: stock_line.sell_price_per_pack,
+            total_before_tax: 0.0,
+            total_after_tax: 0.0,
+            tax: None,
+            number_of_packs,
+            note: stock_line.note.clone(),
+            original_invoice_line_id: None,
+            return_reason: None,
+            inventory_adjustment_reason_id: None,
+        }
+    };
+    let lines = vec![
+        line(
+            InvoiceLineRowType::StockOut,
+            stock_line.id.clone(),
+            stock_line.location_id.clone(),
+        ),
+        line(
+            InvoiceLineRowType::StockIn,
+            destination_stock_line_id,
+            Some(location.id.clone()),
+        ),
+    ];
+
+    Ok(GenerateResult {
+        invoice,
+        lines,
+        stock_lines,
+    })
+}
+
+impl From<RepositoryError> for MoveStockError {
+    fn from(error: RepositoryError) -> Self {
+        MoveStockError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_item_b_lines, mock_location_1, mock_stock_line_a, mock_store_a, MockData,
+            MockDataInserts,
+        },
+        test_db::setup_all_with_data,
+        InvoiceLineRowRepository, InvoiceLineRowType, InvoiceRowType, LocationRow, StockLineRow,
+        StockLineRowRepository,
+    };
+    use util::{inline_edit, inline_init};
+
+    use crate::{location::move_stock::MoveStock, service_provider::ServiceProvider};
+
+    use super::MoveStockError;
+
+    type ServiceError = MoveStockError;
+
+    fn shelf() -> LocationRow {
+        inline_init(|r: &mut LocationRow| {
+            r.id = "move_stock_shelf".to_string();
+            r.code = "shelf".to_string();
+            r.store_id = mock_store_a().id;
+            r.parent_location_id = Some(mock_location_1().id);
+            r.capacity = Some(10);
+        })
+    }
+
+    fn move_stock(number_of_packs: i32) -> MoveStock {
+        inline_init(|r: &mut MoveStock| {
+            r.id = "move_stock".to_string();
+            r.stock_line_id = mock_stock_line_a().id;
+            r.location_id = shelf().id;
+            r.number_of_packs = number_of_packs;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn move_stock_errors() {
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "move_stock_errors",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.locations = vec![shelf()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.location_service;
+        let store_id = mock_store_a().id;
+
+        // StockLineDoesNotExist
+        assert_eq!(
+            service.move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&move_stock(1), |mut u| {
+                    u.stock_line_id = "invalid".to_string();
+                    u
+                })
+            ),
+            Err(ServiceError::StockLineDoesNotExist)
+        );
+        // StockLineDoesNotBelongToCurrentStore
+        assert_eq!(
+            service.move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&move_stock(1), |mut u| {
+                    u.stock_line_id = mock_item_b_lines()[0].id.clone();
+                    u
+                })
+            ),
+            Err(ServiceError::StockLineDoesNotBelongToCurrentStore)
+        );
+        // LocationDoesNotExist
+        assert_eq!(
+            service.move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&move_stock(1), |mut u| {
+                    u.location_id = "invalid".to_string();
+                    u
+                })
+            ),
+            Err(ServiceError::LocationDoesNotExist)
+        );
+        // LocationDoesNotBelongToCurrentStore
+        assert_eq!(
+            service.move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&move_stock(1), |mut u| {
+                    u.location_id = "location_in_another_store".to_string();
+                    u
+                })
+            ),
+            Err(ServiceError::LocationDoesNotBelongToCurrentStore)
+        );
+        // NumberOfPacksBelowOne
+        assert_eq!(
+            service.move_stock(&context, &store_id, "n/a", move_stock(0)),
+            Err(ServiceError::NumberOfPacksBelowOne)
+        );
+        // NumberOfPacksExceedsAvailable
+        assert_eq!(
+            service.move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                move_stock(mock_stock_line_a().available_number_of_packs + 1)
+            ),
+            Err(ServiceError::NumberOfPacksExceedsAvailable {
+                available_number_of_packs: mock_stock_line_a().available_number_of_packs
+            })
+        );
+        // LocationCapacityExceeded
+        assert_eq!(
+            service.move_stock(&context, &store_id, "n/a", move_stock(11)),
+            Err(ServiceError::LocationCapacityExceeded {
+                location_id: shelf().id,
+                capacity: 10,
+                number_of_packs_in_location: 0
+            })
+        );
+        // LocationIsOnHold
+        assert_eq!(
+            service.move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&move_stock(1), |mut u| {
+                    u.location_id = "location_on_hold".to_string();
+                    u
+                })
+            ),
+            Err(ServiceError::LocationIsOnHold)
+        );
+        // StockLineAlreadyInLocation
+        service
+            .move_stock(&context, &store_id, "n/a", move_stock(1))
+            .unwrap();
+        assert_eq!(
+            service.move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&move_stock(1), |mut u| {
+                    u.id = "move_stock_2".to_string();
+                    u.stock_line_id = "stock_line_location_is_on_hold".to_string();
+                    u.location_id = "location_on_hold".to_string();
+                    u
+                })
+            ),
+            Err(ServiceError::StockLineAlreadyInLocation)
+        );
+        // InvoiceAlreadyExists
+        assert_eq!(
+            service.move_stock(&context, &store_id, "n/a", move_stock(1)),
+            Err(ServiceError::InvoiceAlreadyExists)
+        );
+        // StockLineLocationIsOnHold
+        assert_eq!(
+            service.move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                inline_edit(&move_stock(1), |mut u| {
+                    u.id = "move_stock_2".to_string();
+                    u.stock_line_id = "stock_line_location_is_on_hold".to_string();
+                    u
+                })
+            ),
+            Err(ServiceError::StockLineLocationIsOnHold)
+        );
+    }
+
+    #[actix_rt::test]
+    async fn move_stock_success() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "move_stock_success",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.locations = vec![shelf()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.location_service;
+        let store_id = mock_store_a().id;
+        let stock_line_repository = StockLineRowRepository::new(&connection);
+        let source = mock_stock_line_a();
+
+        // Partial move splits the stock line
+        let invoice = service
+            .move_stock(&context, &store_id, "n/a", move_stock(5))
+            .unwrap();
+        assert_eq!(
+            invoice.invoice_row.r#type,
+            InvoiceRowType::InventoryAdjustment
+        );
+
+        let lines = InvoiceLineRowRepository::new(&connection)
+            .find_many_by_invoice_id(&invoice.invoice_row.id)
+            .unwrap();
+        assert_eq!(lines.len(), 2);
+        let out_line = lines
+            .iter()
+            .find(|line| line.r#type == InvoiceLineRowType::StockOut)
+            .unwrap();
+        let in_line = lines
+            .iter()
+            .find(|line| line.r#type == InvoiceLineRowType::StockIn)
+            .unwrap();
+        assert_eq!(out_line.stock_line_id, Some(source.id.clone()));
+        assert_eq!(out_line.location_id, source.location_id);
+        assert_eq!(out_line.number_of_packs, 5);
+        assert_eq!(in_line.location_id, Some(shelf().id));
+        assert_eq!(in_line.number_of_packs, 5);
+
+        let updated_source = stock_line_repository.find_one_by_id(&source.id).unwrap();
+        assert_eq!(
+            updated_source.available_number_of_packs,
+            source.available_number_of_packs - 5
+        );
+        assert_eq!(
+            updated_source.total_number_of_packs,
+            source.total_number_of_packs - 5
+        );
+        assert_eq!(updated_source.location_id, source.location_id);
+
+        let new_stock_line = stock_line_repository
+            .find_one_by_id(in_line.stock_line_id.as_ref().unwrap())
+            .unwrap();
+        assert_ne!(new_stock_line.id, source.id);
+        assert_eq!(new_stock_line.location_id, Some(shelf().id));
+        assert_eq!(new_stock_line.total_number_of_packs, 5);
+        assert_eq!(new_stock_line.available_number_of_packs, 5);
+        assert_eq!(new_stock_line.batch, source.batch);
+
+        // Moving the whole stock line back relocates it
+        let invoice = service
+            .move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                MoveStock {
+                    id: "move_stock_back".to_string(),
+                    stock_line_id: new_stock_line.id.clone(),
+                    location_id: mock_location_1().id,
+                    number_of_packs: 5,
+                    comment: None,
+                },
+            )
+            .unwrap();
+        let lines = InvoiceLineRowRepository::new(&connection)
+            .find_many_by_invoice_id(&invoice.invoice_row.id)
+            .unwrap();
+        assert!(lines
+            .iter()
+            .all(|line| line.stock_line_id == Some(new_stock_line.id.clone())));
+
+        let relocated = stock_line_repository
+            .find_one_by_id(&new_stock_line.id)
+            .unwrap();
+        assert_eq!(relocated.location_id, Some(mock_location_1().id));
+        assert_eq!(relocated.total_number_of_packs, 5);
+    }
+
+    #[actix_rt::test]
+    async fn move_stock_parent_location_capacity() {
+        fn room() -> LocationRow {
+            inline_init(|r: &mut LocationRow| {
+                r.id = "move_stock_room".to_string();
+                r.code = "room".to_string();
+                r.store_id = mock_store_a().id;
+                r.capacity = Some(15);
+            })
+        }
+        fn room_shelf() -> LocationRow {
+            inline_edit(&shelf(), |mut r| {
+                r.parent_location_id = Some(room().id);
+                r
+            })
+        }
+        fn other_room_shelf() -> LocationRow {
+            inline_init(|r: &mut LocationRow| {
+                r.id = "move_stock_other_shelf".to_string();
+                r.code = "other shelf".to_string();
+                r.store_id = mock_store_a().id;
+                r.parent_location_id = Some(room().id);
+            })
+        }
+        fn other_room_shelf_stock_line() -> StockLineRow {
+            inline_edit(&mock_stock_line_a(), |mut r| {
+                r.id = "move_stock_other_shelf_line".to_string();
+                r.location_id = Some(other_room_shelf().id);
+                r.available_number_of_packs = 8;
+                r.total_number_of_packs = 8;
+                r
+            })
+        }
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "move_stock_parent_location_capacity",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.locations = vec![room(), room_shelf(), other_room_shelf()];
+                r.stock_lines = vec![other_room_shelf_stock_line()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.location_service;
+        let store_id = mock_store_a().id;
+
+        // Fits on the shelf but not in the room, which already holds the stock on the other shelf
+        assert_eq!(
+            service.move_stock(&context, &store_id, "n/a", move_stock(8)),
+            Err(ServiceError::LocationCapacityExceeded {
+                location_id: room().id,
+                capacity: 15,
+                number_of_packs_in_location: 8
+            })
+        );
+        service
+            .move_stock(&context, &store_id, "n/a", move_stock(7))
+            .unwrap();
+
+        // The room is full, but moving stock between its shelves doesn't change its total
+        service
+            .move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                MoveStock {
+                    id: "move_stock_within_room".to_string(),
+                    stock_line_id: other_room_shelf_stock_line().id,
+                    location_id: room_shelf().id,
+                    number_of_packs: 3,
+                    comment: None,
+                },
+            )
+            .unwrap();
+
+        // Shelf capacity is still checked
+        assert_eq!(
+            service.move_stock(
+                &context,
+                &store_id,
+                "n/a",
+                MoveStock {
+                    id: "move_stock_within_room_2".to_string(),
+                    stock_line_id: other_room_shelf_stock_line().id,
+                    location_id: room_shelf().id,
+                    number_of_packs: 1,
+                    comment: None,
+                },
+            ),
+            Err(ServiceError::LocationCapacityExceeded {
+                location_id: room_shelf().id,
+                capacity: 10,
+                number_of_packs_in_location: 10
+            })
+        );
+    }
+}
@@ -34,7 +34,11 @@ mod query {
                     id: mock_data["base"].locations[0].id.clone(),
                     code: "invalid".to_owned(),
                     name: None,
-                    on_hold: None
+                    on_hold: None,
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 },
             ),
             Err(InsertLocationError::LocationAlreadyExists)
@@ -48,7 +52,11 @@ mod query {
                     id: "new_id".to_owned(),
                     code: locations_in_store[0].location_row.code.clone(),
                     name: None,
-                    on_hold: None
+                    on_hold: None,
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 },
             ),
             Err(InsertLocationError::LocationWithCodeAlreadyExists)
@@ -73,6 +81,10 @@ mod query {
                 name: "new_code".to_owned(),
                 on_hold: false,
                 store_id: "store_a".to_owned(),
+                parent_location_id: None,
+                capacity: None,
+                min_temperature: None,
+                max_temperature: None,
             },
         };
 
@@ -84,7 +96,11 @@ mod query {
                     id: "new_id".to_owned(),
                     code: "new_code".to_owned(),
                     name: None,
-                    on_hold: None
+                    on_hold: None,
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 },
             ),
             Ok(result_location.clone())
@@ -111,6 +127,10 @@ mod query {
                     code: "store_b_location_code".to_owned(),
                     name: Some("new_location_name".to_owned()),
                     on_hold: Some(true),
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 },
             ),
             Ok(Location {
@@ -120,6 +140,10 @@ mod query {
                     code: "store_b_location_code".to_owned(),
                     on_hold: true,
                     store_id: "store_a".to_owned(),
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 }
             })
         );
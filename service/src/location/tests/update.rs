@@ -6,8 +6,13 @@ mod query {
     };
 
     use crate::{
-        location::update::{UpdateLocation, UpdateLocationError},
+        location::{
+            delete::{DeleteLocation, DeleteLocationError},
+            insert::InsertLocation,
+            update::{UpdateLocation, UpdateLocationError},
+        },
         service_provider::ServiceProvider,
+        NullableUpdate,
     };
 
     #[actix_rt::test]
@@ -38,7 +43,11 @@ mod query {
                     id: "invalid".to_owned(),
                     code: None,
                     name: None,
-                    on_hold: None
+                    on_hold: None,
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 },
             ),
             Err(UpdateLocationError::LocationDoesNotExist)
@@ -53,7 +62,11 @@ mod query {
                     id: locations_not_in_store[0].location_row.id.clone(),
                     code: None,
                     name: None,
-                    on_hold: None
+                    on_hold: None,
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 },
             ),
             Err(UpdateLocationError::LocationDoesNotBelongToCurrentStore)
@@ -68,7 +81,11 @@ mod query {
                     id: locations_in_store[0].location_row.id.clone(),
                     code: Some(locations_in_store[1].location_row.code.clone()),
                     name: None,
-                    on_hold: None
+                    on_hold: None,
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 },
             ),
             Err(UpdateLocationError::CodeAlreadyExists)
@@ -99,7 +116,11 @@ mod query {
                     id: location.location_row.id.clone(),
                     code: None,
                     name: None,
-                    on_hold: None
+                    on_hold: None,
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 },
             ),
             Ok(location.clone())
@@ -129,6 +150,10 @@ mod query {
                     code: Some(location.location_row.code.clone()),
                     name: Some(location.location_row.name.clone()),
                     on_hold: Some(location.location_row.on_hold),
+                    parent_location_id: None,
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
                 },
             ),
             Ok(location.clone())
@@ -143,4 +168,212 @@ mod query {
             location
         );
     }
+
+    #[actix_rt::test]
+    async fn location_service_update_hierarchy() {
+        let (_, _, connection_manager, _) =
+            setup_all("location_service_update_hierarchy", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.location_service;
+
+        let update = |id: &str, parent_location_id: &str| UpdateLocation {
+            id: id.to_owned(),
+            code: None,
+            name: None,
+            on_hold: None,
+            parent_location_id: Some(NullableUpdate {
+                value: Some(parent_location_id.to_owned()),
+            }),
+            capacity: None,
+            min_temperature: None,
+            max_temperature: None,
+        };
+
+        // Parent does not exist
+        assert_eq!(
+            service.update_location(&context, "store_a", update("location_1", "invalid")),
+            Err(UpdateLocationError::ParentLocationDoesNotExist)
+        );
+
+        // Parent in another store
+        assert_eq!(
+            service.update_location(
+                &context,
+                "store_a",
+                update("location_1", "location_in_another_store")
+            ),
+            Err(UpdateLocationError::ParentLocationDoesNotBelongToCurrentStore)
+        );
+
+        // Location as its own parent
+        assert_eq!(
+            service.update_location(&context, "store_a", update("location_1", "location_1")),
+            Err(UpdateLocationError::ParentLocationCreatesCycle)
+        );
+
+        // location_2 -> location_1, then location_1 -> location_2 would be a cycle
+        let result = service
+            .update_location(&context, "store_a", update("location_2", "location_1"))
+            .unwrap();
+        assert_eq!(
+            result.location_row.parent_location_id,
+            Some("location_1".to_owned())
+        );
+        assert_eq!(
+            service.update_location(&context, "store_a", update("location_1", "location_2")),
+            Err(UpdateLocationError::ParentLocationCreatesCycle)
+        );
+
+        // Capacity and temperature range
+        assert_eq!(
+            service.update_location(
+                &context,
+                "store_a",
+                UpdateLocation {
+                    capacity: Some(NullableUpdate { value: Some(-1) }),
+                    ..update("location_2", "location_1")
+                }
+            ),
+            Err(UpdateLocationError::CapacityBelowZero)
+        );
+        assert_eq!(
+            service.update_location(
+                &context,
+                "store_a",
+                UpdateLocation {
+                    min_temperature: Some(NullableUpdate { value: Some(8.0) }),
+                    max_temperature: Some(NullableUpdate { value: Some(2.0) }),
+                    ..update("location_2", "location_1")
+                }
+            ),
+            Err(UpdateLocationError::InvalidTemperatureRange)
+        );
+        let result = service
+            .update_location(
+                &context,
+                "store_a",
+                UpdateLocation {
+                    capacity: Some(NullableUpdate { value: Some(100) }),
+                    min_temperature: Some(NullableUpdate { value: Some(2.0) }),
+                    max_temperature: Some(NullableUpdate { value: Some(8.0) }),
+                    ..update("location_2", "location_1")
+                },
+            )
+            .unwrap();
+        assert_eq!(result.location_row.capacity, Some(100));
+        assert_eq!(result.location_row.min_temperature, Some(2.0));
+        assert_eq!(result.location_row.max_temperature, Some(8.0));
+
+        // Fields that aren't updated are kept
+        let keep = || UpdateLocation {
+            id: "location_2".to_owned(),
+            code: None,
+            name: None,
+            on_hold: None,
+            parent_location_id: None,
+            capacity: None,
+            min_temperature: None,
+            max_temperature: None,
+        };
+        let result = service
+            .update_location(
+                &context,
+                "store_a",
+                UpdateLocation {
+                    name: Some("location 2".to_owned()),
+                    ..keep()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            result.location_row.parent_location_id,
+            Some("location_1".to_owned())
+        );
+        assert_eq!(result.location_row.capacity, Some(100));
+
+        // Clearing each field, min temperature is cleared first so the range stays valid
+        fn clear<T>() -> Option<NullableUpdate<T>> {
+            Some(NullableUpdate { value: None })
+        }
+        let result = service
+            .update_location(
+                &context,
+                "store_a",
+                UpdateLocation {
+                    min_temperature: clear(),
+                    ..keep()
+                },
+            )
+            .unwrap();
+        assert_eq!(result.location_row.min_temperature, None);
+        assert_eq!(result.location_row.max_temperature, Some(8.0));
+        let result = service
+            .update_location(
+                &context,
+                "store_a",
+                UpdateLocation {
+                    max_temperature: clear(),
+                    ..keep()
+                },
+            )
+            .unwrap();
+        assert_eq!(result.location_row.max_temperature, None);
+        let result = service
+            .update_location(
+                &context,
+                "store_a",
+                UpdateLocation {
+                    capacity: clear(),
+                    ..keep()
+                },
+            )
+            .unwrap();
+        assert_eq!(result.location_row.capacity, None);
+        assert_eq!(
+            result.location_row.parent_location_id,
+            Some("location_1".to_owned())
+        );
+        let result = service
+            .update_location(
+                &context,
+                "store_a",
+                UpdateLocation {
+                    parent_location_id: clear(),
+                    ..keep()
+                },
+            )
+            .unwrap();
+        assert_eq!(result.location_row.parent_location_id, None);
+        assert_eq!(result.location_row.name, "location 2");
+
+        // Location with sub locations cannot be deleted
+        service
+            .insert_location(
+                &context,
+                "store_a",
+                InsertLocation {
+                    id: "shelf".to_owned(),
+                    code: "shelf".to_owned(),
+                    name: None,
+                    on_hold: None,
+                    parent_location_id: Some("location_2".to_owned()),
+                    capacity: None,
+                    min_temperature: None,
+                    max_temperature: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            service.delete_location(
+                &context,
+                "store_a",
+                DeleteLocation {
+                    id: "location_2".to_owned()
+                }
+            ),
+            Err(DeleteLocationError::LocationHasSubLocations)
+        );
+    }
 }
@@ -1,8 +1,11 @@
 use super::{
     query::get_location,
-    validate::{check_location_code_is_unique, check_location_exists},
+    validate::{
+        check_location_code_is_unique, check_location_exists,
+        check_parent_location_does_not_create_cycle, check_temperature_range,
+    },
 };
-use crate::{service_provider::ServiceContext, SingleRecordError};
+use crate::{service_provider::ServiceContext, NullableUpdate, SingleRecordError};
 use repository::{
     Location, LocationRow, LocationRowRepository, RepositoryError, StorageConnection,
 };
@@ -12,6 +15,12 @@ pub enum UpdateLocationError {
     LocationDoesNotExist,
     CodeAlreadyExists,
     LocationDoesNotBelongToCurrentStore,
+    ParentLocationDoesNotExist,
+    ParentLocationDoesNotBelongToCurrentStore,
+    /// Parent is the location itself or one of its sub locations
+    ParentLocationCreatesCycle,
+    CapacityBelowZero,
+    InvalidTemperatureRange,
     UpdatedRecordNotFound,
     DatabaseError(RepositoryError),
 }
@@ -21,6 +30,10 @@ pub struct UpdateLocation {
     pub code: Option<String>,
     pub name: Option<String>,
     pub on_hold: Option<bool>,
+    pub parent_location_id: Option<NullableUpdate<String>>,
+    pub capacity: Option<NullableUpdate<i32>>,
+    pub min_temperature: Option<NullableUpdate<f64>>,
+    pub max_temperature: Option<NullableUpdate<f64>>,
 }
 
 pub fn update_location(
@@ -59,6 +72,39 @@ pub fn validate(
         return Err(UpdateLocationError::LocationDoesNotBelongToCurrentStore);
     }
 
+    if let Some(NullableUpdate {
+        value: Some(parent_location_id),
+    }) = &input.parent_location_id
+    {
+        let parent = match check_location_exists(parent_location_id, connection)? {
+            Some(parent) => parent,
+            None => return Err(UpdateLocationError::ParentLocationDoesNotExist),
+        };
+        if parent.store_id != store_id {
+            return Err(UpdateLocationError::ParentLocationDoesNotBelongToCurrentStore);
+        }
+        if !check_parent_location_does_not_create_cycle(&input.id, parent_location_id, connection)?
+        {
+            return Err(UpdateLocationError::ParentLocationCreatesCycle);
+        }
+    }
+
+    if let Some(NullableUpdate {
+        value: Some(capacity),
+    }) = input.capacity
+    {
+        if capacity < 0 {
+            return Err(UpdateLocationError::CapacityBelowZero);
+        }
+    }
+
+    if !check_temperature_range(
+        updated_value(&input.min_temperature, location_row.min_temperature),
+        updated_value(&input.max_temperature, location_row.max_temperature),
+    ) {
+        return Err(UpdateLocationError::InvalidTemperatureRange);
+    }
+
     Ok(location_row)
 }
 
@@ -68,15 +114,32 @@ pub fn generate(
         code,
         name,
         on_hold,
+        parent_location_id,
+        capacity,
+        min_temperature,
+        max_temperature,
     }: UpdateLocation,
     mut location_row: LocationRow,
 ) -> LocationRow {
     location_row.code = code.unwrap_or(location_row.code);
     location_row.name = name.unwrap_or(location_row.name);
     location_row.on_hold = on_hold.unwrap_or(location_row.on_hold);
+    location_row.parent_location_id =
+        updated_value(&parent_location_id, location_row.parent_location_id);
+    location_row.capacity = updated_value(&capacity, location_row.capacity);
+    location_row.min_temperature = updated_value(&min_temperature, location_row.min_temperature);
+    location_row.max_temperature = updated_value(&max_temperature, location_row.max_temperature);
     location_row
 }
 
+/// Value of an optional field after the update, the current value is kept if there is no update
+fn updated_value<T: Clone>(update: &Option<NullableUpdate<T>>, current: Option<T>) -> Option<T> {
+    match update {
+        Some(NullableUpdate { value }) => value.clone(),
+        None => current,
+    }
+}
+
 impl From<RepositoryError> for UpdateLocationError {
     fn from(error: RepositoryError) -> Self {
         UpdateLocationError::DatabaseError(error)
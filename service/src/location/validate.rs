@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use repository::EqualFilter;
 use repository::{
     LocationFilter, LocationRepository, LocationRow, LocationRowRepository, RepositoryError,
@@ -30,3 +32,62 @@ pub fn check_location_exists(
 ) -> Result<Option<LocationRow>, RepositoryError> {
     Ok(LocationRowRepository::new(connection).find_one_by_id(id)?)
 }
+
+/// Returns false if setting `parent_id` as the parent of location `id` would make the location
+/// its own ancestor
+pub fn check_parent_location_does_not_create_cycle(
+    id: &str,
+    parent_id: &str,
+    connection: &StorageConnection,
+) -> Result<bool, RepositoryError> {
+    let repo = LocationRowRepository::new(connection);
+    let mut visited = HashSet::new();
+    let mut current = Some(parent_id.to_string());
+
+    while let Some(current_id) = current {
+        if current_id == id {
+            return Ok(false);
+        }
+        // Guard against existing cycles in the data
+        if !visited.insert(current_id.clone()) {
+            return Ok(true);
+        }
+        current = repo
+            .find_one_by_id(&current_id)?
+            .and_then(|location| location.parent_location_id);
+    }
+
+    Ok(true)
+}
+
+pub fn check_temperature_range(min_temperature: Option<f64>, max_temperature: Option<f64>) -> bool {
+    match (min_temperature, max_temperature) {
+        (Some(min), Some(max)) => min <= max,
+        _ => true,
+    }
+}
+
+/// Returns the id of the location followed by the ids of all its sub locations, at any depth
+pub fn location_and_sub_location_ids(
+    id: &str,
+    connection: &StorageConnection,
+) -> Result<Vec<String>, RepositoryError> {
+    let repo = LocationRepository::new(connection);
+    let mut ids = vec![id.to_string()];
+    let mut parent_ids = ids.clone();
+
+    while !parent_ids.is_empty() {
+        parent_ids = repo
+            .query_by_filter(
+                LocationFilter::new().parent_location_id(EqualFilter::equal_any(parent_ids)),
+            )?
+            .into_iter()
+            .map(|location| location.location_row.id)
+            // Guard against existing cycles in the data
+            .filter(|location_id| !ids.contains(location_id))
+            .collect();
+        ids.extend(parent_ids.iter().cloned());
+    }
+
+    Ok(ids)
+}
@@ -5,7 +5,8 @@ use std::{
 
 use log::info;
 use repository::{
-    Permission, RepositoryError, UserAccountRow, UserPermissionRow, UserStoreJoinRow,
+    Permission, RepositoryError, UserAccountRow, UserAccountRowRepository, UserPermissionRow,
+    UserStoreJoinRow,
 };
 use reqwest::{ClientBuilder, Url};
 use util::uuid::uuid;
@@ -84,17 +85,20 @@ impl LoginService {
         auth_data: &AuthData,
         input: LoginInput,
     ) -> Result<TokenPair, LoginError> {
-        match LoginService::fetch_user_from_central(&input).await {
-            Ok((user, store_permissions)) => {
-                let service_ctx = service_provider.context()?;
-                LoginService::update_user(&service_ctx, user, store_permissions)?;
-            }
-            Err(err) => match err {
-                FetchUserError::Unauthenticated => return Err(LoginError::LoginFailure),
-                FetchUserError::ConnectionError(_) => info!("{:?}", err),
-                FetchUserError::InternalError(_) => info!("{:?}", err),
-            },
-        };
+        // Local users are managed on this site only and the central server doesn't know about them
+        if !LoginService::is_local_user(service_provider, &input.username)? {
+            match LoginService::fetch_user_from_central(&input).await {
+                Ok((user, store_permissions)) => {
+                    let service_ctx = service_provider.context()?;
+                    LoginService::update_user(&service_ctx, user, store_permissions)?;
+                }
+                Err(err) => match err {
+                    FetchUserError::Unauthenticated => return Err(LoginError::LoginFailure),
+                    FetchUserError::ConnectionError(_) => info!("{:?}", err),
+                    FetchUserError::InternalError(_) => info!("{:?}", err),
+                },
+            };
+        }
         let service_ctx = service_provider.context()?;
         let user_service = UserAccountService::new(&service_ctx.connection);
         let user_account = match user_service.verify_password(&input.username, &input.password) {
@@ -110,6 +114,9 @@ impl LoginService {
                 });
             }
         };
+        if user_account.is_disabled {
+            return Err(LoginError::LoginFailure);
+        }
 
         let mut token_service = TokenService::new(
             &auth_data.token_bucket,
@@ -124,6 +131,16 @@ impl LoginService {
         Ok(pair)
     }
 
+    fn is_local_user(
+        service_provider: &ServiceProvider,
+        username: &str,
+    ) -> Result<bool, RepositoryError> {
+        let service_ctx = service_provider.context()?;
+        let user = UserAccountRowRepository::new(&service_ctx.connection)
+            .find_one_by_user_name(username)?;
+        Ok(user.map(|user| user.is_local).unwrap_or(false))
+    }
+
     async fn fetch_user_from_central(
         input: &LoginInput,
     ) -> Result<(UserAccountRow, Vec<StorePermissions>), FetchUserError> {
@@ -182,6 +199,8 @@ impl LoginService {
                 "" => None,
                 _ => Some(user_info.user.e_mail.to_string()),
             },
+            is_local: false,
+            is_disabled: false,
         };
         let stores_permissions: Vec<StorePermissions> = user_info
             .user_stores
@@ -0,0 +1,201 @@
+use chrono::NaiveDate;
+use repository::{
+    Gender, Name, NameRow, NameRowRepository, NameStoreJoinRepository, NameStoreJoinRow, NameType,
+    RepositoryError, StorageConnection,
+};
+use util::uuid::uuid;
+
+use super::{query::get_patient, validate::check_name_does_not_exist};
+use crate::{service_provider::ServiceContext, SingleRecordError};
+
+#[derive(PartialEq, Debug)]
+pub enum InsertPatientError {
+    PatientAlreadyExists,
+    CreatedRecordNotFound,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InsertPatient {
+    pub id: String,
+    pub code: String,
+    pub first_name: String,
+    pub last_name: String,
+    pub gender: Option<Gender>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub national_health_number: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+}
+
+/// Inserts a patient and makes it visible (as a customer) in the store it's created in
+pub fn insert_patient(
+    ctx: &ServiceContext,
+    store_id: &str,
+    input: InsertPatient,
+) -> Result<Name, InsertPatientError> {
+    let patient = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(connection, &input)?;
+            let (name_row, name_store_join_row) = generate(store_id, input);
+
+            NameRowRepository::new(connection).upsert_one(&name_row)?;
+            NameStoreJoinRepository::new(connection).upsert_one(&name_store_join_row)?;
+
+            get_patient(ctx, store_id, &name_row.id).map_err(InsertPatientError::from)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(patient)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    input: &InsertPatient,
+) -> Result<(), InsertPatientError> {
+    if !check_name_does_not_exist(connection, &input.id)? {
+        return Err(InsertPatientError::PatientAlreadyExists);
+    }
+
+    Ok(())
+}
+
+fn generate(
+    store_id: &str,
+    InsertPatient {
+        id,
+        code,
+        first_name,
+        last_name,
+        gender,
+        date_of_birth,
+        national_health_number,
+        phone,
+        email,
+        address,
+    }: InsertPatient,
+) -> (NameRow, NameStoreJoinRow) {
+    let name_store_join_row = NameStoreJoinRow {
+        id: uuid(),
+        name_id: id.clone(),
+        store_id: store_id.to_string(),
+        name_is_customer: true,
+        name_is_supplier: false,
+    };
+    let name_row = NameRow {
+        id,
+        name: patient_name(&first_name, &last_name),
+        code,
+        r#type: NameType::Patient,
+        is_customer: true,
+        is_supplier: false,
+        first_name: Some(first_name),
+        last_name: Some(last_name),
+        gender,
+        date_of_birth,
+        national_health_number,
+        phone,
+        email,
+        address,
+        legacy_record: "{}".to_string(),
+    };
+
+    (name_row, name_store_join_row)
+}
+
+/// Full name of a patient, same format as used by the legacy system
+pub fn patient_name(first_name: &str, last_name: &str) -> String {
+    format!("{}, {}", last_name, first_name)
+}
+
+impl From<RepositoryError> for InsertPatientError {
+    fn from(error: RepositoryError) -> Self {
+        InsertPatientError::DatabaseError(error)
+    }
+}
+
+impl From<SingleRecordError> for InsertPatientError {
+    fn from(error: SingleRecordError) -> Self {
+        use InsertPatientError::*;
+        match error {
+            SingleRecordError::DatabaseError(error) => DatabaseError(error),
+            SingleRecordError::NotFound(_) => CreatedRecordNotFound,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{mock_name_a, mock_store_a, mock_store_b, MockDataInserts},
+        test_db::setup_all,
+        Gender, NameRowRepository, NameType,
+    };
+    use util::inline_init;
+
+    use crate::{
+        patient::{InsertPatient, InsertPatientError},
+        service_provider::ServiceProvider,
+        SingleRecordError,
+    };
+
+    #[actix_rt::test]
+    async fn insert_patient() {
+        let (_, connection, connection_manager, _) =
+            setup_all("insert_patient", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.patient_service;
+
+        // PatientAlreadyExists
+        assert_eq!(
+            service.insert_patient(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut InsertPatient| {
+                    r.id = mock_name_a().id;
+                })
+            ),
+            Err(InsertPatientError::PatientAlreadyExists)
+        );
+
+        // Success
+        let patient = service
+            .insert_patient(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut InsertPatient| {
+                    r.id = "patient".to_string();
+                    r.code = "P1".to_string();
+                    r.first_name = "Jane".to_string();
+                    r.last_name = "Doe".to_string();
+                    r.gender = Some(Gender::Female);
+                    r.date_of_birth = Some(NaiveDate::from_ymd(1990, 5, 21));
+                    r.phone = Some("0211234567".to_string());
+                }),
+            )
+            .unwrap();
+        assert_eq!(patient.name_row.name, "Doe, Jane");
+        assert!(patient.is_customer());
+
+        let name_row = NameRowRepository::new(&connection)
+            .find_one_by_id("patient")
+            .unwrap()
+            .unwrap();
+        assert_eq!(name_row.r#type, NameType::Patient);
+        assert_eq!(name_row.first_name, Some("Jane".to_string()));
+        assert_eq!(
+            name_row.date_of_birth,
+            Some(NaiveDate::from_ymd(1990, 5, 21))
+        );
+
+        // Only visible in the store it was created in
+        assert_eq!(
+            service.get_patient(&context, &mock_store_b().id, "patient"),
+            Err(SingleRecordError::NotFound("patient".to_string()))
+        );
+    }
+}
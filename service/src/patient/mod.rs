@@ -0,0 +1,59 @@
+use repository::{Name, NameFilter, NameSort, PaginationOption};
+
+use crate::{service_provider::ServiceContext, ListError, ListResult, SingleRecordError};
+
+pub mod insert;
+pub use self::insert::*;
+
+pub mod update;
+pub use self::update::*;
+
+pub mod query;
+pub use self::query::*;
+
+mod validate;
+
+/// Patients are names of type `Patient`, they are only listed for the stores they are visible in
+/// (name_store_join).
+pub trait PatientServiceTrait: Sync + Send {
+    fn get_patients(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        pagination: Option<PaginationOption>,
+        filter: Option<NameFilter>,
+        sort: Option<NameSort>,
+    ) -> Result<ListResult<Name>, ListError> {
+        get_patients(ctx, store_id, pagination, filter, sort)
+    }
+
+    fn get_patient(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        id: &str,
+    ) -> Result<Name, SingleRecordError> {
+        get_patient(ctx, store_id, id)
+    }
+
+    fn insert_patient(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        input: InsertPatient,
+    ) -> Result<Name, InsertPatientError> {
+        insert_patient(ctx, store_id, input)
+    }
+
+    fn update_patient(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        input: UpdatePatient,
+    ) -> Result<Name, UpdatePatientError> {
+        update_patient(ctx, store_id, input)
+    }
+}
+
+pub struct PatientService {}
+impl PatientServiceTrait for PatientService {}
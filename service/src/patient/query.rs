@@ -0,0 +1,169 @@
+use repository::{
+    EqualFilter, Name, NameFilter, NameRepository, NameSort, NameType, PaginationOption,
+};
+
+use crate::{
+    get_default_pagination, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
+    SingleRecordError,
+};
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+/// Restricts the filter to patients that are visible in the store
+fn patient_filter(filter: Option<NameFilter>) -> NameFilter {
+    filter
+        .unwrap_or_default()
+        .r#type(NameType::Patient.equal_to())
+        .is_visible(true)
+}
+
+pub fn get_patients(
+    ctx: &ServiceContext,
+    store_id: &str,
+    pagination: Option<PaginationOption>,
+    filter: Option<NameFilter>,
+    sort: Option<NameSort>,
+) -> Result<ListResult<Name>, ListError> {
+    let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+    let repository = NameRepository::new(&ctx.connection);
+    let filter = patient_filter(filter);
+
+    Ok(ListResult {
+        rows: repository.query(store_id, pagination, Some(filter.clone()), sort)?,
+        count: i64_to_u32(repository.count(store_id, Some(filter))?),
+    })
+}
+
+pub fn get_patient(
+    ctx: &ServiceContext,
+    store_id: &str,
+    id: &str,
+) -> Result<Name, SingleRecordError> {
+    let repository = NameRepository::new(&ctx.connection);
+
+    let result = repository.query_one(
+        store_id,
+        patient_filter(Some(NameFilter::new().id(EqualFilter::equal_to(id)))),
+    )?;
+
+    result.ok_or(SingleRecordError::NotFound(id.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{mock_name_a, mock_store_a, mock_store_b, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        DateFilter, Gender, NameFilter, NameRow, NameStoreJoinRow, NameType,
+    };
+    use util::inline_init;
+
+    use crate::{service_provider::ServiceProvider, SingleRecordError};
+
+    fn patient(id: &str, first_name: &str, last_name: &str, date_of_birth: NaiveDate) -> NameRow {
+        inline_init(|r: &mut NameRow| {
+            r.id = id.to_string();
+            r.name = format!("{}, {}", last_name, first_name);
+            r.code = id.to_string();
+            r.r#type = NameType::Patient;
+            r.is_customer = true;
+            r.first_name = Some(first_name.to_string());
+            r.last_name = Some(last_name.to_string());
+            r.gender = Some(Gender::Female);
+            r.date_of_birth = Some(date_of_birth);
+        })
+    }
+
+    fn join(name_id: &str, store_id: &str) -> NameStoreJoinRow {
+        inline_init(|r: &mut NameStoreJoinRow| {
+            r.id = format!("{}_{}", name_id, store_id);
+            r.name_id = name_id.to_string();
+            r.store_id = store_id.to_string();
+            r.name_is_customer = true;
+        })
+    }
+
+    #[actix_rt::test]
+    async fn patient_query() {
+        let jane = patient("jane", "Jane", "Doe", NaiveDate::from_ymd(1990, 5, 21));
+        let john = patient("john", "John", "Doe", NaiveDate::from_ymd(1985, 1, 2));
+        let janet = patient("janet", "Janet", "Smith", NaiveDate::from_ymd(1990, 5, 21));
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "patient_query",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.names = vec![jane.clone(), john.clone(), janet.clone()];
+                r.name_store_joins = vec![
+                    join(&jane.id, &mock_store_a().id),
+                    join(&john.id, &mock_store_a().id),
+                    join(&janet.id, &mock_store_b().id),
+                ];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.patient_service;
+
+        // Only patients visible in the store are listed
+        let result = service
+            .get_patients(&context, &mock_store_a().id, None, None, None)
+            .unwrap();
+        assert_eq!(result.count, 2);
+        let ids: Vec<String> = result.rows.into_iter().map(|r| r.name_row.id).collect();
+        assert_eq!(ids, vec![jane.id.clone(), john.id.clone()]);
+
+        // Fuzzy name search, terms can match first, last or full name
+        let result = service
+            .get_patients(
+                &context,
+                &mock_store_a().id,
+                None,
+                Some(NameFilter::new().search("doe ja")),
+                None,
+            )
+            .unwrap();
+        assert_eq!(result.count, 1);
+        assert_eq!(result.rows[0].name_row.id, jane.id);
+
+        // Date of birth
+        let result = service
+            .get_patients(
+                &context,
+                &mock_store_b().id,
+                None,
+                Some(
+                    NameFilter::new()
+                        .search("jan")
+                        .date_of_birth(DateFilter::equal_to(NaiveDate::from_ymd(1990, 5, 21))),
+                ),
+                None,
+            )
+            .unwrap();
+        assert_eq!(result.count, 1);
+        assert_eq!(result.rows[0].name_row.id, janet.id);
+
+        // Single patient
+        assert_eq!(
+            service
+                .get_patient(&context, &mock_store_a().id, &john.id)
+                .unwrap()
+                .name_row,
+            john
+        );
+        // Not visible in store b
+        assert_eq!(
+            service.get_patient(&context, &mock_store_b().id, &john.id),
+            Err(SingleRecordError::NotFound(john.id.clone()))
+        );
+        // Not a patient
+        assert_eq!(
+            service.get_patient(&context, &mock_store_a().id, &mock_name_a().id),
+            Err(SingleRecordError::NotFound(mock_name_a().id))
+        );
+    }
+}
@@ -0,0 +1,189 @@
+use chrono::NaiveDate;
+use repository::{Gender, Name, NameRow, NameRowRepository, RepositoryError, StorageConnection};
+
+use super::{insert::patient_name, query::get_patient, validate::check_patient_exists};
+use crate::{service_provider::ServiceContext, SingleRecordError};
+
+#[derive(PartialEq, Debug)]
+pub enum UpdatePatientError {
+    PatientDoesNotExist,
+    UpdatedRecordNotFound,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UpdatePatient {
+    pub id: String,
+    pub code: Option<String>,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub gender: Option<Gender>,
+    pub date_of_birth: Option<NaiveDate>,
+    pub national_health_number: Option<String>,
+    pub phone: Option<String>,
+    pub email: Option<String>,
+    pub address: Option<String>,
+}
+
+pub fn update_patient(
+    ctx: &ServiceContext,
+    store_id: &str,
+    input: UpdatePatient,
+) -> Result<Name, UpdatePatientError> {
+    let patient = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let name_row = validate(connection, store_id, &input)?;
+            let updated_name_row = generate(input, name_row);
+            NameRowRepository::new(connection).upsert_one(&updated_name_row)?;
+
+            get_patient(ctx, store_id, &updated_name_row.id).map_err(UpdatePatientError::from)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(patient)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &UpdatePatient,
+) -> Result<NameRow, UpdatePatientError> {
+    match check_patient_exists(connection, store_id, &input.id)? {
+        Some(name_row) => Ok(name_row),
+        None => Err(UpdatePatientError::PatientDoesNotExist),
+    }
+}
+
+fn generate(
+    UpdatePatient {
+        id: _,
+        code,
+        first_name,
+        last_name,
+        gender,
+        date_of_birth,
+        national_health_number,
+        phone,
+        email,
+        address,
+    }: UpdatePatient,
+    mut name_row: NameRow,
+) -> NameRow {
+    name_row.code = code.unwrap_or(name_row.code);
+    name_row.first_name = first_name.or(name_row.first_name);
+    name_row.last_name = last_name.or(name_row.last_name);
+    name_row.name = patient_name(
+        name_row.first_name.as_deref().unwrap_or_default(),
+        name_row.last_name.as_deref().unwrap_or_default(),
+    );
+    name_row.gender = gender.or(name_row.gender);
+    name_row.date_of_birth = date_of_birth.or(name_row.date_of_birth);
+    name_row.national_health_number = national_health_number.or(name_row.national_health_number);
+    name_row.phone = phone.or(name_row.phone);
+    name_row.email = email.or(name_row.email);
+    name_row.address = address.or(name_row.address);
+    name_row
+}
+
+impl From<RepositoryError> for UpdatePatientError {
+    fn from(error: RepositoryError) -> Self {
+        UpdatePatientError::DatabaseError(error)
+    }
+}
+
+impl From<SingleRecordError> for UpdatePatientError {
+    fn from(error: SingleRecordError) -> Self {
+        use UpdatePatientError::*;
+        match error {
+            SingleRecordError::DatabaseError(error) => DatabaseError(error),
+            SingleRecordError::NotFound(_) => UpdatedRecordNotFound,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_name_a, mock_store_a, mock_store_b, MockDataInserts},
+        test_db::setup_all,
+    };
+    use util::inline_init;
+
+    use crate::{
+        patient::{InsertPatient, UpdatePatient, UpdatePatientError},
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn update_patient() {
+        let (_, _, connection_manager, _) =
+            setup_all("update_patient", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.patient_service;
+
+        service
+            .insert_patient(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut InsertPatient| {
+                    r.id = "patient".to_string();
+                    r.first_name = "Jane".to_string();
+                    r.last_name = "Doe".to_string();
+                    r.phone = Some("0211234567".to_string());
+                }),
+            )
+            .unwrap();
+
+        // PatientDoesNotExist
+        assert_eq!(
+            service.update_patient(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdatePatient| {
+                    r.id = "invalid".to_string();
+                })
+            ),
+            Err(UpdatePatientError::PatientDoesNotExist)
+        );
+        // Not a patient
+        assert_eq!(
+            service.update_patient(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdatePatient| {
+                    r.id = mock_name_a().id;
+                })
+            ),
+            Err(UpdatePatientError::PatientDoesNotExist)
+        );
+        // Not visible in store
+        assert_eq!(
+            service.update_patient(
+                &context,
+                &mock_store_b().id,
+                inline_init(|r: &mut UpdatePatient| {
+                    r.id = "patient".to_string();
+                })
+            ),
+            Err(UpdatePatientError::PatientDoesNotExist)
+        );
+
+        // Success
+        let patient = service
+            .update_patient(
+                &context,
+                &mock_store_a().id,
+                inline_init(|r: &mut UpdatePatient| {
+                    r.id = "patient".to_string();
+                    r.last_name = Some("Smith".to_string());
+                    r.email = Some("jane@smith.com".to_string());
+                }),
+            )
+            .unwrap();
+        assert_eq!(patient.name_row.name, "Smith, Jane");
+        assert_eq!(patient.name_row.email, Some("jane@smith.com".to_string()));
+        assert_eq!(patient.name_row.phone, Some("0211234567".to_string()));
+    }
+}
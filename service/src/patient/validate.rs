@@ -0,0 +1,30 @@
+use repository::{
+    EqualFilter, NameFilter, NameRepository, NameRow, NameRowRepository, NameType, RepositoryError,
+    StorageConnection,
+};
+
+pub fn check_name_does_not_exist(
+    connection: &StorageConnection,
+    id: &str,
+) -> Result<bool, RepositoryError> {
+    Ok(NameRowRepository::new(connection)
+        .find_one_by_id(id)?
+        .is_none())
+}
+
+/// Returns the patient when it exists and is visible in the store
+pub fn check_patient_exists(
+    connection: &StorageConnection,
+    store_id: &str,
+    id: &str,
+) -> Result<Option<NameRow>, RepositoryError> {
+    let result = NameRepository::new(connection).query_one(
+        store_id,
+        NameFilter::new()
+            .id(EqualFilter::equal_to(id))
+            .r#type(NameType::Patient.equal_to())
+            .is_visible(true),
+    )?;
+
+    Ok(result.map(|name| name.name_row))
+}
@@ -1,8 +1,8 @@
 use std::collections::HashMap;
 
 use repository::{
-    EqualFilter, Permission, RepositoryError, UserPermissionFilter, UserPermissionRepository,
-    UserPermissionRow,
+    EqualFilter, Permission, RepositoryError, UserAccountRowRepository, UserPermissionFilter,
+    UserPermissionRepository, UserPermissionRow,
 };
 
 use crate::{
@@ -25,28 +25,45 @@ pub enum Resource {
     RouteMe,
     // name
     QueryName,
+    // patient
+    QueryPatient,
+    MutatePatient,
     // location
     QueryLocation,
     MutateLocation,
     // store
     QueryStore,
+    QueryStorePreference,
+    MutateStorePreference,
+    /// Changing whether requisitions of the store require authorisation
+    MutateRequisitionAuthorisationPreference,
     // master list
     QueryMasterList,
     // items
     QueryItems,
     // stock
     StockCount,
+    QueryStockLedger,
+    QueryItemByBarcode,
+    MutateBarcode,
+    // alert
+    QueryAlert,
+    MutateAlert,
     // stocktake
     QueryStocktake,
     MutateStocktake,
     // requisition
     QueryRequisition,
     MutateRequisition,
+    AuthoriseRequisition,
     RequisitionChart,
     // stock take line
     InsertStocktakeLine,
     UpdateStocktakeLine,
     DeleteStocktakeLine,
+    // inventory adjustment
+    QueryInventoryAdjustmentReasons,
+    MutateInventoryAdjustment,
     // invoice
     InvoiceCount,
     QueryInvoice,
@@ -54,6 +71,11 @@ pub enum Resource {
     MutateOutboundShipment,
     // inbound shipment
     MutateInboundShipment,
+    // prescription
+    MutatePrescription,
+    // returns
+    MutateCustomerReturn,
+    MutateSupplierReturn,
     // reporting
     Report,
     // view/edit server setting
@@ -72,6 +94,16 @@ fn all_permissions() -> HashMap<Resource, PermissionDSL> {
     // name
     map.insert(Resource::QueryName, PermissionDSL::HasStoreAccess);
 
+    // patient (patients are registered by the people dispensing to them)
+    map.insert(Resource::QueryPatient, PermissionDSL::HasStoreAccess);
+    map.insert(
+        Resource::MutatePatient,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::OutboundShipmentMutate),
+        ]),
+    );
+
     // location
     map.insert(Resource::QueryLocation, PermissionDSL::HasStoreAccess);
     map.insert(
@@ -83,6 +115,27 @@ fn all_permissions() -> HashMap<Resource, PermissionDSL> {
     );
 
     // store: No permission needed
+    map.insert(
+        Resource::QueryStorePreference,
+        PermissionDSL::HasStoreAccess,
+    );
+    map.insert(
+        Resource::MutateStorePreference,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::OutboundShipmentMutate),
+        ]),
+    );
+    map.insert(
+        Resource::MutateRequisitionAuthorisationPreference,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::Any(vec![
+                PermissionDSL::HasPermission(Permission::RequisitionAuthorise),
+                PermissionDSL::HasPermission(Permission::ServerAdmin),
+            ]),
+        ]),
+    );
 
     // master list
     map.insert(Resource::QueryMasterList, PermissionDSL::HasStoreAccess);
@@ -98,6 +151,44 @@ fn all_permissions() -> HashMap<Resource, PermissionDSL> {
             PermissionDSL::HasPermission(Permission::StockLineQuery),
         ]),
     );
+    map.insert(
+        Resource::QueryStockLedger,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::StockLineQuery),
+        ]),
+    );
+    map.insert(
+        Resource::QueryItemByBarcode,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::StockLineQuery),
+        ]),
+    );
+    // barcodes are registered when unknown packs are received
+    map.insert(
+        Resource::MutateBarcode,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::InboundShipmentMutate),
+        ]),
+    );
+
+    // alert (anyone who can see the stock of the store can handle its alerts)
+    map.insert(
+        Resource::QueryAlert,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::StockLineQuery),
+        ]),
+    );
+    map.insert(
+        Resource::MutateAlert,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::StockLineQuery),
+        ]),
+    );
 
     // stocktake
     map.insert(
@@ -151,6 +242,13 @@ fn all_permissions() -> HashMap<Resource, PermissionDSL> {
             PermissionDSL::HasPermission(Permission::RequisitionMutate),
         ]),
     );
+    map.insert(
+        Resource::AuthoriseRequisition,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::RequisitionAuthorise),
+        ]),
+    );
     map.insert(
         Resource::RequisitionChart,
         PermissionDSL::And(vec![
@@ -192,6 +290,42 @@ fn all_permissions() -> HashMap<Resource, PermissionDSL> {
             PermissionDSL::HasPermission(Permission::InboundShipmentMutate),
         ]),
     );
+    // prescription (dispensing is an issue of stock, same as outbound shipments)
+    map.insert(
+        Resource::MutatePrescription,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::OutboundShipmentMutate),
+        ]),
+    );
+    // returns (customer returns receive stock, supplier returns issue stock)
+    map.insert(
+        Resource::MutateCustomerReturn,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::InboundShipmentMutate),
+        ]),
+    );
+    map.insert(
+        Resource::MutateSupplierReturn,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::OutboundShipmentMutate),
+        ]),
+    );
+
+    // inventory adjustment (a stock correction outside of a stocktake)
+    map.insert(
+        Resource::QueryInventoryAdjustmentReasons,
+        PermissionDSL::HasStoreAccess,
+    );
+    map.insert(
+        Resource::MutateInventoryAdjustment,
+        PermissionDSL::And(vec![
+            PermissionDSL::HasStoreAccess,
+            PermissionDSL::HasPermission(Permission::StocktakeMutate),
+        ]),
+    );
 
     // report
     map.insert(
@@ -234,6 +368,7 @@ fn dummy_user_auth() -> ValidatedUserAuth {
             iat: 0,
             iss: "omSupply-debug".to_string(),
             sub: user_id.to_string(),
+            jti: "".to_string(),
         },
     }
 }
@@ -398,6 +533,16 @@ impl ValidationServiceTrait for ValidationService {
         }
 
         let connection = &context.connection;
+        if let Some(user) =
+            UserAccountRowRepository::new(connection).find_one_by_id(&validated_auth.user_id)?
+        {
+            if user.is_disabled {
+                return Err(ValidationError::Denied(
+                    ValidationDeniedKind::NotAuthenticated("User account is disabled".to_string()),
+                ));
+            }
+        }
+
         let mut permission_filter =
             UserPermissionFilter::new().user_id(EqualFilter::equal_to(&validated_auth.user_id));
         if let Some(store_id) = &resource_request.store_id {
@@ -582,6 +727,8 @@ mod permission_validation_test {
                 username: "user".to_string(),
                 hashed_password: "n/a".to_string(),
                 email: None,
+                is_local: false,
+                is_disabled: false,
             }
         }
 
@@ -591,6 +738,8 @@ mod permission_validation_test {
                 username: "user".to_string(),
                 hashed_password: "n/a".to_string(),
                 email: None,
+                is_local: false,
+                is_disabled: false,
             }
         }
 
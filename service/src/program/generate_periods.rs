@@ -0,0 +1,180 @@
+use chrono::{Duration, NaiveDate};
+use repository::{
+    PeriodRow, PeriodRowRepository, PeriodScheduleRow, PeriodScheduleRowRepository,
+    PeriodScheduleType, RepositoryError,
+};
+use util::uuid::uuid;
+
+use crate::service_provider::ServiceContext;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct GeneratePeriods {
+    pub period_schedule_id: String,
+    pub year: i32,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GeneratePeriodsError {
+    PeriodScheduleDoesNotExist,
+    InvalidYear,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = GeneratePeriodsError;
+
+/// Creates the periods of a year for a schedule, periods that already exist are kept.
+/// Returns all periods of the schedule.
+pub fn generate_periods(
+    ctx: &ServiceContext,
+    input: GeneratePeriods,
+) -> Result<Vec<PeriodRow>, OutError> {
+    let periods = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let period_schedule = PeriodScheduleRowRepository::new(connection)
+                .find_one_by_id(&input.period_schedule_id)?
+                .ok_or(OutError::PeriodScheduleDoesNotExist)?;
+
+            let repository = PeriodRowRepository::new(connection);
+            let existing = repository.find_many_by_period_schedule_id(&period_schedule.id)?;
+
+            for period in generate(&period_schedule, input.year)? {
+                if existing
+                    .iter()
+                    .any(|existing| existing.start_date == period.start_date)
+                {
+                    continue;
+                }
+                repository.upsert_one(&period)?;
+            }
+
+            repository
+                .find_many_by_period_schedule_id(&period_schedule.id)
+                .map_err(OutError::DatabaseError)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(periods)
+}
+
+fn generate(period_schedule: &PeriodScheduleRow, year: i32) -> Result<Vec<PeriodRow>, OutError> {
+    // Also checks that the end of the year can be represented
+    NaiveDate::from_ymd_opt(year + 1, 1, 1).ok_or(OutError::InvalidYear)?;
+
+    let months = period_schedule.r#type.months();
+    let result = (0..12 / months)
+        .map(|index| {
+            let start_month = 1 + index * months;
+            let start_date = NaiveDate::from_ymd(year, start_month, 1);
+            let next_start_date = match start_month + months {
+                13 => NaiveDate::from_ymd(year + 1, 1, 1),
+                next_month => NaiveDate::from_ymd(year, next_month, 1),
+            };
+            let name = match period_schedule.r#type {
+                PeriodScheduleType::Monthly => start_date.format("%B %Y").to_string(),
+                PeriodScheduleType::Quarterly => format!("Q{} {}", index + 1, year),
+            };
+
+            PeriodRow {
+                id: uuid(),
+                period_schedule_id: period_schedule.id.clone(),
+                name,
+                start_date,
+                end_date: next_start_date - Duration::days(1),
+            }
+        })
+        .collect();
+
+    Ok(result)
+}
+
+impl From<RepositoryError> for GeneratePeriodsError {
+    fn from(error: RepositoryError) -> Self {
+        GeneratePeriodsError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{mock_period_schedule_monthly, MockData, MockDataInserts},
+        test_db::setup_all_with_data,
+        PeriodScheduleRow, PeriodScheduleType,
+    };
+    use util::inline_init;
+
+    use crate::{
+        program::generate_periods::{GeneratePeriods, GeneratePeriodsError as ServiceError},
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn generate_periods() {
+        fn quarterly() -> PeriodScheduleRow {
+            PeriodScheduleRow {
+                id: "quarterly".to_string(),
+                name: "Quarterly".to_string(),
+                r#type: PeriodScheduleType::Quarterly,
+            }
+        }
+
+        let (_, _, connection_manager, _) = setup_all_with_data(
+            "generate_periods",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.period_schedules = vec![quarterly()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.program_service;
+
+        // PeriodScheduleDoesNotExist
+        assert_eq!(
+            service.generate_periods(
+                &context,
+                GeneratePeriods {
+                    period_schedule_id: "invalid".to_string(),
+                    year: 2021,
+                },
+            ),
+            Err(ServiceError::PeriodScheduleDoesNotExist)
+        );
+
+        // Quarterly
+        let periods = service
+            .generate_periods(
+                &context,
+                GeneratePeriods {
+                    period_schedule_id: quarterly().id,
+                    year: 2021,
+                },
+            )
+            .unwrap();
+        assert_eq!(periods.len(), 4);
+        assert_eq!(periods[0].name, "Q1 2021");
+        assert_eq!(periods[0].start_date, NaiveDate::from_ymd(2021, 1, 1));
+        assert_eq!(periods[0].end_date, NaiveDate::from_ymd(2021, 3, 31));
+        assert_eq!(periods[3].start_date, NaiveDate::from_ymd(2021, 10, 1));
+        assert_eq!(periods[3].end_date, NaiveDate::from_ymd(2021, 12, 31));
+
+        // Monthly, the two mock periods of 2021 are kept
+        let periods = service
+            .generate_periods(
+                &context,
+                GeneratePeriods {
+                    period_schedule_id: mock_period_schedule_monthly().id,
+                    year: 2021,
+                },
+            )
+            .unwrap();
+        assert_eq!(periods.len(), 12);
+        assert_eq!(periods[0].id, "period_2021_01");
+        assert_eq!(periods[1].id, "period_2021_02");
+        assert_eq!(periods[2].name, "March 2021");
+        assert_eq!(periods[1].end_date, NaiveDate::from_ymd(2021, 2, 28));
+    }
+}
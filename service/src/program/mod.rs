@@ -0,0 +1,53 @@
+use self::{
+    generate_periods::{generate_periods, GeneratePeriods, GeneratePeriodsError},
+    query::{get_programs, Program},
+    upsert_period_schedule::{
+        upsert_period_schedule, UpsertPeriodSchedule, UpsertPeriodScheduleError,
+    },
+    upsert_program::{upsert_program, UpsertProgram, UpsertProgramError},
+};
+
+use crate::service_provider::ServiceContext;
+use repository::{PeriodRow, PeriodScheduleRow, RepositoryError};
+
+pub mod generate_periods;
+pub mod query;
+pub mod upsert_period_schedule;
+pub mod upsert_program;
+
+pub trait ProgramServiceTrait: Sync + Send {
+    fn get_programs(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Vec<Program>, RepositoryError> {
+        get_programs(ctx, store_id)
+    }
+
+    fn generate_periods(
+        &self,
+        ctx: &ServiceContext,
+        input: GeneratePeriods,
+    ) -> Result<Vec<PeriodRow>, GeneratePeriodsError> {
+        generate_periods(ctx, input)
+    }
+
+    fn upsert_period_schedule(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertPeriodSchedule,
+    ) -> Result<PeriodScheduleRow, UpsertPeriodScheduleError> {
+        upsert_period_schedule(ctx, input)
+    }
+
+    fn upsert_program(
+        &self,
+        ctx: &ServiceContext,
+        input: UpsertProgram,
+    ) -> Result<Program, UpsertProgramError> {
+        upsert_program(ctx, input)
+    }
+}
+
+pub struct ProgramService {}
+impl ProgramServiceTrait for ProgramService {}
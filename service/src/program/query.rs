@@ -0,0 +1,58 @@
+use crate::{
+    requisition::request_requisition::check_master_list_for_store, service_provider::ServiceContext,
+};
+use repository::{
+    PeriodRow, PeriodRowRepository, PeriodScheduleRow, PeriodScheduleRowRepository, ProgramRow,
+    ProgramRowRepository, RepositoryError, StorageConnection,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Program {
+    pub program_row: ProgramRow,
+    pub period_schedule_row: PeriodScheduleRow,
+    /// Periods of the programme's schedule, ordered by start date
+    pub periods: Vec<PeriodRow>,
+}
+
+/// Programmes with a master list that is visible to the store
+pub fn get_programs(ctx: &ServiceContext, store_id: &str) -> Result<Vec<Program>, RepositoryError> {
+    let connection = &ctx.connection;
+
+    let mut result = Vec::new();
+    for program_row in ProgramRowRepository::new(connection).find_all()? {
+        if check_master_list_for_store(connection, store_id, &program_row.master_list_id)?.is_none()
+        {
+            continue;
+        }
+        result.push(to_program(connection, program_row)?);
+    }
+
+    Ok(result)
+}
+
+pub fn get_program(
+    connection: &StorageConnection,
+    id: &str,
+) -> Result<Option<Program>, RepositoryError> {
+    match ProgramRowRepository::new(connection).find_one_by_id(id)? {
+        Some(program_row) => Ok(Some(to_program(connection, program_row)?)),
+        None => Ok(None),
+    }
+}
+
+fn to_program(
+    connection: &StorageConnection,
+    program_row: ProgramRow,
+) -> Result<Program, RepositoryError> {
+    let period_schedule_row = PeriodScheduleRowRepository::new(connection)
+        .find_one_by_id(&program_row.period_schedule_id)?
+        .ok_or(RepositoryError::NotFound)?;
+    let periods = PeriodRowRepository::new(connection)
+        .find_many_by_period_schedule_id(&program_row.period_schedule_id)?;
+
+    Ok(Program {
+        program_row,
+        period_schedule_row,
+        periods,
+    })
+}
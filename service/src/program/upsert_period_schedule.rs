@@ -0,0 +1,162 @@
+use repository::{
+    PeriodRowRepository, PeriodScheduleRow, PeriodScheduleRowRepository, PeriodScheduleType,
+    RepositoryError,
+};
+
+use crate::service_provider::ServiceContext;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct UpsertPeriodSchedule {
+    pub id: String,
+    pub name: String,
+    pub r#type: PeriodScheduleType,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpsertPeriodScheduleError {
+    /// Existing periods would no longer match the schedule
+    PeriodScheduleTypeCannotChange,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = UpsertPeriodScheduleError;
+
+/// Creates or updates a period schedule, periods of the schedule are created with
+/// `generate_periods`
+pub fn upsert_period_schedule(
+    ctx: &ServiceContext,
+    input: UpsertPeriodSchedule,
+) -> Result<PeriodScheduleRow, OutError> {
+    let period_schedule = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let repository = PeriodScheduleRowRepository::new(connection);
+            if let Some(existing) = repository.find_one_by_id(&input.id)? {
+                let has_periods = !PeriodRowRepository::new(connection)
+                    .find_many_by_period_schedule_id(&existing.id)?
+                    .is_empty();
+                if has_periods && existing.r#type != input.r#type {
+                    return Err(OutError::PeriodScheduleTypeCannotChange);
+                }
+            }
+
+            let period_schedule = PeriodScheduleRow {
+                id: input.id,
+                name: input.name,
+                r#type: input.r#type,
+            };
+            repository.upsert_one(&period_schedule)?;
+            Ok(period_schedule)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(period_schedule)
+}
+
+impl From<RepositoryError> for UpsertPeriodScheduleError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertPeriodScheduleError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_period_schedule_monthly, MockDataInserts},
+        test_db::setup_all,
+        PeriodScheduleRow, PeriodScheduleRowRepository, PeriodScheduleType,
+    };
+
+    use crate::{
+        program::{
+            generate_periods::GeneratePeriods,
+            upsert_period_schedule::{
+                UpsertPeriodSchedule, UpsertPeriodScheduleError as ServiceError,
+            },
+        },
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn upsert_period_schedule() {
+        let (_, connection, connection_manager, _) =
+            setup_all("upsert_period_schedule", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.program_service;
+
+        // PeriodScheduleTypeCannotChange
+        assert_eq!(
+            service.upsert_period_schedule(
+                &context,
+                UpsertPeriodSchedule {
+                    id: mock_period_schedule_monthly().id,
+                    name: "Quarterly".to_string(),
+                    r#type: PeriodScheduleType::Quarterly,
+                }
+            ),
+            Err(ServiceError::PeriodScheduleTypeCannotChange)
+        );
+
+        // Rename existing schedule
+        let renamed = PeriodScheduleRow {
+            name: "Monthly reporting".to_string(),
+            ..mock_period_schedule_monthly()
+        };
+        assert_eq!(
+            service.upsert_period_schedule(
+                &context,
+                UpsertPeriodSchedule {
+                    id: renamed.id.clone(),
+                    name: renamed.name.clone(),
+                    r#type: renamed.r#type.clone(),
+                }
+            ),
+            Ok(renamed.clone())
+        );
+        assert_eq!(
+            PeriodScheduleRowRepository::new(&connection)
+                .find_one_by_id(&renamed.id)
+                .unwrap(),
+            Some(renamed)
+        );
+
+        // New schedule, type can change until periods are generated
+        let input = UpsertPeriodSchedule {
+            id: "new_schedule".to_string(),
+            name: "New schedule".to_string(),
+            r#type: PeriodScheduleType::Monthly,
+        };
+        service
+            .upsert_period_schedule(&context, input.clone())
+            .unwrap();
+        let quarterly = UpsertPeriodSchedule {
+            r#type: PeriodScheduleType::Quarterly,
+            ..input.clone()
+        };
+        assert_eq!(
+            service.upsert_period_schedule(&context, quarterly.clone()),
+            Ok(PeriodScheduleRow {
+                id: "new_schedule".to_string(),
+                name: "New schedule".to_string(),
+                r#type: PeriodScheduleType::Quarterly,
+            })
+        );
+
+        let periods = service
+            .generate_periods(
+                &context,
+                GeneratePeriods {
+                    period_schedule_id: "new_schedule".to_string(),
+                    year: 2022,
+                },
+            )
+            .unwrap();
+        assert_eq!(periods.len(), 4);
+        assert_eq!(
+            service.upsert_period_schedule(&context, input),
+            Err(ServiceError::PeriodScheduleTypeCannotChange)
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use repository::{
+    EqualFilter, MasterListFilter, MasterListRepository, PeriodScheduleRowRepository, ProgramRow,
+    ProgramRowRepository, RepositoryError,
+};
+
+use crate::service_provider::ServiceContext;
+
+use super::query::{get_program, Program};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct UpsertProgram {
+    pub id: String,
+    pub name: String,
+    /// Items of the master list are requested in the programme requisitions
+    pub master_list_id: String,
+    pub period_schedule_id: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum UpsertProgramError {
+    MasterListDoesNotExist,
+    PeriodScheduleDoesNotExist,
+    UpsertedRecordNotFound,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = UpsertProgramError;
+
+/// Creates or updates a programme. The programme is available to the stores its master list is
+/// visible to.
+pub fn upsert_program(ctx: &ServiceContext, input: UpsertProgram) -> Result<Program, OutError> {
+    let program = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let master_lists = MasterListRepository::new(connection).query_by_filter(
+                MasterListFilter::new().id(EqualFilter::equal_to(&input.master_list_id)),
+            )?;
+            if master_lists.is_empty() {
+                return Err(OutError::MasterListDoesNotExist);
+            }
+            if PeriodScheduleRowRepository::new(connection)
+                .find_one_by_id(&input.period_schedule_id)?
+                .is_none()
+            {
+                return Err(OutError::PeriodScheduleDoesNotExist);
+            }
+
+            ProgramRowRepository::new(connection).upsert_one(&ProgramRow {
+                id: input.id.clone(),
+                name: input.name,
+                master_list_id: input.master_list_id,
+                period_schedule_id: input.period_schedule_id,
+            })?;
+
+            get_program(connection, &input.id)?.ok_or(OutError::UpsertedRecordNotFound)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(program)
+}
+
+impl From<RepositoryError> for UpsertProgramError {
+    fn from(error: RepositoryError) -> Self {
+        UpsertProgramError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_master_list_master_list_line_filter_test, mock_period_2021_01,
+            mock_period_2021_02, mock_period_schedule_monthly, mock_program_a, MockDataInserts,
+        },
+        test_db::setup_all,
+        ProgramRow,
+    };
+    use util::inline_init;
+
+    use crate::{
+        program::upsert_program::{UpsertProgram, UpsertProgramError as ServiceError},
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn upsert_program() {
+        let (_, _, connection_manager, _) =
+            setup_all("upsert_program", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.program_service;
+
+        fn input() -> UpsertProgram {
+            inline_init(|r: &mut UpsertProgram| {
+                r.id = "new_program".to_string();
+                r.name = "New programme".to_string();
+                r.master_list_id = mock_master_list_master_list_line_filter_test()
+                    .master_list
+                    .id;
+                r.period_schedule_id = mock_period_schedule_monthly().id;
+            })
+        }
+
+        // MasterListDoesNotExist
+        assert_eq!(
+            service.upsert_program(
+                &context,
+                inline_init(|r: &mut UpsertProgram| {
+                    *r = input();
+                    r.master_list_id = "invalid".to_string();
+                })
+            ),
+            Err(ServiceError::MasterListDoesNotExist)
+        );
+        // PeriodScheduleDoesNotExist
+        assert_eq!(
+            service.upsert_program(
+                &context,
+                inline_init(|r: &mut UpsertProgram| {
+                    *r = input();
+                    r.period_schedule_id = "invalid".to_string();
+                })
+            ),
+            Err(ServiceError::PeriodScheduleDoesNotExist)
+        );
+
+        // Insert
+        let program = service.upsert_program(&context, input()).unwrap();
+        assert_eq!(
+            program.program_row,
+            ProgramRow {
+                id: input().id,
+                name: input().name,
+                master_list_id: input().master_list_id,
+                period_schedule_id: input().period_schedule_id,
+            }
+        );
+        assert_eq!(program.period_schedule_row, mock_period_schedule_monthly());
+        assert_eq!(
+            program.periods,
+            vec![mock_period_2021_01(), mock_period_2021_02()]
+        );
+
+        // Update
+        let program = service
+            .upsert_program(
+                &context,
+                UpsertProgram {
+                    id: mock_program_a().id,
+                    name: "Programme A renamed".to_string(),
+                    ..input()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            program.program_row,
+            ProgramRow {
+                name: "Programme A renamed".to_string(),
+                ..mock_program_a()
+            }
+        );
+    }
+}
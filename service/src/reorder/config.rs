@@ -0,0 +1,119 @@
+use crate::{
+    service_provider::ServiceContext,
+    validate::{check_other_party, CheckOtherPartyType, OtherPartyErrors},
+};
+use repository::{
+    ReorderConfigRow, ReorderConfigRowRepository, RepositoryError, StorageConnection,
+};
+
+pub const DEFAULT_MIN_MONTHS_OF_STOCK: f64 = 3.0;
+pub const DEFAULT_MAX_MONTHS_OF_STOCK: f64 = 6.0;
+
+/// Returns the stored reorder config or a disabled config if none has been set for the store
+pub fn get_reorder_config(
+    connection: &StorageConnection,
+    store_id: &str,
+) -> Result<ReorderConfigRow, RepositoryError> {
+    let config = ReorderConfigRowRepository::new(connection)
+        .find_one_by_id(store_id)?
+        .unwrap_or_else(|| ReorderConfigRow {
+            id: store_id.to_string(),
+            is_enabled: false,
+            supplier_id: None,
+            min_months_of_stock: DEFAULT_MIN_MONTHS_OF_STOCK,
+            max_months_of_stock: DEFAULT_MAX_MONTHS_OF_STOCK,
+        });
+    Ok(config)
+}
+
+#[derive(PartialEq, Debug)]
+pub enum UpdateReorderConfigError {
+    SupplierDoesNotExist,
+    SupplierNotVisible,
+    SupplierNotASupplier,
+    SupplierIsNotAStore,
+    /// Months of stock are negative or min is greater than max
+    InvalidMonthsOfStock,
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct UpdateReorderConfig {
+    pub is_enabled: bool,
+    pub supplier_id: Option<String>,
+    pub min_months_of_stock: f64,
+    pub max_months_of_stock: f64,
+}
+
+pub fn update_reorder_config(
+    ctx: &ServiceContext,
+    store_id: &str,
+    input: UpdateReorderConfig,
+) -> Result<ReorderConfigRow, UpdateReorderConfigError> {
+    let config = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(connection, store_id, &input)?;
+            let row = generate(store_id, input);
+            ReorderConfigRowRepository::new(connection).upsert_one(&row)?;
+
+            get_reorder_config(connection, store_id).map_err(UpdateReorderConfigError::from)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(config)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &UpdateReorderConfig,
+) -> Result<(), UpdateReorderConfigError> {
+    use UpdateReorderConfigError::*;
+    if input.min_months_of_stock < 0.0 || input.min_months_of_stock > input.max_months_of_stock {
+        return Err(InvalidMonthsOfStock);
+    }
+
+    if let Some(supplier_id) = &input.supplier_id {
+        let supplier = check_other_party(
+            connection,
+            store_id,
+            supplier_id,
+            CheckOtherPartyType::Supplier,
+        )
+        .map_err(|e| match e {
+            OtherPartyErrors::OtherPartyDoesNotExist => SupplierDoesNotExist,
+            OtherPartyErrors::OtherPartyNotVisible => SupplierNotVisible,
+            OtherPartyErrors::TypeMismatched => SupplierNotASupplier,
+            OtherPartyErrors::DatabaseError(repository_error) => DatabaseError(repository_error),
+        })?;
+
+        // Request requisitions can only be sent to other stores
+        supplier.store_id().ok_or(SupplierIsNotAStore)?;
+    }
+
+    Ok(())
+}
+
+fn generate(
+    store_id: &str,
+    UpdateReorderConfig {
+        is_enabled,
+        supplier_id,
+        min_months_of_stock,
+        max_months_of_stock,
+    }: UpdateReorderConfig,
+) -> ReorderConfigRow {
+    ReorderConfigRow {
+        id: store_id.to_string(),
+        is_enabled,
+        supplier_id,
+        min_months_of_stock,
+        max_months_of_stock,
+    }
+}
+
+impl From<RepositoryError> for UpdateReorderConfigError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateReorderConfigError::DatabaseError(error)
+    }
+}
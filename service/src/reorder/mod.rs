@@ -0,0 +1,50 @@
+use self::{
+    config::{
+        get_reorder_config, update_reorder_config, UpdateReorderConfig, UpdateReorderConfigError,
+    },
+    run::{run_reorder, RunReorderError},
+};
+
+use crate::service_provider::ServiceContext;
+use repository::{ReorderConfigRow, ReorderRunRow, ReorderRunRowRepository, RepositoryError};
+
+pub mod config;
+pub mod run;
+
+pub trait ReorderServiceTrait: Sync + Send {
+    fn get_reorder_config(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<ReorderConfigRow, RepositoryError> {
+        get_reorder_config(&ctx.connection, store_id)
+    }
+
+    fn update_reorder_config(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        input: UpdateReorderConfig,
+    ) -> Result<ReorderConfigRow, UpdateReorderConfigError> {
+        update_reorder_config(ctx, store_id, input)
+    }
+
+    fn run_reorder(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<ReorderRunRow, RunReorderError> {
+        run_reorder(ctx, store_id)
+    }
+
+    fn get_last_reorder_run(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<Option<ReorderRunRow>, RepositoryError> {
+        ReorderRunRowRepository::new(&ctx.connection).find_latest_by_store(store_id)
+    }
+}
+
+pub struct ReorderService {}
+impl ReorderServiceTrait for ReorderService {}
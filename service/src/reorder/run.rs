@@ -0,0 +1,521 @@
+use super::config::get_reorder_config;
+use crate::{
+    number::next_number, requisition::request_requisition::generate_requisition_lines,
+    service_provider::ServiceContext,
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use repository::{
+    requisition_row::{RequisitionRow, RequisitionRowStatus, RequisitionRowType},
+    EqualFilter, MasterListFilter, MasterListLineFilter, MasterListLineRepository,
+    MasterListRepository, NumberRowType, ReorderConfigRowRepository, ReorderRunRow,
+    ReorderRunRowRepository, RepositoryError, RequisitionFilter, RequisitionLineFilter,
+    RequisitionLineRepository, RequisitionLineRowRepository, RequisitionRepository,
+    RequisitionRowRepository, StorageConnection,
+};
+use util::uuid::uuid;
+
+/// Minimum time between two scheduled reorder runs of a store
+pub const REORDER_RUN_INTERVAL_HOURS: i64 = 24;
+
+#[derive(Debug, PartialEq)]
+pub enum RunReorderError {
+    NoSupplierConfigured,
+    DatabaseError(RepositoryError),
+}
+
+/// Creates a draft request requisition to the configured supplier for the items of the store's
+/// master lists that are below the minimum months of stock.
+///
+/// Items that are already on a draft request requisition of the store are skipped. Every run is
+/// recorded, including runs that didn't find any item to reorder or failed.
+pub fn run_reorder(ctx: &ServiceContext, store_id: &str) -> Result<ReorderRunRow, RunReorderError> {
+    run_reorder_at(ctx, store_id, Utc::now().naive_utc())
+}
+
+/// Runs the reorder of every store with enabled automatic reorder that hasn't run within
+/// [REORDER_RUN_INTERVAL_HOURS], returns the outcome per store
+pub fn run_due_reorders(
+    ctx: &ServiceContext,
+    now: NaiveDateTime,
+) -> Result<Vec<(String, Result<ReorderRunRow, RunReorderError>)>, RepositoryError> {
+    let configs = ReorderConfigRowRepository::new(&ctx.connection).find_all_enabled()?;
+    let run_repo = ReorderRunRowRepository::new(&ctx.connection);
+
+    let mut results = Vec::new();
+    for config in configs {
+        let is_due = match run_repo.find_latest_by_store(&config.id)? {
+            Some(last_run) => {
+                now - last_run.run_datetime >= Duration::hours(REORDER_RUN_INTERVAL_HOURS)
+            }
+            None => true,
+        };
+        if is_due {
+            let result = run_reorder_at(ctx, &config.id, now);
+            results.push((config.id, result));
+        }
+    }
+    Ok(results)
+}
+
+fn run_reorder_at(
+    ctx: &ServiceContext,
+    store_id: &str,
+    now: NaiveDateTime,
+) -> Result<ReorderRunRow, RunReorderError> {
+    let result = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let run = generate(ctx, connection, store_id, now)?;
+            ReorderRunRowRepository::new(connection).upsert_one(&run)?;
+            Ok(run)
+        })
+        .map_err(|error| error.to_inner_error());
+
+    match result {
+        Err(RunReorderError::NoSupplierConfigured) => {
+            ReorderRunRowRepository::new(&ctx.connection).upsert_one(&ReorderRunRow {
+                id: uuid(),
+                store_id: store_id.to_string(),
+                run_datetime: now,
+                requisition_id: None,
+                number_of_items: 0,
+                error: Some("No supplier configured".to_string()),
+            })?;
+            Err(RunReorderError::NoSupplierConfigured)
+        }
+        result => result,
+    }
+}
+
+fn generate(
+    ctx: &ServiceContext,
+    connection: &StorageConnection,
+    store_id: &str,
+    now: NaiveDateTime,
+) -> Result<ReorderRunRow, RunReorderError> {
+    let config = get_reorder_config(connection, store_id)?;
+    let supplier_id = config
+        .supplier_id
+        .ok_or(RunReorderError::NoSupplierConfigured)?;
+
+    let mut requisition = RequisitionRow {
+        id: uuid(),
+        user_id: None,
+        requisition_number: 0,
+        name_id: supplier_id,
+        store_id: store_id.to_string(),
+        r#type: RequisitionRowType::Request,
+        status: RequisitionRowStatus::Draft,
+        created_datetime: now,
+        comment: Some("Created by automatic reorder".to_string()),
+        max_months_of_stock: config.max_months_of_stock,
+        min_months_of_stock: config.min_months_of_stock,
+        // Default
+        colour: None,
+        their_reference: None,
+        expected_delivery_date: None,
+        sent_datetime: None,
+        finalised_datetime: None,
+        linked_requisition_id: None,
+        program_id: None,
+        period_id: None,
+        approved_by_user_id: None,
+    };
+
+    let item_ids = get_items_to_check(connection, store_id)?;
+    let lines: Vec<_> = generate_requisition_lines(ctx, store_id, &requisition, item_ids)?
+        .into_iter()
+        .filter(|line| line.suggested_quantity > 0)
+        .collect();
+
+    if lines.is_empty() {
+        return Ok(ReorderRunRow {
+            id: uuid(),
+            store_id: store_id.to_string(),
+            run_datetime: now,
+            requisition_id: None,
+            number_of_items: 0,
+            error: None,
+        });
+    }
+
+    requisition.requisition_number =
+        next_number(connection, &NumberRowType::RequestRequisition, store_id)?;
+    RequisitionRowRepository::new(connection).upsert_one(&requisition)?;
+    let line_repo = RequisitionLineRowRepository::new(connection);
+    for line in &lines {
+        line_repo.upsert_one(line)?;
+    }
+
+    Ok(ReorderRunRow {
+        id: uuid(),
+        store_id: store_id.to_string(),
+        run_datetime: now,
+        requisition_id: Some(requisition.id),
+        number_of_items: lines.len() as i32,
+        error: None,
+    })
+}
+
+/// Items on the master lists of the store that are not yet on a draft request requisition
+fn get_items_to_check(
+    connection: &StorageConnection,
+    store_id: &str,
+) -> Result<Vec<String>, RepositoryError> {
+    let master_list_ids: Vec<String> = MasterListRepository::new(connection)
+        .query_by_filter(
+            MasterListFilter::new().exists_for_store_id(EqualFilter::equal_to(store_id)),
+        )?
+        .into_iter()
+        .map(|master_list| master_list.id)
+        .collect();
+
+    let draft_requisition_ids: Vec<String> = RequisitionRepository::new(connection)
+        .query_by_filter(
+            RequisitionFilter::new()
+                .store_id(EqualFilter::equal_to(store_id))
+                .r#type(RequisitionRowType::Request.equal_to())
+                .status(RequisitionRowStatus::Draft.equal_to()),
+        )?
+        .into_iter()
+        .map(|requisition| requisition.requisition_row.id)
+        .collect();
+
+    let item_ids_on_draft_requisitions: Vec<String> = RequisitionLineRepository::new(connection)
+        .query_by_filter(
+            RequisitionLineFilter::new()
+                .requisition_id(EqualFilter::equal_any(draft_requisition_ids)),
+        )?
+        .into_iter()
+        .map(|line| line.requisition_line_row.item_id)
+        .collect();
+
+    let mut item_ids: Vec<String> = MasterListLineRepository::new(connection)
+        .query_by_filter(
+            MasterListLineFilter::new()
+                .master_list_id(EqualFilter::equal_any(master_list_ids))
+                .item_id(EqualFilter::not_equal_all(item_ids_on_draft_requisitions)),
+        )?
+        .into_iter()
+        .map(|line| line.item_id)
+        .collect();
+    item_ids.sort();
+    item_ids.dedup();
+
+    Ok(item_ids)
+}
+
+impl From<RepositoryError> for RunReorderError {
+    fn from(error: RepositoryError) -> Self {
+        RunReorderError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::{Duration, Utc};
+    use repository::{
+        mock::{
+            common::FullMockMasterList, mock_name_store_a, mock_name_store_b, mock_name_store_c,
+            mock_store_a, test_item_stats, MockData, MockDataInserts,
+        },
+        requisition_row::{RequisitionRowStatus, RequisitionRowType},
+        test_db::setup_all_with_data,
+        ItemRow, ItemRowType, MasterListLineRow, MasterListNameJoinRow, MasterListRow,
+        ReorderRunRow, RequisitionRowRepository, StockLineRow,
+    };
+    use util::inline_init;
+
+    use crate::{
+        reorder::{
+            config::{UpdateReorderConfig, UpdateReorderConfigError},
+            run::{run_due_reorders, RunReorderError},
+        },
+        requisition::common::get_lines_for_requisition,
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn run_reorder() {
+        fn master_list() -> FullMockMasterList {
+            let id = "reorder_master_list".to_owned();
+            FullMockMasterList {
+                master_list: MasterListRow {
+                    id: id.clone(),
+                    name: id.clone(),
+                    code: id.clone(),
+                    description: id.clone(),
+                },
+                joins: vec![MasterListNameJoinRow {
+                    id: format!("{}1", id),
+                    master_list_id: id.clone(),
+                    name_id: mock_name_store_a().id,
+                }],
+                lines: vec![
+                    MasterListLineRow {
+                        id: format!("{}1", id),
+                        item_id: test_item_stats::item().id,
+                        master_list_id: id.clone(),
+                    },
+                    MasterListLineRow {
+                        id: format!("{}2", id),
+                        item_id: test_item_stats::item2().id,
+                        master_list_id: id.clone(),
+                    },
+                ],
+            }
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "run_reorder",
+            MockDataInserts::all(),
+            test_item_stats::mock_item_stats().join(inline_init(|r: &mut MockData| {
+                r.full_master_lists = vec![master_list()];
+            })),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.reorder_service;
+        let store_id = mock_store_a().id;
+
+        // NoSupplierConfigured, failed run is recorded
+        assert_eq!(
+            service.run_reorder(&context, &store_id),
+            Err(RunReorderError::NoSupplierConfigured)
+        );
+        let last_run = service
+            .get_last_reorder_run(&context, &store_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(last_run.requisition_id, None);
+        assert!(last_run.error.is_some());
+
+        // SupplierNotASupplier
+        assert_eq!(
+            service.update_reorder_config(
+                &context,
+                &store_id,
+                UpdateReorderConfig {
+                    supplier_id: Some(mock_name_store_b().id),
+                    min_months_of_stock: 1.0,
+                    max_months_of_stock: 2.0,
+                    ..Default::default()
+                },
+            ),
+            Err(UpdateReorderConfigError::SupplierNotASupplier)
+        );
+
+        // InvalidMonthsOfStock
+        assert_eq!(
+            service.update_reorder_config(
+                &context,
+                &store_id,
+                UpdateReorderConfig {
+                    supplier_id: Some(mock_name_store_c().id),
+                    min_months_of_stock: 3.0,
+                    max_months_of_stock: 2.0,
+                    ..Default::default()
+                },
+            ),
+            Err(UpdateReorderConfigError::InvalidMonthsOfStock)
+        );
+
+        service
+            .update_reorder_config(
+                &context,
+                &store_id,
+                UpdateReorderConfig {
+                    is_enabled: true,
+                    supplier_id: Some(mock_name_store_c().id),
+                    min_months_of_stock: 5.0,
+                    max_months_of_stock: 10.0,
+                },
+            )
+            .unwrap();
+
+        // Items below min months of stock are added to a new draft request requisition
+        let run = service.run_reorder(&context, &store_id).unwrap();
+        let requisition = RequisitionRowRepository::new(&connection)
+            .find_one_by_id(run.requisition_id.as_ref().unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(requisition.r#type, RequisitionRowType::Request);
+        assert_eq!(requisition.status, RequisitionRowStatus::Draft);
+        assert_eq!(requisition.name_id, mock_name_store_c().id);
+        assert_eq!(requisition.min_months_of_stock, 5.0);
+        assert_eq!(requisition.max_months_of_stock, 10.0);
+
+        let lines = get_lines_for_requisition(&connection, &requisition.id).unwrap();
+        assert_eq!(lines.len() as i32, run.number_of_items);
+        let line = lines
+            .iter()
+            .find(|line| line.requisition_line_row.item_id == test_item_stats::item().id)
+            .unwrap();
+        assert!(line.requisition_line_row.suggested_quantity > 0);
+        assert_eq!(
+            service.get_last_reorder_run(&context, &store_id),
+            Ok(Some(run.clone()))
+        );
+
+        // Items already on a draft request requisition are not reordered again
+        let second_run = service.run_reorder(&context, &store_id).unwrap();
+        if let Some(requisition_id) = &second_run.requisition_id {
+            assert!(get_lines_for_requisition(&connection, requisition_id)
+                .unwrap()
+                .iter()
+                .all(|line| line.requisition_line_row.item_id != test_item_stats::item().id));
+        }
+
+        // Scheduled run only runs once the interval has passed
+        let now = Utc::now().naive_utc();
+        assert_eq!(run_due_reorders(&context, now).unwrap().len(), 0);
+        let results = run_due_reorders(&context, now + Duration::hours(25)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0, store_id);
+    }
+
+    #[actix_rt::test]
+    async fn run_reorder_thresholds() {
+        fn item_no_stats() -> ItemRow {
+            inline_init(|r: &mut ItemRow| {
+                r.id = "reorder_item_no_stats".to_string();
+                r.name = r.id.clone();
+                r.code = r.id.clone();
+                r.r#type = ItemRowType::Stock;
+            })
+        }
+        fn item_zero_consumption() -> ItemRow {
+            inline_init(|r: &mut ItemRow| {
+                r.id = "reorder_item_zero_consumption".to_string();
+                r.name = r.id.clone();
+                r.code = r.id.clone();
+                r.r#type = ItemRowType::Stock;
+            })
+        }
+        fn stock_line_zero_consumption() -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = "reorder_stock_line_zero_consumption".to_string();
+                r.item_id = item_zero_consumption().id;
+                r.store_id = mock_store_a().id;
+                r.pack_size = 1;
+                r.available_number_of_packs = 5;
+                r.total_number_of_packs = 5;
+            })
+        }
+        fn master_list() -> FullMockMasterList {
+            let id = "reorder_thresholds_master_list".to_owned();
+            let item_ids = vec![
+                test_item_stats::item().id,
+                test_item_stats::item2().id,
+                item_no_stats().id,
+                item_zero_consumption().id,
+            ];
+            FullMockMasterList {
+                master_list: MasterListRow {
+                    id: id.clone(),
+                    name: id.clone(),
+                    code: id.clone(),
+                    description: id.clone(),
+                },
+                joins: vec![MasterListNameJoinRow {
+                    id: format!("{}1", id),
+                    master_list_id: id.clone(),
+                    name_id: mock_name_store_a().id,
+                }],
+                lines: item_ids
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, item_id)| MasterListLineRow {
+                        id: format!("{}{}", id, index),
+                        item_id,
+                        master_list_id: id.clone(),
+                    })
+                    .collect(),
+            }
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "run_reorder_thresholds",
+            MockDataInserts::all(),
+            test_item_stats::mock_item_stats().join(inline_init(|r: &mut MockData| {
+                r.items = vec![item_no_stats(), item_zero_consumption()];
+                r.stock_lines = vec![stock_line_zero_consumption()];
+                r.full_master_lists = vec![master_list()];
+            })),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.reorder_service;
+        let store_id = mock_store_a().id;
+        let item_ids_of_run = |run: &ReorderRunRow| -> Vec<String> {
+            let mut item_ids: Vec<String> =
+                get_lines_for_requisition(&connection, run.requisition_id.as_ref().unwrap())
+                    .unwrap()
+                    .into_iter()
+                    .map(|line| line.requisition_line_row.item_id)
+                    .collect();
+            item_ids.sort();
+            item_ids
+        };
+
+        // Disabled stores are not part of the scheduled run
+        let config = UpdateReorderConfig {
+            is_enabled: false,
+            supplier_id: Some(mock_name_store_c().id),
+            min_months_of_stock: 2.0,
+            max_months_of_stock: 4.0,
+        };
+        service
+            .update_reorder_config(&context, &store_id, config.clone())
+            .unwrap();
+        let now = Utc::now().naive_utc();
+        assert_eq!(run_due_reorders(&context, now).unwrap().len(), 0);
+
+        // item: ~0.65 months of stock (below min), item2: 2.2 months of stock (above min), items
+        // without stats or without consumption are never reordered
+        service
+            .update_reorder_config(
+                &context,
+                &store_id,
+                UpdateReorderConfig {
+                    is_enabled: true,
+                    ..config.clone()
+                },
+            )
+            .unwrap();
+        let mut results = run_due_reorders(&context, now).unwrap();
+        assert_eq!(results.len(), 1);
+        let run = results.pop().unwrap().1.unwrap();
+        assert_eq!(item_ids_of_run(&run), vec![test_item_stats::item().id]);
+
+        // Raising min months of stock above 2.2 reorders item2, item is already on the open
+        // draft requisition and is skipped
+        service
+            .update_reorder_config(
+                &context,
+                &store_id,
+                UpdateReorderConfig {
+                    is_enabled: true,
+                    min_months_of_stock: 3.0,
+                    ..config
+                },
+            )
+            .unwrap();
+        let run = service.run_reorder(&context, &store_id).unwrap();
+        assert_eq!(item_ids_of_run(&run), vec![test_item_stats::item2().id]);
+
+        // Nothing left to reorder, no requisition is created but the run is recorded
+        let run = service.run_reorder(&context, &store_id).unwrap();
+        assert_eq!(run.requisition_id, None);
+        assert_eq!(run.number_of_items, 0);
+        assert_eq!(run.error, None);
+        assert_eq!(
+            service.get_last_reorder_run(&context, &store_id),
+            Ok(Some(run))
+        );
+    }
+}
@@ -2,17 +2,20 @@ use self::{
     query::{get_requisition, get_requisition_by_number, get_requisitions},
     request_requisition::{
         add_from_master_list, batch_request_requisition, delete_request_requisition,
-        insert_request_requisition, update_request_requisition, use_suggested_quantity,
+        insert_program_request_requisition, insert_request_requisition, update_request_requisition, use_suggested_quantity,
         AddFromMasterList, AddFromMasterListError, BatchRequestRequisition,
         BatchRequestRequisitionResult, DeleteRequestRequisition, DeleteRequestRequisitionError,
+        InsertProgramRequestRequisition, InsertProgramRequestRequisitionError,
         InsertRequestRequisition, InsertRequestRequisitionError, UpdateRequestRequisition,
         UpdateRequestRequisitionError, UseSuggestedQuantity, UseSuggestedQuantityError,
     },
     requisition_supply_status::{get_requisitions_supply_statuses, RequisitionLineSupplyStatus},
     response_requisition::{
-        create_requisition_shipment, supply_requested_quantity, update_response_requisition,
-        CreateRequisitionShipment, CreateRequisitionShipmentError, SupplyRequestedQuantity,
-        SupplyRequestedQuantityError, UpdateResponseRequisition, UpdateResponseRequisitionError,
+        authorise_response_requisition, create_requisition_shipment, supply_requested_quantity,
+        update_response_requisition, AuthoriseResponseRequisition,
+        AuthoriseResponseRequisitionError, CreateRequisitionShipment,
+        CreateRequisitionShipmentError, SupplyRequestedQuantity, SupplyRequestedQuantityError,
+        UpdateResponseRequisition, UpdateResponseRequisitionError,
     },
 };
 
@@ -79,6 +82,16 @@ pub trait RequisitionServiceTrait: Sync + Send {
         insert_request_requisition(ctx, store_id, user_id, input)
     }
 
+    fn insert_program_request_requisition(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        input: InsertProgramRequestRequisition,
+    ) -> Result<Requisition, InsertProgramRequestRequisitionError> {
+        insert_program_request_requisition(ctx, store_id, user_id, input)
+    }
+
     fn update_request_requisition(
         &self,
         ctx: &ServiceContext,
@@ -125,6 +138,16 @@ pub trait RequisitionServiceTrait: Sync + Send {
         update_response_requisition(ctx, store_id, user_id, input)
     }
 
+    fn authorise_response_requisition(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        user_id: &str,
+        input: AuthoriseResponseRequisition,
+    ) -> Result<Requisition, AuthoriseResponseRequisitionError> {
+        authorise_response_requisition(ctx, store_id, user_id, input)
+    }
+
     fn supply_requested_quantity(
         &self,
         ctx: &ServiceContext,
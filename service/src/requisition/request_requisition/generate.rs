@@ -1,9 +1,14 @@
 use chrono::Utc;
-use repository::EqualFilter;
+use repository::{
+    ConsumptionCalculationMethod, DatetimeFilter, EqualFilter, InvoiceRowType, PeriodRow,
+    StockLedgerFilter, StockLedgerRepository, StorageConnection,
+};
 use repository::{RepositoryError, RequisitionLineRow, RequisitionRow};
+use util::constants::NUMBER_OF_DAYS_IN_A_MONTH;
 use util::uuid::uuid;
 
-use crate::item_stats::{get_item_stats, ItemStatsFilter};
+use crate::consumption_calculator::get_consumption_calculator;
+use crate::item_stats::{get_item_stats_with_calculator, ItemStatsFilter};
 use crate::service_provider::ServiceContext;
 
 pub struct GenerateSuggestedQuantity {
@@ -39,9 +44,11 @@ pub fn generate_requisition_lines(
     requisition_row: &RequisitionRow,
     item_ids: Vec<String>,
 ) -> Result<Vec<RequisitionLineRow>, RepositoryError> {
-    let item_stats_rows = get_item_stats(
-        ctx,
+    let calculator = get_consumption_calculator(&ctx.connection, store_id)?;
+    let item_stats_rows = get_item_stats_with_calculator(
+        &ctx.connection,
         store_id,
+        calculator.as_ref(),
         None,
         Some(ItemStatsFilter::new().item_id(EqualFilter::equal_any(item_ids))),
     )?;
@@ -51,7 +58,7 @@ pub fn generate_requisition_lines(
         .map(|item_stats| {
             let average_monthly_consumption = item_stats.average_monthly_consumption as i32;
             let available_stock_on_hand = item_stats.available_stock_on_hand as i32;
-            let suggested_quantity = generate_suggested_quantity(GenerateSuggestedQuantity {
+            let suggested_quantity = calculator.suggested_quantity(GenerateSuggestedQuantity {
                 average_monthly_consumption,
                 available_stock_on_hand,
                 min_months_of_stock: requisition_row.min_months_of_stock.clone(),
@@ -66,10 +73,142 @@ pub fn generate_requisition_lines(
                 available_stock_on_hand,
                 average_monthly_consumption,
                 snapshot_datetime: Some(Utc::now().naive_utc()),
+                consumption_calculation_method: calculator.method(),
+                // Default
+                comment: None,
+                supply_quantity: 0,
+                requested_quantity: 0,
+                approved_quantity: 0,
+                approval_comment: None,
+                opening_balance: 0,
+                received_quantity: 0,
+                issued_quantity: 0,
+                adjusted_quantity: 0,
+                closing_balance: 0,
+            }
+        })
+        .collect();
+
+    Ok(result)
+}
+
+/// Stock movements of an item in a store during a period
+#[derive(Debug, PartialEq, Default)]
+pub struct PeriodBalance {
+    pub item_id: String,
+    pub opening_balance: i32,
+    pub received_quantity: i32,
+    pub issued_quantity: i32,
+    /// Net inventory adjustments, negative if stock was removed
+    pub adjusted_quantity: i32,
+    pub closing_balance: i32,
+}
+
+/// Balances of the items over the period, in the order of the item ids
+pub fn get_period_balances(
+    connection: &StorageConnection,
+    store_id: &str,
+    period: &PeriodRow,
+    item_ids: Vec<String>,
+) -> Result<Vec<PeriodBalance>, RepositoryError> {
+    let period_start = period.start_date.and_hms(0, 0, 0);
+    let period_end = period.end_date.and_hms(23, 59, 59);
+
+    let movements = StockLedgerRepository::new(connection).query_by_filter(
+        StockLedgerFilter::new()
+            .store_id(EqualFilter::equal_to(store_id))
+            .item_id(EqualFilter::equal_any(item_ids.clone()))
+            .datetime(DatetimeFilter::before_or_equal_to(period_end)),
+    )?;
+
+    let mut balances: Vec<PeriodBalance> = item_ids
+        .into_iter()
+        .map(|item_id| PeriodBalance {
+            item_id,
+            ..Default::default()
+        })
+        .collect();
+
+    for movement in movements {
+        let balance = match balances
+            .iter_mut()
+            .find(|balance| balance.item_id == movement.item_id)
+        {
+            Some(balance) => balance,
+            None => continue,
+        };
+
+        if movement.datetime < period_start {
+            balance.opening_balance += movement.quantity;
+            continue;
+        }
+
+        match movement.invoice_type {
+            InvoiceRowType::InboundShipment | InvoiceRowType::CustomerReturn => {
+                balance.received_quantity += movement.quantity
+            }
+            InvoiceRowType::OutboundShipment
+            | InvoiceRowType::Prescription
+            | InvoiceRowType::SupplierReturn => balance.issued_quantity -= movement.quantity,
+            InvoiceRowType::InventoryAdjustment => balance.adjusted_quantity += movement.quantity,
+        }
+    }
+
+    for balance in balances.iter_mut() {
+        balance.closing_balance = balance.opening_balance + balance.received_quantity
+            - balance.issued_quantity
+            + balance.adjusted_quantity;
+    }
+
+    Ok(balances)
+}
+
+/// Lines of a programme requisition, consumption and stock on hand are based on the stock
+/// movements of the period rather than the stock at the time of the request
+pub fn generate_period_requisition_lines(
+    connection: &StorageConnection,
+    store_id: &str,
+    requisition_row: &RequisitionRow,
+    period: &PeriodRow,
+    item_ids: Vec<String>,
+) -> Result<Vec<RequisitionLineRow>, RepositoryError> {
+    let period_months =
+        ((period.end_date - period.start_date).num_days() + 1) as f64 / NUMBER_OF_DAYS_IN_A_MONTH;
+
+    let result = get_period_balances(connection, store_id, period, item_ids)?
+        .into_iter()
+        .map(|balance| {
+            let average_monthly_consumption =
+                (balance.issued_quantity as f64 / period_months) as i32;
+            let available_stock_on_hand = balance.closing_balance;
+            let suggested_quantity = generate_suggested_quantity(GenerateSuggestedQuantity {
+                average_monthly_consumption,
+                available_stock_on_hand,
+                min_months_of_stock: requisition_row.min_months_of_stock,
+                max_months_of_stock: requisition_row.max_months_of_stock,
+            });
+
+            RequisitionLineRow {
+                id: uuid(),
+                requisition_id: requisition_row.id.clone(),
+                item_id: balance.item_id,
+                suggested_quantity,
+                available_stock_on_hand,
+                average_monthly_consumption,
+                snapshot_datetime: Some(Utc::now().naive_utc()),
+                opening_balance: balance.opening_balance,
+                received_quantity: balance.received_quantity,
+                issued_quantity: balance.issued_quantity,
+                adjusted_quantity: balance.adjusted_quantity,
+                closing_balance: balance.closing_balance,
+                // Consumption of the period divided by its length
+                consumption_calculation_method: ConsumptionCalculationMethod::SimpleAverage,
                 // Default
                 comment: None,
                 supply_quantity: 0,
                 requested_quantity: 0,
+                approved_quantity: 0,
+                approval_comment: None,
             }
         })
         .collect();
@@ -126,6 +126,9 @@ fn generate(
         sent_datetime: None,
         finalised_datetime: None,
         linked_requisition_id: None,
+        program_id: None,
+        period_id: None,
+        approved_by_user_id: None,
     };
 
     Ok(result)
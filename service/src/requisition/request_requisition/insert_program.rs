@@ -0,0 +1,470 @@
+use crate::{
+    number::next_number,
+    requisition::{common::check_requisition_exists, query::get_requisition},
+    service_provider::ServiceContext,
+    validate::{check_other_party, CheckOtherPartyType, OtherPartyErrors},
+};
+use chrono::Utc;
+use repository::{
+    requisition_row::{RequisitionRow, RequisitionRowStatus, RequisitionRowType},
+    EqualFilter, MasterListLineFilter, MasterListLineRepository, NumberRowType, Pagination,
+    PeriodRow, PeriodRowRepository, ProgramRow, ProgramRowRepository, RepositoryError, Requisition,
+    RequisitionFilter, RequisitionLineRow, RequisitionLineRowRepository, RequisitionRepository,
+    RequisitionRowRepository, StorageConnection, TransactionError,
+};
+
+use super::generate_period_requisition_lines;
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct InsertProgramRequestRequisition {
+    pub id: String,
+    pub other_party_id: String,
+    pub program_id: String,
+    pub period_id: String,
+    pub their_reference: Option<String>,
+    pub comment: Option<String>,
+    pub max_months_of_stock: f64,
+    pub min_months_of_stock: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InsertProgramRequestRequisitionError {
+    RequisitionAlreadyExists,
+    ProgramDoesNotExist,
+    PeriodDoesNotExist,
+    /// Period belongs to a different period schedule than the one of the programme
+    PeriodNotInProgramSchedule,
+    /// The store already has a request requisition for the programme and period
+    ProgramRequisitionAlreadyExists(String),
+    // Name validation
+    OtherPartyNotASupplier,
+    OtherPartyDoesNotExist,
+    OtherPartyNotVisible,
+    OtherPartyIsNotAStore,
+    // Internal
+    NewlyCreatedRequisitionDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = InsertProgramRequestRequisitionError;
+
+/// Creates a request requisition for the period of a programme, with a line for every item of the
+/// programme's master list reporting the stock movements of the period
+pub fn insert_program_request_requisition(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    input: InsertProgramRequestRequisition,
+) -> Result<Requisition, OutError> {
+    let requisition = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (program, period) = validate(connection, store_id, &input)?;
+            let (program_id, period_id) = (program.id.clone(), period.id.clone());
+            let (new_requisition, new_lines) =
+                generate(connection, store_id, user_id, program, period, input)?;
+
+            // The unique index also catches requisitions for the programme period that were
+            // created since validation, e.g. by a concurrent request. Inserting in a sub
+            // transaction keeps the transaction usable for looking up the existing requisition.
+            let result = connection.transaction_sync_etc(
+                |con| RequisitionRowRepository::new(con).upsert_one(&new_requisition),
+                false,
+            );
+            match result {
+                Ok(()) => {}
+                Err(TransactionError::Inner(RepositoryError::UniqueViolation(_))) => {
+                    let existing_id =
+                        find_program_requisition_id(connection, store_id, &program_id, &period_id)?;
+                    return Err(match existing_id {
+                        Some(existing_id) => OutError::ProgramRequisitionAlreadyExists(existing_id),
+                        None => OutError::RequisitionAlreadyExists,
+                    });
+                }
+                Err(error) => return Err(RepositoryError::from(error).into()),
+            }
+            let line_repository = RequisitionLineRowRepository::new(connection);
+            for line in new_lines {
+                line_repository.upsert_one(&line)?;
+            }
+
+            get_requisition(ctx, None, &new_requisition.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::NewlyCreatedRequisitionDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(requisition)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &InsertProgramRequestRequisition,
+) -> Result<(ProgramRow, PeriodRow), OutError> {
+    if check_requisition_exists(connection, &input.id)?.is_some() {
+        return Err(OutError::RequisitionAlreadyExists);
+    }
+
+    let program = ProgramRowRepository::new(connection)
+        .find_one_by_id(&input.program_id)?
+        .ok_or(OutError::ProgramDoesNotExist)?;
+
+    let period = PeriodRowRepository::new(connection)
+        .find_one_by_id(&input.period_id)?
+        .ok_or(OutError::PeriodDoesNotExist)?;
+
+    if period.period_schedule_id != program.period_schedule_id {
+        return Err(OutError::PeriodNotInProgramSchedule);
+    }
+
+    if let Some(existing_id) =
+        find_program_requisition_id(connection, store_id, &program.id, &period.id)?
+    {
+        return Err(OutError::ProgramRequisitionAlreadyExists(existing_id));
+    }
+
+    let other_party = check_other_party(
+        connection,
+        store_id,
+        &input.other_party_id,
+        CheckOtherPartyType::Supplier,
+    )
+    .map_err(|e| match e {
+        OtherPartyErrors::OtherPartyDoesNotExist => OutError::OtherPartyDoesNotExist,
+        OtherPartyErrors::OtherPartyNotVisible => OutError::OtherPartyNotVisible,
+        OtherPartyErrors::TypeMismatched => OutError::OtherPartyNotASupplier,
+        OtherPartyErrors::DatabaseError(repository_error) => {
+            OutError::DatabaseError(repository_error)
+        }
+    })?;
+
+    other_party
+        .store_id()
+        .ok_or(OutError::OtherPartyIsNotAStore)?;
+
+    Ok((program, period))
+}
+
+fn find_program_requisition_id(
+    connection: &StorageConnection,
+    store_id: &str,
+    program_id: &str,
+    period_id: &str,
+) -> Result<Option<String>, RepositoryError> {
+    let existing = RequisitionRepository::new(connection).query_one(
+        RequisitionFilter::new()
+            .store_id(EqualFilter::equal_to(store_id))
+            .r#type(RequisitionRowType::Request.equal_to())
+            .program_id(EqualFilter::equal_to(program_id))
+            .period_id(EqualFilter::equal_to(period_id)),
+    )?;
+    Ok(existing.map(|existing| existing.requisition_row.id))
+}
+
+fn generate(
+    connection: &StorageConnection,
+    store_id: &str,
+    user_id: &str,
+    program: ProgramRow,
+    period: PeriodRow,
+    InsertProgramRequestRequisition {
+        id,
+        other_party_id,
+        program_id: _,
+        period_id: _,
+        their_reference,
+        comment,
+        max_months_of_stock,
+        min_months_of_stock,
+    }: InsertProgramRequestRequisition,
+) -> Result<(RequisitionRow, Vec<RequisitionLineRow>), RepositoryError> {
+    let requisition = RequisitionRow {
+        id,
+        user_id: Some(user_id.to_string()),
+        requisition_number: next_number(connection, &NumberRowType::RequestRequisition, store_id)?,
+        name_id: other_party_id,
+        store_id: store_id.to_owned(),
+        r#type: RequisitionRowType::Request,
+        status: RequisitionRowStatus::Draft,
+        created_datetime: Utc::now().naive_utc(),
+        comment,
+        their_reference,
+        max_months_of_stock,
+        min_months_of_stock,
+        program_id: Some(program.id),
+        period_id: Some(period.id.clone()),
+        // Default
+        colour: None,
+        expected_delivery_date: None,
+        sent_datetime: None,
+        finalised_datetime: None,
+        linked_requisition_id: None,
+        approved_by_user_id: None,
+    };
+
+    let item_ids = MasterListLineRepository::new(connection)
+        .query(
+            Pagination::all(),
+            Some(
+                MasterListLineFilter::new()
+                    .master_list_id(EqualFilter::equal_to(&program.master_list_id)),
+            ),
+        )?
+        .into_iter()
+        .map(|master_list_line| master_list_line.item_id)
+        .collect();
+
+    let lines =
+        generate_period_requisition_lines(connection, store_id, &requisition, &period, item_ids)?;
+
+    Ok((requisition, lines))
+}
+
+impl From<RepositoryError> for InsertProgramRequestRequisitionError {
+    fn from(error: RepositoryError) -> Self {
+        InsertProgramRequestRequisitionError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{
+            common::FullMockMasterList, mock_name_a, mock_name_store_a, mock_name_store_b,
+            mock_name_store_c, mock_period_2021_01, mock_period_schedule_monthly, mock_store_a,
+            MockData, MockDataInserts,
+        },
+        requisition_row::{RequisitionRowStatus, RequisitionRowType},
+        test_db::setup_all_with_data,
+        InvoiceLineRow, InvoiceLineRowType, InvoiceRow, InvoiceRowType, ItemRow, ItemRowType,
+        MasterListLineRow, MasterListNameJoinRow, MasterListRow, ProgramRow,
+    };
+    use util::inline_init;
+
+    use crate::{
+        requisition::{
+            common::get_lines_for_requisition,
+            request_requisition::{
+                InsertProgramRequestRequisition as ServiceInput,
+                InsertProgramRequestRequisitionError as ServiceError,
+            },
+        },
+        service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn insert_program_request_requisition() {
+        fn item() -> ItemRow {
+            inline_init(|r: &mut ItemRow| {
+                r.id = "program_item".to_string();
+                r.name = "program_item".to_string();
+                r.code = "program_item".to_string();
+                r.r#type = ItemRowType::Stock;
+            })
+        }
+
+        fn master_list() -> FullMockMasterList {
+            let id = "program_master_list".to_owned();
+            FullMockMasterList {
+                master_list: MasterListRow {
+                    id: id.clone(),
+                    name: id.clone(),
+                    code: id.clone(),
+                    description: id.clone(),
+                },
+                joins: vec![MasterListNameJoinRow {
+                    id: format!("{}1", id),
+                    master_list_id: id.clone(),
+                    name_id: mock_name_store_a().id,
+                }],
+                lines: vec![MasterListLineRow {
+                    id: format!("{}1", id),
+                    item_id: item().id,
+                    master_list_id: id.clone(),
+                }],
+            }
+        }
+
+        fn program() -> ProgramRow {
+            ProgramRow {
+                id: "test_program".to_string(),
+                name: "test_program".to_string(),
+                master_list_id: master_list().master_list.id,
+                period_schedule_id: mock_period_schedule_monthly().id,
+            }
+        }
+
+        fn movement(
+            id: &str,
+            r#type: InvoiceRowType,
+            line_type: InvoiceLineRowType,
+            date: NaiveDate,
+            number_of_packs: i32,
+        ) -> MockData {
+            let datetime = Some(date.and_hms(12, 0, 0));
+            inline_init(|r: &mut MockData| {
+                r.invoices = vec![inline_init(|r: &mut InvoiceRow| {
+                    r.id = id.to_string();
+                    r.store_id = mock_store_a().id;
+                    r.name_id = mock_name_a().id;
+                    r.r#type = r#type.clone();
+                    r.picked_datetime = datetime;
+                    r.delivered_datetime = datetime;
+                    r.verified_datetime = datetime;
+                })];
+                r.invoice_lines = vec![inline_init(|r: &mut InvoiceLineRow| {
+                    r.id = format!("{}line", id);
+                    r.invoice_id = id.to_string();
+                    r.item_id = item().id;
+                    r.r#type = line_type.clone();
+                    r.pack_size = 1;
+                    r.number_of_packs = number_of_packs;
+                })];
+            })
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "insert_program_request_requisition",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.items = vec![item()];
+                r.full_master_lists = vec![master_list()];
+                r.programs = vec![program()];
+            })
+            // Before the period
+            .join(movement(
+                "program_inbound_december",
+                InvoiceRowType::InboundShipment,
+                InvoiceLineRowType::StockIn,
+                NaiveDate::from_ymd(2020, 12, 15),
+                100,
+            ))
+            // During the period
+            .join(movement(
+                "program_inbound_january",
+                InvoiceRowType::InboundShipment,
+                InvoiceLineRowType::StockIn,
+                NaiveDate::from_ymd(2021, 1, 10),
+                50,
+            ))
+            .join(movement(
+                "program_outbound_january",
+                InvoiceRowType::OutboundShipment,
+                InvoiceLineRowType::StockOut,
+                NaiveDate::from_ymd(2021, 1, 15),
+                30,
+            ))
+            .join(movement(
+                "program_adjustment_january",
+                InvoiceRowType::InventoryAdjustment,
+                InvoiceLineRowType::StockOut,
+                NaiveDate::from_ymd(2021, 1, 31),
+                5,
+            ))
+            // After the period
+            .join(movement(
+                "program_outbound_february",
+                InvoiceRowType::OutboundShipment,
+                InvoiceLineRowType::StockOut,
+                NaiveDate::from_ymd(2021, 2, 1),
+                1000,
+            )),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.requisition_service;
+        let store_id = mock_store_a().id;
+
+        let input = ServiceInput {
+            id: "new_program_request_requisition".to_string(),
+            other_party_id: mock_name_store_c().id,
+            program_id: program().id,
+            period_id: mock_period_2021_01().id,
+            max_months_of_stock: 2.0,
+            min_months_of_stock: 1.0,
+            ..Default::default()
+        };
+
+        // ProgramDoesNotExist
+        assert_eq!(
+            service.insert_program_request_requisition(
+                &context,
+                &store_id,
+                "n/a",
+                inline_init(|r: &mut ServiceInput| {
+                    *r = input.clone();
+                    r.program_id = "invalid".to_string();
+                }),
+            ),
+            Err(ServiceError::ProgramDoesNotExist)
+        );
+
+        // PeriodDoesNotExist
+        assert_eq!(
+            service.insert_program_request_requisition(
+                &context,
+                &store_id,
+                "n/a",
+                inline_init(|r: &mut ServiceInput| {
+                    *r = input.clone();
+                    r.period_id = "invalid".to_string();
+                }),
+            ),
+            Err(ServiceError::PeriodDoesNotExist)
+        );
+
+        // OtherPartyNotASupplier
+        assert_eq!(
+            service.insert_program_request_requisition(
+                &context,
+                &store_id,
+                "n/a",
+                inline_init(|r: &mut ServiceInput| {
+                    *r = input.clone();
+                    r.other_party_id = mock_name_store_b().id;
+                }),
+            ),
+            Err(ServiceError::OtherPartyNotASupplier)
+        );
+
+        // Success
+        let result = service
+            .insert_program_request_requisition(&context, &store_id, "n/a", input.clone())
+            .unwrap();
+        let requisition = result.requisition_row;
+        assert_eq!(requisition.r#type, RequisitionRowType::Request);
+        assert_eq!(requisition.status, RequisitionRowStatus::Draft);
+        assert_eq!(requisition.program_id, Some(program().id));
+        assert_eq!(requisition.period_id, Some(mock_period_2021_01().id));
+
+        let lines = get_lines_for_requisition(&connection, &requisition.id).unwrap();
+        assert_eq!(lines.len(), 1);
+        let line = &lines[0].requisition_line_row;
+        assert_eq!(line.item_id, item().id);
+        assert_eq!(line.opening_balance, 100);
+        assert_eq!(line.received_quantity, 50);
+        assert_eq!(line.issued_quantity, 30);
+        assert_eq!(line.adjusted_quantity, -5);
+        assert_eq!(line.closing_balance, 115);
+        assert_eq!(line.available_stock_on_hand, 115);
+
+        // ProgramRequisitionAlreadyExists
+        assert_eq!(
+            service.insert_program_request_requisition(
+                &context,
+                &store_id,
+                "n/a",
+                inline_init(|r: &mut ServiceInput| {
+                    *r = input.clone();
+                    r.id = "another_program_request_requisition".to_string();
+                }),
+            ),
+            Err(ServiceError::ProgramRequisitionAlreadyExists(
+                requisition.id.clone()
+            ))
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use super::UpdateRequestRequisition;
-use crate::requisition::{
-    common::get_lines_for_requisition,
-    request_requisition::{generate_suggested_quantity, GenerateSuggestedQuantity},
+use crate::{
+    consumption_calculator::consumption_calculator,
+    requisition::{
+        common::get_lines_for_requisition, request_requisition::GenerateSuggestedQuantity,
+    },
+    store_preference::query::get_store_preference,
 };
 use chrono::Utc;
 use repository::{
@@ -60,6 +63,7 @@ pub fn generate(
     let updated_requisition_lines = if should_recalculate {
         generate_updated_lines(
             connection,
+            &updated_requisition_row.store_id,
             &updated_requisition_row.id,
             updated_requisition_row.min_months_of_stock,
             updated_requisition_row.max_months_of_stock,
@@ -71,13 +75,16 @@ pub fn generate(
     Ok((updated_requisition_row, updated_requisition_lines))
 }
 
+/// Suggested quantities are recalculated with the method that calculated the line
 pub fn generate_updated_lines(
     connection: &StorageConnection,
+    store_id: &str,
     requisition_id: &str,
     min_months_of_stock: f64,
     max_months_of_stock: f64,
 ) -> Result<Vec<RequisitionLineRow>, RepositoryError> {
     let lines = get_lines_for_requisition(connection, requisition_id)?;
+    let preference = get_store_preference(connection, store_id)?;
 
     let result = lines
         .into_iter()
@@ -86,8 +93,12 @@ pub fn generate_updated_lines(
                  mut requisition_line_row,
                  ..
              }| {
+                let calculator = consumption_calculator(
+                    requisition_line_row.consumption_calculation_method,
+                    &preference,
+                );
                 requisition_line_row.suggested_quantity =
-                    generate_suggested_quantity(GenerateSuggestedQuantity {
+                    calculator.suggested_quantity(GenerateSuggestedQuantity {
                         average_monthly_consumption: requisition_line_row
                             .average_monthly_consumption,
                         available_stock_on_hand: requisition_line_row.available_stock_on_hand,
@@ -0,0 +1,522 @@
+use crate::{
+    requisition::{
+        common::{check_requisition_exists, get_lines_for_requisition},
+        query::get_requisition,
+    },
+    service_provider::ServiceContext,
+};
+use repository::{
+    requisition_row::{RequisitionRow, RequisitionRowStatus, RequisitionRowType},
+    RepositoryError, Requisition, RequisitionLine, RequisitionLineRow,
+    RequisitionLineRowRepository, RequisitionRowRepository, StorageConnection,
+};
+
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct AuthoriseResponseRequisitionLine {
+    pub id: String,
+    pub approved_quantity: u32,
+    pub approval_comment: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct AuthoriseResponseRequisition {
+    pub id: String,
+    /// Lines that are not listed are approved at their supply quantity
+    pub lines: Vec<AuthoriseResponseRequisitionLine>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AuthoriseResponseRequisitionError {
+    RequisitionDoesNotExist,
+    NotThisStoreRequisition,
+    CannotEditRequisition,
+    NotAResponseRequisition,
+    /// Line with the id doesn't exist or belongs to another requisition
+    RequisitionLineDoesNotExist(String),
+    UpdatedRequisitionDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+type OutError = AuthoriseResponseRequisitionError;
+
+/// Approves a new response requisition, the supply quantity of each line is set to the approved
+/// quantity and the requisition can no longer be edited apart from being finalised
+pub fn authorise_response_requisition(
+    ctx: &ServiceContext,
+    store_id: &str,
+    user_id: &str,
+    input: AuthoriseResponseRequisition,
+) -> Result<Requisition, OutError> {
+    let requisition = ctx
+        .connection
+        .transaction_sync(|connection| {
+            let (requisition_row, lines) = validate(connection, store_id, &input)?;
+            let (updated_requisition, updated_lines) =
+                generate(user_id, requisition_row, lines, input);
+
+            RequisitionRowRepository::new(connection).upsert_one(&updated_requisition)?;
+            let line_repository = RequisitionLineRowRepository::new(connection);
+            for line in updated_lines {
+                line_repository.upsert_one(&line)?;
+            }
+
+            get_requisition(ctx, None, &updated_requisition.id)
+                .map_err(OutError::DatabaseError)?
+                .ok_or(OutError::UpdatedRequisitionDoesNotExist)
+        })
+        .map_err(|error| error.to_inner_error())?;
+
+    Ok(requisition)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &AuthoriseResponseRequisition,
+) -> Result<(RequisitionRow, Vec<RequisitionLine>), OutError> {
+    let requisition_row = check_requisition_exists(connection, &input.id)?
+        .ok_or(OutError::RequisitionDoesNotExist)?;
+
+    if requisition_row.store_id != store_id {
+        return Err(OutError::NotThisStoreRequisition);
+    }
+
+    if requisition_row.r#type != RequisitionRowType::Response {
+        return Err(OutError::NotAResponseRequisition);
+    }
+
+    if requisition_row.status != RequisitionRowStatus::New {
+        return Err(OutError::CannotEditRequisition);
+    }
+
+    let lines = get_lines_for_requisition(connection, &requisition_row.id)?;
+    for input_line in input.lines.iter() {
+        if !lines
+            .iter()
+            .any(|line| line.requisition_line_row.id == input_line.id)
+        {
+            return Err(OutError::RequisitionLineDoesNotExist(input_line.id.clone()));
+        }
+    }
+
+    Ok((requisition_row, lines))
+}
+
+fn generate(
+    user_id: &str,
+    mut requisition_row: RequisitionRow,
+    lines: Vec<RequisitionLine>,
+    AuthoriseResponseRequisition {
+        id: _,
+        lines: input_lines,
+    }: AuthoriseResponseRequisition,
+) -> (RequisitionRow, Vec<RequisitionLineRow>) {
+    requisition_row.status = RequisitionRowStatus::Authorised;
+    requisition_row.approved_by_user_id = Some(user_id.to_string());
+
+    let updated_lines = lines
+        .into_iter()
+        .map(
+            |RequisitionLine {
+                 mut requisition_line_row,
+                 ..
+             }| {
+                match input_lines
+                    .iter()
+                    .find(|input_line| input_line.id == requisition_line_row.id)
+                {
+                    Some(input_line) => {
+                        requisition_line_row.approved_quantity =
+                            input_line.approved_quantity as i32;
+                        requisition_line_row.approval_comment = input_line.approval_comment.clone();
+                    }
+                    None => {
+                        requisition_line_row.approved_quantity =
+                            requisition_line_row.supply_quantity;
+                    }
+                }
+                requisition_line_row.supply_quantity = requisition_line_row.approved_quantity;
+                requisition_line_row
+            },
+        )
+        .collect();
+
+    (requisition_row, updated_lines)
+}
+
+impl From<RepositoryError> for AuthoriseResponseRequisitionError {
+    fn from(error: RepositoryError) -> Self {
+        AuthoriseResponseRequisitionError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::{
+            mock_draft_response_requisition_for_update_test, mock_finalised_response_requisition,
+            mock_item_a, mock_item_b, mock_name_a, mock_new_response_requisition_test,
+            mock_sent_request_requisition, mock_store_a, MockData, MockDataInserts,
+        },
+        requisition_row::{RequisitionRow, RequisitionRowStatus, RequisitionRowType},
+        test_db::{setup_all, setup_all_with_data},
+        RequisitionLineRow, RequisitionLineRowRepository,
+    };
+    use util::inline_init;
+
+    use crate::{
+        requisition::response_requisition::{
+            AuthoriseResponseRequisition, AuthoriseResponseRequisitionError as ServiceError,
+            AuthoriseResponseRequisitionLine, CreateRequisitionShipment,
+            CreateRequisitionShipmentError, UpdateResponseRequisition,
+            UpdateResponseRequstionStatus,
+        },
+        service_provider::ServiceProvider,
+        store_preference::update::UpdateStorePreference,
+    };
+
+    #[actix_rt::test]
+    async fn authorise_response_requisition() {
+        let (_, connection, connection_manager, _) =
+            setup_all("authorise_response_requisition", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.requisition_service;
+
+        // RequisitionDoesNotExist
+        assert_eq!(
+            service.authorise_response_requisition(
+                &context,
+                "store_a",
+                "n/a",
+                AuthoriseResponseRequisition {
+                    id: "invalid".to_owned(),
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::RequisitionDoesNotExist)
+        );
+
+        // NotThisStoreRequisition
+        assert_eq!(
+            service.authorise_response_requisition(
+                &context,
+                "store_b",
+                "n/a",
+                AuthoriseResponseRequisition {
+                    id: mock_draft_response_requisition_for_update_test().id,
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::NotThisStoreRequisition)
+        );
+
+        // CannotEditRequisition
+        assert_eq!(
+            service.authorise_response_requisition(
+                &context,
+                "store_a",
+                "n/a",
+                AuthoriseResponseRequisition {
+                    id: mock_finalised_response_requisition().id,
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::CannotEditRequisition)
+        );
+
+        // NotAResponseRequisition
+        assert_eq!(
+            service.authorise_response_requisition(
+                &context,
+                "store_a",
+                "n/a",
+                AuthoriseResponseRequisition {
+                    id: mock_sent_request_requisition().id,
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::NotAResponseRequisition)
+        );
+
+        // RequisitionLineDoesNotExist
+        let requisition = mock_new_response_requisition_test();
+        assert_eq!(
+            service.authorise_response_requisition(
+                &context,
+                "store_a",
+                "n/a",
+                AuthoriseResponseRequisition {
+                    id: requisition.requisition.id.clone(),
+                    lines: vec![AuthoriseResponseRequisitionLine {
+                        id: "invalid".to_owned(),
+                        ..Default::default()
+                    }],
+                },
+            ),
+            Err(ServiceError::RequisitionLineDoesNotExist(
+                "invalid".to_owned()
+            ))
+        );
+
+        // RequisitionNotAuthorised when the store requires authorisation
+        service_provider
+            .store_preference_service
+            .update_store_preference(
+                &context,
+                "store_a",
+                UpdateStorePreference {
+                    requisitions_require_authorisation: Some(true),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            service.create_requisition_shipment(
+                &context,
+                "store_a",
+                "n/a",
+                CreateRequisitionShipment {
+                    response_requisition_id: requisition.requisition.id.clone(),
+                },
+            ),
+            Err(CreateRequisitionShipmentError::RequisitionNotAuthorised)
+        );
+
+        // Success
+        let result = service
+            .authorise_response_requisition(
+                &context,
+                "store_a",
+                "authoriser",
+                AuthoriseResponseRequisition {
+                    id: requisition.requisition.id.clone(),
+                    lines: vec![AuthoriseResponseRequisitionLine {
+                        id: requisition.lines[0].id.clone(),
+                        approved_quantity: 20,
+                        approval_comment: Some("Reduced, low stock".to_owned()),
+                    }],
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            result.requisition_row.status,
+            RequisitionRowStatus::Authorised
+        );
+        assert_eq!(
+            result.requisition_row.approved_by_user_id,
+            Some("authoriser".to_owned())
+        );
+        // Last editor is not replaced by the authoriser
+        assert_eq!(
+            result.requisition_row.user_id,
+            requisition.requisition.user_id
+        );
+
+        let line_repository = RequisitionLineRowRepository::new(&connection);
+        let approved_line = line_repository
+            .find_one_by_id(&requisition.lines[0].id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(approved_line.approved_quantity, 20);
+        assert_eq!(approved_line.supply_quantity, 20);
+        assert_eq!(
+            approved_line.approval_comment,
+            Some("Reduced, low stock".to_owned())
+        );
+        // Not listed, approved at supply quantity
+        let other_line = line_repository
+            .find_one_by_id(&requisition.lines[1].id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            other_line.approved_quantity,
+            requisition.lines[1].supply_quantity
+        );
+        assert_eq!(
+            other_line.supply_quantity,
+            requisition.lines[1].supply_quantity
+        );
+
+        // Can't authorise twice
+        assert_eq!(
+            service.authorise_response_requisition(
+                &context,
+                "store_a",
+                "n/a",
+                AuthoriseResponseRequisition {
+                    id: requisition.requisition.id.clone(),
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::CannotEditRequisition)
+        );
+
+        // Shipment can be created once authorised
+        assert!(service
+            .create_requisition_shipment(
+                &context,
+                "store_a",
+                "n/a",
+                CreateRequisitionShipment {
+                    response_requisition_id: requisition.requisition.id.clone(),
+                },
+            )
+            .is_ok());
+    }
+
+    #[actix_rt::test]
+    async fn authorise_response_requisition_errors() {
+        fn requisition() -> RequisitionRow {
+            inline_init(|r: &mut RequisitionRow| {
+                r.id = "authorise_errors_requisition".to_owned();
+                r.requisition_number = 10;
+                r.name_id = mock_name_a().id;
+                r.store_id = mock_store_a().id;
+                r.user_id = Some("creator".to_owned());
+                r.r#type = RequisitionRowType::Response;
+                r.status = RequisitionRowStatus::New;
+                r.created_datetime = NaiveDate::from_ymd(2021, 01, 01).and_hms(0, 0, 0);
+            })
+        }
+
+        fn line(id: &str, item_id: String) -> RequisitionLineRow {
+            inline_init(|r: &mut RequisitionLineRow| {
+                r.id = id.to_owned();
+                r.requisition_id = requisition().id;
+                r.item_id = item_id;
+                r.requested_quantity = 10;
+                r.supply_quantity = 10;
+            })
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "authorise_response_requisition_errors",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.requisitions = vec![requisition()];
+                r.requisition_lines = vec![
+                    line("authorise_errors_line1", mock_item_a().id),
+                    line("authorise_errors_line2", mock_item_b().id),
+                ];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.requisition_service;
+
+        // NotThisStoreRequisition
+        assert_eq!(
+            service.authorise_response_requisition(
+                &context,
+                "store_b",
+                "authoriser",
+                AuthoriseResponseRequisition {
+                    id: requisition().id,
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::NotThisStoreRequisition)
+        );
+
+        // Lines approved without a quantity, nothing can be supplied
+        let result = service
+            .authorise_response_requisition(
+                &context,
+                "store_a",
+                "authoriser",
+                AuthoriseResponseRequisition {
+                    id: requisition().id,
+                    lines: vec![
+                        AuthoriseResponseRequisitionLine {
+                            id: "authorise_errors_line1".to_owned(),
+                            approved_quantity: 0,
+                            approval_comment: Some("Out of stock".to_owned()),
+                        },
+                        AuthoriseResponseRequisitionLine {
+                            id: "authorise_errors_line2".to_owned(),
+                            approved_quantity: 0,
+                            approval_comment: None,
+                        },
+                    ],
+                },
+            )
+            .unwrap();
+        assert_eq!(result.requisition_row.user_id, Some("creator".to_owned()));
+
+        let line = RequisitionLineRowRepository::new(&connection)
+            .find_one_by_id("authorise_errors_line1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(line.approved_quantity, 0);
+        assert_eq!(line.supply_quantity, 0);
+
+        assert_eq!(
+            service.create_requisition_shipment(
+                &context,
+                "store_a",
+                "n/a",
+                CreateRequisitionShipment {
+                    response_requisition_id: requisition().id,
+                },
+            ),
+            Err(CreateRequisitionShipmentError::NothingRemainingToSupply)
+        );
+
+        // Shipment can't be created from another store
+        assert_eq!(
+            service.create_requisition_shipment(
+                &context,
+                "store_b",
+                "n/a",
+                CreateRequisitionShipment {
+                    response_requisition_id: requisition().id,
+                },
+            ),
+            Err(CreateRequisitionShipmentError::NotThisStoreRequisition)
+        );
+
+        // CannotEditRequisition, finalised after authorisation
+        service
+            .update_response_requisition(
+                &context,
+                "store_a",
+                "n/a",
+                UpdateResponseRequisition {
+                    id: requisition().id,
+                    colour: None,
+                    their_reference: None,
+                    comment: None,
+                    status: Some(UpdateResponseRequstionStatus::Finalised),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            service.authorise_response_requisition(
+                &context,
+                "store_a",
+                "authoriser",
+                AuthoriseResponseRequisition {
+                    id: requisition().id,
+                    ..Default::default()
+                },
+            ),
+            Err(ServiceError::CannotEditRequisition)
+        );
+        assert_eq!(
+            service.create_requisition_shipment(
+                &context,
+                "store_a",
+                "n/a",
+                CreateRequisitionShipment {
+                    response_requisition_id: requisition().id,
+                },
+            ),
+            Err(CreateRequisitionShipmentError::CannotEditRequisition)
+        );
+    }
+}
@@ -44,6 +44,9 @@ pub fn generate(
         verified_datetime: None,
         colour: None,
         linked_invoice_id: None,
+        prescriber: None,
+        diagnosis: None,
+        directions: None,
     };
 
     let invoice_line_rows = generate_invoice_lines(connection, &new_invoice.id, fullfilments)?;
@@ -83,6 +86,9 @@ pub fn generate_invoice_lines(
             sell_price_per_pack: 0.0,
             cost_price_per_pack: 0.0,
             stock_line_id: None,
+            original_invoice_line_id: None,
+            return_reason: None,
+            inventory_adjustment_reason_id: None,
         });
     }
 
@@ -23,6 +23,8 @@ pub enum CreateRequisitionShipmentError {
     NotThisStoreRequisition,
     CannotEditRequisition,
     NotAResponseRequisition,
+    /// Store requires response requisitions to be authorised before supplying them
+    RequisitionNotAuthorised,
     NothingRemainingToSupply,
     CreatedInvoiceDoesNotExist,
     ProblemGettingOtherParty,
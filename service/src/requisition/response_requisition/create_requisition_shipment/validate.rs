@@ -7,6 +7,7 @@ use crate::requisition::requisition_supply_status::RequisitionLineSupplyStatus;
 use crate::requisition::{
     common::check_requisition_exists, requisition_supply_status::get_requisitions_supply_statuses,
 };
+use crate::store_preference::query::get_store_preference;
 
 use super::{CreateRequisitionShipment, OutError};
 
@@ -26,10 +27,19 @@ pub fn validate(
         return Err(OutError::NotAResponseRequisition);
     }
 
-    if requisition_row.status != RequisitionRowStatus::New {
+    if !matches!(
+        requisition_row.status,
+        RequisitionRowStatus::New | RequisitionRowStatus::Authorised
+    ) {
         return Err(OutError::CannotEditRequisition);
     }
 
+    if requisition_row.status != RequisitionRowStatus::Authorised
+        && get_store_preference(connection, store_id)?.requisitions_require_authorisation
+    {
+        return Err(OutError::RequisitionNotAuthorised);
+    }
+
     let supply_statuses =
         get_requisitions_supply_statuses(connection, vec![requisition_row.id.clone()])?;
 
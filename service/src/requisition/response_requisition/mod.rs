@@ -6,3 +6,6 @@ pub use supply_requested_quantity::*;
 
 mod create_requisition_shipment;
 pub use create_requisition_shipment::*;
+
+mod authorise;
+pub use authorise::*;
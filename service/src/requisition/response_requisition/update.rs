@@ -1,4 +1,5 @@
 use crate::{
+    backorder::generate::generate_backorders,
     requisition::{common::check_requisition_exists, query::get_requisition},
     service_provider::ServiceContext,
     sync_processor::{process_records, Record},
@@ -6,7 +7,8 @@ use crate::{
 use chrono::Utc;
 use repository::{
     requisition_row::{RequisitionRow, RequisitionRowStatus, RequisitionRowType},
-    RepositoryError, Requisition, RequisitionRowRepository, StorageConnection,
+    BackorderRowRepository, RepositoryError, Requisition, RequisitionRowRepository,
+    StorageConnection,
 };
 use util::inline_edit;
 
@@ -47,9 +49,17 @@ pub fn update_response_requisition(
         .connection
         .transaction_sync(|connection| {
             let requisition_row = validate(connection, store_id, &input)?;
+            let is_finalising = input.status.is_some();
             let updated_requisition = generate(user_id, requisition_row, input);
             RequisitionRowRepository::new(&connection).upsert_one(&updated_requisition)?;
 
+            if is_finalising {
+                let backorder_repository = BackorderRowRepository::new(&connection);
+                for backorder in generate_backorders(connection, &updated_requisition)? {
+                    backorder_repository.upsert_one(&backorder)?;
+                }
+            }
+
             get_requisition(ctx, None, &updated_requisition.id)
                 .map_err(|error| OutError::DatabaseError(error))?
                 .ok_or(OutError::UpdatedRequisitionDoesNotExist)
@@ -83,7 +93,10 @@ pub fn validate(
         return Err(OutError::NotAResponseRequisition);
     }
 
-    if requisition_row.status != RequisitionRowStatus::New {
+    if !matches!(
+        requisition_row.status,
+        RequisitionRowStatus::New | RequisitionRowStatus::Authorised
+    ) {
         return Err(OutError::CannotEditRequisition);
     }
 
@@ -1,7 +1,7 @@
 use chrono::NaiveDate;
 use repository::{
-    requisition_row::RequisitionRowType, RepositoryError, RequisitionLine, RequisitionLineRow,
-    StorageConnection,
+    requisition_row::RequisitionRowType, ConsumptionCalculationMethod, RepositoryError,
+    RequisitionLine, RequisitionLineRow, StorageConnection,
 };
 mod historic_consumption;
 pub use historic_consumption::*;
@@ -9,7 +9,11 @@ pub use historic_consumption::*;
 mod stock_evolution;
 pub use stock_evolution::*;
 
-use crate::service_provider::ServiceContext;
+use crate::{
+    consumption_calculator::{consumption_calculator, ConsumptionCalculator},
+    service_provider::ServiceContext,
+    store_preference::query::get_store_preference,
+};
 
 use super::common::check_requisition_line_exists;
 
@@ -31,6 +35,8 @@ pub struct SuggestedQuantityCalculation {
     pub minimum_stock_on_hand: f64,
     pub maximum_stock_on_hand: f64,
     pub suggested: u32,
+    /// Method that calculated the average monthly consumption and suggested quantity
+    pub consumption_calculation_method: ConsumptionCalculationMethod,
 }
 
 #[derive(Debug, PartialEq, Default)]
@@ -51,8 +57,14 @@ pub fn get_requisition_line_chart(
     // Validate
     let requisition_line = validate(&ctx.connection, store_id, requisition_line_id)?;
 
+    let calculator = consumption_calculator(
+        requisition_line
+            .requisition_line_row
+            .consumption_calculation_method,
+        &get_store_preference(&ctx.connection, store_id)?,
+    );
     let suggested_quantity_calculation =
-        SuggestedQuantityCalculation::from_requisition_line(&requisition_line);
+        SuggestedQuantityCalculation::from_requisition_line(&requisition_line, calculator.as_ref());
 
     let (expected_delivery_date, requisition_line_datetime) = match (
         &requisition_line.requisition_row.expected_delivery_date,
@@ -144,16 +156,24 @@ impl From<RepositoryError> for OutError {
 }
 
 impl SuggestedQuantityCalculation {
-    pub fn from_requisition_line(from: &RequisitionLine) -> Self {
+    pub fn from_requisition_line(
+        from: &RequisitionLine,
+        calculator: &dyn ConsumptionCalculator,
+    ) -> Self {
+        let (min_months_of_stock, max_months_of_stock) = calculator.months_of_stock(
+            from.requisition_row.min_months_of_stock,
+            from.requisition_row.max_months_of_stock,
+        );
         SuggestedQuantityCalculation {
             average_monthly_consumption: from.requisition_line_row.average_monthly_consumption
                 as f64,
             stock_on_hand: from.requisition_line_row.available_stock_on_hand as u32,
             minimum_stock_on_hand: from.requisition_line_row.average_monthly_consumption as f64
-                * from.requisition_row.min_months_of_stock,
+                * min_months_of_stock,
             maximum_stock_on_hand: from.requisition_line_row.average_monthly_consumption as f64
-                * from.requisition_row.max_months_of_stock as f64,
+                * max_months_of_stock,
             suggested: from.requisition_line_row.suggested_quantity as u32,
+            consumption_calculation_method: calculator.method(),
         }
     }
 }
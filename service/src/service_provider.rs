@@ -4,23 +4,38 @@ use repository::{
 };
 
 use crate::{
+    alert::{AlertService, AlertServiceTrait},
+    backorder::{BackorderService, BackorderServiceTrait},
+    barcode::{BarcodeService, BarcodeServiceTrait},
+    cycle_count::{CycleCountService, CycleCountServiceTrait},
     dashboard::{
         invoice_count::{InvoiceCountService, InvoiceCountServiceTrait},
         stock_expiry_count::{StockExpiryCountServiceTrait, StockExpiryServiceCount},
     },
+    inventory_adjustment_reason::{
+        InventoryAdjustmentReasonService, InventoryAdjustmentReasonServiceTrait,
+    },
     invoice::{InvoiceService, InvoiceServiceTrait},
     invoice_line::{InvoiceLineService, InvoiceLineServiceTrait},
     item_stats::{ItemStatsService, ItemStatsServiceTrait},
+    local_user::{LocalUserService, LocalUserServiceTrait},
     location::{LocationService, LocationServiceTrait},
     master_list::{MasterListService, MasterListServiceTrait},
     name::get_names,
+    patient::{PatientService, PatientServiceTrait},
     permission_validation::{ValidationService, ValidationServiceTrait},
+    program::{ProgramService, ProgramServiceTrait},
+    reorder::{ReorderService, ReorderServiceTrait},
     report::report_service::{ReportService, ReportServiceTrait},
     requisition::{RequisitionService, RequisitionServiceTrait},
     requisition_line::{RequisitionLineService, RequisitionLineServiceTrait},
+    stock_ledger::{StockLedgerService, StockLedgerServiceTrait},
     stocktake::{StocktakeService, StocktakeServiceTrait},
     stocktake_line::{StocktakeLineService, StocktakeLineServiceTrait},
     store::get_stores,
+    store_preference::{StorePreferenceService, StorePreferenceServiceTrait},
+    sync_error::{SyncErrorService, SyncErrorServiceTrait},
+    sync_status::{SyncStatusService, SyncStatusServiceTrait},
     ListError, ListResult,
 };
 
@@ -33,15 +48,30 @@ pub struct ServiceProvider {
     pub master_list_service: Box<dyn MasterListServiceTrait>,
     pub stocktake_service: Box<dyn StocktakeServiceTrait>,
     pub stocktake_line_service: Box<dyn StocktakeLineServiceTrait>,
+    pub cycle_count_service: Box<dyn CycleCountServiceTrait>,
     pub invoice_line_service: Box<dyn InvoiceLineServiceTrait>,
     pub requisition_service: Box<dyn RequisitionServiceTrait>,
     pub requisition_line_service: Box<dyn RequisitionLineServiceTrait>,
+    pub reorder_service: Box<dyn ReorderServiceTrait>,
+    pub backorder_service: Box<dyn BackorderServiceTrait>,
+    pub program_service: Box<dyn ProgramServiceTrait>,
     pub general_service: Box<dyn GeneralServiceTrait>,
+    pub local_user_service: Box<dyn LocalUserServiceTrait>,
+    pub patient_service: Box<dyn PatientServiceTrait>,
+    pub inventory_adjustment_reason_service: Box<dyn InventoryAdjustmentReasonServiceTrait>,
     // Dashboard:
+    pub alert_service: Box<dyn AlertServiceTrait>,
     pub invoice_count_service: Box<dyn InvoiceCountServiceTrait>,
     pub stock_expiry_count_service: Box<dyn StockExpiryCountServiceTrait>,
     // Stock stats
     pub item_stats_service: Box<dyn ItemStatsServiceTrait>,
+    pub stock_ledger_service: Box<dyn StockLedgerServiceTrait>,
+    pub barcode_service: Box<dyn BarcodeServiceTrait>,
+    // Store preferences
+    pub store_preference_service: Box<dyn StorePreferenceServiceTrait>,
+    // Sync
+    pub sync_status_service: Box<dyn SyncStatusServiceTrait>,
+    pub sync_error_service: Box<dyn SyncErrorServiceTrait>,
 
     // Reports
     pub report_service: Box<dyn ReportServiceTrait>,
@@ -59,15 +89,28 @@ impl ServiceProvider {
             location_service: Box::new(LocationService {}),
             master_list_service: Box::new(MasterListService {}),
             invoice_line_service: Box::new(InvoiceLineService {}),
+            alert_service: Box::new(AlertService {}),
             invoice_count_service: Box::new(InvoiceCountService {}),
             invoice_service: Box::new(InvoiceService {}),
             stock_expiry_count_service: Box::new(StockExpiryServiceCount {}),
             stocktake_service: Box::new(StocktakeService {}),
             stocktake_line_service: Box::new(StocktakeLineService {}),
+            cycle_count_service: Box::new(CycleCountService {}),
             requisition_service: Box::new(RequisitionService {}),
             requisition_line_service: Box::new(RequisitionLineService {}),
+            reorder_service: Box::new(ReorderService {}),
+            backorder_service: Box::new(BackorderService {}),
+            program_service: Box::new(ProgramService {}),
             item_stats_service: Box::new(ItemStatsService {}),
+            stock_ledger_service: Box::new(StockLedgerService {}),
+            barcode_service: Box::new(BarcodeService {}),
+            store_preference_service: Box::new(StorePreferenceService {}),
+            sync_status_service: Box::new(SyncStatusService {}),
+            sync_error_service: Box::new(SyncErrorService {}),
             general_service: Box::new(GeneralService {}),
+            local_user_service: Box::new(LocalUserService {}),
+            patient_service: Box::new(PatientService {}),
+            inventory_adjustment_reason_service: Box::new(InventoryAdjustmentReasonService {}),
             report_service: Box::new(ReportService {}),
         }
     }
@@ -1,7 +1,13 @@
 use repository::{KeyValueStoreRepository, KeyValueType, RepositoryError};
 use reqwest::Url;
 
-use crate::{service_provider::ServiceContext, sync_settings::SyncSettings};
+use crate::{
+    service_provider::ServiceContext,
+    sync_settings::{
+        SyncSettings, DEFAULT_MAX_BACKOFF_SEC, DEFAULT_MAX_JITTER_SEC,
+        DEFAULT_PARTIAL_FAILURE_RETRY_SEC,
+    },
+};
 
 #[derive(Debug)]
 pub enum UpdateSettingsError {
@@ -41,6 +47,19 @@ pub trait SettingsServiceTrait: Sync + Send {
         let site_id = key_value_store.get_i32(KeyValueType::SettingsSyncSideId)?;
         let site_hardware_id =
             key_value_store.get_string(KeyValueType::SettingsSyncSideHardwareId)?;
+        // Retry settings have been added later and fall back to defaults if not set
+        let max_backoff_sec = key_value_store
+            .get_i64(KeyValueType::SettingsSyncMaxBackoffSec)?
+            .map(|value| value as u64)
+            .unwrap_or(DEFAULT_MAX_BACKOFF_SEC);
+        let partial_failure_retry_sec = key_value_store
+            .get_i64(KeyValueType::SettingsSyncPartialFailureRetrySec)?
+            .map(|value| value as u64)
+            .unwrap_or(DEFAULT_PARTIAL_FAILURE_RETRY_SEC);
+        let max_jitter_sec = key_value_store
+            .get_i64(KeyValueType::SettingsSyncMaxJitterSec)?
+            .map(|value| value as u64)
+            .unwrap_or(DEFAULT_MAX_JITTER_SEC);
 
         let make_settings = || {
             Some(SyncSettings {
@@ -51,6 +70,9 @@ pub trait SettingsServiceTrait: Sync + Send {
                 central_server_site_id: central_server_site_id? as u32,
                 site_id: site_id? as u32,
                 site_hardware_id: site_hardware_id?,
+                max_backoff_sec,
+                partial_failure_retry_sec,
+                max_jitter_sec,
             })
         };
 
@@ -94,6 +116,18 @@ pub trait SettingsServiceTrait: Sync + Send {
                     KeyValueType::SettingsSyncSideHardwareId,
                     Some(settings.site_hardware_id.clone()),
                 )?;
+                key_value_store.set_i64(
+                    KeyValueType::SettingsSyncMaxBackoffSec,
+                    Some(settings.max_backoff_sec as i64),
+                )?;
+                key_value_store.set_i64(
+                    KeyValueType::SettingsSyncPartialFailureRetrySec,
+                    Some(settings.partial_failure_retry_sec as i64),
+                )?;
+                key_value_store.set_i64(
+                    KeyValueType::SettingsSyncMaxJitterSec,
+                    Some(settings.max_jitter_sec as i64),
+                )?;
                 Ok(())
             })
             .map_err(|err| UpdateSettingsError::RepositoryError(err.to_inner_error()))?;
@@ -0,0 +1,22 @@
+use self::query::get_stock_ledger;
+
+use crate::{service_provider::ServiceContext, ListError, ListResult};
+use repository::{PaginationOption, StockLedger, StockLedgerFilter, StockLedgerSort};
+
+pub mod query;
+
+pub trait StockLedgerServiceTrait: Sync + Send {
+    fn get_stock_ledger(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        pagination: Option<PaginationOption>,
+        filter: Option<StockLedgerFilter>,
+        sort: Option<StockLedgerSort>,
+    ) -> Result<ListResult<StockLedger>, ListError> {
+        get_stock_ledger(ctx, store_id, pagination, filter, sort)
+    }
+}
+
+pub struct StockLedgerService {}
+impl StockLedgerServiceTrait for StockLedgerService {}
@@ -0,0 +1,93 @@
+use repository::{
+    EqualFilter, PaginationOption, StockLedger, StockLedgerFilter, StockLedgerRepository,
+    StockLedgerSort,
+};
+
+use crate::{
+    get_default_pagination, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
+};
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+/// Lists the stock movements of a store, the store filter is always applied
+pub fn get_stock_ledger(
+    ctx: &ServiceContext,
+    store_id: &str,
+    pagination: Option<PaginationOption>,
+    filter: Option<StockLedgerFilter>,
+    sort: Option<StockLedgerSort>,
+) -> Result<ListResult<StockLedger>, ListError> {
+    let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+    let filter = filter
+        .unwrap_or_default()
+        .store_id(EqualFilter::equal_to(store_id));
+    let repository = StockLedgerRepository::new(&ctx.connection);
+
+    Ok(ListResult {
+        rows: repository.query(pagination, Some(filter.clone()), sort)?,
+        count: i64_to_u32(repository.count(Some(filter))?),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{mock_stock_line_a, mock_store_a, mock_store_b, MockDataInserts},
+        test_db::setup_all,
+        EqualFilter, InventoryAdjustmentType, InvoiceRowType, StockLedgerFilter,
+    };
+    use util::inline_init;
+
+    use crate::{
+        invoice::inventory_adjustment::InsertInventoryAdjustment, service_provider::ServiceProvider,
+    };
+
+    #[actix_rt::test]
+    async fn stock_ledger_service_queries() {
+        let (_, _, connection_manager, _) =
+            setup_all("stock_ledger_service_queries", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.stock_ledger_service;
+
+        service_provider
+            .invoice_service
+            .insert_inventory_adjustment(
+                &context,
+                &mock_store_a().id,
+                "n/a",
+                inline_init(|r: &mut InsertInventoryAdjustment| {
+                    r.id = "stock_ledger_adjustment".to_string();
+                    r.stock_line_id = mock_stock_line_a().id;
+                    r.r#type = InventoryAdjustmentType::Negative;
+                    r.number_of_packs = 2;
+                }),
+            )
+            .unwrap();
+
+        let filter = StockLedgerFilter::new()
+            .stock_line_id(EqualFilter::equal_to(&mock_stock_line_a().id))
+            .invoice_type(InvoiceRowType::InventoryAdjustment.equal_to());
+
+        let result = service
+            .get_stock_ledger(
+                &context,
+                &mock_store_a().id,
+                None,
+                Some(filter.clone()),
+                None,
+            )
+            .unwrap();
+        assert_eq!(result.count, 1);
+        assert_eq!(result.rows[0].invoice_id, "stock_ledger_adjustment");
+        assert_eq!(result.rows[0].quantity, -2 * mock_stock_line_a().pack_size);
+
+        // Movements of other stores are never listed
+        let result = service
+            .get_stock_ledger(&context, &mock_store_b().id, None, Some(filter), None)
+            .unwrap();
+        assert_eq!(result.count, 0);
+    }
+}
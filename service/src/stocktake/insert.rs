@@ -1,10 +1,17 @@
 use chrono::{NaiveDate, Utc};
 use repository::{
-    EqualFilter, NumberRowType, RepositoryError, Stocktake, StocktakeFilter, StocktakeRepository,
-    StocktakeRow, StocktakeRowRepository, StocktakeStatus, StorageConnection,
+    DateFilter, EqualFilter, MasterListLineFilter, MasterListLineRepository, NumberRowType,
+    Pagination, RepositoryError, StockLineFilter, StockLineRepository, Stocktake, StocktakeFilter,
+    StocktakeLineRow, StocktakeLineRowRepository, StocktakeRepository, StocktakeRow,
+    StocktakeRowRepository, StocktakeStatus, StorageConnection,
 };
+use util::uuid::uuid;
 
-use crate::{number::next_number, service_provider::ServiceContext, validate::check_store_exists};
+use crate::{
+    number::next_number, requisition::request_requisition::check_master_list_for_store,
+    service_provider::ServiceContext, stocktake_line::validate::check_location_exists, u32_to_i32,
+    validate::check_store_exists,
+};
 
 use super::query::get_stocktake;
 
@@ -15,6 +22,25 @@ pub struct InsertStocktake {
     pub description: Option<String>,
     pub stocktake_date: Option<NaiveDate>,
     pub is_locked: Option<bool>,
+    /// Hides the snapshot quantities until the count is submitted and requires a second user to
+    /// finalise the stocktake
+    pub is_blind_count: Option<bool>,
+    /// Blind count lines with a larger discrepancy (in packs) need to be recounted
+    pub recount_threshold: Option<u32>,
+    /// Adds a line for every stock line of the store that is in stock and matches all the set
+    /// options, the snapshot quantities are taken when the stocktake is inserted
+    pub generate_lines: Option<GenerateStocktakeLines>,
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct GenerateStocktakeLines {
+    pub location_id: Option<String>,
+    /// Only stock lines of items on the master list
+    pub master_list_id: Option<String>,
+    /// Only batches expiring before this date
+    pub expires_before: Option<NaiveDate>,
+    /// Only stock lines of these items
+    pub item_ids: Option<Vec<String>>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -23,6 +49,8 @@ pub enum InsertStocktakeError {
     InternalError(String),
     StocktakeAlreadyExists,
     InvalidStore,
+    LocationDoesNotExist,
+    MasterListNotFoundForThisStore,
 }
 
 fn check_stocktake_does_not_exist(
@@ -45,6 +73,17 @@ fn validate(
     if !check_store_exists(connection, store_id)? {
         return Err(InsertStocktakeError::InvalidStore);
     }
+    if let Some(generate_lines) = &stocktake.generate_lines {
+        if let Some(location_id) = &generate_lines.location_id {
+            if !check_location_exists(connection, location_id)? {
+                return Err(InsertStocktakeError::LocationDoesNotExist);
+            }
+        }
+        if let Some(master_list_id) = &generate_lines.master_list_id {
+            check_master_list_for_store(connection, store_id, master_list_id)?
+                .ok_or(InsertStocktakeError::MasterListNotFoundForThisStore)?;
+        }
+    }
     Ok(())
 }
 
@@ -58,6 +97,9 @@ fn generate(
         description,
         stocktake_date,
         is_locked,
+        is_blind_count,
+        recount_threshold,
+        generate_lines: _,
     }: InsertStocktake,
 ) -> Result<StocktakeRow, RepositoryError> {
     let stocktake_number = next_number(connection, &NumberRowType::Stocktake, store_id)?;
@@ -73,12 +115,85 @@ fn generate(
         user_id: user_id.to_string(),
         store_id: store_id.to_string(),
         is_locked: is_locked.unwrap_or(false),
+        is_blind_count: is_blind_count.unwrap_or(false),
+        recount_threshold: recount_threshold.map(u32_to_i32),
         // Default
         finalised_datetime: None,
         inventory_adjustment_id: None,
+        counted_by: None,
+        count_submitted_datetime: None,
+        verified_by: None,
     })
 }
 
+fn generate_lines(
+    connection: &StorageConnection,
+    store_id: &str,
+    stocktake_id: &str,
+    GenerateStocktakeLines {
+        location_id,
+        master_list_id,
+        expires_before,
+        item_ids,
+    }: GenerateStocktakeLines,
+) -> Result<Vec<StocktakeLineRow>, RepositoryError> {
+    let mut filter = StockLineFilter::new().store_id(EqualFilter::equal_to(store_id));
+    if let Some(location_id) = location_id {
+        filter = filter.location_id(EqualFilter::equal_to(&location_id));
+    }
+    if let Some(master_list_id) = master_list_id {
+        let master_list_item_ids = MasterListLineRepository::new(connection)
+            .query(
+                Pagination::all(),
+                Some(
+                    MasterListLineFilter::new()
+                        .master_list_id(EqualFilter::equal_to(&master_list_id)),
+                ),
+            )?
+            .into_iter()
+            .map(|line| line.item_id)
+            .filter(|item_id| {
+                item_ids
+                    .as_ref()
+                    .map(|item_ids| item_ids.contains(item_id))
+                    .unwrap_or(true)
+            })
+            .collect();
+        filter = filter.item_id(EqualFilter::equal_any(master_list_item_ids));
+    } else if let Some(item_ids) = item_ids {
+        filter = filter.item_id(EqualFilter::equal_any(item_ids));
+    }
+    if let Some(expires_before) = expires_before {
+        filter = filter.expiry_date(DateFilter::before_or_equal_to(expires_before.pred()));
+    }
+
+    let lines = StockLineRepository::new(connection)
+        .query(Pagination::all(), Some(filter), None)?
+        .into_iter()
+        .map(|stock_line| stock_line.stock_line_row)
+        .filter(|stock_line| stock_line.total_number_of_packs > 0)
+        .map(|stock_line| StocktakeLineRow {
+            id: uuid(),
+            stocktake_id: stocktake_id.to_string(),
+            stock_line_id: Some(stock_line.id),
+            location_id: stock_line.location_id,
+            comment: None,
+            snapshot_number_of_packs: stock_line.total_number_of_packs,
+            counted_number_of_packs: None,
+            second_counted_number_of_packs: None,
+            item_id: stock_line.item_id,
+            batch: None,
+            expiry_date: None,
+            pack_size: None,
+            cost_price_per_pack: None,
+            sell_price_per_pack: None,
+            note: None,
+            inventory_adjustment_reason_id: None,
+        })
+        .collect();
+    Ok(lines)
+}
+
 pub fn insert_stocktake(
     ctx: &ServiceContext,
     store_id: &str,
@@ -89,9 +204,17 @@ pub fn insert_stocktake(
         .connection
         .transaction_sync(|connection| {
             validate(connection, store_id, &input)?;
+            let options = input.generate_lines.clone();
             let new_stocktake = generate(connection, store_id, user_id, input)?;
             StocktakeRowRepository::new(&connection).upsert_one(&new_stocktake)?;
 
+            if let Some(options) = options {
+                let line_repository = StocktakeLineRowRepository::new(connection);
+                for line in generate_lines(connection, store_id, &new_stocktake.id, options)? {
+                    line_repository.upsert_one(&line)?;
+                }
+            }
+
             let stocktake = get_stocktake(ctx, new_stocktake.id)?;
             stocktake.ok_or(InsertStocktakeError::InternalError(
                 "Failed to read the just inserted stocktake!".to_string(),
@@ -111,15 +234,20 @@ impl From<RepositoryError> for InsertStocktakeError {
 mod test {
     use chrono::{NaiveDate, Utc};
     use repository::{
-        mock::{mock_stocktake_a, mock_store_a, mock_user_account_a, MockDataInserts},
-        test_db::setup_all,
-        StocktakeRow, StocktakeRowRepository, StocktakeStatus,
+        mock::{
+            common::FullMockMasterList, mock_item_a, mock_name_store_a, mock_stocktake_a,
+            mock_store_a, mock_user_account_a, MockData, MockDataInserts,
+        },
+        test_db::{setup_all, setup_all_with_data},
+        EqualFilter, ItemRow, LocationRow, MasterListLineRow, MasterListNameJoinRow, MasterListRow,
+        StockLineFilter, StockLineRepository, StockLineRow, StocktakeLineFilter,
+        StocktakeLineRepository, StocktakeRow, StocktakeRowRepository, StocktakeStatus,
     };
     use util::{inline_edit, inline_init};
 
     use crate::{
         service_provider::ServiceProvider,
-        stocktake::insert::{InsertStocktake, InsertStocktakeError},
+        stocktake::insert::{GenerateStocktakeLines, InsertStocktake, InsertStocktakeError},
     };
 
     #[actix_rt::test]
@@ -172,6 +300,9 @@ mod test {
                     description: Some("description".to_string()),
                     stocktake_date: Some(NaiveDate::from_ymd(2020, 01, 02)),
                     is_locked: Some(true),
+                    is_blind_count: Some(true),
+                    recount_threshold: Some(5),
+                    generate_lines: None,
                 },
             )
             .unwrap();
@@ -192,6 +323,8 @@ mod test {
                 i.description = Some("description".to_string());
                 i.stocktake_date = Some(NaiveDate::from_ymd(2020, 01, 02));
                 i.is_locked = true;
+                i.is_blind_count = true;
+                i.recount_threshold = Some(5);
                 i.status = StocktakeStatus::New;
                 i.store_id = store_a.id;
                 i
@@ -202,4 +335,216 @@ mod test {
             new_row.created_datetime > before_insert && new_row.created_datetime < after_insert
         );
     }
+
+    #[actix_rt::test]
+    async fn insert_stocktake_generate_lines() {
+        fn location() -> LocationRow {
+            inline_init(|r: &mut LocationRow| {
+                r.id = "generate_location".to_string();
+                r.store_id = mock_store_a().id;
+            })
+        }
+
+        fn item() -> ItemRow {
+            inline_init(|r: &mut ItemRow| {
+                r.id = "generate_item".to_string();
+            })
+        }
+
+        fn stock_line(
+            id: &str,
+            item_id: &str,
+            location_id: Option<String>,
+            expiry_date: NaiveDate,
+            total_number_of_packs: i32,
+        ) -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = id.to_string();
+                r.item_id = item_id.to_string();
+                r.store_id = mock_store_a().id;
+                r.location_id = location_id;
+                r.expiry_date = Some(expiry_date);
+                r.pack_size = 1;
+                r.total_number_of_packs = total_number_of_packs;
+                r.available_number_of_packs = total_number_of_packs;
+            })
+        }
+
+        fn master_list() -> FullMockMasterList {
+            let id = "generate_master_list".to_string();
+            FullMockMasterList {
+                master_list: MasterListRow {
+                    id: id.clone(),
+                    name: id.clone(),
+                    code: id.clone(),
+                    description: id.clone(),
+                },
+                joins: vec![MasterListNameJoinRow {
+                    id: format!("{}1", id),
+                    master_list_id: id.clone(),
+                    name_id: mock_name_store_a().id,
+                }],
+                lines: vec![MasterListLineRow {
+                    id: format!("{}1", id),
+                    item_id: item().id,
+                    master_list_id: id.clone(),
+                }],
+            }
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "insert_stocktake_generate_lines",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.locations = vec![location()];
+                r.items = vec![item()];
+                r.stock_lines = vec![
+                    stock_line(
+                        "generate_line1",
+                        &mock_item_a().id,
+                        Some(location().id),
+                        NaiveDate::from_ymd(2021, 01, 01),
+                        10,
+                    ),
+                    stock_line(
+                        "generate_line2",
+                        &item().id,
+                        None,
+                        NaiveDate::from_ymd(2021, 06, 01),
+                        5,
+                    ),
+                    // Not in stock
+                    stock_line(
+                        "generate_line3",
+                        &mock_item_a().id,
+                        Some(location().id),
+                        NaiveDate::from_ymd(2020, 01, 01),
+                        0,
+                    ),
+                    stock_line(
+                        "generate_line4",
+                        &item().id,
+                        Some(location().id),
+                        NaiveDate::from_ymd(2022, 01, 01),
+                        3,
+                    ),
+                ];
+                r.full_master_lists = vec![master_list()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.stocktake_service;
+        let store_a = mock_store_a();
+
+        let insert = |id: &str, generate_lines: GenerateStocktakeLines| {
+            service.insert_stocktake(
+                &context,
+                &store_a.id,
+                &mock_user_account_a().id,
+                inline_init(|i: &mut InsertStocktake| {
+                    i.id = id.to_string();
+                    i.generate_lines = Some(generate_lines);
+                }),
+            )
+        };
+        let generated_lines = |stocktake_id: &str| {
+            let mut lines: Vec<(String, i32)> = StocktakeLineRepository::new(&connection)
+                .query_by_filter(
+                    StocktakeLineFilter::new().stocktake_id(EqualFilter::equal_to(stocktake_id)),
+                )
+                .unwrap()
+                .into_iter()
+                .map(|line| {
+                    (
+                        line.line.stock_line_id.unwrap(),
+                        line.line.snapshot_number_of_packs,
+                    )
+                })
+                .collect();
+            lines.sort();
+            lines
+        };
+
+        // error: location does not exist
+        assert_eq!(
+            insert(
+                "generate_stocktake",
+                inline_init(|r: &mut GenerateStocktakeLines| {
+                    r.location_id = Some("invalid".to_string())
+                }),
+            ),
+            Err(InsertStocktakeError::LocationDoesNotExist)
+        );
+
+        // error: master list not visible to the store
+        assert_eq!(
+            insert(
+                "generate_stocktake",
+                inline_init(|r: &mut GenerateStocktakeLines| {
+                    r.master_list_id = Some("invalid".to_string())
+                }),
+            ),
+            Err(InsertStocktakeError::MasterListNotFoundForThisStore)
+        );
+
+        // success: by location
+        insert(
+            "generate_by_location",
+            inline_init(|r: &mut GenerateStocktakeLines| r.location_id = Some(location().id)),
+        )
+        .unwrap();
+        assert_eq!(
+            generated_lines("generate_by_location"),
+            vec![
+                ("generate_line1".to_string(), 10),
+                ("generate_line4".to_string(), 3)
+            ]
+        );
+
+        // success: by master list
+        insert(
+            "generate_by_master_list",
+            inline_init(|r: &mut GenerateStocktakeLines| {
+                r.master_list_id = Some(master_list().master_list.id)
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            generated_lines("generate_by_master_list"),
+            vec![
+                ("generate_line2".to_string(), 5),
+                ("generate_line4".to_string(), 3)
+            ]
+        );
+
+        // success: by location, expiring before a date
+        insert(
+            "generate_by_expiry",
+            inline_init(|r: &mut GenerateStocktakeLines| {
+                r.location_id = Some(location().id);
+                r.expires_before = Some(NaiveDate::from_ymd(2022, 01, 01));
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            generated_lines("generate_by_expiry"),
+            vec![("generate_line1".to_string(), 10)]
+        );
+
+        // success: all stock lines in stock
+        insert("generate_all", GenerateStocktakeLines::default()).unwrap();
+        let mut in_stock: Vec<(String, i32)> = StockLineRepository::new(&connection)
+            .query_by_filter(StockLineFilter::new().store_id(EqualFilter::equal_to(&store_a.id)))
+            .unwrap()
+            .into_iter()
+            .map(|stock_line| stock_line.stock_line_row)
+            .filter(|stock_line| stock_line.total_number_of_packs > 0)
+            .map(|stock_line| (stock_line.id, stock_line.total_number_of_packs))
+            .collect();
+        in_stock.sort();
+        assert_eq!(generated_lines("generate_all"), in_stock);
+    }
 }
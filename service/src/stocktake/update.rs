@@ -6,10 +6,18 @@ use repository::{
     StocktakeLineFilter, StocktakeLineRepository, StocktakeLineRow, StocktakeLineRowRepository,
     StocktakeRow, StocktakeRowRepository, StocktakeStatus, StorageConnection,
 };
+use std::collections::BTreeSet;
 use util::{constants::INVENTORY_ADJUSTMENT_NAME_CODE, inline_edit, uuid::uuid};
 
 use crate::{
-    number::next_number, service_provider::ServiceContext, stocktake::query::get_stocktake,
+    cycle_count::counted::update_last_counted,
+    inventory_adjustment_reason::validate::{
+        adjustment_type_for_delta, check_reason_is_required, check_reason_is_valid,
+    },
+    number::next_number,
+    service_provider::ServiceContext,
+    stocktake::query::get_stocktake,
+    u32_to_i32,
     validate::check_store_id_matches,
 };
 
@@ -23,6 +31,10 @@ pub struct UpdateStocktake {
     pub status: Option<StocktakeStatus>,
     pub stocktake_date: Option<NaiveDate>,
     pub is_locked: Option<bool>,
+    pub is_blind_count: Option<bool>,
+    pub recount_threshold: Option<u32>,
+    /// Submits the count of a blind count, the snapshot quantities are shown afterwards
+    pub submit_count: Option<bool>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -37,6 +49,28 @@ pub enum UpdateStocktakeError {
     NoLines,
     /// Holds list of affected stock lines
     SnapshotCountCurrentCountMismatch(Vec<StocktakeLine>),
+    /// Holds list of lines with a count change but without an adjustment reason
+    AdjustmentReasonNotProvided(Vec<StocktakeLine>),
+    /// Holds list of lines with an inactive reason or a reason for the opposite adjustment type
+    AdjustmentReasonNotValid(Vec<StocktakeLine>),
+    NotABlindCount,
+    /// Blind count settings can't be changed once the count is submitted
+    CountAlreadySubmitted,
+    /// Blind count can't be turned off once lines have been counted
+    CountAlreadyStarted,
+    /// Blind count can't be finalised before the count is submitted
+    CountNotSubmitted,
+    /// Holds list of lines with a discrepancy above the recount threshold but without a second
+    /// count
+    RecountRequired(Vec<StocktakeLine>),
+    /// Blind count needs to be finalised by a different user than the one that counted
+    VerifierIsCounter,
+}
+
+/// A recount replaces the first count
+fn counted_number_of_packs(row: &StocktakeLineRow) -> Option<i32> {
+    row.second_counted_number_of_packs
+        .or(row.counted_number_of_packs)
 }
 
 fn check_snapshot_matches_current_count(
@@ -58,6 +92,82 @@ fn check_snapshot_matches_current_count(
     None
 }
 
+/// Returns lines without a required reason and lines with an invalid reason
+fn check_adjustment_reasons(
+    connection: &StorageConnection,
+    stocktake_lines: &[StocktakeLine],
+) -> Result<(Vec<StocktakeLine>, Vec<StocktakeLine>), RepositoryError> {
+    let mut not_provided = Vec::new();
+    let mut not_valid = Vec::new();
+    for line in stocktake_lines {
+        let row = &line.line;
+        let counted_number_of_packs =
+            counted_number_of_packs(row).unwrap_or(row.snapshot_number_of_packs);
+        let adjustment_type =
+            match adjustment_type_for_delta(counted_number_of_packs - row.snapshot_number_of_packs)
+            {
+                Some(adjustment_type) => adjustment_type,
+                None => continue,
+            };
+        match &row.inventory_adjustment_reason_id {
+            Some(reason_id) => {
+                if !check_reason_is_valid(connection, reason_id, &adjustment_type)? {
+                    not_valid.push(line.clone());
+                }
+            }
+            None => {
+                if check_reason_is_required(connection, &adjustment_type)? {
+                    not_provided.push(line.clone());
+                }
+            }
+        }
+    }
+    Ok((not_provided, not_valid))
+}
+
+fn check_recounts(
+    stocktake: &StocktakeRow,
+    stocktake_lines: &[StocktakeLine],
+) -> Vec<StocktakeLine> {
+    let recount_threshold = match stocktake.recount_threshold {
+        Some(recount_threshold) => recount_threshold,
+        None => return vec![],
+    };
+    stocktake_lines
+        .iter()
+        .filter(|line| {
+            let row = &line.line;
+            let discrepancy = match row.counted_number_of_packs {
+                Some(counted_number_of_packs) => {
+                    i32::abs(counted_number_of_packs - row.snapshot_number_of_packs)
+                }
+                None => return false,
+            };
+            discrepancy > recount_threshold && row.second_counted_number_of_packs.is_none()
+        })
+        .cloned()
+        .collect()
+}
+
+/// Returns an error if the blind count can't be finalised by the user
+fn check_blind_count_can_be_finalised(
+    stocktake: &StocktakeRow,
+    stocktake_lines: &[StocktakeLine],
+    user_id: &str,
+) -> Result<(), UpdateStocktakeError> {
+    if stocktake.count_submitted_datetime.is_none() {
+        return Err(UpdateStocktakeError::CountNotSubmitted);
+    }
+    let recounts = check_recounts(stocktake, stocktake_lines);
+    if !recounts.is_empty() {
+        return Err(UpdateStocktakeError::RecountRequired(recounts));
+    }
+    if stocktake.counted_by.as_deref() == Some(user_id) {
+        return Err(UpdateStocktakeError::VerifierIsCounter);
+    }
+    Ok(())
+}
+
 fn load_stocktake_lines(
     connection: &StorageConnection,
     stocktake_id: &str,
@@ -70,6 +180,7 @@ fn load_stocktake_lines(
 fn validate(
     connection: &StorageConnection,
     store_id: &str,
+    user_id: &str,
     input: &UpdateStocktake,
 ) -> Result<(StocktakeRow, Vec<StocktakeLine>), UpdateStocktakeError> {
     let existing = match check_stocktake_exist(connection, &input.id)? {
@@ -87,18 +198,53 @@ fn validate(
     if !check_store_id_matches(store_id, &existing.store_id) {
         return Err(UpdateStocktakeError::InvalidStore);
     }
+    let count_submitted = existing.count_submitted_datetime.is_some();
+    if count_submitted
+        && (input.is_blind_count.is_some()
+            || input.recount_threshold.is_some()
+            || input.submit_count == Some(true))
+    {
+        return Err(UpdateStocktakeError::CountAlreadySubmitted);
+    }
+    let is_blind_count = input.is_blind_count.unwrap_or(existing.is_blind_count);
+    if input.submit_count == Some(true) && !is_blind_count {
+        return Err(UpdateStocktakeError::NotABlindCount);
+    }
+
     let stocktake_lines = load_stocktake_lines(connection, &input.id)?;
 
+    // Otherwise the counter could show the snapshot and skip the verification
+    let count_started = stocktake_lines
+        .iter()
+        .any(|line| line.line.counted_number_of_packs.is_some());
+    if existing.is_blind_count && !is_blind_count && count_started {
+        return Err(UpdateStocktakeError::CountAlreadyStarted);
+    }
+
     if let Some(StocktakeStatus::Finalised) = input.status {
         if stocktake_lines.len() == 0 {
             return Err(UpdateStocktakeError::NoLines);
         }
 
+        if existing.is_blind_count {
+            check_blind_count_can_be_finalised(&existing, &stocktake_lines, user_id)?;
+        }
+
         if let Some(mismatches) = check_snapshot_matches_current_count(&stocktake_lines) {
             return Err(UpdateStocktakeError::SnapshotCountCurrentCountMismatch(
                 mismatches,
             ));
         }
+
+        let (not_provided, not_valid) = check_adjustment_reasons(connection, &stocktake_lines)?;
+        if !not_provided.is_empty() {
+            return Err(UpdateStocktakeError::AdjustmentReasonNotProvided(
+                not_provided,
+            ));
+        }
+        if !not_valid.is_empty() {
+            return Err(UpdateStocktakeError::AdjustmentReasonNotValid(not_valid));
+        }
     }
 
     Ok((existing, stocktake_lines))
@@ -139,9 +285,7 @@ fn generate_stock_line_update(
     stocktake_line: &StocktakeLine,
     stock_line: &StockLineRow,
 ) -> Result<StockLineJob, UpdateStocktakeError> {
-    let counted_number_of_packs = stocktake_line
-        .line
-        .counted_number_of_packs
+    let counted_number_of_packs = counted_number_of_packs(&stocktake_line.line)
         .unwrap_or(stocktake_line.line.snapshot_number_of_packs);
     let delta = counted_number_of_packs - stocktake_line.line.snapshot_number_of_packs;
     let updated_line = StockLineRow {
@@ -197,6 +341,12 @@ fn generate_stock_line_update(
             tax: None,
             number_of_packs: quantiy_change,
             note: stock_line.note.clone(),
+            original_invoice_line_id: None,
+            return_reason: None,
+            inventory_adjustment_reason_id: stocktake_line
+                .line
+                .inventory_adjustment_reason_id
+                .clone(),
         })
     } else {
         None
@@ -215,7 +365,7 @@ fn generate_new_stock_line(
     invoice_id: &str,
     stocktake_line: StocktakeLine,
 ) -> Result<StockLineJob, UpdateStocktakeError> {
-    let counted_number_of_packs = stocktake_line.line.counted_number_of_packs.unwrap_or(0);
+    let counted_number_of_packs = counted_number_of_packs(&stocktake_line.line).unwrap_or(0);
     let row = stocktake_line.line;
     let pack_size = row.pack_size.unwrap_or(0);
     let cost_price_per_pack = row.cost_price_per_pack.unwrap_or(0.0);
@@ -274,6 +424,9 @@ fn generate_new_stock_line(
             tax: None,
             number_of_packs: counted_number_of_packs,
             note: row.note,
+            original_invoice_line_id: None,
+            return_reason: None,
+            inventory_adjustment_reason_id: row.inventory_adjustment_reason_id,
         })
     } else {
         None
@@ -296,6 +449,9 @@ fn generate(
         status: input_status,
         is_locked: input_is_locked,
         stocktake_date: input_stocktake_date,
+        is_blind_count: input_is_blind_count,
+        recount_threshold: input_recount_threshold,
+        submit_count: input_submit_count,
     }: UpdateStocktake,
     existing: StocktakeRow,
     stocktake_lines: Vec<StocktakeLine>,
@@ -309,6 +465,14 @@ fn generate(
             u.comment = input_comment.or(u.comment);
             u.is_locked = input_is_locked.unwrap_or(false);
             u.stocktake_date = input_stocktake_date.or(u.stocktake_date);
+            u.is_blind_count = input_is_blind_count.unwrap_or(u.is_blind_count);
+            u.recount_threshold = input_recount_threshold
+                .map(u32_to_i32)
+                .or(u.recount_threshold);
+            if input_submit_count == Some(true) {
+                u.counted_by = Some(user_id.to_string());
+                u.count_submitted_datetime = Some(Utc::now().naive_utc());
+            }
             u
         });
         return Ok(StocktakeGenerateJob {
@@ -378,6 +542,9 @@ fn generate(
         colour: None,
         requisition_id: None,
         linked_invoice_id: None,
+        prescriber: None,
+        diagnosis: None,
+        directions: None,
     };
 
     let stocktake = inline_edit(&existing, |mut u: StocktakeRow| {
@@ -386,6 +553,9 @@ fn generate(
         u.comment = input_comment.or(u.comment);
         u.finalised_datetime = Some(now);
         u.inventory_adjustment_id = Some(shipment.id.clone());
+        if u.is_blind_count {
+            u.verified_by = Some(user_id.to_string());
+        }
         u
     });
 
@@ -408,7 +578,17 @@ pub fn update_stocktake(
         .connection
         .transaction_sync(|connection| {
             let stocktake_id = input.id.clone();
-            let (existing, stocktake_lines) = validate(connection, store_id, &input)?;
+            let (existing, stocktake_lines) = validate(connection, store_id, user_id, &input)?;
+            let counted_item_ids = match input.status {
+                Some(StocktakeStatus::Finalised) => {
+                    let item_ids: BTreeSet<String> = stocktake_lines
+                        .iter()
+                        .map(|line| line.line.item_id.clone())
+                        .collect();
+                    Some(item_ids.into_iter().collect::<Vec<String>>())
+                }
+                _ => None,
+            };
             let result = generate(
                 connection,
                 user_id,
@@ -439,6 +619,12 @@ pub fn update_stocktake(
                 shipment_line_repo.upsert_one(&line)?;
             }
             StocktakeRowRepository::new(connection).upsert_one(&result.stocktake)?;
+            // track the last count of the items for cycle counting
+            if let (Some(item_ids), Some(finalised_datetime)) =
+                (counted_item_ids, result.stocktake.finalised_datetime)
+            {
+                update_last_counted(connection, store_id, &item_ids, finalised_datetime)?;
+            }
 
             // return the updated stocktake
             let stocktake = get_stocktake(ctx, stocktake_id)?;
@@ -462,22 +648,26 @@ mod test {
     use chrono::NaiveDate;
     use repository::{
         mock::{
-            mock_locked_stocktake, mock_stock_line_a, mock_stocktake_a,
+            mock_inventory_adjustment_reasons, mock_locked_stocktake,
+            mock_negative_inventory_adjustment_reason, mock_positive_inventory_adjustment_reason,
+            mock_stock_line_a, mock_stock_line_stocktake_surplus, mock_stocktake_a,
             mock_stocktake_finalised_without_lines, mock_stocktake_full_edit,
             mock_stocktake_line_a, mock_stocktake_line_new_stock_line,
-            mock_stocktake_new_stock_line, mock_stocktake_no_count_change, mock_stocktake_no_lines,
-            mock_stocktake_stock_deficit, mock_stocktake_stock_surplus, mock_store_a,
-            MockDataInserts,
+            mock_stocktake_line_stock_surplus, mock_stocktake_new_stock_line,
+            mock_stocktake_no_count_change, mock_stocktake_no_lines, mock_stocktake_stock_deficit,
+            mock_stocktake_stock_surplus, mock_store_a, MockData, MockDataInserts,
         },
-        test_db::setup_all,
-        InvoiceLineRowRepository, InvoiceLineRowType, StockLineRowRepository, StocktakeLine,
-        StocktakeLineRowRepository, StocktakeRepository, StocktakeRow, StocktakeStatus,
+        test_db::{setup_all, setup_all_with_data},
+        InvoiceLineRowRepository, InvoiceLineRowType, StockLineRow, StockLineRowRepository,
+        StocktakeLine, StocktakeLineRow, StocktakeLineRowRepository, StocktakeRepository,
+        StocktakeRow, StocktakeStatus,
     };
     use util::{inline_edit, inline_init};
 
     use crate::{
         service_provider::ServiceProvider,
         stocktake::update::{UpdateStocktake, UpdateStocktakeError},
+        stocktake_line::{UpdateStocktakeLine, UpdateStocktakeLineError},
     };
 
     #[actix_rt::test]
@@ -743,6 +933,9 @@ mod test {
                     status: Some(StocktakeStatus::New),
                     stocktake_date: Some(NaiveDate::from_ymd(2019, 03, 20)),
                     is_locked: Some(false),
+                    is_blind_count: Some(true),
+                    recount_threshold: Some(3),
+                    submit_count: None,
                 },
             )
             .unwrap();
@@ -754,6 +947,8 @@ mod test {
                 i.description = Some("description_1".to_string());
                 i.stocktake_date = Some(NaiveDate::from_ymd(2019, 03, 20));
                 i.is_locked = false;
+                i.is_blind_count = true;
+                i.recount_threshold = Some(3);
                 i
             }),
         );
@@ -801,4 +996,241 @@ mod test {
             .unwrap();
         assert_eq!(updated_stocktake_line.stock_line_id, Some(stock_line.id));
     }
+
+    #[actix_rt::test]
+    async fn update_stocktake_adjustment_reasons() {
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "update_stocktake_adjustment_reasons",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.inventory_adjustment_reasons = mock_inventory_adjustment_reasons();
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.stocktake_service;
+        let finalise = || {
+            inline_init(|i: &mut UpdateStocktake| {
+                i.id = mock_stocktake_stock_surplus().id;
+                i.status = Some(StocktakeStatus::Finalised);
+            })
+        };
+        let stocktake_line = |reason_id: Option<String>| StocktakeLine {
+            line: inline_edit(&mock_stocktake_line_stock_surplus(), |mut r| {
+                r.inventory_adjustment_reason_id = reason_id;
+                r
+            }),
+            stock_line: Some(mock_stock_line_stocktake_surplus()),
+            location: None,
+        };
+
+        // error: AdjustmentReasonNotProvided
+        let error = service
+            .update_stocktake(&context, &mock_store_a().id, "n/a", finalise())
+            .unwrap_err();
+        assert_eq!(
+            error,
+            UpdateStocktakeError::AdjustmentReasonNotProvided(vec![stocktake_line(None)])
+        );
+
+        // error: AdjustmentReasonNotValid (negative reason for a surplus)
+        let negative_reason_id = Some(mock_negative_inventory_adjustment_reason().id);
+        StocktakeLineRowRepository::new(&connection)
+            .upsert_one(&stocktake_line(negative_reason_id.clone()).line)
+            .unwrap();
+        let error = service
+            .update_stocktake(&context, &mock_store_a().id, "n/a", finalise())
+            .unwrap_err();
+        assert_eq!(
+            error,
+            UpdateStocktakeError::AdjustmentReasonNotValid(vec![stocktake_line(
+                negative_reason_id
+            )])
+        );
+
+        // success: reason is copied to the inventory adjustment line
+        let positive_reason_id = Some(mock_positive_inventory_adjustment_reason().id);
+        StocktakeLineRowRepository::new(&connection)
+            .upsert_one(&stocktake_line(positive_reason_id.clone()).line)
+            .unwrap();
+        let result = service
+            .update_stocktake(&context, &mock_store_a().id, "n/a", finalise())
+            .unwrap();
+        let shipment_line = InvoiceLineRowRepository::new(&connection)
+            .find_many_by_invoice_id(&result.inventory_adjustment_id.unwrap())
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(shipment_line.r#type, InvoiceLineRowType::StockIn);
+        assert_eq!(
+            shipment_line.inventory_adjustment_reason_id,
+            positive_reason_id
+        );
+    }
+
+    #[actix_rt::test]
+    async fn update_stocktake_blind_count() {
+        fn stock_line() -> StockLineRow {
+            inline_init(|r: &mut StockLineRow| {
+                r.id = "blind_count_stock_line".to_string();
+                r.item_id = mock_stock_line_a().item_id;
+                r.store_id = mock_store_a().id;
+                r.pack_size = 1;
+                r.total_number_of_packs = 10;
+                r.available_number_of_packs = 10;
+            })
+        }
+
+        fn stocktake() -> StocktakeRow {
+            inline_init(|r: &mut StocktakeRow| {
+                r.id = "blind_count_stocktake".to_string();
+                r.store_id = mock_store_a().id;
+                r.is_blind_count = true;
+                r.recount_threshold = Some(2);
+            })
+        }
+
+        fn stocktake_line() -> StocktakeLineRow {
+            inline_init(|r: &mut StocktakeLineRow| {
+                r.id = "blind_count_stocktake_line".to_string();
+                r.stocktake_id = stocktake().id;
+                r.stock_line_id = Some(stock_line().id);
+                r.item_id = stock_line().item_id;
+                r.snapshot_number_of_packs = 10;
+                r.counted_number_of_packs = Some(5);
+            })
+        }
+
+        let (_, connection, connection_manager, _) = setup_all_with_data(
+            "update_stocktake_blind_count",
+            MockDataInserts::all(),
+            inline_init(|r: &mut MockData| {
+                r.stock_lines = vec![stock_line()];
+                r.stocktakes = vec![stocktake()];
+                r.stocktake_lines = vec![stocktake_line()];
+            }),
+        )
+        .await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.stocktake_service;
+        let line_service = service_provider.stocktake_line_service;
+        let store_id = mock_store_a().id;
+        let submit_count = |id: String| {
+            inline_init(|i: &mut UpdateStocktake| {
+                i.id = id;
+                i.submit_count = Some(true);
+            })
+        };
+        let finalise = || {
+            inline_init(|i: &mut UpdateStocktake| {
+                i.id = stocktake().id;
+                i.status = Some(StocktakeStatus::Finalised);
+            })
+        };
+        let recount = |second_counted_number_of_packs: u32| {
+            inline_init(|i: &mut UpdateStocktakeLine| {
+                i.id = stocktake_line().id;
+                i.second_counted_number_of_packs = Some(second_counted_number_of_packs);
+            })
+        };
+
+        // error: NotABlindCount
+        let error = service
+            .update_stocktake(
+                &context,
+                &store_id,
+                "counter",
+                submit_count(mock_stocktake_a().id),
+            )
+            .unwrap_err();
+        assert_eq!(error, UpdateStocktakeError::NotABlindCount);
+
+        // error: CountNotSubmitted
+        let error = service
+            .update_stocktake(&context, &store_id, "verifier", finalise())
+            .unwrap_err();
+        assert_eq!(error, UpdateStocktakeError::CountNotSubmitted);
+
+        // error: CountAlreadyStarted, blind count can't be turned off once lines are counted
+        let error = service
+            .update_stocktake(
+                &context,
+                &store_id,
+                "counter",
+                inline_init(|i: &mut UpdateStocktake| {
+                    i.id = stocktake().id;
+                    i.is_blind_count = Some(false);
+                }),
+            )
+            .unwrap_err();
+        assert_eq!(error, UpdateStocktakeError::CountAlreadyStarted);
+
+        // error: line can't be recounted before the count is submitted
+        let error = line_service
+            .update_stocktake_line(&context, &store_id, recount(6))
+            .unwrap_err();
+        assert_eq!(error, UpdateStocktakeLineError::CountNotSubmitted);
+
+        // success: submit count
+        let result = service
+            .update_stocktake(&context, &store_id, "counter", submit_count(stocktake().id))
+            .unwrap();
+        assert_eq!(result.counted_by, Some("counter".to_string()));
+        assert!(result.count_submitted_datetime.is_some());
+
+        // error: CountAlreadySubmitted
+        let error = service
+            .update_stocktake(&context, &store_id, "counter", submit_count(stocktake().id))
+            .unwrap_err();
+        assert_eq!(error, UpdateStocktakeError::CountAlreadySubmitted);
+
+        // error: first count can't be changed once submitted
+        let error = line_service
+            .update_stocktake_line(
+                &context,
+                &store_id,
+                inline_init(|i: &mut UpdateStocktakeLine| {
+                    i.id = stocktake_line().id;
+                    i.counted_number_of_packs = Some(10);
+                }),
+            )
+            .unwrap_err();
+        assert_eq!(error, UpdateStocktakeLineError::CountAlreadySubmitted);
+
+        // error: RecountRequired, discrepancy of 5 is above the threshold
+        let error = service
+            .update_stocktake(&context, &store_id, "verifier", finalise())
+            .unwrap_err();
+        assert_eq!(
+            error,
+            UpdateStocktakeError::RecountRequired(vec![StocktakeLine {
+                line: stocktake_line(),
+                stock_line: Some(stock_line()),
+                location: None,
+            }])
+        );
+
+        // error: VerifierIsCounter
+        line_service
+            .update_stocktake_line(&context, &store_id, recount(7))
+            .unwrap();
+        let error = service
+            .update_stocktake(&context, &store_id, "counter", finalise())
+            .unwrap_err();
+        assert_eq!(error, UpdateStocktakeError::VerifierIsCounter);
+
+        // success: second count is used for the adjustment
+        let result = service
+            .update_stocktake(&context, &store_id, "verifier", finalise())
+            .unwrap();
+        assert_eq!(result.verified_by, Some("verifier".to_string()));
+        let updated_stock_line = StockLineRowRepository::new(&connection)
+            .find_one_by_id(&stock_line().id)
+            .unwrap();
+        assert_eq!(updated_stock_line.total_number_of_packs, 7);
+    }
 }
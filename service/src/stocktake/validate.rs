@@ -15,6 +15,11 @@ pub fn check_stocktake_not_finalised(status: &StocktakeStatus) -> bool {
     *status != StocktakeStatus::Finalised
 }
 
+/// Counts of a submitted blind count can only be changed by a recount
+pub fn check_count_not_submitted(stocktake: &StocktakeRow) -> bool {
+    stocktake.count_submitted_datetime.is_none()
+}
+
 pub fn check_no_stocktake_lines_exist(
     connection: &StorageConnection,
     stocktake_line_id: &str,
@@ -4,7 +4,9 @@ use repository::{
 
 use crate::{
     service_provider::ServiceContext,
-    stocktake::validate::{check_stocktake_exist, check_stocktake_not_finalised},
+    stocktake::validate::{
+        check_count_not_submitted, check_stocktake_exist, check_stocktake_not_finalised,
+    },
     stocktake_line::validate::check_stocktake_line_exist,
     validate::check_store_id_matches,
 };
@@ -17,6 +19,7 @@ pub enum DeleteStocktakeLineError {
     StocktakeLineDoesNotExist,
     CannotEditFinalised,
     StocktakeIsLocked,
+    CountAlreadySubmitted,
 }
 
 fn validate(
@@ -48,6 +51,9 @@ fn validate(
     if !check_store_id_matches(store_id, &stocktake.store_id) {
         return Err(DeleteStocktakeLineError::InvalidStore);
     }
+    if !check_count_not_submitted(&stocktake) {
+        return Err(DeleteStocktakeLineError::CountAlreadySubmitted);
+    }
     Ok(())
 }
 
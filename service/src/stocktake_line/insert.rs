@@ -7,8 +7,11 @@ use repository::{
 };
 
 use crate::{
+    inventory_adjustment_reason::validate::check_reason_exists,
     service_provider::ServiceContext,
-    stocktake::validate::{check_stocktake_exist, check_stocktake_not_finalised},
+    stocktake::validate::{
+        check_count_not_submitted, check_stocktake_exist, check_stocktake_not_finalised,
+    },
     stocktake_line::{
         query::get_stocktake_line,
         validate::{check_item_exists, check_location_exists},
@@ -33,6 +36,7 @@ pub struct InsertStocktakeLine {
     pub cost_price_per_pack: Option<f64>,
     pub sell_price_per_pack: Option<f64>,
     pub note: Option<String>,
+    pub inventory_adjustment_reason_id: Option<String>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -50,6 +54,8 @@ pub enum InsertStocktakeLineError {
     StockLineXOrItem,
     ItemDoesNotExist,
     StocktakeIsLocked,
+    AdjustmentReasonDoesNotExist,
+    CountAlreadySubmitted,
 }
 
 fn check_stocktake_line_does_not_exist(
@@ -135,6 +141,10 @@ fn validate(
         return Err(InsertStocktakeLineError::StocktakeIsLocked);
     }
 
+    if !check_count_not_submitted(&stocktake) {
+        return Err(InsertStocktakeLineError::CountAlreadySubmitted);
+    }
+
     let stock_line = if let Some(stock_line_id) = &input.stock_line_id {
         check_stock_line_exists(connection, stock_line_id)?
     } else {
@@ -164,6 +174,12 @@ fn validate(
         }
     }
 
+    if let Some(reason_id) = &input.inventory_adjustment_reason_id {
+        if !check_reason_exists(connection, reason_id)? {
+            return Err(InsertStocktakeLineError::AdjustmentReasonDoesNotExist);
+        }
+    }
+
     Ok((stock_line, item_id))
 }
 
@@ -184,6 +200,7 @@ fn generate(
         cost_price_per_pack,
         sell_price_per_pack,
         note,
+        inventory_adjustment_reason_id,
     }: InsertStocktakeLine,
 ) -> StocktakeLineRow {
     let snapshot_number_of_packs = if let Some(stock_line) = stock_line {
@@ -199,6 +216,7 @@ fn generate(
         comment,
         snapshot_number_of_packs,
         counted_number_of_packs: counted_number_of_packs.map(u32_to_i32),
+        second_counted_number_of_packs: None,
         item_id: item_id.to_string(),
         batch,
         expiry_date,
@@ -206,6 +224,7 @@ fn generate(
         cost_price_per_pack,
         sell_price_per_pack,
         note,
+        inventory_adjustment_reason_id,
     }
 }
 
@@ -336,6 +355,28 @@ mod stocktake_line_test {
             .unwrap_err();
         assert_eq!(error, InsertStocktakeLineError::LocationDoesNotExist);
 
+        // error AdjustmentReasonDoesNotExist
+        let store_a = mock_store_a();
+        let stocktake_a = mock_stocktake_a();
+        let stock_line = mock_new_stock_line_for_stocktake_a();
+        let error = service
+            .insert_stocktake_line(
+                &context,
+                &store_a.id,
+                inline_init(|r: &mut InsertStocktakeLine| {
+                    r.id = uuid();
+                    r.stocktake_id = stocktake_a.id;
+                    r.stock_line_id = Some(stock_line.id);
+                    r.counted_number_of_packs = Some(17);
+                    r.inventory_adjustment_reason_id = Some("invalid".to_string());
+                }),
+            )
+            .unwrap_err();
+        assert_eq!(
+            error,
+            InsertStocktakeLineError::AdjustmentReasonDoesNotExist
+        );
+
         // error StocktakeLineAlreadyExists
         let store_a = mock_store_a();
         let stocktake_a = mock_stocktake_a();
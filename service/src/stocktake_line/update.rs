@@ -4,8 +4,11 @@ use repository::{
 };
 
 use crate::{
+    inventory_adjustment_reason::validate::check_reason_exists,
     service_provider::ServiceContext,
-    stocktake::validate::{check_stocktake_exist, check_stocktake_not_finalised},
+    stocktake::validate::{
+        check_count_not_submitted, check_stocktake_exist, check_stocktake_not_finalised,
+    },
     stocktake_line::{
         query::get_stocktake_line,
         validate::{check_location_exists, check_stocktake_line_exist},
@@ -21,6 +24,8 @@ pub struct UpdateStocktakeLine {
     pub comment: Option<String>,
     pub snapshot_number_of_packs: Option<u32>,
     pub counted_number_of_packs: Option<u32>,
+    /// Recount of a line with a discrepancy, for blind counts only once the count is submitted
+    pub second_counted_number_of_packs: Option<u32>,
 
     pub batch: Option<String>,
     pub expiry_date: Option<NaiveDate>,
@@ -28,6 +33,7 @@ pub struct UpdateStocktakeLine {
     pub cost_price_per_pack: Option<f64>,
     pub sell_price_per_pack: Option<f64>,
     pub note: Option<String>,
+    pub inventory_adjustment_reason_id: Option<String>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -39,6 +45,11 @@ pub enum UpdateStocktakeLineError {
     LocationDoesNotExist,
     CannotEditFinalised,
     StocktakeIsLocked,
+    AdjustmentReasonDoesNotExist,
+    /// Only the second count can be changed once a blind count is submitted
+    CountAlreadySubmitted,
+    /// Blind count lines can only be recounted once the count is submitted
+    CountNotSubmitted,
 }
 
 fn validate(
@@ -70,12 +81,31 @@ fn validate(
         return Err(UpdateStocktakeLineError::InvalidStore);
     }
 
+    let count_not_submitted = check_count_not_submitted(&stocktake);
+    if !count_not_submitted
+        && (input.snapshot_number_of_packs.is_some() || input.counted_number_of_packs.is_some())
+    {
+        return Err(UpdateStocktakeLineError::CountAlreadySubmitted);
+    }
+    if stocktake.is_blind_count
+        && count_not_submitted
+        && input.second_counted_number_of_packs.is_some()
+    {
+        return Err(UpdateStocktakeLineError::CountNotSubmitted);
+    }
+
     if let Some(location_id) = &input.location_id {
         if !check_location_exists(connection, location_id)? {
             return Err(UpdateStocktakeLineError::LocationDoesNotExist);
         }
     }
 
+    if let Some(reason_id) = &input.inventory_adjustment_reason_id {
+        if !check_reason_exists(connection, reason_id)? {
+            return Err(UpdateStocktakeLineError::AdjustmentReasonDoesNotExist);
+        }
+    }
+
     Ok(stocktake_line)
 }
 
@@ -87,12 +117,14 @@ fn generate(
         comment,
         snapshot_number_of_packs,
         counted_number_of_packs,
+        second_counted_number_of_packs,
         batch,
         expiry_date,
         pack_size,
         cost_price_per_pack,
         sell_price_per_pack,
         note,
+        inventory_adjustment_reason_id,
     }: UpdateStocktakeLine,
 ) -> Result<StocktakeLineRow, UpdateStocktakeLineError> {
     Ok(StocktakeLineRow {
@@ -108,6 +140,9 @@ fn generate(
         counted_number_of_packs: counted_number_of_packs
             .map(u32_to_i32)
             .or(existing.counted_number_of_packs),
+        second_counted_number_of_packs: second_counted_number_of_packs
+            .map(u32_to_i32)
+            .or(existing.second_counted_number_of_packs),
 
         item_id: existing.item_id,
         expiry_date: expiry_date.or(existing.expiry_date),
@@ -116,6 +151,8 @@ fn generate(
         cost_price_per_pack: cost_price_per_pack.or(existing.cost_price_per_pack),
         sell_price_per_pack: sell_price_per_pack.or(existing.sell_price_per_pack),
         note: note.or(existing.note),
+        inventory_adjustment_reason_id: inventory_adjustment_reason_id
+            .or(existing.inventory_adjustment_reason_id),
     })
 }
 
@@ -213,6 +250,24 @@ mod stocktake_line_test {
             .unwrap_err();
         assert_eq!(error, UpdateStocktakeLineError::LocationDoesNotExist);
 
+        // error: AdjustmentReasonDoesNotExist
+        let store_a = mock_store_a();
+        let stocktake_line_a = mock_stocktake_line_a();
+        let error = service
+            .update_stocktake_line(
+                &context,
+                &store_a.id,
+                inline_init(|r: &mut UpdateStocktakeLine| {
+                    r.id = stocktake_line_a.id;
+                    r.inventory_adjustment_reason_id = Some("invalid".to_string());
+                }),
+            )
+            .unwrap_err();
+        assert_eq!(
+            error,
+            UpdateStocktakeLineError::AdjustmentReasonDoesNotExist
+        );
+
         // error CannotEditFinalised
         let store_a = mock_store_a();
         let stocktake_line_a = mock_stocktake_line_finalised();
@@ -308,10 +363,12 @@ mod stocktake_line_test {
                 sell_price_per_pack: Some(25.0),
                 snapshot_number_of_packs: 10,
                 counted_number_of_packs: Some(14),
+                second_counted_number_of_packs: None,
                 item_id: stocktake_line_a.item_id,
                 expiry_date: None,
                 pack_size: None,
                 note: None,
+                inventory_adjustment_reason_id: None,
             }
         );
     }
@@ -0,0 +1,44 @@
+use self::{
+    query::get_store_preference,
+    update::{
+        set_customer_shelf_life, update_store_preference, SetCustomerShelfLife,
+        SetCustomerShelfLifeError, UpdateStorePreference, UpdateStorePreferenceError,
+    },
+};
+
+use crate::service_provider::ServiceContext;
+use repository::{RepositoryError, StorePreferenceRow};
+
+pub mod query;
+pub mod update;
+
+pub trait StorePreferenceServiceTrait: Sync + Send {
+    fn get_store_preference(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+    ) -> Result<StorePreferenceRow, RepositoryError> {
+        get_store_preference(&ctx.connection, store_id)
+    }
+
+    fn update_store_preference(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        input: UpdateStorePreference,
+    ) -> Result<StorePreferenceRow, UpdateStorePreferenceError> {
+        update_store_preference(ctx, store_id, input)
+    }
+
+    fn set_customer_shelf_life(
+        &self,
+        ctx: &ServiceContext,
+        store_id: &str,
+        input: SetCustomerShelfLife,
+    ) -> Result<(), SetCustomerShelfLifeError> {
+        set_customer_shelf_life(ctx, store_id, input)
+    }
+}
+
+pub struct StorePreferenceService {}
+impl StorePreferenceServiceTrait for StorePreferenceService {}
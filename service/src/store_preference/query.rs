@@ -0,0 +1,55 @@
+use repository::{
+    AllocationStrategy, CustomerShelfLifeRowRepository, RepositoryError, StorageConnection,
+    StorePreferenceRow, StorePreferenceRowRepository,
+};
+
+/// Returns the stored preferences or the default preferences if none have been set for the store
+pub fn get_store_preference(
+    connection: &StorageConnection,
+    store_id: &str,
+) -> Result<StorePreferenceRow, RepositoryError> {
+    let preference = StorePreferenceRowRepository::new(connection)
+        .find_one_by_id(store_id)?
+        .unwrap_or_else(|| StorePreferenceRow {
+            id: store_id.to_string(),
+            ..Default::default()
+        });
+    Ok(preference)
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct AllocationPreference {
+    pub strategy: AllocationStrategy,
+    pub preferred_location_id: Option<String>,
+    pub minimum_shelf_life_days: Option<i32>,
+}
+
+/// Preferences for allocating stock of a store to a customer, the minimum shelf life of the
+/// customer takes precedence over the one of the store
+pub fn get_allocation_preference(
+    connection: &StorageConnection,
+    store_id: &str,
+    name_id: &str,
+) -> Result<AllocationPreference, RepositoryError> {
+    let StorePreferenceRow {
+        id: _,
+        allocation_strategy,
+        preferred_location_id,
+        minimum_shelf_life_days,
+        requisitions_require_authorisation: _,
+        consumption_calculation_method: _,
+        lead_time_months: _,
+        safety_stock_months: _,
+    } = get_store_preference(connection, store_id)?;
+
+    let customer_shelf_life = CustomerShelfLifeRowRepository::new(connection)
+        .find_one_by_store_and_name(store_id, name_id)?;
+
+    Ok(AllocationPreference {
+        strategy: allocation_strategy,
+        preferred_location_id,
+        minimum_shelf_life_days: customer_shelf_life
+            .map(|row| row.minimum_shelf_life_days)
+            .or(minimum_shelf_life_days),
+    })
+}
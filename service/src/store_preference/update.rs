@@ -0,0 +1,405 @@
+use super::query::get_store_preference;
+use crate::{
+    service_provider::ServiceContext,
+    validate::{check_other_party, CheckOtherPartyType, OtherPartyErrors},
+    NullableUpdate,
+};
+use repository::{
+    AllocationStrategy, ConsumptionCalculationMethod, CustomerShelfLifeRow,
+    CustomerShelfLifeRowRepository, LocationRowRepository, RepositoryError, StorageConnection,
+    StorePreferenceRow, StorePreferenceRowRepository,
+};
+use util::uuid::uuid;
+
+#[derive(PartialEq, Debug)]
+pub enum UpdateStorePreferenceError {
+    LocationDoesNotExist,
+    LocationDoesNotBelongToCurrentStore,
+    LeadTimeOrSafetyStockIsNegative,
+    DatabaseError(RepositoryError),
+}
+
+/// Updates the preferences of the store, settings that are not set keep their current value
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct UpdateStorePreference {
+    pub allocation_strategy: Option<AllocationStrategy>,
+    pub preferred_location_id: Option<NullableUpdate<String>>,
+    pub minimum_shelf_life_days: Option<NullableUpdate<u32>>,
+    pub requisitions_require_authorisation: Option<bool>,
+    pub consumption_calculation_method: Option<ConsumptionCalculationMethod>,
+    pub lead_time_months: Option<f64>,
+    pub safety_stock_months: Option<f64>,
+}
+
+pub fn update_store_preference(
+    ctx: &ServiceContext,
+    store_id: &str,
+    input: UpdateStorePreference,
+) -> Result<StorePreferenceRow, UpdateStorePreferenceError> {
+    let preference = ctx
+        .connection
+        .transaction_sync(|connection| {
+            validate(connection, store_id, &input)?;
+            let existing = get_store_preference(connection, store_id)?;
+            let row = generate(existing, input);
+            StorePreferenceRowRepository::new(connection).upsert_one(&row)?;
+
+            get_store_preference(connection, store_id).map_err(UpdateStorePreferenceError::from)
+        })
+        .map_err(|error| error.to_inner_error())?;
+    Ok(preference)
+}
+
+fn validate(
+    connection: &StorageConnection,
+    store_id: &str,
+    input: &UpdateStorePreference,
+) -> Result<(), UpdateStorePreferenceError> {
+    if let Some(NullableUpdate {
+        value: Some(location_id),
+    }) = &input.preferred_location_id
+    {
+        let location = LocationRowRepository::new(connection)
+            .find_one_by_id(location_id)?
+            .ok_or(UpdateStorePreferenceError::LocationDoesNotExist)?;
+
+        if location.store_id != store_id {
+            return Err(UpdateStorePreferenceError::LocationDoesNotBelongToCurrentStore);
+        }
+    }
+
+    let lead_time_months = input.lead_time_months.unwrap_or_default();
+    let safety_stock_months = input.safety_stock_months.unwrap_or_default();
+    if lead_time_months < 0.0 || safety_stock_months < 0.0 {
+        return Err(UpdateStorePreferenceError::LeadTimeOrSafetyStockIsNegative);
+    }
+
+    Ok(())
+}
+
+fn generate(
+    existing: StorePreferenceRow,
+    UpdateStorePreference {
+        allocation_strategy,
+        preferred_location_id,
+        minimum_shelf_life_days,
+        requisitions_require_authorisation,
+        consumption_calculation_method,
+        lead_time_months,
+        safety_stock_months,
+    }: UpdateStorePreference,
+) -> StorePreferenceRow {
+    StorePreferenceRow {
+        id: existing.id,
+        allocation_strategy: allocation_strategy.unwrap_or(existing.allocation_strategy),
+        preferred_location_id: match preferred_location_id {
+            Some(NullableUpdate { value }) => value,
+            None => existing.preferred_location_id,
+        },
+        minimum_shelf_life_days: match minimum_shelf_life_days {
+            Some(NullableUpdate { value }) => value.map(|days| days as i32),
+            None => existing.minimum_shelf_life_days,
+        },
+        requisitions_require_authorisation: requisitions_require_authorisation
+            .unwrap_or(existing.requisitions_require_authorisation),
+        consumption_calculation_method: consumption_calculation_method
+            .unwrap_or(existing.consumption_calculation_method),
+        lead_time_months: lead_time_months.unwrap_or(existing.lead_time_months),
+        safety_stock_months: safety_stock_months.unwrap_or(existing.safety_stock_months),
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum SetCustomerShelfLifeError {
+    OtherPartyDoesNotExist,
+    OtherPartyNotVisible,
+    OtherPartyNotACustomer,
+    DatabaseError(RepositoryError),
+}
+
+pub struct SetCustomerShelfLife {
+    pub name_id: String,
+    /// Removes the customer specific shelf life if not set
+    pub minimum_shelf_life_days: Option<u32>,
+}
+
+pub fn set_customer_shelf_life(
+    ctx: &ServiceContext,
+    store_id: &str,
+    input: SetCustomerShelfLife,
+) -> Result<(), SetCustomerShelfLifeError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            use SetCustomerShelfLifeError::*;
+            check_other_party(
+                connection,
+                store_id,
+                &input.name_id,
+                CheckOtherPartyType::Customer,
+            )
+            .map_err(|e| match e {
+                OtherPartyErrors::OtherPartyDoesNotExist => OtherPartyDoesNotExist,
+                OtherPartyErrors::OtherPartyNotVisible => OtherPartyNotVisible,
+                OtherPartyErrors::TypeMismatched => OtherPartyNotACustomer,
+                OtherPartyErrors::DatabaseError(repository_error) => {
+                    DatabaseError(repository_error)
+                }
+            })?;
+
+            let repo = CustomerShelfLifeRowRepository::new(connection);
+            let existing = repo.find_one_by_store_and_name(store_id, &input.name_id)?;
+            match (existing, input.minimum_shelf_life_days) {
+                (Some(existing), None) => repo.delete(&existing.id)?,
+                (None, None) => {}
+                (existing, Some(days)) => repo.upsert_one(&CustomerShelfLifeRow {
+                    id: existing.map(|row| row.id).unwrap_or_else(uuid),
+                    store_id: store_id.to_string(),
+                    name_id: input.name_id,
+                    minimum_shelf_life_days: days as i32,
+                })?,
+            };
+            Ok(())
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+impl From<RepositoryError> for UpdateStorePreferenceError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateStorePreferenceError::DatabaseError(error)
+    }
+}
+
+impl From<RepositoryError> for SetCustomerShelfLifeError {
+    fn from(error: RepositoryError) -> Self {
+        SetCustomerShelfLifeError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{
+        mock::{
+            mock_location_1, mock_name_a, mock_name_store_b, mock_store_a, mock_store_b,
+            MockDataInserts,
+        },
+        test_db::setup_all,
+        AllocationStrategy, ConsumptionCalculationMethod, StorePreferenceRow,
+    };
+
+    use crate::{
+        service_provider::ServiceProvider,
+        store_preference::{
+            query::{get_allocation_preference, AllocationPreference},
+            update::{
+                SetCustomerShelfLife, SetCustomerShelfLifeError, UpdateStorePreference,
+                UpdateStorePreferenceError,
+            },
+        },
+        NullableUpdate,
+    };
+
+    #[actix_rt::test]
+    async fn update_store_preference() {
+        let (_, connection, connection_manager, _) =
+            setup_all("update_store_preference", MockDataInserts::all()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.store_preference_service;
+
+        // Default
+        assert_eq!(
+            service
+                .get_store_preference(&context, &mock_store_a().id)
+                .unwrap(),
+            StorePreferenceRow {
+                id: mock_store_a().id,
+                allocation_strategy: AllocationStrategy::Fefo,
+                preferred_location_id: None,
+                minimum_shelf_life_days: None,
+                requisitions_require_authorisation: false,
+                consumption_calculation_method: ConsumptionCalculationMethod::SimpleAverage,
+                lead_time_months: 0.0,
+                safety_stock_months: 0.0,
+            }
+        );
+
+        // LocationDoesNotExist
+        assert_eq!(
+            service.update_store_preference(
+                &context,
+                &mock_store_a().id,
+                UpdateStorePreference {
+                    preferred_location_id: Some(NullableUpdate {
+                        value: Some("invalid".to_string()),
+                    }),
+                    ..Default::default()
+                },
+            ),
+            Err(UpdateStorePreferenceError::LocationDoesNotExist)
+        );
+
+        // LocationDoesNotBelongToCurrentStore
+        assert_eq!(
+            service.update_store_preference(
+                &context,
+                &mock_store_b().id,
+                UpdateStorePreference {
+                    preferred_location_id: Some(NullableUpdate {
+                        value: Some(mock_location_1().id),
+                    }),
+                    ..Default::default()
+                },
+            ),
+            Err(UpdateStorePreferenceError::LocationDoesNotBelongToCurrentStore)
+        );
+
+        // Success
+        assert_eq!(
+            service.update_store_preference(
+                &context,
+                &mock_store_a().id,
+                UpdateStorePreference {
+                    allocation_strategy: Some(AllocationStrategy::Fifo),
+                    preferred_location_id: Some(NullableUpdate {
+                        value: Some(mock_location_1().id),
+                    }),
+                    minimum_shelf_life_days: Some(NullableUpdate { value: Some(30) }),
+                    requisitions_require_authorisation: Some(true),
+                    consumption_calculation_method: Some(
+                        ConsumptionCalculationMethod::LeadTimeSafetyStock
+                    ),
+                    lead_time_months: Some(1.0),
+                    safety_stock_months: Some(0.5),
+                },
+            ),
+            Ok(StorePreferenceRow {
+                id: mock_store_a().id,
+                allocation_strategy: AllocationStrategy::Fifo,
+                preferred_location_id: Some(mock_location_1().id),
+                minimum_shelf_life_days: Some(30),
+                requisitions_require_authorisation: true,
+                consumption_calculation_method: ConsumptionCalculationMethod::LeadTimeSafetyStock,
+                lead_time_months: 1.0,
+                safety_stock_months: 0.5,
+            })
+        );
+
+        // Updating one setting keeps the others
+        assert_eq!(
+            service.update_store_preference(
+                &context,
+                &mock_store_a().id,
+                UpdateStorePreference {
+                    lead_time_months: Some(2.0),
+                    ..Default::default()
+                },
+            ),
+            Ok(StorePreferenceRow {
+                id: mock_store_a().id,
+                allocation_strategy: AllocationStrategy::Fifo,
+                preferred_location_id: Some(mock_location_1().id),
+                minimum_shelf_life_days: Some(30),
+                requisitions_require_authorisation: true,
+                consumption_calculation_method: ConsumptionCalculationMethod::LeadTimeSafetyStock,
+                lead_time_months: 2.0,
+                safety_stock_months: 0.5,
+            })
+        );
+
+        // Unset preferred location
+        assert_eq!(
+            service
+                .update_store_preference(
+                    &context,
+                    &mock_store_a().id,
+                    UpdateStorePreference {
+                        preferred_location_id: Some(NullableUpdate { value: None }),
+                        ..Default::default()
+                    },
+                )
+                .map(|preference| (
+                    preference.preferred_location_id,
+                    preference.minimum_shelf_life_days
+                )),
+            Ok((None, Some(30)))
+        );
+
+        // LeadTimeOrSafetyStockIsNegative
+        assert_eq!(
+            service.update_store_preference(
+                &context,
+                &mock_store_a().id,
+                UpdateStorePreference {
+                    safety_stock_months: Some(-1.0),
+                    ..Default::default()
+                },
+            ),
+            Err(UpdateStorePreferenceError::LeadTimeOrSafetyStockIsNegative)
+        );
+
+        // OtherPartyDoesNotExist
+        assert_eq!(
+            service.set_customer_shelf_life(
+                &context,
+                &mock_store_a().id,
+                SetCustomerShelfLife {
+                    name_id: "invalid".to_string(),
+                    minimum_shelf_life_days: Some(60),
+                },
+            ),
+            Err(SetCustomerShelfLifeError::OtherPartyDoesNotExist)
+        );
+
+        // OtherPartyNotACustomer
+        assert_eq!(
+            service.set_customer_shelf_life(
+                &context,
+                &mock_store_a().id,
+                SetCustomerShelfLife {
+                    name_id: mock_name_a().id,
+                    minimum_shelf_life_days: Some(60),
+                },
+            ),
+            Err(SetCustomerShelfLifeError::OtherPartyNotACustomer)
+        );
+
+        // Customer shelf life overrides store shelf life
+        service
+            .set_customer_shelf_life(
+                &context,
+                &mock_store_a().id,
+                SetCustomerShelfLife {
+                    name_id: mock_name_store_b().id,
+                    minimum_shelf_life_days: Some(60),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            get_allocation_preference(&connection, &mock_store_a().id, &mock_name_store_b().id),
+            Ok(AllocationPreference {
+                strategy: AllocationStrategy::Fifo,
+                preferred_location_id: None,
+                minimum_shelf_life_days: Some(60),
+            })
+        );
+
+        // Removing the customer shelf life falls back to the store shelf life
+        service
+            .set_customer_shelf_life(
+                &context,
+                &mock_store_a().id,
+                SetCustomerShelfLife {
+                    name_id: mock_name_store_b().id,
+                    minimum_shelf_life_days: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            get_allocation_preference(&connection, &mock_store_a().id, &mock_name_store_b().id)
+                .unwrap()
+                .minimum_shelf_life_days,
+            Some(30)
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use log::info;
+use rand::{thread_rng, Rng};
+use tokio::{
+    sync::mpsc::{self, error as mpsc_error, Receiver as MpscReceiver, Sender as MpscSender},
+    time::{self, Duration},
+};
+
+use crate::sync_settings::SyncSettings;
+
+pub fn get_sync_actors() -> (SyncSenderActor, SyncReceiverActor) {
+    // We use a single-element channel so that we can only have one sync pending at a time.
+    // We consume this at the *start* of sync, so we could schedule a sync while syncing.
+    // Worst-case scenario, we produce an infinite stream of sync instructions and always go
+    // straight from one sync to the next, but that's OK.
+    let (sender, receiver) = mpsc::channel(1);
+    // Nothing is known about the link to the central server until the first sync
+    let is_online = Arc::new(AtomicBool::new(false));
+
+    let sync_sender = SyncSenderActor {
+        sender,
+        is_online: is_online.clone(),
+    };
+    let sync_receiver = SyncReceiverActor {
+        receiver,
+        is_online,
+    };
+
+    (sync_sender, sync_receiver)
+}
+
+/// Outcome of a sync run, used to schedule the next sync
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyncOutcome {
+    Success,
+    /// Part of the sync failed, e.g. the push, but the central server could be reached
+    PartialFailure,
+    Failure,
+}
+
+#[derive(Clone)]
+pub struct SyncSenderActor {
+    sender: MpscSender<()>,
+    is_online: Arc<AtomicBool>,
+}
+
+impl SyncSenderActor {
+    /// Returns false if a sync is already pending
+    pub fn send(&self) -> bool {
+        match self.sender.try_send(()) {
+            Ok(()) => {
+                info!("Successfully sent sync message");
+                true
+            }
+            Err(mpsc_error::TrySendError::Full(())) => {
+                info!("Failed to send sync message as another sync is already pending");
+                false
+            }
+            Err(mpsc_error::TrySendError::Closed(())) => {
+                unreachable!("Sync channel has closed. Is the receiver dead!?")
+            }
+        }
+    }
+
+    /// Requests a sync after local changes, but only if the latest sync reached the central
+    /// server. Otherwise the changes are pushed with the next scheduled sync.
+    pub fn send_if_online(&self) -> bool {
+        if !self.is_online.load(Ordering::Relaxed) {
+            return false;
+        }
+        self.send()
+    }
+}
+
+pub struct SyncReceiverActor {
+    receiver: MpscReceiver<()>,
+    is_online: Arc<AtomicBool>,
+}
+
+impl SyncReceiverActor {
+    // Runs `sync` whenever a sync message is received or the next sync is due according to the
+    // schedule. The first sync is run straight away.
+    pub async fn listen<F, Fut>(&mut self, mut schedule: SyncSchedule, mut sync: F)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = anyhow::Result<SyncOutcome>>,
+    {
+        let mut delay = Duration::ZERO;
+        loop {
+            tokio::select! {
+                message = self.receiver.recv() => {
+                    if message.is_none() {
+                        unreachable!(
+                            "Sync receiver has stopped listening as channel has closed. Are the senders dead!?"
+                        );
+                    }
+                    info!("Received sync message");
+                }
+                () = time::sleep(delay) => info!("Scheduled sync is due"),
+            };
+
+            info!("Starting sync...");
+            let outcome = match sync().await {
+                Ok(outcome) => {
+                    info!("Finished sync! ({:?})", outcome);
+                    outcome
+                }
+                Err(error) => {
+                    info!("Sync encountered an error!");
+                    info!("{:?}", error);
+                    SyncOutcome::Failure
+                }
+            };
+            self.is_online
+                .store(outcome != SyncOutcome::Failure, Ordering::Relaxed);
+
+            delay = schedule.next_delay(&outcome);
+            info!("Next scheduled sync in {} sec", delay.as_secs());
+        }
+    }
+}
+
+/// Calculates the delay until the next scheduled sync:
+/// - after a successful sync the regular sync interval is used
+/// - after a failure the delay doubles with every consecutive failure (exponential backoff),
+/// starting at the sync interval for failures and at the (shorter) partial failure retry delay
+/// for partial failures
+/// - the backoff is limited to the max backoff (but never less than the sync interval)
+/// - a random jitter is added so that many sites don't sync at the same time
+pub struct SyncSchedule {
+    interval: Duration,
+    max_backoff: Duration,
+    partial_failure_retry: Duration,
+    max_jitter: Duration,
+    consecutive_failures: u32,
+}
+
+impl SyncSchedule {
+    pub fn new(settings: &SyncSettings) -> Self {
+        SyncSchedule {
+            interval: Duration::from_secs(settings.interval_sec),
+            max_backoff: Duration::from_secs(settings.max_backoff_sec),
+            partial_failure_retry: Duration::from_secs(settings.partial_failure_retry_sec),
+            max_jitter: Duration::from_secs(settings.max_jitter_sec),
+            consecutive_failures: 0,
+        }
+    }
+
+    pub fn next_delay(&mut self, outcome: &SyncOutcome) -> Duration {
+        let delay = match outcome {
+            SyncOutcome::Success => {
+                self.consecutive_failures = 0;
+                self.interval
+            }
+            SyncOutcome::PartialFailure => {
+                self.consecutive_failures += 1;
+                self.backoff(self.partial_failure_retry)
+            }
+            SyncOutcome::Failure => {
+                self.consecutive_failures += 1;
+                self.backoff(self.interval)
+            }
+        };
+        delay + self.jitter()
+    }
+
+    fn backoff(&self, base: Duration) -> Duration {
+        let factor = 2u32.saturating_pow(self.consecutive_failures.saturating_sub(1));
+        base.saturating_mul(factor)
+            .min(self.max_backoff.max(self.interval))
+    }
+
+    fn jitter(&self) -> Duration {
+        let max_jitter_ms = self.max_jitter.as_millis() as u64;
+        Duration::from_millis(thread_rng().gen_range(0..=max_jitter_ms))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tokio::time::Duration;
+
+    use crate::sync_settings::SyncSettings;
+
+    use super::{SyncOutcome, SyncSchedule};
+
+    #[test]
+    fn sync_schedule() {
+        let settings = SyncSettings {
+            url: "http://localhost".to_string(),
+            username: "username".to_string(),
+            password_sha256: "password".to_string(),
+            interval_sec: 300,
+            central_server_site_id: 1,
+            site_id: 2,
+            site_hardware_id: "".to_string(),
+            max_backoff_sec: 1000,
+            partial_failure_retry_sec: 30,
+            max_jitter_sec: 0,
+        };
+        let mut schedule = SyncSchedule::new(&settings);
+        let sec = |sec| Duration::from_secs(sec);
+
+        assert_eq!(schedule.next_delay(&SyncOutcome::Success), sec(300));
+        // Backoff up to max backoff
+        assert_eq!(schedule.next_delay(&SyncOutcome::Failure), sec(300));
+        assert_eq!(schedule.next_delay(&SyncOutcome::Failure), sec(600));
+        assert_eq!(schedule.next_delay(&SyncOutcome::Failure), sec(1000));
+        assert_eq!(schedule.next_delay(&SyncOutcome::Failure), sec(1000));
+        // Success resets the backoff
+        assert_eq!(schedule.next_delay(&SyncOutcome::Success), sec(300));
+        // Fast retry after partial failures
+        assert_eq!(schedule.next_delay(&SyncOutcome::PartialFailure), sec(30));
+        assert_eq!(schedule.next_delay(&SyncOutcome::PartialFailure), sec(60));
+        assert_eq!(schedule.next_delay(&SyncOutcome::Success), sec(300));
+
+        // Max backoff is never below the sync interval
+        let mut schedule = SyncSchedule::new(&SyncSettings {
+            max_backoff_sec: 10,
+            ..settings.clone()
+        });
+        assert_eq!(schedule.next_delay(&SyncOutcome::Failure), sec(300));
+        assert_eq!(schedule.next_delay(&SyncOutcome::Failure), sec(300));
+
+        // Jitter
+        let mut schedule = SyncSchedule::new(&SyncSettings {
+            max_jitter_sec: 10,
+            ..settings
+        });
+        for _ in 0..10 {
+            let delay = schedule.next_delay(&SyncOutcome::Success);
+            assert!(delay >= sec(300) && delay <= sec(310));
+        }
+    }
+}
@@ -0,0 +1,43 @@
+use self::{
+    query::get_sync_errors,
+    update::{discard_sync_error, retry_sync_error, UpdateSyncErrorError},
+};
+
+use crate::{service_provider::ServiceContext, ListError, ListResult};
+use repository::{PaginationOption, SyncErrorRow};
+
+pub mod query;
+pub mod update;
+
+/// Quarantined records are retried automatically on every sync until they have been attempted
+/// this many times, after that they are only retried when requested through the API
+pub const MAX_AUTOMATIC_ATTEMPTS: i32 = 10;
+
+pub trait SyncErrorServiceTrait: Sync + Send {
+    fn get_sync_errors(
+        &self,
+        ctx: &ServiceContext,
+        pagination: Option<PaginationOption>,
+    ) -> Result<ListResult<SyncErrorRow>, ListError> {
+        get_sync_errors(ctx, pagination)
+    }
+
+    fn retry_sync_error(
+        &self,
+        ctx: &ServiceContext,
+        id: &str,
+    ) -> Result<SyncErrorRow, UpdateSyncErrorError> {
+        retry_sync_error(ctx, id)
+    }
+
+    fn discard_sync_error(
+        &self,
+        ctx: &ServiceContext,
+        id: &str,
+    ) -> Result<String, UpdateSyncErrorError> {
+        discard_sync_error(ctx, id)
+    }
+}
+
+pub struct SyncErrorService {}
+impl SyncErrorServiceTrait for SyncErrorService {}
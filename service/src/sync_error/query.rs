@@ -0,0 +1,21 @@
+use repository::{PaginationOption, SyncErrorRow, SyncErrorRowRepository};
+
+use crate::{
+    get_default_pagination, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
+};
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+pub fn get_sync_errors(
+    ctx: &ServiceContext,
+    pagination: Option<PaginationOption>,
+) -> Result<ListResult<SyncErrorRow>, ListError> {
+    let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+    let repository = SyncErrorRowRepository::new(&ctx.connection);
+
+    Ok(ListResult {
+        rows: repository.query(pagination)?,
+        count: i64_to_u32(repository.count()?),
+    })
+}
@@ -0,0 +1,121 @@
+use repository::{RepositoryError, StorageConnection, SyncErrorRow, SyncErrorRowRepository};
+
+use crate::service_provider::ServiceContext;
+
+#[derive(Debug, PartialEq)]
+pub enum UpdateSyncErrorError {
+    SyncErrorDoesNotExist,
+    DatabaseError(RepositoryError),
+}
+
+/// Resets the attempt count so the record is retried on the next sync(s)
+pub fn retry_sync_error(
+    ctx: &ServiceContext,
+    id: &str,
+) -> Result<SyncErrorRow, UpdateSyncErrorError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            let sync_error = SyncErrorRow {
+                attempt_count: 0,
+                ..validate(connection, id)?
+            };
+            SyncErrorRowRepository::new(connection).upsert_one(&sync_error)?;
+            Ok(sync_error)
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+/// Removes the record from the quarantine, the record is not imported
+pub fn discard_sync_error(ctx: &ServiceContext, id: &str) -> Result<String, UpdateSyncErrorError> {
+    ctx.connection
+        .transaction_sync(|connection| {
+            validate(connection, id)?;
+            SyncErrorRowRepository::new(connection).delete(id)?;
+            Ok(id.to_string())
+        })
+        .map_err(|error| error.to_inner_error())
+}
+
+fn validate(
+    connection: &StorageConnection,
+    id: &str,
+) -> Result<SyncErrorRow, UpdateSyncErrorError> {
+    SyncErrorRowRepository::new(connection)
+        .find_one_by_id(id)?
+        .ok_or(UpdateSyncErrorError::SyncErrorDoesNotExist)
+}
+
+impl From<RepositoryError> for UpdateSyncErrorError {
+    fn from(error: RepositoryError) -> Self {
+        UpdateSyncErrorError::DatabaseError(error)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::NaiveDate;
+    use repository::{
+        mock::MockDataInserts, test_db::setup_all, SyncErrorRow, SyncErrorRowRepository,
+        SyncErrorSource,
+    };
+
+    use crate::{service_provider::ServiceProvider, sync_error::update::UpdateSyncErrorError};
+
+    #[actix_rt::test]
+    async fn retry_and_discard_sync_error() {
+        let (_, connection, connection_manager, _) =
+            setup_all("retry_and_discard_sync_error", MockDataInserts::none()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.sync_error_service;
+
+        let datetime = NaiveDate::from_ymd(2022, 6, 14).and_hms(10, 0, 0);
+        let sync_error = SyncErrorRow {
+            id: "sync_error".to_string(),
+            source: SyncErrorSource::Central,
+            table_name: "item".to_string(),
+            record_id: "item_a".to_string(),
+            remote_action: None,
+            data: "{}".to_string(),
+            error_message: "missing field `code`".to_string(),
+            attempt_count: 10,
+            created_datetime: datetime,
+            last_attempt_datetime: datetime,
+        };
+        let repository = SyncErrorRowRepository::new(&connection);
+        repository.upsert_one(&sync_error).unwrap();
+
+        // SyncErrorDoesNotExist
+        assert_eq!(
+            service.retry_sync_error(&context, "invalid"),
+            Err(UpdateSyncErrorError::SyncErrorDoesNotExist)
+        );
+        assert_eq!(
+            service.discard_sync_error(&context, "invalid"),
+            Err(UpdateSyncErrorError::SyncErrorDoesNotExist)
+        );
+
+        // Retry resets the attempt count
+        let result = service.retry_sync_error(&context, "sync_error").unwrap();
+        assert_eq!(
+            result,
+            SyncErrorRow {
+                attempt_count: 0,
+                ..sync_error
+            }
+        );
+        assert_eq!(
+            repository.find_one_by_id("sync_error").unwrap(),
+            Some(result)
+        );
+
+        // Discard
+        assert_eq!(
+            service.discard_sync_error(&context, "sync_error"),
+            Ok("sync_error".to_string())
+        );
+        assert_eq!(repository.find_one_by_id("sync_error").unwrap(), None);
+        assert_eq!(service.get_sync_errors(&context, None).unwrap().count, 0);
+    }
+}
@@ -106,6 +106,9 @@ pub fn generate_linked_invoice(
         allocated_datetime: None,
         delivered_datetime: None,
         verified_datetime: None,
+        prescriber: None,
+        diagnosis: None,
+        directions: None,
     };
 
     Ok(result)
@@ -160,6 +163,9 @@ pub fn regenerate_linked_invoice_lines(
                  total_after_tax: _,
                  total_before_tax: _,
                  tax: _,
+                 original_invoice_line_id: _,
+                 return_reason: _,
+                 inventory_adjustment_reason_id: _,
              }| {
                 let cost_price_per_pack = sell_price_per_pack;
                 InvoiceLineRow {
@@ -189,6 +195,9 @@ pub fn regenerate_linked_invoice_lines(
                     location_id: None,
                     sell_price_per_pack: 0.0,
                     tax: Some(0.0),
+                    original_invoice_line_id: None,
+                    return_reason: None,
+                    inventory_adjustment_reason_id: None,
                 }
             },
         )
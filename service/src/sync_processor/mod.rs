@@ -1,8 +1,8 @@
 use repository::EqualFilter;
 use repository::{
-    InvoiceFilter, InvoiceRepository, InvoiceRow, NameRow, NameRowRepository, RepositoryError,
-    RequisitionFilter, RequisitionRepository, RequisitionRow, StorageConnection, StoreRow,
-    StoreRowRepository,
+    InvoiceFilter, InvoiceRepository, InvoiceRow, KeyValueStoreRepository, KeyValueType, NameRow,
+    NameRowRepository, RepositoryError, RequisitionFilter, RequisitionRepository, RequisitionRow,
+    StorageConnection, StoreRow, StoreRowRepository,
 };
 
 use self::{
@@ -182,23 +182,48 @@ fn get_linked_record(
     Ok(result)
 }
 
-fn is_active_record_on_site(_: &StorageConnection, _: &Record) -> Result<bool, RepositoryError> {
-    // TODO
-    Ok(true)
+fn is_active_record_on_site(
+    connection: &StorageConnection,
+    record: &Record,
+) -> Result<bool, RepositoryError> {
+    let store_id = match record {
+        Record::RequisitionRow(requisition_row) => &requisition_row.store_id,
+        Record::InvoiceRow(invoice_row) => &invoice_row.store_id,
+    };
+
+    let store_row = StoreRowRepository::new(connection).find_one_by_id(store_id)?;
+    Ok(match store_row {
+        Some(store_row) => is_store_on_site(connection, &store_row)?,
+        None => false,
+    })
 }
 
 fn is_other_party_active_on_site(
-    _: &StorageConnection,
+    connection: &StorageConnection,
     store_row: &Option<StoreRow>,
     _: &Record,
 ) -> Result<bool, RepositoryError> {
-    // TODO
     Ok(match store_row {
-        Some(_) => true,
+        Some(store_row) => is_store_on_site(connection, store_row)?,
         None => false,
     })
 }
 
+/// Checks if the store is managed by this site.
+/// If the site id is not known yet, e.g. sync hasn't been configured, all stores are treated as
+/// being on this site.
+fn is_store_on_site(
+    connection: &StorageConnection,
+    store_row: &StoreRow,
+) -> Result<bool, RepositoryError> {
+    let site_id =
+        KeyValueStoreRepository::new(connection).get_i32(KeyValueType::SettingsSyncSideId)?;
+    Ok(match site_id {
+        Some(site_id) => store_row.site_id == site_id,
+        None => true,
+    })
+}
+
 impl From<RepositoryError> for ProcessRecordError {
     fn from(error: RepositoryError) -> Self {
         ProcessRecordError::DatabaseError(error)
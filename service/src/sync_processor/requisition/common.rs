@@ -88,12 +88,15 @@ pub fn generate_linked_requisition(
         min_months_of_stock: source_requisition.min_months_of_stock.clone(),
         linked_requisition_id: Some(source_requisition.id.clone()),
         expected_delivery_date: source_requisition.expected_delivery_date,
+        program_id: source_requisition.program_id.clone(),
+        period_id: source_requisition.period_id.clone(),
         // Default
         user_id: None,
         sent_datetime: None,
         finalised_datetime: None,
         colour: None,
         comment: None,
+        approved_by_user_id: None,
     };
 
     Ok(result)
@@ -120,9 +123,19 @@ fn generate_linked_requisition_lines(
                 .requisition_line_row
                 .average_monthly_consumption,
             snapshot_datetime: source_line.requisition_line_row.snapshot_datetime,
+            opening_balance: source_line.requisition_line_row.opening_balance,
+            received_quantity: source_line.requisition_line_row.received_quantity,
+            issued_quantity: source_line.requisition_line_row.issued_quantity,
+            adjusted_quantity: source_line.requisition_line_row.adjusted_quantity,
+            closing_balance: source_line.requisition_line_row.closing_balance,
+            consumption_calculation_method: source_line
+                .requisition_line_row
+                .consumption_calculation_method,
             // Default
             supply_quantity: 0,
             comment: None,
+            approved_quantity: 0,
+            approval_comment: None,
         });
     }
 
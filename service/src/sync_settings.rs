@@ -1,3 +1,7 @@
+pub const DEFAULT_MAX_BACKOFF_SEC: u64 = 60 * 60;
+pub const DEFAULT_PARTIAL_FAILURE_RETRY_SEC: u64 = 60;
+pub const DEFAULT_MAX_JITTER_SEC: u64 = 30;
+
 #[derive(serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct SyncSettings {
     pub url: String,
@@ -8,4 +12,26 @@ pub struct SyncSettings {
     pub central_server_site_id: u32,
     pub site_id: u32,
     pub site_hardware_id: String,
+    /// Upper limit of the delay between retries after consecutive sync failures
+    #[serde(default = "default_max_backoff_sec")]
+    pub max_backoff_sec: u64,
+    /// Delay before retrying after a partial failure, e.g. when only the push failed
+    #[serde(default = "default_partial_failure_retry_sec")]
+    pub partial_failure_retry_sec: u64,
+    /// Random delay of up to this many sec is added to every scheduled sync so that many sites
+    /// don't sync at the same time
+    #[serde(default = "default_max_jitter_sec")]
+    pub max_jitter_sec: u64,
+}
+
+fn default_max_backoff_sec() -> u64 {
+    DEFAULT_MAX_BACKOFF_SEC
+}
+
+fn default_partial_failure_retry_sec() -> u64 {
+    DEFAULT_PARTIAL_FAILURE_RETRY_SEC
+}
+
+fn default_max_jitter_sec() -> u64 {
+    DEFAULT_MAX_JITTER_SEC
 }
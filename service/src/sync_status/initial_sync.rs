@@ -0,0 +1,199 @@
+use std::collections::BTreeMap;
+
+use repository::{KeyValueStoreRepository, KeyValueType, RepositoryError, StorageConnection};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InitialSyncSource {
+    Central,
+    Remote,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InitialSyncTableProgress {
+    pub pulled: u32,
+    pub integrated: u32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InitialSyncSourceProgress {
+    /// Total number of records to pull, as far as known from the central server
+    pub queue_length: u32,
+    /// Progress by table name
+    pub tables: BTreeMap<String, InitialSyncTableProgress>,
+}
+
+impl InitialSyncSourceProgress {
+    pub fn pulled(&self) -> u32 {
+        self.tables.values().map(|table| table.pulled).sum()
+    }
+
+    pub fn integrated(&self) -> u32 {
+        self.tables.values().map(|table| table.integrated).sum()
+    }
+}
+
+/// Progress of the initial sync, stored in the key value store so that it survives restarts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InitialSyncProgress {
+    pub central: InitialSyncSourceProgress,
+    pub remote: InitialSyncSourceProgress,
+    /// Error of the latest initial sync attempt, cleared when the initial sync is resumed
+    pub error_message: Option<String>,
+}
+
+impl InitialSyncProgress {
+    pub fn load(connection: &StorageConnection) -> Result<InitialSyncProgress, RepositoryError> {
+        let value = KeyValueStoreRepository::new(connection)
+            .get_string(KeyValueType::InitialSyncProgress)?;
+        // Progress is only informational, start from scratch if it can't be read
+        let progress = value
+            .and_then(|value| serde_json::from_str(&value).ok())
+            .unwrap_or_default();
+        Ok(progress)
+    }
+
+    fn save(&self, connection: &StorageConnection) -> Result<(), RepositoryError> {
+        let value = serde_json::to_string(self).map_err(|error| {
+            RepositoryError::as_db_error("Failed to serialise initial sync progress", error)
+        })?;
+        KeyValueStoreRepository::new(connection)
+            .set_string(KeyValueType::InitialSyncProgress, Some(value))
+    }
+
+    fn source_mut(&mut self, source: InitialSyncSource) -> &mut InitialSyncSourceProgress {
+        match source {
+            InitialSyncSource::Central => &mut self.central,
+            InitialSyncSource::Remote => &mut self.remote,
+        }
+    }
+}
+
+/// Records the progress of the initial sync. Progress of a previous attempt is kept, i.e. the
+/// counts continue where the interrupted attempt stopped.
+pub struct InitialSyncLogger<'a> {
+    connection: &'a StorageConnection,
+    progress: InitialSyncProgress,
+}
+
+impl<'a> InitialSyncLogger<'a> {
+    pub fn start(connection: &'a StorageConnection) -> Result<Self, RepositoryError> {
+        let mut progress = InitialSyncProgress::load(connection)?;
+        progress.error_message = None;
+        progress.save(connection)?;
+
+        Ok(InitialSyncLogger {
+            connection,
+            progress,
+        })
+    }
+
+    /// Sets the number of records that are still to be pulled
+    pub fn set_remaining(
+        &mut self,
+        source: InitialSyncSource,
+        remaining: u32,
+    ) -> Result<(), RepositoryError> {
+        let progress = self.progress.source_mut(source);
+        progress.queue_length = progress.pulled() + remaining;
+        self.progress.save(self.connection)
+    }
+
+    pub fn pulled<'b>(
+        &mut self,
+        source: InitialSyncSource,
+        table_names: impl IntoIterator<Item = &'b str>,
+    ) -> Result<(), RepositoryError> {
+        let progress = self.progress.source_mut(source);
+        for table_name in table_names {
+            progress
+                .tables
+                .entry(table_name.to_string())
+                .or_default()
+                .pulled += 1;
+        }
+        self.progress.save(self.connection)
+    }
+
+    pub fn integrated(
+        &mut self,
+        source: InitialSyncSource,
+        table_name: &str,
+        count: u32,
+    ) -> Result<(), RepositoryError> {
+        self.progress
+            .source_mut(source)
+            .tables
+            .entry(table_name.to_string())
+            .or_default()
+            .integrated += count;
+        self.progress.save(self.connection)
+    }
+
+    pub fn error(mut self, error_message: String) -> Result<(), RepositoryError> {
+        self.progress.error_message = Some(error_message);
+        self.progress.save(self.connection)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{mock::MockDataInserts, test_db::setup_all};
+
+    use crate::{
+        service_provider::ServiceProvider,
+        sync_status::initial_sync::{InitialSyncLogger, InitialSyncSource},
+    };
+
+    #[actix_rt::test]
+    async fn initial_sync_logger() {
+        let (_, connection, connection_manager, _) =
+            setup_all("initial_sync_logger", MockDataInserts::none()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.sync_status_service;
+
+        let status = service.get_initial_sync_status(&context).unwrap();
+        assert_eq!(status.is_finished, false);
+        assert_eq!(status.progress.central.queue_length, 0);
+
+        let mut logger = InitialSyncLogger::start(&connection).unwrap();
+        logger
+            .pulled(InitialSyncSource::Central, vec!["item", "item", "name"])
+            .unwrap();
+        logger.set_remaining(InitialSyncSource::Central, 7).unwrap();
+        logger
+            .integrated(InitialSyncSource::Central, "item", 2)
+            .unwrap();
+        logger
+            .pulled(InitialSyncSource::Remote, vec!["transact"])
+            .unwrap();
+        logger.error("Connection refused".to_string()).unwrap();
+
+        let progress = service.get_initial_sync_status(&context).unwrap().progress;
+        assert_eq!(progress.central.queue_length, 10);
+        assert_eq!(progress.central.pulled(), 3);
+        assert_eq!(progress.central.integrated(), 2);
+        assert_eq!(progress.central.tables["item"].pulled, 2);
+        assert_eq!(progress.central.tables["name"].integrated, 0);
+        assert_eq!(progress.remote.pulled(), 1);
+        assert_eq!(
+            progress.error_message,
+            Some("Connection refused".to_string())
+        );
+
+        // Resumed initial sync continues with the previous progress
+        let mut logger = InitialSyncLogger::start(&connection).unwrap();
+        logger
+            .pulled(InitialSyncSource::Central, vec!["name"])
+            .unwrap();
+        logger.set_remaining(InitialSyncSource::Central, 0).unwrap();
+
+        let progress = service.get_initial_sync_status(&context).unwrap().progress;
+        assert_eq!(progress.central.queue_length, 4);
+        assert_eq!(progress.central.tables["name"].pulled, 2);
+        assert_eq!(progress.remote.pulled(), 1);
+        assert_eq!(progress.error_message, None);
+    }
+}
@@ -0,0 +1,158 @@
+use chrono::Utc;
+use repository::{
+    RepositoryError, StorageConnection, SyncLogPhase, SyncLogRow, SyncLogRowRepository,
+};
+use util::uuid::uuid;
+
+pub const INTERRUPTED_SYNC_MESSAGE: &str = "Sync was interrupted before it finished";
+
+/// Records the progress of a single sync run in the sync log
+pub struct SyncLogger<'a> {
+    connection: &'a StorageConnection,
+    row: SyncLogRow,
+}
+
+impl<'a> SyncLogger<'a> {
+    /// Starts a new sync run in the push phase. A previous run that never finished (e.g. because
+    /// the server was stopped while syncing) is marked as interrupted.
+    pub fn start(connection: &'a StorageConnection) -> Result<Self, RepositoryError> {
+        let repository = SyncLogRowRepository::new(connection);
+        let now = Utc::now().naive_utc();
+
+        if let Some(unfinished) = repository
+            .find_latest()?
+            .filter(|log| log.finished_datetime.is_none())
+        {
+            repository.upsert_one(&SyncLogRow {
+                finished_datetime: Some(now),
+                error_message: Some(INTERRUPTED_SYNC_MESSAGE.to_string()),
+                ..unfinished
+            })?;
+        }
+
+        let row = SyncLogRow {
+            id: uuid(),
+            started_datetime: now,
+            finished_datetime: None,
+            phase: SyncLogPhase::Push,
+            push_record_count: 0,
+            remote_pull_record_count: 0,
+            central_pull_record_count: 0,
+            integration_record_count: 0,
+            error_message: None,
+        };
+        repository.upsert_one(&row)?;
+
+        Ok(SyncLogger { connection, row })
+    }
+
+    pub fn start_phase(&mut self, phase: SyncLogPhase) -> Result<(), RepositoryError> {
+        self.row.phase = phase;
+        self.save()
+    }
+
+    /// Sets the number of records handled in the current phase
+    pub fn finish_phase(&mut self, record_count: u32) -> Result<(), RepositoryError> {
+        let record_count = record_count as i32;
+        match self.row.phase {
+            SyncLogPhase::Push => self.row.push_record_count = record_count,
+            SyncLogPhase::RemotePull => self.row.remote_pull_record_count = record_count,
+            SyncLogPhase::CentralPull => self.row.central_pull_record_count = record_count,
+            SyncLogPhase::Integrate => self.row.integration_record_count = record_count,
+        }
+        self.save()
+    }
+
+    pub fn success(mut self) -> Result<SyncLogRow, RepositoryError> {
+        self.row.finished_datetime = Some(Utc::now().naive_utc());
+        self.save()?;
+        Ok(self.row)
+    }
+
+    /// Finishes the sync run with an error, the phase is left at the phase that failed
+    pub fn error(mut self, error_message: String) -> Result<SyncLogRow, RepositoryError> {
+        self.row.finished_datetime = Some(Utc::now().naive_utc());
+        self.row.error_message = Some(error_message);
+        self.save()?;
+        Ok(self.row)
+    }
+
+    fn save(&self) -> Result<(), RepositoryError> {
+        SyncLogRowRepository::new(self.connection).upsert_one(&self.row)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use repository::{mock::MockDataInserts, test_db::setup_all, SyncLogPhase, SyncLogRowRepository};
+
+    use crate::{
+        service_provider::ServiceProvider,
+        sync_status::logger::{SyncLogger, INTERRUPTED_SYNC_MESSAGE},
+    };
+
+    #[actix_rt::test]
+    async fn sync_logger() {
+        let (_, connection, connection_manager, _) =
+            setup_all("sync_logger", MockDataInserts::none()).await;
+
+        let service_provider = ServiceProvider::new(connection_manager);
+        let context = service_provider.context().unwrap();
+        let service = service_provider.sync_status_service;
+
+        let status = service.get_sync_status(&context).unwrap();
+        assert_eq!(status.is_syncing, false);
+        assert_eq!(status.latest, None);
+
+        // Successful run
+        let mut logger = SyncLogger::start(&connection).unwrap();
+        logger.finish_phase(3).unwrap();
+        logger.start_phase(SyncLogPhase::RemotePull).unwrap();
+        logger.finish_phase(5).unwrap();
+
+        let status = service.get_sync_status(&context).unwrap();
+        assert_eq!(status.is_syncing, true);
+        assert_eq!(status.latest_successful, None);
+
+        logger.start_phase(SyncLogPhase::CentralPull).unwrap();
+        logger.finish_phase(7).unwrap();
+        logger.start_phase(SyncLogPhase::Integrate).unwrap();
+        logger.finish_phase(12).unwrap();
+        let successful = logger.success().unwrap();
+        assert_eq!(successful.push_record_count, 3);
+        assert_eq!(successful.remote_pull_record_count, 5);
+        assert_eq!(successful.central_pull_record_count, 7);
+        assert_eq!(successful.integration_record_count, 12);
+
+        let status = service.get_sync_status(&context).unwrap();
+        assert_eq!(status.is_syncing, false);
+        assert_eq!(status.latest, Some(successful.clone()));
+        assert_eq!(status.latest_successful, Some(successful.clone()));
+
+        // Failed run keeps the phase that failed
+        let mut logger = SyncLogger::start(&connection).unwrap();
+        logger.start_phase(SyncLogPhase::RemotePull).unwrap();
+        let failed = logger.error("Connection refused".to_string()).unwrap();
+        assert_eq!(failed.phase, SyncLogPhase::RemotePull);
+
+        let status = service.get_sync_status(&context).unwrap();
+        assert_eq!(status.latest, Some(failed.clone()));
+        assert_eq!(status.latest_successful, Some(successful.clone()));
+
+        // Unfinished run is marked as interrupted when the next run starts
+        let unfinished_id = SyncLogger::start(&connection).unwrap().row.id.clone();
+        SyncLogger::start(&connection).unwrap();
+        let interrupted = SyncLogRowRepository::new(&connection)
+            .find_one_by_id(&unfinished_id)
+            .unwrap()
+            .unwrap();
+        assert!(interrupted.finished_datetime.is_some());
+        assert_eq!(
+            interrupted.error_message,
+            Some(INTERRUPTED_SYNC_MESSAGE.to_string())
+        );
+
+        let history = service.get_sync_logs(&context, None).unwrap();
+        assert_eq!(history.count, 4);
+    }
+}
@@ -0,0 +1,34 @@
+use self::query::{
+    get_initial_sync_status, get_sync_logs, get_sync_status, InitialSyncStatus, SyncStatus,
+};
+
+use crate::{service_provider::ServiceContext, ListError, ListResult};
+use repository::{PaginationOption, RepositoryError, SyncLogRow};
+
+pub mod initial_sync;
+pub mod logger;
+pub mod query;
+
+pub trait SyncStatusServiceTrait: Sync + Send {
+    fn get_sync_status(&self, ctx: &ServiceContext) -> Result<SyncStatus, RepositoryError> {
+        get_sync_status(ctx)
+    }
+
+    fn get_sync_logs(
+        &self,
+        ctx: &ServiceContext,
+        pagination: Option<PaginationOption>,
+    ) -> Result<ListResult<SyncLogRow>, ListError> {
+        get_sync_logs(ctx, pagination)
+    }
+
+    fn get_initial_sync_status(
+        &self,
+        ctx: &ServiceContext,
+    ) -> Result<InitialSyncStatus, RepositoryError> {
+        get_initial_sync_status(ctx)
+    }
+}
+
+pub struct SyncStatusService {}
+impl SyncStatusServiceTrait for SyncStatusService {}
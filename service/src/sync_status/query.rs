@@ -0,0 +1,66 @@
+use repository::{
+    KeyValueStoreRepository, KeyValueType, PaginationOption, RepositoryError, SyncLogRow,
+    SyncLogRowRepository,
+};
+
+use crate::{
+    get_default_pagination, i64_to_u32, service_provider::ServiceContext, ListError, ListResult,
+};
+
+use super::initial_sync::InitialSyncProgress;
+
+pub const MAX_LIMIT: u32 = 1000;
+pub const MIN_LIMIT: u32 = 1;
+
+#[derive(Debug, PartialEq)]
+pub struct SyncStatus {
+    /// A sync run has started and not finished yet
+    pub is_syncing: bool,
+    pub latest: Option<SyncLogRow>,
+    pub latest_successful: Option<SyncLogRow>,
+}
+
+pub fn get_sync_status(ctx: &ServiceContext) -> Result<SyncStatus, RepositoryError> {
+    let repository = SyncLogRowRepository::new(&ctx.connection);
+    let latest = repository.find_latest()?;
+
+    Ok(SyncStatus {
+        is_syncing: latest
+            .as_ref()
+            .map(|log| log.finished_datetime.is_none())
+            .unwrap_or(false),
+        latest,
+        latest_successful: repository.find_latest_successful()?,
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub struct InitialSyncStatus {
+    /// Remote data has been pulled and integrated, i.e. the initial sync is done
+    pub is_finished: bool,
+    pub progress: InitialSyncProgress,
+}
+
+pub fn get_initial_sync_status(ctx: &ServiceContext) -> Result<InitialSyncStatus, RepositoryError> {
+    let is_finished = KeyValueStoreRepository::new(&ctx.connection)
+        .get_bool(KeyValueType::RemoteSyncInitilisationFinished)?
+        .unwrap_or(false);
+
+    Ok(InitialSyncStatus {
+        is_finished,
+        progress: InitialSyncProgress::load(&ctx.connection)?,
+    })
+}
+
+pub fn get_sync_logs(
+    ctx: &ServiceContext,
+    pagination: Option<PaginationOption>,
+) -> Result<ListResult<SyncLogRow>, ListError> {
+    let pagination = get_default_pagination(pagination, MAX_LIMIT, MIN_LIMIT)?;
+    let repository = SyncLogRowRepository::new(&ctx.connection);
+
+    Ok(ListResult {
+        rows: repository.query(pagination)?,
+        count: i64_to_u32(repository.count()?),
+    })
+}
@@ -4,9 +4,11 @@ use anyhow::anyhow;
 use chrono::Utc;
 use jsonwebtoken::errors::{Error as JWTError, ErrorKind as JWTErrorKind};
 use log::error;
+use repository::{KeyValueStoreRepository, KeyValueType, RepositoryError, StorageConnection};
 use serde::{Deserialize, Serialize};
+use util::uuid::uuid;
 
-use super::token_bucket::TokenBucket;
+use super::token_bucket::{SessionInfo, TokenBucket};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Audience {
@@ -32,6 +34,9 @@ pub struct OmSupplyClaim {
     pub iss: String,
     /// Subject (user id the token refers to)
     pub sub: String,
+    /// JWT ID, makes every issued token unique (even when issued for the same user at the same
+    /// time)
+    pub jti: String,
 }
 
 /// Error for getting a JWT token
@@ -39,6 +44,7 @@ pub struct OmSupplyClaim {
 pub enum JWTIssuingError {
     CanNotCreateToken(JWTError),
     ConcurrencyLockError(anyhow::Error),
+    DatabaseError(RepositoryError),
 }
 
 #[derive(Debug)]
@@ -60,11 +66,19 @@ pub enum JWTRefreshError {
     /// Token has been invalidated on the backend
     TokenInvalided,
     ConcurrencyLockError(anyhow::Error),
+    DatabaseError(RepositoryError),
 }
 
 #[derive(Debug)]
 pub enum JWTLogoutError {
     ConcurrencyLockError(anyhow::Error),
+    DatabaseError(RepositoryError),
+}
+
+#[derive(Debug)]
+pub enum JWTSessionError {
+    ConcurrencyLockError(anyhow::Error),
+    DatabaseError(RepositoryError),
 }
 
 #[derive(Debug)]
@@ -147,8 +161,18 @@ impl<'a> TokenService<'a> {
             error!("{}", e);
             return JWTIssuingError::ConcurrencyLockError(anyhow!("jwt_token: {}", e));
         })?;
-        token_bucket.put(user_id, &pair.token, pair.expiry_date);
-        token_bucket.put(user_id, &pair.refresh, pair.refresh_expiry_date);
+        let session_id = uuid();
+        token_bucket
+            .put(user_id, &session_id, &pair.token, pair.expiry_date)
+            .map_err(JWTIssuingError::DatabaseError)?;
+        token_bucket
+            .put(
+                user_id,
+                &session_id,
+                &pair.refresh,
+                pair.refresh_expiry_date,
+            )
+            .map_err(JWTIssuingError::DatabaseError)?;
 
         Ok(pair)
     }
@@ -200,10 +224,23 @@ impl<'a> TokenService<'a> {
         if !token_bucket.contains(&user_id, refresh_token) {
             return Err(JWTRefreshError::TokenInvalided);
         }
-
-        // add new tokens to bucket
-        token_bucket.put(&user_id, &pair.token, pair.expiry_date);
-        token_bucket.put(&user_id, &pair.refresh, pair.refresh_expiry_date);
+        let session_id = match token_bucket.session_id(&user_id, refresh_token) {
+            Some(session_id) => session_id,
+            None => return Err(JWTRefreshError::TokenInvalided),
+        };
+
+        // add new tokens to bucket (the new tokens belong to the same session)
+        token_bucket
+            .put(&user_id, &session_id, &pair.token, pair.expiry_date)
+            .map_err(JWTRefreshError::DatabaseError)?;
+        token_bucket
+            .put(
+                &user_id,
+                &session_id,
+                &pair.refresh,
+                pair.refresh_expiry_date,
+            )
+            .map_err(JWTRefreshError::DatabaseError)?;
         // Shorten the expiry time of the old refresh token.
         //
         // Note, if the client goes offline before receiving the new refresh token the user might
@@ -211,7 +248,9 @@ impl<'a> TokenService<'a> {
         // issue.
         let reduced_expiry =
             std::cmp::min(Utc::now().timestamp() as usize + 5 * 60, decoded.claims.exp);
-        token_bucket.put(&user_id, refresh_token, reduced_expiry);
+        token_bucket
+            .put(&user_id, &session_id, refresh_token, reduced_expiry)
+            .map_err(JWTRefreshError::DatabaseError)?;
 
         Ok(pair)
     }
@@ -260,9 +299,65 @@ impl<'a> TokenService<'a> {
             error!("logout: {}", e);
             JWTLogoutError::ConcurrencyLockError(anyhow!("logout: {}", e))
         })?;
-        token_bucket.clear(user_id);
+        token_bucket
+            .clear(user_id)
+            .map_err(JWTLogoutError::DatabaseError)?;
         Ok(())
     }
+
+    /// Returns the session id of a valid token
+    pub fn session_id(
+        &self,
+        user_id: &str,
+        token: &str,
+    ) -> Result<Option<String>, JWTSessionError> {
+        let token_bucket = self.token_bucket.read().map_err(|e| {
+            error!("session_id: {}", e);
+            JWTSessionError::ConcurrencyLockError(anyhow!("session_id: {}", e))
+        })?;
+        Ok(token_bucket.session_id(user_id, token))
+    }
+
+    /// Lists all active sessions of a user
+    pub fn sessions(&self, user_id: &str) -> Result<Vec<SessionInfo>, JWTSessionError> {
+        let token_bucket = self.token_bucket.read().map_err(|e| {
+            error!("sessions: {}", e);
+            JWTSessionError::ConcurrencyLockError(anyhow!("sessions: {}", e))
+        })?;
+        Ok(token_bucket.sessions(user_id))
+    }
+
+    /// Log a user out of a single session.
+    /// Returns false if the session is not known for the user.
+    pub fn revoke_session(
+        &mut self,
+        user_id: &str,
+        session_id: &str,
+    ) -> Result<bool, JWTSessionError> {
+        let mut token_bucket = self.token_bucket.write().map_err(|e| {
+            error!("revoke_session: {}", e);
+            JWTSessionError::ConcurrencyLockError(anyhow!("revoke_session: {}", e))
+        })?;
+        token_bucket
+            .remove_session(user_id, session_id)
+            .map_err(JWTSessionError::DatabaseError)
+    }
+}
+
+/// Returns the persisted secret to sign auth tokens or creates and stores a new secret if there is
+/// none yet.
+///
+/// Persisting the secret keeps issued tokens valid across server restarts.
+pub fn load_or_create_token_secret(
+    connection: &StorageConnection,
+) -> Result<String, RepositoryError> {
+    let repo = KeyValueStoreRepository::new(connection);
+    if let Some(secret) = repo.get_string(KeyValueType::ServerAuthTokenSecret)? {
+        return Ok(secret);
+    }
+    let secret = uuid();
+    repo.set_string(KeyValueType::ServerAuthTokenSecret, Some(secret.clone()))?;
+    Ok(secret)
 }
 
 /// Creates a token and refresh token pair
@@ -283,6 +378,7 @@ fn create_jwt_pair(
         iat: now,
         iss: ISSUER.to_string(),
         sub: user_id.to_owned(),
+        jti: uuid(),
     };
     let api_token = jsonwebtoken::encode(
         &jsonwebtoken::Header::default(),
@@ -297,6 +393,7 @@ fn create_jwt_pair(
         iat: now,
         iss: ISSUER.to_string(),
         sub: user_id.to_owned(),
+        jti: uuid(),
     };
     let refresh_token = jsonwebtoken::encode(
         &jsonwebtoken::Header::default(),
@@ -364,6 +461,48 @@ mod user_account_test {
         assert!(matches!(err, JWTRefreshError::TokenInvalided));
     }
 
+    #[actix_rt::test]
+    async fn test_user_sessions() {
+        let bucket = RwLock::new(TokenBucket::new());
+        const JWT_TOKEN_SECRET: &[u8] = "some secret".as_bytes();
+        let user_id = "test_user_id";
+        let mut service = TokenService::new(&bucket, JWT_TOKEN_SECRET);
+
+        // every login creates a new session
+        let token_pair_a = service.jwt_token(user_id, 60, 120).unwrap();
+        let token_pair_b = service.jwt_token(user_id, 60, 120).unwrap();
+        let sessions = service.sessions(user_id).unwrap();
+        assert_eq!(sessions.len(), 2);
+        let session_a = service
+            .session_id(user_id, &token_pair_a.token)
+            .unwrap()
+            .unwrap();
+
+        // refreshed tokens stay in the same session
+        let token_pair_a = service
+            .refresh_token(&token_pair_a.refresh, 60, 120, Some(0))
+            .unwrap();
+        assert_eq!(
+            service.session_id(user_id, &token_pair_a.token).unwrap(),
+            Some(session_a.clone())
+        );
+        assert_eq!(service.sessions(user_id).unwrap().len(), 2);
+
+        // revoking a session only logs out the tokens of that session
+        assert!(service.revoke_session(user_id, &session_a).unwrap());
+        assert!(!service.revoke_session(user_id, &session_a).unwrap());
+        let err = service
+            .verify_token(&token_pair_a.token, Some(0))
+            .unwrap_err();
+        assert!(matches!(err, JWTValidationError::TokenInvalidated));
+        let err = service
+            .refresh_token(&token_pair_a.refresh, 60, 120, Some(0))
+            .unwrap_err();
+        assert!(matches!(err, JWTRefreshError::TokenInvalided));
+        service.verify_token(&token_pair_b.token, Some(0)).unwrap();
+        assert_eq!(service.sessions(user_id).unwrap().len(), 1);
+    }
+
     #[actix_rt::test]
     async fn test_user_auth_token_expiry() {
         let bucket = RwLock::new(TokenBucket::new());
@@ -2,10 +2,13 @@ use std::collections::{hash_map::Entry, HashMap};
 
 use chrono::Utc;
 
+use repository::{AuthTokenRow, AuthTokenRowRepository, RepositoryError, StorageConnectionManager};
 use util::hash::sha256;
 
 struct TokenInfo {
     token_hash: String,
+    session_id: String,
+    issued_date: usize,
     expiry_date: usize,
 }
 
@@ -13,35 +16,68 @@ fn token_hash(token: &str) -> String {
     sha256(token)
 }
 
+/// Summary of the tokens issued for a single login session
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionInfo {
+    pub session_id: String,
+    pub user_id: String,
+    /// Time the session has been created, i.e. the time of the login (unix timestamp [s])
+    pub created_date: usize,
+    /// Time the last token has been issued for this session (unix timestamp [s])
+    pub last_refreshed_date: usize,
+    /// Time the last token of the session expires (unix timestamp [s])
+    pub expiry_date: usize,
+}
+
 /// Tracks if a token is still valid
 ///
 /// There are two ways a token can expire prematurely:
 /// 1) User logs out and token is removed from the bucket
 /// 2) Token expiry time is reduce (server side), e.g. when an token has been renewed and the old
 /// token should expiry sooner.
+///
+/// If the bucket has been created with a storage connection all changes are also written to the
+/// DB so that the bucket can be restored after a server restart.
 pub struct TokenBucket {
     users: HashMap<String, Vec<TokenInfo>>,
+    storage: Option<StorageConnectionManager>,
 }
 
 impl TokenBucket {
+    /// Creates a bucket that only lives in memory
     pub fn new() -> Self {
         TokenBucket {
             users: HashMap::new(),
+            storage: None,
+        }
+    }
+
+    /// Creates a bucket that is backed by the DB and loads all still valid tokens from the DB
+    pub fn load(connection_manager: StorageConnectionManager) -> Result<Self, RepositoryError> {
+        let now = Utc::now().timestamp();
+        let connection = connection_manager.connection()?;
+        let repo = AuthTokenRowRepository::new(&connection);
+        repo.delete_expired(now)?;
+
+        let mut users: HashMap<String, Vec<TokenInfo>> = HashMap::new();
+        for row in repo.find_valid(now)? {
+            users.entry(row.user_id).or_default().push(TokenInfo {
+                token_hash: row.token_hash,
+                session_id: row.session_id,
+                issued_date: row.issued_date as usize,
+                expiry_date: row.expiry_date as usize,
+            });
         }
+
+        Ok(TokenBucket {
+            users,
+            storage: Some(connection_manager),
+        })
     }
 
     /// Checks if the token is known for the given user
     pub fn contains(&self, user_id: &str, token: &str) -> bool {
-        let user_tokens = match self.users.get(user_id) {
-            Some(value) => value,
-            None => return false,
-        };
-
-        let token_hash = token_hash(token);
-        let existing_token = match user_tokens
-            .iter()
-            .find(|item| item.token_hash == token_hash)
-        {
+        let existing_token = match self.find(user_id, token) {
             Some(value) => value,
             None => return false,
         };
@@ -51,41 +87,203 @@ impl TokenBucket {
         existing_token.expiry_date >= now
     }
 
+    /// Returns the session id of a known token
+    pub fn session_id(&self, user_id: &str, token: &str) -> Option<String> {
+        self.find(user_id, token)
+            .map(|token_info| token_info.session_id.clone())
+    }
+
     /// Adds a token for a given user.
     /// If token is already known the expiry_date is updated.
     /// This can be used to reduce the expiry date of a token on the server, e.g. to reduce the
     /// token expiry time of a token that just has been refreshed.
-    pub fn put(&mut self, user_id: &str, token: &str, expiry_date: usize) {
+    pub fn put(
+        &mut self,
+        user_id: &str,
+        session_id: &str,
+        token: &str,
+        expiry_date: usize,
+    ) -> Result<(), RepositoryError> {
         let now = Utc::now().timestamp() as usize;
         if expiry_date < now {
-            return;
+            return Ok(());
+        }
+        let token_hash = token_hash(token);
+        // expired tokens are cleaned up below, i.e. only a still valid token is updated
+        let existing_token = self.users.get(user_id).and_then(|user_tokens| {
+            user_tokens
+                .iter()
+                .find(|item| item.token_hash == token_hash && item.expiry_date > now)
+        });
+        let token_info = match existing_token {
+            Some(existing) => TokenInfo {
+                token_hash: token_hash.clone(),
+                session_id: existing.session_id.clone(),
+                issued_date: existing.issued_date,
+                expiry_date,
+            },
+            None => TokenInfo {
+                token_hash: token_hash.clone(),
+                session_id: session_id.to_string(),
+                issued_date: now,
+                expiry_date,
+            },
+        };
+
+        // Write to the DB first so that memory and DB don't get out of sync if the write fails
+        if let Some(storage) = &self.storage {
+            let connection = storage.connection()?;
+            let repo = AuthTokenRowRepository::new(&connection);
+            repo.upsert_one(&AuthTokenRow {
+                token_hash: token_info.token_hash.clone(),
+                session_id: token_info.session_id.clone(),
+                user_id: user_id.to_string(),
+                issued_date: token_info.issued_date as i64,
+                expiry_date: token_info.expiry_date as i64,
+            })?;
+            repo.delete_expired(now as i64)?;
         }
+
         let user_tokens = match self.users.entry(user_id.to_string()) {
             Entry::Occupied(o) => o.into_mut(),
             Entry::Vacant(v) => v.insert(Vec::new()),
         };
-
         // clean up expired tokens
         user_tokens.retain(|item| item.expiry_date > now);
 
         // update existing or add new token
-        let token_hash = token_hash(token);
-        let existing_token = user_tokens
+        match user_tokens
             .iter_mut()
-            .find(|item| item.token_hash == token_hash);
-        match existing_token {
-            Some(existing) => existing.expiry_date = expiry_date,
-            None => {
-                user_tokens.push(TokenInfo {
-                    token_hash,
-                    expiry_date,
-                });
+            .find(|item| item.token_hash == token_hash)
+        {
+            Some(existing) => *existing = token_info,
+            None => user_tokens.push(token_info),
+        };
+
+        Ok(())
+    }
+
+    /// Returns the still valid sessions of a given user
+    pub fn sessions(&self, user_id: &str) -> Vec<SessionInfo> {
+        let now = Utc::now().timestamp() as usize;
+        let user_tokens = match self.users.get(user_id) {
+            Some(value) => value,
+            None => return Vec::new(),
+        };
+
+        let mut sessions: Vec<SessionInfo> = Vec::new();
+        for token in user_tokens.iter().filter(|item| item.expiry_date >= now) {
+            match sessions
+                .iter_mut()
+                .find(|session| session.session_id == token.session_id)
+            {
+                Some(session) => {
+                    session.created_date = session.created_date.min(token.issued_date);
+                    session.last_refreshed_date =
+                        session.last_refreshed_date.max(token.issued_date);
+                    session.expiry_date = session.expiry_date.max(token.expiry_date);
+                }
+                None => sessions.push(SessionInfo {
+                    session_id: token.session_id.clone(),
+                    user_id: user_id.to_string(),
+                    created_date: token.issued_date,
+                    last_refreshed_date: token.issued_date,
+                    expiry_date: token.expiry_date,
+                }),
             }
         }
+        sessions.sort_by(|a, b| b.last_refreshed_date.cmp(&a.last_refreshed_date));
+        sessions
+    }
+
+    /// Removes all tokens of a single session of a given user.
+    /// Returns false if the session is not known for the user.
+    pub fn remove_session(
+        &mut self,
+        user_id: &str,
+        session_id: &str,
+    ) -> Result<bool, RepositoryError> {
+        let is_known_session = match self.users.get(user_id) {
+            Some(user_tokens) => user_tokens.iter().any(|item| item.session_id == session_id),
+            None => false,
+        };
+        if !is_known_session {
+            return Ok(false);
+        }
+
+        if let Some(storage) = &self.storage {
+            let connection = storage.connection()?;
+            AuthTokenRowRepository::new(&connection).delete_by_session_id(session_id)?;
+        }
+
+        if let Some(user_tokens) = self.users.get_mut(user_id) {
+            user_tokens.retain(|item| item.session_id != session_id);
+        }
+        Ok(true)
     }
 
     /// Removes all known tokens for a given user
-    pub fn clear(&mut self, user_id: &str) {
+    pub fn clear(&mut self, user_id: &str) -> Result<(), RepositoryError> {
+        if let Some(storage) = &self.storage {
+            let connection = storage.connection()?;
+            AuthTokenRowRepository::new(&connection).delete_by_user_id(user_id)?;
+        }
+
         self.users.remove(user_id);
+        Ok(())
+    }
+
+    fn find(&self, user_id: &str, token: &str) -> Option<&TokenInfo> {
+        let user_tokens = self.users.get(user_id)?;
+        let token_hash = token_hash(token);
+        user_tokens
+            .iter()
+            .find(|item| item.token_hash == token_hash)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+    use repository::{
+        mock::{mock_user_account_a, MockDataInserts},
+        test_db::setup_all,
+    };
+
+    use super::TokenBucket;
+
+    #[actix_rt::test]
+    async fn token_bucket_persistence() {
+        let (_, _, connection_manager, _) = setup_all(
+            "token_bucket_persistence",
+            MockDataInserts::none().user_accounts(),
+        )
+        .await;
+        let user_id = &mock_user_account_a().id;
+        let expiry_date = Utc::now().timestamp() as usize + 60;
+
+        let mut bucket = TokenBucket::load(connection_manager.clone()).unwrap();
+        bucket
+            .put(user_id, "session_a", "token_a", expiry_date)
+            .unwrap();
+        bucket
+            .put(user_id, "session_b", "token_b", expiry_date)
+            .unwrap();
+
+        // tokens survive a restart
+        let mut bucket = TokenBucket::load(connection_manager.clone()).unwrap();
+        assert!(bucket.contains(user_id, "token_a"));
+        assert!(bucket.contains(user_id, "token_b"));
+        assert_eq!(bucket.sessions(user_id).len(), 2);
+
+        // revoked sessions stay revoked after a restart
+        assert!(bucket.remove_session(user_id, "session_a").unwrap());
+        let mut bucket = TokenBucket::load(connection_manager.clone()).unwrap();
+        assert!(!bucket.contains(user_id, "token_a"));
+        assert!(bucket.contains(user_id, "token_b"));
+
+        bucket.clear(user_id).unwrap();
+        let bucket = TokenBucket::load(connection_manager).unwrap();
+        assert!(!bucket.contains(user_id, "token_b"));
     }
 }
@@ -134,6 +134,8 @@ impl<'a> UserAccountService<'a> {
                     username: user.username,
                     hashed_password: hashed_password,
                     email: user.email,
+                    is_local: true,
+                    is_disabled: false,
                 };
                 repo.insert_one(&row)?;
                 Ok(row)